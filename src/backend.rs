@@ -10,16 +10,29 @@ use crate::config::{MangaTuiConfig, CONFIG};
 use crate::logger::ILogger;
 
 pub mod api_responses;
+pub mod bug_report;
+pub mod cache;
+pub mod chapter_notifier;
 pub mod database;
+pub mod database_actor;
 pub mod download;
 pub mod error_log;
 pub mod fetch;
 pub mod filter;
+pub mod media_server;
 pub mod migration;
+pub mod myanimelist_list;
+pub mod provider_error;
+pub mod rate_limit;
+pub mod reading_report;
 pub mod release_notifier;
+pub mod request_log;
 pub mod secrets;
+pub mod startup_timings;
+pub mod tachiyomi;
 pub mod tracker;
 pub mod tui;
+pub mod watch_folder;
 
 #[derive(Display, EnumIter)]
 pub enum AppDirectories {
@@ -31,6 +44,8 @@ pub enum AppDirectories {
     History,
     #[strum(to_string = "config")]
     Config,
+    #[strum(to_string = "cache")]
+    Cache,
 }
 
 static ERROR_LOGS_FILE: &str = "manga-tui-error-logs.txt";
@@ -68,7 +83,7 @@ impl AppDirectories {
             Self::Config => PathBuf::from(base_directory).join(CONFIG_FILE),
             Self::History => PathBuf::from(base_directory).join(DATABASE_FILE),
             Self::ErrorLogs => PathBuf::from(base_directory).join(ERROR_LOGS_FILE),
-            Self::MangaDownloads => PathBuf::from(base_directory),
+            Self::MangaDownloads | Self::Cache => PathBuf::from(base_directory),
         }
     }
 }
@@ -165,7 +180,7 @@ mod test {
             amount_directories += 1;
         }
 
-        assert_eq!(4, amount_directories);
+        assert_eq!(5, amount_directories);
 
         let error_logs_path = dbg!(AppDirectories::ErrorLogs.get_full_path());
 
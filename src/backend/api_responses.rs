@@ -16,6 +16,14 @@ pub struct SearchMangaResponse {
     pub total: u32,
 }
 
+impl SearchMangaResponse {
+    /// `total` is kept as it is, it's the amount of mangas mangadex found
+    pub fn without_mangas(mut self, manga_ids: &[String]) -> Self {
+        self.data.retain(|manga| !manga_ids.contains(&manga.id));
+        self
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Data {
@@ -28,6 +36,9 @@ pub struct Data {
 #[serde(rename_all = "camelCase")]
 pub struct Attributes {
     pub title: Title,
+    /// Each one is the title in a single language, like `{ "en": "..." }`
+    #[serde(default)]
+    pub alt_titles: Vec<HashMap<String, String>>,
     pub description: Option<Description>,
     pub status: String,
     pub tags: Vec<Tag>,
@@ -66,6 +77,8 @@ pub struct MangaSearchRelationship {
     #[serde(rename = "type")]
     pub type_field: String,
     pub attributes: Option<MangaSearchAttributes>,
+    /// Only present on relationships of type `manga`, for example "sequel" or "side_story"
+    pub related: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -145,7 +158,12 @@ pub struct Relationship {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ChapterRelationshipAttribute {
+    /// Set for scanlation groups
+    #[serde(default)]
     pub name: String,
+    /// Set for mangas, which are only included in the chapters of the latest updates
+    #[serde(default)]
+    pub title: Option<Title>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -162,6 +180,11 @@ impl ChapterPagesResponse {
         format!("{}/{}/{}", self.base_url, quality.as_param(), self.chapter.hash)
     }
 
+    /// Download the pages from a different host than the one mangadex assigned
+    pub fn set_base_url(&mut self, base_url: &Url) {
+        self.base_url = base_url.as_str().trim_end_matches('/').to_string();
+    }
+
     /// Based on the mangadex api the `data_saver` array is used when image quality is low and
     /// `data` is used when ImageQuality is high
     pub fn get_files_based_on_quality(self, quality: ImageQuality) -> Vec<String> {
@@ -346,6 +369,64 @@ pub mod authors {
     }
 }
 
+pub mod custom_list {
+    use serde::{Deserialize, Serialize};
+
+    /// A list of mangas made by a user, mangadex uses them for the staff picks and seasonal mangas
+    #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CustomListResponse {
+        pub result: String,
+        pub response: String,
+        pub data: Data,
+    }
+
+    #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Data {
+        pub id: String,
+        pub attributes: Attributes,
+        pub relationships: Vec<Relationship>,
+    }
+
+    #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Attributes {
+        pub name: String,
+    }
+
+    #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Relationship {
+        pub id: String,
+        #[serde(rename = "type")]
+        pub type_field: String,
+    }
+
+    impl CustomListResponse {
+        pub fn manga_ids(&self) -> impl Iterator<Item = &str> {
+            self.data
+                .relationships
+                .iter()
+                .filter(|relationship| relationship.type_field == "manga")
+                .map(|relationship| relationship.id.as_str())
+        }
+    }
+}
+
+pub mod auth {
+    use serde::{Deserialize, Serialize};
+
+    /// Response of mangadex's auth server, unlike the api its fields are snake_case
+    #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct LoginResponse {
+        pub access_token: String,
+        /// Seconds until the access token expires
+        pub expires_in: u64,
+        pub refresh_token: String,
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OneChapterResponse {
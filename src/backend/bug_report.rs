@@ -0,0 +1,135 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use reqwest::Url;
+
+use super::error_log::{get_error_logs_path, write_to_error_log, ErrorType};
+
+pub static NEW_ISSUE_URL: &str = "https://github.com/josueBarretogit/manga-tui/issues/new";
+
+/// The issue url gets too long for browsers past this, so the error is cut
+pub static MAX_ERROR_LENGTH: usize = 1500;
+
+/// What comes after these is redacted, they are matched ignoring case
+static SECRET_MARKERS: [&str; 5] = ["bearer ", "token=", "password=", "client_secret=", "client_id="];
+
+fn redact_after(text: &str, marker: &str) -> String {
+    // ascii lowercase keeps the byte indices of `text`
+    let lowercase = text.to_ascii_lowercase();
+    let mut redacted = String::with_capacity(text.len());
+    let mut copied_up_to = 0;
+
+    for (index, _) in lowercase.match_indices(marker) {
+        let value_start = index + marker.len();
+        let value_end = text[value_start..]
+            .find(|c: char| c.is_whitespace() || "&,}\"'".contains(c))
+            .map_or(text.len(), |end| value_start + end);
+
+        redacted.push_str(&text[copied_up_to..value_start]);
+        if value_end > value_start {
+            redacted.push_str("[redacted]");
+        }
+        copied_up_to = value_end;
+    }
+
+    redacted.push_str(&text[copied_up_to..]);
+    redacted
+}
+
+/// Tokens and passwords are redacted and the home directory is replaced so the username is not published
+pub fn sanitize(error: &str, home_directory: Option<&Path>) -> String {
+    let mut sanitized = SECRET_MARKERS
+        .iter()
+        .fold(error.to_string(), |error, marker| redact_after(&error, marker));
+
+    if let Some(home) = home_directory.and_then(|home| home.to_str()).filter(|home| !home.is_empty()) {
+        sanitized = sanitized.replace(home, "~");
+    }
+
+    match sanitized.char_indices().nth(MAX_ERROR_LENGTH) {
+        Some((index, _)) => format!("{}...", &sanitized[..index]),
+        None => sanitized,
+    }
+}
+
+/// Entries of the error log are separated by an empty line
+pub fn last_logged_error(error_log: &str) -> Option<&str> {
+    error_log.split("\n \n").map(str::trim).filter(|entry| !entry.is_empty()).last()
+}
+
+pub fn bug_report_url(error: &str) -> Url {
+    let body = format!(
+        "**Describe what you were doing when the error happened**\n\n\n\
+         **Context**\n- manga-tui version: {}\n- provider: Mangadex\n- os: {}\n\n\
+         **Error**\n```\n{error}\n```",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+    );
+
+    Url::parse_with_params(NEW_ISSUE_URL, [("title", "[Bug] "), ("labels", "bug"), ("body", &body)]).unwrap()
+}
+
+/// Opens the browser to file an issue with the last error written to the error log
+pub fn report_last_error() -> Result<(), Box<dyn Error>> {
+    let error_log = fs::read_to_string(get_error_logs_path())?;
+    let error = last_logged_error(&error_log).unwrap_or("no error was logged");
+
+    let home_directory = directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+
+    open::that(bug_report_url(&sanitize(error, home_directory.as_deref())).as_str())?;
+
+    Ok(())
+}
+
+/// Used by the pages when the user asks to report the error they just got
+pub fn report_bug() {
+    if let Err(e) = report_last_error() {
+        write_to_error_log(ErrorType::Error(e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_removes_secrets_and_the_home_directory_from_the_error() {
+        let error = "could not save /home/someone/manga-tui with Bearer abc.def to /manga?access_token=123&page=1";
+
+        assert_eq!(
+            "could not save ~/manga-tui with Bearer [redacted] to /manga?access_token=[redacted]&page=1",
+            sanitize(error, Some(Path::new("/home/someone")))
+        );
+
+        let long_error = "a".repeat(MAX_ERROR_LENGTH + 10);
+
+        assert_eq!(MAX_ERROR_LENGTH + 3, sanitize(&long_error, None).len());
+    }
+
+    #[test]
+    fn it_takes_the_most_recent_error_of_the_log() {
+        let error_log = "2024-01-01 | first error \n \n2024-01-02 | second error \n \n";
+
+        assert_eq!(Some("2024-01-02 | second error"), last_logged_error(error_log));
+        assert_eq!(None, last_logged_error(""));
+    }
+
+    #[test]
+    fn the_issue_is_prefilled_with_the_version_and_the_error() {
+        let url = bug_report_url("error decoding response body");
+
+        let body = url
+            .query_pairs()
+            .find(|(key, _)| key == "body")
+            .map(|(_, body)| body.to_string())
+            .unwrap();
+
+        assert!(url.as_str().starts_with(NEW_ISSUE_URL));
+        assert!(body.contains(env!("CARGO_PKG_VERSION")));
+        assert!(body.contains("provider: Mangadex"));
+        assert!(body.contains("error decoding response body"));
+    }
+}
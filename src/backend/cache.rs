@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use super::api_responses::tags::TagsResponse;
+
+/// How many chapters to keep the pages of, enough to go back and forth between a few chapters
+pub static CHAPTER_PAGES_CACHE_CAPACITY: usize = 10;
+
+/// Mangadex's at-home urls are only valid for a limited time, after that they have to be requested again
+pub static CHAPTER_PAGES_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug)]
+struct CachedChapterPages {
+    chapter_id: String,
+    pages_url: Vec<Url>,
+    cached_at: Instant,
+}
+
+/// Keeps the page urls of the most recently read chapters so reopening one of them doesn't request them again
+#[derive(Debug)]
+pub struct ChapterPagesCache {
+    capacity: usize,
+    time_to_live: Duration,
+    entries: VecDeque<CachedChapterPages>,
+}
+
+impl Default for ChapterPagesCache {
+    fn default() -> Self {
+        Self::new(CHAPTER_PAGES_CACHE_CAPACITY, CHAPTER_PAGES_CACHE_TTL)
+    }
+}
+
+impl ChapterPagesCache {
+    pub fn new(capacity: usize, time_to_live: Duration) -> Self {
+        Self {
+            capacity,
+            time_to_live,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn get(&mut self, chapter_id: &str) -> Option<Vec<Url>> {
+        self.entries.retain(|entry| entry.cached_at.elapsed() < self.time_to_live);
+
+        let index = self.entries.iter().position(|entry| entry.chapter_id == chapter_id)?;
+
+        // move it to the back so the chapters being read are the last ones to be dropped
+        let entry = self.entries.remove(index)?;
+        let pages_url = entry.pages_url.clone();
+        self.entries.push_back(entry);
+
+        Some(pages_url)
+    }
+
+    pub fn insert(&mut self, chapter_id: &str, pages_url: Vec<Url>) {
+        self.entries.retain(|entry| entry.chapter_id != chapter_id);
+
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(CachedChapterPages {
+            chapter_id: chapter_id.to_string(),
+            pages_url,
+            cached_at: Instant::now(),
+        });
+    }
+}
+
+/// Mangadex rarely adds new tags, so there is no need to request them every time the filter widget is built
+pub static TAGS_CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+pub static TAGS_CACHE_FILE: &str = "mangadex-tags.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedTagsFile {
+    /// Unix timestamp in seconds
+    cached_at: i64,
+    tags: TagsResponse,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedTags {
+    pub tags: TagsResponse,
+    /// The tags can still be used, but they should be requested again
+    pub is_expired: bool,
+}
+
+/// Keeps mangadex's tags in a file inside the data directory
+#[derive(Debug, Clone)]
+pub struct TagsCache {
+    path: PathBuf,
+    time_to_live: Duration,
+}
+
+impl TagsCache {
+    pub fn new(path: PathBuf, time_to_live: Duration) -> Self {
+        Self { path, time_to_live }
+    }
+
+    /// `None` if the tags were never cached or the file could not be read
+    pub fn get(&self) -> Option<CachedTags> {
+        let contents = fs::read_to_string(&self.path).ok()?;
+        let cached: CachedTagsFile = serde_json::from_str(&contents).ok()?;
+
+        let elapsed = Utc::now().timestamp().saturating_sub(cached.cached_at);
+
+        Some(CachedTags {
+            tags: cached.tags,
+            is_expired: elapsed.unsigned_abs() >= self.time_to_live.as_secs(),
+        })
+    }
+
+    pub fn save(&self, tags: &TagsResponse) -> Result<(), Box<dyn Error>> {
+        let cached = CachedTagsFile {
+            cached_at: Utc::now().timestamp(),
+            tags: tags.clone(),
+        };
+
+        fs::write(&self.path, serde_json::to_string(&cached)?)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread::sleep;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn pages_url(chapter_id: &str) -> Vec<Url> {
+        vec![format!("http://localhost/{chapter_id}/1.png").parse().unwrap()]
+    }
+
+    #[test]
+    fn it_returns_pages_of_chapter_cached() {
+        let mut cache = ChapterPagesCache::default();
+
+        cache.insert("chapter_1", pages_url("chapter_1"));
+
+        assert_eq!(Some(pages_url("chapter_1")), cache.get("chapter_1"));
+        assert!(cache.get("chapter_2").is_none());
+    }
+
+    #[test]
+    fn it_drops_least_recently_used_chapter_when_full() {
+        let mut cache = ChapterPagesCache::new(2, CHAPTER_PAGES_CACHE_TTL);
+
+        cache.insert("chapter_1", pages_url("chapter_1"));
+        cache.insert("chapter_2", pages_url("chapter_2"));
+
+        cache.get("chapter_1");
+
+        cache.insert("chapter_3", pages_url("chapter_3"));
+
+        assert!(cache.get("chapter_2").is_none());
+        assert!(cache.get("chapter_1").is_some());
+        assert!(cache.get("chapter_3").is_some());
+    }
+
+    #[test]
+    fn it_does_not_return_expired_chapters() {
+        let mut cache = ChapterPagesCache::new(2, Duration::from_millis(10));
+
+        cache.insert("chapter_1", pages_url("chapter_1"));
+
+        sleep(Duration::from_millis(20));
+
+        assert!(cache.get("chapter_1").is_none());
+    }
+
+    fn tags_cache_path(file_name: &str) -> PathBuf {
+        let directory = PathBuf::from("./test_results/tags-cache");
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join(file_name);
+        fs::remove_file(&path).ok();
+        path
+    }
+
+    #[test]
+    fn it_returns_tags_cached_in_file() {
+        let cache = TagsCache::new(tags_cache_path("cached.json"), TAGS_CACHE_TTL);
+
+        assert!(cache.get().is_none());
+
+        let tags = TagsResponse {
+            result: "ok".to_string(),
+            ..Default::default()
+        };
+
+        cache.save(&tags).unwrap();
+
+        assert_eq!(
+            Some(CachedTags {
+                tags,
+                is_expired: false
+            }),
+            cache.get()
+        );
+    }
+
+    #[test]
+    fn it_flags_tags_as_expired_after_time_to_live() {
+        let cache = TagsCache::new(tags_cache_path("expired.json"), Duration::ZERO);
+
+        cache.save(&TagsResponse::default()).unwrap();
+
+        assert!(cache.get().is_some_and(|cached| cached.is_expired));
+    }
+}
@@ -0,0 +1,369 @@
+use std::error::Error;
+use std::time::Duration;
+
+use http::StatusCode;
+use reqwest::Client;
+use serde_json::json;
+
+use crate::backend::api_responses::{ChapterData, ChapterResponse, SearchMangaResponse};
+use crate::backend::database::Database;
+use crate::backend::fetch::ApiClient;
+use crate::backend::filter::{Author, Filters, Languages, SortBy};
+use crate::config::{NotificationService, NotificationSettings};
+use crate::global::USER_AGENT;
+use crate::logger::ILogger;
+use crate::utils::from_manga_response;
+use crate::view::tasks::manga::describe_chapter_to_download;
+
+/// Pushes "new chapter" notifications to a ntfy topic or a gotify server
+#[derive(Debug)]
+pub struct ChapterNotifier {
+    settings: NotificationSettings,
+    client: Client,
+}
+
+impl ChapterNotifier {
+    pub fn new(settings: NotificationSettings) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .user_agent(&*USER_AGENT)
+            .build()
+            .unwrap();
+
+        Self { settings, client }
+    }
+
+    pub async fn notify(&self, title: &str, message: &str) -> Result<(), Box<dyn Error>> {
+        let url = self.settings.url.trim_end_matches('/');
+
+        let request = match self.settings.service {
+            NotificationService::Ntfy => {
+                let request = self
+                    .client
+                    .post(url)
+                    .header("Title", title)
+                    .header("Tags", "books")
+                    .body(message.to_string());
+                match self.settings.token.as_deref() {
+                    Some(token) => request.bearer_auth(token),
+                    None => request,
+                }
+            },
+            NotificationService::Gotify => {
+                let token = self
+                    .settings
+                    .token
+                    .as_deref()
+                    .ok_or("gotify needs the token of an application to push notifications")?;
+                self.client
+                    .post(format!("{url}/message"))
+                    .header("X-Gotify-Key", token)
+                    .json(&json!({ "title" : title, "message" : message, "priority" : 5 }))
+            },
+        };
+
+        let response = request.send().await?;
+
+        if response.status() != StatusCode::OK {
+            return Err(format!(
+                "{} did not accept the notification, it answered with status {}",
+                self.settings.service,
+                response.status()
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Chapters are only new if they are in the preferred language and no notification was sent for them yet
+fn new_chapters(chapters: Vec<ChapterData>, language: Languages, already_notified: &[String]) -> Vec<ChapterData> {
+    chapters
+        .into_iter()
+        .filter(|chapter| {
+            chapter.attributes.translated_language == language.as_iso_code() && !already_notified.contains(&chapter.id)
+        })
+        .collect()
+}
+
+/// Looks for new chapters of every manga in the library and pushes a notification for each one, the first time a manga is
+/// checked its latest chapters are only remembered so the user is not flooded with notifications. Returns how many
+/// notifications were pushed
+pub async fn check_new_chapters(
+    api_client: &impl ApiClient,
+    database: &Database<'_>,
+    notifier: &ChapterNotifier,
+    language: Languages,
+    logger: &impl ILogger,
+) -> Result<u32, Box<dyn Error>> {
+    let mut notifications_pushed = 0;
+
+    for (manga_id, manga_title) in database.get_mangas_to_check_for_new_chapters()? {
+        let response: ChapterResponse = match api_client.get_latest_chapters(&manga_id).await {
+            Ok(response) => response.json().await?,
+            Err(e) => {
+                logger.warn(format!("Could not check the chapters of {manga_title}, more details : {e}"));
+                continue;
+            },
+        };
+
+        let already_notified = database.get_notified_chapters(&manga_id)?;
+        let is_first_check = already_notified.is_none();
+
+        let chapters = new_chapters(response.data, language, &already_notified.unwrap_or_default());
+
+        for chapter in &chapters {
+            if is_first_check {
+                continue;
+            }
+
+            notifier
+                .notify(&format!("New chapter of {manga_title}"), &describe_chapter_to_download(chapter))
+                .await?;
+
+            notifications_pushed += 1;
+        }
+
+        let ids: Vec<String> = chapters.into_iter().map(|chapter| chapter.id).collect();
+        database.save_notified_chapters(&manga_id, &ids)?;
+    }
+
+    Ok(notifications_pushed)
+}
+
+/// Looks for mangas published by the authors the user follows and pushes a notification for each one that was not
+/// found before, like with chapters the first check only remembers the mangas the author already had. Returns how many
+/// notifications were pushed
+pub async fn check_new_mangas_of_followed_authors(
+    api_client: &impl ApiClient,
+    database: &Database<'_>,
+    notifier: &ChapterNotifier,
+    logger: &impl ILogger,
+) -> Result<u32, Box<dyn Error>> {
+    let mut notifications_pushed = 0;
+
+    for (author_id, author_name) in database.get_followed_authors()? {
+        let mut filters = Filters::default();
+        filters.set_authors(vec![Author::new(author_id.clone())]);
+        filters.sort_by = SortBy::RecentlyAdded;
+
+        let response: SearchMangaResponse = match api_client.search_mangas(None, 1, filters).await {
+            Ok(response) => response.json().await?,
+            Err(e) => {
+                logger.warn(format!("Could not check the mangas of {author_name}, more details : {e}"));
+                continue;
+            },
+        };
+
+        let already_known = database.get_known_mangas_of_author(&author_id)?;
+        let is_first_check = already_known.is_none();
+        let already_known = already_known.unwrap_or_default();
+
+        let new_mangas: Vec<_> = response
+            .data
+            .into_iter()
+            .map(from_manga_response)
+            .filter(|manga| !already_known.contains(&manga.id))
+            .collect();
+
+        for manga in &new_mangas {
+            if is_first_check {
+                continue;
+            }
+
+            notifier.notify(&format!("New series by {author_name}"), &manga.title).await?;
+
+            notifications_pushed += 1;
+        }
+
+        let ids: Vec<String> = new_mangas.into_iter().map(|manga| manga.id).collect();
+        database.save_known_mangas_of_author(&author_id, &ids)?;
+    }
+
+    Ok(notifications_pushed)
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::Method::POST;
+    use httpmock::MockServer;
+    use pretty_assertions::assert_eq;
+    use rusqlite::{params, Connection};
+
+    use super::*;
+    use crate::backend::api_responses::{Attributes, ChapterAttribute, Data, Title};
+    use crate::backend::fetch::fake_api_client::MockMangadexClient;
+    use crate::logger::DefaultLogger;
+
+    fn chapter(id: &str, language: &str) -> ChapterData {
+        ChapterData {
+            id: id.to_string(),
+            attributes: ChapterAttribute {
+                chapter: Some(id.to_string()),
+                translated_language: language.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn it_pushes_notifications_to_ntfy_and_gotify() -> Result<(), Box<dyn Error>> {
+        let server = MockServer::start_async().await;
+
+        let ntfy_request = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/my-topic")
+                    .header("Title", "New chapter of some manga")
+                    .header("Authorization", "Bearer secret")
+                    .body("Ch. 2");
+                then.status(200);
+            })
+            .await;
+
+        let gotify_request = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/message")
+                    .header("X-Gotify-Key", "app-token")
+                    .json_body(json!({ "title" : "New chapter of some manga", "message" : "Ch. 2", "priority" : 5 }));
+                then.status(200);
+            })
+            .await;
+
+        let ntfy = ChapterNotifier::new(NotificationSettings {
+            service: NotificationService::Ntfy,
+            url: server.url("/my-topic"),
+            token: Some("secret".to_string()),
+        });
+
+        ntfy.notify("New chapter of some manga", "Ch. 2").await?;
+
+        let gotify = ChapterNotifier::new(NotificationSettings {
+            service: NotificationService::Gotify,
+            url: server.base_url(),
+            token: Some("app-token".to_string()),
+        });
+
+        gotify.notify("New chapter of some manga", "Ch. 2").await?;
+
+        ntfy_request.assert_async().await;
+        gotify_request.assert_async().await;
+
+        let gotify_without_token = ChapterNotifier::new(NotificationSettings {
+            service: NotificationService::Gotify,
+            url: server.base_url(),
+            token: None,
+        });
+
+        assert!(gotify_without_token.notify("title", "message").await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_only_notifies_chapters_released_after_the_first_check() -> Result<(), Box<dyn Error>> {
+        let server = MockServer::start_async().await;
+
+        let notification = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/my-topic").body("Ch. 3");
+                then.status(200);
+            })
+            .await;
+
+        let notifier = ChapterNotifier::new(NotificationSettings {
+            service: NotificationService::Ntfy,
+            url: server.url("/my-topic"),
+            token: None,
+        });
+
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+        database.setup()?;
+
+        connection.execute("INSERT INTO mangas(id, title) VALUES(?1, ?2)", params!["manga", "some manga"])?;
+
+        let latest_chapters = |chapters: Vec<ChapterData>| {
+            MockMangadexClient::new().with_chapter_response(ChapterResponse {
+                data: chapters,
+                ..Default::default()
+            })
+        };
+
+        let first_check = latest_chapters(vec![chapter("1", "en"), chapter("2", "en")]);
+
+        assert_eq!(0, check_new_chapters(&first_check, &database, &notifier, Languages::English, &DefaultLogger).await?);
+
+        let second_check =
+            latest_chapters(vec![chapter("1", "en"), chapter("2", "en"), chapter("3", "en"), chapter("3-es", "es")]);
+
+        assert_eq!(1, check_new_chapters(&second_check, &database, &notifier, Languages::English, &DefaultLogger).await?);
+        assert_eq!(0, check_new_chapters(&second_check, &database, &notifier, Languages::English, &DefaultLogger).await?);
+
+        notification.assert_hits_async(1).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_notifies_mangas_of_followed_authors_found_after_the_first_check() -> Result<(), Box<dyn Error>> {
+        let server = MockServer::start_async().await;
+
+        let notification = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/my-topic")
+                    .header("Title", "New series by Some author")
+                    .body("Brand new series");
+                then.status(200);
+            })
+            .await;
+
+        let notifier = ChapterNotifier::new(NotificationSettings {
+            service: NotificationService::Ntfy,
+            url: server.url("/my-topic"),
+            token: None,
+        });
+
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+        database.setup()?;
+        database.toggle_followed_author("author", "Some author")?;
+
+        let mangas_of_author = |titles: &[&str]| {
+            MockMangadexClient::new().with_search_response(SearchMangaResponse {
+                data: titles
+                    .iter()
+                    .map(|title| Data {
+                        id: title.to_string(),
+                        attributes: Attributes {
+                            title: Title {
+                                en: Some(title.to_string()),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            })
+        };
+
+        let first_check = mangas_of_author(&["Old series"]);
+
+        assert_eq!(0, check_new_mangas_of_followed_authors(&first_check, &database, &notifier, &DefaultLogger).await?);
+
+        let second_check = mangas_of_author(&["Brand new series", "Old series"]);
+
+        assert_eq!(1, check_new_mangas_of_followed_authors(&second_check, &database, &notifier, &DefaultLogger).await?);
+        assert_eq!(0, check_new_mangas_of_followed_authors(&second_check, &database, &notifier, &DefaultLogger).await?);
+
+        notification.assert_hits_async(1).await;
+
+        Ok(())
+    }
+}
@@ -1,27 +1,42 @@
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
-use chrono::Utc;
-use manga_tui::SearchTerm;
+use chrono::{NaiveDate, Utc};
+use manga_tui::{SearchTerm, TagName};
 use once_cell::sync::Lazy;
 use rusqlite::{params, Connection, OptionalExtension};
-use strum::{Display, EnumIter};
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
 
 use super::filter::Languages;
+use super::migration::migrate_up;
 use super::AppDirectories;
-use crate::view::widgets::feed::FeedTabs;
+use crate::config::DownloadType;
+use crate::logger::Logger;
 
-#[derive(Display, Debug, Clone, Copy)]
+#[derive(Display, EnumString, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MangaHistoryType {
     PlanToRead,
     ReadingHistory,
 }
 
-impl From<FeedTabs> for MangaHistoryType {
-    fn from(value: FeedTabs) -> Self {
-        match value {
-            FeedTabs::History => Self::ReadingHistory,
-            FeedTabs::PlantToRead => Self::PlanToRead,
-        }
+/// How the reader shows the pages of a manga, remembered for each manga
+#[derive(Display, EnumString, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReadingMode {
+    #[default]
+    #[strum(to_string = "paged")]
+    Paged,
+    /// The pages are stitched one after the other and scrolled, meant for long strip webtoons
+    #[strum(to_string = "webtoon")]
+    Webtoon,
+    /// Two pages are shown side by side, from right to left
+    #[strum(to_string = "double_page")]
+    DoublePage,
+}
+
+impl ReadingMode {
+    /// Switching to the mode already in use goes back to reading page by page
+    pub fn switch_to(self, mode: Self) -> Self {
+        if self == mode { Self::Paged } else { mode }
     }
 }
 
@@ -35,6 +50,44 @@ pub enum Table {
     Chapters,
     #[strum(to_string = "manga_history_union")]
     MangaHistoryUnion,
+    #[strum(to_string = "reading_session")]
+    ReadingSession,
+    #[strum(to_string = "dead_mangas")]
+    DeadMangas,
+    #[strum(to_string = "blocked_scanlation_groups")]
+    BlockedScanlationGroups,
+    #[strum(to_string = "manga_alt_titles")]
+    MangaAltTitles,
+    #[strum(to_string = "downloads")]
+    Downloads,
+    #[strum(to_string = "download_all_job")]
+    DownloadAllJob,
+    #[strum(to_string = "tracker_bindings")]
+    TrackerBindings,
+    #[strum(to_string = "tracker_queue")]
+    TrackerQueue,
+    #[strum(to_string = "collections")]
+    Collections,
+    #[strum(to_string = "manga_collection")]
+    MangaCollection,
+    #[strum(to_string = "tags")]
+    Tags,
+    #[strum(to_string = "manga_tags")]
+    MangaTags,
+    #[strum(to_string = "notified_chapters")]
+    NotifiedChapters,
+    #[strum(to_string = "followed_authors")]
+    FollowedAuthors,
+    #[strum(to_string = "author_known_mangas")]
+    AuthorKnownMangas,
+    #[strum(to_string = "chapter_progress")]
+    ChapterProgress,
+    #[strum(to_string = "manga_metadata")]
+    MangaMetadata,
+    #[strum(to_string = "not_interested_mangas")]
+    NotInterestedMangas,
+    #[strum(to_string = "manga_reading_mode")]
+    MangaReadingMode,
 }
 
 #[deprecated(since = "0.3.2", note = "Prefer to use `Database` struct instead")]
@@ -51,78 +104,7 @@ pub static DBCONN: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| {
 
     let conn = conn.unwrap();
 
-    conn.execute(
-        "CREATE TABLE if not exists app_version (
-                version TEXT PRIMARY KEY
-             )",
-        (),
-    )
-    .unwrap();
-
-    let already_has_data: i32 = conn.query_row("SELECT COUNT(*) from app_version", [], |row| row.get(0)).unwrap();
-
-    if already_has_data == 0 {
-        conn.execute("INSERT INTO app_version(version) VALUES (?1) ", [env!("CARGO_PKG_VERSION")])
-            .unwrap();
-    }
-
-    conn.execute(
-        "CREATE TABLE if not exists history_types (
-                id    INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL UNIQUE
-             )",
-        (),
-    )
-    .unwrap();
-
-    conn.execute(
-        "CREATE TABLE if not exists mangas (
-                id    TEXT  PRIMARY KEY,
-                title TEXT  NOT NULL,
-                created_at  DATETIME DEFAULT (datetime('now')),
-                updated_at  DATETIME DEFAULT (datetime('now')),
-                last_read  DATETIME DEFAULT (datetime('now')),
-                deleted_at  DATETIME NULL,
-                img_url TEXT NULL
-             )",
-        (),
-    )
-    .unwrap();
-
-    conn.execute(
-        "CREATE TABLE if not exists chapters (
-                id    TEXT  PRIMARY KEY,
-                title TEXT  NOT NULL,
-                manga_id TEXT  NOT NULL,
-                is_read BOOLEAN NOT NULL DEFAULT 0,
-                is_downloaded BOOLEAN NOT NULL DEFAULT 0,
-                FOREIGN KEY (manga_id) REFERENCES mangas (id)
-            )",
-        (),
-    )
-    .unwrap();
-
-    conn.execute(
-        "CREATE TABLE if not exists manga_history_union (
-                manga_id TEXT, 
-                type_id INTEGER, 
-                PRIMARY KEY (manga_id, type_id),
-                FOREIGN KEY (manga_id) REFERENCES mangas (id),
-                FOREIGN KEY (type_id) REFERENCES history_types (id)
-             )",
-        (),
-    )
-    .unwrap();
-
-    let already_has_data: i32 = conn.query_row("SELECT COUNT(*) from history_types", [], |row| row.get(0)).unwrap();
-
-    if already_has_data < 2 {
-        conn.execute("INSERT INTO history_types(name) VALUES (?1) ", [MangaHistoryType::ReadingHistory.to_string()])
-            .unwrap();
-
-        conn.execute("INSERT INTO history_types(name) VALUES (?1) ", [MangaHistoryType::PlanToRead.to_string()])
-            .unwrap();
-    }
+    Database::new(&conn).setup().unwrap();
 
     Mutex::new(Some(conn))
 });
@@ -224,14 +206,19 @@ pub struct MangaReadingHistorySave<'a> {
 }
 
 /// This function creates a manga in the database if it does not exists and saves it in the reading
-/// history section
+/// history section, all in one transaction
 pub fn save_history(data: MangaReadingHistorySave<'_>, conn: &Connection) -> rusqlite::Result<()> {
     let database = Database::new(conn);
 
+    // reading a manga that was moved to the trash takes it out of there, even if the chapter was already read
+    database.restore_from_trash(data.id)?;
+
     if database.check_chapter_is_already_reading(data.chapter.id)? {
         return Ok(());
     }
 
+    let transaction = conn.unchecked_transaction()?;
+
     database.create_manga_if_not_exists(MangaInsert {
         id: data.id,
         title: data.title,
@@ -256,14 +243,45 @@ pub fn save_history(data: MangaReadingHistorySave<'_>, conn: &Connection) -> rus
         conn.execute("UPDATE mangas SET last_read = ?1 WHERE id = ?2", params![now.to_string(), data.id])?;
     }
 
-    conn.execute("UPDATE chapters SET is_read = true WHERE id = ?1", params![data.chapter.id])?;
-    Ok(())
+    conn.execute("UPDATE chapters SET is_read = true, read_at = datetime('now') WHERE id = ?1", params![data.chapter.id])?;
+
+    transaction.commit()
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct MangaReadingHistoryRetrieve {
     pub id: String,
     pub is_downloaded: bool,
     pub is_read: bool,
+    /// `None` if the chapter was downloaded before the `downloads` table existed
+    pub download: Option<ChapterDownloaded>,
+    /// `None` if the chapter was never opened in the reader
+    pub progress: Option<ChapterProgress>,
+}
+
+/// The furthest page reached in a chapter, every chapter opened in the reader has its own
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ChapterProgress {
+    /// Index of the page, starting at 0 like `number_page_bookmarked`
+    pub last_page: u32,
+    pub total_pages: u32,
+}
+
+impl ChapterProgress {
+    pub fn is_finished(&self) -> bool {
+        self.last_page + 1 >= self.total_pages
+    }
+
+    /// The page the reader opens at, a finished chapter is read again from the start
+    pub fn page_to_resume(&self) -> Option<u32> {
+        if self.is_finished() { None } else { Some(self.last_page) }
+    }
+}
+
+impl std::fmt::Display for ChapterProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} pages", self.last_page + 1, self.total_pages)
+    }
 }
 
 // retrieve the `is_reading` and `is_downloaded` data for a chapter
@@ -271,13 +289,34 @@ pub fn get_chapters_history_status(manga_id: &str, conn: &Connection) -> rusqlit
     let mut chapter_ids: Vec<MangaReadingHistoryRetrieve> = vec![];
 
     let mut result = conn
-        .prepare("SELECT chapters.id, chapters.is_downloaded, chapters.is_read from chapters INNER JOIN mangas ON mangas.id = chapters.manga_id WHERE mangas.id = ?1")?;
+        .prepare("SELECT chapters.id, chapters.is_downloaded OR downloads.chapter_id IS NOT NULL, chapters.is_read, downloads.path, downloads.format, downloads.bytes, chapter_progress.last_page, chapter_progress.total_pages from chapters INNER JOIN mangas ON mangas.id = chapters.manga_id LEFT JOIN downloads ON downloads.chapter_id = chapters.id LEFT JOIN chapter_progress ON chapter_progress.chapter_id = chapters.id WHERE mangas.id = ?1")?;
 
     let result_iter = result.query_map(params![manga_id], |row| {
+        let path: Option<String> = row.get(3)?;
+        let format: Option<String> = row.get(4)?;
+
+        let download = path.zip(format).and_then(|(path, format)| {
+            DownloadType::iter()
+                .find(|download_type| download_type.to_string() == format)
+                .map(|format| ChapterDownloaded {
+                    path: PathBuf::from(path),
+                    format,
+                    bytes: row.get::<_, Option<u64>>(5).ok().flatten().unwrap_or_default(),
+                })
+        });
+
+        let last_page: Option<u32> = row.get(6)?;
+        let total_pages: Option<u32> = row.get(7)?;
+
         Ok(MangaReadingHistoryRetrieve {
             id: row.get(0)?,
             is_downloaded: row.get(1)?,
             is_read: row.get(2)?,
+            download,
+            progress: last_page.zip(total_pages).map(|(last_page, total_pages)| ChapterProgress {
+                last_page,
+                total_pages,
+            }),
         })
     })?;
 
@@ -293,6 +332,10 @@ pub struct MangaHistory {
     pub id: String,
     pub title: String,
     // img_url: Option<String>,
+    /// Only set when searching the reading history and plan to read list together
+    pub category: Option<MangaHistoryType>,
+    /// The 1-10 rating the user gave to the manga
+    pub personal_rating: Option<u8>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -304,88 +347,127 @@ pub struct MangaHistoryResponse {
 
 pub struct GetHistoryArgs<'a> {
     pub conn: &'a Connection,
-    pub hist_type: MangaHistoryType,
+    /// `None` searches the reading history and the plan to read list together, grouped by category
+    pub hist_type: Option<MangaHistoryType>,
     pub page: u32,
     pub search: Option<SearchTerm>,
+    /// Only the mangas with this tag are retrieved
+    pub tag: Option<TagName>,
     pub items_per_page: u32,
+    pub order: HistoryOrder,
+    /// Retrieve the mangas moved to the trash instead of the ones not deleted
+    pub in_trash: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryOrder {
+    #[default]
+    LastRead,
+    /// Highest rated first, the mangas without a rating go last
+    PersonalRating,
+}
+
+impl HistoryOrder {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::LastRead => Self::PersonalRating,
+            Self::PersonalRating => Self::LastRead,
+        }
+    }
+
+    fn as_sql(self) -> &'static str {
+        match self {
+            Self::LastRead => "mangas.last_read DESC",
+            Self::PersonalRating => "mangas.personal_rating IS NULL, mangas.personal_rating DESC, mangas.last_read DESC",
+        }
+    }
+}
+
+/// Sql condition for a manga whose title or one of its alternative titles contains the parameter `?{param}`, or whose
+/// description or genres match the full-text query `?{metadata_param}`, or any manga if they are `NULL`
+fn matches_search(param: u8, metadata_param: u8) -> String {
+    format!(
+        "(?{param} IS NULL OR LOWER(mangas.title) LIKE '%' || ?{param} || '%' OR EXISTS (
+            SELECT 1 FROM manga_alt_titles
+            WHERE manga_alt_titles.manga_id = mangas.id AND LOWER(manga_alt_titles.title) LIKE '%' || ?{param} || '%'
+         ) OR mangas.id IN (SELECT manga_id FROM manga_metadata WHERE manga_metadata MATCH ?{metadata_param}))"
+    )
 }
+
+/// Every word of the search has to be in the description or genres, each one is quoted so characters like `-` or `*`
+/// are not taken as fts5 operators and matches the words it is the beginning of, so "isekai" finds "isekai-ish"
+fn metadata_query(search: &str) -> String {
+    search
+        .split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Sql condition for a manga tagged with the parameter `?{param}`, or any manga if it is `NULL`
+fn has_tag(param: u8) -> String {
+    format!(
+        "(?{param} IS NULL OR EXISTS (
+            SELECT 1 FROM manga_tags
+            INNER JOIN tags ON tags.id = manga_tags.tag_id
+            WHERE manga_tags.manga_id = mangas.id AND tags.name = ?{param}
+         ))"
+    )
+}
+
 /// This is used in the `feed` page to retrieve the mangas the user is currently reading
 pub fn get_history(args: GetHistoryArgs<'_>) -> rusqlite::Result<MangaHistoryResponse> {
     let items_per_page = args.items_per_page;
     let offset = (args.page - 1) * items_per_page;
 
-    let history_type_id: i32 =
-        args.conn
-            .query_row("SELECT id from history_types WHERE name = ?1", params![args.hist_type.to_string()], |row| row.get(0))?;
+    let history_type_id: Option<i32> = match args.hist_type {
+        Some(hist_type) => Some(get_history_type(hist_type, args.conn)?),
+        None => None,
+    };
+
+    let search = args.search.as_ref().map(|term| term.get());
+    let metadata_search = search.map(metadata_query);
+    let tag = args.tag.as_ref().map(|tag| tag.get());
 
     let total_mangas: u32 = args.conn.query_row(
-        "
-                SELECT COUNT(*) from mangas
-                INNER JOIN manga_history_union ON mangas.id = manga_history_union.manga_id 
-                WHERE manga_history_union.type_id = ?1",
-        params![history_type_id],
+        &format!(
+            "SELECT COUNT(*) from mangas
+             INNER JOIN manga_history_union ON mangas.id = manga_history_union.manga_id
+             WHERE (?1 IS NULL OR manga_history_union.type_id = ?1) AND {} AND {}
+             AND (mangas.deleted_at IS NOT NULL) = ?4",
+            matches_search(2, 5),
+            has_tag(3)
+        ),
+        params![history_type_id, search, tag, args.in_trash, metadata_search],
         |row| row.get(0),
     )?;
 
-    let mut get_statement = args.conn.prepare(
-        "SELECT  mangas.id, mangas.title from mangas 
-                     INNER JOIN manga_history_union ON mangas.id = manga_history_union.manga_id 
-                     WHERE manga_history_union.type_id = ?1
-                     ORDER BY mangas.last_read DESC
-                     LIMIT ?2 OFFSET ?3",
-    )?;
-
-    let mut get_statement_with_search_term = args.conn.prepare(
-        "SELECT  mangas.id, mangas.title from mangas 
-                     INNER JOIN manga_history_union ON mangas.id = manga_history_union.manga_id 
-                     WHERE manga_history_union.type_id = ?1 AND LOWER(mangas.title) LIKE '%' || ?2 || '%'
-                     ORDER BY mangas.last_read DESC
-                     LIMIT ?3 OFFSET ?4",
-    )?;
-
-    let mut manga_history: Vec<MangaHistory> = vec![];
-
-    if let Some(search_term) = args.search {
-        let search_term = search_term.get();
-        let total_mangas_with_search: u32 = args.conn.query_row(
-            "
-                SELECT COUNT(*) from mangas
-                INNER JOIN manga_history_union ON mangas.id = manga_history_union.manga_id 
-                WHERE manga_history_union.type_id = ?1 AND LOWER(mangas.title) LIKE '%' || ?2 || '%'",
-            params![history_type_id, search_term],
-            |row| row.get(0),
-        )?;
-        let iter_mangas =
-            get_statement_with_search_term.query_map(params![history_type_id, search_term, items_per_page, offset], |row| {
-                Ok(MangaHistory {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    // img_url: row.get(2)?,
-                })
-            })?;
-
-        for manga in iter_mangas {
-            manga_history.push(manga?);
-        }
-
-        return Ok(MangaHistoryResponse {
-            mangas: manga_history,
-            total_items: total_mangas_with_search,
-            page: args.page,
-        });
-    }
-
-    let iter_mangas = get_statement.query_map(params![history_type_id, items_per_page, offset], |row| {
+    let mut get_statement = args.conn.prepare(&format!(
+        "SELECT  mangas.id, mangas.title, history_types.name, mangas.personal_rating from mangas
+         INNER JOIN manga_history_union ON mangas.id = manga_history_union.manga_id
+         INNER JOIN history_types ON history_types.id = manga_history_union.type_id
+         WHERE (?1 IS NULL OR manga_history_union.type_id = ?1) AND {} AND {} AND (mangas.deleted_at IS NOT NULL) = ?6
+         ORDER BY manga_history_union.type_id, {}
+         LIMIT ?4 OFFSET ?5",
+        matches_search(2, 7),
+        has_tag(3),
+        args.order.as_sql()
+    ))?;
+
+    let to_manga_history = |row: &rusqlite::Row<'_>| {
+        let category: String = row.get(2)?;
         Ok(MangaHistory {
             id: row.get(0)?,
             title: row.get(1)?,
             // img_url: row.get(2)?,
+            category: if args.hist_type.is_none() { category.parse().ok() } else { None },
+            personal_rating: row.get(3)?,
         })
-    })?;
+    };
 
-    for manga in iter_mangas {
-        manga_history.push(manga?);
-    }
+    let manga_history = get_statement
+        .query_map(params![history_type_id, search, tag, items_per_page, offset, args.in_trash, metadata_search], to_manga_history)?
+        .collect::<rusqlite::Result<Vec<MangaHistory>>>()?;
 
     Ok(MangaHistoryResponse {
         mangas: manga_history,
@@ -420,6 +502,8 @@ fn get_history_type(hist_type: MangaHistoryType, conn: &Connection) -> rusqlite:
 pub fn save_plan_to_read(manga: MangaPlanToReadSave<'_>, conn: &Connection) -> rusqlite::Result<()> {
     let history_type = get_history_type(MangaHistoryType::PlanToRead, conn)?;
 
+    Database::new(conn).restore_from_trash(manga.id)?;
+
     if !manga_is_plan_to_read(manga.id, conn)? {
         if check_exists(manga.id, conn, Table::Mangas)? {
             conn.execute("INSERT INTO manga_history_union VALUES (?1, ?2)", (manga.id, history_type))?;
@@ -442,20 +526,60 @@ pub fn save_plan_to_read(manga: MangaPlanToReadSave<'_>, conn: &Connection) -> r
     Ok(())
 }
 
+/// Where and how a chapter was downloaded
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChapterDownloaded {
+    /// The cbz / epub file or the directory with the images
+    pub path: PathBuf,
+    pub format: DownloadType,
+    pub bytes: u64,
+}
+
+impl ChapterDownloaded {
+    /// The directory which contains the chapter, for raw downloads it's the directory with the images
+    pub fn location(&self) -> &Path {
+        match self.format {
+            DownloadType::Raw => &self.path,
+            DownloadType::Cbz | DownloadType::Epub => self.path.parent().unwrap_or(&self.path),
+        }
+    }
+}
+
 pub struct SetChapterDownloaded<'a> {
     pub id: &'a str,
     pub title: &'a str,
     pub manga_id: &'a str,
     pub manga_title: &'a str,
     pub img_url: Option<&'a str>,
+    pub download: &'a ChapterDownloaded,
+}
+
+/// If the chapter was downloaded before the row is replaced with the latest download
+fn save_download(chapter_id: &str, download: &ChapterDownloaded, conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO downloads(chapter_id, path, format, bytes) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(chapter_id) DO UPDATE SET path = excluded.path, format = excluded.format, bytes = excluded.bytes, finished_at = datetime('now')",
+        params![chapter_id, download.path.to_string_lossy(), download.format.to_string(), download.bytes],
+    )?;
+    Ok(())
 }
 
 // a chapter cannot exist if a manga doesnt exist
 // therefore if manga exists chapter exists
 
 // First check if the chapters is already in the database, if not insert it, or else update and set
-// its download status to true
+// its download status to true, the chapter is not flagged as downloaded if saving where it was downloaded fails
 pub fn set_chapter_downloaded(chapter: SetChapterDownloaded<'_>, conn: &Connection) -> rusqlite::Result<()> {
+    let transaction = conn.unchecked_transaction()?;
+
+    let (chapter_id, download) = (chapter.id, chapter.download);
+    set_chapter_downloaded_flag(chapter, conn)?;
+    save_download(chapter_id, download, conn)?;
+
+    transaction.commit()
+}
+
+fn set_chapter_downloaded_flag(chapter: SetChapterDownloaded<'_>, conn: &Connection) -> rusqlite::Result<()> {
     if check_exists(chapter.manga_id, conn, Table::Mangas)? {
         update_or_insert_manga_most_recent_read(chapter.manga_id, conn)?;
 
@@ -511,7 +635,19 @@ impl<'a> Database<'a> {
         Self { connection: conn }
     }
 
+    /// Creates every table and applies the migrations which are pending
     pub fn setup(&self) -> rusqlite::Result<()> {
+        self.create_base_tables()?;
+
+        migrate_up(self.connection, &Logger)?;
+
+        Ok(())
+    }
+
+    /// The tables every migration builds on, the rest are created by the migrations so they can be undone. When the
+    /// migrations are applied separately, like on startup, only these are created. They are the schema of
+    /// `OLDEST_SCHEMA_VERSION`, the columns added later belong in a migration
+    pub fn create_base_tables(&self) -> rusqlite::Result<()> {
         self.connection.execute(
             "CREATE TABLE if not exists app_version (
                 version TEXT PRIMARY KEY
@@ -626,7 +762,10 @@ impl<'a> Database<'a> {
         Ok(())
     }
 
+    /// Only one chapter per manga can be bookmarked, so the previous bookmark is removed in the same transaction
     fn bookmark_chapter(&mut self, chapter_to_bookmark: ChapterToBookmark<'_>) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+
         self.create_manga_if_not_exists(MangaInsert {
             id: chapter_to_bookmark.manga_id,
             title: chapter_to_bookmark.manga_title,
@@ -653,7 +792,7 @@ impl<'a> Database<'a> {
                 chapter_to_bookmark.chapter_id
             ])?;
 
-        Ok(())
+        transaction.commit()
     }
 
     fn get_chapter_bookmarked(&self, manga_id: &str) -> rusqlite::Result<Option<ChapterBookmarked>> {
@@ -679,131 +818,1298 @@ impl<'a> Database<'a> {
             })
             .optional()
     }
-}
 
-#[derive(Default, Debug)]
-pub struct ChapterToInsert<'a> {
-    pub id: &'a str,
-    pub title: &'a str,
-    pub manga_id: &'a str,
-    pub is_read: bool,
-    pub is_downloaded: bool,
-    pub is_bookmarked: bool,
-    pub translated_language: &'a str,
-    pub number_page_bookmarked: Option<u32>,
-}
+    /// Flag all the `chapters` as read in a single transaction, if one of them fails none is saved
+    fn mark_chapters_as_read(&self, manga: MangaInsert<'_>, chapters: &[ChapterToSaveHistory<'_>]) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
 
-#[derive(Default, Debug)]
-pub struct ChapterToBookmark<'a> {
-    pub chapter_id: &'a str,
-    pub manga_id: &'a str,
-    pub chapter_title: &'a str,
-    pub manga_title: &'a str,
-    pub manga_cover_url: Option<&'a str>,
-    pub translated_language: Languages,
-    pub page_number: Option<u32>,
-}
+        self.create_manga_if_not_exists(manga.clone())?;
 
-pub trait Bookmark {
-    fn bookmark(&mut self, chapter_to_bookmark: ChapterToBookmark<'_>) -> Result<(), Box<dyn std::error::Error>>;
-}
+        update_or_insert_manga_most_recent_read(manga.id, self.connection)?;
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub struct ChapterBookmarked {
-    pub id: String,
-    pub translated_language: Option<String>,
-    pub number_page_bookmarked: Option<u32>,
-    pub manga_title: String,
-    pub manga_id: String,
-}
+        for chapter in chapters {
+            self.create_chapter_if_not_exists(ChapterToInsert {
+                id: chapter.id,
+                title: chapter.title,
+                manga_id: manga.id,
+                translated_language: chapter.translated_language,
+                ..Default::default()
+            })?;
 
-pub trait RetrieveBookmark {
-    fn get_bookmarked(&self, manga_id: &str) -> Result<Option<ChapterBookmarked>, Box<dyn std::error::Error>>;
-}
+            self.connection
+                .execute("UPDATE chapters SET is_read = true WHERE id = ?1", params![chapter.id])?;
+        }
 
-impl<'a> Bookmark for Database<'a> {
-    fn bookmark(&mut self, chapter_to_bookmark: ChapterToBookmark<'_>) -> Result<(), Box<dyn std::error::Error>> {
-        Ok(self.bookmark_chapter(chapter_to_bookmark)?)
+        transaction.commit()
     }
-}
 
-impl<'a> RetrieveBookmark for Database<'a> {
-    fn get_bookmarked(&self, manga_id: &str) -> Result<Option<ChapterBookmarked>, Box<dyn std::error::Error>> {
-        Ok(self.get_chapter_bookmarked(manga_id)?)
+    /// Undoes reading a chapter, the manga stays in the reading history
+    pub fn set_chapter_unread(&self, chapter_id: &str) -> rusqlite::Result<()> {
+        self.connection
+            .execute("UPDATE chapters SET is_read = false, read_at = NULL WHERE id = ?1", params![chapter_id])?;
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod test {
+    /// The ids of all the mangas stored, be it in reading history or plan to read
+    pub fn get_library_manga_ids(&self) -> rusqlite::Result<Vec<String>> {
+        let mut statement = self.connection.prepare("SELECT id FROM mangas")?;
 
-    use pretty_assertions::assert_eq;
-    use rusqlite::Result;
-    use strum::IntoEnumIterator;
-    use uuid::Uuid;
+        let ids = statement.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<Vec<String>>>()?;
 
-    use super::*;
+        Ok(ids)
+    }
 
-    fn check_tables_exist(connection: &Connection) -> Result<()> {
-        for table in Table::iter() {
-            connection.query_row(
-                format!("SELECT name FROM sqlite_master WHERE type='table' AND name='{}'", table).as_str(),
-                [],
-                |row| row.get::<_, String>(0),
-            )?;
-        }
+    /// `None` removes the rating
+    pub fn set_personal_rating(&self, manga: MangaInsert<'_>, rating: Option<u8>) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
 
-        let amount_types: i32 =
-            connection.query_row(format!("SELECT COUNT(*) from {}", Table::HistoryTypes).as_str(), [], |row| row.get(0))?;
+        self.create_manga_if_not_exists(manga.clone())?;
+        self.connection
+            .execute("UPDATE mangas SET personal_rating = ?1 WHERE id = ?2", params![rating, manga.id])?;
 
-        assert!(amount_types > 0, "there should be history types");
+        transaction.commit()
+    }
+
+    pub fn get_personal_rating(&self, manga_id: &str) -> rusqlite::Result<Option<u8>> {
+        let rating = self
+            .connection
+            .query_row("SELECT personal_rating FROM mangas WHERE id = ?1", params![manga_id], |row| row.get(0))
+            .optional()?;
+
+        Ok(rating.flatten())
+    }
 
+    /// The total of chapters is cached to show how much of the manga was read without searching its chapters, it is
+    /// only saved if the manga is in the library
+    pub fn save_total_chapters(&self, manga_id: &str, total_chapters: u32) -> rusqlite::Result<()> {
+        self.connection
+            .execute("UPDATE mangas SET total_chapters = ?1 WHERE id = ?2", params![total_chapters, manga_id])?;
         Ok(())
     }
 
-    struct GetChapters {
-        id: String,
-        title: String,
-        manga_id: String,
-        is_read: bool,
-        is_downloaded: bool,
+    pub fn set_reading_mode(&self, manga_id: &str, mode: ReadingMode) -> rusqlite::Result<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO manga_reading_mode(manga_id, mode) VALUES(?1, ?2)",
+            params![manga_id, mode.to_string()],
+        )?;
+        Ok(())
     }
 
-    fn get_all_chapters(conn: &Connection) -> Result<Vec<GetChapters>> {
-        let mut statement = conn.prepare(format!("SELECT * FROM {}", Table::Chapters).as_str())?;
+    pub fn get_reading_mode(&self, manga_id: &str) -> rusqlite::Result<ReadingMode> {
+        let mode: Option<String> = self
+            .connection
+            .query_row("SELECT mode FROM manga_reading_mode WHERE manga_id = ?1", params![manga_id], |row| row.get(0))
+            .optional()?;
 
-        let mut chapters: Vec<GetChapters> = vec![];
+        Ok(mode.and_then(|mode| mode.parse().ok()).unwrap_or_default())
+    }
 
-        let chapter_rows = statement.query_map(params![], |row| {
-            Ok(GetChapters {
+    /// Adds the manga to the reading history without any chapter read, used when the chapters read are not known
+    pub fn add_to_reading_history(&self, manga: MangaInsert<'_>) -> rusqlite::Result<()> {
+        self.mark_chapters_as_read(manga, &[])
+    }
+
+    /// The manga is hidden from the feed until it is restored, its chapters and history are kept
+    pub fn move_to_trash(&self, manga_id: &str) -> rusqlite::Result<()> {
+        self.connection
+            .execute("UPDATE mangas SET deleted_at = datetime('now') WHERE id = ?1", params![manga_id])?;
+        Ok(())
+    }
+
+    pub fn restore_from_trash(&self, manga_id: &str) -> rusqlite::Result<()> {
+        self.connection
+            .execute("UPDATE mangas SET deleted_at = NULL WHERE id = ?1", params![manga_id])?;
+        Ok(())
+    }
+
+    /// Every manga in the reading history and plan to read, a manga in both appears once for each
+    pub fn get_library_entries(&self) -> rusqlite::Result<Vec<LibraryEntry>> {
+        let mut statement = self.connection.prepare(
+            "SELECT mangas.id, mangas.title, history_types.name, mangas.personal_rating,
+            (SELECT COUNT(*) FROM chapters WHERE chapters.manga_id = mangas.id AND chapters.is_read = true)
+            FROM mangas
+            INNER JOIN manga_history_union ON manga_history_union.manga_id = mangas.id
+            INNER JOIN history_types ON history_types.id = manga_history_union.type_id
+            WHERE mangas.deleted_at IS NULL
+            ORDER BY mangas.title",
+        )?;
+
+        let entries = statement.query_map([], |row| {
+            let history_type: String = row.get(2)?;
+            Ok(LibraryEntry {
                 id: row.get(0)?,
                 title: row.get(1)?,
-                manga_id: row.get(2)?,
-                is_read: row.get(3)?,
-                is_downloaded: row.get(4)?,
+                history_type: history_type.parse().unwrap_or(MangaHistoryType::ReadingHistory),
+                personal_rating: row.get(3)?,
+                chapters_read: row.get(4)?,
             })
         })?;
 
-        for chapter in chapter_rows {
-            chapters.push(chapter?);
-        }
-
-        Ok(chapters)
+        entries.collect()
     }
 
-    #[test]
-    fn database_is_initialized() -> Result<()> {
-        let connection = Connection::open_in_memory()?;
+    /// The mangas stored which are not flagged as dead, as `(id, title)`
+    pub fn get_mangas_to_check_for_new_chapters(&self) -> rusqlite::Result<Vec<(String, String)>> {
+        let mut statement = self.connection.prepare(
+            "SELECT id, title FROM mangas WHERE deleted_at IS NULL AND id NOT IN (SELECT manga_id FROM dead_mangas)",
+        )?;
 
-        let database = Database::new(&connection);
+        let mangas = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
 
-        database.setup().expect("could not setup the database");
+        mangas.collect()
+    }
 
-        check_tables_exist(&connection)?;
+    /// `None` if the manga was never checked for new chapters
+    pub fn get_notified_chapters(&self, manga_id: &str) -> rusqlite::Result<Option<Vec<String>>> {
+        let mut statement = self.connection.prepare("SELECT chapter_id FROM notified_chapters WHERE manga_id = ?1")?;
 
-        Ok(())
+        let ids = statement
+            .query_map(params![manga_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+
+        Ok(if ids.is_empty() { None } else { Some(ids) })
     }
 
-    #[test]
+    pub fn save_notified_chapters(&self, manga_id: &str, chapter_ids: &[String]) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+
+        for id in chapter_ids {
+            self.connection
+                .execute("INSERT OR IGNORE INTO notified_chapters(chapter_id, manga_id) VALUES(?1, ?2)", params![id, manga_id])?;
+        }
+
+        transaction.commit()
+    }
+
+    /// Follows the author if it was not followed, otherwise unfollows it. Returns whether the author is now followed
+    pub fn toggle_followed_author(&self, author_id: &str, name: &str) -> rusqlite::Result<bool> {
+        if self.is_following_author(author_id)? {
+            let transaction = self.connection.unchecked_transaction()?;
+            self.connection
+                .execute("DELETE FROM followed_authors WHERE id = ?1", params![author_id])?;
+            self.connection
+                .execute("DELETE FROM author_known_mangas WHERE author_id = ?1", params![author_id])?;
+            transaction.commit()?;
+            return Ok(false);
+        }
+
+        self.connection
+            .execute("INSERT INTO followed_authors(id, name) VALUES(?1, ?2)", params![author_id, name])?;
+        Ok(true)
+    }
+
+    pub fn is_following_author(&self, author_id: &str) -> rusqlite::Result<bool> {
+        self.connection
+            .query_row("SELECT EXISTS(SELECT * FROM followed_authors WHERE id = ?1)", params![author_id], |row| row.get(0))
+    }
+
+    /// As `(id, name)`, the authors followed first come first
+    pub fn get_followed_authors(&self) -> rusqlite::Result<Vec<(String, String)>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, name FROM followed_authors ORDER BY followed_at, name")?;
+
+        let authors = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        authors.collect()
+    }
+
+    /// `None` if the author was never checked for new mangas
+    pub fn get_known_mangas_of_author(&self, author_id: &str) -> rusqlite::Result<Option<Vec<String>>> {
+        let mut statement = self.connection.prepare("SELECT manga_id FROM author_known_mangas WHERE author_id = ?1")?;
+
+        let ids = statement
+            .query_map(params![author_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+
+        Ok(if ids.is_empty() { None } else { Some(ids) })
+    }
+
+    pub fn save_known_mangas_of_author(&self, author_id: &str, manga_ids: &[String]) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+
+        for id in manga_ids {
+            self.connection
+                .execute("INSERT OR IGNORE INTO author_known_mangas(author_id, manga_id) VALUES(?1, ?2)", params![author_id, id])?;
+        }
+
+        transaction.commit()
+    }
+
+    /// Replace the mangas flagged as dead with the ones found on the latest library health check
+    pub fn set_dead_mangas(&self, manga_ids: &[String]) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+
+        self.connection.execute("DELETE FROM dead_mangas", [])?;
+
+        for id in manga_ids {
+            self.connection.execute("INSERT INTO dead_mangas(manga_id) VALUES(?1)", params![id])?;
+        }
+
+        transaction.commit()
+    }
+
+    /// Flag a single manga as dead, for example when opening it shows it was removed or licensed
+    pub fn add_dead_manga(&self, manga_id: &str) -> rusqlite::Result<()> {
+        self.connection
+            .execute("INSERT OR IGNORE INTO dead_mangas(manga_id) VALUES(?1)", params![manga_id])?;
+        Ok(())
+    }
+
+    pub fn get_dead_mangas(&self) -> rusqlite::Result<Vec<String>> {
+        let mut statement = self.connection.prepare("SELECT manga_id FROM dead_mangas")?;
+
+        let ids = statement.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<Vec<String>>>()?;
+
+        Ok(ids)
+    }
+
+    pub fn set_not_interested(&self, manga_id: &str, title: &str) -> rusqlite::Result<()> {
+        self.connection
+            .execute("INSERT OR IGNORE INTO not_interested_mangas(manga_id, title) VALUES(?1, ?2)", params![manga_id, title])?;
+        Ok(())
+    }
+
+    pub fn remove_not_interested(&self, manga_id: &str) -> rusqlite::Result<()> {
+        self.connection
+            .execute("DELETE FROM not_interested_mangas WHERE manga_id = ?1", params![manga_id])?;
+        Ok(())
+    }
+
+    pub fn get_not_interested_mangas(&self) -> rusqlite::Result<Vec<String>> {
+        let mut statement = self.connection.prepare("SELECT manga_id FROM not_interested_mangas")?;
+
+        let ids = statement.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<Vec<String>>>()?;
+
+        Ok(ids)
+    }
+
+    pub fn bind_tracker_entry(&self, manga_id: &str, tracker: &str, entry_id: &str) -> rusqlite::Result<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO tracker_bindings(manga_id, tracker, entry_id) VALUES(?1, ?2, ?3)",
+            params![manga_id, tracker, entry_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_tracker_entry(&self, manga_id: &str, tracker: &str) -> rusqlite::Result<Option<String>> {
+        self.connection
+            .query_row(
+                "SELECT entry_id FROM tracker_bindings WHERE manga_id = ?1 AND tracker = ?2",
+                params![manga_id, tracker],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    pub fn queue_tracker_update(&self, update: &PendingTrackerUpdate) -> rusqlite::Result<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO tracker_queue(manga_id, tracker, manga_title, chapter_number, volume_number)
+             VALUES(?1, ?2, ?3, ?4, ?5)",
+            params![update.manga_id, update.tracker, update.manga_title, update.chapter_number, update.volume_number],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_tracker_queue(&self) -> rusqlite::Result<Vec<PendingTrackerUpdate>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT manga_id, tracker, manga_title, chapter_number, volume_number FROM tracker_queue")?;
+
+        let pending = statement.query_map([], |row| {
+            Ok(PendingTrackerUpdate {
+                manga_id: row.get(0)?,
+                tracker: row.get(1)?,
+                manga_title: row.get(2)?,
+                chapter_number: row.get(3)?,
+                volume_number: row.get(4)?,
+            })
+        })?;
+
+        pending.collect()
+    }
+
+    pub fn remove_from_tracker_queue(&self, manga_id: &str, tracker: &str) -> rusqlite::Result<()> {
+        self.connection
+            .execute("DELETE FROM tracker_queue WHERE manga_id = ?1 AND tracker = ?2", params![manga_id, tracker])?;
+        Ok(())
+    }
+
+    /// Does nothing if there is already a collection with that name
+    pub fn create_collection(&self, name: &str) -> rusqlite::Result<()> {
+        self.connection
+            .execute("INSERT OR IGNORE INTO collections(name) VALUES(?1)", params![name])?;
+        Ok(())
+    }
+
+    pub fn delete_collection(&self, collection_id: i64) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+
+        self.connection
+            .execute("DELETE FROM manga_collection WHERE collection_id = ?1", params![collection_id])?;
+        self.connection.execute("DELETE FROM collections WHERE id = ?1", params![collection_id])?;
+
+        transaction.commit()
+    }
+
+    pub fn get_collections(&self) -> rusqlite::Result<Vec<Collection>> {
+        let mut statement = self.connection.prepare(
+            "SELECT collections.id, collections.name, COUNT(manga_collection.manga_id) FROM collections
+             LEFT JOIN manga_collection ON manga_collection.collection_id = collections.id
+             GROUP BY collections.id
+             ORDER BY collections.name",
+        )?;
+
+        let collections = statement.query_map([], |row| {
+            Ok(Collection {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                total_mangas: row.get(2)?,
+            })
+        })?;
+
+        collections.collect()
+    }
+
+    /// Ids of the collections the manga is in
+    pub fn get_manga_collections(&self, manga_id: &str) -> rusqlite::Result<Vec<i64>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT collection_id FROM manga_collection WHERE manga_id = ?1")?;
+
+        let ids = statement.query_map(params![manga_id], |row| row.get(0))?;
+
+        ids.collect()
+    }
+
+    /// Adds the manga to the collection or removes it if it was already there, returns whether it ended up in the
+    /// collection
+    pub fn toggle_manga_in_collection(&self, collection_id: i64, manga: MangaInsert<'_>) -> rusqlite::Result<bool> {
+        let removed = self
+            .connection
+            .execute("DELETE FROM manga_collection WHERE collection_id = ?1 AND manga_id = ?2", params![collection_id, manga.id])?;
+
+        if removed > 0 {
+            return Ok(false);
+        }
+
+        self.create_manga_if_not_exists(manga.clone())?;
+        self.connection
+            .execute("INSERT INTO manga_collection(collection_id, manga_id) VALUES(?1, ?2)", params![collection_id, manga.id])?;
+
+        Ok(true)
+    }
+
+    /// The mangas most recently added come first, `search` matches their title, any of their alternative titles, their
+    /// description or genres and `tag` keeps only the mangas tagged with it
+    pub fn get_collection_mangas(
+        &self,
+        collection_id: i64,
+        page: u32,
+        items_per_page: u32,
+        search: Option<SearchTerm>,
+        tag: Option<TagName>,
+    ) -> rusqlite::Result<MangaHistoryResponse> {
+        let search = search.map(|term| term.get().to_string());
+        let metadata_search = search.as_deref().map(metadata_query);
+        let tag = tag.map(|tag| tag.get().to_string());
+        let offset = (page - 1) * items_per_page;
+
+        let total_items: u32 = self.connection.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM mangas
+                 INNER JOIN manga_collection ON manga_collection.manga_id = mangas.id
+                 WHERE manga_collection.collection_id = ?1 AND {} AND {}",
+                matches_search(2, 4),
+                has_tag(3)
+            ),
+            params![collection_id, search, tag, metadata_search],
+            |row| row.get(0),
+        )?;
+
+        let mut statement = self.connection.prepare(&format!(
+            "SELECT mangas.id, mangas.title, mangas.personal_rating FROM mangas
+             INNER JOIN manga_collection ON manga_collection.manga_id = mangas.id
+             WHERE manga_collection.collection_id = ?1 AND {} AND {}
+             ORDER BY manga_collection.added_at DESC, manga_collection.rowid DESC
+             LIMIT ?4 OFFSET ?5",
+            matches_search(2, 6),
+            has_tag(3)
+        ))?;
+
+        let mangas = statement
+            .query_map(params![collection_id, search, tag, items_per_page, offset, metadata_search], |row| {
+                Ok(MangaHistory {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    category: None,
+                    personal_rating: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<MangaHistory>>>()?;
+
+        Ok(MangaHistoryResponse {
+            mangas,
+            page,
+            total_items,
+        })
+    }
+
+    /// Tags the manga, the tag is created if no other manga has it yet
+    pub fn add_manga_tag(&self, manga: MangaInsert<'_>, tag: &TagName) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+
+        self.create_manga_if_not_exists(manga.clone())?;
+        self.connection
+            .execute("INSERT OR IGNORE INTO tags(name) VALUES(?1)", params![tag.get()])?;
+        self.connection.execute(
+            "INSERT OR IGNORE INTO manga_tags(tag_id, manga_id) SELECT id, ?2 FROM tags WHERE name = ?1",
+            params![tag.get(), manga.id],
+        )?;
+
+        transaction.commit()
+    }
+
+    /// The tag is deleted once no manga has it
+    pub fn remove_manga_tag(&self, manga_id: &str, tag: &TagName) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+
+        self.connection.execute(
+            "DELETE FROM manga_tags WHERE manga_id = ?1 AND tag_id = (SELECT id FROM tags WHERE name = ?2)",
+            params![manga_id, tag.get()],
+        )?;
+        self.connection
+            .execute("DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM manga_tags)", [])?;
+
+        transaction.commit()
+    }
+
+    pub fn get_manga_tags(&self, manga_id: &str) -> rusqlite::Result<Vec<String>> {
+        let mut statement = self.connection.prepare(
+            "SELECT tags.name FROM tags
+             INNER JOIN manga_tags ON manga_tags.tag_id = tags.id
+             WHERE manga_tags.manga_id = ?1
+             ORDER BY tags.name",
+        )?;
+
+        let tags = statement.query_map(params![manga_id], |row| row.get(0))?;
+
+        tags.collect()
+    }
+
+    /// Every tag the user has put on a manga
+    pub fn get_tags(&self) -> rusqlite::Result<Vec<String>> {
+        let mut statement = self.connection.prepare("SELECT name FROM tags ORDER BY name")?;
+
+        let tags = statement.query_map([], |row| row.get(0))?;
+
+        tags.collect()
+    }
+
+    /// Only saved if the manga is in the library, there is no point in keeping them for every manga opened
+    pub fn save_alt_titles(&self, manga_id: &str, alt_titles: &[String]) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+
+        for title in alt_titles {
+            self.connection.execute(
+                "INSERT OR IGNORE INTO manga_alt_titles(manga_id, title)
+                 SELECT ?1, ?2 WHERE EXISTS (SELECT 1 FROM mangas WHERE id = ?1)",
+                params![manga_id, title],
+            )?;
+        }
+
+        transaction.commit()
+    }
+
+    /// Replaces the description and genres stored for the manga, so the history can be searched by them, like the
+    /// alt titles they are only saved if the manga is in the library
+    pub fn save_metadata(&self, manga_id: &str, description: &str, genres: &[String]) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+
+        self.connection
+            .execute("DELETE FROM manga_metadata WHERE manga_id = ?1", params![manga_id])?;
+        self.connection.execute(
+            "INSERT INTO manga_metadata(manga_id, description, genres)
+             SELECT ?1, ?2, ?3 WHERE EXISTS (SELECT 1 FROM mangas WHERE id = ?1)",
+            params![manga_id, description, genres.join(", ")],
+        )?;
+
+        transaction.commit()
+    }
+
+    /// Block a scanlation group only for `manga_id` or for every manga if it is `None`
+    fn insert_blocked_scanlation_group(&self, group: &ScanlationGroup<'_>, manga_id: Option<&str>) -> rusqlite::Result<()> {
+        self.connection.execute(
+            "INSERT INTO blocked_scanlation_groups(group_id, group_name, manga_id)
+             SELECT ?1, ?2, ?3
+             WHERE NOT EXISTS (SELECT 1 FROM blocked_scanlation_groups WHERE group_id = ?1 AND manga_id IS ?3)",
+            params![group.id, group.name, manga_id],
+        )?;
+
+        Ok(())
+    }
+
+    /// The ids of the groups blocked for this manga, including the ones blocked for every manga
+    fn get_blocked_scanlation_group_ids(&self, manga_id: &str) -> rusqlite::Result<Vec<String>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT DISTINCT group_id FROM blocked_scanlation_groups WHERE manga_id = ?1 OR manga_id IS NULL")?;
+
+        let ids = statement
+            .query_map(params![manga_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+
+        Ok(ids)
+    }
+
+    /// Unblock every group blocked only for `manga_id` or the ones blocked for every manga if it is `None`, returns how
+    /// many were unblocked
+    fn delete_blocked_scanlation_groups(&self, manga_id: Option<&str>) -> rusqlite::Result<usize> {
+        self.connection
+            .execute("DELETE FROM blocked_scanlation_groups WHERE manga_id IS ?1", params![manga_id])
+    }
+
+    /// Keep track of the chapter and page being read, if the app is not closed properly this is used to resume reading
+    pub fn save_reading_session(&self, session: &ChapterBookmarked) -> rusqlite::Result<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO reading_session(id, chapter_id, translated_language, page_number, manga_id, manga_title, updated_at)
+             VALUES(1, ?1, ?2, ?3, ?4, ?5, datetime('now'))",
+            params![session.id, session.translated_language, session.number_page_bookmarked, session.manga_id, session.manga_title],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_reading_session(&self) -> rusqlite::Result<Option<ChapterBookmarked>> {
+        self.connection
+            .query_row(
+                "SELECT chapter_id, translated_language, page_number, manga_title, manga_id FROM reading_session WHERE id = 1",
+                [],
+                |row| {
+                    Ok(ChapterBookmarked {
+                        id: row.get(0)?,
+                        translated_language: row.get(1)?,
+                        number_page_bookmarked: row.get(2)?,
+                        manga_title: row.get(3)?,
+                        manga_id: row.get(4)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    /// Only the last page reached is kept, going back to previous pages moves the progress back too
+    pub fn save_chapter_progress(&self, chapter_id: &str, progress: ChapterProgress) -> rusqlite::Result<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO chapter_progress(chapter_id, last_page, total_pages, updated_at) VALUES(?1, ?2, ?3, datetime('now'))",
+            params![chapter_id, progress.last_page, progress.total_pages],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_chapter_progress(&self, chapter_id: &str) -> rusqlite::Result<Option<ChapterProgress>> {
+        self.connection
+            .query_row("SELECT last_page, total_pages FROM chapter_progress WHERE chapter_id = ?1", params![chapter_id], |row| {
+                Ok(ChapterProgress {
+                    last_page: row.get(0)?,
+                    total_pages: row.get(1)?,
+                })
+            })
+            .optional()
+    }
+
+    /// The mangas of the reading history read most recently, each with the bookmarked chapter or otherwise the chapter
+    /// which was read last in the reader
+    pub fn get_continue_reading(&self, limit: u32) -> rusqlite::Result<Vec<ContinueReading>> {
+        let reading_history = get_history_type(MangaHistoryType::ReadingHistory, self.connection)?;
+
+        let mut statement = self.connection.prepare(
+            "SELECT chapters.id, chapters.translated_language, chapters.is_bookmarked, chapters.number_page_bookmarked,
+                    mangas.title, mangas.id, chapter_progress.last_page, chapter_progress.total_pages,
+                    (SELECT COUNT(*) FROM chapters WHERE chapters.manga_id = mangas.id AND chapters.is_read = true),
+                    mangas.total_chapters
+             FROM mangas
+             INNER JOIN manga_history_union ON manga_history_union.manga_id = mangas.id AND manga_history_union.type_id = ?1
+             INNER JOIN chapters ON chapters.id = (
+                SELECT chapters.id FROM chapters
+                LEFT JOIN chapter_progress ON chapter_progress.chapter_id = chapters.id
+                WHERE chapters.manga_id = mangas.id AND (chapters.is_bookmarked = true OR chapter_progress.chapter_id IS NOT NULL)
+                ORDER BY chapters.is_bookmarked DESC, chapter_progress.updated_at DESC
+                LIMIT 1
+             )
+             LEFT JOIN chapter_progress ON chapter_progress.chapter_id = chapters.id
+             WHERE mangas.deleted_at IS NULL
+             ORDER BY mangas.last_read DESC
+             LIMIT ?2",
+        )?;
+
+        let continue_reading = statement.query_map(params![reading_history, limit], |row| {
+            let is_bookmarked: bool = row.get(2)?;
+            let last_page: Option<u32> = row.get(6)?;
+            let total_pages: Option<u32> = row.get(7)?;
+
+            Ok(ContinueReading {
+                chapter: ChapterBookmarked {
+                    id: row.get(0)?,
+                    translated_language: row.get(1)?,
+                    number_page_bookmarked: if is_bookmarked { row.get(3)? } else { None },
+                    manga_title: row.get(4)?,
+                    manga_id: row.get(5)?,
+                },
+                progress: last_page.zip(total_pages).map(|(last_page, total_pages)| ChapterProgress {
+                    last_page,
+                    total_pages,
+                }),
+                chapters_read: row.get(8)?,
+                total_chapters: row.get(9)?,
+            })
+        })?;
+
+        continue_reading.collect()
+    }
+
+    pub fn add_reading_time(&self, chapter_id: &str, seconds: u64) -> rusqlite::Result<()> {
+        self.connection
+            .execute("UPDATE chapters SET reading_seconds = reading_seconds + ?1 WHERE id = ?2", params![seconds, chapter_id])?;
+
+        Ok(())
+    }
+
+    /// Called when the reader is closed properly so that there is nothing to resume on the next launch
+    pub fn clear_reading_session(&self) -> rusqlite::Result<()> {
+        self.connection.execute("DELETE FROM reading_session", [])?;
+        Ok(())
+    }
+
+    pub fn save_download_all_job(&self, job: &DownloadAllJob) -> rusqlite::Result<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO download_all_job(id, manga_id, manga_title, translated_language, started_at)
+             VALUES(1, ?1, ?2, ?3, datetime('now'))",
+            params![job.manga_id, job.manga_title, job.translated_language],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_download_all_job(&self) -> rusqlite::Result<Option<DownloadAllJob>> {
+        self.connection
+            .query_row("SELECT manga_id, manga_title, translated_language FROM download_all_job WHERE id = 1", [], |row| {
+                Ok(DownloadAllJob {
+                    manga_id: row.get(0)?,
+                    manga_title: row.get(1)?,
+                    translated_language: row.get(2)?,
+                })
+            })
+            .optional()
+    }
+
+    /// Called when downloading all chapters finished or was aborted by the user
+    pub fn clear_download_all_job(&self) -> rusqlite::Result<()> {
+        self.connection.execute("DELETE FROM download_all_job", [])?;
+        Ok(())
+    }
+
+    /// Used to skip the chapters that were already downloaded when resuming a download of all chapters
+    pub fn get_downloaded_chapter_ids(&self, manga_id: &str) -> rusqlite::Result<Vec<String>> {
+        let mut statement = self.connection.prepare(
+            "SELECT downloads.chapter_id FROM downloads INNER JOIN chapters ON chapters.id = downloads.chapter_id WHERE chapters.manga_id = ?1",
+        )?;
+
+        let ids = statement.query_map([manga_id], |row| row.get(0))?;
+
+        ids.collect()
+    }
+
+    /// How many chapters `prune_orphan_chapters` would remove
+    pub fn count_orphan_chapters(&self) -> rusqlite::Result<usize> {
+        self.connection
+            .query_row("SELECT COUNT(*) FROM chapters WHERE manga_id NOT IN (SELECT id FROM mangas)", [], |row| row.get(0))
+    }
+
+    /// Removes the chapters whose manga no longer exists along with their downloads and progress, as well as the alt
+    /// titles and metadata of mangas which are not in the library, returns how many chapters were removed
+    pub fn prune_orphan_chapters(&self) -> rusqlite::Result<usize> {
+        let orphan_chapters = "SELECT id FROM chapters WHERE manga_id NOT IN (SELECT id FROM mangas)";
+
+        let transaction = self.connection.unchecked_transaction()?;
+
+        self.connection
+            .execute(&format!("DELETE FROM downloads WHERE chapter_id IN ({orphan_chapters})"), [])?;
+        self.connection
+            .execute(&format!("DELETE FROM chapter_progress WHERE chapter_id IN ({orphan_chapters})"), [])?;
+        let removed = self
+            .connection
+            .execute("DELETE FROM chapters WHERE manga_id NOT IN (SELECT id FROM mangas)", [])?;
+        self.connection
+            .execute("DELETE FROM manga_alt_titles WHERE manga_id NOT IN (SELECT id FROM mangas)", [])?;
+        self.connection
+            .execute("DELETE FROM manga_metadata WHERE manga_id NOT IN (SELECT id FROM mangas)", [])?;
+
+        transaction.commit()?;
+
+        Ok(removed)
+    }
+
+    /// Moves to the trash the mangas of the reading history not read in the last `months`, unless they are also in plan
+    /// to read or have chapters downloaded, returns how many were moved
+    pub fn prune_history(&self, months: u32) -> rusqlite::Result<usize> {
+        let reading_history = get_history_type(MangaHistoryType::ReadingHistory, self.connection)?;
+        let plan_to_read = get_history_type(MangaHistoryType::PlanToRead, self.connection)?;
+
+        self.connection.execute(
+            &format!("UPDATE mangas SET deleted_at = datetime('now') WHERE {STALE_HISTORY_CONDITION}"),
+            params![format!("-{months} months"), reading_history, plan_to_read],
+        )
+    }
+
+    /// The mangas `prune_history` would move to the trash, so the user can be asked before doing it
+    pub fn history_to_prune(&self, months: u32) -> rusqlite::Result<HistoryToPrune> {
+        let reading_history = get_history_type(MangaHistoryType::ReadingHistory, self.connection)?;
+        let plan_to_read = get_history_type(MangaHistoryType::PlanToRead, self.connection)?;
+
+        let mut statement = self
+            .connection
+            .prepare(&format!("SELECT title FROM mangas WHERE {STALE_HISTORY_CONDITION} ORDER BY last_read"))?;
+
+        let titles = statement
+            .query_map(params![format!("-{months} months"), reading_history, plan_to_read], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+
+        Ok(HistoryToPrune { months, titles })
+    }
+
+    /// A manga of the reading history or plan to read other than `manga_id` whose title or alternative titles are
+    /// near-identical to any of `titles`
+    pub fn find_duplicate(&self, manga_id: &str, titles: &[String]) -> rusqlite::Result<Option<MatchedManga>> {
+        self.find_manga_by_titles(titles, Some(manga_id))
+    }
+
+    /// The manga of the reading history or plan to read whose title or alternative titles are near-identical to `title`
+    pub fn find_manga_by_title(&self, title: &str) -> rusqlite::Result<Option<MatchedManga>> {
+        self.find_manga_by_titles(&[title.to_string()], None)
+    }
+
+    fn find_manga_by_titles(&self, titles: &[String], except_id: Option<&str>) -> rusqlite::Result<Option<MatchedManga>> {
+        let titles: Vec<String> = titles
+            .iter()
+            .map(|title| normalize_title(title))
+            .filter(|title| !title.is_empty())
+            .collect();
+
+        let mut statement = self.connection.prepare(
+            "SELECT mangas.id, mangas.title, manga_alt_titles.title FROM mangas
+             LEFT JOIN manga_alt_titles ON manga_alt_titles.manga_id = mangas.id
+             WHERE mangas.id IS NOT ?1 AND mangas.deleted_at IS NULL
+             AND mangas.id IN (SELECT manga_id FROM manga_history_union)
+             ORDER BY mangas.last_read DESC",
+        )?;
+
+        let mut rows = statement.query(params![except_id])?;
+
+        while let Some(row) = rows.next()? {
+            let title: String = row.get(1)?;
+            let alt_title: Option<String> = row.get(2)?;
+
+            let is_duplicate = std::iter::once(&title)
+                .chain(alt_title.as_ref())
+                .any(|candidate| titles.contains(&normalize_title(candidate)));
+
+            if is_duplicate {
+                return Ok(Some(MatchedManga {
+                    id: row.get(0)?,
+                    title,
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// The chapters read, lists, tags, collections and rating of `duplicate_id` are moved to `manga` and the duplicate
+    /// goes to the trash, so only one of them is shown in the feed
+    pub fn merge_duplicate(&self, duplicate_id: &str, manga: MangaInsert<'_>) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+
+        self.create_manga_if_not_exists(manga.clone())?;
+
+        self.connection.execute(
+            "INSERT OR IGNORE INTO manga_history_union(manga_id, type_id)
+             SELECT ?1, type_id FROM manga_history_union WHERE manga_id = ?2",
+            params![manga.id, duplicate_id],
+        )?;
+        self.connection
+            .execute("UPDATE chapters SET manga_id = ?1 WHERE manga_id = ?2", params![manga.id, duplicate_id])?;
+        self.connection.execute(
+            "INSERT OR IGNORE INTO manga_tags(tag_id, manga_id) SELECT tag_id, ?1 FROM manga_tags WHERE manga_id = ?2",
+            params![manga.id, duplicate_id],
+        )?;
+        self.connection.execute(
+            "INSERT OR IGNORE INTO manga_collection(collection_id, manga_id, added_at)
+             SELECT collection_id, ?1, added_at FROM manga_collection WHERE manga_id = ?2",
+            params![manga.id, duplicate_id],
+        )?;
+        self.connection.execute(
+            "INSERT OR IGNORE INTO manga_alt_titles(manga_id, title)
+             SELECT ?1, title FROM mangas WHERE id = ?2
+             UNION SELECT ?1, title FROM manga_alt_titles WHERE manga_id = ?2",
+            params![manga.id, duplicate_id],
+        )?;
+        self.connection.execute(
+            "UPDATE mangas SET
+             personal_rating = COALESCE(personal_rating, (SELECT personal_rating FROM mangas WHERE id = ?2)),
+             last_read = MAX(last_read, (SELECT last_read FROM mangas WHERE id = ?2))
+             WHERE id = ?1",
+            params![manga.id, duplicate_id],
+        )?;
+
+        self.move_to_trash(duplicate_id)?;
+
+        transaction.commit()
+    }
+
+    /// Whether a chapter was already downloaded to or registered from `path`
+    pub fn is_download_registered(&self, path: &Path) -> rusqlite::Result<bool> {
+        self.connection.query_row(
+            "SELECT EXISTS(SELECT 1 FROM downloads WHERE path = ?1)",
+            params![path.to_string_lossy()],
+            |row| row.get(0),
+        )
+    }
+
+    pub fn manga_exists(&self, manga_id: &str) -> rusqlite::Result<bool> {
+        check_exists(manga_id, self.connection, Table::Mangas)
+    }
+
+    /// The manga the chapter belongs to, if the chapter is in the database
+    pub fn get_chapter_manga(&self, chapter_id: &str) -> rusqlite::Result<Option<String>> {
+        self.connection
+            .query_row("SELECT manga_id FROM chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))
+            .optional()
+    }
+
+    /// Registers a chapter downloaded outside of manga-tui, unlike the ones downloaded from the manga page it is not
+    /// marked as read
+    pub fn register_external_chapter(
+        &self,
+        manga_id: &str,
+        chapter_id: &str,
+        title: &str,
+        download: &ChapterDownloaded,
+    ) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+
+        if check_exists(chapter_id, self.connection, Table::Chapters)? {
+            self.connection
+                .execute("UPDATE chapters SET is_downloaded = true WHERE id = ?1", params![chapter_id])?;
+        } else {
+            self.connection.execute(
+                "INSERT INTO chapters(id, title, manga_id, is_downloaded) VALUES (?1, ?2, ?3, true)",
+                params![chapter_id, title, manga_id],
+            )?;
+        }
+
+        save_download(chapter_id, download, self.connection)?;
+
+        transaction.commit()
+    }
+
+    /// Rebuilds the database file so the space of the rows deleted is given back
+    pub fn vacuum(&self) -> rusqlite::Result<()> {
+        self.connection.execute_batch("VACUUM")
+    }
+
+    /// Empty if nothing is wrong, otherwise the problems sqlite found
+    pub fn integrity_check(&self) -> rusqlite::Result<Vec<String>> {
+        let mut statement = self.connection.prepare("PRAGMA integrity_check")?;
+
+        let problems = statement.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<Vec<String>>>()?;
+
+        Ok(problems.into_iter().filter(|problem| problem != "ok").collect())
+    }
+
+    /// In bytes
+    pub fn size(&self) -> rusqlite::Result<u64> {
+        let page_count: u64 = self.connection.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        let page_size: u64 = self.connection.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+
+        Ok(page_count * page_size)
+    }
+
+    /// Iso code of the languages chapters were read in and how many chapters were read in each, the most read first
+    pub fn get_chapters_read_by_language(&self) -> rusqlite::Result<Vec<(String, u64)>> {
+        self.connection
+            .prepare(
+                "SELECT translated_language, COUNT(*) AS chapters_read FROM chapters
+                 WHERE is_read = true AND translated_language IS NOT NULL AND translated_language != ''
+                 GROUP BY translated_language ORDER BY chapters_read DESC, translated_language",
+            )?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect()
+    }
+
+    /// Chapters read from the start of `since` (UTC) onwards, for the reading goal shown in the feed page
+    pub fn chapters_read_since(&self, since: NaiveDate) -> rusqlite::Result<u32> {
+        self.connection.query_row(
+            "SELECT COUNT(*) FROM chapters WHERE is_read = true AND read_at >= ?1",
+            [since.to_string()],
+            |row| row.get(0),
+        )
+    }
+
+    /// Used in the wrapped page, `year` only applies to the downloads since the date a chapter was read is not known
+    /// for the chapters read before it started being saved
+    pub fn get_reading_summary(&self, year: i32) -> rusqlite::Result<ReadingSummary> {
+        let (chapters_read, mangas_read): (u64, u64) = self.connection.query_row(
+            "SELECT COUNT(*), COUNT(DISTINCT manga_id) FROM chapters WHERE is_read = true",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        let most_read: Vec<(String, u64)> = self
+            .connection
+            .prepare(
+                "SELECT mangas.title, COUNT(*) AS chapters_read FROM chapters INNER JOIN mangas ON mangas.id = chapters.manga_id
+                 WHERE chapters.is_read = true GROUP BY mangas.id ORDER BY chapters_read DESC, mangas.title LIMIT ?1",
+            )?
+            .query_map([MOST_READ_MANGAS_IN_SUMMARY], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let chapters_read_by_language = self.get_chapters_read_by_language()?;
+
+        let seconds_read: u64 = self
+            .connection
+            .query_row("SELECT COALESCE(SUM(reading_seconds), 0) FROM chapters", [], |row| row.get(0))?;
+
+        let (chapters_downloaded, bytes_downloaded): (u64, u64) = self.connection.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(bytes), 0) FROM downloads WHERE strftime('%Y', finished_at) = ?1",
+            [year.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        let busiest_download_day: Option<(String, u64)> = self
+            .connection
+            .query_row(
+                "SELECT date(finished_at) AS day, COUNT(*) AS total FROM downloads WHERE strftime('%Y', finished_at) = ?1
+                 GROUP BY day ORDER BY total DESC, day LIMIT 1",
+                [year.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        let chapters_read_by_day: Vec<(String, u64)> = self
+            .connection
+            .prepare(
+                "SELECT date(read_at) AS day, COUNT(*) FROM chapters WHERE is_read = true AND strftime('%Y', read_at) = ?1
+                 GROUP BY day ORDER BY day",
+            )?
+            .query_map([year.to_string()], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        Ok(ReadingSummary {
+            year,
+            chapters_read,
+            mangas_read,
+            most_read,
+            chapters_read_by_language,
+            seconds_read,
+            chapters_downloaded,
+            bytes_downloaded,
+            busiest_download_day,
+            chapters_read_by_day,
+        })
+    }
+}
+
+const MOST_READ_MANGAS_IN_SUMMARY: u32 = 5;
+
+/// Mangas of the reading history (`?2`) not read since `?1` which are neither in plan to read (`?3`) nor downloaded
+const STALE_HISTORY_CONDITION: &str = "deleted_at IS NULL AND last_read < datetime('now', ?1)
+    AND id IN (SELECT manga_id FROM manga_history_union WHERE type_id = ?2)
+    AND id NOT IN (SELECT manga_id FROM manga_history_union WHERE type_id = ?3)
+    AND id NOT IN (
+        SELECT manga_id FROM chapters
+        WHERE is_downloaded = true OR id IN (SELECT chapter_id FROM downloads)
+    )";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ReadingSummary {
+    pub year: i32,
+    pub chapters_read: u64,
+    pub mangas_read: u64,
+    /// Title of the manga and how many of its chapters were read, the most read first
+    pub most_read: Vec<(String, u64)>,
+    /// Iso code of the language and how many chapters were read in it, the most read first
+    pub chapters_read_by_language: Vec<(String, u64)>,
+    /// Time spent in the reader across every chapter
+    pub seconds_read: u64,
+    pub chapters_downloaded: u64,
+    pub bytes_downloaded: u64,
+    /// The day formatted as `YYYY-MM-DD` and how many chapters were downloaded that day
+    pub busiest_download_day: Option<(String, u64)>,
+    /// The days of the year in which chapters were read formatted as `YYYY-MM-DD` and how many, used for the heatmap
+    pub chapters_read_by_day: Vec<(String, u64)>,
+}
+
+/// A download of all the chapters of a manga which has not finished yet
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DownloadAllJob {
+    pub manga_id: String,
+    pub manga_title: String,
+    /// Iso code of the language the chapters are downloaded in
+    pub translated_language: String,
+}
+
+/// A manga of the reading history or plan to read, as it is exported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryEntry {
+    pub id: String,
+    pub title: String,
+    pub history_type: MangaHistoryType,
+    pub personal_rating: Option<u8>,
+    pub chapters_read: u32,
+}
+
+/// The mangas of the reading history not read in `months`, which are moved to the trash once the user confirms it
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HistoryToPrune {
+    pub months: u32,
+    pub titles: Vec<String>,
+}
+
+/// A manga of the library found by its title
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchedManga {
+    pub id: String,
+    pub title: String,
+}
+
+/// Titles which only differ in case, spaces or punctuation are taken as the same
+fn normalize_title(title: &str) -> String {
+    title.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// A named group of mangas made by the user, like "Favorites" or "On hold"
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Collection {
+    pub id: i64,
+    pub name: String,
+    pub total_mangas: u32,
+}
+
+/// Reading progress that will be sent to the tracker named `tracker` the next time manga-tui starts
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PendingTrackerUpdate {
+    pub manga_id: String,
+    pub tracker: String,
+    pub manga_title: String,
+    pub chapter_number: u32,
+    pub volume_number: Option<u32>,
+}
+
+#[derive(Default, Debug)]
+pub struct ChapterToInsert<'a> {
+    pub id: &'a str,
+    pub title: &'a str,
+    pub manga_id: &'a str,
+    pub is_read: bool,
+    pub is_downloaded: bool,
+    pub is_bookmarked: bool,
+    pub translated_language: &'a str,
+    pub number_page_bookmarked: Option<u32>,
+}
+
+#[derive(Default, Debug)]
+pub struct ChapterToBookmark<'a> {
+    pub chapter_id: &'a str,
+    pub manga_id: &'a str,
+    pub chapter_title: &'a str,
+    pub manga_title: &'a str,
+    pub manga_cover_url: Option<&'a str>,
+    pub translated_language: Languages,
+    pub page_number: Option<u32>,
+}
+
+pub trait Bookmark {
+    fn bookmark(&mut self, chapter_to_bookmark: ChapterToBookmark<'_>) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChapterBookmarked {
+    pub id: String,
+    pub translated_language: Option<String>,
+    pub number_page_bookmarked: Option<u32>,
+    pub manga_title: String,
+    pub manga_id: String,
+}
+
+/// A manga of the reading history and the chapter where reading can be continued
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContinueReading {
+    /// `number_page_bookmarked` is only set if the chapter is the one bookmarked
+    pub chapter: ChapterBookmarked,
+    pub progress: Option<ChapterProgress>,
+    pub chapters_read: u32,
+    /// `None` until the chapters of the manga are searched from its page
+    pub total_chapters: Option<u32>,
+}
+
+impl ContinueReading {
+    /// The bookmark is what the user chose, so it goes before the last page reached
+    pub fn page_to_resume(&self) -> Option<u32> {
+        self.chapter
+            .number_page_bookmarked
+            .or_else(|| self.progress.and_then(|progress| progress.page_to_resume()))
+    }
+
+    /// How much of the manga was read, `None` if its total of chapters is not known yet
+    pub fn chapters_read_ratio(&self) -> Option<f64> {
+        self.total_chapters
+            .filter(|total| *total > 0)
+            .map(|total| (self.chapters_read as f64 / total as f64).min(1.0))
+    }
+}
+
+pub trait MarkAsRead {
+    fn mark_as_read(
+        &mut self,
+        manga: MangaInsert<'_>,
+        chapters: &[ChapterToSaveHistory<'_>],
+    ) -> Result<(), Box<dyn std::error::Error>>;
+
+    fn mark_as_unread(&mut self, chapter_id: &str) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanlationGroup<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+}
+
+pub trait BlockScanlationGroup {
+    fn block_group(&mut self, group: ScanlationGroup<'_>, manga_id: Option<&str>) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Returns how many groups were unblocked
+    fn unblock_groups(&mut self, manga_id: Option<&str>) -> Result<usize, Box<dyn std::error::Error>>;
+
+    fn get_blocked_groups(&self, manga_id: &str) -> Result<Vec<String>, Box<dyn std::error::Error>>;
+}
+
+pub trait RetrieveBookmark {
+    fn get_bookmarked(&self, manga_id: &str) -> Result<Option<ChapterBookmarked>, Box<dyn std::error::Error>>;
+}
+
+impl<'a> Bookmark for Database<'a> {
+    fn bookmark(&mut self, chapter_to_bookmark: ChapterToBookmark<'_>) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(self.bookmark_chapter(chapter_to_bookmark)?)
+    }
+}
+
+impl<'a> MarkAsRead for Database<'a> {
+    fn mark_as_read(
+        &mut self,
+        manga: MangaInsert<'_>,
+        chapters: &[ChapterToSaveHistory<'_>],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(self.mark_chapters_as_read(manga, chapters)?)
+    }
+
+    fn mark_as_unread(&mut self, chapter_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(self.set_chapter_unread(chapter_id)?)
+    }
+}
+
+impl<'a> BlockScanlationGroup for Database<'a> {
+    fn block_group(&mut self, group: ScanlationGroup<'_>, manga_id: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(self.insert_blocked_scanlation_group(&group, manga_id)?)
+    }
+
+    fn unblock_groups(&mut self, manga_id: Option<&str>) -> Result<usize, Box<dyn std::error::Error>> {
+        Ok(self.delete_blocked_scanlation_groups(manga_id)?)
+    }
+
+    fn get_blocked_groups(&self, manga_id: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        Ok(self.get_blocked_scanlation_group_ids(manga_id)?)
+    }
+}
+
+impl<'a> RetrieveBookmark for Database<'a> {
+    fn get_bookmarked(&self, manga_id: &str) -> Result<Option<ChapterBookmarked>, Box<dyn std::error::Error>> {
+        Ok(self.get_chapter_bookmarked(manga_id)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+    use rusqlite::Result;
+    use strum::IntoEnumIterator;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn check_tables_exist(connection: &Connection) -> Result<()> {
+        for table in Table::iter() {
+            connection.query_row(
+                format!("SELECT name FROM sqlite_master WHERE type='table' AND name='{}'", table).as_str(),
+                [],
+                |row| row.get::<_, String>(0),
+            )?;
+        }
+
+        let amount_types: i32 =
+            connection.query_row(format!("SELECT COUNT(*) from {}", Table::HistoryTypes).as_str(), [], |row| row.get(0))?;
+
+        assert!(amount_types > 0, "there should be history types");
+
+        Ok(())
+    }
+
+    struct GetChapters {
+        id: String,
+        title: String,
+        manga_id: String,
+        is_read: bool,
+        is_downloaded: bool,
+    }
+
+    fn get_all_chapters(conn: &Connection) -> Result<Vec<GetChapters>> {
+        let mut statement = conn.prepare(format!("SELECT * FROM {}", Table::Chapters).as_str())?;
+
+        let mut chapters: Vec<GetChapters> = vec![];
+
+        let chapter_rows = statement.query_map(params![], |row| {
+            Ok(GetChapters {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                manga_id: row.get(2)?,
+                is_read: row.get(3)?,
+                is_downloaded: row.get(4)?,
+            })
+        })?;
+
+        for chapter in chapter_rows {
+            chapters.push(chapter?);
+        }
+
+        Ok(chapters)
+    }
+
+    #[test]
+    fn database_is_initialized() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+
+        let database = Database::new(&connection);
+
+        database.setup().expect("could not setup the database");
+
+        check_tables_exist(&connection)?;
+
+        Ok(())
+    }
+
+    #[test]
     fn insert_manga_and_chapter() -> Result<()> {
         let binding = DBCONN.lock().expect("could not get db conn");
         let connection = binding.as_ref().unwrap();
@@ -812,886 +2118,2285 @@ mod test {
 
         let chapter_id = Uuid::new_v4().to_string();
 
-        insert_manga(
-            MangaInsert {
-                id: &manga_id,
+        insert_manga(
+            MangaInsert {
+                id: &manga_id,
+                title: "some_title",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        insert_chapter(
+            ChapterInsert {
+                id: &chapter_id,
+                title: "some_title",
+                manga_id: &manga_id,
+                is_read: true,
+                is_downloaded: false,
+            },
+            connection,
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_chapter_is_already_reading() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+
+        let database = Database::new(&conn);
+
+        database.setup()?;
+
+        let manga_id = Uuid::new_v4().to_string();
+
+        let chapter_id_not_read = Uuid::new_v4().to_string();
+        let chapter_id_is_read = Uuid::new_v4().to_string();
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_id,
+                title: "some_title",
+                img_url: None,
+            },
+            &conn,
+        )?;
+
+        conn.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1, ?2, ?3)", params![
+            chapter_id_not_read.clone(),
+            "some_title",
+            manga_id.clone(),
+        ])?;
+
+        conn.execute("INSERT INTO chapters(id, is_read, title, manga_id) VALUES(?1, ?2, ?3, ?4)", params![
+            chapter_id_is_read.clone(),
+            true,
+            "some_title",
+            manga_id,
+        ])?;
+
+        let non_existent = database.check_chapter_is_already_reading("non_existent")?;
+        let is_already_reading = database.check_chapter_is_already_reading(&chapter_id_is_read)?;
+        let not_reading = database.check_chapter_is_already_reading(&chapter_id_not_read)?;
+
+        assert!(is_already_reading);
+        assert!(!non_existent);
+        assert!(!not_reading);
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_manga_plan_to_read_which_does_not_exist() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+        let manga_id = Uuid::new_v4().to_string();
+
+        save_plan_to_read(
+            MangaPlanToReadSave {
+                id: &manga_id,
+                title: "some_title",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        let manga_was_saved = check_exists(&manga_id, connection, Table::Mangas)?;
+
+        assert!(manga_was_saved, "manga should have been saved");
+
+        let manga_is_plan_to_read = manga_is_plan_to_read(&manga_id, connection)?;
+
+        assert!(manga_is_plan_to_read, "the manga was not plan to read");
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_already_existing_manga_plan_to_read() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+        let manga_id = Uuid::new_v4().to_string();
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_id,
+                title: "some_title",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        let manga_should_not_be_plan_to_read = manga_is_plan_to_read(&manga_id, connection)?;
+
+        assert!(!manga_should_not_be_plan_to_read);
+
+        save_plan_to_read(
+            MangaPlanToReadSave {
+                id: &manga_id,
+                title: "some_title",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        let manga_should_be_plan_to_read = manga_is_plan_to_read(&manga_id, connection)?;
+
+        assert!(manga_should_be_plan_to_read);
+
+        Ok(())
+    }
+
+    // Both manga and chapter are not in the database
+    #[test]
+    fn save_manga_reading_status_which_does_not_exist() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+
+        let database = Database::new(&connection);
+
+        database.setup()?;
+
+        let manga_id = Uuid::new_v4().to_string();
+        let chapter_id = Uuid::new_v4().to_string();
+
+        save_history(
+            MangaReadingHistorySave {
+                id: &manga_id,
+                title: "some_title",
+                img_url: None,
+                chapter: ChapterToSaveHistory {
+                    id: &chapter_id,
+                    ..Default::default()
+                },
+            },
+            &connection,
+        )?;
+
+        let manga_was_created = check_exists(&manga_id, &connection, Table::Mangas)?;
+        let chapter_was_created = check_exists(&chapter_id, &connection, Table::Chapters)?;
+
+        assert!(manga_was_created);
+
+        assert!(chapter_was_created);
+
+        Ok(())
+    }
+
+    // manga is already in database, chapter isnt
+    #[test]
+    fn save_manga_reading_status_which_already_exists() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+
+        let database = Database::new(&connection);
+
+        database.setup()?;
+
+        let manga_id = Uuid::new_v4().to_string();
+        let chapter_id = Uuid::new_v4().to_string();
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_id,
+                title: "some_title",
+                img_url: None,
+            },
+            &connection,
+        )?;
+
+        save_history(
+            MangaReadingHistorySave {
+                id: &manga_id,
+                title: "some_title",
+                img_url: None,
+                chapter: ChapterToSaveHistory {
+                    id: &chapter_id,
+                    ..Default::default()
+                },
+            },
+            &connection,
+        )?;
+
+        let chapters = get_all_chapters(&connection)?;
+
+        let saved_chapter = chapters
+            .iter()
+            .find(|chap| chap.id == chapter_id)
+            .expect("no chapter was saved as being read");
+
+        assert!(saved_chapter.is_read);
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_manga_reading_both_manga_and_chapter_exist_and_chapter_is_already_reading() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let manga_id = Uuid::new_v4().to_string();
+        let chapter_id = Uuid::new_v4().to_string();
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_id,
+                title: "some_title",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        let chapter_which_is_already_reading = ChapterInsert {
+            id: &chapter_id,
+            title: "some_title",
+            manga_id: &manga_id,
+            is_read: true,
+            is_downloaded: true,
+        };
+
+        insert_chapter(chapter_which_is_already_reading.clone(), connection)?;
+
+        save_history(
+            MangaReadingHistorySave {
+                id: &manga_id,
+                title: chapter_which_is_already_reading.title,
+                img_url: None,
+                chapter: ChapterToSaveHistory {
+                    id: &chapter_id,
+                    ..Default::default()
+                },
+            },
+            connection,
+        )
+        .expect("could not save chapter history");
+
+        let chapters = get_all_chapters(connection)?;
+
+        let saved_chapter = chapters
+            .iter()
+            .find(|chap| chap.id == chapter_id)
+            .expect("no chapter was saved as being read");
+
+        // saving reading status should not have overwritten its donwload status
+        assert!(saved_chapter.is_downloaded);
+        assert!(saved_chapter.is_read);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_chapters_which_have_reading_status() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let manga_id = Uuid::new_v4().to_string();
+        let chapter_id_not_read = Uuid::new_v4().to_string();
+        let chapter_id_read = Uuid::new_v4().to_string();
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_id,
+                title: "some_manga",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        insert_chapter(
+            ChapterInsert {
+                id: &chapter_id_not_read,
+                title: "some_chapter",
+                manga_id: &manga_id,
+                is_read: false,
+                is_downloaded: false,
+            },
+            connection,
+        )?;
+
+        insert_chapter(
+            ChapterInsert {
+                id: &chapter_id_read,
+                title: "some_chapter",
+                manga_id: &manga_id,
+                is_read: true,
+                is_downloaded: false,
+            },
+            connection,
+        )?;
+
+        let chapters = get_chapters_history_status(&manga_id, connection)?;
+
+        assert!(!chapters.is_empty());
+
+        let mut chapters = chapters.into_iter();
+
+        let first_chapter = chapters.next().unwrap();
+
+        assert_eq!(chapter_id_not_read, first_chapter.id);
+        assert!(!first_chapter.is_read);
+
+        let second_chapter = chapters.next().unwrap();
+
+        assert_eq!(chapter_id_read, second_chapter.id);
+        assert!(second_chapter.is_read);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_manga_history_reading_with_no_search_term() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let manga_is_in_reading_history_id = Uuid::new_v4().to_string();
+        let manga_not_in_reading_history_id = Uuid::new_v4().to_string();
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_is_in_reading_history_id,
+                title: "some_title",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        insert_manga_in_reading_history(&manga_is_in_reading_history_id, connection)?;
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_not_in_reading_history_id,
+                title: "some_title",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        let history = get_history(GetHistoryArgs {
+            conn: connection,
+            hist_type: Some(MangaHistoryType::ReadingHistory),
+            page: 1,
+            search: None,
+            tag: None,
+            items_per_page: 100,
+            order: HistoryOrder::default(),
+            in_trash: false,
+        })?;
+
+        assert!(history.total_items > 0);
+
+        assert!(history.mangas.iter().any(|manga| manga.id == manga_is_in_reading_history_id));
+
+        assert!(!history.mangas.iter().any(|manga| manga.id == manga_not_in_reading_history_id));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_manga_history_matches_description_and_genres() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let manga_id = Uuid::new_v4().to_string();
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_id,
+                title: "Dungeon Meshi",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        insert_manga_in_reading_history(&manga_id, connection)?;
+
+        let database = Database::new(connection);
+        database.save_metadata(&manga_id, "A party cooks the basiliskwyrm they defeat", &["Gastronomycore".to_string()])?;
+        database.save_metadata(&manga_id, "A party cooks the monsterwyrm they defeat", &["Gastronomycore".to_string()])?;
+
+        for search in ["monsterwyrm", "gastronomy", "cooks monsterwyrm", "\"gastronomycore*"] {
+            let history = get_history(GetHistoryArgs {
+                conn: connection,
+                hist_type: Some(MangaHistoryType::ReadingHistory),
+                page: 1,
+                search: SearchTerm::trimmed_lowercased(search),
+                tag: None,
+                items_per_page: 100,
+                order: HistoryOrder::default(),
+                in_trash: false,
+            })?;
+
+            assert_eq!(vec![manga_id.clone()], history.mangas.into_iter().map(|manga| manga.id).collect::<Vec<String>>());
+            assert_eq!(1, history.total_items);
+        }
+
+        let history = get_history(GetHistoryArgs {
+            conn: connection,
+            hist_type: Some(MangaHistoryType::ReadingHistory),
+            page: 1,
+            search: SearchTerm::trimmed_lowercased("basiliskwyrm"),
+            tag: None,
+            items_per_page: 100,
+            order: HistoryOrder::default(),
+            in_trash: false,
+        })?;
+
+        assert_eq!(0, history.total_items);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_manga_history_matches_alternative_titles() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let manga_id = Uuid::new_v4().to_string();
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_id,
+                title: "Shingeki no Kyojin",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        insert_manga_in_reading_history(&manga_id, connection)?;
+
+        Database::new(connection).save_alt_titles(&manga_id, &["Attack on Titan".to_string(), "進撃の巨人".to_string()])?;
+
+        for search in ["attack on", "進撃", "kyojin"] {
+            let history = get_history(GetHistoryArgs {
+                conn: connection,
+                hist_type: Some(MangaHistoryType::ReadingHistory),
+                page: 1,
+                search: SearchTerm::trimmed_lowercased(search),
+                tag: None,
+                items_per_page: 100,
+                order: HistoryOrder::default(),
+                in_trash: false,
+            })?;
+
+            assert!(history.mangas.iter().any(|manga| manga.id == manga_id));
+            assert_eq!(1, history.total_items);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn alt_titles_and_metadata_are_only_saved_for_mangas_in_the_library() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+
+        database.setup()?;
+
+        database.save_alt_titles("not_in_library", &["Attack on Titan".to_string()])?;
+        database.save_metadata("not_in_library", "some description", &["Action".to_string()])?;
+
+        let alt_titles: u32 = connection.query_row("SELECT COUNT(*) FROM manga_alt_titles", [], |row| row.get(0))?;
+        let metadata: u32 = connection.query_row("SELECT COUNT(*) FROM manga_metadata", [], |row| row.get(0))?;
+
+        assert_eq!(0, alt_titles);
+        assert_eq!(0, metadata);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_manga_history_reading_with_search_term() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let manga_id_filtered_out = Uuid::new_v4().to_string();
+        let manga_id_included_in_search = Uuid::new_v4().to_string();
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_id_filtered_out,
+                title: "filtered_out",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        insert_manga_in_reading_history(&manga_id_filtered_out, connection)?;
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_id_included_in_search,
+                title: "included",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        insert_manga_in_reading_history(&manga_id_included_in_search, connection)?;
+
+        let history = get_history(GetHistoryArgs {
+            conn: connection,
+            hist_type: Some(MangaHistoryType::ReadingHistory),
+            page: 1,
+            search: SearchTerm::trimmed_lowercased("Included"),
+            tag: None,
+            items_per_page: 100,
+            order: HistoryOrder::default(),
+            in_trash: false,
+        })?;
+
+        assert!(history.total_items > 0);
+
+        assert!(!history.mangas.iter().any(|manga| manga.id == manga_id_filtered_out));
+        assert!(history.mangas.iter().any(|manga| manga.id == manga_id_included_in_search));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_manga_planned_to_read_with_search_term() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let manga_id_filtered_out = Uuid::new_v4().to_string();
+        let manga_id_included_in_search = Uuid::new_v4().to_string();
+
+        let manga_filtered_out = MangaPlanToReadSave {
+            id: &manga_id_filtered_out,
+            title: "filtered_out",
+            img_url: None,
+        };
+
+        let manga_included = MangaPlanToReadSave {
+            id: &manga_id_included_in_search,
+            title: "included",
+            img_url: None,
+        };
+
+        save_plan_to_read(manga_filtered_out, connection)?;
+
+        save_plan_to_read(manga_included, connection)?;
+
+        let history = get_history(GetHistoryArgs {
+            conn: connection,
+            hist_type: Some(MangaHistoryType::PlanToRead),
+            page: 1,
+            search: SearchTerm::trimmed_lowercased("Included"),
+            tag: None,
+            items_per_page: 100,
+            order: HistoryOrder::default(),
+            in_trash: false,
+        })?;
+
+        assert!(history.total_items > 0);
+
+        assert!(!history.mangas.iter().any(|manga| manga.id == manga_id_filtered_out));
+        assert!(history.mangas.iter().any(|manga| manga.id == manga_id_included_in_search));
+
+        Ok(())
+    }
+    #[test]
+    fn get_manga_planned_to_read() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let manga_id_1 = Uuid::new_v4().to_string();
+        let manga_id_2 = Uuid::new_v4().to_string();
+
+        let manga_1 = MangaPlanToReadSave {
+            id: &manga_id_1,
+            title: "manga_1",
+            img_url: None,
+        };
+
+        let manga_2 = MangaPlanToReadSave {
+            id: &manga_id_2,
+            title: "manga_2",
+            img_url: None,
+        };
+
+        save_plan_to_read(manga_1, connection)?;
+
+        save_plan_to_read(manga_2, connection)?;
+
+        let history = get_history(GetHistoryArgs {
+            conn: connection,
+            hist_type: Some(MangaHistoryType::PlanToRead),
+            page: 1,
+            search: None,
+            tag: None,
+            items_per_page: 100,
+            order: HistoryOrder::default(),
+            in_trash: false,
+        })?;
+
+        assert!(history.total_items > 0);
+
+        assert!(history.mangas.iter().any(|manga| manga.id == manga_id_1));
+        assert!(history.mangas.iter().any(|manga| manga.id == manga_id_2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_history_of_both_categories_grouped_by_category() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let search = Uuid::new_v4().simple().to_string();
+        let reading_id = Uuid::new_v4().to_string();
+        let planned_id = Uuid::new_v4().to_string();
+
+        save_plan_to_read(
+            MangaPlanToReadSave {
+                id: &planned_id,
+                title: &format!("planned {search}"),
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        insert_manga(
+            MangaInsert {
+                id: &reading_id,
+                title: &format!("reading {search}"),
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        insert_manga_in_reading_history(&reading_id, connection)?;
+
+        let history = get_history(GetHistoryArgs {
+            conn: connection,
+            hist_type: None,
+            page: 1,
+            search: SearchTerm::trimmed_lowercased(&search),
+            tag: None,
+            items_per_page: 100,
+            order: HistoryOrder::default(),
+            in_trash: false,
+        })?;
+
+        assert_eq!(2, history.total_items);
+        assert_eq!(
+            vec![
+                MangaHistory {
+                    id: reading_id,
+                    title: format!("reading {search}"),
+                    category: Some(MangaHistoryType::ReadingHistory),
+                    personal_rating: None,
+                },
+                MangaHistory {
+                    id: planned_id,
+                    title: format!("planned {search}"),
+                    category: Some(MangaHistoryType::PlanToRead),
+                    personal_rating: None,
+                },
+            ],
+            history.mangas
+        );
+
+        Ok(())
+    }
+
+    // Test the case when a manga is not in the database and a chapters is not in the database
+    // either
+    #[test]
+    fn save_chapter_download_status_manga_doesnt_exist_and_chapter_doesnt_exist() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let chapter_id = Uuid::new_v4().to_string();
+        let manga_id = Uuid::new_v4().to_string();
+
+        set_chapter_downloaded(
+            SetChapterDownloaded {
+                id: &chapter_id,
+                title: "some_title",
+                manga_id: &manga_id,
+                manga_title: "some_title",
+                img_url: None,
+                download: &ChapterDownloaded {
+                    path: PathBuf::from("./test_results/chapter.cbz"),
+                    format: DownloadType::Cbz,
+                    bytes: 10,
+                },
+            },
+            connection,
+        )?;
+
+        assert!(check_exists(&manga_id, connection, Table::Mangas)?);
+
+        let chapters = get_all_chapters(connection)?;
+
+        let chapter_downloaded = chapters
+            .iter()
+            .find(|chap| chap.id == chapter_id)
+            .expect("chapter downloaded was nost found");
+
+        assert!(chapter_downloaded.is_read);
+        assert!(chapter_downloaded.is_downloaded);
+
+        Ok(())
+    }
+
+    // Test the case when both manga and chapter already exist in database
+    #[test]
+    fn save_chapter_download_status_manga_and_chapter_exists() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let chapter_id_exist_in_database = Uuid::new_v4().to_string();
+        let manga_id_exist_in_database = Uuid::new_v4().to_string();
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_id_exist_in_database,
+                title: "some_title",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        insert_chapter(
+            ChapterInsert {
+                id: &chapter_id_exist_in_database,
+                title: "some_title",
+                manga_id: &manga_id_exist_in_database,
+                is_read: false,
+                is_downloaded: false,
+            },
+            connection,
+        )?;
+
+        set_chapter_downloaded(
+            SetChapterDownloaded {
+                id: &chapter_id_exist_in_database,
+                title: "some_title",
+                manga_id: &manga_id_exist_in_database,
+                manga_title: "some_title",
+                img_url: None,
+                download: &ChapterDownloaded {
+                    path: PathBuf::from("./test_results/chapter.cbz"),
+                    format: DownloadType::Cbz,
+                    bytes: 10,
+                },
+            },
+            connection,
+        )?;
+
+        let chapters = get_all_chapters(connection)?;
+
+        let chapter_downloaded = chapters
+            .iter()
+            .find(|chap| chap.id == chapter_id_exist_in_database)
+            .expect("chapter downloaded was nost found");
+
+        assert!(chapter_downloaded.is_read);
+        assert!(chapter_downloaded.is_downloaded);
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_chapter_download_status_manga_exist_and_chapter_doesnt_exist() -> Result<()> {
+        let binding = DBCONN.lock().expect("could not get db conn");
+        let connection = binding.as_ref().unwrap();
+
+        let chapter_id = Uuid::new_v4().to_string();
+        let manga_id = Uuid::new_v4().to_string();
+
+        insert_manga(
+            MangaInsert {
+                id: &manga_id,
+                title: "some_title",
+                img_url: None,
+            },
+            connection,
+        )?;
+
+        set_chapter_downloaded(
+            SetChapterDownloaded {
+                id: &chapter_id,
+                title: "some_title",
+                manga_id: &manga_id,
+                manga_title: "some_title",
+                img_url: None,
+                download: &ChapterDownloaded {
+                    path: PathBuf::from("./test_results/chapter.cbz"),
+                    format: DownloadType::Cbz,
+                    bytes: 10,
+                },
+            },
+            connection,
+        )?;
+
+        let chapters = get_all_chapters(connection)?;
+
+        let chapter_downloaded = chapters
+            .iter()
+            .find(|chap| chap.id == chapter_id)
+            .expect("chapter downloaded was nost found");
+
+        assert!(chapter_downloaded.is_read);
+        assert!(chapter_downloaded.is_downloaded);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_keeps_one_download_per_chapter_with_its_path_format_and_size() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+
+        database.setup()?;
+
+        let chapter_id = Uuid::new_v4().to_string();
+        let manga_id = Uuid::new_v4().to_string();
+
+        let first_download = ChapterDownloaded {
+            path: PathBuf::from("./test_results/chapter.cbz"),
+            format: DownloadType::Cbz,
+            bytes: 10,
+        };
+
+        let second_download = ChapterDownloaded {
+            path: PathBuf::from("./test_results/chapter.epub"),
+            format: DownloadType::Epub,
+            bytes: 20,
+        };
+
+        for download in [&first_download, &second_download] {
+            set_chapter_downloaded(
+                SetChapterDownloaded {
+                    id: &chapter_id,
+                    title: "some_title",
+                    manga_id: &manga_id,
+                    manga_title: "some_title",
+                    img_url: None,
+                    download,
+                },
+                &connection,
+            )?;
+        }
+
+        let downloads: Vec<(String, String, String, u64)> = connection
+            .prepare("SELECT chapter_id, path, format, bytes FROM downloads")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+            .collect::<Result<_>>()?;
+
+        assert_eq!(vec![(chapter_id.clone(), "./test_results/chapter.epub".to_string(), "Epub".to_string(), 20)], downloads);
+
+        connection.execute("UPDATE chapters SET is_downloaded = false WHERE id = ?1", [&chapter_id])?;
+
+        let status = get_chapters_history_status(&manga_id, &connection)?;
+
+        assert!(status.iter().any(|chapter| chapter.id == chapter_id && chapter.is_downloaded));
+
+        Ok(())
+    }
+
+    #[test]
+    fn chapters_history_status_includes_where_the_chapter_was_downloaded() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+
+        database.setup()?;
+
+        let chapter_id = Uuid::new_v4().to_string();
+        let manga_id = Uuid::new_v4().to_string();
+
+        let download = ChapterDownloaded {
+            path: PathBuf::from("./test_results/manga/chapter.cbz"),
+            format: DownloadType::Cbz,
+            bytes: 10,
+        };
+
+        set_chapter_downloaded(
+            SetChapterDownloaded {
+                id: &chapter_id,
+                title: "some_title",
+                manga_id: &manga_id,
+                manga_title: "some_title",
+                img_url: None,
+                download: &download,
+            },
+            &connection,
+        )?;
+
+        let status = get_chapters_history_status(&manga_id, &connection)?;
+        let chapter = status.iter().find(|chapter| chapter.id == chapter_id).expect("chapter was not found");
+
+        assert_eq!(Some(download), chapter.download);
+        assert_eq!(Path::new("./test_results/manga"), chapter.download.as_ref().unwrap().location());
+
+        let raw_download = ChapterDownloaded {
+            path: PathBuf::from("./test_results/manga/chapter"),
+            format: DownloadType::Raw,
+            bytes: 10,
+        };
+
+        assert_eq!(Path::new("./test_results/manga/chapter"), raw_download.location());
+
+        Ok(())
+    }
+
+    #[test]
+    fn chapter_is_not_flagged_as_downloaded_if_the_download_could_not_be_saved() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+
+        database.setup()?;
+
+        connection.execute("DROP TABLE downloads", [])?;
+
+        let chapter_id = Uuid::new_v4().to_string();
+        let manga_id = Uuid::new_v4().to_string();
+
+        let result = set_chapter_downloaded(
+            SetChapterDownloaded {
+                id: &chapter_id,
+                title: "some_title",
+                manga_id: &manga_id,
+                manga_title: "some_title",
+                img_url: None,
+                download: &ChapterDownloaded {
+                    path: PathBuf::from("./test_results/manga/chapter.cbz"),
+                    format: DownloadType::Cbz,
+                    bytes: 10,
+                },
+            },
+            &connection,
+        );
+
+        assert!(result.is_err());
+        assert!(!check_exists(&manga_id, &connection, Table::Mangas)?);
+        assert!(!check_exists(&chapter_id, &connection, Table::Chapters)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn database_bookmarks_chapter() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let mut database = Database::new(&connection);
+
+        database.setup()?;
+
+        let chapter_id = Uuid::new_v4().to_string();
+        let manga_id = Uuid::new_v4().to_string();
+
+        connection.execute("INSERT INTO mangas(id, title) VALUES(?1,?2)", params![manga_id.clone(), "some_title"])?;
+
+        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1,?2,?3)", params![
+            chapter_id.clone(),
+            "some_title",
+            manga_id
+        ])?;
+
+        let chapter_to_bookmark1: ChapterToBookmark = ChapterToBookmark {
+            chapter_id: &chapter_id,
+            manga_id: &manga_id,
+            page_number: Some(3),
+            ..Default::default()
+        };
+
+        database.bookmark(chapter_to_bookmark1).expect("failed to bookmark chapter");
+
+        let was_bookmarked: bool =
+            connection.query_row("SELECT is_bookmarked FROM chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))?;
+
+        let page_set: Option<u32> =
+            connection
+                .query_row("SELECT number_page_bookmarked FROM chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))?;
+
+        assert!(was_bookmarked);
+        assert_eq!(page_set.expect("should not be null"), 3);
+
+        let chapter_to_bookmark1: ChapterToBookmark = ChapterToBookmark {
+            chapter_id: &chapter_id,
+            manga_id: &manga_id,
+            page_number: None,
+            ..Default::default()
+        };
+
+        database.bookmark(chapter_to_bookmark1).expect("failed to bookmark chapter");
+
+        let page_set_to_none: Option<u32> =
+            connection
+                .query_row("SELECT number_page_bookmarked FROM chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))?;
+
+        assert!(page_set_to_none.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn database_only_bookmarks_one_chapter_at_a_time_per_manga() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let mut database = Database::new(&connection);
+
+        database.setup()?;
+
+        let chapter_id = Uuid::new_v4().to_string();
+        let chapter_id_2 = Uuid::new_v4().to_string();
+        let chapter_id_should_stay_bookmarked = Uuid::new_v4().to_string();
+        let manga_id = Uuid::new_v4().to_string();
+        let manga_id_2 = Uuid::new_v4().to_string();
+
+        connection.execute("INSERT INTO mangas(id, title) VALUES(?1,?2)", params![manga_id.clone(), "some_title"])?;
+        connection.execute("INSERT INTO mangas(id, title) VALUES(?1,?2)", params![manga_id_2.clone(), "some_title2"])?;
+
+        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1,?2,?3)", params![
+            chapter_id.clone(),
+            "some_title",
+            manga_id
+        ])?;
+
+        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1,?2,?3)", params![
+            chapter_id_2.clone(),
+            "some_title",
+            manga_id
+        ])?;
+
+        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1,?2,?3)", params![
+            chapter_id_should_stay_bookmarked.clone(),
+            "some_title",
+            manga_id_2
+        ])?;
+
+        let chapter_to_bookmark1: ChapterToBookmark = ChapterToBookmark {
+            chapter_id: &chapter_id,
+            manga_id: &manga_id,
+            page_number: None,
+            ..Default::default()
+        };
+
+        database.bookmark_chapter(chapter_to_bookmark1).expect("failed to bookmark chapter1");
+
+        let chapter_to_bookmark_should_stay_bookmarked: ChapterToBookmark = ChapterToBookmark {
+            chapter_id: &chapter_id_should_stay_bookmarked,
+            manga_id: &manga_id_2,
+            page_number: None,
+            ..Default::default()
+        };
+
+        database
+            .bookmark_chapter(chapter_to_bookmark_should_stay_bookmarked)
+            .expect("failed to bookmark chapter_id_should_stay_bookmarked");
+
+        let chapter_to_bookmark2 = ChapterToBookmark {
+            chapter_id: &chapter_id_2,
+            manga_id: &manga_id,
+            page_number: None,
+            ..Default::default()
+        };
+
+        database.bookmark_chapter(chapter_to_bookmark2).expect("failed to bookmark chapter2");
+
+        let was_bookmarked_1: bool =
+            connection.query_row("SELECT is_bookmarked FROM chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))?;
+
+        let was_bookmarked_2: bool =
+            connection.query_row("SELECT is_bookmarked FROM chapters WHERE id = ?1", params![chapter_id_2], |row| row.get(0))?;
+
+        let should_stay_bookmarked: bool = connection.query_row(
+            "SELECT is_bookmarked FROM chapters WHERE id = ?1",
+            params![chapter_id_should_stay_bookmarked],
+            |row| row.get(0),
+        )?;
+
+        assert!(!was_bookmarked_1);
+        assert!(was_bookmarked_2);
+        assert!(should_stay_bookmarked);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_inserts_manga_and_chapter_if_it_does_not_exists() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+
+        database.setup()?;
+
+        let id_manga = Uuid::new_v4().to_string();
+        let chapter_id = Uuid::new_v4().to_string();
+
+        database.create_manga_if_not_exists(MangaInsert {
+            id: &id_manga,
+            title: "some_title",
+            img_url: None,
+        })?;
+
+        let id_was_created: String = connection
+            .query_row("SELECT id from mangas WHERE id = ?1", params![id_manga], |row| row.get(0))
+            .expect("manga was not created");
+
+        assert_eq!(id_was_created, id_manga);
+
+        database
+            .create_manga_if_not_exists(MangaInsert {
+                id: &id_manga,
                 title: "some_title",
                 img_url: None,
-            },
-            connection,
-        )?;
+            })
+            .expect("should not try to create already existing manga");
+
+        database
+            .create_chapter_if_not_exists(ChapterToInsert {
+                id: &chapter_id,
+                title: "some_title",
+                manga_id: &id_manga,
+                ..Default::default()
+            })
+            .expect("should create chapter");
+
+        let id_chapter_was_created: String = connection
+            .query_row("SELECT id from chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))
+            .expect("chapter was not created");
+
+        assert_eq!(chapter_id, id_chapter_was_created);
+
+        database
+            .create_chapter_if_not_exists(ChapterToInsert {
+                id: &chapter_id,
+                title: "some_title",
+                manga_id: &id_manga,
+                ..Default::default()
+            })
+            .expect("should try to create chapter already existing");
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_bookmarks_chapter_if_it_does_not_exits_in_database() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let mut database = Database::new(&connection);
+
+        database.setup()?;
+
+        let chapter_id = Uuid::new_v4().to_string();
+        let manga_id = Uuid::new_v4().to_string();
+
+        let chapter_to_bookmark = ChapterToBookmark {
+            chapter_id: &chapter_id,
+            manga_id: &manga_id,
+            page_number: None,
+            ..Default::default()
+        };
+
+        database.bookmark_chapter(chapter_to_bookmark).expect("failed to bookmark chapter");
+
+        let was_bookmarked: bool = connection
+            .query_row("SELECT is_bookmarked FROM chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))
+            .expect("chapter was not created");
+
+        assert!(was_bookmarked);
+
+        Ok(())
+    }
+
+    #[test]
+    fn database_gets_chapter_bookmarked() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+
+        database.setup()?;
+
+        let chapter_id = Uuid::new_v4().to_string();
+        let manga_id = Uuid::new_v4().to_string();
+
+        connection.execute("INSERT INTO mangas(id, title) VALUES(?1, ?2)", params![manga_id.clone(), "some_title"])?;
+
+        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1,?2,?3)", params![
+            chapter_id.clone(),
+            "some_title",
+            manga_id
+        ])?;
+
+        let expected: ChapterBookmarked = ChapterBookmarked {
+            id: "bookmarked".to_string(),
+            translated_language: Some("en".to_string()),
+            number_page_bookmarked: Some(2),
+            manga_id: manga_id.clone(),
+            manga_title: "some_title".to_string(),
+        };
 
-        insert_chapter(
-            ChapterInsert {
-                id: &chapter_id,
-                title: "some_title",
-                manga_id: &manga_id,
-                is_read: true,
-                is_downloaded: false,
-            },
-            connection,
+        connection.execute(
+            "INSERT INTO chapters(id, title, manga_id, translated_language, number_page_bookmarked, is_bookmarked) VALUES(?1,?2,?3,?4,?5,?6)",
+            params![expected.id, "some_title", manga_id, expected.translated_language, expected.number_page_bookmarked, true],
         )?;
 
+        let result = database.get_bookmarked(&manga_id).expect("should be ok").expect("should not be none");
+
+        assert_eq!(expected, result);
+
         Ok(())
     }
 
     #[test]
-    fn check_chapter_is_already_reading() -> Result<()> {
-        let conn = Connection::open_in_memory()?;
-
-        let database = Database::new(&conn);
+    fn database_saves_and_clears_reading_session() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
         database.setup()?;
 
-        let manga_id = Uuid::new_v4().to_string();
+        assert!(database.get_reading_session()?.is_none());
 
-        let chapter_id_not_read = Uuid::new_v4().to_string();
-        let chapter_id_is_read = Uuid::new_v4().to_string();
+        let mut session = ChapterBookmarked {
+            id: Uuid::new_v4().to_string(),
+            translated_language: Some("en".to_string()),
+            number_page_bookmarked: Some(3),
+            manga_id: Uuid::new_v4().to_string(),
+            manga_title: "some_title".to_string(),
+        };
 
-        insert_manga(
-            MangaInsert {
-                id: &manga_id,
-                title: "some_title",
-                img_url: None,
-            },
-            &conn,
-        )?;
+        database.save_reading_session(&session)?;
 
-        conn.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1, ?2, ?3)", params![
-            chapter_id_not_read.clone(),
-            "some_title",
-            manga_id.clone(),
-        ])?;
+        session.number_page_bookmarked = Some(4);
 
-        conn.execute("INSERT INTO chapters(id, is_read, title, manga_id) VALUES(?1, ?2, ?3, ?4)", params![
-            chapter_id_is_read.clone(),
-            true,
-            "some_title",
-            manga_id,
-        ])?;
+        database.save_reading_session(&session)?;
 
-        let non_existent = database.check_chapter_is_already_reading("non_existent")?;
-        let is_already_reading = database.check_chapter_is_already_reading(&chapter_id_is_read)?;
-        let not_reading = database.check_chapter_is_already_reading(&chapter_id_not_read)?;
+        let amount_sessions: i32 =
+            connection.query_row(format!("SELECT COUNT(*) from {}", Table::ReadingSession).as_str(), [], |row| row.get(0))?;
 
-        assert!(is_already_reading);
-        assert!(!non_existent);
-        assert!(!not_reading);
+        assert_eq!(1, amount_sessions);
+        assert_eq!(Some(session), database.get_reading_session()?);
+
+        database.clear_reading_session()?;
+
+        assert!(database.get_reading_session()?.is_none());
 
         Ok(())
     }
 
     #[test]
-    fn save_manga_plan_to_read_which_does_not_exist() -> Result<()> {
-        let binding = DBCONN.lock().expect("could not get db conn");
-        let connection = binding.as_ref().unwrap();
-        let manga_id = Uuid::new_v4().to_string();
+    fn database_saves_download_all_job_and_the_chapters_already_downloaded() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
-        save_plan_to_read(
-            MangaPlanToReadSave {
-                id: &manga_id,
+        database.setup()?;
+
+        assert!(database.get_download_all_job()?.is_none());
+
+        let job = DownloadAllJob {
+            manga_id: Uuid::new_v4().to_string(),
+            manga_title: "some_title".to_string(),
+            translated_language: "en".to_string(),
+        };
+
+        database.save_download_all_job(&job)?;
+        database.save_download_all_job(&job)?;
+
+        assert_eq!(Some(job.clone()), database.get_download_all_job()?);
+
+        let chapter_id = Uuid::new_v4().to_string();
+
+        set_chapter_downloaded(
+            SetChapterDownloaded {
+                id: &chapter_id,
                 title: "some_title",
+                manga_id: &job.manga_id,
+                manga_title: &job.manga_title,
                 img_url: None,
+                download: &ChapterDownloaded {
+                    path: PathBuf::from("./test_results/chapter.cbz"),
+                    format: DownloadType::Cbz,
+                    bytes: 10,
+                },
             },
-            connection,
+            &connection,
         )?;
 
-        let manga_was_saved = check_exists(&manga_id, connection, Table::Mangas)?;
-
-        assert!(manga_was_saved, "manga should have been saved");
+        assert_eq!(vec![chapter_id], database.get_downloaded_chapter_ids(&job.manga_id)?);
 
-        let manga_is_plan_to_read = manga_is_plan_to_read(&manga_id, connection)?;
+        database.clear_download_all_job()?;
 
-        assert!(manga_is_plan_to_read, "the manga was not plan to read");
+        assert!(database.get_download_all_job()?.is_none());
 
         Ok(())
     }
 
     #[test]
-    fn save_already_existing_manga_plan_to_read() -> Result<()> {
-        let binding = DBCONN.lock().expect("could not get db conn");
-        let connection = binding.as_ref().unwrap();
-        let manga_id = Uuid::new_v4().to_string();
-
-        insert_manga(
-            MangaInsert {
-                id: &manga_id,
-                title: "some_title",
-                img_url: None,
-            },
-            connection,
-        )?;
-
-        let manga_should_not_be_plan_to_read = manga_is_plan_to_read(&manga_id, connection)?;
+    fn database_summarizes_chapters_read_and_downloaded() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
-        assert!(!manga_should_not_be_plan_to_read);
+        database.setup()?;
 
-        save_plan_to_read(
-            MangaPlanToReadSave {
-                id: &manga_id,
-                title: "some_title",
-                img_url: None,
-            },
-            connection,
+        connection.execute("INSERT INTO mangas(id, title) VALUES ('manga_1', 'Most read'), ('manga_2', 'Less read')", [])?;
+        connection.execute(
+            "INSERT INTO chapters(id, title, manga_id, is_read, translated_language, read_at) VALUES
+             ('chapter_1', '', 'manga_1', true, 'es', '2024-02-10 10:00:00'), ('chapter_2', '', 'manga_1', true, 'en', '2024-02-10 22:00:00'),
+             ('chapter_3', '', 'manga_1', false, 'es', NULL), ('chapter_4', '', 'manga_2', true, 'es', '2023-12-31 10:00:00')",
+            [],
+        )?;
+        database.add_reading_time("chapter_1", 90)?;
+        database.add_reading_time("chapter_1", 30)?;
+        database.add_reading_time("chapter_4", 60)?;
+        connection.execute(
+            "INSERT INTO downloads(chapter_id, path, format, bytes, finished_at) VALUES
+             ('chapter_1', '', 'Cbz', 10, '2024-03-01 10:00:00'), ('chapter_2', '', 'Cbz', 20, '2024-03-01 11:00:00'),
+             ('chapter_3', '', 'Cbz', 30, '2024-05-02 10:00:00'), ('chapter_4', '', 'Cbz', 40, '2023-01-01 10:00:00')",
+            [],
         )?;
 
-        let manga_should_be_plan_to_read = manga_is_plan_to_read(&manga_id, connection)?;
+        let summary = database.get_reading_summary(2024)?;
+
+        assert_eq!(
+            ReadingSummary {
+                year: 2024,
+                chapters_read: 3,
+                mangas_read: 2,
+                most_read: vec![("Most read".to_string(), 2), ("Less read".to_string(), 1)],
+                chapters_read_by_language: vec![("es".to_string(), 2), ("en".to_string(), 1)],
+                seconds_read: 180,
+                chapters_downloaded: 3,
+                bytes_downloaded: 60,
+                busiest_download_day: Some(("2024-03-01".to_string(), 2)),
+                chapters_read_by_day: vec![("2024-02-10".to_string(), 2)],
+            },
+            summary
+        );
 
-        assert!(manga_should_be_plan_to_read);
+        assert!(database.get_reading_summary(2020)?.busiest_download_day.is_none());
 
         Ok(())
     }
 
-    // Both manga and chapter are not in the database
     #[test]
-    fn save_manga_reading_status_which_does_not_exist() -> Result<()> {
+    fn database_counts_the_chapters_read_since_a_date() -> Result<()> {
         let connection = Connection::open_in_memory()?;
-
         let database = Database::new(&connection);
 
         database.setup()?;
 
-        let manga_id = Uuid::new_v4().to_string();
-        let chapter_id = Uuid::new_v4().to_string();
+        connection.execute("INSERT INTO mangas(id, title) VALUES ('manga_1', 'some_title')", [])?;
+        connection.execute(
+            "INSERT INTO chapters(id, title, manga_id, is_read, read_at) VALUES
+             ('chapter_1', '', 'manga_1', true, '2024-08-10 22:00:00'), ('chapter_2', '', 'manga_1', true, '2024-08-12 00:10:00'),
+             ('chapter_3', '', 'manga_1', true, NULL)",
+            [],
+        )?;
+
+        let monday = NaiveDate::from_ymd_opt(2024, 8, 12).unwrap();
+
+        assert_eq!(1, database.chapters_read_since(monday)?);
 
         save_history(
             MangaReadingHistorySave {
-                id: &manga_id,
+                id: "manga_1",
                 title: "some_title",
                 img_url: None,
                 chapter: ChapterToSaveHistory {
-                    id: &chapter_id,
+                    id: "chapter_4",
                     ..Default::default()
                 },
             },
             &connection,
         )?;
 
-        let manga_was_created = check_exists(&manga_id, &connection, Table::Mangas)?;
-        let chapter_was_created = check_exists(&chapter_id, &connection, Table::Chapters)?;
+        assert_eq!(2, database.chapters_read_since(monday)?);
 
-        assert!(manga_was_created);
+        database.set_chapter_unread("chapter_2")?;
 
-        assert!(chapter_was_created);
+        assert_eq!(1, database.chapters_read_since(monday)?);
 
         Ok(())
     }
 
-    // manga is already in database, chapter isnt
     #[test]
-    fn save_manga_reading_status_which_already_exists() -> Result<()> {
+    fn database_follows_and_unfollows_authors() -> Result<()> {
         let connection = Connection::open_in_memory()?;
-
         let database = Database::new(&connection);
 
         database.setup()?;
 
-        let manga_id = Uuid::new_v4().to_string();
-        let chapter_id = Uuid::new_v4().to_string();
-
-        insert_manga(
-            MangaInsert {
-                id: &manga_id,
-                title: "some_title",
-                img_url: None,
-            },
-            &connection,
-        )?;
+        assert!(database.toggle_followed_author("author_1", "Some author")?);
+        assert!(database.is_following_author("author_1")?);
+        assert_eq!(vec![("author_1".to_string(), "Some author".to_string())], database.get_followed_authors()?);
 
-        save_history(
-            MangaReadingHistorySave {
-                id: &manga_id,
-                title: "some_title",
-                img_url: None,
-                chapter: ChapterToSaveHistory {
-                    id: &chapter_id,
-                    ..Default::default()
-                },
-            },
-            &connection,
-        )?;
+        assert_eq!(None, database.get_known_mangas_of_author("author_1")?);
 
-        let chapters = get_all_chapters(&connection)?;
+        database.save_known_mangas_of_author("author_1", &["manga_1".to_string(), "manga_2".to_string()])?;
+        database.save_known_mangas_of_author("author_1", &["manga_2".to_string()])?;
 
-        let saved_chapter = chapters
-            .iter()
-            .find(|chap| chap.id == chapter_id)
-            .expect("no chapter was saved as being read");
+        assert_eq!(Some(2), database.get_known_mangas_of_author("author_1")?.map(|mangas| mangas.len()));
 
-        assert!(saved_chapter.is_read);
+        assert!(!database.toggle_followed_author("author_1", "Some author")?);
+        assert!(!database.is_following_author("author_1")?);
+        assert!(database.get_followed_authors()?.is_empty());
+        assert_eq!(None, database.get_known_mangas_of_author("author_1")?);
 
         Ok(())
     }
 
     #[test]
-    fn save_manga_reading_both_manga_and_chapter_exist_and_chapter_is_already_reading() -> Result<()> {
-        let binding = DBCONN.lock().expect("could not get db conn");
-        let connection = binding.as_ref().unwrap();
+    fn database_keeps_the_last_page_reached_of_every_chapter() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+
+        database.setup()?;
 
         let manga_id = Uuid::new_v4().to_string();
         let chapter_id = Uuid::new_v4().to_string();
 
-        insert_manga(
-            MangaInsert {
-                id: &manga_id,
-                title: "some_title",
-                img_url: None,
+        connection.execute("INSERT INTO mangas(id, title) VALUES(?1, ?2)", params![manga_id, "some_title"])?;
+        connection
+            .execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1, ?2, ?3)", params![chapter_id, "some_title", manga_id])?;
+
+        assert_eq!(None, database.get_chapter_progress(&chapter_id)?);
+
+        database.save_chapter_progress(
+            &chapter_id,
+            ChapterProgress {
+                last_page: 5,
+                total_pages: 45,
+            },
+        )?;
+        database.save_chapter_progress(
+            &chapter_id,
+            ChapterProgress {
+                last_page: 11,
+                total_pages: 45,
             },
-            connection,
         )?;
 
-        let chapter_which_is_already_reading = ChapterInsert {
-            id: &chapter_id,
-            title: "some_title",
-            manga_id: &manga_id,
-            is_read: true,
-            is_downloaded: true,
-        };
+        let progress = database.get_chapter_progress(&chapter_id)?.expect("progress was not saved");
 
-        insert_chapter(chapter_which_is_already_reading.clone(), connection)?;
+        assert_eq!("12/45 pages", progress.to_string());
+        assert_eq!(Some(11), progress.page_to_resume());
 
-        save_history(
-            MangaReadingHistorySave {
-                id: &manga_id,
-                title: chapter_which_is_already_reading.title,
-                img_url: None,
-                chapter: ChapterToSaveHistory {
-                    id: &chapter_id,
-                    ..Default::default()
-                },
-            },
-            connection,
-        )
-        .expect("could not save chapter history");
+        let status = get_chapters_history_status(&manga_id, &connection)?;
 
-        let chapters = get_all_chapters(connection)?;
+        assert_eq!(Some(progress), status[0].progress);
 
-        let saved_chapter = chapters
-            .iter()
-            .find(|chap| chap.id == chapter_id)
-            .expect("no chapter was saved as being read");
+        let finished = ChapterProgress {
+            last_page: 44,
+            total_pages: 45,
+        };
 
-        // saving reading status should not have overwritten its donwload status
-        assert!(saved_chapter.is_downloaded);
-        assert!(saved_chapter.is_read);
+        assert!(finished.is_finished());
+        assert_eq!(None, finished.page_to_resume());
 
         Ok(())
     }
 
     #[test]
-    fn get_chapters_which_have_reading_status() -> Result<()> {
-        let binding = DBCONN.lock().expect("could not get db conn");
-        let connection = binding.as_ref().unwrap();
+    fn database_gets_where_to_continue_reading_the_mangas_read_last() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
-        let manga_id = Uuid::new_v4().to_string();
-        let chapter_id_not_read = Uuid::new_v4().to_string();
-        let chapter_id_read = Uuid::new_v4().to_string();
+        database.setup()?;
 
-        insert_manga(
-            MangaInsert {
-                id: &manga_id,
-                title: "some_manga",
-                img_url: None,
-            },
-            connection,
-        )?;
+        let read = |manga_id: &str, chapter_id: &str| {
+            save_history(
+                MangaReadingHistorySave {
+                    id: manga_id,
+                    title: manga_id,
+                    img_url: None,
+                    chapter: ChapterToSaveHistory {
+                        id: chapter_id,
+                        title: chapter_id,
+                        translated_language: "en",
+                    },
+                },
+                &connection,
+            )
+        };
 
-        insert_chapter(
-            ChapterInsert {
-                id: &chapter_id_not_read,
-                title: "some_chapter",
-                manga_id: &manga_id,
-                is_read: false,
-                is_downloaded: false,
+        read("manga_with_progress", "chapter_1")?;
+        read("manga_with_progress", "chapter_2")?;
+        read("manga_bookmarked", "chapter_bookmarked")?;
+        read("manga_never_opened_in_reader", "chapter_3")?;
+
+        database.save_chapter_progress(
+            "chapter_1",
+            ChapterProgress {
+                last_page: 20,
+                total_pages: 21,
             },
-            connection,
         )?;
-
-        insert_chapter(
-            ChapterInsert {
-                id: &chapter_id_read,
-                title: "some_chapter",
-                manga_id: &manga_id,
-                is_read: true,
-                is_downloaded: false,
+        database.save_chapter_progress(
+            "chapter_2",
+            ChapterProgress {
+                last_page: 3,
+                total_pages: 21,
             },
-            connection,
         )?;
+        connection.execute("UPDATE chapter_progress SET updated_at = '2024-01-01' WHERE chapter_id = 'chapter_1'", [])?;
+        connection
+            .execute("UPDATE chapters SET is_bookmarked = true, number_page_bookmarked = 7 WHERE id = 'chapter_bookmarked'", [])?;
+
+        connection.execute("UPDATE mangas SET last_read = '2024-01-02' WHERE id = 'manga_with_progress'", [])?;
+        connection.execute("UPDATE mangas SET last_read = '2024-01-03' WHERE id = 'manga_bookmarked'", [])?;
+
+        let continue_reading = database.get_continue_reading(5)?;
+
+        let chapters: Vec<(&str, &str, Option<u32>)> = continue_reading
+            .iter()
+            .map(|item| (item.chapter.manga_id.as_str(), item.chapter.id.as_str(), item.page_to_resume()))
+            .collect();
+
+        assert_eq!(
+            vec![("manga_bookmarked", "chapter_bookmarked", Some(7)), ("manga_with_progress", "chapter_2", Some(3))],
+            chapters
+        );
+
+        assert_eq!(1, database.get_continue_reading(1)?.len());
 
-        let chapters = get_chapters_history_status(&manga_id, connection)?;
+        database.save_total_chapters("manga_with_progress", 40)?;
+        database.save_total_chapters("manga_not_in_library", 10)?;
 
-        assert!(!chapters.is_empty());
+        let progress: Vec<(u32, Option<u32>)> = database
+            .get_continue_reading(5)?
+            .iter()
+            .map(|item| (item.chapters_read, item.total_chapters))
+            .collect();
 
-        let mut chapters = chapters.into_iter();
+        assert_eq!(vec![(1, None), (2, Some(40))], progress);
+        assert!(!check_exists("manga_not_in_library", &connection, Table::Mangas)?);
 
-        let first_chapter = chapters.next().unwrap();
+        Ok(())
+    }
 
-        assert_eq!(chapter_id_not_read, first_chapter.id);
-        assert!(!first_chapter.is_read);
+    #[test]
+    fn database_marks_chapters_as_read() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let mut database = Database::new(&connection);
 
-        let second_chapter = chapters.next().unwrap();
+        database.setup()?;
 
-        assert_eq!(chapter_id_read, second_chapter.id);
-        assert!(second_chapter.is_read);
+        let manga_id = Uuid::new_v4().to_string();
+        let already_saved_chapter = Uuid::new_v4().to_string();
+        let new_chapter = Uuid::new_v4().to_string();
+
+        connection.execute("INSERT INTO mangas(id, title) VALUES(?1, ?2)", params![manga_id, "some_title"])?;
+
+        connection.execute(
+            "INSERT INTO chapters(id, title, manga_id) VALUES(?1,?2,?3)",
+            params![already_saved_chapter, "some_title", manga_id],
+        )?;
+
+        database
+            .mark_as_read(
+                MangaInsert {
+                    id: &manga_id,
+                    title: "some_title",
+                    img_url: None,
+                },
+                &[
+                    ChapterToSaveHistory {
+                        id: &already_saved_chapter,
+                        title: "some_title",
+                        translated_language: "en",
+                    },
+                    ChapterToSaveHistory {
+                        id: &new_chapter,
+                        title: "some_title",
+                        translated_language: "en",
+                    },
+                ],
+            )
+            .expect("should mark chapters as read");
+
+        let chapters = get_chapters_history_status(&manga_id, &connection)?;
+
+        assert_eq!(2, chapters.len());
+        assert!(chapters.iter().all(|chapter| chapter.is_read));
+        assert!(manga_is_reading(&manga_id, &connection)?);
+
+        database.mark_as_unread(&new_chapter).expect("should mark chapter as unread");
+
+        assert!(!database.check_chapter_is_already_reading(&new_chapter)?);
+        assert!(database.check_chapter_is_already_reading(&already_saved_chapter)?);
+        assert!(manga_is_reading(&manga_id, &connection)?);
 
         Ok(())
     }
 
     #[test]
-    fn get_manga_history_reading_with_no_search_term() -> Result<()> {
-        let binding = DBCONN.lock().expect("could not get db conn");
-        let connection = binding.as_ref().unwrap();
+    fn database_binds_a_tracker_entry_to_a_manga() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
-        let manga_is_in_reading_history_id = Uuid::new_v4().to_string();
-        let manga_not_in_reading_history_id = Uuid::new_v4().to_string();
+        database.setup()?;
 
-        insert_manga(
-            MangaInsert {
-                id: &manga_is_in_reading_history_id,
-                title: "some_title",
-                img_url: None,
-            },
-            connection,
-        )?;
+        assert_eq!(None, database.get_tracker_entry("some_manga", "anilist")?);
 
-        insert_manga_in_reading_history(&manga_is_in_reading_history_id, connection)?;
+        database.bind_tracker_entry("some_manga", "anilist", "123")?;
+        database.bind_tracker_entry("some_manga", "anilist", "456")?;
+        database.bind_tracker_entry("some_manga", "myanimelist", "789")?;
 
-        insert_manga(
-            MangaInsert {
-                id: &manga_not_in_reading_history_id,
-                title: "some_title",
-                img_url: None,
-            },
-            connection,
-        )?;
+        assert_eq!(Some("456".to_string()), database.get_tracker_entry("some_manga", "anilist")?);
+        assert_eq!(Some("789".to_string()), database.get_tracker_entry("some_manga", "myanimelist")?);
 
-        let history = get_history(GetHistoryArgs {
-            conn: connection,
-            hist_type: MangaHistoryType::ReadingHistory,
-            page: 1,
-            search: None,
-            items_per_page: 100,
+        Ok(())
+    }
+
+    #[test]
+    fn database_keeps_the_latest_pending_update_of_each_manga() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+
+        database.setup()?;
+
+        let update = PendingTrackerUpdate {
+            manga_id: "some_manga".to_string(),
+            tracker: "anilist".to_string(),
+            manga_title: "some title".to_string(),
+            chapter_number: 3,
+            volume_number: None,
+        };
+
+        database.queue_tracker_update(&update)?;
+        database.queue_tracker_update(&PendingTrackerUpdate {
+            chapter_number: 4,
+            volume_number: Some(1),
+            ..update.clone()
+        })?;
+        database.queue_tracker_update(&PendingTrackerUpdate {
+            tracker: "myanimelist".to_string(),
+            ..update.clone()
         })?;
 
-        assert!(history.total_items > 0);
+        let queue = database.get_tracker_queue()?;
 
-        assert!(history.mangas.iter().any(|manga| manga.id == manga_is_in_reading_history_id));
+        assert_eq!(2, queue.len());
+        assert!(queue.iter().any(|pending| pending.tracker == "anilist" && pending.chapter_number == 4));
 
-        assert!(!history.mangas.iter().any(|manga| manga.id == manga_not_in_reading_history_id));
+        database.remove_from_tracker_queue("some_manga", "anilist")?;
+
+        assert_eq!(
+            vec![PendingTrackerUpdate {
+                tracker: "myanimelist".to_string(),
+                ..update
+            }],
+            database.get_tracker_queue()?
+        );
 
         Ok(())
     }
 
     #[test]
-    fn get_manga_history_reading_with_search_term() -> Result<()> {
-        let binding = DBCONN.lock().expect("could not get db conn");
-        let connection = binding.as_ref().unwrap();
+    fn database_groups_mangas_in_collections() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
-        let manga_id_filtered_out = Uuid::new_v4().to_string();
-        let manga_id_included_in_search = Uuid::new_v4().to_string();
+        database.setup()?;
 
-        insert_manga(
+        database.create_collection("Favorites")?;
+        database.create_collection("On hold")?;
+        database.create_collection("Favorites")?;
+
+        let collections = database.get_collections()?;
+        assert_eq!(vec!["Favorites", "On hold"], collections.iter().map(|collection| collection.name.as_str()).collect::<Vec<_>>());
+
+        let favorites = collections[0].id;
+        let on_hold = collections[1].id;
+
+        for (id, title) in [("manga_1", "Some title"), ("manga_2", "Another title"), ("manga_3", "Some other title")] {
+            assert!(database.toggle_manga_in_collection(
+                favorites,
+                MangaInsert {
+                    id,
+                    title,
+                    img_url: None
+                }
+            )?);
+        }
+
+        database.toggle_manga_in_collection(
+            on_hold,
             MangaInsert {
-                id: &manga_id_filtered_out,
-                title: "filtered_out",
+                id: "manga_1",
+                title: "Some title",
                 img_url: None,
             },
-            connection,
         )?;
 
-        insert_manga_in_reading_history(&manga_id_filtered_out, connection)?;
-
-        insert_manga(
+        assert!(!database.toggle_manga_in_collection(
+            favorites,
             MangaInsert {
-                id: &manga_id_included_in_search,
-                title: "included",
-                img_url: None,
-            },
-            connection,
-        )?;
+                id: "manga_2",
+                title: "Another title",
+                img_url: None
+            }
+        )?);
+
+        assert_eq!(vec![favorites, on_hold], {
+            let mut ids = database.get_manga_collections("manga_1")?;
+            ids.sort();
+            ids
+        });
 
-        insert_manga_in_reading_history(&manga_id_included_in_search, connection)?;
+        let first_page = database.get_collection_mangas(favorites, 1, 1, None, None)?;
 
-        let history = get_history(GetHistoryArgs {
-            conn: connection,
-            hist_type: MangaHistoryType::ReadingHistory,
-            page: 1,
-            search: SearchTerm::trimmed_lowercased("Included"),
-            items_per_page: 100,
-        })?;
+        assert_eq!(2, first_page.total_items);
+        assert_eq!("manga_3", first_page.mangas[0].id);
 
-        assert!(history.total_items > 0);
+        let searched = database.get_collection_mangas(favorites, 1, 5, SearchTerm::trimmed_lowercased("other"), None)?;
 
-        assert!(!history.mangas.iter().any(|manga| manga.id == manga_id_filtered_out));
-        assert!(history.mangas.iter().any(|manga| manga.id == manga_id_included_in_search));
+        assert_eq!(1, searched.total_items);
+        assert_eq!("Some other title", searched.mangas[0].title);
+
+        database.delete_collection(favorites)?;
+
+        assert_eq!(1, database.get_collections()?.len());
+        assert_eq!(vec![on_hold], database.get_manga_collections("manga_1")?);
 
         Ok(())
     }
 
     #[test]
-    fn get_manga_planned_to_read_with_search_term() -> Result<()> {
-        let binding = DBCONN.lock().expect("could not get db conn");
-        let connection = binding.as_ref().unwrap();
+    fn database_tags_mangas_and_filters_the_history_by_tag() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
-        let manga_id_filtered_out = Uuid::new_v4().to_string();
-        let manga_id_included_in_search = Uuid::new_v4().to_string();
+        database.setup()?;
 
-        let manga_filtered_out = MangaPlanToReadSave {
-            id: &manga_id_filtered_out,
-            title: "filtered_out",
-            img_url: None,
-        };
+        for index in 1..=3 {
+            let id = format!("manga_{index}");
+            insert_manga(
+                MangaInsert {
+                    id: &id,
+                    title: &format!("title {index}"),
+                    img_url: None,
+                },
+                &connection,
+            )?;
+            insert_manga_in_reading_history(&id, &connection)?;
+        }
 
-        let manga_included = MangaPlanToReadSave {
-            id: &manga_id_included_in_search,
-            title: "included",
+        let comfy = TagName::new("comfy").unwrap();
+        let to_reread = TagName::new("to reread").unwrap();
+
+        let manga = |id| MangaInsert {
+            id,
+            title: "title",
             img_url: None,
         };
 
-        save_plan_to_read(manga_filtered_out, connection)?;
+        database.add_manga_tag(manga("manga_1"), &comfy)?;
+        database.add_manga_tag(manga("manga_1"), &to_reread)?;
+        database.add_manga_tag(manga("manga_1"), &comfy)?;
+        database.add_manga_tag(manga("manga_3"), &comfy)?;
 
-        save_plan_to_read(manga_included, connection)?;
+        assert_eq!(vec!["comfy", "to-reread"], database.get_manga_tags("manga_1")?);
+        assert_eq!(vec!["comfy", "to-reread"], database.get_tags()?);
 
         let history = get_history(GetHistoryArgs {
-            conn: connection,
-            hist_type: MangaHistoryType::PlanToRead,
+            conn: &connection,
+            hist_type: Some(MangaHistoryType::ReadingHistory),
             page: 1,
-            search: SearchTerm::trimmed_lowercased("Included"),
-            items_per_page: 100,
+            search: None,
+            tag: Some(comfy.clone()),
+            items_per_page: 5,
+            order: HistoryOrder::default(),
+            in_trash: false,
         })?;
 
-        assert!(history.total_items > 0);
+        assert_eq!(2, history.total_items);
+        let mut ids: Vec<String> = history.mangas.into_iter().map(|manga| manga.id).collect();
+        ids.sort();
+        assert_eq!(vec!["manga_1", "manga_3"], ids);
 
-        assert!(!history.mangas.iter().any(|manga| manga.id == manga_id_filtered_out));
-        assert!(history.mangas.iter().any(|manga| manga.id == manga_id_included_in_search));
+        let history = get_history(GetHistoryArgs {
+            conn: &connection,
+            hist_type: Some(MangaHistoryType::ReadingHistory),
+            page: 1,
+            search: SearchTerm::trimmed_lowercased("title 3"),
+            tag: Some(comfy.clone()),
+            items_per_page: 5,
+            order: HistoryOrder::default(),
+            in_trash: false,
+        })?;
 
-        Ok(())
-    }
-    #[test]
-    fn get_manga_planned_to_read() -> Result<()> {
-        let binding = DBCONN.lock().expect("could not get db conn");
-        let connection = binding.as_ref().unwrap();
+        assert_eq!(1, history.total_items);
+        assert_eq!("manga_3", history.mangas[0].id);
 
-        let manga_id_1 = Uuid::new_v4().to_string();
-        let manga_id_2 = Uuid::new_v4().to_string();
+        database.remove_manga_tag("manga_1", &to_reread)?;
 
-        let manga_1 = MangaPlanToReadSave {
-            id: &manga_id_1,
-            title: "manga_1",
-            img_url: None,
-        };
+        assert_eq!(vec!["comfy"], database.get_manga_tags("manga_1")?);
+        assert_eq!(vec!["comfy"], database.get_tags()?);
 
-        let manga_2 = MangaPlanToReadSave {
-            id: &manga_id_2,
-            title: "manga_2",
-            img_url: None,
-        };
+        Ok(())
+    }
 
-        save_plan_to_read(manga_1, connection)?;
+    #[test]
+    fn history_is_sorted_by_personal_rating_with_unrated_mangas_last() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
-        save_plan_to_read(manga_2, connection)?;
+        database.setup()?;
+
+        for (id, rating) in [("unrated", None), ("liked", Some(6)), ("loved", Some(10))] {
+            database.set_personal_rating(
+                MangaInsert {
+                    id,
+                    title: id,
+                    img_url: None,
+                },
+                rating,
+            )?;
+            insert_manga_in_reading_history(id, &connection)?;
+        }
 
         let history = get_history(GetHistoryArgs {
-            conn: connection,
-            hist_type: MangaHistoryType::PlanToRead,
+            conn: &connection,
+            hist_type: Some(MangaHistoryType::ReadingHistory),
             page: 1,
             search: None,
-            items_per_page: 100,
+            tag: None,
+            items_per_page: 10,
+            order: HistoryOrder::PersonalRating,
+            in_trash: false,
         })?;
 
-        assert!(history.total_items > 0);
+        let sorted: Vec<(&str, Option<u8>)> =
+            history.mangas.iter().map(|manga| (manga.id.as_str(), manga.personal_rating)).collect();
 
-        assert!(history.mangas.iter().any(|manga| manga.id == manga_id_1));
-        assert!(history.mangas.iter().any(|manga| manga.id == manga_id_2));
+        assert_eq!(vec![("loved", Some(10)), ("liked", Some(6)), ("unrated", None)], sorted);
 
         Ok(())
     }
 
-    // Test the case when a manga is not in the database and a chapters is not in the database
-    // either
     #[test]
-    fn save_chapter_download_status_manga_doesnt_exist_and_chapter_doesnt_exist() -> Result<()> {
-        let binding = DBCONN.lock().expect("could not get db conn");
-        let connection = binding.as_ref().unwrap();
+    fn it_prunes_the_chapters_whose_manga_no_longer_exists() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
-        let chapter_id = Uuid::new_v4().to_string();
-        let manga_id = Uuid::new_v4().to_string();
+        database.setup()?;
 
-        set_chapter_downloaded(
-            SetChapterDownloaded {
-                id: &chapter_id,
-                title: "some_title",
-                manga_id: &manga_id,
-                manga_title: "some_title",
+        database.mark_chapters_as_read(
+            MangaInsert {
+                id: "manga",
+                title: "manga",
                 img_url: None,
             },
-            connection,
+            &[ChapterToSaveHistory {
+                id: "kept",
+                title: "kept",
+                translated_language: "en",
+            }],
         )?;
 
-        assert!(check_exists(&manga_id, connection, Table::Mangas)?);
+        // sqlite only enforces foreign keys when they are turned on, other tools editing the database may not do it
+        connection.execute_batch("PRAGMA foreign_keys = OFF")?;
+        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES ('orphan', 'orphan', 'deleted_manga')", [])?;
+        connection.execute("INSERT INTO manga_alt_titles(manga_id, title) VALUES ('deleted_manga', 'some title')", [])?;
+        connection.execute_batch("PRAGMA foreign_keys = ON")?;
+        database.save_chapter_progress(
+            "orphan",
+            ChapterProgress {
+                last_page: 1,
+                total_pages: 10,
+            },
+        )?;
 
-        let chapters = get_all_chapters(connection)?;
+        let size = database.size()?;
 
-        let chapter_downloaded = chapters
-            .iter()
-            .find(|chap| chap.id == chapter_id)
-            .expect("chapter downloaded was nost found");
+        assert_eq!(1, database.prune_orphan_chapters()?);
+        assert_eq!(0, database.prune_orphan_chapters()?);
+        assert!(database.get_chapter_progress("orphan")?.is_none());
+        assert!(check_exists("kept", &connection, Table::Chapters)?);
+        assert_eq!(0, connection.query_row("SELECT COUNT(*) FROM manga_alt_titles", [], |row| row.get::<_, u32>(0))?);
 
-        assert!(chapter_downloaded.is_read);
-        assert!(chapter_downloaded.is_downloaded);
+        database.vacuum()?;
+
+        assert!(database.integrity_check()?.is_empty());
+        assert!(database.size()? <= size);
 
         Ok(())
     }
 
-    // Test the case when both manga and chapter already exist in database
     #[test]
-    fn save_chapter_download_status_manga_and_chapter_exists() -> Result<()> {
-        let binding = DBCONN.lock().expect("could not get db conn");
-        let connection = binding.as_ref().unwrap();
+    fn it_prunes_the_reading_history_not_read_in_months() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
-        let chapter_id_exist_in_database = Uuid::new_v4().to_string();
-        let manga_id_exist_in_database = Uuid::new_v4().to_string();
+        database.setup()?;
 
-        insert_manga(
-            MangaInsert {
-                id: &manga_id_exist_in_database,
-                title: "some_title",
+        for id in ["stale", "recent", "planned", "downloaded"] {
+            insert_manga(
+                MangaInsert {
+                    id,
+                    title: id,
+                    img_url: None,
+                },
+                &connection,
+            )?;
+            insert_manga_in_reading_history(id, &connection)?;
+        }
+
+        save_plan_to_read(
+            MangaPlanToReadSave {
+                id: "planned",
+                title: "planned",
                 img_url: None,
             },
-            connection,
+            &connection,
         )?;
-
-        insert_chapter(
-            ChapterInsert {
-                id: &chapter_id_exist_in_database,
-                title: "some_title",
-                manga_id: &manga_id_exist_in_database,
-                is_read: false,
-                is_downloaded: false,
-            },
-            connection,
+        connection.execute(
+            "INSERT INTO chapters(id, title, manga_id, is_downloaded) VALUES ('chapter', 'chapter', 'downloaded', true)",
+            [],
         )?;
+        connection.execute("UPDATE mangas SET last_read = '2020-01-01' WHERE id != 'recent'", [])?;
 
-        set_chapter_downloaded(
-            SetChapterDownloaded {
-                id: &chapter_id_exist_in_database,
-                title: "some_title",
-                manga_id: &manga_id_exist_in_database,
-                manga_title: "some_title",
-                img_url: None,
-            },
-            connection,
-        )?;
+        assert_eq!(vec!["stale".to_string()], database.history_to_prune(6)?.titles);
 
-        let chapters = get_all_chapters(connection)?;
+        assert_eq!(1, database.prune_history(6)?);
+        assert_eq!(0, database.prune_history(6)?);
+        assert!(database.history_to_prune(6)?.titles.is_empty());
 
-        let chapter_downloaded = chapters
-            .iter()
-            .find(|chap| chap.id == chapter_id_exist_in_database)
-            .expect("chapter downloaded was nost found");
+        let mut statement = connection.prepare("SELECT id FROM mangas WHERE deleted_at IS NOT NULL")?;
+        let in_trash = statement.query_map([], |row| row.get(0))?.collect::<Result<Vec<String>>>()?;
 
-        assert!(chapter_downloaded.is_read);
-        assert!(chapter_downloaded.is_downloaded);
+        assert_eq!(vec!["stale".to_string()], in_trash);
 
         Ok(())
     }
 
-    #[test]
-    fn save_chapter_download_status_manga_exist_and_chapter_doesnt_exist() -> Result<()> {
-        let binding = DBCONN.lock().expect("could not get db conn");
-        let connection = binding.as_ref().unwrap();
-
-        let chapter_id = Uuid::new_v4().to_string();
-        let manga_id = Uuid::new_v4().to_string();
+    #[test]
+    fn it_merges_a_duplicate_of_a_manga_already_in_the_library() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
-        insert_manga(
+        database.setup()?;
+
+        database.mark_chapters_as_read(
             MangaInsert {
-                id: &manga_id,
-                title: "some_title",
+                id: "duplicate",
+                title: "Kimetsu no Yaiba",
                 img_url: None,
             },
-            connection,
+            &[ChapterToSaveHistory {
+                id: "chapter_read",
+                title: "chapter_read",
+                translated_language: "en",
+            }],
         )?;
-
-        set_chapter_downloaded(
-            SetChapterDownloaded {
-                id: &chapter_id,
-                title: "some_title",
-                manga_id: &manga_id,
-                manga_title: "some_title",
+        database.save_alt_titles("duplicate", &["Demon Slayer".to_string()])?;
+
+        let titles = ["Kimetsu No Yaiba!".to_string()];
+
+        assert_eq!(
+            Some(MatchedManga {
+                id: "duplicate".to_string(),
+                title: "Kimetsu no Yaiba".to_string(),
+            }),
+            database.find_duplicate("manga", &titles)?
+        );
+        assert!(database.find_duplicate("manga", &["demon-slayer".to_string()])?.is_some());
+        assert!(database.find_duplicate("duplicate", &titles)?.is_none());
+        assert!(database.find_duplicate("manga", &["Chainsaw Man".to_string()])?.is_none());
+
+        database.merge_duplicate(
+            "duplicate",
+            MangaInsert {
+                id: "manga",
+                title: "Kimetsu No Yaiba!",
                 img_url: None,
             },
-            connection,
         )?;
 
-        let chapters = get_all_chapters(connection)?;
+        assert!(database.find_duplicate("manga", &titles)?.is_none());
 
-        let chapter_downloaded = chapters
-            .iter()
-            .find(|chap| chap.id == chapter_id)
-            .expect("chapter downloaded was nost found");
+        let history = get_history(GetHistoryArgs {
+            conn: &connection,
+            hist_type: Some(MangaHistoryType::ReadingHistory),
+            page: 1,
+            search: SearchTerm::trimmed_lowercased("demon slayer"),
+            tag: None,
+            items_per_page: 10,
+            order: HistoryOrder::default(),
+            in_trash: false,
+        })?;
 
-        assert!(chapter_downloaded.is_read);
-        assert!(chapter_downloaded.is_downloaded);
+        assert_eq!(vec!["manga".to_string()], history.mangas.into_iter().map(|manga| manga.id).collect::<Vec<String>>());
+        assert!(
+            get_chapters_history_status("manga", &connection)?
+                .iter()
+                .any(|chapter| chapter.id == "chapter_read")
+        );
 
         Ok(())
     }
 
     #[test]
-    fn database_bookmarks_chapter() -> Result<()> {
+    fn mangas_moved_to_the_trash_are_only_shown_there_until_restored() -> Result<()> {
         let connection = Connection::open_in_memory()?;
-        let mut database = Database::new(&connection);
+        let database = Database::new(&connection);
 
         database.setup()?;
 
-        let chapter_id = Uuid::new_v4().to_string();
-        let manga_id = Uuid::new_v4().to_string();
-
-        connection.execute("INSERT INTO mangas(id, title) VALUES(?1,?2)", params![manga_id.clone(), "some_title"])?;
-
-        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1,?2,?3)", params![
-            chapter_id.clone(),
-            "some_title",
-            manga_id
-        ])?;
+        for id in ["kept", "trashed"] {
+            database.add_to_reading_history(MangaInsert {
+                id,
+                title: id,
+                img_url: None,
+            })?;
+        }
 
-        let chapter_to_bookmark1: ChapterToBookmark = ChapterToBookmark {
-            chapter_id: &chapter_id,
-            manga_id: &manga_id,
-            page_number: Some(3),
-            ..Default::default()
+        database.move_to_trash("trashed")?;
+
+        let history_ids = |in_trash: bool| -> Result<Vec<String>> {
+            let history = get_history(GetHistoryArgs {
+                conn: &connection,
+                hist_type: None,
+                page: 1,
+                search: None,
+                tag: None,
+                items_per_page: 10,
+                order: HistoryOrder::default(),
+                in_trash,
+            })?;
+            Ok(history.mangas.into_iter().map(|manga| manga.id).collect())
         };
 
-        database.bookmark(chapter_to_bookmark1).expect("failed to bookmark chapter");
+        assert_eq!(vec!["kept"], history_ids(false)?);
+        assert_eq!(vec!["trashed"], history_ids(true)?);
+        assert!(!database.get_library_entries()?.iter().any(|entry| entry.id == "trashed"));
 
-        let was_bookmarked: bool =
-            connection.query_row("SELECT is_bookmarked FROM chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))?;
+        // adding it again to plan to read takes it out of the trash
+        save_plan_to_read(
+            MangaPlanToReadSave {
+                id: "trashed",
+                title: "trashed",
+                img_url: None,
+            },
+            &connection,
+        )?;
 
-        let page_set: Option<u32> =
-            connection
-                .query_row("SELECT number_page_bookmarked FROM chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))?;
+        assert!(history_ids(true)?.is_empty());
 
-        assert!(was_bookmarked);
-        assert_eq!(page_set.expect("should not be null"), 3);
+        database.move_to_trash("kept")?;
+        database.restore_from_trash("kept")?;
 
-        let chapter_to_bookmark1: ChapterToBookmark = ChapterToBookmark {
-            chapter_id: &chapter_id,
-            manga_id: &manga_id,
-            page_number: None,
-            ..Default::default()
+        assert!(history_ids(false)?.contains(&"kept".to_string()));
+
+        // reading again a chapter that was already read also takes it out of the trash
+        let read_chapter = || {
+            save_history(
+                MangaReadingHistorySave {
+                    id: "kept",
+                    title: "kept",
+                    img_url: None,
+                    chapter: ChapterToSaveHistory {
+                        id: "chapter",
+                        ..Default::default()
+                    },
+                },
+                &connection,
+            )
         };
 
-        database.bookmark(chapter_to_bookmark1).expect("failed to bookmark chapter");
+        read_chapter()?;
+        database.move_to_trash("kept")?;
+        read_chapter()?;
 
-        let page_set_to_none: Option<u32> =
-            connection
-                .query_row("SELECT number_page_bookmarked FROM chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))?;
+        assert!(history_ids(true)?.is_empty());
 
-        assert!(page_set_to_none.is_none());
         Ok(())
     }
 
     #[test]
-    fn database_only_bookmarks_one_chapter_at_a_time_per_manga() -> Result<()> {
+    fn database_lists_the_library_with_the_chapters_read() -> Result<()> {
         let connection = Connection::open_in_memory()?;
-        let mut database = Database::new(&connection);
+        let database = Database::new(&connection);
 
         database.setup()?;
 
-        let chapter_id = Uuid::new_v4().to_string();
-        let chapter_id_2 = Uuid::new_v4().to_string();
-        let chapter_id_should_stay_bookmarked = Uuid::new_v4().to_string();
-        let manga_id = Uuid::new_v4().to_string();
-        let manga_id_2 = Uuid::new_v4().to_string();
+        let manga = MangaInsert {
+            id: "reading",
+            title: "Being read",
+            img_url: None,
+        };
 
-        connection.execute("INSERT INTO mangas(id, title) VALUES(?1,?2)", params![manga_id.clone(), "some_title"])?;
-        connection.execute("INSERT INTO mangas(id, title) VALUES(?1,?2)", params![manga_id_2.clone(), "some_title2"])?;
+        database.mark_chapters_as_read(
+            manga.clone(),
+            &[
+                ChapterToSaveHistory {
+                    id: "chapter-1",
+                    ..Default::default()
+                },
+                ChapterToSaveHistory {
+                    id: "chapter-2",
+                    ..Default::default()
+                },
+            ],
+        )?;
+        database.set_personal_rating(manga, Some(7))?;
 
-        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1,?2,?3)", params![
-            chapter_id.clone(),
-            "some_title",
-            manga_id
-        ])?;
+        database.add_to_reading_history(MangaInsert {
+            id: "started",
+            title: "Just started",
+            img_url: None,
+        })?;
 
-        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1,?2,?3)", params![
-            chapter_id_2.clone(),
-            "some_title",
-            manga_id
-        ])?;
+        save_plan_to_read(
+            MangaPlanToReadSave {
+                id: "plan",
+                title: "Plan to read",
+                img_url: None,
+            },
+            &connection,
+        )?;
 
-        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1,?2,?3)", params![
-            chapter_id_should_stay_bookmarked.clone(),
-            "some_title",
-            manga_id_2
-        ])?;
+        assert_eq!(
+            vec![
+                LibraryEntry {
+                    id: "reading".to_string(),
+                    title: "Being read".to_string(),
+                    history_type: MangaHistoryType::ReadingHistory,
+                    personal_rating: Some(7),
+                    chapters_read: 2,
+                },
+                LibraryEntry {
+                    id: "started".to_string(),
+                    title: "Just started".to_string(),
+                    history_type: MangaHistoryType::ReadingHistory,
+                    personal_rating: None,
+                    chapters_read: 0,
+                },
+                LibraryEntry {
+                    id: "plan".to_string(),
+                    title: "Plan to read".to_string(),
+                    history_type: MangaHistoryType::PlanToRead,
+                    personal_rating: None,
+                    chapters_read: 0,
+                },
+            ],
+            database.get_library_entries()?
+        );
 
-        let chapter_to_bookmark1: ChapterToBookmark = ChapterToBookmark {
-            chapter_id: &chapter_id,
-            manga_id: &manga_id,
-            page_number: None,
-            ..Default::default()
-        };
+        Ok(())
+    }
 
-        database.bookmark_chapter(chapter_to_bookmark1).expect("failed to bookmark chapter1");
+    #[test]
+    fn the_reading_mode_is_remembered_for_each_manga() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
 
-        let chapter_to_bookmark_should_stay_bookmarked: ChapterToBookmark = ChapterToBookmark {
-            chapter_id: &chapter_id_should_stay_bookmarked,
-            manga_id: &manga_id_2,
-            page_number: None,
-            ..Default::default()
-        };
+        database.setup()?;
 
-        database
-            .bookmark_chapter(chapter_to_bookmark_should_stay_bookmarked)
-            .expect("failed to bookmark chapter_id_should_stay_bookmarked");
+        let webtoon_id = Uuid::new_v4().to_string();
 
-        let chapter_to_bookmark2 = ChapterToBookmark {
-            chapter_id: &chapter_id_2,
-            manga_id: &manga_id,
-            page_number: None,
-            ..Default::default()
-        };
+        assert_eq!(ReadingMode::Paged, database.get_reading_mode(&webtoon_id)?);
 
-        database.bookmark_chapter(chapter_to_bookmark2).expect("failed to bookmark chapter2");
+        database.set_reading_mode(&webtoon_id, ReadingMode::Webtoon)?;
 
-        let was_bookmarked_1: bool =
-            connection.query_row("SELECT is_bookmarked FROM chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))?;
+        assert_eq!(ReadingMode::Webtoon, database.get_reading_mode(&webtoon_id)?);
+        assert_eq!(ReadingMode::Paged, database.get_reading_mode("other_manga")?);
 
-        let was_bookmarked_2: bool =
-            connection.query_row("SELECT is_bookmarked FROM chapters WHERE id = ?1", params![chapter_id_2], |row| row.get(0))?;
+        database.set_reading_mode(&webtoon_id, ReadingMode::Webtoon.switch_to(ReadingMode::DoublePage))?;
 
-        let should_stay_bookmarked: bool = connection.query_row(
-            "SELECT is_bookmarked FROM chapters WHERE id = ?1",
-            params![chapter_id_should_stay_bookmarked],
-            |row| row.get(0),
-        )?;
+        assert_eq!(ReadingMode::DoublePage, database.get_reading_mode(&webtoon_id)?);
 
-        assert!(!was_bookmarked_1);
-        assert!(was_bookmarked_2);
-        assert!(should_stay_bookmarked);
+        database.set_reading_mode(&webtoon_id, ReadingMode::DoublePage.switch_to(ReadingMode::DoublePage))?;
+
+        assert_eq!(ReadingMode::Paged, database.get_reading_mode(&webtoon_id)?);
 
         Ok(())
     }
 
     #[test]
-    fn it_inserts_manga_and_chapter_if_it_does_not_exists() -> Result<()> {
+    fn mangas_can_be_marked_as_not_interested() -> Result<()> {
         let connection = Connection::open_in_memory()?;
         let database = Database::new(&connection);
 
         database.setup()?;
 
-        let id_manga = Uuid::new_v4().to_string();
-        let chapter_id = Uuid::new_v4().to_string();
-
-        database.create_manga_if_not_exists(MangaInsert {
-            id: &id_manga,
-            title: "some_title",
-            img_url: None,
-        })?;
-
-        let id_was_created: String = connection
-            .query_row("SELECT id from mangas WHERE id = ?1", params![id_manga], |row| row.get(0))
-            .expect("manga was not created");
-
-        assert_eq!(id_was_created, id_manga);
-
-        database
-            .create_manga_if_not_exists(MangaInsert {
-                id: &id_manga,
-                title: "some_title",
-                img_url: None,
-            })
-            .expect("should not try to create already existing manga");
+        let manga_id = Uuid::new_v4().to_string();
 
-        database
-            .create_chapter_if_not_exists(ChapterToInsert {
-                id: &chapter_id,
-                title: "some_title",
-                manga_id: &id_manga,
-                ..Default::default()
-            })
-            .expect("should create chapter");
+        database.set_not_interested(&manga_id, "some_title")?;
+        database.set_not_interested(&manga_id, "some_title")?;
 
-        let id_chapter_was_created: String = connection
-            .query_row("SELECT id from chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))
-            .expect("chapter was not created");
+        assert_eq!(vec![manga_id.clone()], database.get_not_interested_mangas()?);
 
-        assert_eq!(chapter_id, id_chapter_was_created);
+        database.remove_not_interested(&manga_id)?;
 
-        database
-            .create_chapter_if_not_exists(ChapterToInsert {
-                id: &chapter_id,
-                title: "some_title",
-                manga_id: &id_manga,
-                ..Default::default()
-            })
-            .expect("should try to create chapter already existing");
+        assert!(database.get_not_interested_mangas()?.is_empty());
 
         Ok(())
     }
 
     #[test]
-    fn it_bookmarks_chapter_if_it_does_not_exits_in_database() -> Result<()> {
+    fn database_replaces_dead_mangas() -> Result<()> {
         let connection = Connection::open_in_memory()?;
-        let mut database = Database::new(&connection);
+        let database = Database::new(&connection);
 
         database.setup()?;
 
-        let chapter_id = Uuid::new_v4().to_string();
-        let manga_id = Uuid::new_v4().to_string();
+        let manga_ids: Vec<String> = (0..3).map(|_| Uuid::new_v4().to_string()).collect();
 
-        let chapter_to_bookmark = ChapterToBookmark {
-            chapter_id: &chapter_id,
-            manga_id: &manga_id,
-            page_number: None,
-            ..Default::default()
-        };
+        for id in &manga_ids {
+            connection.execute("INSERT INTO mangas(id, title) VALUES(?1, ?2)", params![id, "some_title"])?;
+        }
 
-        database.bookmark_chapter(chapter_to_bookmark).expect("failed to bookmark chapter");
+        let mut library = database.get_library_manga_ids()?;
+        library.sort();
 
-        let was_bookmarked: bool = connection
-            .query_row("SELECT is_bookmarked FROM chapters WHERE id = ?1", params![chapter_id], |row| row.get(0))
-            .expect("chapter was not created");
+        let mut expected = manga_ids.clone();
+        expected.sort();
 
-        assert!(was_bookmarked);
+        assert_eq!(expected, library);
+
+        database.set_dead_mangas(&manga_ids[0..2])?;
+
+        assert_eq!(2, database.get_dead_mangas()?.len());
+
+        database.set_dead_mangas(&manga_ids[2..])?;
+
+        assert_eq!(vec![manga_ids[2].clone()], database.get_dead_mangas()?);
+
+        database.add_dead_manga(&manga_ids[0])?;
+        database.add_dead_manga(&manga_ids[0])?;
+
+        assert_eq!(2, database.get_dead_mangas()?.len());
 
         Ok(())
     }
 
     #[test]
-    fn database_gets_chapter_bookmarked() -> Result<()> {
+    fn database_blocks_scanlation_groups_per_manga_and_globally() -> Result<()> {
         let connection = Connection::open_in_memory()?;
         let database = Database::new(&connection);
 
         database.setup()?;
 
-        let chapter_id = Uuid::new_v4().to_string();
         let manga_id = Uuid::new_v4().to_string();
+        let other_manga_id = Uuid::new_v4().to_string();
 
-        connection.execute("INSERT INTO mangas(id, title) VALUES(?1, ?2)", params![manga_id.clone(), "some_title"])?;
-
-        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1,?2,?3)", params![
-            chapter_id.clone(),
-            "some_title",
-            manga_id
-        ])?;
+        let group = ScanlationGroup {
+            id: "group_blocked_here",
+            name: "some group",
+        };
 
-        let expected: ChapterBookmarked = ChapterBookmarked {
-            id: "bookmarked".to_string(),
-            translated_language: Some("en".to_string()),
-            number_page_bookmarked: Some(2),
-            manga_id: manga_id.clone(),
-            manga_title: "some_title".to_string(),
+        let global_group = ScanlationGroup {
+            id: "group_blocked_everywhere",
+            name: "some other group",
         };
 
-        connection.execute(
-            "INSERT INTO chapters(id, title, manga_id, translated_language, number_page_bookmarked, is_bookmarked) VALUES(?1,?2,?3,?4,?5,?6)",
-            params![expected.id, "some_title", manga_id, expected.translated_language, expected.number_page_bookmarked, true],
-        )?;
+        database.insert_blocked_scanlation_group(&group, Some(&manga_id))?;
+        database.insert_blocked_scanlation_group(&group, Some(&manga_id))?;
+        database.insert_blocked_scanlation_group(&global_group, None)?;
 
-        let result = database.get_bookmarked(&manga_id).expect("should be ok").expect("should not be none");
+        let mut blocked = database.get_blocked_scanlation_group_ids(&manga_id)?;
+        blocked.sort();
 
-        assert_eq!(expected, result);
+        assert_eq!(vec!["group_blocked_everywhere".to_string(), "group_blocked_here".to_string()], blocked);
+        assert_eq!(vec!["group_blocked_everywhere".to_string()], database.get_blocked_scanlation_group_ids(&other_manga_id)?);
+
+        assert_eq!(1, database.delete_blocked_scanlation_groups(Some(&manga_id))?);
+
+        assert_eq!(vec!["group_blocked_everywhere".to_string()], database.get_blocked_scanlation_group_ids(&manga_id)?);
+
+        assert_eq!(1, database.delete_blocked_scanlation_groups(None)?);
+
+        assert!(database.get_blocked_scanlation_group_ids(&manga_id)?.is_empty());
+        assert!(database.get_blocked_scanlation_group_ids(&other_manga_id)?.is_empty());
 
         Ok(())
     }
@@ -0,0 +1,193 @@
+//! The database is only accessed from one thread, pages send it the queries to run and await the result instead of
+//! locking a connection in the ui thread
+use std::error::Error;
+use std::future::Future;
+use std::sync::mpsc as std_mpsc;
+use std::thread;
+use std::time::Duration;
+
+use once_cell::sync::OnceCell;
+use rusqlite::Connection;
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio::sync::oneshot;
+
+use super::database::Database;
+use super::error_log::{write_to_error_log, ErrorType};
+
+type Job = Box<dyn FnOnce(&mut Connection) + Send>;
+
+static DATABASE_ACTOR: OnceCell<DatabaseActor> = OnceCell::new();
+
+#[derive(Debug, Clone)]
+pub struct DatabaseActor {
+    jobs: UnboundedSender<Job>,
+}
+
+impl DatabaseActor {
+    /// The connection is opened in the thread that runs the queries, if it can't be opened every query fails
+    pub fn spawn(open_connection: impl FnOnce() -> rusqlite::Result<Connection> + Send + 'static) -> Self {
+        let (jobs, mut jobs_rx) = mpsc::unbounded_channel::<Job>();
+
+        thread::spawn(move || {
+            let mut connection = match open_connection() {
+                Ok(connection) => connection,
+                Err(e) => {
+                    write_to_error_log(ErrorType::Error(Box::new(e)));
+                    return;
+                },
+            };
+
+            while let Some(job) = jobs_rx.blocking_recv() {
+                job(&mut connection);
+            }
+        });
+
+        Self { jobs }
+    }
+
+    pub fn global() -> &'static Self {
+        DATABASE_ACTOR.get_or_init(|| Self::spawn(Database::get_connection))
+    }
+
+    /// The query is queued as soon as this is called, so queries run in the order they were sent even if the futures
+    /// are awaited in a different order
+    pub fn run<T, F>(&self, query: F) -> impl Future<Output = Result<T, Box<dyn Error + Send + Sync>>>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+    {
+        let (result_tx, result_rx) = oneshot::channel();
+
+        let job: Job = Box::new(move |connection| {
+            result_tx.send(query(connection)).ok();
+        });
+
+        let is_running = self.jobs.send(job).is_ok();
+
+        async move {
+            if !is_running {
+                return Err("the database is not running".into());
+            }
+
+            let result = result_rx.await.map_err(|_| "the database stopped before the query finished")?;
+
+            Ok(result?)
+        }
+    }
+
+    /// Queues the query without waiting for it, for writes done often like saving where the user is reading, a
+    /// failed write is not worth interrupting the user so it is only written to the error log
+    pub fn execute<F>(&self, query: F)
+    where
+        F: FnOnce(&mut Connection) -> rusqlite::Result<()> + Send + 'static,
+    {
+        let job: Job = Box::new(move |connection| {
+            if let Err(e) = query(connection) {
+                write_to_error_log(ErrorType::Error(Box::new(e)));
+            }
+        });
+
+        self.jobs.send(job).ok();
+    }
+
+    /// Runs `query` without waiting for it and sends the event made from its result to `tx`, so pages handle it in a
+    /// later tick like the rest of their events
+    pub fn query<R, E>(
+        &self,
+        tx: UnboundedSender<E>,
+        query: impl FnOnce(&mut Connection) -> Result<R, Box<dyn Error>> + Send + 'static,
+        into_event: impl FnOnce(R) -> E + Send + 'static,
+    ) where
+        E: Send + 'static,
+    {
+        let job: Job = Box::new(move |connection| match query(connection) {
+            Ok(result) => {
+                tx.send(into_event(result)).ok();
+            },
+            Err(e) => write_to_error_log(ErrorType::Error(e)),
+        });
+
+        self.jobs.send(job).ok();
+    }
+
+    /// Blocks until the queries sent so far ran, used before quitting so queued writes are not lost. Returns `false`
+    /// if they did not finish within `timeout`
+    pub fn flush(&self, timeout: Duration) -> bool {
+        let (done_tx, done_rx) = std_mpsc::channel();
+
+        let job: Job = Box::new(move |_| {
+            done_tx.send(()).ok();
+        });
+
+        self.jobs.send(job).is_ok() && done_rx.recv_timeout(timeout).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_runs_the_queries_in_the_order_they_were_sent() -> Result<(), Box<dyn Error + Send + Sync>> {
+        let database = DatabaseActor::spawn(Connection::open_in_memory);
+
+        let create_table = database.run(|conn| conn.execute("CREATE TABLE mangas (id TEXT PRIMARY KEY)", []));
+        let insert = database.run(|conn| conn.execute("INSERT INTO mangas(id) VALUES ('some_id')", []));
+        let count = database.run(|conn| conn.query_row("SELECT COUNT(*) FROM mangas", [], |row| row.get::<_, u32>(0)));
+
+        assert_eq!(1, count.await?);
+        assert_eq!(1, insert.await?);
+        assert_eq!(0, create_table.await?);
+
+        let error = database.run(|conn| conn.execute("INSERT INTO chapters(id) VALUES ('some_id')", [])).await;
+
+        assert!(error.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn queries_executed_without_waiting_run_before_a_flush_returns() {
+        let database = DatabaseActor::spawn(Connection::open_in_memory);
+
+        database.execute(|conn| conn.execute_batch("CREATE TABLE mangas (id TEXT PRIMARY KEY)"));
+        database.execute(|conn| conn.execute("INSERT INTO mangas(id) VALUES ('some_id')", []).map(|_| ()));
+
+        assert!(database.flush(Duration::from_secs(5)));
+
+        let count = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(database.run(|conn| conn.query_row("SELECT COUNT(*) FROM mangas", [], |row| row.get::<_, u32>(0))))
+            .unwrap();
+
+        assert_eq!(1, count);
+    }
+
+    #[test]
+    fn the_result_of_a_query_is_sent_as_an_event() {
+        let database = DatabaseActor::spawn(Connection::open_in_memory);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        database.query(tx.clone(), |conn| Ok(conn.query_row("SELECT 1 + 1", [], |row| row.get::<_, u32>(0))?), Some);
+        database.query(tx, |conn| Ok(conn.execute("INSERT INTO mangas(id) VALUES ('some_id')", [])?), |_| None);
+
+        assert!(database.flush(Duration::from_secs(5)));
+
+        assert_eq!(Ok(Some(2)), rx.try_recv());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn queries_fail_if_the_connection_could_not_be_opened() {
+        let database = DatabaseActor::spawn(|| Connection::open("/this/directory/does/not/exist/manga-tui.db"));
+
+        assert!(
+            database
+                .run(|conn| conn.execute("CREATE TABLE mangas (id TEXT PRIMARY KEY)", []))
+                .await
+                .is_err()
+        );
+    }
+}
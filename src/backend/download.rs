@@ -1,4 +1,4 @@
-use std::fs::{create_dir, create_dir_all, File};
+use std::fs::{self, create_dir, create_dir_all, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
@@ -25,6 +25,74 @@ static EPUB_FILE_TEMPLATE: &str = r#"
                             </html>
 "#;
 
+/// Most filesystems limit a single file name to 255 bytes, some room is left for the extension
+const WINDOWS_MAX_FILE_NAME_BYTES: usize = 240;
+
+/// Names are only shortened on Windows where paths are short, elsewhere they are kept whole so the chapters downloaded by
+/// older versions are still found
+const MAX_FILE_NAME_BYTES: usize = if cfg!(windows) { WINDOWS_MAX_FILE_NAME_BYTES } else { usize::MAX };
+
+/// Cut `text` to at most `max_bytes` without splitting a character
+fn truncate_to_bytes(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    text[..end].trim_end()
+}
+
+/// Windows paths are limited to 260 characters unless they are written in their extended form, which is `\\?\C:\...`
+/// for local paths and `\\?\UNC\server\share\...` for network shares, returns `None` if the path cannot be extended
+fn extended_length_path(path: &str) -> Option<String> {
+    if path.starts_with(r"\\?\") {
+        return None;
+    }
+
+    // extended paths are not normalized so only backslashes are valid separators
+    let path = path.replace('/', r"\");
+
+    if let Some(share) = path.strip_prefix(r"\\") {
+        return Some(format!(r"\\?\UNC\{share}"));
+    }
+
+    let bytes = path.as_bytes();
+    let is_absolute_with_drive = bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\';
+
+    is_absolute_with_drive.then(|| format!(r"\\?\{path}"))
+}
+
+/// The path used to create files and directories, so that downloading to deeply nested directories works on Windows
+fn long_path(path: &Path) -> PathBuf {
+    if cfg!(windows) {
+        if let Some(extended) = path.to_str().and_then(extended_length_path) {
+            return PathBuf::from(extended);
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Where a chapter is written while it is being downloaded, it is moved to `path` once it is complete so that a
+/// chapter which failed to download or was interrupted is never mistaken for a complete one
+pub fn partial_download_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".part");
+    path.with_file_name(file_name)
+}
+
+/// Move a complete download from its partial path to `path`, replacing the chapter if it was downloaded before
+pub fn finish_download(path: &Path) -> Result<(), std::io::Error> {
+    if path.is_dir() {
+        fs::remove_dir_all(long_path(path))?;
+    }
+
+    fs::rename(long_path(&partial_download_path(path)), long_path(path))
+}
+
 #[derive(Debug, Clone)]
 pub struct DownloadChapter {
     id_chapter: SanitizedFilename,
@@ -74,22 +142,43 @@ impl<'a> DownloadChapter {
     }
 
     fn make_chapter_file_name(&'a self) -> String {
-        let file_name = format!("Ch. {} {} {} {}", self.number, self.chapter_title, self.scanlator, self.id_chapter);
-        file_name
+        self.chapter_file_name_within(MAX_FILE_NAME_BYTES)
+    }
+
+    /// The chapter title is shortened first and then the scanlator if the name is longer than `max_bytes`, the id is
+    /// always kept so that the name is still unique
+    fn chapter_file_name_within(&'a self, max_bytes: usize) -> String {
+        let fixed_length = format!("Ch. {}   {}", self.number, self.id_chapter).len();
+        let available = max_bytes.saturating_sub(fixed_length);
+
+        let scanlator = self.scanlator.to_string();
+        let scanlator = truncate_to_bytes(&scanlator, available);
+
+        let chapter_title = self.chapter_title.to_string();
+        let chapter_title = truncate_to_bytes(&chapter_title, available - scanlator.len());
+
+        format!("Ch. {} {} {} {}", self.number, chapter_title, scanlator, self.id_chapter)
     }
 
     fn make_manga_directory_filename(&'a self) -> String {
-        format!("{} {}", self.manga_title, self.manga_id)
+        self.manga_directory_filename_within(MAX_FILE_NAME_BYTES)
+    }
+
+    fn manga_directory_filename_within(&'a self, max_bytes: usize) -> String {
+        let manga_id = self.manga_id.to_string();
+        let manga_title = self.manga_title.to_string();
+
+        format!("{} {}", truncate_to_bytes(&manga_title, max_bytes.saturating_sub(manga_id.len() + 1)), manga_id)
     }
 
+    /// The images are written to the partial path of the returned directory, see [`finish_download`]
     pub fn make_chapter_directory(&'a self, base_directory: &Path) -> Result<PathBuf, std::io::Error> {
         let directory = base_directory.join(self.make_chapter_file_name());
-        if !exists!(&directory) {
-            create_dir(&directory)?;
-            Ok(directory)
-        } else {
-            Ok(directory)
+        let partial_directory = partial_download_path(&directory);
+        if !exists!(&partial_directory) {
+            create_dir(long_path(&partial_directory))?;
         }
+        Ok(directory)
     }
 
     pub fn create_image_file(
@@ -100,7 +189,7 @@ impl<'a> DownloadChapter {
     ) -> Result<PathBuf, std::io::Error> {
         let image_path = base_directory.join(image_filename.as_path());
 
-        let mut image_created = File::create(&image_path)?;
+        let mut image_created = File::create(long_path(&image_path))?;
 
         image_created.write_all(image_bytes)?;
 
@@ -112,7 +201,7 @@ impl<'a> DownloadChapter {
 
         let cbz_path = base_directory.join(&cbz_filename);
 
-        let cbz_file = File::create(&cbz_path)?;
+        let cbz_file = File::create(long_path(&partial_download_path(&cbz_path)))?;
 
         let zip = ZipWriter::new(cbz_file);
 
@@ -132,7 +221,7 @@ impl<'a> DownloadChapter {
     pub fn create_epub_file(&'a self, base_directory: &Path) -> color_eyre::eyre::Result<(EpubBuilder<ZipLibrary>, File, PathBuf)> {
         let epub_path = base_directory.join(format!("{}.epub", self.make_chapter_file_name()));
 
-        let epub_file = File::create(&epub_path)?;
+        let epub_file = File::create(long_path(&partial_download_path(&epub_path)))?;
 
         let mut epub_builder = EpubBuilder::new(ZipLibrary::new()?)?;
 
@@ -175,22 +264,37 @@ impl<'a> DownloadChapter {
         let dir_manga = base_directory.join(self.make_manga_directory_filename());
 
         if !exists!(&dir_manga) {
-            create_dir_all(&dir_manga)?;
+            create_dir_all(long_path(&dir_manga))?;
         }
 
         let chapter_language_dir = dir_manga.join(self.lang.as_path());
 
         if !exists!(&chapter_language_dir) {
-            create_dir(&chapter_language_dir)?;
+            create_dir(long_path(&chapter_language_dir))?;
         }
 
         Ok(chapter_language_dir)
     }
 }
 
+/// Size in bytes of a downloaded chapter, which is either a file or a directory with the images
+pub fn download_size(path: &Path) -> Result<u64, std::io::Error> {
+    let metadata = fs::metadata(path)?;
+
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut size = 0;
+    for entry in fs::read_dir(path)? {
+        size += download_size(&entry?.path())?;
+    }
+
+    Ok(size)
+}
+
 #[cfg(test)]
 mod tests {
-    use std::fs;
 
     use fake::faker::name::en::Name;
     use fake::Fake;
@@ -222,6 +326,120 @@ mod tests {
         )
     }
 
+    #[test]
+    fn download_size_adds_up_the_images_of_a_chapter_directory() -> Result<(), std::io::Error> {
+        let chapter_directory = create_tests_directory()?.join(Uuid::new_v4().to_string());
+
+        create_dir_all(&chapter_directory)?;
+
+        fs::write(chapter_directory.join("1.jpg"), [0; 10])?;
+        fs::write(chapter_directory.join("2.jpg"), [0; 5])?;
+
+        assert_eq!(15, download_size(&chapter_directory)?);
+        assert_eq!(10, download_size(&chapter_directory.join("1.jpg"))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn long_names_are_shortened_but_keep_the_id() {
+        let chapter = DownloadChapter::new(
+            "some-chapter-id",
+            "some-manga-id",
+            &"ñ".repeat(300),
+            &"é".repeat(200),
+            "12",
+            "scanlator",
+            "English",
+        );
+
+        let chapter_name = chapter.chapter_file_name_within(WINDOWS_MAX_FILE_NAME_BYTES);
+
+        assert!(chapter_name.len() <= WINDOWS_MAX_FILE_NAME_BYTES);
+        assert!(chapter_name.starts_with("Ch. 12 ééé"));
+        assert!(chapter_name.ends_with(" scanlator some-chapter-id"));
+
+        let directory_name = chapter.manga_directory_filename_within(WINDOWS_MAX_FILE_NAME_BYTES);
+
+        assert!(directory_name.len() <= WINDOWS_MAX_FILE_NAME_BYTES);
+        assert!(directory_name.ends_with("ñ some-manga-id"));
+
+        let chapter = DownloadChapter::new("some-chapter-id", "some-manga-id", "title", "", "1", &"s".repeat(300), "English");
+
+        let chapter_name = chapter.chapter_file_name_within(WINDOWS_MAX_FILE_NAME_BYTES);
+
+        assert!(chapter_name.len() <= WINDOWS_MAX_FILE_NAME_BYTES);
+        assert!(chapter_name.starts_with("Ch. 1  sss"));
+        assert!(chapter_name.ends_with(" some-chapter-id"));
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn long_names_are_kept_whole_outside_windows() {
+        let manga_title = "ñ".repeat(300);
+        let chapter = DownloadChapter::new("some-chapter-id", "some-manga-id", &manga_title, "title", "1", "", "English");
+
+        assert_eq!(format!("{manga_title} some-manga-id"), chapter.make_manga_directory_filename());
+    }
+
+    #[test]
+    fn short_names_are_not_changed() {
+        let chapter =
+            DownloadChapter::new("some-chapter-id", "some-manga-id", "Some manga", "Some title", "1", "scanlator", "English");
+
+        assert_eq!("Ch. 1 Some title scanlator some-chapter-id", chapter.make_chapter_file_name());
+        assert_eq!("Some manga some-manga-id", chapter.make_manga_directory_filename());
+    }
+
+    #[test]
+    fn finished_downloads_are_moved_from_their_partial_path() -> Result<(), std::io::Error> {
+        let base_directory = create_tests_directory()?.join(Uuid::new_v4().to_string());
+        create_dir_all(&base_directory)?;
+
+        let chapter_file = base_directory.join("chapter.cbz");
+
+        assert_eq!(base_directory.join("chapter.cbz.part"), partial_download_path(&chapter_file));
+
+        fs::write(partial_download_path(&chapter_file), "complete chapter")?;
+        finish_download(&chapter_file)?;
+
+        assert_eq!("complete chapter", fs::read_to_string(&chapter_file)?);
+        assert!(!partial_download_path(&chapter_file).exists());
+
+        let chapter_directory = base_directory.join("chapter");
+        create_dir_all(&chapter_directory)?;
+        fs::write(chapter_directory.join("1.jpg"), "old page")?;
+
+        create_dir_all(partial_download_path(&chapter_directory))?;
+        fs::write(partial_download_path(&chapter_directory).join("1.jpg"), "new page")?;
+        finish_download(&chapter_directory)?;
+
+        assert_eq!("new page", fs::read_to_string(chapter_directory.join("1.jpg"))?);
+        assert!(!partial_download_path(&chapter_directory).exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn truncating_does_not_split_characters() {
+        assert_eq!("ab", truncate_to_bytes("abñ", 3));
+        assert_eq!("abñ", truncate_to_bytes("abñ", 4));
+        assert_eq!("ab", truncate_to_bytes("ab  cd", 4));
+    }
+
+    #[test]
+    fn windows_paths_are_written_in_their_extended_form() {
+        assert_eq!(
+            Some(r"\\?\C:\Users\user\manga-tui\mangaDownloads".to_string()),
+            extended_length_path(r"C:\Users\user\manga-tui\mangaDownloads")
+        );
+        assert_eq!(Some(r"\\?\D:\manga\chapter".to_string()), extended_length_path("D:/manga/chapter"));
+        assert_eq!(Some(r"\\?\UNC\server\share\manga".to_string()), extended_length_path(r"\\server\share\manga"));
+        assert_eq!(None, extended_length_path(r"\\?\C:\already\extended"));
+        assert_eq!(None, extended_length_path(r"relative\path"));
+        assert_eq!(None, extended_length_path("/home/user/manga"));
+    }
+
     /// For creating epub or cbz chapter file
     #[test]
     #[ignore]
@@ -285,7 +503,7 @@ mod tests {
 
         let path = chapter.make_chapter_directory(&base_directory)?;
 
-        fs::read_dir(&path)?;
+        fs::read_dir(partial_download_path(&path))?;
 
         assert_eq!(path, base_directory.join(chapter.make_chapter_file_name()));
 
@@ -326,6 +544,10 @@ mod tests {
 
         zip.finish()?;
 
+        assert!(!cbz_path.exists());
+
+        finish_download(&cbz_path)?;
+
         let zip_file_created = File::open(&cbz_path)?;
 
         let mut zip_file_created = zip::ZipArchive::new(zip_file_created)?;
@@ -351,6 +573,9 @@ mod tests {
         chapter.insert_into_epub(&mut epub_builder, "test2.jpg", "jpg", 1, include_bytes!("../../data_test/images/2.jpg"));
 
         epub_builder.generate(&mut file)?;
+        drop(file);
+
+        finish_download(&epub_path)?;
 
         fs::File::open(&epub_path)?;
 
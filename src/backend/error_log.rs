@@ -27,7 +27,7 @@ impl<'a> From<String> for ErrorType<'a> {
     }
 }
 
-fn get_error_logs_path() -> PathBuf {
+pub fn get_error_logs_path() -> PathBuf {
     let path = AppDirectories::ErrorLogs.get_base_directory();
 
     if !exists!(&path) {
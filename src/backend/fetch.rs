@@ -1,18 +1,31 @@
 use std::error::Error;
 use std::future::Future;
-use std::time::Duration as StdDuration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration as StdDuration, Instant};
 
 use bytes::Bytes;
 use chrono::Months;
 use image::io::Reader;
 use image::GenericImageView;
-use manga_tui::SearchTerm;
+use manga_tui::{ChapterNumber, SearchTerm};
 use once_cell::sync::OnceCell;
-use reqwest::{Client, Response, Url};
-
-use super::api_responses::{AggregateChapterResponse, ChapterPagesResponse};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Client, RequestBuilder, Response, StatusCode, Url};
+use serde::Serialize;
+use tokio::sync::Mutex as AsyncMutex;
+
+use super::api_responses::auth::LoginResponse;
+use super::api_responses::custom_list::CustomListResponse;
+use super::api_responses::{AggregateChapterResponse, ChapterPagesResponse, SearchMangaResponse};
+use super::cache::{ChapterPagesCache, TagsCache, TAGS_CACHE_FILE, TAGS_CACHE_TTL};
+use super::error_log::{write_to_error_log, ErrorType};
 use super::filter::Languages;
+use super::rate_limit::{retry_after, RateLimit, MAX_REPLAYS};
+use super::request_log::{RequestLog, RequestRecord};
+use super::secrets::mangadex::MangadexAccount;
+use crate::backend::api_responses::tags::TagsResponse;
 use crate::backend::api_responses::OneChapterResponse;
+use crate::backend::AppDirectories;
 use crate::backend::filter::{Filters, IntoParam};
 use crate::config::ImageQuality;
 use crate::global::USER_AGENT;
@@ -22,7 +35,8 @@ use crate::view::pages::reader::{ChapterToRead, ListOfChapters, MangaPanel, Sear
 
 // Todo! this trait should be split 💀💀
 pub trait ApiClient: Clone + Send + 'static {
-    fn get_chapter_page(&self, endpoint: Url) -> impl Future<Output = Result<Response, reqwest::Error>> + Send;
+    /// The bytes of the image, the whole body is read so the time it took to download it can be reported
+    fn get_chapter_page(&self, endpoint: Url) -> impl Future<Output = Result<Bytes, reqwest::Error>> + Send;
 
     fn search_mangas(
         &self,
@@ -46,6 +60,7 @@ pub trait ApiClient: Clone + Send + 'static {
         page: u32,
         language: Languages,
         order: ChapterOrder,
+        excluded_groups: &[String],
     ) -> impl Future<Output = Result<Response, reqwest::Error>> + Send;
 
     fn get_chapter_pages(&self, chapter_id: &str) -> impl Future<Output = Result<Response, reqwest::Error>> + Send;
@@ -69,6 +84,8 @@ pub trait ApiClient: Clone + Send + 'static {
         id: &str,
         language: Languages,
     ) -> impl Future<Output = Result<Response, reqwest::Error>> + Send;
+
+    fn get_latest_updates(&self, page: u32, language: Languages) -> impl Future<Output = Result<Response, reqwest::Error>> + Send;
 }
 
 #[derive(Clone, Debug)]
@@ -76,7 +93,66 @@ pub struct MangadexClient {
     client: reqwest::Client,
     api_url_base: Url,
     cover_img_url_base: Url,
+    /// Overrides the host mangadex assigns to download chapter pages from
+    images_url_base: Option<Url>,
     image_quality: ImageQuality,
+    chapter_pages_cache: Arc<Mutex<ChapterPagesCache>>,
+    tags_cache: TagsCache,
+    auth_url: Url,
+    /// Pages downloaded from MangaDex@Home servers are only reported if this is set
+    at_home_report_url: Option<Url>,
+    /// Only set if the user ran `manga-tui mangadex init`
+    account: Option<MangadexAccount>,
+    /// Locked while logging in so requests sent at the same time log in only once
+    access_token: Arc<AsyncMutex<Option<AccessToken>>>,
+    /// Only set when running with `--debug`
+    request_log: Option<&'static RequestLog>,
+}
+
+/// Mangadex asks clients to report whether pages could be downloaded from its MangaDex@Home servers and how long it
+/// took, which helps it route readers to healthy servers
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct AtHomeReport {
+    url: String,
+    success: bool,
+    cached: bool,
+    bytes: u64,
+    /// In milliseconds
+    duration: u128,
+}
+
+impl AtHomeReport {
+    /// Made before the body is read since reading it consumes the response
+    fn new(url: &Url, response: Option<&Response>) -> Self {
+        Self {
+            url: url.to_string(),
+            success: response.is_some_and(|response| response.status().is_success()),
+            cached: response
+                .and_then(|response| response.headers().get("X-Cache"))
+                .and_then(|cache| cache.to_str().ok())
+                .is_some_and(|cache| cache.starts_with("HIT")),
+            bytes: 0,
+            duration: 0,
+        }
+    }
+
+    /// The page counts as downloaded only once its whole body was read, `duration` includes reading it
+    fn page_read(self, page: Option<&Bytes>, duration: StdDuration) -> Self {
+        Self {
+            success: self.success && page.is_some(),
+            bytes: page.map(|page| page.len() as u64).unwrap_or_default(),
+            duration: duration.as_millis(),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AccessToken {
+    token: String,
+    /// Used to get a new access token once this one expires without sending the password again
+    refresh_token: String,
+    expires_at: Instant,
 }
 
 pub static MANGADEX_CLIENT_INSTANCE: OnceCell<MangadexClient> = once_cell::sync::OnceCell::new();
@@ -85,12 +161,32 @@ pub static API_URL_BASE: &str = "https://api.mangadex.org";
 
 pub static COVER_IMG_URL_BASE: &str = "https://uploads.mangadex.org/covers";
 
+/// Where the result of downloading a page from a MangaDex@Home server is reported
+pub static AT_HOME_REPORT_URL: &str = "https://api.mangadex.network/report";
+
+pub static AUTH_URL: &str = "https://auth.mangadex.org/realms/mangadex/protocol/openid-connect/token";
+
 pub static ITEMS_PER_PAGE_CHAPTERS: u32 = 16;
 
 pub static ITEMS_PER_PAGE_LATEST_CHAPTERS: u32 = 5;
 
 pub static ITEMS_PER_PAGE_SEARCH: u32 = 10;
 
+pub static ITEMS_PER_PAGE_LATEST_UPDATES: u32 = 20;
+
+/// The home page only shows this many mangas of each list
+pub static ITEMS_PER_CUSTOM_LIST: usize = 5;
+
+/// The list mangadex shows in its home page with the mangas chosen by its staff
+pub static STAFF_PICKS_LIST_ID: &str = "805ba886-dd99-4aa4-b460-4bd7c7b71352";
+
+/// The name of a mangadex list along with its first mangas
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CustomListMangas {
+    pub name: String,
+    pub mangas: SearchMangaResponse,
+}
+
 impl MangadexClient {
     pub fn global() -> &'static MangadexClient {
         MANGADEX_CLIENT_INSTANCE.get().expect("could not build mangadex client")
@@ -108,6 +204,14 @@ impl MangadexClient {
             api_url_base,
             cover_img_url_base,
             image_quality: ImageQuality::default(),
+            chapter_pages_cache: Arc::new(Mutex::new(ChapterPagesCache::default())),
+            tags_cache: TagsCache::new(AppDirectories::Cache.get_full_path().join(TAGS_CACHE_FILE), TAGS_CACHE_TTL),
+            auth_url: AUTH_URL.parse().unwrap(),
+            at_home_report_url: None,
+            images_url_base: None,
+            account: None,
+            access_token: Arc::new(AsyncMutex::new(None)),
+            request_log: None,
         }
     }
 
@@ -116,11 +220,268 @@ impl MangadexClient {
         self
     }
 
+    pub fn with_tags_cache(mut self, tags_cache: TagsCache) -> Self {
+        self.tags_cache = tags_cache;
+        self
+    }
+
+    pub fn with_auth_url(mut self, auth_url: Url) -> Self {
+        self.auth_url = auth_url;
+        self
+    }
+
+    pub fn with_at_home_report_url(mut self, at_home_report_url: Url) -> Self {
+        self.at_home_report_url = Some(at_home_report_url);
+        self
+    }
+
+    pub fn with_images_url(mut self, images_url_base: Url) -> Self {
+        self.images_url_base = Some(images_url_base);
+        self
+    }
+
+    pub fn with_account(mut self, account: MangadexAccount) -> Self {
+        self.account = Some(account);
+        self
+    }
+
+    pub fn with_request_log(mut self, request_log: &'static RequestLog) -> Self {
+        self.request_log = Some(request_log);
+        self
+    }
+
+    pub fn is_logged_in(&self) -> bool {
+        self.account.is_some()
+    }
+
+    /// Get an access token with the personal client of the account, it is reused until it expires and then renewed with
+    /// the refresh token, the password is only sent again if renewing it fails
+    pub async fn login(&self) -> Result<String, Box<dyn Error>> {
+        let account = self
+            .account
+            .as_ref()
+            .ok_or("there is no mangadex account setup, run `manga-tui mangadex init`")?;
+
+        let mut access_token = self.access_token.lock().await;
+
+        if let Some(access_token) = access_token.as_ref().filter(|access_token| access_token.expires_at > Instant::now()) {
+            return Ok(access_token.token.clone());
+        }
+
+        let password_form = [
+            ("grant_type", "password"),
+            ("username", &account.username),
+            ("password", &account.password),
+            ("client_id", &account.client_id),
+            ("client_secret", &account.client_secret),
+        ];
+
+        let login = match access_token.as_ref() {
+            Some(expired) => {
+                let refresh_form = [
+                    ("grant_type", "refresh_token"),
+                    ("refresh_token", &expired.refresh_token),
+                    ("client_id", &account.client_id),
+                    ("client_secret", &account.client_secret),
+                ];
+
+                let renewed = self.request_access_token(&refresh_form).await.ok();
+
+                match renewed {
+                    Some(login) => login,
+                    None => self.request_access_token(&password_form).await?,
+                }
+            },
+            None => self.request_access_token(&password_form).await?,
+        };
+
+        // Renewed a bit before it actually expires so requests being sent don't fail
+        let expires_at = Instant::now() + StdDuration::from_secs(login.expires_in.saturating_sub(30));
+
+        *access_token = Some(AccessToken {
+            token: login.access_token.clone(),
+            refresh_token: login.refresh_token,
+            expires_at,
+        });
+
+        Ok(login.access_token)
+    }
+
+    async fn request_access_token(&self, form: &[(&str, &str)]) -> Result<LoginResponse, Box<dyn Error>> {
+        // Not sent with `send` so the tokens are not shown in the debug page
+        let response = self.client.post(self.auth_url.clone()).form(form).send().await?;
+
+        if !response.status().is_success() {
+            return Err(format!("could not log in to mangadex, status: {}", response.status()).into());
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Used in `feed` page to list the mangas the account follows
+    pub async fn get_followed_mangas(&self, page: u32, items_per_page: u32) -> Result<SearchMangaResponse, Box<dyn Error>> {
+        let access_token = self.login().await?;
+        let offset = (page - 1) * items_per_page;
+
+        let endpoint = format!(
+            "{}/user/follows/manga?limit={items_per_page}&offset={offset}&includes[]=cover_art&includes[]=author&includes[]=artist",
+            self.api_url_base
+        );
+
+        let response = self.send(self.client.get(endpoint).bearer_auth(access_token)).await?;
+
+        if !response.status().is_success() {
+            return Err(format!("could not get the mangas followed on mangadex, status: {}", response.status()).into());
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Used in `home` page to show the mangas of a list like the staff picks or the seasonal ones
+    pub async fn get_custom_list(&self, list_id: &str) -> Result<CustomListMangas, Box<dyn Error>> {
+        let endpoint = format!("{}/list/{list_id}", self.api_url_base);
+
+        let response = self.send(self.client.get(endpoint)).await?;
+
+        if !response.status().is_success() {
+            return Err(format!("could not get the list {list_id} from mangadex, status: {}", response.status()).into());
+        }
+
+        let list: CustomListResponse = response.json().await?;
+
+        let ids: Vec<&str> = list.manga_ids().take(ITEMS_PER_CUSTOM_LIST).collect();
+
+        if ids.is_empty() {
+            return Ok(CustomListMangas {
+                name: list.data.attributes.name,
+                mangas: SearchMangaResponse::default(),
+            });
+        }
+
+        let mangas = self.get_mangas_by_ids(&ids).await?;
+
+        Ok(CustomListMangas {
+            name: list.data.attributes.name,
+            mangas,
+        })
+    }
+
+    /// At most 100 mangas can be requested at once
+    pub async fn get_mangas_by_ids(&self, manga_ids: &[&str]) -> Result<SearchMangaResponse, Box<dyn Error>> {
+        let limit = manga_ids.len();
+        let ids: String = manga_ids.iter().map(|id| format!("&ids[]={id}")).collect();
+
+        let endpoint = format!(
+            "{}/manga?limit={limit}&includes[]=cover_art&includes[]=artist&includes[]=author&contentRating[]=safe&contentRating[]=suggestive{ids}",
+            self.api_url_base
+        );
+
+        let response = self.send(self.client.get(endpoint)).await?;
+
+        if !response.status().is_success() {
+            return Err(format!("could not get mangas from mangadex, status: {}", response.status()).into());
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Sync the chapters read in manga-tui with the read markers of the account
+    pub async fn mark_chapters_as_read(&self, manga_id: &str, chapter_ids: &[String]) -> Result<(), Box<dyn Error>> {
+        let access_token = self.login().await?;
+
+        let endpoint = format!("{}/manga/{manga_id}/read", self.api_url_base);
+
+        let body = serde_json::json!({ "chapterIdsRead": chapter_ids });
+
+        let response = self.send(self.client.post(endpoint).bearer_auth(access_token).json(&body)).await?;
+
+        if !response.status().is_success() {
+            return Err(format!("could not mark chapters as read on mangadex, status: {}", response.status()).into());
+        }
+
+        Ok(())
+    }
+
+    /// Requests answered with `429 Too Many Requests` are sent again once the time in `Retry-After` passes, at most
+    /// `MAX_REPLAYS` times, meanwhile the ui shows a countdown
+    async fn send(&self, request: RequestBuilder) -> Result<Response, reqwest::Error> {
+        let mut request = request;
+        let mut replays = 0;
+
+        loop {
+            // Requests with a streamed body can't be cloned, those are not replayed
+            let replay = request.try_clone();
+            let response = self.send_and_record(request).await?;
+
+            match replay {
+                Some(replay) if response.status() == StatusCode::TOO_MANY_REQUESTS && replays < MAX_REPLAYS => {
+                    let wait = retry_after(response.headers());
+                    RateLimit::global().wait(wait);
+                    tokio::time::sleep(wait).await;
+
+                    replays += 1;
+                    request = replay;
+                },
+                _ => return Ok(response),
+            }
+        }
+    }
+
+    /// Sends the request and records it if the app is running with `--debug`,
+    /// only text / json bodies are kept, images are recorded without body
+    async fn send_and_record(&self, request: RequestBuilder) -> Result<Response, reqwest::Error> {
+        let Some(request_log) = self.request_log else {
+            return request.send().await;
+        };
+
+        let (client, request) = request.build_split();
+        let request = request?;
+        let method = request.method().to_string();
+        let url = request.url().to_string();
+        let start = Instant::now();
+
+        let response = match client.execute(request).await {
+            Ok(res) => res,
+            Err(e) => {
+                request_log.push(RequestRecord::new(&method, &url, None, start.elapsed(), &e.to_string()));
+                return Err(e);
+            },
+        };
+
+        let status = response.status();
+        let has_text_body = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.contains("json") || content_type.contains("text"));
+
+        if !has_text_body {
+            request_log.push(RequestRecord::new(&method, &url, Some(status.as_u16()), start.elapsed(), ""));
+            return Ok(response);
+        }
+
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+
+        request_log.push(RequestRecord::new(
+            &method,
+            &url,
+            Some(status.as_u16()),
+            start.elapsed(),
+            &String::from_utf8_lossy(&body),
+        ));
+
+        // The body was consumed so the response has to be built again for the caller
+        let mut rebuilt_response = http::Response::new(body);
+        *rebuilt_response.status_mut() = status;
+        *rebuilt_response.headers_mut() = headers;
+
+        Ok(Response::from(rebuilt_response))
+    }
+
     // Not crucial this doesnt need to be tested
     pub async fn get_mangadex_image_support(&self) -> Result<Bytes, reqwest::Error> {
-        self.client
-            .get("https://mangadex.org/img/namicomi/support-dex-chan-1.png")
-            .send()
+        self.send(self.client.get("https://mangadex.org/img/namicomi/support-dex-chan-1.png"))
             .await?
             .bytes()
             .await
@@ -129,24 +490,98 @@ impl MangadexClient {
     /// Check if mangadex is available
     pub async fn check_status(&self) -> Result<Response, reqwest::Error> {
         let endpoint = format!("{}/ping", self.api_url_base);
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
     }
 
     pub async fn search_chapters_aggregate(&self, manga_id: &str, language: Languages) -> Result<Response, reqwest::Error> {
         let endpoint =
             format!("{}/manga/{}/aggregate?translatedLanguage[]={}", self.api_url_base, manga_id, language.as_iso_code());
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
+    }
+
+    fn report_at_home_page(&self, report: AtHomeReport) {
+        let Some(report_url) = self.at_home_report_url.clone() else {
+            return;
+        };
+        let request = self.client.post(report_url).json(&report);
+        tokio::spawn(async move {
+            request.send().await.ok();
+        });
+    }
+
+    /// Get the urls of the pages of a chapter, if it was read recently they are taken from the cache
+    pub async fn get_chapter_pages_url(&self, chapter_id: &str) -> Result<Vec<Url>, Box<dyn Error>> {
+        if let Some(pages_url) = self.chapter_pages_cache.lock().unwrap().get(chapter_id) {
+            return Ok(pages_url);
+        }
+
+        let mut pages_response: ChapterPagesResponse = self.get_chapter_pages(chapter_id).await?.json().await?;
+        if let Some(images_url_base) = self.images_url_base.as_ref() {
+            pages_response.set_base_url(images_url_base);
+        }
+
+        let pages_url = pages_response.get_files_based_on_quality_as_url(self.image_quality);
+
+        if !pages_url.is_empty() {
+            self.chapter_pages_cache.lock().unwrap().insert(chapter_id, pages_url.clone());
+        }
+
+        Ok(pages_url)
     }
 
     pub async fn search_chapters_by_id(&self, chapter_id: &str) -> Result<Response, reqwest::Error> {
         let endpoint = format!("{}/chapter/{chapter_id}", self.api_url_base);
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
+    }
+
+    /// Used in `FilterWidget`, the tags are taken from disk and if they expired they are requested again in the background
+    pub async fn get_cached_filters(&self) -> Result<TagsResponse, Box<dyn Error>> {
+        match self.tags_cache.get() {
+            Some(cached) => {
+                if cached.is_expired {
+                    let api_client = self.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = api_client.refresh_tags_cache().await {
+                            write_to_error_log(ErrorType::Error(e));
+                        }
+                    });
+                }
+                Ok(cached.tags)
+            },
+            None => self.refresh_tags_cache().await,
+        }
+    }
+
+    async fn refresh_tags_cache(&self) -> Result<TagsResponse, Box<dyn Error>> {
+        let tags: TagsResponse = self.get_tags().await?.json().await?;
+
+        // The tags can still be used even if they could not be cached
+        if let Err(e) = self.tags_cache.save(&tags) {
+            write_to_error_log(ErrorType::Error(e));
+        }
+
+        Ok(tags)
     }
 }
 
 impl ApiClient for MangadexClient {
-    async fn get_chapter_page(&self, endpoint: Url) -> Result<Response, reqwest::Error> {
-        self.client.get(endpoint).timeout(StdDuration::from_secs(20)).send().await
+    async fn get_chapter_page(&self, endpoint: Url) -> Result<Bytes, reqwest::Error> {
+        let start = Instant::now();
+        let response = self.send(self.client.get(endpoint.clone()).timeout(StdDuration::from_secs(20))).await;
+
+        let report = AtHomeReport::new(&endpoint, response.as_ref().ok());
+
+        let page = match response {
+            Ok(response) => response.bytes().await,
+            Err(e) => Err(e),
+        };
+
+        // Pages from mangadex's own servers or from a mirror set by the user are not reported
+        if self.images_url_base.is_none() && endpoint.host_str().is_some_and(|host| !host.ends_with("mangadex.org")) {
+            self.report_at_home_page(report.page_read(page.as_ref().ok(), start.elapsed()));
+        }
+
+        page
     }
 
     async fn search_mangas(
@@ -169,22 +604,18 @@ impl ApiClient for MangadexClient {
             self.api_url_base,
         );
 
-        self.client.get(url).send().await
+        self.send(self.client.get(url)).await
     }
 
     async fn get_cover_for_manga(&self, id_manga: &str, file_name: &str) -> Result<Response, reqwest::Error> {
         let file_name = format!("{file_name}.512.jpg");
-        self.client
-            .get(format!("{}/{id_manga}/{file_name}", self.cover_img_url_base))
-            .send()
+        self.send(self.client.get(format!("{}/{id_manga}/{file_name}", self.cover_img_url_base)))
             .await
     }
 
     async fn get_cover_for_manga_lower_quality(&self, id_manga: &str, file_name: &str) -> Result<Response, reqwest::Error> {
         let file_name = format!("{file_name}.256.jpg");
-        self.client
-            .get(format!("{}/{id_manga}/{file_name}", self.cover_img_url_base))
-            .send()
+        self.send(self.client.get(format!("{}/{id_manga}/{file_name}", self.cover_img_url_base)))
             .await
     }
 
@@ -195,32 +626,35 @@ impl ApiClient for MangadexClient {
         page: u32,
         language: Languages,
         order: ChapterOrder,
+        excluded_groups: &[String],
     ) -> Result<Response, reqwest::Error> {
         let language = language.as_iso_code();
         let page = (page - 1) * ITEMS_PER_PAGE_CHAPTERS;
 
         let order = format!("order[volume]={order}&order[chapter]={order}");
 
+        let excluded_groups: String = excluded_groups.iter().map(|group_id| format!("&excludedGroups[]={group_id}")).collect();
+
         let endpoint = format!(
-            "{}/manga/{manga_id}/feed?limit={ITEMS_PER_PAGE_CHAPTERS}&offset={page}&{order}&translatedLanguage[]={language}&includes[]=scanlation_group&includeExternalUrl=0&contentRating[]=safe&contentRating[]=suggestive&contentRating[]=erotica&contentRating[]=pornographic",
+            "{}/manga/{manga_id}/feed?limit={ITEMS_PER_PAGE_CHAPTERS}&offset={page}&{order}&translatedLanguage[]={language}&includes[]=scanlation_group&includeExternalUrl=0&contentRating[]=safe&contentRating[]=suggestive&contentRating[]=erotica&contentRating[]=pornographic{excluded_groups}",
             self.api_url_base,
         );
 
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
     }
 
     /// Used to get the list of endpoints which provide the url to get a chapter's pages / panels
     async fn get_chapter_pages(&self, chapter_id: &str) -> Result<Response, reqwest::Error> {
         let endpoint = format!("{}/at-home/server/{chapter_id}", self.api_url_base);
 
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
     }
 
     /// Used in `manga` page to request the the amount of follows and stars a manga has
     async fn get_manga_statistics(&self, id_manga: &str) -> Result<Response, reqwest::Error> {
         let endpoint = format!("{}/statistics/manga/{id_manga}", self.api_url_base);
 
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
     }
 
     /// Used in `home` page to request the popular mangas of this month
@@ -233,7 +667,7 @@ impl ApiClient for MangadexClient {
             self.api_url_base,
         );
 
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
     }
 
     /// Used in `home` page to request the most recently added mangas
@@ -244,13 +678,13 @@ impl ApiClient for MangadexClient {
             self.api_url_base,
         );
 
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
     }
 
     /// Used in `feed` page to request a single manga
     async fn get_one_manga(&self, manga_id: &str) -> Result<Response, reqwest::Error> {
         let endpoint = format!("{}/manga/{manga_id}?includes[]=cover_art&includes[]=author&includes[]=artist", self.api_url_base);
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
     }
 
     /// Used in `feed` to request most recent chapters of a manga
@@ -260,21 +694,21 @@ impl ApiClient for MangadexClient {
             self.api_url_base,
         );
 
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
     }
 
     /// Request the tags / genres available on mangadex used in `FilterWidget`
     async fn get_tags(&self) -> Result<Response, reqwest::Error> {
         let endpoint = format!("{}/manga/tag", self.api_url_base);
 
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
     }
 
     /// Used in `FilterWidget` to search an author and artist
     async fn get_authors(&self, name_to_search: SearchTerm) -> Result<Response, reqwest::Error> {
         let endpoint = format!("{}/author?name={name_to_search}", self.api_url_base);
 
-        self.client.get(endpoint).send().await
+        self.send(self.client.get(endpoint)).await
     }
 
     /// Used when downloading all chapters of a manga, request as much chapters as possible
@@ -288,7 +722,20 @@ impl ApiClient for MangadexClient {
             self.api_url_base
         );
 
-        self.client.get(endpoint).timeout(StdDuration::from_secs(10)).send().await
+        self.send(self.client.get(endpoint).timeout(StdDuration::from_secs(10))).await
+    }
+
+    /// The chapters most recently published of any manga, like the homepage of mangadex
+    async fn get_latest_updates(&self, page: u32, language: Languages) -> Result<Response, reqwest::Error> {
+        let language = language.as_iso_code();
+        let offset = (page - 1) * ITEMS_PER_PAGE_LATEST_UPDATES;
+
+        let endpoint = format!(
+            "{}/chapter?limit={ITEMS_PER_PAGE_LATEST_UPDATES}&offset={offset}&translatedLanguage[]={language}&includes[]=manga&includes[]=scanlation_group&order[readableAt]=desc&includeExternalUrl=0&contentRating[]=safe&contentRating[]=suggestive&contentRating[]=erotica&contentRating[]=pornographic",
+            self.api_url_base
+        );
+
+        self.send(self.client.get(endpoint)).await
     }
 }
 
@@ -297,6 +744,7 @@ pub mod fake_api_client {
 
     use std::time::Duration;
 
+    use bytes::Bytes;
     use manga_tui::SearchTerm;
     use reqwest::{Client, Response, Url};
     use serde::Serialize;
@@ -316,6 +764,7 @@ pub mod fake_api_client {
         /// How many `items` the fake response is expected to return
         amount_results: Option<usize>,
         chapters_response: Option<ChapterResponse>,
+        search_response: Option<SearchMangaResponse>,
         return_error: bool,
         client: Client,
     }
@@ -349,6 +798,11 @@ pub mod fake_api_client {
             self
         }
 
+        pub fn with_search_response(mut self, response: SearchMangaResponse) -> Self {
+            self.search_response = Some(response);
+            self
+        }
+
         pub fn with_returning_errors(mut self) -> Self {
             self.return_error = true;
             self
@@ -358,6 +812,7 @@ pub mod fake_api_client {
             MockMangadexClient {
                 amount_results: None,
                 chapters_response: None,
+                search_response: None,
                 return_error: false,
                 client: Client::builder().timeout(Duration::from_millis(100)).build().unwrap(),
             }
@@ -376,8 +831,8 @@ pub mod fake_api_client {
     }
 
     impl ApiClient for MockMangadexClient {
-        async fn get_chapter_page(&self, _endpoint: Url) -> Result<Response, reqwest::Error> {
-            Self::mock_bytes_response()
+        async fn get_chapter_page(&self, _endpoint: Url) -> Result<Bytes, reqwest::Error> {
+            Self::mock_bytes_response()?.bytes().await
         }
 
         async fn search_mangas(
@@ -386,7 +841,7 @@ pub mod fake_api_client {
             _page: u32,
             _filters: Filters,
         ) -> Result<Response, reqwest::Error> {
-            Self::mock_json_response(SearchMangaResponse::default())
+            Self::mock_json_response(self.search_response.clone().unwrap_or_default())
         }
 
         async fn get_cover_for_manga(&self, _id_manga: &str, _file_name: &str) -> Result<Response, reqwest::Error> {
@@ -403,6 +858,7 @@ pub mod fake_api_client {
             _page: u32,
             _language: Languages,
             _order: ChapterOrder,
+            _excluded_groups: &[String],
         ) -> Result<Response, reqwest::Error> {
             Self::mock_json_response(ChapterResponse::default())
         }
@@ -452,6 +908,10 @@ pub mod fake_api_client {
             Self::mock_json_response(self.chapters_response.as_ref().cloned().unwrap_or_default())
         }
 
+        async fn get_latest_updates(&self, _page: u32, _language: Languages) -> Result<Response, reqwest::Error> {
+            Self::mock_json_response(self.chapters_response.as_ref().cloned().unwrap_or_default())
+        }
+
         async fn get_tags(&self) -> Result<Response, reqwest::Error> {
             Self::mock_json_response(TagsResponse::default())
         }
@@ -469,7 +929,7 @@ pub mod fake_api_client {
 impl SearchChapter for MangadexClient {
     async fn search_chapter(&self, chapter_id: &str) -> Result<ChapterToRead, Box<dyn std::error::Error>> {
         let response: OneChapterResponse = self.search_chapters_by_id(chapter_id).await?.json().await?;
-        let pages_response: ChapterPagesResponse = self.get_chapter_pages(chapter_id).await?.json().await?;
+        let pages_url = self.get_chapter_pages_url(chapter_id).await?;
 
         let language = Languages::try_from_iso_code(response.data.attributes.translated_language.as_str()).unwrap_or_default();
 
@@ -480,19 +940,20 @@ impl SearchChapter for MangadexClient {
                 .data
                 .attributes
                 .chapter
-                .map(|num| num.parse().unwrap_or_default())
+                .map(|num| ChapterNumber::parse(&num).as_f64())
                 .unwrap_or_default(),
             volume_number: response.data.attributes.volume,
             num_page_bookmarked: None,
+            page_to_resume: None,
             language,
-            pages_url: pages_response.get_files_based_on_quality_as_url(self.image_quality),
+            pages_url,
         })
     }
 }
 
 impl SearchMangaPanel for MangadexClient {
     async fn search_manga_panel(&self, endpoint: Url) -> Result<MangaPanel, Box<dyn Error>> {
-        let response = self.get_chapter_page(endpoint).await?.bytes().await?;
+        let response = self.get_chapter_page(endpoint).await?;
 
         let image_decoded = Reader::new(std::io::Cursor::new(response)).with_guessed_format()?.decode()?;
 
@@ -511,7 +972,7 @@ impl FetchChapterBookmarked for MangadexClient {
         chapter: super::database::ChapterBookmarked,
     ) -> Result<(ChapterToRead, MangaToRead), Box<dyn Error>> {
         let response: OneChapterResponse = self.search_chapters_by_id(&chapter.id).await?.json().await?;
-        let pages_response: ChapterPagesResponse = self.get_chapter_pages(&chapter.id).await?.json().await?;
+        let pages_url = self.get_chapter_pages_url(&chapter.id).await?;
 
         let list_of_chapters: AggregateChapterResponse = self
             .search_chapters_aggregate(
@@ -522,7 +983,12 @@ impl FetchChapterBookmarked for MangadexClient {
             .json()
             .await?;
 
-        let number: f64 = response.data.attributes.chapter.unwrap_or("0".to_string()).parse().unwrap();
+        let number = response
+            .data
+            .attributes
+            .chapter
+            .map(|num| ChapterNumber::parse(&num).as_f64())
+            .unwrap_or_default();
         let volume_number = response.data.attributes.volume;
         let language = Languages::try_from_iso_code(&response.data.attributes.translated_language).unwrap_or_default();
 
@@ -532,8 +998,9 @@ impl FetchChapterBookmarked for MangadexClient {
             number,
             volume_number,
             num_page_bookmarked: chapter.number_page_bookmarked,
+            page_to_resume: None,
             language,
-            pages_url: pages_response.get_files_based_on_quality_as_url(self.image_quality),
+            pages_url,
         };
 
         let manga_to_read: MangaToRead = MangaToRead {
@@ -548,22 +1015,41 @@ impl FetchChapterBookmarked for MangadexClient {
 
 #[cfg(test)]
 mod test {
-    use httpmock::Method::GET;
+    use httpmock::Method::{GET, POST};
     use httpmock::MockServer;
     use pretty_assertions::assert_eq;
     use reqwest::StatusCode;
     use uuid::Uuid;
 
     use self::api_responses::authors::AuthorsResponse;
+    use self::api_responses::custom_list;
     use self::api_responses::feed::OneMangaResponse;
     use self::api_responses::tags::TagsResponse;
     use self::api_responses::{
-        AggregateChapterResponse, ChapterPagesResponse, ChapterResponse, MangaStatisticsResponse, OneChapterResponse,
+        AggregateChapterResponse, ChapterPages, ChapterPagesResponse, ChapterResponse, MangaStatisticsResponse, OneChapterResponse,
         SearchMangaResponse,
     };
     use super::*;
+    use crate::backend::request_log::MAX_REQUESTS_RECORDED;
     use crate::backend::*;
 
+    fn mangadex_account() -> MangadexAccount {
+        MangadexAccount {
+            client_id: "personal-client-id".to_string(),
+            client_secret: "client_secret".to_string(),
+            username: "username".to_string(),
+            password: "password".to_string(),
+        }
+    }
+
+    fn login_response() -> api_responses::auth::LoginResponse {
+        api_responses::auth::LoginResponse {
+            access_token: "access_token".to_string(),
+            expires_in: 900,
+            refresh_token: "refresh_token".to_string(),
+        }
+    }
+
     #[test]
     fn expected_mangadex_endpoints() {
         assert_eq!("https://api.mangadex.org", API_URL_BASE);
@@ -606,6 +1092,26 @@ mod test {
         assert_eq!(expected, response);
     }
 
+    #[tokio::test]
+    async fn requests_rate_limited_are_replayed_after_retry_after() {
+        let server = MockServer::start_async().await;
+
+        let rate_limited = server
+            .mock_async(|when, then| {
+                when.method(GET).path_contains("/ping");
+                then.status(429).header("Retry-After", "0");
+            })
+            .await;
+
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap());
+
+        let response = client.check_status().await.expect("the request should not fail");
+
+        rate_limited.assert_hits_async(1 + MAX_REPLAYS as usize).await;
+
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    }
+
     #[tokio::test]
     async fn get_cover_image_works() {
         let server = MockServer::start_async().await;
@@ -674,7 +1180,8 @@ mod test {
                     .query_param("offset", "0")
                     .query_param("translatedLanguage[]", default_language.as_iso_code())
                     .query_param("order[volume]", default_chapter_order.to_string())
-                    .query_param("order[chapter]", default_chapter_order.to_string());
+                    .query_param("order[chapter]", default_chapter_order.to_string())
+                    .query_param("excludedGroups[]", "blocked_group");
 
                 then.status(200).header("content-type", "application/json").json_body_obj(&expected);
             })
@@ -683,7 +1190,7 @@ mod test {
         let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap());
 
         let response = client
-            .get_manga_chapters("id_manga", 1, Languages::default(), ChapterOrder::default())
+            .get_manga_chapters("id_manga", 1, Languages::default(), ChapterOrder::default(), &["blocked_group".to_string()])
             .await
             .expect("could not get manga chapters");
 
@@ -722,6 +1229,69 @@ mod test {
         assert_eq!(response, expected);
     }
 
+    #[tokio::test]
+    async fn it_gets_chapter_pages_url_from_cache_after_first_request() {
+        let server = MockServer::start_async().await;
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap());
+
+        let response = ChapterPagesResponse {
+            base_url: server.base_url(),
+            chapter: ChapterPages {
+                hash: "hash".to_string(),
+                data: vec!["1.png".to_string()],
+                data_saver: vec!["1.jpg".to_string()],
+            },
+            ..Default::default()
+        };
+
+        let expected = response.clone().get_files_based_on_quality_as_url(ImageQuality::default());
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(GET).path_contains("at-home").path_contains("chapter_id");
+                then.status(200).header("content-type", "application/json").json_body_obj(&response);
+            })
+            .await;
+
+        let first_pages_url = client.get_chapter_pages_url("chapter_id").await.expect("error getting chapter pages url");
+        let second_pages_url = client.get_chapter_pages_url("chapter_id").await.expect("error getting chapter pages url");
+
+        request.assert_hits_async(1).await;
+
+        assert_eq!(expected, first_pages_url);
+        assert_eq!(expected, second_pages_url);
+    }
+
+    #[tokio::test]
+    async fn it_gets_chapter_pages_url_from_images_url_set() {
+        let server = MockServer::start_async().await;
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap())
+            .with_images_url("https://images.mangadex-mirror.org".parse().unwrap());
+
+        let response = ChapterPagesResponse {
+            base_url: "https://assigned-by-mangadex.org".to_string(),
+            chapter: ChapterPages {
+                hash: "hash".to_string(),
+                data: vec!["1.png".to_string()],
+                data_saver: vec!["1.jpg".to_string()],
+            },
+            ..Default::default()
+        };
+
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path_contains("at-home").path_contains("chapter_id");
+                then.status(200).header("content-type", "application/json").json_body_obj(&response);
+            })
+            .await;
+
+        let pages_url = client.get_chapter_pages_url("chapter_id").await.expect("error getting chapter pages url");
+
+        let expected: Url = "https://images.mangadex-mirror.org/data-saver/hash/1.jpg".parse().unwrap();
+
+        assert_eq!(vec![expected], pages_url);
+    }
+
     #[tokio::test]
     async fn get_chapter_page() {
         let server = MockServer::start_async().await;
@@ -746,11 +1316,71 @@ mod test {
 
         request.assert_async().await;
 
-        let response = response.bytes().await.expect("could not get manga page bytes");
-
         assert_eq!(expected, response)
     }
 
+    #[tokio::test]
+    async fn it_reports_to_mangadex_at_home_if_the_page_could_be_downloaded() {
+        let server = MockServer::start_async().await;
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap())
+            .with_at_home_report_url(format!("{}/report", server.base_url()).parse().unwrap());
+
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path_contains("chapter.png");
+                then.status(200).header("X-Cache", "HIT").body("some_page_bytes");
+            })
+            .await;
+
+        let endpoint: Url = format!("{}/{}", server.base_url(), "chapter.png").parse().unwrap();
+
+        let report = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/report").json_body_partial(
+                    serde_json::json!({
+                        "url" : endpoint.to_string(),
+                        "success" : true,
+                        "cached" : true,
+                        "bytes" : 15
+                    })
+                    .to_string(),
+                );
+                then.status(200);
+            })
+            .await;
+
+        client.get_chapter_page(endpoint.clone()).await.expect("could not get chapter page");
+
+        // The report is sent in the background
+        for _ in 0..20 {
+            if report.hits_async().await > 0 {
+                break;
+            }
+            tokio::time::sleep(StdDuration::from_millis(50)).await;
+        }
+
+        report.assert_async().await;
+    }
+
+    #[test]
+    fn a_page_whose_body_could_not_be_read_is_reported_as_not_downloaded() {
+        let response: Response = http::Response::builder().status(200).body("some_page_bytes").unwrap().into();
+        let url: Url = "https://some.mangadex.network/data/hash/1.jpg".parse().unwrap();
+
+        let report = AtHomeReport::new(&url, Some(&response));
+
+        assert_eq!(
+            AtHomeReport {
+                url: url.to_string(),
+                success: false,
+                cached: false,
+                bytes: 0,
+                duration: 250,
+            },
+            report.page_read(None, StdDuration::from_millis(250))
+        );
+    }
+
     #[tokio::test]
     async fn get_manga_statistics() {
         let server = MockServer::start_async().await;
@@ -883,6 +1513,41 @@ mod test {
         assert_eq!(response, expected);
     }
 
+    #[tokio::test]
+    async fn get_latest_updates_mangadex() {
+        let server = MockServer::start_async().await;
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap());
+
+        let expected = ChapterResponse::default();
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .header_exists("User-Agent")
+                    .path_contains("/chapter")
+                    .query_param("limit", ITEMS_PER_PAGE_LATEST_UPDATES.to_string())
+                    .query_param("offset", ITEMS_PER_PAGE_LATEST_UPDATES.to_string())
+                    .query_param("translatedLanguage[]", "es")
+                    .query_param("includes[]", "manga")
+                    .query_param("includes[]", "scanlation_group")
+                    .query_param("order[readableAt]", "desc");
+
+                then.status(200).json_body_obj(&expected);
+            })
+            .await;
+
+        let response = client
+            .get_latest_updates(2, Languages::Spanish)
+            .await
+            .expect("Could not send request to get the latest updates");
+
+        request.assert_async().await;
+
+        let response: ChapterResponse = response.json().await.expect("Could not deserialize get_latest_updates response");
+
+        assert_eq!(response, expected);
+    }
+
     #[tokio::test]
     async fn get_latest_chapters_mangadex() {
         let server = MockServer::start_async().await;
@@ -1105,6 +1770,330 @@ mod test {
         assert_eq!(expected_response, data_sent);
     }
 
+    #[tokio::test]
+    async fn it_records_requests_when_debug_is_enabled() {
+        let server = MockServer::start_async().await;
+        let request_log: &'static RequestLog = Box::leak(Box::new(RequestLog::new(MAX_REQUESTS_RECORDED)));
+
+        let manga_id = Uuid::new_v4().to_string();
+        let expected = MangaStatisticsResponse::default();
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(GET).path_contains("/statistics/manga").path_contains(&manga_id);
+                then.status(200).header("content-type", "application/json").json_body_obj(&expected);
+            })
+            .await;
+
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap())
+            .with_request_log(request_log);
+
+        let response = client.get_manga_statistics(&manga_id).await.expect("could not get manga statistics");
+
+        request.assert_async().await;
+
+        // the body should still be readable after it was recorded
+        let data_sent: MangaStatisticsResponse = response.json().await.expect("error deserializing response");
+        assert_eq!(expected, data_sent);
+
+        let record = request_log
+            .records()
+            .into_iter()
+            .find(|record| record.url.contains(&manga_id))
+            .expect("request was not recorded");
+
+        assert_eq!("GET", record.method);
+        assert_eq!(Some(200), record.status);
+        assert_eq!(serde_json::to_string(&expected).unwrap(), record.body_preview);
+    }
+
+    #[tokio::test]
+    async fn get_cached_filters_only_requests_tags_if_not_cached() {
+        let server = MockServer::start_async().await;
+
+        let expected = TagsResponse {
+            result: "ok".to_string(),
+            ..Default::default()
+        };
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(GET).path_contains("/manga/tag");
+                then.status(200).header("content-type", "application/json").json_body_obj(&expected);
+            })
+            .await;
+
+        let cache_directory = std::path::PathBuf::from("./test_results/fetch-tags-cache");
+        std::fs::create_dir_all(&cache_directory).unwrap();
+        let cache_path = cache_directory.join(format!("{}.json", Uuid::new_v4()));
+
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap())
+            .with_tags_cache(TagsCache::new(cache_path, TAGS_CACHE_TTL));
+
+        let tags = client.get_cached_filters().await.expect("could not get tags");
+        let tags_cached = client.get_cached_filters().await.expect("could not get tags");
+
+        request.assert_hits_async(1).await;
+
+        assert_eq!(expected, tags);
+        assert_eq!(expected, tags_cached);
+    }
+
+    #[tokio::test]
+    async fn it_logs_in_only_once_while_access_token_is_valid() {
+        let server = MockServer::start_async().await;
+
+        let login_request = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path_contains("/token")
+                    .x_www_form_urlencoded_tuple("grant_type", "password")
+                    .x_www_form_urlencoded_tuple("username", "username")
+                    .x_www_form_urlencoded_tuple("password", "password")
+                    .x_www_form_urlencoded_tuple("client_id", "personal-client-id")
+                    .x_www_form_urlencoded_tuple("client_secret", "client_secret");
+
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .json_body_obj(&login_response());
+            })
+            .await;
+
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap())
+            .with_auth_url(server.url("/token").parse().unwrap())
+            .with_account(mangadex_account());
+
+        let access_token = client.login().await.expect("could not log in");
+        client.login().await.expect("could not log in");
+
+        login_request.assert_hits_async(1).await;
+
+        assert_eq!("access_token", access_token);
+    }
+
+    #[tokio::test]
+    async fn the_access_token_is_renewed_with_the_refresh_token_once_it_expires() {
+        let server = MockServer::start_async().await;
+
+        let login_request = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path_contains("/token")
+                    .x_www_form_urlencoded_tuple("grant_type", "password");
+
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .json_body_obj(&api_responses::auth::LoginResponse {
+                        expires_in: 0,
+                        ..login_response()
+                    });
+            })
+            .await;
+
+        let refresh_request = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path_contains("/token")
+                    .x_www_form_urlencoded_tuple("grant_type", "refresh_token")
+                    .x_www_form_urlencoded_tuple("refresh_token", "refresh_token")
+                    .x_www_form_urlencoded_tuple("client_id", "personal-client-id");
+
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .json_body_obj(&api_responses::auth::LoginResponse {
+                        access_token: "renewed_access_token".to_string(),
+                        ..login_response()
+                    });
+            })
+            .await;
+
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap())
+            .with_auth_url(server.url("/token").parse().unwrap())
+            .with_account(mangadex_account());
+
+        assert_eq!("access_token", client.login().await.expect("could not log in"));
+        assert_eq!("renewed_access_token", client.login().await.expect("could not renew the access token"));
+
+        login_request.assert_hits_async(1).await;
+        refresh_request.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn requests_sent_at_the_same_time_log_in_only_once() {
+        let server = MockServer::start_async().await;
+
+        let login_request = server
+            .mock_async(|when, then| {
+                when.method(POST).path_contains("/token");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .json_body_obj(&login_response());
+            })
+            .await;
+
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap())
+            .with_auth_url(server.url("/token").parse().unwrap())
+            .with_account(mangadex_account());
+
+        let (first, second) = tokio::join!(client.login(), client.login());
+
+        assert!(first.is_ok() && second.is_ok());
+
+        login_request.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn it_does_not_log_in_without_account() {
+        let client = MangadexClient::new(API_URL_BASE.parse().unwrap(), COVER_IMG_URL_BASE.parse().unwrap());
+
+        assert!(!client.is_logged_in());
+        assert!(client.login().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn get_followed_mangas_sends_access_token() {
+        let server = MockServer::start_async().await;
+
+        let expected = SearchMangaResponse::default();
+
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path_contains("/token");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .json_body_obj(&login_response());
+            })
+            .await;
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path_contains("/user/follows/manga")
+                    .header("Authorization", "Bearer access_token")
+                    .query_param("limit", "5")
+                    .query_param("offset", "5")
+                    .query_param("includes[]", "cover_art");
+
+                then.status(200).header("content-type", "application/json").json_body_obj(&expected);
+            })
+            .await;
+
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap())
+            .with_auth_url(server.url("/token").parse().unwrap())
+            .with_account(mangadex_account());
+
+        let response = client.get_followed_mangas(2, 5).await.expect("could not get followed mangas");
+
+        request.assert_async().await;
+
+        assert_eq!(expected, response);
+    }
+
+    #[tokio::test]
+    async fn mark_chapters_as_read_sends_chapters_ids() {
+        let server = MockServer::start_async().await;
+
+        let manga_id = Uuid::new_v4().to_string();
+
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path_contains("/token");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .json_body_obj(&login_response());
+            })
+            .await;
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path_contains(format!("/manga/{manga_id}/read"))
+                    .header("Authorization", "Bearer access_token")
+                    .json_body(serde_json::json!({ "chapterIdsRead": ["chapter_1", "chapter_2"] }));
+
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .json_body(serde_json::json!({ "result": "ok" }));
+            })
+            .await;
+
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap())
+            .with_auth_url(server.url("/token").parse().unwrap())
+            .with_account(mangadex_account());
+
+        client
+            .mark_chapters_as_read(&manga_id, &["chapter_1".to_string(), "chapter_2".to_string()])
+            .await
+            .expect("could not mark chapters as read");
+
+        request.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn get_custom_list_searches_the_mangas_of_the_list() {
+        let server = MockServer::start_async().await;
+
+        let list_id = Uuid::new_v4().to_string();
+        let manga_id = Uuid::new_v4().to_string();
+
+        let list = custom_list::CustomListResponse {
+            result: "ok".to_string(),
+            response: "entity".to_string(),
+            data: custom_list::Data {
+                id: list_id.clone(),
+                attributes: custom_list::Attributes {
+                    name: "Staff Picks".to_string(),
+                },
+                relationships: vec![
+                    custom_list::Relationship {
+                        id: manga_id.clone(),
+                        type_field: "manga".to_string(),
+                    },
+                    custom_list::Relationship {
+                        id: "owner_id".to_string(),
+                        type_field: "user".to_string(),
+                    },
+                ],
+            },
+        };
+
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path_contains(format!("/list/{list_id}"));
+                then.status(200).header("content-type", "application/json").json_body_obj(&list);
+            })
+            .await;
+
+        let expected = SearchMangaResponse {
+            data: vec![api_responses::Data {
+                id: manga_id.clone(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mangas_request = server
+            .mock_async(|when, then| {
+                when.method(GET).path_contains("/manga").query_param("ids[]", &manga_id);
+                then.status(200).header("content-type", "application/json").json_body_obj(&expected);
+            })
+            .await;
+
+        let client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap());
+
+        let custom_list = client.get_custom_list(&list_id).await.expect("could not get custom list");
+
+        mangas_request.assert_async().await;
+
+        assert_eq!(
+            CustomListMangas {
+                name: "Staff Picks".to_string(),
+                mangas: expected,
+            },
+            custom_list
+        );
+    }
+
     //#[tokio::test]
     //async fn test_mangadex() {
     //    let client = MangadexClient::new(API_URL_BASE.parse().unwrap(), COVER_IMG_URL_BASE.parse().unwrap());
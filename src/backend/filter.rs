@@ -1,6 +1,9 @@
+use std::error::Error;
 use std::fmt::{Debug, Write};
+use std::fs;
+use std::path::Path;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIter, IntoEnumIterator};
 
 use crate::global::PREFERRED_LANGUAGE;
@@ -527,6 +530,35 @@ impl Filters {
     }
 }
 
+/// Each provider has its own filters, so they are saved on separate files
+pub static MANGADEX_FILTERS_FILE: &str = "mangadex-filters.json";
+
+/// What the user selected on the filter widget, kept so the filters don't have to be built again the next time
+/// manga-tui is opened. The lists store the names shown on the widget and tags are stored by id
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedFilters {
+    pub content_rating: Vec<String>,
+    pub publication_status: Vec<String>,
+    pub sort_by: Option<String>,
+    pub magazine_demographic: Vec<String>,
+    pub languages: Vec<String>,
+    pub included_tags: Vec<String>,
+    pub excluded_tags: Vec<String>,
+}
+
+impl SavedFilters {
+    /// `None` if the filters were never saved or the file could not be read
+    pub fn load(path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
 /// This test may be changed depending on the Mangadex Api
 #[cfg(test)]
 mod test {
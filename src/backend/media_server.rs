@@ -0,0 +1,263 @@
+//! Import of the series of a Komga or Kavita server, they are searched on mangadex by their title and go to plan to read
+//! or the reading history depending on the read progress the server has of them
+use std::error::Error;
+use std::time::Duration;
+
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::myanimelist_list::{ListEntry, PLAN_TO_READ_STATUS, READING_STATUS};
+use crate::global::USER_AGENT;
+
+/// How many series are requested to komga at a time
+const KOMGA_PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerSeries {
+    pub title: String,
+    /// `true` if at least one book or page of the series was read on the server
+    pub is_started: bool,
+}
+
+impl From<ServerSeries> for ListEntry {
+    fn from(series: ServerSeries) -> Self {
+        let status = if series.is_started { READING_STATUS } else { PLAN_TO_READ_STATUS };
+        Self {
+            title: series.title,
+            status: status.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KomgaSeriesPage {
+    content: Vec<KomgaSeries>,
+    last: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KomgaSeries {
+    metadata: KomgaSeriesMetadata,
+    books_read_count: u32,
+    books_in_progress_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct KomgaSeriesMetadata {
+    title: String,
+}
+
+impl From<KomgaSeries> for ServerSeries {
+    fn from(series: KomgaSeries) -> Self {
+        Self {
+            title: series.metadata.title,
+            is_started: series.books_read_count + series.books_in_progress_count > 0,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KavitaUser {
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KavitaSeries {
+    name: String,
+    pages_read: u32,
+}
+
+impl From<KavitaSeries> for ServerSeries {
+    fn from(series: KavitaSeries) -> Self {
+        Self {
+            title: series.name,
+            is_started: series.pages_read > 0,
+        }
+    }
+}
+
+/// Both servers let users create an api key from their account settings, it's used instead of their password
+#[derive(Debug)]
+pub struct MediaServerClient {
+    base_url: String,
+    api_key: String,
+    client: Client,
+}
+
+impl MediaServerClient {
+    pub fn new(base_url: Url, api_key: String) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(&*USER_AGENT)
+            .build()
+            .unwrap();
+
+        Self {
+            // the server may be behind a path like `https://example.com/komga/`
+            base_url: base_url.as_str().trim_end_matches('/').to_string(),
+            api_key,
+            client,
+        }
+    }
+
+    pub async fn get_komga_series(&self) -> Result<Vec<ServerSeries>, Box<dyn Error>> {
+        let endpoint = format!("{}/api/v1/series", self.base_url);
+        let mut series = vec![];
+        let mut page = 0;
+
+        loop {
+            let response: KomgaSeriesPage = self
+                .client
+                .get(&endpoint)
+                .header("X-API-Key", &self.api_key)
+                .query(&[("page", page), ("size", KOMGA_PAGE_SIZE)])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            series.extend(response.content.into_iter().map(ServerSeries::from));
+
+            if response.last {
+                return Ok(series);
+            }
+            page += 1;
+        }
+    }
+
+    /// Kavita's api key is exchanged for a token which is the one used to request the series
+    pub async fn get_kavita_series(&self) -> Result<Vec<ServerSeries>, Box<dyn Error>> {
+        let user: KavitaUser = self
+            .client
+            .post(format!("{}/api/Plugin/authenticate", self.base_url))
+            .query(&[("apiKey", self.api_key.as_str()), ("pluginName", "manga-tui")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        // a page size of 0 returns all of the series
+        let series: Vec<KavitaSeries> = self
+            .client
+            .post(format!("{}/api/Series/all-v2", self.base_url))
+            .bearer_auth(user.token)
+            .query(&[("PageNumber", 1), ("PageSize", 0)])
+            .json(&json!({ "statements": [], "combination": 1, "limitTo": 0 }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(series.into_iter().map(ServerSeries::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::Method::{GET, POST};
+    use httpmock::MockServer;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_requests_every_page_of_komga_series() -> Result<(), Box<dyn Error>> {
+        let server = MockServer::start_async().await;
+        let client = MediaServerClient::new(format!("{}/komga/", server.base_url()).parse()?, "api-key".to_string());
+
+        let series = |title: &str, books_read: u32| {
+            json!({
+                "metadata": { "title": title },
+                "booksReadCount": books_read,
+                "booksInProgressCount": 0
+            })
+        };
+
+        let first_page = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/komga/api/v1/series")
+                    .header("X-API-Key", "api-key")
+                    .query_param("page", "0");
+                then.status(200).json_body(json!({ "content": [series("Berserk", 3)], "last": false }));
+            })
+            .await;
+
+        let last_page = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/komga/api/v1/series").query_param("page", "1");
+                then.status(200).json_body(json!({ "content": [series("Vagabond", 0)], "last": true }));
+            })
+            .await;
+
+        let found = client.get_komga_series().await?;
+
+        first_page.assert_async().await;
+        last_page.assert_async().await;
+
+        assert_eq!(
+            vec![
+                ServerSeries {
+                    title: "Berserk".to_string(),
+                    is_started: true,
+                },
+                ServerSeries {
+                    title: "Vagabond".to_string(),
+                    is_started: false,
+                },
+            ],
+            found
+        );
+
+        let entries: Vec<ListEntry> = found.into_iter().map(ListEntry::from).collect();
+
+        assert!(!entries[0].is_plan_to_read());
+        assert!(entries[1].is_plan_to_read());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_authenticates_with_kavita_before_requesting_the_series() -> Result<(), Box<dyn Error>> {
+        let server = MockServer::start_async().await;
+        let client = MediaServerClient::new(server.base_url().parse()?, "api-key".to_string());
+
+        let authenticate = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/api/Plugin/authenticate").query_param("apiKey", "api-key");
+                then.status(200).json_body(json!({ "token": "kavita-token" }));
+            })
+            .await;
+
+        let all_series = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/api/Series/all-v2")
+                    .header("Authorization", "Bearer kavita-token");
+                then.status(200).json_body(json!([{ "name": "Berserk", "pagesRead": 120, "pages": 300 }]));
+            })
+            .await;
+
+        let found = client.get_kavita_series().await?;
+
+        authenticate.assert_async().await;
+        all_series.assert_async().await;
+
+        assert_eq!(
+            vec![ServerSeries {
+                title: "Berserk".to_string(),
+                is_started: true,
+            }],
+            found
+        );
+
+        Ok(())
+    }
+}
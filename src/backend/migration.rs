@@ -1,48 +1,64 @@
+//! Schema changes are numbered migrations, the ones applied are saved in the table `schema_migrations` so each one runs
+//! only once, and every migration knows how to undo itself to go back to an older version of manga-tui
 use std::fmt::{Debug, Display};
 use std::marker::PhantomData;
 
-use rusqlite::{Connection, Result, Transaction};
+use rusqlite::{params, Connection, Result, Transaction};
 
 use crate::logger::ILogger;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct MigrationTable {
-    id: u32,
-    name: String,
-    version: String,
+    pub number: u32,
+    pub name: String,
     applied_at: String,
 }
 
 impl MigrationTable {
-    fn new(id: u32, name: String, version: String, applied_at: String) -> Self {
+    fn new(number: u32, name: String, applied_at: String) -> Self {
         Self {
-            id,
+            number,
             name,
-            version,
             applied_at,
         }
     }
 
     fn get_schema() -> &'static str {
         r"
-        CREATE TABLE IF NOT EXISTS migrations(
-            id INTEGER PRIMARY KEY, 
+        CREATE TABLE IF NOT EXISTS schema_migrations(
+            number INTEGER PRIMARY KEY,
             name VARCHAR NOT NULL,
-            version VARCHAR NOT NULL,
             applied_at  DATETIME DEFAULT (datetime('now'))
-        )"
+        )
+        "
     }
 }
 
 pub enum AlterTableCommand<'a> {
     Add { column: &'a str, data_type: &'a str },
+    Drop { column: &'a str },
 }
 
+/// Named after the SQL statement each one runs
+#[allow(clippy::enum_variant_names)]
 pub enum Query<'a> {
     AlterTable {
         table_name: &'a str,
         command: AlterTableCommand<'a>,
     },
+    /// `columns` goes between the parentheses of `CREATE TABLE`, with the constraints of the table
+    CreateTable {
+        table_name: &'a str,
+        columns: &'a str,
+    },
+    /// `module` is the module of the virtual table with its arguments, like `fts5(title, description)`
+    CreateVirtualTable {
+        table_name: &'a str,
+        module: &'a str,
+    },
+    DropTable {
+        table_name: &'a str,
+    },
 }
 
 impl<'a> Display for Query<'a> {
@@ -56,7 +72,16 @@ impl<'a> Display for Query<'a> {
                     column: column_to_add,
                     data_type,
                 } => write!(f, "ALTER TABLE {} ADD {} {}", table_name, column_to_add, data_type),
+                AlterTableCommand::Drop { column } => write!(f, "ALTER TABLE {} DROP COLUMN {}", table_name, column),
+            },
+            Self::CreateTable {
+                table_name,
+                columns,
+            } => write!(f, "CREATE TABLE IF NOT EXISTS {} ({})", table_name, columns),
+            Self::CreateVirtualTable { table_name, module } => {
+                write!(f, "CREATE VIRTUAL TABLE IF NOT EXISTS {} USING {}", table_name, module)
             },
+            Self::DropTable { table_name } => write!(f, "DROP TABLE IF EXISTS {}", table_name),
         }
     }
 }
@@ -78,7 +103,7 @@ pub struct Building;
 
 #[derive(Debug)]
 pub struct Migration<'a, T = Building> {
-    version: &'a str,
+    number: u32,
     queries: &'a [Query<'a>],
     name: &'a str,
     _phantom_data: PhantomData<T>,
@@ -87,15 +112,15 @@ pub struct Migration<'a, T = Building> {
 impl<'a> Migration<'a, Building> {
     pub fn new(queries: &'a [Query<'a>]) -> Self {
         Self {
-            version: "",
+            number: 0,
             queries,
             name: "",
             _phantom_data: PhantomData,
         }
     }
 
-    pub fn with_version(mut self, version: &'a str) -> Self {
-        self.version = version;
+    pub fn with_number(mut self, number: u32) -> Self {
+        self.number = number;
         self
     }
 
@@ -104,8 +129,9 @@ impl<'a> Migration<'a, Building> {
         self
     }
 
-    pub fn up(self, connection: &mut Connection) -> Result<Option<Migration<'a, Up>>> {
-        let transaction = connection.transaction()?;
+    /// `None` if the migration was already applied
+    pub fn up(self, connection: &Connection) -> Result<Option<Migration<'a, Up>>> {
+        let transaction = connection.unchecked_transaction()?;
 
         self.create_table_migrations_if_not_exists(&transaction)?;
 
@@ -116,24 +142,45 @@ impl<'a> Migration<'a, Building> {
 
         transaction.commit()?;
 
-        let migration: Migration<Up> = Migration::new_up_migration(self.version, self.queries, self.name);
+        let migration: Migration<Up> = Migration::new_up_migration(self.number, self.queries, self.name);
 
         Ok(Some(migration))
     }
+
+    /// `None` if the migration was never applied so there is nothing to undo, the queries must be the ones that undo it
+    pub fn down(self, connection: &Connection) -> Result<Option<Migration<'a, Down>>> {
+        let transaction = connection.unchecked_transaction()?;
+
+        self.create_table_migrations_if_not_exists(&transaction)?;
+
+        if self.should_run_migration(&transaction)? {
+            transaction.commit()?;
+            return Ok(None);
+        }
+
+        transaction.commit()?;
+
+        Ok(Some(Migration {
+            number: self.number,
+            queries: self.queries,
+            name: self.name,
+            _phantom_data: PhantomData,
+        }))
+    }
 }
 
 impl<'a> Migration<'a, Up> {
-    fn new_up_migration(version: &'a str, queries: &'a [Query<'a>], name: &'a str) -> Migration<'a, Up> {
+    fn new_up_migration(number: u32, queries: &'a [Query<'a>], name: &'a str) -> Migration<'a, Up> {
         Migration {
-            version,
+            number,
             queries,
             name,
             _phantom_data: PhantomData,
         }
     }
 
-    pub fn update(self, connection: &mut Connection) -> rusqlite::Result<MigrationTable> {
-        let transaction = connection.transaction()?;
+    pub fn update(self, connection: &Connection) -> rusqlite::Result<MigrationTable> {
+        let transaction = connection.unchecked_transaction()?;
 
         self.run_queries(&transaction)?;
 
@@ -145,6 +192,20 @@ impl<'a> Migration<'a, Up> {
     }
 }
 
+impl<'a> Migration<'a, Down> {
+    pub fn rollback(self, connection: &Connection) -> rusqlite::Result<()> {
+        let transaction = connection.unchecked_transaction()?;
+
+        self.run_queries(&transaction)?;
+
+        transaction.execute("DELETE FROM schema_migrations WHERE number = ?1", params![self.number])?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
+
 impl<'a, T> Migration<'a, T> {
     fn run_queries(&self, transaction: &Transaction) -> rusqlite::Result<()> {
         for query in self.queries {
@@ -162,7 +223,12 @@ impl<'a, T> Migration<'a, T> {
                 command,
             } => match command {
                 AlterTableCommand::Add { column, .. } => !self.column_exists(table_name, column, transaction)?,
+                AlterTableCommand::Drop { column } => self.column_exists(table_name, column, transaction)?,
+            },
+            Query::CreateTable { table_name, .. } | Query::CreateVirtualTable { table_name, .. } => {
+                !self.table_exists(table_name, transaction)?
             },
+            Query::DropTable { table_name } => self.table_exists(table_name, transaction)?,
         };
 
         Ok(can_run_query)
@@ -185,9 +251,13 @@ impl<'a, T> Migration<'a, T> {
         Ok(false)
     }
 
+    fn table_exists(&self, table_name: &str, transaction: &Transaction) -> rusqlite::Result<bool> {
+        transaction.query_row("SELECT EXISTS(SELECT name FROM sqlite_master WHERE name = ?1)", [table_name], |row| row.get(0))
+    }
+
     fn should_run_migration(&self, transaction: &Transaction) -> rusqlite::Result<bool> {
-        let query = "SELECT EXISTS(SELECT id FROM migrations WHERE name = ?1 AND version = ?2) as row_exists";
-        let migration_exists: bool = transaction.query_row(query, [self.name, self.version], |row| row.get(0))?;
+        let query = "SELECT EXISTS(SELECT number FROM schema_migrations WHERE number = ?1) as row_exists";
+        let migration_exists: bool = transaction.query_row(query, [self.number], |row| row.get(0))?;
 
         Ok(!migration_exists)
     }
@@ -201,10 +271,10 @@ impl<'a, T> Migration<'a, T> {
     }
 
     fn save_migration(&self, transaction: &Transaction) -> rusqlite::Result<MigrationTable> {
-        let insert_query = "INSERT INTO migrations(name, version) VALUES(?1, ?2) RETURNING id, name, version, applied_at";
+        let insert_query = "INSERT INTO schema_migrations(number, name) VALUES(?1, ?2) RETURNING number, name, applied_at";
 
-        let result: MigrationTable = transaction.query_row(insert_query, [self.name, self.version], |row| {
-            let migration_saved = MigrationTable::new(row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?);
+        let result: MigrationTable = transaction.query_row(insert_query, params![self.number, self.name], |row| {
+            let migration_saved = MigrationTable::new(row.get(0)?, row.get(1)?, row.get(2)?);
 
             Ok(migration_saved)
         })?;
@@ -213,60 +283,492 @@ impl<'a, T> Migration<'a, T> {
     }
 }
 
-impl<'a> Migration<'a, Down> {
-    pub fn rollback(self, connection: &mut Connection) -> rusqlite::Result<()> {
-        let transaction = connection.transaction()?;
-
-        self.run_queries(&transaction)?;
-
-        transaction.commit()?;
-
-        Ok(())
-    }
+/// A schema change, `down` undoes what `up` does
+#[derive(Debug)]
+pub struct NumberedMigration {
+    pub number: u32,
+    pub name: &'static str,
+    pub up: &'static [Query<'static>],
+    pub down: &'static [Query<'static>],
 }
 
-/// migrate to version 0.4.0
-pub fn migrate_version(connection: &mut Connection, logger: &impl ILogger) -> rusqlite::Result<Option<MigrationTable>> {
-    let queries = [
-        Query::AlterTable {
+/// The first migration adds columns that every table created since 0.4.0 has, so migrations can't be undone past it
+pub const OLDEST_SCHEMA_VERSION: u32 = 1;
+
+/// Every schema change in the order they are applied, new ones go at the end with the next number and the ones
+/// released must never change since users may have already applied them
+pub static MIGRATIONS: [NumberedMigration; 21] = [
+    // version 0.4.0
+    NumberedMigration {
+        number: 1,
+        name: "Add columns is_bookmarked, number_page_bookmarked and translated_language to table chapters",
+        up: &[
+            Query::AlterTable {
+                table_name: "chapters",
+                command: AlterTableCommand::Add {
+                    column: "is_bookmarked",
+                    data_type: "BOOLEAN NOT NULL DEFAULT false",
+                },
+            },
+            Query::AlterTable {
+                table_name: "chapters",
+                command: AlterTableCommand::Add {
+                    column: "number_page_bookmarked",
+                    data_type: "INT NULL",
+                },
+            },
+            Query::AlterTable {
+                table_name: "chapters",
+                command: AlterTableCommand::Add {
+                    column: "translated_language",
+                    data_type: "TEXT NULL",
+                },
+            },
+        ],
+        // Every table created since 0.4.0 has these columns so they are kept, see `OLDEST_SCHEMA_VERSION`
+        down: &[],
+    },
+    // after version 0.5.0
+    NumberedMigration {
+        number: 2,
+        name: "Add column personal_rating to table mangas",
+        up: &[Query::AlterTable {
+            table_name: "mangas",
+            command: AlterTableCommand::Add {
+                column: "personal_rating",
+                data_type: "INTEGER NULL",
+            },
+        }],
+        down: &[Query::AlterTable {
+            table_name: "mangas",
+            command: AlterTableCommand::Drop {
+                column: "personal_rating",
+            },
+        }],
+    },
+    NumberedMigration {
+        number: 3,
+        name: "Add column reading_seconds to table chapters",
+        up: &[Query::AlterTable {
             table_name: "chapters",
             command: AlterTableCommand::Add {
-                column: "is_bookmarked",
-                data_type: "BOOLEAN NOT NULL DEFAULT false",
+                column: "reading_seconds",
+                data_type: "INTEGER NOT NULL DEFAULT 0",
+            },
+        }],
+        down: &[Query::AlterTable {
+            table_name: "chapters",
+            command: AlterTableCommand::Drop {
+                column: "reading_seconds",
             },
-        },
-        Query::AlterTable {
+        }],
+    },
+    NumberedMigration {
+        number: 4,
+        name: "Add column read_at to table chapters",
+        up: &[Query::AlterTable {
             table_name: "chapters",
             command: AlterTableCommand::Add {
-                column: "number_page_bookmarked",
-                data_type: "INT NULL",
+                column: "read_at",
+                data_type: "DATETIME NULL",
             },
-        },
-        Query::AlterTable {
+        }],
+        down: &[Query::AlterTable {
             table_name: "chapters",
+            command: AlterTableCommand::Drop { column: "read_at" },
+        }],
+    },
+    NumberedMigration {
+        number: 5,
+        name: "Add column total_chapters to table mangas",
+        up: &[Query::AlterTable {
+            table_name: "mangas",
             command: AlterTableCommand::Add {
-                column: "translated_language",
-                data_type: "TEXT NULL",
-            },
-        },
-    ];
-
-    let migration = Migration::new(&queries)
-        .with_name("Add columns is_bookmarked, number_page_bookmarked and translated_language to table chapters")
-        .with_version("0.4.0")
-        .up(connection)?;
-
-    let migration_result = match migration {
-        Some(available_migration) => {
-            logger.inform("Updating database");
-            let migration_result = available_migration.update(connection)?;
-            logger.inform("Database schema is up to date");
-            Some(migration_result)
-        },
-        None => None,
-    };
-
-    Ok(migration_result)
+                column: "total_chapters",
+                data_type: "INTEGER NULL",
+            },
+        }],
+        down: &[Query::AlterTable {
+            table_name: "mangas",
+            command: AlterTableCommand::Drop {
+                column: "total_chapters",
+            },
+        }],
+    },
+    // Before migrations were numbered the ones applied were saved in the table `migrations`, its columns are checked
+    // before adding them so nothing is lost by dropping it
+    NumberedMigration {
+        number: 6,
+        name: "Drop table migrations, replaced by schema_migrations",
+        up: &[Query::DropTable {
+            table_name: "migrations",
+        }],
+        down: &[Query::CreateTable {
+            table_name: "migrations",
+            columns: "id INTEGER PRIMARY KEY,
+                name VARCHAR NOT NULL,
+                version VARCHAR NOT NULL,
+                applied_at DATETIME DEFAULT (datetime('now'))",
+        }],
+    },
+    // Only one row is ever stored: the chapter and page that was being read the last time
+    NumberedMigration {
+        number: 7,
+        name: "Create table reading_session",
+        up: &[Query::CreateTable {
+            table_name: "reading_session",
+            columns: "id INTEGER PRIMARY KEY CHECK (id = 1),
+                chapter_id TEXT NOT NULL,
+                translated_language TEXT NULL,
+                page_number INT NULL,
+                manga_id TEXT NOT NULL,
+                manga_title TEXT NOT NULL,
+                updated_at DATETIME DEFAULT (datetime('now'))",
+        }],
+        down: &[Query::DropTable {
+            table_name: "reading_session",
+        }],
+    },
+    // Mangas which no longer exist on mangadex, found by the library health check
+    NumberedMigration {
+        number: 8,
+        name: "Create table dead_mangas",
+        up: &[Query::CreateTable {
+            table_name: "dead_mangas",
+            columns: "manga_id TEXT PRIMARY KEY,
+                checked_at DATETIME DEFAULT (datetime('now')),
+                FOREIGN KEY (manga_id) REFERENCES mangas (id)",
+        }],
+        down: &[Query::DropTable {
+            table_name: "dead_mangas",
+        }],
+    },
+    // A NULL manga_id means the group is blocked for every manga
+    NumberedMigration {
+        number: 9,
+        name: "Create table blocked_scanlation_groups",
+        up: &[Query::CreateTable {
+            table_name: "blocked_scanlation_groups",
+            columns: "id INTEGER PRIMARY KEY AUTOINCREMENT,
+                group_id TEXT NOT NULL,
+                group_name TEXT NOT NULL,
+                manga_id TEXT NULL,
+                blocked_at DATETIME DEFAULT (datetime('now'))",
+        }],
+        down: &[Query::DropTable {
+            table_name: "blocked_scanlation_groups",
+        }],
+    },
+    // Other titles the manga is known by, so the library can be searched with any of them
+    NumberedMigration {
+        number: 10,
+        name: "Create table manga_alt_titles",
+        up: &[Query::CreateTable {
+            table_name: "manga_alt_titles",
+            columns: "manga_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                PRIMARY KEY (manga_id, title),
+                FOREIGN KEY (manga_id) REFERENCES mangas (id)",
+        }],
+        down: &[Query::DropTable {
+            table_name: "manga_alt_titles",
+        }],
+    },
+    // One row per chapter downloaded, replaces the `is_downloaded` flag of `chapters` which is only kept for older versions
+    NumberedMigration {
+        number: 11,
+        name: "Create table downloads",
+        up: &[Query::CreateTable {
+            table_name: "downloads",
+            columns: "chapter_id TEXT PRIMARY KEY,
+                path TEXT NOT NULL,
+                format TEXT NOT NULL,
+                bytes INTEGER NOT NULL,
+                finished_at DATETIME DEFAULT (datetime('now')),
+                FOREIGN KEY (chapter_id) REFERENCES chapters (id)",
+        }],
+        down: &[Query::DropTable {
+            table_name: "downloads",
+        }],
+    },
+    // Only one row is ever stored: the manga whose chapters were being downloaded when the app was closed
+    NumberedMigration {
+        number: 12,
+        name: "Create table download_all_job",
+        up: &[Query::CreateTable {
+            table_name: "download_all_job",
+            columns: "id INTEGER PRIMARY KEY CHECK (id = 1),
+                manga_id TEXT NOT NULL,
+                manga_title TEXT NOT NULL,
+                translated_language TEXT NOT NULL,
+                started_at DATETIME DEFAULT (datetime('now'))",
+        }],
+        down: &[Query::DropTable {
+            table_name: "download_all_job",
+        }],
+    },
+    // The entries chosen in the trackers when the one matched by title was wrong, and the progress which could not be synced
+    NumberedMigration {
+        number: 13,
+        name: "Create tables tracker_bindings and tracker_queue",
+        up: &[
+            Query::CreateTable {
+                table_name: "tracker_bindings",
+                columns: "manga_id TEXT NOT NULL,
+                tracker TEXT NOT NULL,
+                entry_id TEXT NOT NULL,
+                PRIMARY KEY (manga_id, tracker)",
+            },
+            Query::CreateTable {
+                table_name: "tracker_queue",
+                columns: "manga_id TEXT NOT NULL,
+                tracker TEXT NOT NULL,
+                manga_title TEXT NOT NULL,
+                chapter_number INTEGER NOT NULL,
+                volume_number INTEGER NULL,
+                PRIMARY KEY (manga_id, tracker)",
+            },
+        ],
+        down: &[
+            Query::DropTable {
+                table_name: "tracker_queue",
+            },
+            Query::DropTable {
+                table_name: "tracker_bindings",
+            },
+        ],
+    },
+    NumberedMigration {
+        number: 14,
+        name: "Create tables collections and manga_collection",
+        up: &[
+            Query::CreateTable {
+                table_name: "collections",
+                columns: "id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                created_at DATETIME DEFAULT (datetime('now'))",
+            },
+            Query::CreateTable {
+                table_name: "manga_collection",
+                columns: "collection_id INTEGER NOT NULL,
+                manga_id TEXT NOT NULL,
+                added_at DATETIME DEFAULT (datetime('now')),
+                PRIMARY KEY (collection_id, manga_id),
+                FOREIGN KEY (collection_id) REFERENCES collections (id),
+                FOREIGN KEY (manga_id) REFERENCES mangas (id)",
+            },
+        ],
+        down: &[
+            Query::DropTable {
+                table_name: "manga_collection",
+            },
+            Query::DropTable {
+                table_name: "collections",
+            },
+        ],
+    },
+    NumberedMigration {
+        number: 15,
+        name: "Create tables tags and manga_tags",
+        up: &[
+            Query::CreateTable {
+                table_name: "tags",
+                columns: "id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE",
+            },
+            Query::CreateTable {
+                table_name: "manga_tags",
+                columns: "tag_id INTEGER NOT NULL,
+                manga_id TEXT NOT NULL,
+                PRIMARY KEY (tag_id, manga_id),
+                FOREIGN KEY (tag_id) REFERENCES tags (id),
+                FOREIGN KEY (manga_id) REFERENCES mangas (id)",
+            },
+        ],
+        down: &[
+            Query::DropTable {
+                table_name: "manga_tags",
+            },
+            Query::DropTable { table_name: "tags" },
+        ],
+    },
+    // Chapters a "new chapter" notification was already pushed for, so they are not pushed twice
+    NumberedMigration {
+        number: 16,
+        name: "Create table notified_chapters",
+        up: &[Query::CreateTable {
+            table_name: "notified_chapters",
+            columns: "chapter_id TEXT PRIMARY KEY,
+                manga_id TEXT NOT NULL,
+                notified_at DATETIME DEFAULT (datetime('now'))",
+        }],
+        down: &[Query::DropTable {
+            table_name: "notified_chapters",
+        }],
+    },
+    // The mangas of a followed author already found, so only the ones published afterwards are notified
+    NumberedMigration {
+        number: 17,
+        name: "Create tables followed_authors and author_known_mangas",
+        up: &[
+            Query::CreateTable {
+                table_name: "followed_authors",
+                columns: "id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                followed_at DATETIME DEFAULT (datetime('now'))",
+            },
+            Query::CreateTable {
+                table_name: "author_known_mangas",
+                columns: "author_id TEXT NOT NULL,
+                manga_id TEXT NOT NULL,
+                PRIMARY KEY (author_id, manga_id)",
+            },
+        ],
+        down: &[
+            Query::DropTable {
+                table_name: "author_known_mangas",
+            },
+            Query::DropTable {
+                table_name: "followed_authors",
+            },
+        ],
+    },
+    NumberedMigration {
+        number: 18,
+        name: "Create table chapter_progress",
+        up: &[Query::CreateTable {
+            table_name: "chapter_progress",
+            columns: "chapter_id TEXT PRIMARY KEY,
+                last_page INT NOT NULL,
+                total_pages INT NOT NULL,
+                updated_at DATETIME DEFAULT (datetime('now'))",
+        }],
+        down: &[Query::DropTable {
+            table_name: "chapter_progress",
+        }],
+    },
+    // Description and genres of the mangas full-text indexed so the history can be searched by them
+    NumberedMigration {
+        number: 19,
+        name: "Create table manga_metadata",
+        up: &[Query::CreateVirtualTable {
+            table_name: "manga_metadata",
+            module: "fts5(manga_id UNINDEXED, description, genres)",
+        }],
+        down: &[Query::DropTable {
+            table_name: "manga_metadata",
+        }],
+    },
+    NumberedMigration {
+        number: 20,
+        name: "Create table not_interested_mangas",
+        up: &[Query::CreateTable {
+            table_name: "not_interested_mangas",
+            columns: "manga_id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                marked_at DATETIME DEFAULT (datetime('now'))",
+        }],
+        down: &[Query::DropTable {
+            table_name: "not_interested_mangas",
+        }],
+    },
+    // Only the mangas whose reading mode was changed have a row
+    NumberedMigration {
+        number: 21,
+        name: "Create table manga_reading_mode",
+        up: &[Query::CreateTable {
+            table_name: "manga_reading_mode",
+            columns: "manga_id TEXT PRIMARY KEY,
+                mode TEXT NOT NULL",
+        }],
+        down: &[Query::DropTable {
+            table_name: "manga_reading_mode",
+        }],
+    },
+];
+
+/// The number of the last migration applied, 0 if none was
+pub fn current_schema_version(connection: &Connection) -> rusqlite::Result<u32> {
+    connection.execute(MigrationTable::get_schema(), [])?;
+
+    connection.query_row("SELECT COALESCE(MAX(number), 0) FROM schema_migrations", [], |row| row.get(0))
+}
+
+/// The migrations `migrate_up` would apply, in the order they would run
+pub fn pending_migrations(connection: &Connection) -> rusqlite::Result<Vec<&'static NumberedMigration>> {
+    let mut pending = vec![];
+    for migration in MIGRATIONS.iter() {
+        if Migration::new(migration.up).with_number(migration.number).up(connection)?.is_some() {
+            pending.push(migration);
+        }
+    }
+    Ok(pending)
+}
+
+/// The migrations `migrate_down` would undo to go back to `to`, in the order they would run
+pub fn migrations_to_roll_back(connection: &Connection, to: u32) -> rusqlite::Result<Vec<&'static NumberedMigration>> {
+    let mut to_roll_back = vec![];
+    for migration in MIGRATIONS
+        .iter()
+        .rev()
+        .filter(|migration| migration.number > to.max(OLDEST_SCHEMA_VERSION))
+    {
+        if Migration::new(migration.down).with_number(migration.number).down(connection)?.is_some() {
+            to_roll_back.push(migration);
+        }
+    }
+    Ok(to_roll_back)
+}
+
+/// Applies the migrations which are not in `schema_migrations` yet. The columns they add are skipped if they already
+/// exist, which is the case for databases created after them or updated before migrations were numbered
+pub fn migrate_up(connection: &Connection, logger: &impl ILogger) -> rusqlite::Result<Vec<MigrationTable>> {
+    let mut applied = vec![];
+
+    for migration in MIGRATIONS.iter() {
+        let pending = Migration::new(migration.up)
+            .with_number(migration.number)
+            .with_name(migration.name)
+            .up(connection)?;
+
+        if let Some(pending) = pending {
+            if applied.is_empty() {
+                logger.inform("Updating database");
+            }
+            applied.push(pending.update(connection)?);
+        }
+    }
+
+    if !applied.is_empty() {
+        logger.inform("Database schema is up to date");
+    }
+
+    Ok(applied)
+}
+
+/// Undoes the migrations applied after the migration number `to`, or after `OLDEST_SCHEMA_VERSION` if `to` is older,
+/// starting from the most recent one, returns the numbers of the migrations undone
+pub fn migrate_down(connection: &Connection, to: u32, logger: &impl ILogger) -> rusqlite::Result<Vec<u32>> {
+    let mut rolled_back = vec![];
+
+    for migration in MIGRATIONS
+        .iter()
+        .rev()
+        .filter(|migration| migration.number > to.max(OLDEST_SCHEMA_VERSION))
+    {
+        let applied = Migration::new(migration.down)
+            .with_number(migration.number)
+            .with_name(migration.name)
+            .down(connection)?;
+
+        if let Some(applied) = applied {
+            logger.inform(format!("Undoing migration {} : {}", migration.number, migration.name));
+            applied.rollback(connection)?;
+            rolled_back.push(migration.number);
+        }
+    }
+
+    Ok(rolled_back)
 }
 
 #[cfg(test)]
@@ -279,9 +781,26 @@ mod tests {
     use uuid::Uuid;
 
     use super::*;
+    use crate::backend::database::Database;
     use crate::backend::filter::Languages;
     use crate::logger::DefaultLogger;
 
+    fn numbers_of(migrations: Vec<&NumberedMigration>) -> Vec<u32> {
+        migrations.iter().map(|migration| migration.number).collect()
+    }
+
+    /// Applies only the migration with this number
+    fn apply_migration(connection: &mut Connection, number: u32) -> Result<Option<MigrationTable>> {
+        let migration = MIGRATIONS.iter().find(|migration| migration.number == number).unwrap();
+
+        Migration::new(migration.up)
+            .with_number(migration.number)
+            .with_name(migration.name)
+            .up(connection)?
+            .map(|pending| pending.update(connection))
+            .transpose()
+    }
+
     #[test]
     fn it_makes_alter_table_add_query() {
         let query = Query::AlterTable {
@@ -309,20 +828,20 @@ mod tests {
     fn it_creates_migration_table() -> Result<(), Box<dyn Error>> {
         let mut connection = Connection::open_in_memory()?;
 
-        let migration: Migration<Up> = Migration::<Up>::new_up_migration("1.0.1", &[], "some change");
+        let migration: Migration<Up> = Migration::<Up>::new_up_migration(1, &[], "some change");
 
         let transaction = connection.transaction()?;
 
         migration.create_table_migrations_if_not_exists(&transaction)?;
 
         let confirmation: String = transaction
-            .query_row("SELECT name FROM sqlite_master WHERE type='table' AND name='migrations';", [], |row| {
+            .query_row("SELECT name FROM sqlite_master WHERE type='table' AND name='schema_migrations';", [], |row| {
                 let table_name: String = row.get(0)?;
                 Ok(table_name)
             })
             .unwrap();
 
-        assert_eq!(confirmation, "migrations");
+        assert_eq!(confirmation, "schema_migrations");
 
         Ok(())
     }
@@ -331,19 +850,9 @@ mod tests {
     fn it_saves_migration() -> Result<(), Box<dyn Error>> {
         let mut connection = Connection::open_in_memory()?;
 
-        connection.execute(
-            "
-        CREATE TABLE  migrations(
-            id INTEGER PRIMARY KEY, 
-            name VARCHAR NOT NULL,
-            version VARCHAR NOT NULL,
-            applied_at  DATETIME DEFAULT (datetime('now'))
-        )
-        ",
-            [],
-        )?;
+        connection.execute(MigrationTable::get_schema(), [])?;
 
-        let version = "0.4.0";
+        let number = 4;
 
         let queries = [Query::AlterTable {
             table_name: "contacts",
@@ -353,13 +862,13 @@ mod tests {
             },
         }];
 
-        let migration: Migration<Up> = Migration::<Up>::new_up_migration(version, &queries, "some name");
+        let migration: Migration<Up> = Migration::<Up>::new_up_migration(number, &queries, "some name");
 
         let transaction = connection.transaction()?;
 
         let migration_info: MigrationTable = migration.save_migration(&transaction).expect("could not save migration");
 
-        assert_eq!(migration_info.version, version);
+        assert_eq!(migration_info.number, number);
 
         Ok(())
     }
@@ -386,7 +895,7 @@ mod tests {
             },
         ];
 
-        let migration = Migration::<Up>::new_up_migration("", &queries, "");
+        let migration = Migration::<Up>::new_up_migration(1, &queries, "");
 
         let transaction = connection.transaction()?;
 
@@ -407,7 +916,7 @@ mod tests {
 
         let transaction = connection.transaction()?;
 
-        let migration: Migration<Up> = Migration::new_up_migration("0.1.0", &[], "add column title");
+        let migration: Migration<Up> = Migration::new_up_migration(1, &[], "add column title");
 
         assert!(migration.column_exists("mangas", "title", &transaction)?);
         assert!(!migration.column_exists("mangas", "description", &transaction)?);
@@ -440,7 +949,7 @@ mod tests {
 
         let transaction = connection.transaction()?;
 
-        let migration: Migration<Up> = Migration::new_up_migration("0.1.0", &queries, "add column title");
+        let migration: Migration<Up> = Migration::new_up_migration(1, &queries, "add column title");
 
         assert!(!migration.can_run_query(&queries[0], &transaction)?);
         assert!(migration.can_run_query(&should_run_this_query, &transaction)?);
@@ -450,7 +959,7 @@ mod tests {
 
     #[test]
     fn it_runs_migration() -> Result<(), Box<dyn Error>> {
-        let mut connection = Connection::open_in_memory()?;
+        let connection = Connection::open_in_memory()?;
 
         let table_test_query = r"CREATE TABLE contacts (
                                    contact_id INTEGER PRIMARY KEY,
@@ -461,9 +970,8 @@ mod tests {
         connection.execute(table_test_query, [])?;
 
         let expected_result = MigrationTable {
-            id: 1,
+            number: 1,
             name: "add new column".into(),
-            version: "1.0.1".into(),
             applied_at: "20-30-10".into(),
         };
 
@@ -485,24 +993,23 @@ mod tests {
         ];
 
         let migration: Migration<Up> = Migration::new(&queries)
-            .with_version(&expected_result.version)
+            .with_number(expected_result.number)
             .with_name(&expected_result.name)
-            .up(&mut connection)
+            .up(&connection)
             .expect("this migration should be run")
             .unwrap();
 
-        let migration = migration.update(&mut connection)?;
+        let migration = migration.update(&connection)?;
 
-        assert_eq!(expected_result.id, migration.id);
+        assert_eq!(expected_result.number, migration.number);
         assert_eq!(expected_result.name, migration.name);
-        assert_eq!(expected_result.version, migration.version);
 
         Ok(())
     }
 
     #[test]
     fn it_runs_migration_add_multiple_columns() -> Result<(), Box<dyn Error>> {
-        let mut connection = Connection::open_in_memory()?;
+        let connection = Connection::open_in_memory()?;
 
         let table_test_query = "CREATE TABLE contacts (contact_id INTEGER PRIMARY KEY);";
 
@@ -527,25 +1034,23 @@ mod tests {
         ];
 
         let expected_result = MigrationTable {
-            id: 1,
+            number: 1,
             name: "add datetime column".into(),
-            version: "1.0.1".into(),
             applied_at: "20-30-10".into(),
         };
 
         let migration: Migration<Up> = Migration::new(&queries)
             .with_name(&expected_result.name)
-            .with_version(&expected_result.version)
-            .up(&mut connection)
+            .with_number(expected_result.number)
+            .up(&connection)
             .expect("this migration should be run")
             .unwrap();
 
-        let migration_result = migration.update(&mut connection)?;
+        let migration_result = migration.update(&connection)?;
 
         connection.execute("INSERT INTO contacts(first_name, last_name) VALUES('john', 'doe')", [])?;
 
-        assert_eq!(migration_result.version, expected_result.version);
-        assert_eq!(migration_result.id, expected_result.id);
+        assert_eq!(migration_result.number, expected_result.number);
         assert_eq!(migration_result.name, expected_result.name);
 
         Ok(())
@@ -564,7 +1069,7 @@ mod tests {
                 data_type: "VARCHAR NOT NULL DEFAULT 'no'",
             },
         }])
-        .with_version("1.0.0")
+        .with_number(1)
         .with_name("some_migration");
 
         let transaction = connection.transaction()?;
@@ -578,15 +1083,15 @@ mod tests {
     fn it_knows_it_must_not_run_migration() -> Result<(), Box<dyn Error>> {
         let mut connection = Connection::open_in_memory()?;
 
-        let already_existing_migration_version = "1.0.3";
+        let already_existing_number = 3;
         let already_existing_name = "Update table chapters";
 
         connection.execute(MigrationTable::get_schema(), [])?;
 
-        connection.execute("INSERT INTO migrations(name, version) VALUES(?1, ?2)", [
-            already_existing_name,
-            already_existing_migration_version,
-        ])?;
+        connection.execute(
+            "INSERT INTO schema_migrations(number, name) VALUES(?1, ?2)",
+            params![already_existing_number, already_existing_name,],
+        )?;
 
         let migration = Migration::new(&[Query::AlterTable {
             table_name: "chapters",
@@ -595,7 +1100,7 @@ mod tests {
                 data_type: "VARCHAR NOT NULL DEFAULT 'no'",
             },
         }])
-        .with_version(already_existing_migration_version)
+        .with_number(already_existing_number)
         .with_name(already_existing_name);
 
         let transaction = connection.transaction()?;
@@ -607,7 +1112,7 @@ mod tests {
 
     #[test]
     fn migration_does_not_add_columns_that_already_exist() -> Result<(), Box<dyn Error>> {
-        let mut connection = Connection::open_in_memory()?;
+        let connection = Connection::open_in_memory()?;
         connection.execute("CREATE TABLE clients(id PRIMARY KEY, name VARCHAR NULL, address VARCHAR NULL)", [])?;
         let table_name = "clients";
 
@@ -644,12 +1149,12 @@ mod tests {
 
         let migration = Migration::new(&queries)
             .with_name("Add column name and last_name")
-            .with_version("0.0.7")
-            .up(&mut connection)
+            .with_number(7)
+            .up(&connection)
             .expect("must run this Up migration")
             .unwrap();
 
-        migration.update(&mut connection)?;
+        migration.update(&connection)?;
 
         connection
             .execute("INSERT INTO clients(name, last_name, email, address) VALUES(?1, ?2, ?3, ?4)", [
@@ -702,11 +1207,9 @@ mod tests {
             manga_id.clone(),
         ])?;
 
-        let migration_result = migrate_version(&mut conn, &DefaultLogger)
-            .expect("the update did not ran successfully")
-            .unwrap();
+        let migration_result = apply_migration(&mut conn, 1).expect("the update did not ran successfully").unwrap();
 
-        assert_eq!(migration_result.version, "0.4.0");
+        assert_eq!(migration_result.number, 1);
 
         conn.execute("INSERT INTO chapters(id, title, manga_id, translated_language, is_bookmarked, number_page_bookmarked) VALUES(?1, ?2, ?3, ?4, ?5, ?6)", [
             Uuid::new_v4().to_string(),
@@ -718,10 +1221,214 @@ mod tests {
         ])
         .expect("migration did not update table chapters");
 
-        let second_time = migrate_version(&mut conn, &DefaultLogger).expect("should not run migration twice");
+        let second_time = apply_migration(&mut conn, 1).expect("should not run migration twice");
 
         assert!(second_time.is_none());
 
         Ok(())
     }
+
+    #[test]
+    fn it_adds_the_personal_rating_to_mangas_stored_before_it_existed() -> Result<(), Box<dyn Error>> {
+        let mut conn = Connection::open_in_memory()?;
+
+        conn.execute("CREATE TABLE mangas (id TEXT PRIMARY KEY, title TEXT NOT NULL)", ())?;
+        conn.execute("INSERT INTO mangas(id, title) VALUES(?1, ?2)", ["some_id", "some_title"])?;
+
+        let migration_result = apply_migration(&mut conn, 2)?.expect("the migration should run");
+
+        assert_eq!(2, migration_result.number);
+
+        conn.execute("UPDATE mangas SET personal_rating = 8 WHERE id = ?1", ["some_id"])
+            .expect("migration did not update table mangas");
+
+        assert!(apply_migration(&mut conn, 2)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_adds_the_reading_time_to_chapters_stored_before_it_existed() -> Result<(), Box<dyn Error>> {
+        let mut conn = Connection::open_in_memory()?;
+
+        conn.execute("CREATE TABLE chapters (id TEXT PRIMARY KEY, title TEXT NOT NULL, manga_id TEXT NOT NULL)", ())?;
+        conn.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1, ?2, ?3)", ["some_id", "some_title", "manga_id"])?;
+
+        apply_migration(&mut conn, 3)?.expect("the migration should run");
+
+        let reading_seconds: u64 =
+            conn.query_row("SELECT reading_seconds FROM chapters WHERE id = ?1", ["some_id"], |row| row.get(0))?;
+
+        assert_eq!(0, reading_seconds);
+        assert!(apply_migration(&mut conn, 3)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_adds_the_date_chapters_were_read_to_chapters_stored_before_it_existed() -> Result<(), Box<dyn Error>> {
+        let mut conn = Connection::open_in_memory()?;
+
+        conn.execute("CREATE TABLE chapters (id TEXT PRIMARY KEY, title TEXT NOT NULL, manga_id TEXT NOT NULL)", ())?;
+        conn.execute("INSERT INTO chapters(id, title, manga_id) VALUES(?1, ?2, ?3)", ["some_id", "some_title", "manga_id"])?;
+
+        apply_migration(&mut conn, 4)?.expect("the migration should run");
+
+        let read_at: Option<String> =
+            conn.query_row("SELECT read_at FROM chapters WHERE id = ?1", ["some_id"], |row| row.get(0))?;
+
+        assert!(read_at.is_none());
+        assert!(apply_migration(&mut conn, 4)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_applies_the_pending_migrations_in_order_and_can_undo_them() -> Result<(), Box<dyn Error>> {
+        let mut conn = Connection::open_in_memory()?;
+
+        conn.execute("CREATE TABLE mangas (id TEXT PRIMARY KEY, title TEXT NOT NULL)", ())?;
+        conn.execute("CREATE TABLE chapters (id TEXT PRIMARY KEY, title TEXT NOT NULL, manga_id TEXT NOT NULL)", ())?;
+
+        let all: Vec<u32> = MIGRATIONS.iter().map(|migration| migration.number).collect();
+        let last = *all.last().unwrap();
+
+        assert_eq!((1..=last).collect::<Vec<u32>>(), all, "migrations should be numbered in order without gaps");
+
+        assert_eq!(0, current_schema_version(&conn)?);
+        assert_eq!(all, numbers_of(pending_migrations(&conn)?));
+
+        let applied = migrate_up(&conn, &DefaultLogger)?;
+
+        assert_eq!(all, applied.iter().map(|migration| migration.number).collect::<Vec<u32>>());
+        assert_eq!(last, current_schema_version(&conn)?);
+        assert!(pending_migrations(&conn)?.is_empty());
+        assert!(migrate_up(&conn, &DefaultLogger)?.is_empty());
+
+        let undone: Vec<u32> = (3..=last).rev().collect();
+
+        assert_eq!(undone, numbers_of(migrations_to_roll_back(&conn, 2)?));
+        assert_eq!(undone, migrate_down(&conn, 2, &DefaultLogger)?);
+        assert_eq!(2, current_schema_version(&conn)?);
+
+        let transaction = conn.transaction()?;
+        let migration: Migration<Up> = Migration::new_up_migration(0, &[], "");
+
+        assert!(!migration.column_exists("chapters", "reading_seconds", &transaction)?);
+        assert!(!migration.column_exists("chapters", "read_at", &transaction)?);
+        assert!(!migration.column_exists("mangas", "total_chapters", &transaction)?);
+        assert!(!migration.table_exists("reading_session", &transaction)?);
+        assert!(!migration.table_exists("manga_metadata", &transaction)?);
+        assert!(migration.column_exists("mangas", "personal_rating", &transaction)?);
+
+        transaction.commit()?;
+
+        assert_eq!((3..=last).collect::<Vec<u32>>(), numbers_of(pending_migrations(&conn)?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn the_first_migration_is_never_undone() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+
+        conn.execute("CREATE TABLE mangas (id TEXT PRIMARY KEY, title TEXT NOT NULL)", ())?;
+        conn.execute("CREATE TABLE chapters (id TEXT PRIMARY KEY, title TEXT NOT NULL, manga_id TEXT NOT NULL)", ())?;
+
+        migrate_up(&conn, &DefaultLogger)?;
+
+        assert!(!numbers_of(migrations_to_roll_back(&conn, 0)?).contains(&1));
+
+        migrate_down(&conn, 0, &DefaultLogger)?;
+
+        assert_eq!(OLDEST_SCHEMA_VERSION, current_schema_version(&conn)?);
+
+        let is_bookmarked: bool =
+            conn.query_row("SELECT EXISTS(SELECT 1 FROM pragma_table_info('chapters') WHERE name = 'is_bookmarked')", [], |row| {
+                row.get(0)
+            })?;
+
+        assert!(is_bookmarked);
+
+        Ok(())
+    }
+
+    #[test]
+    fn the_base_tables_are_the_schema_of_the_oldest_version() -> Result<(), Box<dyn Error>> {
+        let columns_of = |conn: &Connection, table: &str| -> rusqlite::Result<Vec<String>> {
+            let mut statement = conn.prepare("SELECT name FROM pragma_table_info(?1) ORDER BY name")?;
+            let columns = statement.query_map([table], |row| row.get(0))?.collect();
+            columns
+        };
+
+        let base = Connection::open_in_memory()?;
+        Database::new(&base).create_base_tables()?;
+
+        let rolled_back = Connection::open_in_memory()?;
+        Database::new(&rolled_back).setup()?;
+        migrate_down(&rolled_back, OLDEST_SCHEMA_VERSION, &DefaultLogger)?;
+
+        for table in ["mangas", "chapters", "manga_history_union", "history_types"] {
+            assert_eq!(columns_of(&rolled_back, table)?, columns_of(&base, table)?, "columns of {table}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn the_table_of_migrations_used_before_they_were_numbered_is_dropped() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+
+        conn.execute("CREATE TABLE mangas (id TEXT PRIMARY KEY, title TEXT NOT NULL)", ())?;
+        conn.execute("CREATE TABLE chapters (id TEXT PRIMARY KEY, title TEXT NOT NULL, manga_id TEXT NOT NULL)", ())?;
+        conn.execute(
+            "CREATE TABLE migrations(id INTEGER PRIMARY KEY, name VARCHAR NOT NULL, version VARCHAR NOT NULL, applied_at DATETIME)",
+            (),
+        )?;
+
+        let legacy_table_exists = || -> rusqlite::Result<bool> {
+            conn.query_row("SELECT EXISTS(SELECT name FROM sqlite_master WHERE name = 'migrations')", [], |row| row.get(0))
+        };
+
+        migrate_up(&conn, &DefaultLogger)?;
+
+        assert!(!legacy_table_exists()?);
+
+        migrate_down(&conn, 5, &DefaultLogger)?;
+
+        assert!(legacy_table_exists()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_makes_alter_table_drop_query() {
+        let query = Query::AlterTable {
+            table_name: "chapters",
+            command: AlterTableCommand::Drop { column: "read_at" },
+        };
+
+        assert_eq!("ALTER TABLE chapters DROP COLUMN read_at", query.to_string());
+    }
+
+    #[test]
+    fn it_makes_create_and_drop_table_queries() {
+        let query = Query::CreateTable {
+            table_name: "tags",
+            columns: "id INTEGER PRIMARY KEY, name TEXT NOT NULL",
+        };
+
+        assert_eq!("CREATE TABLE IF NOT EXISTS tags (id INTEGER PRIMARY KEY, name TEXT NOT NULL)", query.to_string());
+
+        let query = Query::CreateVirtualTable {
+            table_name: "manga_metadata",
+            module: "fts5(description)",
+        };
+
+        assert_eq!("CREATE VIRTUAL TABLE IF NOT EXISTS manga_metadata USING fts5(description)", query.to_string());
+
+        let query = Query::DropTable { table_name: "tags" };
+
+        assert_eq!("DROP TABLE IF EXISTS tags", query.to_string());
+    }
 }
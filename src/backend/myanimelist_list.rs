@@ -0,0 +1,378 @@
+//! Export and import of the XML list used by MyAnimeList, so the library can be moved to and from other apps and sites
+use std::error::Error;
+use std::fmt::Write;
+
+use manga_tui::SearchTerm;
+use rusqlite::Connection;
+
+use super::api_responses::SearchMangaResponse;
+use super::database::{save_plan_to_read, Database, LibraryEntry, MangaHistoryType, MangaInsert, MangaPlanToReadSave};
+use super::fetch::ApiClient;
+use super::filter::Filters;
+use super::tachiyomi::ImportSummary;
+use crate::logger::ILogger;
+use crate::utils::from_manga_response;
+
+/// The name of the tracker used when binding a manga to its myanimelist entry
+static MYANIMELIST_TRACKER: &str = "myanimelist";
+
+/// MyAnimeList keeps comments as they are, so the mangadex id is stored there to not search the manga when importing
+/// a list that was exported by manga-tui
+static MANGADEX_ID_PREFIX: &str = "mangadex:";
+
+pub static PLAN_TO_READ_STATUS: &str = "Plan to Read";
+
+pub static READING_STATUS: &str = "Reading";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ListEntry {
+    /// `None` if the manga is not bound to a myanimelist entry
+    pub myanimelist_id: Option<String>,
+    pub mangadex_id: Option<String>,
+    pub title: String,
+    /// Reading, Completed, On-Hold, Dropped or Plan to Read
+    pub status: String,
+    pub chapters_read: u32,
+    /// 0 means no score
+    pub score: u8,
+}
+
+impl ListEntry {
+    fn from_library(entry: LibraryEntry, myanimelist_id: Option<String>) -> Self {
+        let status = match entry.history_type {
+            MangaHistoryType::PlanToRead => PLAN_TO_READ_STATUS,
+            MangaHistoryType::ReadingHistory => READING_STATUS,
+        };
+
+        Self {
+            myanimelist_id,
+            mangadex_id: Some(entry.id),
+            title: entry.title,
+            status: status.to_string(),
+            chapters_read: entry.chapters_read,
+            score: entry.personal_rating.unwrap_or_default(),
+        }
+    }
+
+    pub fn is_plan_to_read(&self) -> bool {
+        self.status.eq_ignore_ascii_case(PLAN_TO_READ_STATUS)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The text inside `<tag>`, which may be wrapped in `<![CDATA[...]]>`
+fn tag_value(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{tag}>"))? + tag.len() + 2;
+    let end = start + xml[start..].find(&format!("</{tag}>"))?;
+    let value = xml[start..end].trim();
+
+    match value.strip_prefix("<![CDATA[").and_then(|value| value.strip_suffix("]]>")) {
+        Some(cdata) => Some(cdata.to_string()),
+        None => Some(unescape(value)),
+    }
+}
+
+/// The mangas of the reading history are exported as "Reading" and the ones in plan to read as "Plan to Read", a manga in
+/// both is only exported as "Reading"
+pub fn export_list(database: &Database<'_>) -> Result<String, Box<dyn Error>> {
+    let library = database.get_library_entries()?;
+
+    let reading: Vec<&str> = library
+        .iter()
+        .filter(|entry| entry.history_type == MangaHistoryType::ReadingHistory)
+        .map(|entry| entry.id.as_str())
+        .collect();
+
+    let mut entries = vec![];
+    for entry in library.iter().cloned() {
+        if entry.history_type == MangaHistoryType::PlanToRead && reading.contains(&entry.id.as_str()) {
+            continue;
+        }
+        let myanimelist_id = database.get_tracker_entry(&entry.id, MYANIMELIST_TRACKER)?;
+        entries.push(ListEntry::from_library(entry, myanimelist_id));
+    }
+
+    Ok(to_xml(&entries))
+}
+
+pub fn to_xml(entries: &[ListEntry]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n<myanimelist>\n  <myinfo>\n    <user_export_type>2</user_export_type>\n  </myinfo>\n",
+    );
+
+    for entry in entries {
+        let comments = entry
+            .mangadex_id
+            .as_ref()
+            .map(|id| format!("{MANGADEX_ID_PREFIX}{id}"))
+            .unwrap_or_default();
+
+        // writing to a String does not fail
+        let _ = write!(
+            xml,
+            "  <manga>\n    <manga_mangadb_id>{}</manga_mangadb_id>\n    <manga_title>{}</manga_title>\n    \
+             <my_read_chapters>{}</my_read_chapters>\n    <my_score>{}</my_score>\n    <my_status>{}</my_status>\n    \
+             <my_comments>{}</my_comments>\n    <update_on_import>1</update_on_import>\n  </manga>\n",
+            entry.myanimelist_id.as_deref().unwrap_or("0"),
+            escape(&entry.title),
+            entry.chapters_read,
+            entry.score,
+            escape(&entry.status),
+            comments,
+        );
+    }
+
+    xml.push_str("</myanimelist>\n");
+    xml
+}
+
+pub fn parse_list(xml: &str) -> Vec<ListEntry> {
+    xml.split("<manga>")
+        .skip(1)
+        .filter_map(|manga| manga.split("</manga>").next())
+        .map(|manga| ListEntry {
+            myanimelist_id: tag_value(manga, "manga_mangadb_id").filter(|id| !id.is_empty() && id != "0"),
+            mangadex_id: tag_value(manga, "my_comments")
+                .and_then(|comments| comments.strip_prefix(MANGADEX_ID_PREFIX).map(|id| id.trim().to_string())),
+            title: tag_value(manga, "manga_title").unwrap_or_default(),
+            status: tag_value(manga, "my_status").unwrap_or_default(),
+            chapters_read: tag_value(manga, "my_read_chapters")
+                .and_then(|read| read.parse().ok())
+                .unwrap_or_default(),
+            score: tag_value(manga, "my_score").and_then(|score| score.parse().ok()).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// The first manga found on mangadex whose title is exactly the same as the one on the list, `(id, title, cover)`
+async fn search_on_mangadex(
+    api_client: &impl ApiClient,
+    title: &str,
+) -> Result<Option<(String, String, Option<String>)>, Box<dyn Error>> {
+    let response: SearchMangaResponse = api_client
+        .search_mangas(SearchTerm::trimmed(title), 1, Filters::default())
+        .await?
+        .json()
+        .await?;
+
+    let found = response.data.into_iter().map(from_manga_response).find(|manga| {
+        manga.title.eq_ignore_ascii_case(title) || manga.alt_titles.iter().any(|alt_title| alt_title.eq_ignore_ascii_case(title))
+    });
+
+    Ok(found.map(|manga| (manga.id, manga.title, manga.img_url)))
+}
+
+/// Mangas exported by manga-tui are found by their mangadex id and the rest are searched on mangadex by their title,
+/// "Plan to Read" ones go to plan to read and the rest to the reading history with the score as the personal rating
+pub async fn import_list(
+    entries: &[ListEntry],
+    api_client: &impl ApiClient,
+    conn: &Connection,
+    logger: &impl ILogger,
+) -> Result<ImportSummary, Box<dyn Error>> {
+    let database = Database::new(conn);
+    let mut summary = ImportSummary::default();
+
+    for entry in entries {
+        let found = match entry.mangadex_id.clone() {
+            Some(id) => Some((id, entry.title.clone(), None)),
+            None => match search_on_mangadex(api_client, &entry.title).await {
+                Ok(found) => found,
+                Err(e) => {
+                    logger.warn(format!("Could not search {} on mangadex, more details : {e}", entry.title));
+                    None
+                },
+            },
+        };
+
+        let Some((id, title, img_url)) = found else {
+            summary.skipped.push(entry.title.clone());
+            continue;
+        };
+
+        let manga = MangaInsert {
+            id: &id,
+            title: &title,
+            img_url: img_url.as_deref(),
+        };
+
+        if entry.is_plan_to_read() {
+            save_plan_to_read(
+                MangaPlanToReadSave {
+                    id: &id,
+                    title: &title,
+                    img_url: img_url.as_deref(),
+                },
+                conn,
+            )?;
+            summary.plan_to_read += 1;
+        } else {
+            database.add_to_reading_history(manga.clone())?;
+            summary.reading_history += 1;
+        }
+
+        if (1..=10).contains(&entry.score) {
+            database.set_personal_rating(manga, Some(entry.score))?;
+        }
+
+        if let Some(myanimelist_id) = entry.myanimelist_id.as_deref() {
+            database.bind_tracker_entry(&id, MYANIMELIST_TRACKER, myanimelist_id)?;
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::backend::api_responses::{Attributes, Data, Title};
+    use crate::backend::fetch::fake_api_client::MockMangadexClient;
+    use crate::logger::DefaultLogger;
+
+    #[test]
+    fn the_exported_list_can_be_imported_back() {
+        let entries = vec![
+            ListEntry {
+                myanimelist_id: Some("13".to_string()),
+                mangadex_id: Some("a1c7c817".to_string()),
+                title: "One Piece".to_string(),
+                status: "Reading".to_string(),
+                chapters_read: 1100,
+                score: 9,
+            },
+            ListEntry {
+                myanimelist_id: None,
+                mangadex_id: Some("b2".to_string()),
+                title: "Tom & Jerry <]]> special".to_string(),
+                status: "Plan to Read".to_string(),
+                chapters_read: 0,
+                score: 0,
+            },
+        ];
+
+        let xml = to_xml(&entries);
+
+        assert!(xml.contains("<manga_mangadb_id>13</manga_mangadb_id>"));
+        assert!(xml.contains("<my_status>Plan to Read</my_status>"));
+
+        assert_eq!(entries, parse_list(&xml));
+    }
+
+    #[test]
+    fn it_reads_lists_exported_by_myanimelist() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" ?>
+            <myanimelist>
+              <manga>
+                <manga_mangadb_id>2</manga_mangadb_id>
+                <manga_title><![CDATA[Berserk]]></manga_title>
+                <my_read_chapters>120</my_read_chapters>
+                <my_score>10</my_score>
+                <my_status>Completed</my_status>
+                <my_comments><![CDATA[]]></my_comments>
+              </manga>
+            </myanimelist>"#;
+
+        assert_eq!(
+            vec![ListEntry {
+                myanimelist_id: Some("2".to_string()),
+                mangadex_id: None,
+                title: "Berserk".to_string(),
+                status: "Completed".to_string(),
+                chapters_read: 120,
+                score: 10,
+            }],
+            parse_list(xml)
+        );
+    }
+
+    #[tokio::test]
+    async fn it_imports_the_mangas_found_on_mangadex() -> Result<(), Box<dyn Error>> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+        database.setup()?;
+
+        let api_client = MockMangadexClient::new().with_search_response(SearchMangaResponse {
+            data: vec![Data {
+                id: "berserk-id".to_string(),
+                attributes: Attributes {
+                    title: Title {
+                        en: Some("Berserk".to_string()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        let entries = vec![
+            ListEntry {
+                myanimelist_id: Some("2".to_string()),
+                title: "berserk".to_string(),
+                status: "Completed".to_string(),
+                score: 10,
+                ..Default::default()
+            },
+            ListEntry {
+                mangadex_id: Some("exported-id".to_string()),
+                title: "Exported by manga-tui".to_string(),
+                status: "Plan to Read".to_string(),
+                ..Default::default()
+            },
+            ListEntry {
+                title: "Not on mangadex".to_string(),
+                status: "Reading".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let summary = import_list(&entries, &api_client, &connection, &DefaultLogger).await?;
+
+        assert_eq!(
+            ImportSummary {
+                reading_history: 1,
+                plan_to_read: 1,
+                skipped: vec!["Not on mangadex".to_string()],
+            },
+            summary
+        );
+
+        let library: Vec<(String, MangaHistoryType)> = database
+            .get_library_entries()?
+            .into_iter()
+            .map(|entry| (entry.id, entry.history_type))
+            .collect();
+
+        assert_eq!(
+            vec![
+                ("berserk-id".to_string(), MangaHistoryType::ReadingHistory),
+                ("exported-id".to_string(), MangaHistoryType::PlanToRead)
+            ],
+            library
+        );
+        assert_eq!(Some(10), database.get_personal_rating("berserk-id")?);
+        assert_eq!(Some("2".to_string()), database.get_tracker_entry("berserk-id", MYANIMELIST_TRACKER)?);
+
+        let exported = export_list(&database)?;
+
+        assert!(exported.contains("<my_comments>mangadex:berserk-id</my_comments>"));
+        assert!(exported.contains("<manga_mangadb_id>2</manga_mangadb_id>"));
+
+        Ok(())
+    }
+}
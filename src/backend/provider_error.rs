@@ -0,0 +1,98 @@
+//! Why a request to mangadex failed, shown to the user so they know if trying again can help
+use std::error::Error;
+
+use reqwest::StatusCode;
+use strum::Display;
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderErrorKind {
+    #[strum(to_string = "could not connect, check your internet connection")]
+    Network,
+    #[strum(to_string = "mangadex took too long to answer")]
+    Timeout,
+    #[strum(to_string = "too many requests were made, wait a moment")]
+    RateLimited,
+    #[strum(to_string = "mangadex is having problems")]
+    Server,
+    #[strum(to_string = "mangadex rejected the request")]
+    Rejected,
+    #[strum(to_string = "the response could not be read")]
+    InvalidResponse,
+}
+
+impl ProviderErrorKind {
+    pub fn from_status(status: StatusCode) -> Self {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            Self::RateLimited
+        } else if status.is_server_error() {
+            Self::Server
+        } else {
+            Self::Rejected
+        }
+    }
+
+    /// Errors which don't come from sending the request are taken as the response not being the one expected
+    pub fn classify(error: &(dyn Error + 'static)) -> Self {
+        let Some(error) = error.downcast_ref::<reqwest::Error>() else {
+            return Self::InvalidResponse;
+        };
+
+        if error.is_timeout() {
+            Self::Timeout
+        } else if error.is_connect() {
+            Self::Network
+        } else {
+            error.status().map_or(Self::InvalidResponse, Self::from_status)
+        }
+    }
+
+    /// The request could not even be sent, as opposed to the provider rejecting it
+    pub fn is_network(self) -> bool {
+        matches!(self, Self::Network | Self::Timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::Method::GET;
+    use httpmock::MockServer;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_classifies_why_a_request_failed() {
+        let server = MockServer::start_async().await;
+
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/rate-limited");
+                then.status(429);
+            })
+            .await;
+
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/down");
+                then.status(503);
+            })
+            .await;
+
+        let status_error = |path: &str| {
+            let url = server.url(path);
+            async move { reqwest::get(url).await.unwrap().error_for_status().unwrap_err() }
+        };
+
+        assert_eq!(ProviderErrorKind::RateLimited, ProviderErrorKind::classify(&status_error("/rate-limited").await));
+        assert_eq!(ProviderErrorKind::Server, ProviderErrorKind::classify(&status_error("/down").await));
+
+        let connection_refused = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+
+        assert_eq!(ProviderErrorKind::Network, ProviderErrorKind::classify(&connection_refused));
+        assert!(ProviderErrorKind::classify(&connection_refused).is_network());
+
+        let not_json = serde_json::from_str::<u32>("{").unwrap_err();
+
+        assert_eq!(ProviderErrorKind::InvalidResponse, ProviderErrorKind::classify(&not_json));
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+/// How many times a request answered with `429 Too Many Requests` is sent again before giving up
+pub const MAX_REPLAYS: u32 = 3;
+
+/// Used when the provider does not say how long to wait
+pub const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(5);
+
+/// Waiting longer than this is not worth it, the user can just try again later
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// When the request that was rate limited is sent again, it is read by the ui to show a countdown
+pub static RATE_LIMIT: Lazy<RateLimit> = Lazy::new(RateLimit::default);
+
+#[derive(Debug, Default)]
+pub struct RateLimit {
+    retry_at: Mutex<Option<Instant>>,
+}
+
+impl RateLimit {
+    pub fn global() -> &'static RateLimit {
+        &RATE_LIMIT
+    }
+
+    /// If several requests are waiting the countdown shows the one that is replayed last
+    pub fn wait(&self, retry_after: Duration) {
+        let mut retry_at = self.retry_at.lock().unwrap();
+        let new_retry_at = Instant::now() + retry_after;
+        *retry_at = Some(retry_at.map_or(new_retry_at, |current| current.max(new_retry_at)));
+    }
+
+    /// `None` if no request is waiting to be replayed
+    pub fn remaining(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.retry_at
+            .lock()
+            .unwrap()
+            .filter(|retry_at| *retry_at > now)
+            .map(|retry_at| retry_at - now)
+    }
+}
+
+/// `Retry-After` is either the seconds to wait or the date after which the request can be sent again
+pub fn retry_after(headers: &HeaderMap) -> Duration {
+    let Some(value) = headers.get(RETRY_AFTER).and_then(|value| value.to_str().ok()) else {
+        return DEFAULT_RETRY_AFTER;
+    };
+
+    let retry_after = match value.trim().parse::<u64>() {
+        Ok(seconds) => Duration::from_secs(seconds),
+        Err(_) => match DateTime::parse_from_rfc2822(value) {
+            Ok(date) => (date.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default(),
+            Err(_) => DEFAULT_RETRY_AFTER,
+        },
+    };
+
+    retry_after.min(MAX_RETRY_AFTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    fn headers(retry_after: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(retry_after).unwrap());
+        headers
+    }
+
+    #[test]
+    fn it_reads_how_long_to_wait_from_retry_after() {
+        assert_eq!(Duration::from_secs(12), retry_after(&headers("12")));
+        assert_eq!(MAX_RETRY_AFTER, retry_after(&headers("3600")));
+        assert_eq!(DEFAULT_RETRY_AFTER, retry_after(&headers("soon")));
+        assert_eq!(DEFAULT_RETRY_AFTER, retry_after(&HeaderMap::new()));
+        assert_eq!(Duration::ZERO, retry_after(&headers("Wed, 21 Oct 2015 07:28:00 GMT")));
+
+        let in_a_while = (Utc::now() + chrono::Duration::seconds(30)).to_rfc2822();
+
+        assert!(retry_after(&headers(&in_a_while)) > Duration::from_secs(25));
+    }
+
+    #[test]
+    fn it_counts_down_until_the_request_is_replayed() {
+        let rate_limit = RateLimit::default();
+
+        assert_eq!(None, rate_limit.remaining());
+
+        rate_limit.wait(Duration::from_secs(10));
+        rate_limit.wait(Duration::from_secs(2));
+
+        assert!(rate_limit.remaining().is_some_and(|remaining| remaining > Duration::from_secs(9)));
+
+        let rate_limit = RateLimit::default();
+        rate_limit.wait(Duration::ZERO);
+
+        assert_eq!(None, rate_limit.remaining());
+    }
+}
@@ -0,0 +1,208 @@
+//! The yearly reading report shown in the wrapped page as a Markdown file and a heatmap image, so it can be shared
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDate};
+use image::{Rgba, RgbaImage};
+
+use super::database::ReadingSummary;
+use super::filter::Languages;
+use crate::view::pages::wrapped::{format_reading_time, format_size};
+
+/// How each day of the heatmap is drawn in the Markdown report, from no chapters read to the day the most were read
+const HEATMAP_LEVELS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+
+/// Same levels as `HEATMAP_LEVELS` for the image
+const HEATMAP_COLORS: [Rgba<u8>; 5] = [
+    Rgba([235, 237, 240, 255]),
+    Rgba([155, 233, 168, 255]),
+    Rgba([64, 196, 99, 255]),
+    Rgba([48, 161, 78, 255]),
+    Rgba([33, 110, 57, 255]),
+];
+
+/// Size in pixels of each day of the heatmap image and the space between them
+const HEATMAP_CELL_SIZE: u32 = 12;
+const HEATMAP_CELL_GAP: u32 = 3;
+
+fn report_file_name(year: i32) -> String {
+    format!("manga-tui-report-{year}")
+}
+
+/// Every day of the year and its level in `HEATMAP_LEVELS`, which is relative to the day the most chapters were read
+fn heatmap_levels(summary: &ReadingSummary) -> Vec<(NaiveDate, usize)> {
+    let chapters_by_day: HashMap<&str, u64> =
+        summary.chapters_read_by_day.iter().map(|(day, total)| (day.as_str(), *total)).collect();
+    let most_read = chapters_by_day.values().copied().max().unwrap_or_default();
+
+    let Some(first_day) = NaiveDate::from_ymd_opt(summary.year, 1, 1) else {
+        return vec![];
+    };
+
+    first_day
+        .iter_days()
+        .take_while(|day| day.year() == summary.year)
+        .map(|day| {
+            let total = chapters_by_day
+                .get(day.format("%Y-%m-%d").to_string().as_str())
+                .copied()
+                .unwrap_or_default();
+            let level = if total == 0 { 0 } else { (total * 4).div_ceil(most_read) as usize };
+            (day, level)
+        })
+        .collect()
+}
+
+/// `heatmap_image` is the file name of the image to show in the report, if it was exported too
+pub fn markdown_report(summary: &ReadingSummary, heatmap_image: Option<&str>) -> String {
+    let mut report = format!("# My {} in manga\n\n", summary.year);
+
+    let _ = writeln!(report, "- **{}** chapters read across **{}** series", summary.chapters_read, summary.mangas_read);
+    let _ = writeln!(report, "- **{}** spent in the reader", format_reading_time(summary.seconds_read));
+    let _ = writeln!(
+        report,
+        "- **{}** chapters downloaded, **{}** on disk",
+        summary.chapters_downloaded,
+        format_size(summary.bytes_downloaded)
+    );
+    if let Some((day, total)) = summary.busiest_download_day.as_ref() {
+        let _ = writeln!(report, "- Busiest download day: **{day}** with {total} chapters");
+    }
+
+    if !summary.most_read.is_empty() {
+        report.push_str("\n## Most read series\n\n| Series | Chapters read |\n| --- | --- |\n");
+        for (title, chapters_read) in &summary.most_read {
+            let _ = writeln!(report, "| {} | {chapters_read} |", title.replace('|', "\\|"));
+        }
+    }
+
+    if !summary.chapters_read_by_language.is_empty() {
+        report.push_str("\n## Read in\n\n| Language | Chapters read |\n| --- | --- |\n");
+        for (code, chapters_read) in &summary.chapters_read_by_language {
+            let language = Languages::try_from_iso_code(code).map_or(code.clone(), |language| language.as_human_readable());
+            let _ = writeln!(report, "| {language} | {chapters_read} |");
+        }
+    }
+
+    report.push_str("\n## Reading heatmap\n\n");
+
+    if let Some(image) = heatmap_image {
+        let _ = writeln!(report, "![Reading heatmap]({image})\n");
+    }
+
+    report.push_str("```\n");
+    let mut current_month = None;
+    for (day, level) in heatmap_levels(summary) {
+        if current_month != Some(day.month()) {
+            if current_month.is_some() {
+                report.push('\n');
+            }
+            current_month = Some(day.month());
+            let _ = write!(report, "{} ", day.format("%b"));
+        }
+        report.push(HEATMAP_LEVELS[level]);
+    }
+    let _ = writeln!(report, "\n```\n\nLess {} More", HEATMAP_LEVELS.iter().collect::<String>());
+
+    report
+}
+
+/// Like the contributions graph of github, a column for every week and a row for every day of the week starting on
+/// monday
+pub fn heatmap_image(summary: &ReadingSummary) -> RgbaImage {
+    let levels = heatmap_levels(summary);
+    let first_weekday = levels.first().map(|(day, _)| day.weekday().num_days_from_monday()).unwrap_or_default();
+    let weeks = (first_weekday + levels.len() as u32).div_ceil(7);
+
+    let cell = HEATMAP_CELL_SIZE + HEATMAP_CELL_GAP;
+    let mut image = RgbaImage::from_pixel(weeks * cell + HEATMAP_CELL_GAP, 7 * cell + HEATMAP_CELL_GAP, Rgba([255, 255, 255, 255]));
+
+    for (index, (_, level)) in levels.iter().enumerate() {
+        let position = first_weekday + index as u32;
+        let x = HEATMAP_CELL_GAP + (position / 7) * cell;
+        let y = HEATMAP_CELL_GAP + (position % 7) * cell;
+
+        for pixel_x in x..x + HEATMAP_CELL_SIZE {
+            for pixel_y in y..y + HEATMAP_CELL_SIZE {
+                image.put_pixel(pixel_x, pixel_y, HEATMAP_COLORS[*level]);
+            }
+        }
+    }
+
+    image
+}
+
+/// Writes the report to `directory` and returns its path, with `with_image` the heatmap is also saved as a png next to
+/// it
+pub fn export_report(summary: &ReadingSummary, directory: &Path, with_image: bool) -> Result<PathBuf, Box<dyn Error>> {
+    let file_name = report_file_name(summary.year);
+
+    let image_name = if with_image {
+        let image_name = format!("{file_name}.png");
+        heatmap_image(summary).save(directory.join(&image_name))?;
+        Some(image_name)
+    } else {
+        None
+    };
+
+    let report_path = directory.join(format!("{file_name}.md"));
+    fs::write(&report_path, markdown_report(summary, image_name.as_deref()))?;
+
+    Ok(report_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn summary() -> ReadingSummary {
+        ReadingSummary {
+            year: 2024,
+            chapters_read: 3,
+            mangas_read: 2,
+            most_read: vec![("Most read".to_string(), 2), ("Less read".to_string(), 1)],
+            chapters_read_by_language: vec![("en".to_string(), 3)],
+            seconds_read: 3600,
+            chapters_read_by_day: vec![("2024-01-01".to_string(), 2), ("2024-01-03".to_string(), 1)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn the_heatmap_is_relative_to_the_day_the_most_chapters_were_read() {
+        let levels = heatmap_levels(&summary());
+
+        // 2024 is a leap year
+        assert_eq!(366, levels.len());
+        assert_eq!(vec![4, 0, 2, 0], levels.iter().take(4).map(|(_, level)| *level).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn the_report_has_the_totals_the_most_read_series_and_the_heatmap() {
+        let report = markdown_report(&summary(), Some("manga-tui-report-2024.png"));
+
+        assert!(report.starts_with("# My 2024 in manga"));
+        assert!(report.contains("- **3** chapters read across **2** series"));
+        assert!(report.contains("- **1h 00m** spent in the reader"));
+        assert!(report.contains("| Most read | 2 |"));
+        assert!(report.contains("| English | 3 |"));
+        assert!(report.contains("![Reading heatmap](manga-tui-report-2024.png)"));
+        assert!(report.contains("Jan █·▒·"));
+        assert!(!report.contains("Busiest download day"));
+    }
+
+    #[test]
+    fn the_heatmap_image_has_a_column_per_week() {
+        let image = heatmap_image(&summary());
+
+        // January 1st of 2024 was a monday so the year takes 53 weeks
+        assert_eq!(53 * (HEATMAP_CELL_SIZE + HEATMAP_CELL_GAP) + HEATMAP_CELL_GAP, image.width());
+        assert_eq!(HEATMAP_COLORS[4], *image.get_pixel(HEATMAP_CELL_GAP, HEATMAP_CELL_GAP));
+        assert_eq!(HEATMAP_COLORS[0], *image.get_pixel(HEATMAP_CELL_GAP, HEATMAP_CELL_GAP + HEATMAP_CELL_SIZE + HEATMAP_CELL_GAP));
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use once_cell::sync::OnceCell;
+
+/// Only set when running with `--debug`, if it's `None` requests are not recorded at all
+pub static REQUEST_LOG: OnceCell<RequestLog> = OnceCell::new();
+
+pub const MAX_REQUESTS_RECORDED: usize = 50;
+
+/// Bodies bigger than this are cut, no need to keep a whole search response in memory
+pub const MAX_BODY_PREVIEW_LENGTH: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestRecord {
+    pub method: String,
+    pub url: String,
+    /// `None` if the request failed before getting a response, like a timeout
+    pub status: Option<u16>,
+    pub elapsed: Duration,
+    pub body_preview: String,
+}
+
+impl RequestRecord {
+    pub fn new(method: &str, url: &str, status: Option<u16>, elapsed: Duration, body: &str) -> Self {
+        Self {
+            method: method.to_string(),
+            url: url.to_string(),
+            status,
+            elapsed,
+            body_preview: truncate_body(body, MAX_BODY_PREVIEW_LENGTH),
+        }
+    }
+}
+
+/// Keeps the last `capacity` requests made to the manga provider, the oldest one is dropped when full
+#[derive(Debug)]
+pub struct RequestLog {
+    capacity: usize,
+    records: Mutex<VecDeque<RequestRecord>>,
+}
+
+impl RequestLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn global() -> Option<&'static RequestLog> {
+        REQUEST_LOG.get()
+    }
+
+    pub fn is_enabled() -> bool {
+        REQUEST_LOG.get().is_some()
+    }
+
+    pub fn push(&self, record: RequestRecord) {
+        let mut records = self.records.lock().unwrap();
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// Most recent requests come first
+    pub fn records(&self) -> Vec<RequestRecord> {
+        self.records.lock().unwrap().iter().rev().cloned().collect()
+    }
+}
+
+fn truncate_body(body: &str, max_length: usize) -> String {
+    match body.char_indices().nth(max_length) {
+        Some((index, _)) => format!("{}... ({} bytes total)", &body[..index], body.len()),
+        None => body.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_keeps_only_the_most_recent_requests() {
+        let log = RequestLog::new(2);
+
+        log.push(RequestRecord::new("GET", "http://localhost/1", Some(200), Duration::from_millis(10), ""));
+        log.push(RequestRecord::new("GET", "http://localhost/2", Some(404), Duration::from_millis(10), ""));
+        log.push(RequestRecord::new("GET", "http://localhost/3", None, Duration::from_millis(10), ""));
+
+        let urls: Vec<String> = log.records().into_iter().map(|record| record.url).collect();
+
+        assert_eq!(vec!["http://localhost/3".to_string(), "http://localhost/2".to_string()], urls);
+    }
+
+    #[test]
+    fn it_truncates_long_bodies() {
+        let body = "a".repeat(20);
+
+        assert_eq!("aaaaa... (20 bytes total)", truncate_body(&body, 5));
+        assert_eq!("short", truncate_body("short", 5));
+        assert_eq!("ñañ... (8 bytes total)", truncate_body("ñañañ", 3));
+    }
+}
@@ -1,4 +1,6 @@
 pub mod anilist;
+pub mod mangadex;
+pub mod myanimelist;
 
 use std::collections::HashMap;
 use std::error::Error;
@@ -0,0 +1,105 @@
+use std::error::Error;
+
+use clap::crate_name;
+use keyring::Entry;
+use strum::Display;
+
+use super::SecretStorage;
+
+#[derive(Debug)]
+pub struct MangadexStorage {
+    service_name: &'static str,
+}
+
+#[derive(Debug, Display, Clone, Copy)]
+pub enum MangadexCredentials {
+    #[strum(to_string = "mangadex_client_id")]
+    ClientId,
+    #[strum(to_string = "mangadex_client_secret")]
+    ClientSecret,
+    #[strum(to_string = "mangadex_username")]
+    Username,
+    #[strum(to_string = "mangadex_password")]
+    Password,
+}
+
+impl From<MangadexCredentials> for String {
+    fn from(value: MangadexCredentials) -> Self {
+        value.to_string()
+    }
+}
+
+/// What is needed to log in with a mangadex personal api client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MangadexAccount {
+    pub client_id: String,
+    pub client_secret: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl MangadexStorage {
+    pub fn new() -> Self {
+        Self {
+            service_name: crate_name!(),
+        }
+    }
+
+    pub fn check_credentials_stored(&self) -> Result<Option<MangadexAccount>, Box<dyn Error>> {
+        let credentials = self.get_multiple_secrets(
+            [
+                MangadexCredentials::ClientId,
+                MangadexCredentials::ClientSecret,
+                MangadexCredentials::Username,
+                MangadexCredentials::Password,
+            ]
+            .into_iter(),
+        )?;
+
+        let get_credential =
+            |name: MangadexCredentials| credentials.get(&name.to_string()).filter(|value| !value.is_empty()).cloned();
+
+        let account = get_credential(MangadexCredentials::ClientId)
+            .zip(get_credential(MangadexCredentials::ClientSecret))
+            .zip(get_credential(MangadexCredentials::Username))
+            .zip(get_credential(MangadexCredentials::Password))
+            .map(|(((client_id, client_secret), username), password)| MangadexAccount {
+                client_id,
+                client_secret,
+                username,
+                password,
+            });
+
+        Ok(account)
+    }
+}
+
+impl SecretStorage for MangadexStorage {
+    fn save_secret<T: Into<String>>(&mut self, secret_name: T, value: T) -> Result<(), Box<dyn std::error::Error>> {
+        let secret = Entry::new(self.service_name, &secret_name.into())?;
+
+        let secret_as_string: String = value.into();
+
+        secret.set_secret(secret_as_string.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn get_secret<T: Into<String>>(&self, secret_name: T) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let secret = Entry::new(self.service_name, &secret_name.into())?;
+
+        match secret.get_secret() {
+            Ok(secret_as_bytes) => Ok(Some(String::from_utf8(secret_as_bytes)?)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+
+    fn remove_secret<T: AsRef<str>>(&mut self, secret_name: T) -> Result<(), Box<dyn std::error::Error>> {
+        let secret = Entry::new(self.service_name, secret_name.as_ref())?;
+
+        secret.delete_credential()?;
+
+        Ok(())
+    }
+}
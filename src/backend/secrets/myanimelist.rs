@@ -0,0 +1,87 @@
+use std::error::Error;
+
+use clap::crate_name;
+use keyring::Entry;
+use strum::Display;
+
+use super::anilist::Credentials;
+use super::SecretStorage;
+
+#[derive(Debug)]
+pub struct MyAnimeListStorage {
+    service_name: &'static str,
+}
+
+#[derive(Debug, Display, Clone, Copy)]
+pub enum MyAnimeListCredentials {
+    #[strum(to_string = "myanimelist_client_id")]
+    ClientId,
+    #[strum(to_string = "myanimelist_access_token")]
+    AccessToken,
+}
+
+impl From<MyAnimeListCredentials> for String {
+    fn from(value: MyAnimeListCredentials) -> Self {
+        value.to_string()
+    }
+}
+
+impl MyAnimeListStorage {
+    pub fn new() -> Self {
+        Self {
+            service_name: crate_name!(),
+        }
+    }
+
+    pub fn check_credentials_stored(&self) -> Result<Option<Credentials>, Box<dyn Error>> {
+        let credentials =
+            self.get_multiple_secrets([MyAnimeListCredentials::ClientId, MyAnimeListCredentials::AccessToken].into_iter())?;
+
+        let client_id = credentials.get(&MyAnimeListCredentials::ClientId.to_string()).cloned();
+        let access_token = credentials.get(&MyAnimeListCredentials::AccessToken.to_string()).cloned();
+
+        match client_id.zip(access_token) {
+            Some((id, token)) => {
+                if id.is_empty() || token.is_empty() {
+                    return Ok(None);
+                }
+
+                Ok(Some(Credentials {
+                    access_token: token,
+                    client_id: id,
+                }))
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+impl SecretStorage for MyAnimeListStorage {
+    fn save_secret<T: Into<String>>(&mut self, secret_name: T, value: T) -> Result<(), Box<dyn std::error::Error>> {
+        let secret = Entry::new(self.service_name, &secret_name.into())?;
+
+        let secret_as_string: String = value.into();
+
+        secret.set_secret(secret_as_string.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn get_secret<T: Into<String>>(&self, secret_name: T) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let secret = Entry::new(self.service_name, &secret_name.into())?;
+
+        match secret.get_secret() {
+            Ok(secret_as_bytes) => Ok(Some(String::from_utf8(secret_as_bytes)?)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+
+    fn remove_secret<T: AsRef<str>>(&mut self, secret_name: T) -> Result<(), Box<dyn std::error::Error>> {
+        let secret = Entry::new(self.service_name, secret_name.as_ref())?;
+
+        secret.delete_credential()?;
+
+        Ok(())
+    }
+}
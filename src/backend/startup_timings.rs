@@ -0,0 +1,85 @@
+use std::time::{Duration, Instant};
+
+use crate::logger::ILogger;
+
+/// Measures how long each stage of the startup takes, only reported when running with `--timings`
+#[derive(Debug)]
+pub struct StartupTimings {
+    enabled: bool,
+    started_at: Instant,
+    stage_started_at: Instant,
+    stages: Vec<(&'static str, Duration)>,
+}
+
+impl StartupTimings {
+    pub fn new(enabled: bool) -> Self {
+        let now = Instant::now();
+        Self {
+            enabled,
+            started_at: now,
+            stage_started_at: now,
+            stages: vec![],
+        }
+    }
+
+    /// Record the time elapsed since the previous stage finished
+    pub fn finish_stage(&mut self, name: &'static str) {
+        if self.enabled {
+            self.stages.push((name, self.stage_started_at.elapsed()));
+        }
+        self.stage_started_at = Instant::now();
+    }
+
+    fn report_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .stages
+            .iter()
+            .map(|(name, elapsed)| format!("{name}: {}ms", elapsed.as_millis()))
+            .collect();
+
+        lines.push(format!("Total startup time: {}ms", self.started_at.elapsed().as_millis()));
+
+        lines
+    }
+
+    pub fn report(&self, logger: &impl ILogger) {
+        if !self.enabled {
+            return;
+        }
+
+        for line in self.report_lines() {
+            logger.inform(line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_records_stages_only_if_enabled() {
+        let mut timings = StartupTimings::new(false);
+
+        timings.finish_stage("database setup");
+
+        assert!(timings.stages.is_empty());
+
+        let mut timings = StartupTimings::new(true);
+
+        timings.finish_stage("database setup");
+        timings.finish_stage("migrations");
+
+        let stages: Vec<&str> = timings.stages.iter().map(|(name, _)| *name).collect();
+
+        assert_eq!(vec!["database setup", "migrations"], stages);
+
+        let report = timings.report_lines();
+
+        assert_eq!(3, report.len());
+        assert!(report[0].starts_with("database setup: "));
+        assert!(report[2].starts_with("Total startup time: "));
+    }
+}
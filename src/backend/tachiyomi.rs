@@ -0,0 +1,442 @@
+//! Importer for the backups made by Tachiyomi and its forks like Mihon, a `.tachibk` file is a gzipped protobuf message,
+//! only the fields needed to fill the reading history and the plan to read list are decoded
+use std::error::Error;
+use std::io::Read;
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+use rusqlite::Connection;
+
+use super::database::{save_history, save_plan_to_read, ChapterToSaveHistory, MangaPlanToReadSave, MangaReadingHistorySave};
+use super::filter::Languages;
+
+static GZIP_MAGIC_NUMBER: [u8; 2] = [0x1f, 0x8b];
+
+/// The name Tachiyomi gives to the mangadex extension, it is the only source that maps to a provider of manga-tui
+static MANGADEX_SOURCE: &str = "MangaDex";
+
+#[derive(Debug, PartialEq, Eq)]
+enum WireValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    /// 32 and 64 bits fields are not used by the importer so only their length matters
+    Fixed,
+}
+
+/// Reads the fields of a protobuf message one by one
+struct ProtoReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ProtoReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn read_varint(&mut self) -> Result<u64, Box<dyn Error>> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = *self.bytes.get(self.position).ok_or("the backup ended in the middle of a number")?;
+            self.position += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("the backup has a number that is too long".into())
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], Box<dyn Error>> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or("the backup is truncated")?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn next_field(&mut self) -> Result<Option<(u64, WireValue<'a>)>, Box<dyn Error>> {
+        if self.position >= self.bytes.len() {
+            return Ok(None);
+        }
+
+        let key = self.read_varint()?;
+        let value = match key & 0x7 {
+            0 => WireValue::Varint(self.read_varint()?),
+            1 => {
+                self.take(8)?;
+                WireValue::Fixed
+            },
+            2 => {
+                let length = self.read_varint()? as usize;
+                WireValue::Bytes(self.take(length)?)
+            },
+            5 => {
+                self.take(4)?;
+                WireValue::Fixed
+            },
+            wire_type => return Err(format!("the backup has an unsupported field of type {wire_type}").into()),
+        };
+
+        Ok(Some((key >> 3, value)))
+    }
+}
+
+fn read_string(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BackupChapter {
+    pub url: String,
+    pub name: String,
+    pub read: bool,
+}
+
+impl BackupChapter {
+    fn decode(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut chapter = Self::default();
+        let mut reader = ProtoReader::new(bytes);
+        while let Some((field, value)) = reader.next_field()? {
+            match (field, value) {
+                (1, WireValue::Bytes(url)) => chapter.url = read_string(url),
+                (2, WireValue::Bytes(name)) => chapter.name = read_string(name),
+                (4, WireValue::Varint(read)) => chapter.read = read != 0,
+                _ => {},
+            }
+        }
+        Ok(chapter)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupManga {
+    pub source: u64,
+    pub url: String,
+    pub title: String,
+    pub thumbnail_url: Option<String>,
+    pub favorite: bool,
+    pub chapters: Vec<BackupChapter>,
+}
+
+impl Default for BackupManga {
+    fn default() -> Self {
+        // Tachiyomi leaves out `favorite` when it has its default value, which is true
+        Self {
+            source: 0,
+            url: String::new(),
+            title: String::new(),
+            thumbnail_url: None,
+            favorite: true,
+            chapters: vec![],
+        }
+    }
+}
+
+impl BackupManga {
+    fn decode(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut manga = Self::default();
+        let mut reader = ProtoReader::new(bytes);
+        while let Some((field, value)) = reader.next_field()? {
+            match (field, value) {
+                (1, WireValue::Varint(source)) => manga.source = source,
+                (2, WireValue::Bytes(url)) => manga.url = read_string(url),
+                (3, WireValue::Bytes(title)) => manga.title = read_string(title),
+                (9, WireValue::Bytes(thumbnail_url)) => manga.thumbnail_url = Some(read_string(thumbnail_url)),
+                (16, WireValue::Bytes(chapter)) => manga.chapters.push(BackupChapter::decode(chapter)?),
+                (100, WireValue::Varint(favorite)) => manga.favorite = favorite != 0,
+                _ => {},
+            }
+        }
+        Ok(manga)
+    }
+
+    /// The manga url of the mangadex extension is `/manga/<id>`
+    fn mangadex_id(&self) -> Option<&str> {
+        self.url
+            .trim_end_matches('/')
+            .rsplit_once('/')
+            .map(|(_, id)| id)
+            .filter(|id| !id.is_empty())
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BackupSource {
+    pub name: String,
+    pub source_id: u64,
+}
+
+impl BackupSource {
+    fn decode(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut source = Self::default();
+        let mut reader = ProtoReader::new(bytes);
+        while let Some((field, value)) = reader.next_field()? {
+            match (field, value) {
+                (1, WireValue::Bytes(name)) => source.name = read_string(name),
+                (2, WireValue::Varint(source_id)) => source.source_id = source_id,
+                _ => {},
+            }
+        }
+        Ok(source)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub mangas: Vec<BackupManga>,
+    pub sources: Vec<BackupSource>,
+}
+
+impl Backup {
+    /// Accepts both the gzipped `.tachibk` file and the plain protobuf message
+    pub fn decode(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let decompressed;
+        let message = if bytes.starts_with(&GZIP_MAGIC_NUMBER) {
+            let mut buffer = vec![];
+            GzDecoder::new(bytes).read_to_end(&mut buffer)?;
+            decompressed = buffer;
+            decompressed.as_slice()
+        } else {
+            bytes
+        };
+
+        let mut backup = Self::default();
+        let mut reader = ProtoReader::new(message);
+        while let Some((field, value)) = reader.next_field()? {
+            match (field, value) {
+                (1, WireValue::Bytes(manga)) => backup.mangas.push(BackupManga::decode(manga)?),
+                (101, WireValue::Bytes(source)) => backup.sources.push(BackupSource::decode(source)?),
+                _ => {},
+            }
+        }
+        Ok(backup)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Self::decode(&std::fs::read(path)?)
+    }
+
+    fn is_from_mangadex(&self, manga: &BackupManga) -> bool {
+        self.sources
+            .iter()
+            .any(|source| source.source_id == manga.source && source.name.eq_ignore_ascii_case(MANGADEX_SOURCE))
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub reading_history: u32,
+    pub plan_to_read: u32,
+    /// Titles of the mangas that could not be imported, for example because their source is not supported
+    pub skipped: Vec<String>,
+}
+
+/// Mangas with read chapters go to the reading history and the rest of the library goes to plan to read, mangas from
+/// sources other than mangadex are skipped since there is no provider to open them with
+pub fn import_backup(backup: &Backup, conn: &Connection, language: Languages) -> Result<ImportSummary, Box<dyn Error>> {
+    let mut summary = ImportSummary::default();
+
+    for manga in &backup.mangas {
+        let Some(manga_id) = manga.mangadex_id().filter(|_| backup.is_from_mangadex(manga)) else {
+            summary.skipped.push(manga.title.clone());
+            continue;
+        };
+
+        let chapters_read: Vec<(&str, &str)> = manga
+            .chapters
+            .iter()
+            .filter(|chapter| chapter.read)
+            .filter_map(|chapter| {
+                chapter
+                    .url
+                    .trim_end_matches('/')
+                    .rsplit_once('/')
+                    .map(|(_, id)| (id, chapter.name.as_str()))
+            })
+            .collect();
+
+        if !chapters_read.is_empty() {
+            for (chapter_id, chapter_title) in chapters_read {
+                save_history(
+                    MangaReadingHistorySave {
+                        id: manga_id,
+                        title: &manga.title,
+                        img_url: manga.thumbnail_url.as_deref(),
+                        chapter: ChapterToSaveHistory {
+                            id: chapter_id,
+                            title: chapter_title,
+                            translated_language: language.as_iso_code(),
+                        },
+                    },
+                    conn,
+                )?;
+            }
+            summary.reading_history += 1;
+        } else if manga.favorite {
+            save_plan_to_read(
+                MangaPlanToReadSave {
+                    id: manga_id,
+                    title: &manga.title,
+                    img_url: manga.thumbnail_url.as_deref(),
+                },
+                conn,
+            )?;
+            summary.plan_to_read += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use pretty_assertions::assert_eq;
+    use super::*;
+    use crate::backend::database::Database;
+
+    fn varint(mut value: u64) -> Vec<u8> {
+        let mut bytes = vec![];
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                bytes.push(byte);
+                return bytes;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    fn varint_field(field: u64, value: u64) -> Vec<u8> {
+        [varint(field << 3), varint(value)].concat()
+    }
+
+    fn bytes_field(field: u64, value: &[u8]) -> Vec<u8> {
+        [varint((field << 3) | 2), varint(value.len() as u64), value.to_vec()].concat()
+    }
+
+    fn chapter(url: &str, name: &str, read: bool) -> Vec<u8> {
+        [bytes_field(1, url.as_bytes()), bytes_field(2, name.as_bytes()), varint_field(4, read as u64)].concat()
+    }
+
+    fn manga(source: u64, url: &str, title: &str, chapters: &[Vec<u8>]) -> Vec<u8> {
+        let mut manga = [varint_field(1, source), bytes_field(2, url.as_bytes()), bytes_field(3, title.as_bytes())].concat();
+        for chapter in chapters {
+            manga.extend(bytes_field(16, chapter));
+        }
+        manga
+    }
+
+    fn source(name: &str, source_id: u64) -> Vec<u8> {
+        [bytes_field(1, name.as_bytes()), varint_field(2, source_id)].concat()
+    }
+
+    fn backup_file() -> Vec<u8> {
+        let message = [
+            bytes_field(
+                1,
+                &manga(
+                    2499283573021220255,
+                    "/manga/reading-id",
+                    "Being read",
+                    &[chapter("/chapter/chapter-1", "Ch. 1", true), chapter("/chapter/chapter-2", "Ch. 2", false)],
+                ),
+            ),
+            bytes_field(1, &manga(2499283573021220255, "/manga/plan-id", "Plan to read", &[])),
+            bytes_field(1, &manga(1998944621602463790, "/manga/other", "From another source", &[])),
+            bytes_field(101, &source("MangaDex", 2499283573021220255)),
+            bytes_field(101, &source("MangaPlus", 1998944621602463790)),
+        ]
+        .concat();
+
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(&message).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn it_decodes_a_gzipped_backup() -> Result<(), Box<dyn Error>> {
+        let backup = Backup::decode(&backup_file())?;
+
+        assert_eq!(3, backup.mangas.len());
+        assert_eq!(
+            BackupSource {
+                name: "MangaDex".to_string(),
+                source_id: 2499283573021220255
+            },
+            backup.sources[0]
+        );
+
+        let manga = &backup.mangas[0];
+
+        assert_eq!("Being read", manga.title);
+        assert_eq!(Some("reading-id"), manga.mangadex_id());
+        assert!(manga.favorite);
+        assert_eq!(
+            vec![
+                BackupChapter {
+                    url: "/chapter/chapter-1".to_string(),
+                    name: "Ch. 1".to_string(),
+                    read: true
+                },
+                BackupChapter {
+                    url: "/chapter/chapter-2".to_string(),
+                    name: "Ch. 2".to_string(),
+                    read: false
+                },
+            ],
+            manga.chapters
+        );
+
+        assert!(Backup::decode(&[0x0a, 0x05, 0x01]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_imports_the_reading_history_and_plan_to_read_of_mangadex_mangas() -> Result<(), Box<dyn Error>> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+        database.setup()?;
+
+        let summary = import_backup(&Backup::decode(&backup_file())?, &connection, Languages::English)?;
+
+        assert_eq!(
+            ImportSummary {
+                reading_history: 1,
+                plan_to_read: 1,
+                skipped: vec!["From another source".to_string()],
+            },
+            summary
+        );
+
+        let history_type = |manga_id: &str| -> rusqlite::Result<String> {
+            connection.query_row(
+                "SELECT history_types.name FROM manga_history_union
+                 INNER JOIN history_types ON history_types.id = manga_history_union.type_id
+                 WHERE manga_history_union.manga_id = ?1",
+                [manga_id],
+                |row| row.get(0),
+            )
+        };
+
+        assert_eq!("ReadingHistory", history_type("reading-id")?);
+        assert_eq!("PlanToRead", history_type("plan-id")?);
+
+        let chapter_read: bool =
+            connection.query_row("SELECT is_read FROM chapters WHERE id = 'chapter-1'", [], |row| row.get(0))?;
+
+        assert!(chapter_read);
+        assert!(!database.check_chapter_is_already_reading("chapter-2")?);
+
+        Ok(())
+    }
+}
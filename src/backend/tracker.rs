@@ -3,14 +3,31 @@ use std::error::Error;
 use futures::Future;
 use manga_tui::SearchTerm;
 use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+use self::anilist::Anilist;
+use super::database::{Database, PendingTrackerUpdate};
+use super::error_log::{write_to_error_log, ErrorType};
+use super::provider_error::ProviderErrorKind;
+use self::journal::LocalJournal;
+use self::myanimelist::MyAnimeList;
 
 pub mod anilist;
+pub mod journal;
+pub mod myanimelist;
 
 #[derive(Debug, Deserialize, Serialize, Default, PartialEq, Eq)]
 pub struct MangaToTrack {
     pub id: String,
 }
 
+/// One of the results shown when the user binds a manga to an entry of the tracker by hand
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackerEntry {
+    pub id: String,
+    pub title: String,
+}
+
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct MarkAsRead<'a> {
     pub id: &'a str,
@@ -23,12 +40,34 @@ pub struct PlanToReadArgs<'a> {
     pub id: &'a str,
 }
 
-pub trait MangaTracker: Send + Clone + 'static {
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display, EnumIter)]
+pub enum TrackingStatus {
+    #[default]
+    Reading,
+    Completed,
+    Dropped,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct UpdateEntryArgs<'a> {
+    pub id: &'a str,
+    pub status: TrackingStatus,
+    /// From 1 to 10, `None` keeps the score the entry already has
+    pub score: Option<u32>,
+}
+
+pub trait MangaTracker: Send + Sync + Clone + 'static {
+    /// Keeps the entries bound to a manga of one tracker apart from the ones of another
+    fn name(&self) -> &'static str;
+
     fn search_manga_by_title(
         &self,
         title: SearchTerm,
     ) -> impl Future<Output = Result<Option<MangaToTrack>, Box<dyn std::error::Error>>> + Send;
 
+    /// Unlike `search_manga_by_title` all the matches are returned so that the user can choose the right one
+    fn search_manga_entries(&self, title: SearchTerm) -> impl Future<Output = Result<Vec<TrackerEntry>, Box<dyn Error>>> + Send;
+
     /// Implementors may require api key / account token in order to perform this operation
     fn mark_manga_as_read_with_chapter_count(
         &self,
@@ -40,15 +79,105 @@ pub trait MangaTracker: Send + Clone + 'static {
         &self,
         manga_to_plan_to_read: PlanToReadArgs<'_>,
     ) -> impl Future<Output = Result<(), Box<dyn Error>>> + Send;
+
+    /// How many chapters the user has read according to the tracker, `None` if the manga is not in their list
+    fn get_reading_progress(&self, id: &str) -> impl Future<Output = Result<Option<u32>, Box<dyn Error>>> + Send;
+
+    /// Implementors may require api key / account token in order to perform this operation
+    fn update_list_entry(&self, entry: UpdateEntryArgs<'_>) -> impl Future<Output = Result<(), Box<dyn Error>>> + Send;
+}
+
+/// One of the trackers chosen in the config file, reading progress is synced with all of them
+#[derive(Debug, Clone)]
+pub enum Tracker {
+    Anilist(Anilist),
+    MyAnimeList(MyAnimeList),
+    Journal(LocalJournal),
+}
+
+impl MangaTracker for Tracker {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Anilist(anilist) => anilist.name(),
+            Self::MyAnimeList(myanimelist) => myanimelist.name(),
+            Self::Journal(journal) => journal.name(),
+        }
+    }
+
+    async fn search_manga_entries(&self, title: SearchTerm) -> Result<Vec<TrackerEntry>, Box<dyn Error>> {
+        match self {
+            Self::Anilist(anilist) => anilist.search_manga_entries(title).await,
+            Self::MyAnimeList(myanimelist) => myanimelist.search_manga_entries(title).await,
+            Self::Journal(journal) => journal.search_manga_entries(title).await,
+        }
+    }
+
+    async fn search_manga_by_title(&self, title: SearchTerm) -> Result<Option<MangaToTrack>, Box<dyn Error>> {
+        match self {
+            Self::Anilist(anilist) => anilist.search_manga_by_title(title).await,
+            Self::MyAnimeList(myanimelist) => myanimelist.search_manga_by_title(title).await,
+            Self::Journal(journal) => journal.search_manga_by_title(title).await,
+        }
+    }
+
+    async fn mark_manga_as_read_with_chapter_count(&self, manga: MarkAsRead<'_>) -> Result<(), Box<dyn Error>> {
+        match self {
+            Self::Anilist(anilist) => anilist.mark_manga_as_read_with_chapter_count(manga).await,
+            Self::MyAnimeList(myanimelist) => myanimelist.mark_manga_as_read_with_chapter_count(manga).await,
+            Self::Journal(journal) => journal.mark_manga_as_read_with_chapter_count(manga).await,
+        }
+    }
+
+    async fn mark_manga_as_plan_to_read(&self, manga_to_plan_to_read: PlanToReadArgs<'_>) -> Result<(), Box<dyn Error>> {
+        match self {
+            Self::Anilist(anilist) => anilist.mark_manga_as_plan_to_read(manga_to_plan_to_read).await,
+            Self::MyAnimeList(myanimelist) => myanimelist.mark_manga_as_plan_to_read(manga_to_plan_to_read).await,
+            Self::Journal(journal) => journal.mark_manga_as_plan_to_read(manga_to_plan_to_read).await,
+        }
+    }
+
+    async fn get_reading_progress(&self, id: &str) -> Result<Option<u32>, Box<dyn Error>> {
+        match self {
+            Self::Anilist(anilist) => anilist.get_reading_progress(id).await,
+            Self::MyAnimeList(myanimelist) => myanimelist.get_reading_progress(id).await,
+            Self::Journal(journal) => journal.get_reading_progress(id).await,
+        }
+    }
+
+    async fn update_list_entry(&self, entry: UpdateEntryArgs<'_>) -> Result<(), Box<dyn Error>> {
+        match self {
+            Self::Anilist(anilist) => anilist.update_list_entry(entry).await,
+            Self::MyAnimeList(myanimelist) => myanimelist.update_list_entry(entry).await,
+            Self::Journal(journal) => journal.update_list_entry(entry).await,
+        }
+    }
+}
+
+/// The entry the user bound to the manga if there is one, otherwise the one matched by its title
+async fn find_tracked_manga(
+    manga_id: &str,
+    manga_title: SearchTerm,
+    tracker: &impl MangaTracker,
+) -> Result<Option<MangaToTrack>, Box<dyn Error>> {
+    let bound_entry = Database::get_connection()
+        .and_then(|conn| Database::new(&conn).get_tracker_entry(manga_id, tracker.name()))
+        .ok()
+        .flatten();
+
+    match bound_entry {
+        Some(id) => Ok(Some(MangaToTrack { id })),
+        None => tracker.search_manga_by_title(manga_title).await,
+    }
 }
 
 async fn update_reading_progress(
+    manga_id: &str,
     manga_title: SearchTerm,
     chapter_number: u32,
     volume_number: Option<u32>,
     tracker: impl MangaTracker,
 ) -> Result<(), Box<dyn Error>> {
-    let response = tracker.search_manga_by_title(manga_title).await?;
+    let response = find_tracked_manga(manga_id, manga_title, &tracker).await?;
     if let Some(manga) = response {
         tracker
             .mark_manga_as_read_with_chapter_count(MarkAsRead {
@@ -61,46 +190,216 @@ async fn update_reading_progress(
     Ok(())
 }
 
-async fn update_plan_to_read(manga_title: SearchTerm, tracker: impl MangaTracker) -> Result<(), Box<dyn Error>> {
-    let response = tracker.search_manga_by_title(manga_title).await?;
+async fn update_plan_to_read(manga_id: &str, manga_title: SearchTerm, tracker: impl MangaTracker) -> Result<(), Box<dyn Error>> {
+    let response = find_tracked_manga(manga_id, manga_title, &tracker).await?;
     if let Some(manga) = response {
         tracker.mark_manga_as_plan_to_read(PlanToReadArgs { id: &manga.id }).await?;
     }
     Ok(())
 }
 
-pub fn track_manga<T, F>(tracker: Option<T>, manga_title: String, chapter_number: u32, volume_number: Option<u32>, on_error: F)
+async fn update_entry(
+    manga_id: &str,
+    manga_title: SearchTerm,
+    status: TrackingStatus,
+    score: Option<u32>,
+    tracker: impl MangaTracker,
+) -> Result<(), Box<dyn Error>> {
+    let response = find_tracked_manga(manga_id, manga_title, &tracker).await?;
+    if let Some(manga) = response {
+        tracker
+            .update_list_entry(UpdateEntryArgs {
+                id: &manga.id,
+                status,
+                score,
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+/// Used to mark as read the chapters the user read on another device
+pub async fn get_tracked_progress(
+    manga_id: &str,
+    manga_title: SearchTerm,
+    tracker: impl MangaTracker,
+) -> Result<Option<u32>, Box<dyn Error>> {
+    let response = find_tracked_manga(manga_id, manga_title, &tracker).await?;
+    match response {
+        Some(manga) => tracker.get_reading_progress(&manga.id).await,
+        None => Ok(None),
+    }
+}
+
+/// The request could not even be sent, as opposed to the tracker rejecting it
+fn is_network_error(error: &(dyn Error + 'static)) -> bool {
+    ProviderErrorKind::classify(error).is_network()
+}
+
+fn queue_update(update: PendingTrackerUpdate) {
+    if let Err(e) = Database::get_connection().and_then(|conn| Database::new(&conn).queue_tracker_update(&update)) {
+        write_to_error_log(ErrorType::Error(Box::new(e)));
+    }
+}
+
+fn remove_from_queue(update: &PendingTrackerUpdate) {
+    if let Err(e) = Database::get_connection()
+        .and_then(|conn| Database::new(&conn).remove_from_tracker_queue(&update.manga_id, &update.tracker))
+    {
+        write_to_error_log(ErrorType::Error(Box::new(e)));
+    }
+}
+
+/// Sends the reading progress that could not be synced because there was no connection, updates for a tracker which
+/// is no longer in use stay in the queue until it is used again
+pub fn retry_tracker_queue<T, F>(trackers: Vec<T>, on_error: F)
 where
     T: MangaTracker,
     F: Fn(String) + Send + 'static,
 {
-    if let Some(tracker) = tracker {
-        tokio::spawn(async move {
-            let title = SearchTerm::trimmed(&manga_title);
-            if let Some(search_term) = title {
-                let response = update_reading_progress(search_term, chapter_number, volume_number, tracker).await;
-                if let Err(e) = response {
-                    on_error(e.to_string());
-                }
+    if trackers.is_empty() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let queue = match Database::get_connection().and_then(|conn| Database::new(&conn).get_tracker_queue()) {
+            Ok(queue) => queue,
+            Err(e) => {
+                on_error(e.to_string());
+                return;
+            },
+        };
+
+        for pending in queue {
+            let Some(tracker) = trackers.iter().find(|tracker| tracker.name() == pending.tracker).cloned() else {
+                continue;
+            };
+            let Some(search_term) = SearchTerm::trimmed(&pending.manga_title) else {
+                remove_from_queue(&pending);
+                continue;
+            };
+
+            let response =
+                update_reading_progress(&pending.manga_id, search_term, pending.chapter_number, pending.volume_number, tracker)
+                    .await;
+
+            match response {
+                Ok(()) => remove_from_queue(&pending),
+                // Still offline, there is no point in trying the rest
+                Err(e) if is_network_error(e.as_ref()) => break,
+                Err(e) => {
+                    remove_from_queue(&pending);
+                    on_error(format!("{}: {e}", pending.tracker));
+                },
             }
-        });
+        }
+    });
+}
+
+/// Each tracker is updated one after the other, if one of them fails the error is reported with its name and the rest
+/// are still updated
+pub fn track_manga<T, F>(
+    trackers: Vec<T>,
+    manga_id: String,
+    manga_title: String,
+    chapter_number: u32,
+    volume_number: Option<u32>,
+    on_error: F,
+) where
+    T: MangaTracker,
+    F: Fn(String) + Send + 'static,
+{
+    if trackers.is_empty() {
+        return;
     }
+
+    tokio::spawn(async move {
+        for tracker in trackers {
+            let Some(search_term) = SearchTerm::trimmed(&manga_title) else {
+                return;
+            };
+            let name = tracker.name();
+            let response = update_reading_progress(&manga_id, search_term, chapter_number, volume_number, tracker).await;
+            if let Err(e) = response {
+                if is_network_error(e.as_ref()) {
+                    queue_update(PendingTrackerUpdate {
+                        manga_id: manga_id.clone(),
+                        tracker: name.to_string(),
+                        manga_title: manga_title.clone(),
+                        chapter_number,
+                        volume_number,
+                    });
+                }
+                on_error(format!("{name}: {e}"));
+            }
+        }
+    });
 }
 
-pub fn track_manga_plan_to_read<T, F>(tracker: Option<T>, manga_title: String, on_error: F)
+pub fn track_manga_plan_to_read<T, F>(trackers: Vec<T>, manga_id: String, manga_title: String, on_error: F)
 where
     T: MangaTracker,
     F: Fn(String) + Send + 'static,
 {
-    if let Some(tracker) = tracker {
-        tokio::spawn(async move {
-            let title = SearchTerm::trimmed(&manga_title);
-            if let Some(search_term) = title {
-                let response = update_plan_to_read(search_term, tracker).await;
-                if let Err(e) = response {
-                    on_error(e.to_string());
-                }
+    if trackers.is_empty() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        for tracker in trackers {
+            let Some(search_term) = SearchTerm::trimmed(&manga_title) else {
+                return;
+            };
+            let name = tracker.name();
+            let response = update_plan_to_read(&manga_id, search_term, tracker).await;
+            if let Err(e) = response {
+                on_error(format!("{name}: {e}"));
             }
-        });
+        }
+    });
+}
+
+pub fn track_manga_entry<T, F>(
+    trackers: Vec<T>,
+    manga_id: String,
+    manga_title: String,
+    status: TrackingStatus,
+    score: Option<u32>,
+    on_error: F,
+) where
+    T: MangaTracker,
+    F: Fn(String) + Send + 'static,
+{
+    if trackers.is_empty() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        for tracker in trackers {
+            let Some(search_term) = SearchTerm::trimmed(&manga_title) else {
+                return;
+            };
+            let name = tracker.name();
+            let response = update_entry(&manga_id, search_term, status, score, tracker).await;
+            if let Err(e) = response {
+                on_error(format!("{name}: {e}"));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn only_requests_that_could_not_be_sent_are_network_errors() {
+        let connection_refused: Box<dyn Error> = reqwest::get("http://127.0.0.1:1").await.unwrap_err().into();
+
+        assert!(is_network_error(connection_refused.as_ref()));
+
+        let rejected: Box<dyn Error> = "could not sync reading status with anilist".into();
+
+        assert!(!is_network_error(rejected.as_ref()));
     }
 }
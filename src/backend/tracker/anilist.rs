@@ -13,7 +13,7 @@ use reqwest::{Body, Client, StatusCode, Url};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use crate::backend::tracker::{MangaToTrack, MangaTracker, MarkAsRead};
+use crate::backend::tracker::{MangaToTrack, MangaTracker, MarkAsRead, TrackerEntry, TrackingStatus, UpdateEntryArgs};
 use crate::cli::AnilistTokenChecker;
 use crate::global::USER_AGENT;
 
@@ -119,6 +119,57 @@ pub struct GetMangaByTitleMedia {
     id: u32,
 }
 
+struct SearchMangaEntriesQuery<'a>(&'a str);
+
+impl GraphqlBody for SearchMangaEntriesQuery<'_> {
+    fn query(&self) -> &'static str {
+        r#"
+            query ($search: String) {
+              Page(perPage: 10) {
+                media(search: $search, type: MANGA, sort: SEARCH_MATCH) {
+                  id
+                  title {
+                    userPreferred
+                  }
+                }
+              }
+            }
+        "#
+    }
+
+    fn variables(&self) -> serde_json::Value {
+        json!({ "search" : self.0 })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct SearchMangaEntriesResponse {
+    data: SearchMangaEntriesData,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct SearchMangaEntriesData {
+    #[serde(rename = "Page")]
+    page: SearchMangaEntriesPage,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct SearchMangaEntriesPage {
+    media: Vec<SearchMangaEntriesMedia>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct SearchMangaEntriesMedia {
+    id: u32,
+    title: SearchMangaEntriesTitle,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct SearchMangaEntriesTitle {
+    #[serde(rename = "userPreferred")]
+    user_preferred: String,
+}
+
 #[derive(Debug, Deserialize, Serialize, Default)]
 pub struct GetUserIdQueryResponse {
     data: GetUserIdQueryData,
@@ -164,6 +215,49 @@ impl GraphqlBody for GetUserIdBody {
     }
 }
 
+struct GetReadingProgressQuery(u32);
+
+impl GraphqlBody for GetReadingProgressQuery {
+    fn query(&self) -> &'static str {
+        r#"
+            query ($id: Int) {
+              Media(id: $id, type: MANGA) {
+                mediaListEntry {
+                  progress
+                }
+              }
+            }
+        "#
+    }
+
+    fn variables(&self) -> serde_json::Value {
+        json!({ "id" : self.0 })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct GetReadingProgressResponse {
+    data: GetReadingProgressData,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct GetReadingProgressData {
+    #[serde(rename = "Media")]
+    media: GetReadingProgressMedia,
+}
+
+/// `mediaListEntry` is null when the manga is not in the user's list
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct GetReadingProgressMedia {
+    #[serde(rename = "mediaListEntry")]
+    media_list_entry: Option<MediaListEntry>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct MediaListEntry {
+    progress: Option<u32>,
+}
+
 struct MarkMangaAsPlanToRead(u32);
 
 impl MarkMangaAsPlanToRead {
@@ -191,6 +285,57 @@ impl GraphqlBody for MarkMangaAsPlanToRead {
     }
 }
 
+/// Anilist's `scoreRaw` goes from 0 to 100 regardless of the score format the user chose, so the score is scaled from 1-10
+struct UpdateMediaListEntry {
+    id: u32,
+    status: TrackingStatus,
+    score: Option<u32>,
+}
+
+impl UpdateMediaListEntry {
+    fn new(id: u32, status: TrackingStatus, score: Option<u32>) -> Self {
+        Self { id, status, score }
+    }
+}
+
+impl GraphqlBody for UpdateMediaListEntry {
+    fn query(&self) -> &'static str {
+        match self.score {
+            Some(_) => {
+                r#"
+                    mutation ($id: Int, $status: MediaListStatus, $scoreRaw: Int) {
+                      SaveMediaListEntry(mediaId: $id, status: $status, scoreRaw: $scoreRaw) {
+                        id
+                      }
+                    }
+                "#
+            },
+            None => {
+                r#"
+                    mutation ($id: Int, $status: MediaListStatus) {
+                      SaveMediaListEntry(mediaId: $id, status: $status) {
+                        id
+                      }
+                    }
+                "#
+            },
+        }
+    }
+
+    fn variables(&self) -> serde_json::Value {
+        let status = match self.status {
+            TrackingStatus::Reading => "CURRENT",
+            TrackingStatus::Completed => "COMPLETED",
+            TrackingStatus::Dropped => "DROPPED",
+        };
+
+        match self.score {
+            Some(score) => json!({ "id" : self.id, "status" : status, "scoreRaw" : score * 10 }),
+            None => json!({ "id" : self.id, "status" : status }),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Anilist {
     base_url: Url,
@@ -301,6 +446,33 @@ impl From<GetMangaByTitleResponse> for MangaToTrack {
 }
 
 impl MangaTracker for Anilist {
+    fn name(&self) -> &'static str {
+        "anilist"
+    }
+
+    async fn search_manga_entries(&self, title: SearchTerm) -> Result<Vec<TrackerEntry>, Box<dyn Error>> {
+        let query = SearchMangaEntriesQuery(title.get());
+
+        let response = self.client.post(self.base_url.clone()).body(query.into_body()).send().await?;
+
+        if response.status() != StatusCode::OK {
+            return Err(format!("could not search mangas in anilist, more details of the response : \n {:#?}  ", response).into());
+        }
+
+        let response: SearchMangaEntriesResponse = response.json().await?;
+
+        Ok(response
+            .data
+            .page
+            .media
+            .into_iter()
+            .map(|media| TrackerEntry {
+                id: media.id.to_string(),
+                title: media.title.user_preferred,
+            })
+            .collect())
+    }
+
     async fn search_manga_by_title(&self, title: SearchTerm) -> Result<Option<MangaToTrack>, Box<dyn std::error::Error>> {
         let query = GetMangaByTitleQuery::new(title.get());
 
@@ -357,6 +529,52 @@ impl MangaTracker for Anilist {
 
         Ok(())
     }
+
+    async fn get_reading_progress(&self, id: &str) -> Result<Option<u32>, Box<dyn Error>> {
+        let query = GetReadingProgressQuery(id.parse()?);
+
+        let response = self
+            .client
+            .post(self.base_url.clone())
+            .body(query.into_body())
+            .header(AUTHORIZATION, self.access_token.clone())
+            .send()
+            .await?;
+
+        if response.status() != StatusCode::OK {
+            return Err(format!(
+                "could not get the reading progress from anilist, more details of the response : \n {:#?}  ",
+                response
+            )
+            .into());
+        }
+
+        let response: GetReadingProgressResponse = response.json().await?;
+
+        Ok(response.data.media.media_list_entry.and_then(|entry| entry.progress))
+    }
+
+    async fn update_list_entry(&self, entry: UpdateEntryArgs<'_>) -> Result<(), Box<dyn Error>> {
+        let query = UpdateMediaListEntry::new(entry.id.parse()?, entry.status, entry.score);
+
+        let response = self
+            .client
+            .post(self.base_url.clone())
+            .body(query.into_body())
+            .header(AUTHORIZATION, self.access_token.clone())
+            .send()
+            .await?;
+
+        if response.status() != StatusCode::OK {
+            return Err(format!(
+                "could not update the manga entry in anilist, more details of the response : \n {:#?}  ",
+                response
+            )
+            .into());
+        }
+
+        Ok(())
+    }
 }
 
 impl AnilistTokenChecker for Anilist {
@@ -652,4 +870,111 @@ mod tests {
 
         request.assert_async().await;
     }
+
+    #[test]
+    fn update_media_list_entry_scales_the_score() {
+        let query = UpdateMediaListEntry::new(123, TrackingStatus::Completed, Some(8));
+
+        assert_eq!(json!({ "id" : 123, "status" : "COMPLETED", "scoreRaw" : 80 }), query.variables());
+        assert!(query.query().contains("scoreRaw"));
+
+        let query = UpdateMediaListEntry::new(123, TrackingStatus::Dropped, None);
+
+        assert_eq!(json!({ "id" : 123, "status" : "DROPPED" }), query.variables());
+        assert!(!query.query().contains("scoreRaw"));
+    }
+
+    #[tokio::test]
+    async fn anilist_updates_the_status_and_score_of_an_entry() {
+        let server = MockServer::start_async().await;
+
+        let access_token = Uuid::new_v4().to_string();
+        let anilist = Anilist::new(server.base_url().parse().unwrap()).with_token(access_token.clone());
+
+        let expected_body_sent = UpdateMediaListEntry::new(86635, TrackingStatus::Reading, Some(7)).into_json();
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(POST).header("Authorization", access_token).json_body_obj(&expected_body_sent);
+                then.status(200);
+            })
+            .await;
+
+        anilist
+            .update_list_entry(UpdateEntryArgs {
+                id: "86635",
+                status: TrackingStatus::Reading,
+                score: Some(7),
+            })
+            .await
+            .expect("should update the entry");
+
+        request.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn anilist_gets_the_reading_progress_of_a_manga() {
+        let server = MockServer::start_async().await;
+
+        let access_token = Uuid::new_v4().to_string();
+        let anilist = Anilist::new(server.base_url().parse().unwrap()).with_token(access_token.clone());
+
+        let expected_body_sent = GetReadingProgressQuery(86635).into_json();
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(POST).header("Authorization", access_token).json_body_obj(&expected_body_sent);
+                then.status(200).json_body(json!({
+                    "data" : { "Media" : { "mediaListEntry" : { "progress" : 12 } } }
+                }));
+            })
+            .await;
+
+        let progress = anilist.get_reading_progress("86635").await.expect("should get the progress");
+
+        request.assert_async().await;
+
+        assert_eq!(Some(12), progress);
+    }
+
+    #[tokio::test]
+    async fn anilist_searches_all_the_entries_matching_a_title() {
+        let server = MockServer::start_async().await;
+        let anilist = Anilist::new(server.base_url().parse().unwrap());
+
+        let expected_body_sent = SearchMangaEntriesQuery("some title").into_json();
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(POST).json_body_obj(&expected_body_sent);
+                then.status(200).json_body(json!({
+                    "data" : { "Page" : { "media" : [
+                        { "id" : 1, "title" : { "userPreferred" : "some title" } },
+                        { "id" : 2, "title" : { "userPreferred" : "some title: side story" } }
+                    ] } }
+                }));
+            })
+            .await;
+
+        let entries = anilist
+            .search_manga_entries(SearchTerm::trimmed("some title").unwrap())
+            .await
+            .expect("should search the entries");
+
+        request.assert_async().await;
+
+        assert_eq!(
+            vec![
+                TrackerEntry {
+                    id: "1".to_string(),
+                    title: "some title".to_string()
+                },
+                TrackerEntry {
+                    id: "2".to_string(),
+                    title: "some title: side story".to_string()
+                },
+            ],
+            entries
+        );
+    }
 }
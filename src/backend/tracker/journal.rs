@@ -0,0 +1,238 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use manga_tui::SearchTerm;
+use serde::Serialize;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+
+use crate::backend::tracker::{
+    MangaToTrack, MangaTracker, MarkAsRead, PlanToReadArgs, TrackerEntry, TrackingStatus, UpdateEntryArgs,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalFormat {
+    /// One json object per line
+    Json,
+    Markdown,
+}
+
+impl JournalFormat {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Json => "reading-journal.jsonl",
+            Self::Markdown => "reading-journal.md",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct JournalEntry<'a> {
+    date: String,
+    title: &'a str,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chapter: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    volume: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<u32>,
+}
+
+impl JournalEntry<'_> {
+    fn to_markdown(&self) -> String {
+        let mut progress = match (self.chapter, self.volume) {
+            (Some(chapter), Some(volume)) => format!("read up to chapter {chapter} (volume {volume})"),
+            (Some(chapter), None) => format!("read up to chapter {chapter}"),
+            _ => self.status.replace('_', " "),
+        };
+
+        if let Some(score) = self.score {
+            progress.push_str(&format!(", scored {score}/10"));
+        }
+
+        format!("- {} | **{}** | {progress}", self.date, self.title)
+    }
+}
+
+/// Keeps the reading progress in a file inside the data directory instead of an online service, since there is no
+/// service to match the manga against, its title is used as the id
+#[derive(Debug, Clone)]
+pub struct LocalJournal {
+    path: PathBuf,
+    format: JournalFormat,
+}
+
+impl LocalJournal {
+    pub fn new(directory: &Path, format: JournalFormat) -> Self {
+        Self {
+            path: directory.join(format.file_name()),
+            format,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn append(&self, entry: JournalEntry<'_>) -> Result<(), Box<dyn Error>> {
+        let line = match self.format {
+            JournalFormat::Json => serde_json::to_string(&entry)?,
+            JournalFormat::Markdown => entry.to_markdown(),
+        };
+
+        let mut journal = OpenOptions::new().create(true).append(true).open(&self.path).await?;
+        journal.write_all(format!("{line}\n").as_bytes()).await?;
+        journal.flush().await?;
+
+        Ok(())
+    }
+}
+
+fn now() -> String {
+    Local::now().format("%Y-%m-%d %H:%M").to_string()
+}
+
+impl MangaTracker for LocalJournal {
+    fn name(&self) -> &'static str {
+        "journal"
+    }
+
+    async fn search_manga_entries(&self, title: SearchTerm) -> Result<Vec<TrackerEntry>, Box<dyn Error>> {
+        Ok(vec![TrackerEntry {
+            id: title.get().to_string(),
+            title: title.get().to_string(),
+        }])
+    }
+
+    async fn search_manga_by_title(&self, title: SearchTerm) -> Result<Option<MangaToTrack>, Box<dyn Error>> {
+        Ok(Some(MangaToTrack {
+            id: title.get().to_string(),
+        }))
+    }
+
+    async fn mark_manga_as_read_with_chapter_count(&self, manga: MarkAsRead<'_>) -> Result<(), Box<dyn Error>> {
+        self.append(JournalEntry {
+            date: now(),
+            title: manga.id,
+            status: "reading",
+            chapter: Some(manga.chapter_number),
+            volume: manga.volume_number,
+            score: None,
+        })
+        .await
+    }
+
+    async fn mark_manga_as_plan_to_read(&self, manga_to_plan_to_read: PlanToReadArgs<'_>) -> Result<(), Box<dyn Error>> {
+        self.append(JournalEntry {
+            date: now(),
+            title: manga_to_plan_to_read.id,
+            status: "plan_to_read",
+            chapter: None,
+            volume: None,
+            score: None,
+        })
+        .await
+    }
+
+    /// The journal is only ever appended to, it is not read back
+    async fn get_reading_progress(&self, _id: &str) -> Result<Option<u32>, Box<dyn Error>> {
+        Ok(None)
+    }
+
+    async fn update_list_entry(&self, entry: UpdateEntryArgs<'_>) -> Result<(), Box<dyn Error>> {
+        self.append(JournalEntry {
+            date: now(),
+            title: entry.id,
+            status: match entry.status {
+                TrackingStatus::Reading => "reading",
+                TrackingStatus::Completed => "completed",
+                TrackingStatus::Dropped => "dropped",
+            },
+            chapter: None,
+            volume: None,
+            score: entry.score,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn create_tests_directory() -> PathBuf {
+        let directory = Path::new("./test_results/journal").join(Uuid::new_v4().to_string());
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    #[tokio::test]
+    async fn it_appends_reading_progress_as_json_lines() -> Result<(), Box<dyn Error>> {
+        let journal = LocalJournal::new(&create_tests_directory(), JournalFormat::Json);
+
+        let manga = journal.search_manga_by_title(SearchTerm::trimmed("some title").unwrap()).await?.unwrap();
+
+        journal
+            .mark_manga_as_read_with_chapter_count(MarkAsRead {
+                id: &manga.id,
+                chapter_number: 3,
+                volume_number: Some(1),
+            })
+            .await?;
+        journal
+            .mark_manga_as_plan_to_read(PlanToReadArgs {
+                id: "another title",
+            })
+            .await?;
+
+        let contents = fs::read_to_string(journal.path())?;
+        let entries: Vec<serde_json::Value> = contents.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+        assert_eq!(2, entries.len());
+        assert_eq!("some title", entries[0]["title"]);
+        assert_eq!("reading", entries[0]["status"]);
+        assert_eq!(3, entries[0]["chapter"]);
+        assert_eq!(1, entries[0]["volume"]);
+        assert_eq!("plan_to_read", entries[1]["status"]);
+        assert!(entries[1].get("chapter").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_writes_entries_as_markdown_list_items() {
+        let entry = JournalEntry {
+            date: "2024-03-01 12:00".to_string(),
+            title: "some title",
+            status: "reading",
+            chapter: Some(3),
+            volume: None,
+            score: None,
+        };
+
+        assert_eq!("- 2024-03-01 12:00 | **some title** | read up to chapter 3", entry.to_markdown());
+
+        let entry = JournalEntry {
+            status: "plan_to_read",
+            chapter: None,
+            ..entry
+        };
+
+        assert_eq!("- 2024-03-01 12:00 | **some title** | plan to read", entry.to_markdown());
+
+        let entry = JournalEntry {
+            status: "completed",
+            score: Some(8),
+            ..entry
+        };
+
+        assert_eq!("- 2024-03-01 12:00 | **some title** | completed, scored 8/10", entry.to_markdown());
+    }
+}
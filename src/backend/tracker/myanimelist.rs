@@ -0,0 +1,557 @@
+pub static BASE_MYANIMELIST_API_URL: &str = "https://api.myanimelist.net/v2";
+pub static MYANIMELIST_AUTHORIZE_URL: &str = "https://myanimelist.net/v1/oauth2/authorize";
+pub static MYANIMELIST_TOKEN_URL: &str = "https://myanimelist.net/v1/oauth2/token";
+
+use std::error::Error;
+use std::time::Duration;
+
+use http::{HeaderMap, HeaderValue};
+use manga_tui::SearchTerm;
+use reqwest::header::ACCEPT;
+use reqwest::{Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::tracker::{
+    MangaToTrack, MangaTracker, MarkAsRead, PlanToReadArgs, TrackerEntry, TrackingStatus, UpdateEntryArgs,
+};
+use crate::global::USER_AGENT;
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct SearchMangaResponse {
+    data: Vec<SearchMangaData>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct SearchMangaData {
+    node: SearchMangaNode,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct SearchMangaNode {
+    id: u32,
+    #[serde(default)]
+    title: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct MangaListStatusResponse {
+    my_list_status: Option<MyListStatus>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct MyListStatus {
+    num_chapters_read: u32,
+}
+
+/// The fields sent when updating an entry of the user's list
+#[derive(Debug, Deserialize, Serialize, Default, PartialEq, Eq)]
+pub struct UpdateListStatusBody {
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_chapters_read: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_volumes_read: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<u32>,
+}
+
+impl UpdateListStatusBody {
+    fn reading(chapter_number: u32, volume_number: Option<u32>) -> Self {
+        Self {
+            status: "reading",
+            num_chapters_read: Some(chapter_number),
+            num_volumes_read: volume_number,
+            ..Default::default()
+        }
+    }
+
+    fn plan_to_read() -> Self {
+        Self {
+            status: "plan_to_read",
+            ..Default::default()
+        }
+    }
+
+    fn entry(status: TrackingStatus, score: Option<u32>) -> Self {
+        Self {
+            status: match status {
+                TrackingStatus::Reading => "reading",
+                TrackingStatus::Completed => "completed",
+                TrackingStatus::Dropped => "dropped",
+            },
+            score,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MyAnimeListAccessTokenResponse {
+    pub access_token: String,
+}
+
+/// Myanimelist uses OAuth2 with PKCE, only the `plain` method is supported so the code challenge is the verifier itself
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeVerifier(String);
+
+impl CodeVerifier {
+    /// The verifier must be between 43 and 128 characters long
+    pub fn generate() -> Self {
+        Self(format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn authorize_url(&self, client_id: &str) -> String {
+        format!(
+            "{MYANIMELIST_AUTHORIZE_URL}?response_type=code&client_id={client_id}&code_challenge={}&code_challenge_method=plain",
+            self.0
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MyAnimeList {
+    base_url: Url,
+    access_token: String,
+    client_id: String,
+    client: Client,
+}
+
+impl MyAnimeList {
+    pub fn new(base_url: Url) -> Self {
+        let mut default_headers = HeaderMap::new();
+
+        default_headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+
+        let client = Client::builder()
+            .default_headers(default_headers)
+            .timeout(Duration::from_secs(10))
+            .user_agent(&*USER_AGENT)
+            .build()
+            .unwrap();
+
+        Self {
+            base_url,
+            client,
+            client_id: String::default(),
+            access_token: String::default(),
+        }
+    }
+
+    pub fn with_token(mut self, token: String) -> Self {
+        self.access_token = token;
+        self
+    }
+
+    pub fn with_client_id(mut self, client_id: String) -> Self {
+        self.client_id = client_id;
+        self
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url.as_str().trim_end_matches('/'))
+    }
+
+    /// Exchange the code obtained after the user authorized the app for an access token
+    pub async fn request_access_token(
+        &self,
+        token_url: Url,
+        code: &str,
+        code_verifier: &CodeVerifier,
+    ) -> Result<String, Box<dyn Error>> {
+        let response = self
+            .client
+            .post(token_url)
+            .form(&[
+                ("client_id", self.client_id.as_str()),
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("code_verifier", code_verifier.as_str()),
+            ])
+            .send()
+            .await?;
+
+        if response.status() != StatusCode::OK {
+            return Err(
+                format!("could not get the myanimelist access token, more details of the response : \n {:#?}", response).into()
+            );
+        }
+
+        let response: MyAnimeListAccessTokenResponse = response.json().await?;
+
+        Ok(response.access_token)
+    }
+
+    pub async fn check_credentials_are_valid(&self) -> Result<bool, Box<dyn Error>> {
+        let response = self
+            .client
+            .get(self.endpoint("/users/@me"))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
+
+        Ok(response.status() == StatusCode::OK)
+    }
+
+    async fn update_list_status(&self, id: &str, body: UpdateListStatusBody) -> Result<StatusCode, Box<dyn Error>> {
+        let response = self
+            .client
+            .patch(self.endpoint(&format!("/manga/{id}/my_list_status")))
+            .bearer_auth(&self.access_token)
+            .form(&body)
+            .send()
+            .await?;
+
+        Ok(response.status())
+    }
+
+    async fn search_manga(&self, title: SearchTerm, limit: &str) -> Result<SearchMangaResponse, Box<dyn Error>> {
+        let response = self
+            .client
+            .get(self.endpoint("/manga"))
+            .query(&[("q", title.get()), ("limit", limit)])
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(SearchMangaResponse::default());
+        }
+
+        Ok(response.json().await?)
+    }
+}
+
+impl MangaTracker for MyAnimeList {
+    fn name(&self) -> &'static str {
+        "myanimelist"
+    }
+
+    async fn search_manga_entries(&self, title: SearchTerm) -> Result<Vec<TrackerEntry>, Box<dyn Error>> {
+        let response = self.search_manga(title, "10").await?;
+
+        Ok(response
+            .data
+            .into_iter()
+            .map(|manga| TrackerEntry {
+                id: manga.node.id.to_string(),
+                title: manga.node.title,
+            })
+            .collect())
+    }
+
+    async fn search_manga_by_title(&self, title: SearchTerm) -> Result<Option<MangaToTrack>, Box<dyn Error>> {
+        let response = self.search_manga(title, "1").await?;
+
+        Ok(response.data.first().map(|manga| MangaToTrack {
+            id: manga.node.id.to_string(),
+        }))
+    }
+
+    async fn mark_manga_as_read_with_chapter_count(&self, manga: MarkAsRead<'_>) -> Result<(), Box<dyn Error>> {
+        let status = self
+            .update_list_status(manga.id, UpdateListStatusBody::reading(manga.chapter_number, manga.volume_number))
+            .await?;
+
+        if status != StatusCode::OK {
+            return Err(format!("could not sync reading status with myanimelist, status code of the response : {status}").into());
+        }
+
+        Ok(())
+    }
+
+    async fn mark_manga_as_plan_to_read(&self, manga_to_plan_to_read: PlanToReadArgs<'_>) -> Result<(), Box<dyn Error>> {
+        let status = self
+            .update_list_status(manga_to_plan_to_read.id, UpdateListStatusBody::plan_to_read())
+            .await?;
+
+        if status != StatusCode::OK {
+            return Err(
+                format!("could not mark manga as plan to read in myanimelist, status code of the response : {status}").into()
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn get_reading_progress(&self, id: &str) -> Result<Option<u32>, Box<dyn Error>> {
+        let response = self
+            .client
+            .get(self.endpoint(&format!("/manga/{id}")))
+            .query(&[("fields", "my_list_status")])
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
+
+        if response.status() != StatusCode::OK {
+            return Err(format!(
+                "could not get the reading progress from myanimelist, status code of the response : {}",
+                response.status()
+            )
+            .into());
+        }
+
+        let response: MangaListStatusResponse = response.json().await?;
+
+        Ok(response.my_list_status.map(|status| status.num_chapters_read))
+    }
+
+    async fn update_list_entry(&self, entry: UpdateEntryArgs<'_>) -> Result<(), Box<dyn Error>> {
+        let status = self
+            .update_list_status(entry.id, UpdateListStatusBody::entry(entry.status, entry.score))
+            .await?;
+
+        if status != StatusCode::OK {
+            return Err(format!("could not update the manga entry in myanimelist, status code of the response : {status}").into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::Method::{GET, PATCH, POST};
+    use httpmock::MockServer;
+    use pretty_assertions::assert_eq;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn code_verifier_has_a_valid_length() {
+        let verifier = CodeVerifier::generate();
+
+        assert!((43..=128).contains(&verifier.as_str().len()));
+        assert!(
+            verifier
+                .authorize_url("some_id")
+                .contains(&format!("code_challenge={}", verifier.as_str()))
+        );
+    }
+
+    #[tokio::test]
+    async fn myanimelist_searches_a_manga_by_its_title() {
+        let server = MockServer::start_async().await;
+        let access_token = Uuid::new_v4().to_string();
+        let myanimelist = MyAnimeList::new(server.base_url().parse().unwrap()).with_token(access_token.clone());
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/manga")
+                    .query_param("q", "some title")
+                    .query_param("limit", "1")
+                    .header("Authorization", format!("Bearer {access_token}"));
+                then.status(200).json_body(serde_json::json!({
+                    "data" : [{ "node" : { "id" : 2, "title" : "some title" } }]
+                }));
+            })
+            .await;
+
+        let response = myanimelist
+            .search_manga_by_title(SearchTerm::trimmed("some title").unwrap())
+            .await
+            .expect("should search manga by title");
+
+        request.assert_async().await;
+
+        assert_eq!(
+            Some(MangaToTrack {
+                id: "2".to_string()
+            }),
+            response
+        );
+    }
+
+    #[tokio::test]
+    async fn myanimelist_searches_all_the_entries_matching_a_title() {
+        let server = MockServer::start_async().await;
+        let myanimelist = MyAnimeList::new(server.base_url().parse().unwrap());
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/manga").query_param("q", "some title").query_param("limit", "10");
+                then.status(200).json_body(serde_json::json!({
+                    "data" : [
+                        { "node" : { "id" : 2, "title" : "some title" } },
+                        { "node" : { "id" : 3, "title" : "some title: side story" } }
+                    ]
+                }));
+            })
+            .await;
+
+        let entries = myanimelist
+            .search_manga_entries(SearchTerm::trimmed("some title").unwrap())
+            .await
+            .expect("should search the entries");
+
+        request.assert_async().await;
+
+        let ids: Vec<&str> = entries.iter().map(|entry| entry.id.as_str()).collect();
+
+        assert_eq!(vec!["2", "3"], ids);
+        assert_eq!("some title: side story", entries[1].title);
+    }
+
+    #[tokio::test]
+    async fn myanimelist_returns_none_if_no_manga_matches_the_title() {
+        let server = MockServer::start_async().await;
+        let myanimelist = MyAnimeList::new(server.base_url().parse().unwrap());
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/manga");
+                then.status(200).json_body(serde_json::json!({ "data" : [] }));
+            })
+            .await;
+
+        let response = myanimelist
+            .search_manga_by_title(SearchTerm::trimmed("some title").unwrap())
+            .await
+            .expect("should search manga by title");
+
+        request.assert_async().await;
+
+        assert!(response.is_none());
+    }
+
+    #[tokio::test]
+    async fn myanimelist_marks_manga_as_reading_with_chapter_and_volume_count() {
+        let server = MockServer::start_async().await;
+        let access_token = Uuid::new_v4().to_string();
+        let myanimelist = MyAnimeList::new(server.base_url().parse().unwrap()).with_token(access_token.clone());
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(PATCH)
+                    .path("/manga/2/my_list_status")
+                    .header("Authorization", format!("Bearer {access_token}"))
+                    .x_www_form_urlencoded_tuple("status", "reading")
+                    .x_www_form_urlencoded_tuple("num_chapters_read", "10")
+                    .x_www_form_urlencoded_tuple("num_volumes_read", "1");
+                then.status(200);
+            })
+            .await;
+
+        myanimelist
+            .mark_manga_as_read_with_chapter_count(MarkAsRead {
+                id: "2",
+                chapter_number: 10,
+                volume_number: Some(1),
+            })
+            .await
+            .expect("should mark manga as reading");
+
+        request.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn myanimelist_marks_manga_as_plan_to_read() {
+        let server = MockServer::start_async().await;
+        let myanimelist = MyAnimeList::new(server.base_url().parse().unwrap()).with_token(Uuid::new_v4().to_string());
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(PATCH)
+                    .path("/manga/2/my_list_status")
+                    .x_www_form_urlencoded_tuple("status", "plan_to_read");
+                then.status(200);
+            })
+            .await;
+
+        myanimelist
+            .mark_manga_as_plan_to_read(PlanToReadArgs { id: "2" })
+            .await
+            .expect("should mark manga as plan to read");
+
+        request.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn myanimelist_updates_the_status_and_score_of_an_entry() {
+        let server = MockServer::start_async().await;
+        let myanimelist = MyAnimeList::new(server.base_url().parse().unwrap()).with_token(Uuid::new_v4().to_string());
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(PATCH)
+                    .path("/manga/2/my_list_status")
+                    .x_www_form_urlencoded_tuple("status", "completed")
+                    .x_www_form_urlencoded_tuple("score", "9");
+                then.status(200);
+            })
+            .await;
+
+        myanimelist
+            .update_list_entry(UpdateEntryArgs {
+                id: "2",
+                status: TrackingStatus::Completed,
+                score: Some(9),
+            })
+            .await
+            .expect("should update the entry");
+
+        request.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn myanimelist_gets_the_chapters_read_of_a_manga() {
+        let server = MockServer::start_async().await;
+        let myanimelist = MyAnimeList::new(server.base_url().parse().unwrap()).with_token(Uuid::new_v4().to_string());
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/manga/2").query_param("fields", "my_list_status");
+                then.status(200).json_body(serde_json::json!({
+                    "id" : 2,
+                    "title" : "some title",
+                    "my_list_status" : { "status" : "reading", "num_chapters_read" : 15 }
+                }));
+            })
+            .await;
+
+        let progress = myanimelist.get_reading_progress("2").await.expect("should get the progress");
+
+        request.assert_async().await;
+
+        assert_eq!(Some(15), progress);
+    }
+
+    #[tokio::test]
+    async fn myanimelist_exchanges_the_code_for_an_access_token() {
+        let server = MockServer::start_async().await;
+        let myanimelist = MyAnimeList::new(server.base_url().parse().unwrap()).with_client_id("some_client_id".to_string());
+        let verifier = CodeVerifier::generate();
+        let expected_token = Uuid::new_v4().to_string();
+
+        let request = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/token")
+                    .x_www_form_urlencoded_tuple("client_id", "some_client_id")
+                    .x_www_form_urlencoded_tuple("grant_type", "authorization_code")
+                    .x_www_form_urlencoded_tuple("code", "some_code")
+                    .x_www_form_urlencoded_tuple("code_verifier", verifier.as_str());
+                then.status(200).json_body(serde_json::json!({
+                    "token_type" : "Bearer",
+                    "expires_in" : 2678400,
+                    "access_token" : expected_token,
+                    "refresh_token" : "some_refresh_token"
+                }));
+            })
+            .await;
+
+        let token = myanimelist
+            .request_access_token(format!("{}/token", server.base_url()).parse().unwrap(), "some_code", &verifier)
+            .await
+            .expect("should get the access token");
+
+        request.assert_async().await;
+
+        assert_eq!(expected_token, token);
+    }
+}
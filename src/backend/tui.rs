@@ -3,13 +3,16 @@ use std::time::Duration;
 
 use crossterm::event::{KeyEvent, MouseEvent};
 use futures::{FutureExt, StreamExt};
+use manga_tui::MangaLink;
 use ratatui::backend::Backend;
 use ratatui::Terminal;
 use ratatui_image::picker::{Picker, ProtocolType};
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::task::JoinHandle;
 
+use super::database::{ChapterBookmarked, DownloadAllJob, HistoryToPrune};
 use super::fetch::ApiClient;
+use super::filter::Languages;
 use super::tracker::MangaTracker;
 use crate::common::{Artist, Author};
 use crate::view::app::{App, AppState, MangaToRead};
@@ -27,14 +30,22 @@ pub enum Events {
     Tick,
     Key(KeyEvent),
     Mouse(MouseEvent),
+    /// The terminal was resized to these columns and rows
+    Resize(u16, u16),
     GoToMangaPage(MangaItem),
     GoBackMangaPage,
     GoToHome,
     GoSearchPage,
     GoSearchMangasAuthor(Author),
     GoSearchMangasArtist(Artist),
+    GoSearchMangasTitle(String),
+    /// A chapter of the manga with this id was read, the manga page and the mangas to continue reading on the home page
+    /// are updated
+    ChapterStatusChanged(String),
     GoFeedPage,
     ReadChapter(ChapterToRead, MangaToRead),
+    /// Go to the manga page and continue downloading the chapters that are not downloaded yet
+    ResumeDownloadAll(MangaItem, Languages),
 }
 
 #[cfg(unix)]
@@ -96,9 +107,20 @@ fn get_picker() -> Option<Picker> {
 pub async fn run_app(
     mut terminal: Terminal<impl Backend>,
     api_client: impl ApiClient + SearchChapter + SearchMangaPanel,
-    manga_tracker: Option<impl MangaTracker>,
+    manga_trackers: Vec<impl MangaTracker>,
+    session_to_restore: Option<ChapterBookmarked>,
+    download_to_resume: Option<DownloadAllJob>,
+    history_to_prune: Option<HistoryToPrune>,
+    manga_to_open: Option<MangaLink>,
 ) -> Result<(), Box<dyn Error>> {
-    let mut app = App::new(api_client, manga_tracker, get_picker());
+    let mut app = App::new(api_client, manga_trackers, get_picker())
+        .with_session_to_restore(session_to_restore)
+        .with_download_to_resume(download_to_resume)
+        .with_history_to_prune(history_to_prune);
+
+    if let Some(link) = manga_to_open {
+        app.open_manga_link(link);
+    }
 
     let tick_rate = std::time::Duration::from_millis(250);
 
@@ -140,6 +162,9 @@ pub fn handle_events(tick_rate: Duration, event_tx: UnboundedSender<Events>) ->
                                 crossterm::event::Event::Mouse(mouse_event) => {
                                     event_tx.send(Events::Mouse(mouse_event)).ok();
                                 }
+                                crossterm::event::Event::Resize(columns, rows) => {
+                                    event_tx.send(Events::Resize(columns, rows)).ok();
+                                }
                                 _ => {}
                             }
                         }
@@ -0,0 +1,374 @@
+//! Cbz files dropped in the folder set in `watch_folder` are registered as downloaded chapters of the manga of the
+//! library they belong to, the folder is checked every minute while manga-tui is running
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use uuid::Uuid;
+use zip::ZipArchive;
+
+use super::database::{ChapterDownloaded, Database};
+use super::error_log::{write_to_error_log, ErrorType};
+use crate::config::DownloadType;
+
+const SCAN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What could be found out about a chapter from its `ComicInfo.xml` and its file name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExternalArchive {
+    pub path: PathBuf,
+    pub series: Option<String>,
+    pub chapter_number: Option<String>,
+    pub title: Option<String>,
+    /// Archives downloaded by manga-tui end with the id of the chapter and are in a directory ending with the id of the
+    /// manga
+    pub chapter_id: Option<String>,
+    pub manga_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanResult {
+    Registered {
+        manga_title: String,
+        chapter_title: String,
+    },
+    /// No manga of the library has the title of the series the archive is from
+    NotMatched(PathBuf),
+}
+
+/// The last word of `name` if it is an id like the ones mangadex uses
+fn trailing_id(name: &str) -> Option<String> {
+    name.split_whitespace()
+        .last()
+        .filter(|word| Uuid::parse_str(word).is_ok())
+        .map(String::from)
+}
+
+fn without_trailing_id(name: &str) -> &str {
+    match trailing_id(name) {
+        Some(id) => name.trim_end().trim_end_matches(id.as_str()).trim_end(),
+        None => name.trim(),
+    }
+}
+
+const CHAPTER_PREFIXES: [&str; 7] = ["", "c", "c.", "ch", "ch.", "chapter", "#"];
+
+/// The number of words like "12", "Ch.12" or "c012.5", words like "20th" or "Vol.3" are not chapter numbers
+fn chapter_number(word: &str) -> Option<String> {
+    let (prefix, number) = word.split_at(word.find(|c: char| c.is_ascii_digit())?);
+    let number = number.trim_end_matches('.');
+
+    if !CHAPTER_PREFIXES.contains(&prefix.to_lowercase().as_str()) || !number.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+
+    // "012" is chapter "12" but "0" and "0.5" keep their zero
+    let without_zeros = number.trim_start_matches('0');
+    if without_zeros.is_empty() || without_zeros.starts_with('.') {
+        Some(format!("0{without_zeros}"))
+    } else {
+        Some(without_zeros.to_string())
+    }
+}
+
+/// Understands names like "One Piece - Ch. 1050", "Berserk 364" or the ones manga-tui gives, "Ch. 12 <title> <id>"
+fn parse_file_name(name: &str) -> (Option<String>, Option<String>) {
+    let name = without_trailing_id(name);
+
+    let (series, rest) = match name.split_once(" - ") {
+        Some((series, rest)) => (Some(series.trim()), rest),
+        None => (None, name),
+    };
+
+    let words: Vec<&str> = rest.split_whitespace().collect();
+
+    let number_position = words.iter().enumerate().position(|(index, word)| {
+        chapter_number(word).is_some()
+            || (["ch", "ch.", "chapter"].contains(&word.to_lowercase().as_str())
+                && words.get(index + 1).and_then(|next| chapter_number(next)).is_some())
+    });
+
+    let Some(position) = number_position else {
+        return (series.or(Some(rest.trim())).filter(|series| !series.is_empty()).map(String::from), None);
+    };
+
+    let number = chapter_number(words[position]).or_else(|| words.get(position + 1).and_then(|word| chapter_number(word)));
+
+    let series = series
+        .map(String::from)
+        .or_else(|| Some(words[..position].join(" ")))
+        .filter(|series| !series.is_empty());
+
+    (series, number)
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The text of the first `<tag>` of the xml, `ComicInfo.xml` is simple enough to not need a xml parser
+fn xml_tag(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{tag}>"))? + tag.len() + 2;
+    let end = xml[start..].find(&format!("</{tag}>"))? + start;
+
+    Some(unescape_xml(xml[start..end].trim())).filter(|text| !text.is_empty())
+}
+
+impl ExternalArchive {
+    /// `ComicInfo.xml` is preferred over the file name when the archive has it
+    pub fn parse(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut archive = ZipArchive::new(File::open(path)?)?;
+
+        let comic_info = match archive.by_name("ComicInfo.xml") {
+            Ok(mut file) => {
+                let mut xml = String::new();
+                file.read_to_string(&mut xml)?;
+                Some(xml)
+            },
+            Err(_) => None,
+        };
+
+        let file_name = path.file_stem().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let directory_name = path
+            .parent()
+            .and_then(|directory| directory.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let (series, chapter_number) = parse_file_name(&file_name);
+        let manga_id = trailing_id(&directory_name);
+
+        let comic_info_value = |tag: &str| comic_info.as_deref().and_then(|xml| xml_tag(xml, tag));
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            series: comic_info_value("Series")
+                .or(series)
+                .or_else(|| manga_id.is_some().then(|| without_trailing_id(&directory_name).to_string())),
+            chapter_number: comic_info_value("Number").or(chapter_number),
+            title: comic_info_value("Title"),
+            chapter_id: trailing_id(&file_name),
+            manga_id,
+        })
+    }
+
+    /// Ids of archives from other sources are made from their file name so the same file is registered once
+    fn id(&self) -> String {
+        self.chapter_id
+            .clone()
+            .unwrap_or_else(|| format!("local:{}", self.path.file_name().unwrap_or_default().to_string_lossy()))
+    }
+
+    fn chapter_title(&self) -> String {
+        match (&self.chapter_number, &self.title) {
+            (Some(number), Some(title)) => format!("Ch. {number} {title}"),
+            (Some(number), None) => format!("Ch. {number}"),
+            (None, Some(title)) => title.clone(),
+            (None, None) => self.path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+        }
+    }
+}
+
+/// The archive's chapter or manga id are used if they are in the database, otherwise the manga is searched by the
+/// title of the series
+fn find_manga(archive: &ExternalArchive, database: &Database<'_>) -> Result<Option<(String, String)>, Box<dyn Error>> {
+    if let Some(manga_id) = archive
+        .chapter_id
+        .as_deref()
+        .map(|id| database.get_chapter_manga(id))
+        .transpose()?
+        .flatten()
+    {
+        return Ok(Some((manga_id, archive.series.clone().unwrap_or_default())));
+    }
+
+    if let Some(manga_id) = archive.manga_id.as_deref() {
+        if database.manga_exists(manga_id)? {
+            return Ok(Some((manga_id.to_string(), archive.series.clone().unwrap_or_default())));
+        }
+    }
+
+    let found = archive
+        .series
+        .as_deref()
+        .map(|series| database.find_manga_by_title(series))
+        .transpose()?
+        .flatten();
+
+    Ok(found.map(|manga| (manga.id, manga.title)))
+}
+
+fn find_archives(folder: &Path, archives: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(folder)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            find_archives(&path, archives)?;
+        } else if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("cbz")) {
+            archives.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Registers the cbz files of `folder` and its subdirectories which were not registered yet
+pub fn scan_folder(folder: &Path, database: &Database<'_>) -> Result<Vec<ScanResult>, Box<dyn Error>> {
+    let mut archives = vec![];
+    find_archives(folder, &mut archives)?;
+
+    let mut results = vec![];
+
+    for path in archives {
+        if database.is_download_registered(&path)? {
+            continue;
+        }
+
+        let archive = ExternalArchive::parse(&path)?;
+
+        let Some((manga_id, manga_title)) = find_manga(&archive, database)? else {
+            results.push(ScanResult::NotMatched(path));
+            continue;
+        };
+
+        let chapter_title = archive.chapter_title();
+
+        database.register_external_chapter(
+            &manga_id,
+            &archive.id(),
+            &chapter_title,
+            &ChapterDownloaded {
+                bytes: std::fs::metadata(&path)?.len(),
+                path,
+                format: DownloadType::Cbz,
+            },
+        )?;
+
+        results.push(ScanResult::Registered {
+            manga_title,
+            chapter_title,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Archives which could not be matched are checked again each time in case the manga is added to the library later
+pub fn watch_folder(folder: PathBuf) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SCAN_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let folder = folder.clone();
+            let scanned = tokio::task::spawn_blocking(move || {
+                let connection = Database::get_connection()?;
+                scan_folder(&folder, &Database::new(&connection)).map_err(|e| e.to_string())?;
+                Ok::<(), Box<dyn Error + Send + Sync>>(())
+            })
+            .await;
+
+            if let Ok(Err(e)) = scanned {
+                write_to_error_log(ErrorType::String(&format!("Could not register the chapters of the watched folder: {e}")));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use pretty_assertions::assert_eq;
+    use rusqlite::Connection;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    use super::*;
+    use crate::backend::database::{save_plan_to_read, MangaPlanToReadSave};
+
+    fn create_cbz(path: &Path, comic_info: Option<&str>) -> Result<(), Box<dyn Error>> {
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        let mut zip = ZipWriter::new(File::create(path)?);
+
+        if let Some(comic_info) = comic_info {
+            zip.start_file("ComicInfo.xml", SimpleFileOptions::default())?;
+            zip.write_all(comic_info.as_bytes())?;
+        }
+
+        zip.start_file("1.jpg", SimpleFileOptions::default())?;
+        zip.write_all(b"image")?;
+        zip.finish()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_parses_the_series_and_chapter_number_from_the_file_name() {
+        let chapter_id = Uuid::new_v4().to_string();
+
+        assert_eq!((Some("One Piece".to_string()), Some("1050".to_string())), parse_file_name("One Piece - Ch. 1050"));
+        assert_eq!((Some("Berserk".to_string()), Some("364".to_string())), parse_file_name("Berserk 364"));
+        assert_eq!((Some("Dandadan".to_string()), Some("12.5".to_string())), parse_file_name("Dandadan c012.5"));
+        assert_eq!((None, Some("12".to_string())), parse_file_name(&format!("Ch. 12 The title Some group {chapter_id}")));
+        assert_eq!((Some("20th Century Boys".to_string()), Some("5".to_string())), parse_file_name("20th Century Boys Chapter 5"));
+        assert_eq!((Some("Oneshot".to_string()), None), parse_file_name("Oneshot"));
+        assert_eq!(Some(chapter_id.clone()), trailing_id(&format!("Ch. 12 The title {chapter_id}")));
+    }
+
+    #[test]
+    fn it_registers_the_archives_of_mangas_in_the_library() -> Result<(), Box<dyn Error>> {
+        let folder = Path::new("./test_results/watch-folder");
+        let _ = std::fs::remove_dir_all(folder);
+
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+        database.setup()?;
+
+        save_plan_to_read(
+            MangaPlanToReadSave {
+                id: "some_manga",
+                title: "Vinland Saga",
+                img_url: None,
+            },
+            &connection,
+        )?;
+
+        create_cbz(
+            &folder.join("Vinland Saga - Ch. 200.cbz"),
+            Some("<ComicInfo><Series>Vinland Saga</Series><Number>201</Number><Title>Thorfinn &amp; Einar</Title></ComicInfo>"),
+        )?;
+        create_cbz(&folder.join("nested").join("Unknown manga 3.cbz"), None)?;
+
+        let mut results = scan_folder(folder, &database)?;
+        results.sort_by_key(|result| matches!(result, ScanResult::NotMatched(_)));
+
+        assert_eq!(
+            vec![
+                ScanResult::Registered {
+                    manga_title: "Vinland Saga".to_string(),
+                    chapter_title: "Ch. 201 Thorfinn & Einar".to_string(),
+                },
+                ScanResult::NotMatched(folder.join("nested").join("Unknown manga 3.cbz")),
+            ],
+            results
+        );
+
+        let chapter = database.get_chapter_manga("local:Vinland Saga - Ch. 200.cbz")?;
+
+        assert_eq!(Some("some_manga".to_string()), chapter);
+
+        let registered_again = scan_folder(folder, &database)?;
+
+        assert_eq!(vec![ScanResult::NotMatched(folder.join("nested").join("Unknown manga 3.cbz"))], registered_again);
+
+        Ok(())
+    }
+}
@@ -2,19 +2,38 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::future::Future;
 use std::io::BufRead;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 
 use clap::{crate_version, Parser, Subcommand};
+use manga_tui::{exists, MangaLink};
+use reqwest::Url;
+use rusqlite::Connection;
 use strum::IntoEnumIterator;
 
+use crate::backend::chapter_notifier::{check_new_chapters, check_new_mangas_of_followed_authors, ChapterNotifier};
+use crate::backend::database::Database;
 use crate::backend::error_log::write_to_error_log;
 use crate::backend::filter::Languages;
+use crate::backend::media_server::{MediaServerClient, ServerSeries};
+use crate::backend::migration::{
+    current_schema_version, migrate_down, migrate_up, migrations_to_roll_back, pending_migrations, NumberedMigration,
+};
+use crate::backend::fetch::{MangadexClient, API_URL_BASE, COVER_IMG_URL_BASE};
+use crate::backend::request_log::{RequestLog, MAX_REQUESTS_RECORDED, REQUEST_LOG};
 use crate::backend::secrets::anilist::{AnilistCredentials, AnilistStorage};
+use crate::backend::secrets::mangadex::{MangadexCredentials, MangadexStorage};
+use crate::backend::secrets::myanimelist::{MyAnimeListCredentials, MyAnimeListStorage};
+use crate::backend::myanimelist_list::{export_list, import_list, parse_list, ListEntry};
 use crate::backend::secrets::SecretStorage;
+use crate::backend::tachiyomi::{import_backup, Backup, ImportSummary};
 use crate::backend::tracker::anilist::{self, BASE_ANILIST_API_URL};
-use crate::backend::APP_DATA_DIR;
+use crate::backend::tracker::myanimelist::{CodeVerifier, MyAnimeList, BASE_MYANIMELIST_API_URL, MYANIMELIST_TOKEN_URL};
+use crate::backend::{build_data_dir, APP_DATA_DIR};
+use crate::config::MangaTuiConfig;
 use crate::global::PREFERRED_LANGUAGE;
 use crate::logger::{ILogger, Logger};
+use crate::view::pages::wrapped::format_size;
 
 fn read_input(mut input_reader: impl BufRead, logger: &impl ILogger, message: &str) -> Result<String, Box<dyn Error>> {
     logger.inform(message);
@@ -31,6 +50,65 @@ pub enum AnilistCommand {
     Check,
 }
 
+#[derive(Subcommand, Clone, Copy)]
+pub enum MyAnimeListCommand {
+    /// login to your myanimelist account to be able to sync reading progress
+    Init,
+    /// check wheter or not myanimelist is setup correctly
+    Check,
+}
+
+#[derive(Subcommand, Clone, Copy)]
+pub enum MangadexCommand {
+    /// login to your mangadex account to see your follows and sync chapters read
+    Init,
+    /// check wheter or not the mangadex account is setup correctly
+    Check,
+}
+
+#[derive(Subcommand, Clone)]
+pub enum ConfigCommand {
+    /// print the settings being used and whether they come from the config file or the defaults
+    Show,
+    /// copy the config file to `file` to use it on another machine, the reading history is not included
+    Export { file: PathBuf },
+    /// replace the config file with `file` if it is valid, the current one is kept as `manga-tui-config.toml.bak`
+    Import { file: PathBuf },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum ImportCommand {
+    /// import the reading history and library of a Tachiyomi or Mihon backup (`.tachibk`), only mangas from the mangadex
+    /// source are imported
+    Tachiyomi { file: PathBuf },
+    /// import a list exported from myanimelist, the mangas are searched on mangadex by their title
+    #[command(name = "myanimelist")]
+    MyAnimeList { file: PathBuf },
+    /// import the series of a komga server, the ones with read books go to the reading history and the rest to plan to
+    /// read, the mangas are searched on mangadex by their title
+    Komga {
+        url: Url,
+        /// created on komga's account settings
+        #[arg(long)]
+        api_key: String,
+    },
+    /// import the series of a kavita server, the ones with read pages go to the reading history and the rest to plan to
+    /// read, the mangas are searched on mangadex by their title
+    Kavita {
+        url: Url,
+        /// shown on kavita's user settings
+        #[arg(long)]
+        api_key: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum ExportCommand {
+    /// export the reading history and plan to read as a list that can be imported in myanimelist
+    #[command(name = "myanimelist")]
+    MyAnimeList { file: PathBuf },
+}
+
 #[derive(Subcommand, Clone)]
 pub enum Commands {
     Lang {
@@ -44,6 +122,64 @@ pub enum Commands {
         #[command(subcommand)]
         command: AnilistCommand,
     },
+
+    Mangadex {
+        #[command(subcommand)]
+        command: MangadexCommand,
+    },
+
+    #[command(name = "myanimelist")]
+    MyAnimeList {
+        #[command(subcommand)]
+        command: MyAnimeListCommand,
+    },
+
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
+    /// open a manga by its mangadex url or id, for example `https://mangadex.org/title/<id>`, or a chapter of it in the
+    /// reader with `mangadex:<manga-id>:<chapter-id>` or `manga-tui://mangadex/<manga-id>/<chapter-id>`
+    Open { url: String },
+
+    /// push a notification for each new chapter of the mangas in your library to the service set in `[notifications]`,
+    /// meant to be run periodically for example with cron
+    CheckChapters,
+
+    /// maintenance of the database where the reading history is stored, its size and schema version are always reported
+    Db {
+        /// rebuild the database file to give back the space of the rows deleted
+        #[arg(long)]
+        vacuum: bool,
+        /// check the database is not corrupted
+        #[arg(long)]
+        integrity_check: bool,
+        /// remove the chapters whose manga no longer exists
+        #[arg(long)]
+        prune_orphans: bool,
+        /// apply the migrations which are pending, this is also done every time manga-tui starts
+        #[arg(long)]
+        migrate: bool,
+        /// undo the migrations after this number, needed to go back to an older version of manga-tui, the first one
+        /// can't be undone
+        #[arg(long, value_name = "NUMBER", conflicts_with = "migrate")]
+        rollback_to: Option<u32>,
+        /// only show the queries `--migrate` or `--rollback-to` would run and how many chapters `--prune-orphans` would
+        /// remove, nothing is changed
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    Import {
+        #[command(subcommand)]
+        command: ImportCommand,
+    },
+
+    Export {
+        #[command(subcommand)]
+        command: ExportCommand,
+    },
 }
 
 #[derive(Parser, Clone)]
@@ -53,6 +189,12 @@ pub struct CliArgs {
     pub command: Option<Commands>,
     #[arg(short, long)]
     pub data_dir: bool,
+    /// show the debug page (F12) with the last requests made to mangadex
+    #[arg(long)]
+    pub debug: bool,
+    /// log how long each step of the startup takes
+    #[arg(long)]
+    pub timings: bool,
 }
 
 pub struct AnilistCredentialsProvided<'a> {
@@ -60,11 +202,25 @@ pub struct AnilistCredentialsProvided<'a> {
     pub client_id: &'a str,
 }
 
+pub struct MyAnimeListCredentialsProvided<'a> {
+    pub access_token: &'a str,
+    pub client_id: &'a str,
+}
+
+pub struct MangadexCredentialsProvided<'a> {
+    pub client_id: &'a str,
+    pub client_secret: &'a str,
+    pub username: &'a str,
+    pub password: &'a str,
+}
+
 impl CliArgs {
     pub fn new() -> Self {
         Self {
             command: None,
             data_dir: false,
+            debug: false,
+            timings: false,
         }
     }
 
@@ -73,6 +229,14 @@ impl CliArgs {
         self
     }
 
+    /// The manga to show once the app starts, if it was run with `open`
+    pub fn manga_to_open(&self) -> Option<MangaLink> {
+        match &self.command {
+            Some(Commands::Open { url }) => MangaLink::parse(url),
+            _ => None,
+        }
+    }
+
     pub fn print_available_languages() {
         println!("The available languages are:");
         Languages::iter().filter(|lang| *lang != Languages::Unkown).for_each(|lang| {
@@ -80,6 +244,57 @@ impl CliArgs {
         });
     }
 
+    pub fn print_effective_config() -> Result<(), Box<dyn Error>> {
+        let data_dir = APP_DATA_DIR.as_ref().ok_or("data dir could not be found")?;
+        let config_file = data_dir.join(MangaTuiConfig::get_config_file_path());
+
+        let data_dir_source = if std::env::var("MANGA_TUI_DATA_DIR").is_ok() { "MANGA_TUI_DATA_DIR" } else { "default" };
+        println!("Config file: {} (data directory from: {data_dir_source})", config_file.display());
+
+        let raw_config = if exists!(&config_file) {
+            MangaTuiConfig::read_raw_config(data_dir)?
+        } else {
+            println!("The config file does not exist yet, it is created the next time manga-tui is run");
+            String::new()
+        };
+
+        let (values, parse_error) = MangaTuiConfig::effective_values(&raw_config)?;
+
+        if let Some(e) = parse_error.filter(|_| !raw_config.is_empty()) {
+            println!("The config file could not be parsed so the defaults are used instead, more details : {e}");
+        }
+
+        println!();
+        for value in values {
+            println!("{} = {} # {}", value.key, value.value, value.source);
+        }
+
+        Ok(())
+    }
+
+    fn export_config(file: &Path, logger: &impl ILogger) -> Result<(), Box<dyn Error>> {
+        let data_dir = APP_DATA_DIR.as_ref().ok_or("data dir could not be found")?;
+
+        MangaTuiConfig::export_config(data_dir, file)?;
+
+        logger.inform(format!("Your config was exported to {}", file.display()));
+
+        Ok(())
+    }
+
+    fn import_config(file: &Path, logger: &impl ILogger) -> Result<(), Box<dyn Error>> {
+        let data_dir = APP_DATA_DIR.as_ref().ok_or("data dir could not be found")?;
+
+        let backup = MangaTuiConfig::import_config(&std::fs::read_to_string(file)?, data_dir)?;
+
+        logger.inform(format!("The config of {} is now being used", file.display()));
+        if let Some(backup) = backup {
+            logger.inform(format!("The previous config was kept at {}", backup.display()));
+        }
+
+        Ok(())
+    }
+
     pub fn init_anilist(
         &self,
         mut input_reader: impl BufRead,
@@ -159,6 +374,358 @@ impl CliArgs {
         Ok(())
     }
 
+    pub async fn init_myanimelist(
+        &self,
+        mut input_reader: impl BufRead,
+        storage: &mut impl SecretStorage,
+        logger: impl ILogger,
+    ) -> Result<(), Box<dyn Error>> {
+        let client_id = read_input(&mut input_reader, &logger, "Provide your client id")?;
+        let client_id = client_id.trim();
+
+        let code_verifier = CodeVerifier::generate();
+        let authorize_url = code_verifier.authorize_url(client_id);
+
+        logger.inform(format!("Opening {authorize_url} to authorize manga-tui"));
+
+        open::that(authorize_url)?;
+
+        let code =
+            read_input(&mut input_reader, &logger, "Enter the code, it is the `code` parameter of the url you were redirected to")?;
+
+        let myanimelist = MyAnimeList::new(BASE_MYANIMELIST_API_URL.parse().unwrap()).with_client_id(client_id.to_string());
+
+        let access_token = myanimelist
+            .request_access_token(MYANIMELIST_TOKEN_URL.parse().unwrap(), code.trim(), &code_verifier)
+            .await?;
+
+        self.save_myanimelist_credentials(
+            MyAnimeListCredentialsProvided {
+                access_token: &access_token,
+                client_id,
+            },
+            storage,
+        )?;
+
+        logger.inform(
+            "Myanimelist was correctly setup, set `tracker = \"myanimelist\"` in the config file to sync your reading progress :D",
+        );
+
+        Ok(())
+    }
+
+    fn save_myanimelist_credentials(
+        &self,
+        credentials: MyAnimeListCredentialsProvided<'_>,
+        storage: &mut impl SecretStorage,
+    ) -> Result<(), Box<dyn Error>> {
+        storage.save_multiple_secrets(HashMap::from([
+            (MyAnimeListCredentials::AccessToken.to_string(), credentials.access_token.to_string()),
+            (MyAnimeListCredentials::ClientId.to_string(), credentials.client_id.to_string()),
+        ]))?;
+        Ok(())
+    }
+
+    async fn check_myanimelist_status(&self, logger: &impl ILogger) -> Result<(), Box<dyn Error>> {
+        let storage = MyAnimeListStorage::new();
+        logger.inform("Checking client id and access token are stored");
+
+        let Some(credentials) = storage.check_credentials_stored()? else {
+            logger.warn("The client id or the access token are empty, run `manga-tui myanimelist init`");
+            exit(0)
+        };
+
+        logger.inform("Checking your access token is valid, this may take a while");
+
+        let myanimelist = MyAnimeList::new(BASE_MYANIMELIST_API_URL.parse().unwrap())
+            .with_token(credentials.access_token)
+            .with_client_id(credentials.client_id);
+
+        if myanimelist.check_credentials_are_valid().await? {
+            logger.inform("Everything is setup correctly :D");
+        } else {
+            logger.error("The myanimelist access token is not valid or expired, please run `manga-tui myanimelist init`".into());
+            exit(0)
+        }
+
+        Ok(())
+    }
+
+    pub fn init_mangadex(
+        &self,
+        mut input_reader: impl BufRead,
+        storage: &mut impl SecretStorage,
+        logger: impl ILogger,
+    ) -> Result<(), Box<dyn Error>> {
+        logger.inform("Create a personal api client in https://mangadex.org/settings under the `API Clients` section");
+
+        let client_id = read_input(&mut input_reader, &logger, "Provide your client id")?;
+        let client_secret = read_input(&mut input_reader, &logger, "Provide your client secret")?;
+        let username = read_input(&mut input_reader, &logger, "Provide your mangadex username")?;
+        let password = read_input(&mut input_reader, &logger, "Provide your mangadex password")?;
+
+        self.save_mangadex_credentials(
+            MangadexCredentialsProvided {
+                client_id: client_id.trim(),
+                client_secret: client_secret.trim(),
+                username: username.trim(),
+                password: password.trim(),
+            },
+            storage,
+        )?;
+
+        logger.inform("Mangadex account was stored, run `manga-tui mangadex check` to verify you can log in");
+
+        Ok(())
+    }
+
+    fn save_mangadex_credentials(
+        &self,
+        credentials: MangadexCredentialsProvided<'_>,
+        storage: &mut impl SecretStorage,
+    ) -> Result<(), Box<dyn Error>> {
+        storage.save_multiple_secrets(HashMap::from([
+            (MangadexCredentials::ClientId.to_string(), credentials.client_id.to_string()),
+            (MangadexCredentials::ClientSecret.to_string(), credentials.client_secret.to_string()),
+            (MangadexCredentials::Username.to_string(), credentials.username.to_string()),
+            (MangadexCredentials::Password.to_string(), credentials.password.to_string()),
+        ]))?;
+        Ok(())
+    }
+
+    async fn check_mangadex_status(&self, logger: &impl ILogger) -> Result<(), Box<dyn Error>> {
+        let storage = MangadexStorage::new();
+        logger.inform("Checking mangadex account is stored");
+
+        let Some(account) = storage.check_credentials_stored()? else {
+            logger.warn("Some of the mangadex credentials are empty, run `manga-tui mangadex init`");
+            exit(0)
+        };
+
+        logger.inform("Logging in to mangadex, this may take a while");
+
+        let mangadex_client =
+            MangadexClient::new(API_URL_BASE.parse().unwrap(), COVER_IMG_URL_BASE.parse().unwrap()).with_account(account);
+
+        mangadex_client.login().await?;
+
+        logger.inform("Everything is setup correctly :D");
+
+        Ok(())
+    }
+
+    fn import_tachiyomi_backup(file: &Path, logger: &impl ILogger) -> Result<(), Box<dyn Error>> {
+        build_data_dir(logger)?;
+
+        let backup = Backup::read(file)?;
+
+        let connection = Database::get_connection()?;
+        Database::new(&connection).setup()?;
+
+        let summary = import_backup(&backup, &connection, *Languages::get_preferred_lang())?;
+
+        Self::print_import_summary(&summary, logger);
+
+        Ok(())
+    }
+
+    async fn import_myanimelist_list(file: &Path, logger: &impl ILogger) -> Result<(), Box<dyn Error>> {
+        build_data_dir(logger)?;
+
+        let entries = parse_list(&std::fs::read_to_string(file)?);
+
+        let mangadex_urls = MangaTuiConfig::get().mangadex_urls()?;
+        let api_client = MangadexClient::new(mangadex_urls.api, mangadex_urls.cover);
+
+        let connection = Database::get_connection()?;
+        Database::new(&connection).setup()?;
+
+        logger.inform(format!("Searching {} mangas on mangadex", entries.len()));
+
+        let summary = import_list(&entries, &api_client, &connection, logger).await?;
+
+        Self::print_import_summary(&summary, logger);
+
+        Ok(())
+    }
+
+    async fn import_media_server(
+        series: impl Future<Output = Result<Vec<ServerSeries>, Box<dyn Error>>>,
+        logger: &impl ILogger,
+    ) -> Result<(), Box<dyn Error>> {
+        build_data_dir(logger)?;
+
+        let entries: Vec<ListEntry> = series.await?.into_iter().map(ListEntry::from).collect();
+
+        let mangadex_urls = MangaTuiConfig::get().mangadex_urls()?;
+        let api_client = MangadexClient::new(mangadex_urls.api, mangadex_urls.cover);
+
+        let connection = Database::get_connection()?;
+        Database::new(&connection).setup()?;
+
+        logger.inform(format!("Searching {} mangas on mangadex", entries.len()));
+
+        let summary = import_list(&entries, &api_client, &connection, logger).await?;
+
+        Self::print_import_summary(&summary, logger);
+
+        Ok(())
+    }
+
+    fn export_myanimelist_list(file: &Path, logger: &impl ILogger) -> Result<(), Box<dyn Error>> {
+        build_data_dir(logger)?;
+
+        let connection = Database::get_connection()?;
+        let database = Database::new(&connection);
+        database.setup()?;
+
+        std::fs::write(file, export_list(&database)?)?;
+
+        logger.inform(format!("Your list was exported to {}, import it on https://myanimelist.net/import.php", file.display()));
+
+        Ok(())
+    }
+
+    fn print_import_summary(summary: &ImportSummary, logger: &impl ILogger) {
+        logger.inform(format!(
+            "Imported {} mangas to the reading history and {} to plan to read",
+            summary.reading_history, summary.plan_to_read
+        ));
+
+        if !summary.skipped.is_empty() {
+            logger.warn(format!("{} mangas could not be imported: {}", summary.skipped.len(), summary.skipped.join(", ")));
+        }
+    }
+
+    fn print_migrations(migrations: &[&NumberedMigration], undo: bool, logger: &impl ILogger) {
+        if migrations.is_empty() {
+            logger.inform("There are no migrations to run");
+        }
+
+        for migration in migrations {
+            logger.inform(format!("{} {} : {}", if undo { "Undo" } else { "Apply" }, migration.number, migration.name));
+            for query in if undo { migration.down } else { migration.up } {
+                println!("    {query}");
+            }
+        }
+    }
+
+    fn migrate_database(
+        connection: &Connection,
+        migrate: bool,
+        rollback_to: Option<u32>,
+        dry_run: bool,
+        logger: &impl ILogger,
+    ) -> Result<(), Box<dyn Error>> {
+        match (rollback_to, dry_run) {
+            (Some(number), true) => Self::print_migrations(&migrations_to_roll_back(connection, number)?, true, logger),
+            (Some(number), false) => {
+                let rolled_back = migrate_down(connection, number, logger)?;
+                logger.inform(format!("{} migrations were undone", rolled_back.len()));
+            },
+            (None, true) => Self::print_migrations(&pending_migrations(connection)?, false, logger),
+            (None, false) if migrate => {
+                let applied = migrate_up(connection, logger)?;
+                logger.inform(format!("{} migrations were applied", applied.len()));
+            },
+            (None, false) => {},
+        }
+
+        logger.inform(format!("The database schema is at migration {}", current_schema_version(connection)?));
+
+        Ok(())
+    }
+
+    fn prune_orphans(database: &Database<'_>, dry_run: bool, logger: &impl ILogger) -> rusqlite::Result<()> {
+        if dry_run {
+            let orphans = database.count_orphan_chapters()?;
+            logger.inform(format!("{orphans} chapters whose manga no longer exists would be removed"));
+        } else {
+            let removed = database.prune_orphan_chapters()?;
+            logger.inform(format!("Removed {removed} chapters whose manga no longer exists"));
+        }
+
+        Ok(())
+    }
+
+    fn maintain_database(
+        vacuum: bool,
+        integrity_check: bool,
+        prune_orphans: bool,
+        migrate: bool,
+        rollback_to: Option<u32>,
+        dry_run: bool,
+        logger: &impl ILogger,
+    ) -> Result<(), Box<dyn Error>> {
+        build_data_dir(logger)?;
+
+        let connection = Database::get_connection()?;
+        Database::new(&connection).create_base_tables()?;
+
+        Self::migrate_database(&connection, migrate, rollback_to, dry_run, logger)?;
+
+        let database = Database::new(&connection);
+        let size_before = database.size()?;
+
+        if prune_orphans {
+            Self::prune_orphans(&database, dry_run, logger)?;
+        }
+
+        if vacuum {
+            database.vacuum()?;
+            logger.inform("The database was vacuumed");
+        }
+
+        if integrity_check {
+            let problems = database.integrity_check()?;
+            if !problems.is_empty() {
+                return Err(format!("the integrity check found these problems : \n {}", problems.join("\n")).into());
+            }
+            logger.inform("The integrity check found no problems");
+        }
+
+        let size_after = database.size()?;
+
+        if size_after == size_before {
+            logger.inform(format!("The database takes {}", format_size(size_after)));
+        } else {
+            logger.inform(format!("The database went from {} to {}", format_size(size_before), format_size(size_after)));
+        }
+
+        Ok(())
+    }
+
+    async fn notify_new_chapters(logger: &impl ILogger) -> Result<(), Box<dyn Error>> {
+        build_data_dir(logger)?;
+
+        let settings = MangaTuiConfig::get()
+            .notifications
+            .clone()
+            .ok_or("there is no `[notifications]` section in the config file, run `manga-tui config show` to find it")?;
+
+        let mangadex_urls = MangaTuiConfig::get().mangadex_urls()?;
+        let api_client = MangadexClient::new(mangadex_urls.api, mangadex_urls.cover);
+
+        let connection = Database::get_connection()?;
+        let database = Database::new(&connection);
+        database.setup()?;
+
+        logger.inform(format!("Checking for new chapters, notifications are pushed to {}", settings.service));
+
+        let notifier = ChapterNotifier::new(settings);
+
+        let notifications_pushed =
+            check_new_chapters(&api_client, &database, &notifier, *Languages::get_preferred_lang(), logger).await?;
+
+        logger.inform(format!("Found {notifications_pushed} new chapters"));
+
+        let new_mangas = check_new_mangas_of_followed_authors(&api_client, &database, &notifier, logger).await?;
+
+        logger.inform(format!("Found {new_mangas} new series by the authors you follow"));
+
+        Ok(())
+    }
+
     /// This method should only return `Ok(())` it the app should keep running, otherwise `exit`
     pub async fn proccess_args(self) -> Result<(), Box<dyn Error>> {
         if self.data_dir {
@@ -167,6 +734,10 @@ impl CliArgs {
             exit(0)
         }
 
+        if self.debug {
+            REQUEST_LOG.get_or_init(|| RequestLog::new(MAX_REQUESTS_RECORDED));
+        }
+
         match &self.command {
             Some(command) => match command {
                 Commands::Lang { print, set } => {
@@ -215,6 +786,181 @@ impl CliArgs {
                         }
                     },
                 },
+
+                Commands::Mangadex { command } => match command {
+                    MangadexCommand::Init => {
+                        let mut storage = MangadexStorage::new();
+                        self.init_mangadex(std::io::stdin().lock(), &mut storage, Logger)?;
+                        exit(0)
+                    },
+                    MangadexCommand::Check => {
+                        let logger = Logger;
+                        if let Err(e) = self.check_mangadex_status(&logger).await {
+                            logger.error(format!("Could not log in to mangadex, more details \n {}", e).into());
+                            write_to_error_log(e.into());
+                            exit(1);
+                        } else {
+                            exit(0)
+                        }
+                    },
+                },
+
+                Commands::MyAnimeList { command } => match command {
+                    MyAnimeListCommand::Init => {
+                        let logger = Logger;
+                        let mut storage = MyAnimeListStorage::new();
+                        if let Err(e) = self.init_myanimelist(std::io::stdin().lock(), &mut storage, Logger).await {
+                            logger.error(format!("Could not setup myanimelist, more details \n {}", e).into());
+                            write_to_error_log(e.into());
+                            exit(1);
+                        } else {
+                            exit(0)
+                        }
+                    },
+                    MyAnimeListCommand::Check => {
+                        let logger = Logger;
+                        if let Err(e) = self.check_myanimelist_status(&logger).await {
+                            logger.error(format!("Some error ocurred, more details \n {}", e).into());
+                            write_to_error_log(e.into());
+                            exit(1);
+                        } else {
+                            exit(0)
+                        }
+                    },
+                },
+
+                Commands::Config { command } => match command {
+                    ConfigCommand::Show => {
+                        Self::print_effective_config()?;
+                        exit(0)
+                    },
+                    ConfigCommand::Export { file } => {
+                        let logger = Logger;
+                        if let Err(e) = Self::export_config(file, &logger) {
+                            logger.error(format!("Could not export the config, more details \n {}", e).into());
+                            exit(1);
+                        } else {
+                            exit(0)
+                        }
+                    },
+                    ConfigCommand::Import { file } => {
+                        let logger = Logger;
+                        if let Err(e) = Self::import_config(file, &logger) {
+                            logger.error(format!("Could not import the config, more details \n {}", e).into());
+                            exit(1);
+                        } else {
+                            exit(0)
+                        }
+                    },
+                },
+
+                Commands::Db {
+                    vacuum,
+                    integrity_check,
+                    prune_orphans,
+                    migrate,
+                    rollback_to,
+                    dry_run,
+                } => {
+                    let logger = Logger;
+                    if let Err(e) = Self::maintain_database(
+                        *vacuum,
+                        *integrity_check,
+                        *prune_orphans,
+                        *migrate,
+                        *rollback_to,
+                        *dry_run,
+                        &logger,
+                    ) {
+                        logger.error(format!("Could not maintain the database, more details \n {}", e).into());
+                        write_to_error_log(e.into());
+                        exit(1);
+                    } else {
+                        exit(0)
+                    }
+                },
+
+                Commands::CheckChapters => {
+                    let logger = Logger;
+                    if let Err(e) = Self::notify_new_chapters(&logger).await {
+                        logger.error(format!("Could not check for new chapters, more details \n {}", e).into());
+                        write_to_error_log(e.into());
+                        exit(1);
+                    } else {
+                        exit(0)
+                    }
+                },
+
+                Commands::Import { command } => match command {
+                    ImportCommand::Tachiyomi { file } => {
+                        let logger = Logger;
+                        if let Err(e) = Self::import_tachiyomi_backup(file, &logger) {
+                            logger.error(format!("Could not import the backup, more details \n {}", e).into());
+                            write_to_error_log(e.into());
+                            exit(1);
+                        } else {
+                            exit(0)
+                        }
+                    },
+                    ImportCommand::MyAnimeList { file } => {
+                        let logger = Logger;
+                        if let Err(e) = Self::import_myanimelist_list(file, &logger).await {
+                            logger.error(format!("Could not import the list, more details \n {}", e).into());
+                            write_to_error_log(e.into());
+                            exit(1);
+                        } else {
+                            exit(0)
+                        }
+                    },
+                    ImportCommand::Komga { url, api_key } => {
+                        let logger = Logger;
+                        let client = MediaServerClient::new(url.clone(), api_key.clone());
+                        if let Err(e) = Self::import_media_server(client.get_komga_series(), &logger).await {
+                            logger.error(format!("Could not import the library, more details \n {}", e).into());
+                            write_to_error_log(e.into());
+                            exit(1);
+                        } else {
+                            exit(0)
+                        }
+                    },
+                    ImportCommand::Kavita { url, api_key } => {
+                        let logger = Logger;
+                        let client = MediaServerClient::new(url.clone(), api_key.clone());
+                        if let Err(e) = Self::import_media_server(client.get_kavita_series(), &logger).await {
+                            logger.error(format!("Could not import the library, more details \n {}", e).into());
+                            write_to_error_log(e.into());
+                            exit(1);
+                        } else {
+                            exit(0)
+                        }
+                    },
+                },
+
+                Commands::Export { command } => match command {
+                    ExportCommand::MyAnimeList { file } => {
+                        let logger = Logger;
+                        if let Err(e) = Self::export_myanimelist_list(file, &logger) {
+                            logger.error(format!("Could not export the list, more details \n {}", e).into());
+                            write_to_error_log(e.into());
+                            exit(1);
+                        } else {
+                            exit(0)
+                        }
+                    },
+                },
+
+                Commands::Open { url } => {
+                    if MangaLink::parse(url).is_none() {
+                        println!(
+                            "`{url}` is not a mangadex url or id, it should look like `https://mangadex.org/title/<id>` or \
+                             `mangadex:<manga-id>[:<chapter-id>]`"
+                        );
+                        exit(1)
+                    }
+
+                    PREFERRED_LANGUAGE.set(Languages::default()).unwrap();
+                    Ok(())
+                },
             },
             None => {
                 PREFERRED_LANGUAGE.set(Languages::default()).unwrap();
@@ -237,6 +983,7 @@ mod tests {
     use uuid::Uuid;
 
     use super::*;
+    use crate::logger::DefaultLogger;
 
     #[derive(Default, Clone)]
     struct MockStorage {
@@ -261,6 +1008,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn orphan_chapters_are_only_counted_with_dry_run() -> Result<(), Box<dyn Error>> {
+        let connection = Connection::open_in_memory()?;
+        let database = Database::new(&connection);
+        database.setup()?;
+
+        connection.execute_batch("PRAGMA foreign_keys = OFF")?;
+        connection.execute("INSERT INTO chapters(id, title, manga_id) VALUES ('orphan', 'orphan', 'deleted_manga')", [])?;
+        connection.execute_batch("PRAGMA foreign_keys = ON")?;
+
+        CliArgs::prune_orphans(&database, true, &DefaultLogger)?;
+
+        assert_eq!(1, database.count_orphan_chapters()?);
+
+        CliArgs::prune_orphans(&database, false, &DefaultLogger)?;
+
+        assert_eq!(0, database.count_orphan_chapters()?);
+
+        Ok(())
+    }
+
     #[test]
     fn it_saves_anilist_access_token_and_user_id() {
         let cli = CliArgs::new();
@@ -289,6 +1057,41 @@ mod tests {
         assert_eq!(user_id.parse::<u32>().unwrap(), value.parse::<u32>().unwrap());
     }
 
+    #[test]
+    fn it_saves_myanimelist_access_token_and_client_id() {
+        let cli = CliArgs::new();
+        let access_token = Uuid::new_v4().to_string();
+
+        let mut storage = MockStorage::default();
+
+        cli.save_myanimelist_credentials(
+            MyAnimeListCredentialsProvided {
+                access_token: &access_token,
+                client_id: "some_client_id",
+            },
+            &mut storage,
+        )
+        .expect("should not fail");
+
+        assert_eq!(&access_token, storage.secrets_stored.get("myanimelist_access_token").unwrap());
+        assert_eq!("some_client_id", storage.secrets_stored.get("myanimelist_client_id").unwrap());
+    }
+
+    #[test]
+    fn it_saves_mangadex_account() {
+        let cli = CliArgs::new();
+        let input = "client_id\nclient_secret\nusername\npassword\n".as_bytes();
+
+        let mut storage = MockStorage::default();
+
+        cli.init_mangadex(input, &mut storage, Logger).expect("should not fail");
+
+        assert_eq!("client_id", storage.secrets_stored.get("mangadex_client_id").unwrap());
+        assert_eq!("client_secret", storage.secrets_stored.get("mangadex_client_secret").unwrap());
+        assert_eq!("username", storage.secrets_stored.get("mangadex_username").unwrap());
+        assert_eq!("password", storage.secrets_stored.get("mangadex_password").unwrap());
+    }
+
     #[derive(Debug)]
     struct AnilistCheckerTest {
         should_fail: bool,
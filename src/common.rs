@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 
 use ratatui::layout::Rect;
@@ -22,6 +22,7 @@ pub struct Artist {
 pub struct Manga {
     pub id: String,
     pub title: String,
+    pub alt_titles: Vec<String>,
     pub description: String,
     pub content_rating: String,
     pub publication_demographic: String,
@@ -32,6 +33,15 @@ pub struct Manga {
     pub artist: Artist,
     pub available_languages: Vec<Languages>,
     pub created_at: String,
+    /// Sequels, prequels, spin-offs and so on
+    pub related: Vec<RelatedManga>,
+}
+
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct RelatedManga {
+    pub id: String,
+    /// How it is related, for example "sequel" or "side_story"
+    pub relation: String,
 }
 
 #[derive(Display, Clone, Copy, EnumIter, Default, Debug, Eq, PartialEq)]
@@ -63,6 +73,8 @@ impl PageType {
 pub struct ImageState {
     /// save the image loaded for a manga, it will be retrieved by it's id
     image_state: HashMap<String, Box<dyn Protocol>>,
+    /// Mangas without cover or whose cover could not be loaded, a placeholder is shown for them instead
+    failed: HashSet<String>,
     img_area: Rect,
 }
 
@@ -88,6 +100,14 @@ impl ImageState {
     pub fn is_empty(&self) -> bool {
         self.image_state.is_empty()
     }
+
+    pub fn set_failed(&mut self, id_manga: String) {
+        self.failed.insert(id_manga);
+    }
+
+    pub fn has_failed(&self, id: &str) -> bool {
+        self.failed.contains(id)
+    }
 }
 
 pub fn format_error_message_tracking_reading_history<A: Display, B: Display, C: Display>(
@@ -1,19 +1,22 @@
 use std::error::Error;
 use std::fs::{File, OpenOptions};
-use std::io::{Read, Write};
+use std::io::{Cursor, Read, Write};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
+use chrono::{Datelike, Days, NaiveDate};
 use manga_tui::exists;
 use once_cell::sync::OnceCell;
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIter};
 use toml::Table;
 
+use crate::backend::fetch::{API_URL_BASE, COVER_IMG_URL_BASE, STAFF_PICKS_LIST_ID};
 use crate::backend::AppDirectories;
 use crate::logger::ILogger;
 
-#[derive(Default, Debug, Serialize, Deserialize, Display, EnumIter, Clone, Copy)]
+#[derive(Default, Debug, Serialize, Deserialize, Display, EnumIter, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum DownloadType {
     #[default]
@@ -39,6 +42,78 @@ impl ImageQuality {
     }
 }
 
+#[derive(Default, Debug, Serialize, Deserialize, Display, EnumIter, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackerKind {
+    #[default]
+    Anilist,
+    MyAnimeList,
+    /// Reading progress is appended to a file in the data directory
+    #[serde(rename = "json_journal")]
+    JsonJournal,
+    #[serde(rename = "markdown_journal")]
+    MarkdownJournal,
+}
+
+/// Either `tracker = "anilist"` or `tracker = ["anilist", "myanimelist"]` to sync reading progress with several trackers
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum TrackerSetting {
+    One(TrackerKind),
+    Many(Vec<TrackerKind>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationService {
+    Ntfy,
+    Gotify,
+}
+
+/// Where a notification is pushed to when a manga in the library gets a new chapter
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct NotificationSettings {
+    pub service: NotificationService,
+    /// The url of the topic for ntfy, or the url of the server for gotify
+    pub url: String,
+    /// The application token for gotify, or an access token for ntfy topics which are protected
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum GoalPeriod {
+    Week,
+    Month,
+}
+
+/// How many chapters to read each week or month, the progress is shown in the feed page
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ReadingGoal {
+    pub chapters: u32,
+    pub period: GoalPeriod,
+}
+
+impl ReadingGoal {
+    /// The day the current period started, weeks start on monday
+    pub fn period_start(&self, today: NaiveDate) -> NaiveDate {
+        match self.period {
+            GoalPeriod::Week => today - Days::new(today.weekday().num_days_from_monday() as u64),
+            GoalPeriod::Month => today.with_day(1).unwrap_or(today),
+        }
+    }
+
+    /// From 0.0 to 1.0, reading more chapters than the goal does not go past 1.0
+    pub fn progress(&self, chapters_read: u32) -> f64 {
+        if self.chapters == 0 {
+            return 1.0;
+        }
+        (chapters_read as f64 / self.chapters as f64).min(1.0)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MangaTuiConfig {
     pub download_type: DownloadType,
@@ -46,6 +121,51 @@ pub struct MangaTuiConfig {
     pub auto_bookmark: bool,
     pub amount_pages: u8,
     pub track_reading_when_download: bool,
+    #[serde(default)]
+    pub low_memory_mode: bool,
+    #[serde(default)]
+    pub track_reading_when_marking_read: bool,
+    #[serde(default = "warn_skipped_chapters_default")]
+    pub warn_skipped_chapters: bool,
+    #[serde(default = "animations_default")]
+    pub animations: bool,
+    /// The urls below are optional, they are only set to use a mirror of mangadex
+    #[serde(default)]
+    pub mangadex_api_url: Option<String>,
+    #[serde(default)]
+    pub mangadex_cover_url: Option<String>,
+    #[serde(default)]
+    pub mangadex_images_url: Option<String>,
+    /// Ids of the mangadex lists shown in the home page, by default the staff picks
+    #[serde(default)]
+    pub mangadex_home_lists: Option<Vec<String>>,
+    /// strftime format of the dates shown, by default they are shown relative to today like "3 days ago"
+    #[serde(default)]
+    pub date_format: Option<String>,
+    /// Which services reading progress is synced with, by default anilist
+    #[serde(default)]
+    pub tracker: Option<TrackerSetting>,
+    /// Where new chapters are pushed to when running `manga-tui check-chapters`
+    #[serde(default)]
+    pub notifications: Option<NotificationSettings>,
+    /// Chapters to read each week or month, shown as a gauge in the feed page
+    #[serde(default)]
+    pub reading_goal: Option<ReadingGoal>,
+    /// The mangas of the reading history not read in this many months are moved to the trash on startup, once the user
+    /// confirms it
+    #[serde(default)]
+    pub prune_history_after_months: Option<u32>,
+    /// Folder where cbz files downloaded outside of manga-tui are picked up from
+    #[serde(default)]
+    pub watch_folder: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MangadexUrls {
+    pub api: Url,
+    pub cover: Url,
+    /// `None` means using the host mangadex assigns for each chapter
+    pub images: Option<Url>,
 }
 
 impl Default for MangaTuiConfig {
@@ -56,10 +176,65 @@ impl Default for MangaTuiConfig {
             download_type: DownloadType::default(),
             image_quality: ImageQuality::default(),
             track_reading_when_download: false,
+            low_memory_mode: false,
+            track_reading_when_marking_read: false,
+            warn_skipped_chapters: true,
+            animations: true,
+            mangadex_api_url: None,
+            mangadex_cover_url: None,
+            mangadex_images_url: None,
+            mangadex_home_lists: None,
+            date_format: None,
+            tracker: None,
+            notifications: None,
+            reading_goal: None,
+            prune_history_after_months: None,
+            watch_folder: None,
         }
     }
 }
 
+fn warn_skipped_chapters_default() -> bool {
+    true
+}
+
+fn animations_default() -> bool {
+    true
+}
+
+/// Where the value of a setting comes from when running `manga-tui config show`
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    #[strum(to_string = "default")]
+    Default,
+    #[strum(to_string = "config file")]
+    ConfigFile,
+    /// The key is in the config file but it is not a setting, so it does nothing
+    #[strum(to_string = "ignored, not a known setting")]
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveConfigValue {
+    pub key: String,
+    pub value: String,
+    pub source: ConfigSource,
+}
+
+/// Settings which are left out of the config when they are not set
+static OPTIONAL_FIELDS: [&str; 10] = [
+    "mangadex_api_url",
+    "mangadex_cover_url",
+    "mangadex_images_url",
+    "mangadex_home_lists",
+    "date_format",
+    "tracker",
+    "notifications",
+    "reading_goal",
+    "prune_history_after_months",
+    "watch_folder",
+];
+
 pub static CONFIG: OnceCell<MangaTuiConfig> = OnceCell::new();
 
 static CONFIG_TEMPLATE: &str = include_str!("../manga-tui-config.toml");
@@ -69,6 +244,37 @@ impl MangaTuiConfig {
         CONFIG.get_or_init(MangaTuiConfig::default)
     }
 
+    /// The urls set in the config take precedence over mangadex's ones
+    pub fn mangadex_urls(&self) -> Result<MangadexUrls, Box<dyn Error>> {
+        let parse = |url: &str| url.parse::<Url>().map_err(|e| format!("`{url}` is not a valid url: {e}"));
+
+        Ok(MangadexUrls {
+            api: parse(self.mangadex_api_url.as_deref().unwrap_or(API_URL_BASE))?,
+            cover: parse(self.mangadex_cover_url.as_deref().unwrap_or(COVER_IMG_URL_BASE))?,
+            images: self.mangadex_images_url.as_deref().map(parse).transpose()?,
+        })
+    }
+
+    /// Without duplicates, in the order they were set
+    pub fn trackers(&self) -> Vec<TrackerKind> {
+        let kinds = match self.tracker.clone() {
+            Some(TrackerSetting::One(kind)) => vec![kind],
+            Some(TrackerSetting::Many(kinds)) => kinds,
+            None => vec![TrackerKind::default()],
+        };
+
+        kinds.into_iter().fold(Vec::new(), |mut trackers, kind| {
+            if !trackers.contains(&kind) {
+                trackers.push(kind);
+            }
+            trackers
+        })
+    }
+
+    pub fn home_lists(&self) -> Vec<String> {
+        self.mangadex_home_lists.clone().unwrap_or_else(|| vec![STAFF_PICKS_LIST_ID.to_string()])
+    }
+
     pub fn read_raw_config(base_directory: &Path) -> Result<String, std::io::Error> {
         let mut config_file = Self::get_config_file(base_directory)?;
 
@@ -100,6 +306,56 @@ impl MangaTuiConfig {
         Ok(())
     }
 
+    /// Every setting with the value that is used and where it comes from, if the config file is not valid the
+    /// defaults are used for everything and the parsing error is returned alongside
+    pub fn effective_values(raw_config: &str) -> Result<(Vec<EffectiveConfigValue>, Option<String>), Box<dyn Error>> {
+        let in_file: Table = toml::from_str(raw_config).unwrap_or_default();
+
+        let (config, parse_error) = match toml::from_str::<Self>(raw_config) {
+            Ok(config) => (config, None),
+            Err(e) => (Self::default(), Some(e.message().to_string())),
+        };
+
+        let effective = Table::try_from(&config)?;
+
+        let mut values: Vec<EffectiveConfigValue> = effective
+            .iter()
+            .map(|(key, value)| EffectiveConfigValue {
+                key: key.clone(),
+                value: value.to_string(),
+                source: if parse_error.is_none() && in_file.contains_key(key) {
+                    ConfigSource::ConfigFile
+                } else {
+                    ConfigSource::Default
+                },
+            })
+            .collect();
+
+        values.extend(
+            OPTIONAL_FIELDS
+                .iter()
+                .filter(|key| !effective.contains_key(**key))
+                .map(|key| EffectiveConfigValue {
+                    key: key.to_string(),
+                    value: "not set".to_string(),
+                    source: ConfigSource::Default,
+                }),
+        );
+
+        values.extend(
+            in_file
+                .iter()
+                .filter(|(key, _)| !effective.contains_key(*key) && !OPTIONAL_FIELDS.contains(&key.as_str()))
+                .map(|(key, value)| EffectiveConfigValue {
+                    key: key.clone(),
+                    value: value.to_string(),
+                    source: ConfigSource::Unknown,
+                }),
+        );
+
+        Ok((values, parse_error))
+    }
+
     // refactor this function to make it more dynamic, at the moment the fields are hardcoded
     fn add_missing_fields(mut file: impl Write + Read, existing_config: Table) -> Result<Self, std::io::Error> {
         if !existing_config.contains_key("amount_pages") {
@@ -138,6 +394,54 @@ track_reading_when_download = false
             )?;
         }
 
+        if !existing_config.contains_key("low_memory_mode") {
+            file.write_all(
+                "
+# Only keep the current page and the ones next to it in memory while reading, recommended for machines with little RAM
+# values : true, false
+# default : false
+low_memory_mode = false
+"
+                .as_bytes(),
+            )?;
+        }
+
+        if !existing_config.contains_key("track_reading_when_marking_read") {
+            file.write_all(
+                "
+# Whether or not marking chapters as read with \"mark read up to here\" counts as reading them on services like anilist
+# values : true, false
+# default : false
+track_reading_when_marking_read = false
+"
+                .as_bytes(),
+            )?;
+        }
+
+        if !existing_config.contains_key("warn_skipped_chapters") {
+            file.write_all(
+                "
+# Warn on the first page of a chapter when the chapters between it and the last one read are unread
+# values : true, false
+# default : true
+warn_skipped_chapters = true
+"
+                .as_bytes(),
+            )?;
+        }
+
+        if !existing_config.contains_key("animations") {
+            file.write_all(
+                "
+# Spin the loading indicators, turning it off keeps the screen still while loading which saves bandwidth on slow ssh sessions
+# values : true, false
+# default : true
+animations = true
+"
+                .as_bytes(),
+            )?;
+        }
+
         let mut contents = String::new();
 
         file.read_to_string(&mut contents)?;
@@ -162,6 +466,46 @@ track_reading_when_download = false
             .read(true)
             .open(base_directory.join(Self::get_config_file_path()))
     }
+
+    /// Copies the config file as it is, comments included, so it can be imported on another machine
+    pub fn export_config(base_directory: &Path, destination: &Path) -> Result<(), Box<dyn Error>> {
+        std::fs::write(destination, Self::read_raw_config(base_directory)?)?;
+        Ok(())
+    }
+
+    /// Replaces the config file with `contents` only if it is a valid config, the previous one is kept as
+    /// `manga-tui-config.toml.bak` and its path returned. A config from an older version is valid too, the fields it
+    /// lacks are added with their default value like on startup
+    pub fn import_config(contents: &str, base_directory: &Path) -> Result<Option<PathBuf>, Box<dyn Error>> {
+        let invalid = |e: toml::de::Error| format!("the config to import is not valid : {}", e.message());
+
+        let already_existing: Table = toml::Table::from_str(contents).map_err(invalid)?;
+
+        let mut completed = Cursor::new(contents.as_bytes().to_vec());
+        completed.set_position(contents.len() as u64);
+        Self::add_missing_fields(&mut completed, already_existing)?;
+
+        let contents = String::from_utf8(completed.into_inner())?;
+
+        toml::from_str::<Self>(&contents).map_err(invalid)?;
+
+        let config_file = base_directory.join(Self::get_config_file_path());
+
+        let backup = if exists!(&config_file) {
+            let backup = config_file.with_extension("toml.bak");
+            std::fs::rename(&config_file, &backup)?;
+            Some(backup)
+        } else {
+            if let Some(config_directory) = config_file.parent() {
+                std::fs::create_dir_all(config_directory)?;
+            }
+            None
+        };
+
+        std::fs::write(config_file, contents)?;
+
+        Ok(backup)
+    }
 }
 
 #[cfg(test)]
@@ -204,6 +548,26 @@ mod tests {
 # values : true, false
 # default : false
 track_reading_when_download = false
+
+# Only keep the current page and the ones next to it in memory while reading, recommended for machines with little RAM
+# values : true, false
+# default : false
+low_memory_mode = false
+
+# Whether or not marking chapters as read with "mark read up to here" counts as reading them on services like anilist
+# values : true, false
+# default : false
+track_reading_when_marking_read = false
+
+# Warn on the first page of a chapter when the chapters between it and the last one read are unread
+# values : true, false
+# default : true
+warn_skipped_chapters = true
+
+# Spin the loading indicators, turning it off keeps the screen still while loading which saves bandwidth on slow ssh sessions
+# values : true, false
+# default : true
+animations = true
                 "#;
 
         MangaTuiConfig::add_missing_fields(&mut test_file, current_contents.parse::<Table>()?)?;
@@ -217,6 +581,185 @@ track_reading_when_download = false
         Ok(())
     }
 
+    #[test]
+    fn mangadex_urls_set_in_config_override_the_default_ones() -> Result<(), Box<dyn Error>> {
+        let config = MangaTuiConfig::default();
+
+        assert_eq!(
+            MangadexUrls {
+                api: API_URL_BASE.parse()?,
+                cover: COVER_IMG_URL_BASE.parse()?,
+                images: None,
+            },
+            config.mangadex_urls()?
+        );
+
+        let config: MangaTuiConfig = toml::from_str(
+            r#"
+download_type = "cbz"
+image_quality = "low"
+amount_pages = 5
+auto_bookmark = true
+track_reading_when_download = false
+low_memory_mode = false
+mangadex_api_url = "https://mangadex-mirror.org/api"
+mangadex_images_url = "https://images.mangadex-mirror.org"
+            "#,
+        )?;
+
+        let urls = config.mangadex_urls()?;
+
+        assert_eq!("https://mangadex-mirror.org/api", urls.api.as_str());
+        assert_eq!(COVER_IMG_URL_BASE, urls.cover.as_str());
+        assert_eq!(Some("https://images.mangadex-mirror.org/".parse()?), urls.images);
+
+        let config = MangaTuiConfig {
+            mangadex_cover_url: Some("not a url".to_string()),
+            ..Default::default()
+        };
+
+        assert!(config.mangadex_urls().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_syncs_with_one_or_several_trackers() -> Result<(), Box<dyn Error>> {
+        assert_eq!(vec![TrackerKind::Anilist], MangaTuiConfig::default().trackers());
+
+        let with_trackers = |tracker: &str| -> Result<Vec<TrackerKind>, Box<dyn Error>> {
+            let tracker: Table = toml::from_str(&format!("tracker = {tracker}"))?;
+            Ok(MangaTuiConfig {
+                tracker: Some(tracker["tracker"].clone().try_into()?),
+                ..Default::default()
+            }
+            .trackers())
+        };
+
+        assert_eq!(vec![TrackerKind::MyAnimeList], with_trackers(r#""myanimelist""#)?);
+        assert_eq!(
+            vec![TrackerKind::Anilist, TrackerKind::MarkdownJournal],
+            with_trackers(r#"["anilist", "markdown_journal", "anilist"]"#)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_tracks_the_reading_goal_since_the_start_of_the_week_or_month() -> Result<(), Box<dyn Error>> {
+        let config: Table = toml::from_str("reading_goal = { chapters = 20, period = \"week\" }")?;
+        let goal: ReadingGoal = config["reading_goal"].clone().try_into()?;
+
+        // a thursday
+        let today = NaiveDate::from_ymd_opt(2024, 8, 15).unwrap();
+
+        assert_eq!(NaiveDate::from_ymd_opt(2024, 8, 12).unwrap(), goal.period_start(today));
+        assert_eq!(0.5, goal.progress(10));
+        assert_eq!(1.0, goal.progress(25));
+
+        let goal = ReadingGoal {
+            period: GoalPeriod::Month,
+            ..goal
+        };
+
+        assert_eq!(NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(), goal.period_start(today));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_shows_where_each_setting_comes_from() -> Result<(), Box<dyn Error>> {
+        let raw_config = r#"
+download_type = "epub"
+image_quality = "low"
+auto_bookmark = true
+amount_pages = 5
+track_reading_when_download = false
+low_memory_mode = false
+date_format = "%d/%m/%Y"
+dowload_type = "raw"
+"#;
+
+        let (values, parse_error) = MangaTuiConfig::effective_values(raw_config)?;
+        let find = |key: &str| values.iter().find(|value| value.key == key).cloned().unwrap();
+
+        assert!(parse_error.is_none());
+        assert_eq!("\"epub\"", find("download_type").value);
+        assert_eq!(ConfigSource::ConfigFile, find("download_type").source);
+        assert_eq!(ConfigSource::ConfigFile, find("date_format").source);
+        assert_eq!("not set", find("tracker").value);
+        assert_eq!(ConfigSource::Default, find("tracker").source);
+        assert_eq!(ConfigSource::Unknown, find("dowload_type").source);
+
+        let (values, parse_error) = MangaTuiConfig::effective_values("download_type = \"pdf\"")?;
+
+        assert!(parse_error.is_some());
+        assert!(values.iter().all(|value| value.source == ConfigSource::Default));
+
+        Ok(())
+    }
+
+    #[test]
+    fn configs_written_before_low_memory_mode_existed_can_be_read() -> Result<(), Box<dyn Error>> {
+        let raw_config = r#"
+download_type = "epub"
+image_quality = "low"
+auto_bookmark = true
+amount_pages = 5
+track_reading_when_download = false
+"#;
+
+        let config: MangaTuiConfig = toml::from_str(raw_config)?;
+
+        assert!(!config.low_memory_mode);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_imports_a_config_keeping_the_previous_one_as_backup() -> Result<(), Box<dyn Error>> {
+        let base_directory = Path::new("./test_results/config-import");
+        let config_file = base_directory.join(MangaTuiConfig::get_config_file_path());
+
+        std::fs::create_dir_all(config_file.parent().unwrap())?;
+        std::fs::write(&config_file, "amount_pages = 5")?;
+
+        assert!(MangaTuiConfig::import_config("amount_pages = \"ten\"", base_directory).is_err());
+        assert!(MangaTuiConfig::import_config("amount_pages = ", base_directory).is_err());
+        assert_eq!("amount_pages = 5", std::fs::read_to_string(&config_file)?);
+
+        let backup = MangaTuiConfig::import_config(MangaTuiConfig::get_config_template(), base_directory)?;
+
+        assert_eq!(Some(config_file.with_extension("toml.bak")), backup);
+        assert_eq!("amount_pages = 5", std::fs::read_to_string(backup.unwrap())?);
+
+        let exported = base_directory.join("exported.toml");
+        MangaTuiConfig::export_config(base_directory, &exported)?;
+
+        assert_eq!(MangaTuiConfig::get_config_template(), std::fs::read_to_string(exported)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_imports_a_config_from_an_older_version_adding_the_fields_it_lacks() -> Result<(), Box<dyn Error>> {
+        let base_directory = Path::new("./test_results/config-import-older");
+
+        let older_config = "download_type = \"epub\"\nimage_quality = \"low\"\namount_pages = 10\n";
+
+        MangaTuiConfig::import_config(older_config, base_directory)?;
+
+        let imported = MangaTuiConfig::read_raw_config(base_directory)?;
+        let config: MangaTuiConfig = toml::from_str(&imported)?;
+
+        assert!(imported.starts_with(older_config));
+        assert_eq!(DownloadType::Epub, config.download_type);
+        assert_eq!(10, config.amount_pages);
+        assert!(config.auto_bookmark);
+
+        Ok(())
+    }
+
     #[test]
     fn it_does_not_add_already_existing_keys() -> Result<(), Box<dyn Error>> {
         let current_contents = r#"
@@ -234,6 +777,26 @@ amount_pages = 5
 # values : true, false
 # default : false
 track_reading_when_download = false
+
+# Only keep the current page and the ones next to it in memory while reading, recommended for machines with little RAM
+# values : true, false
+# default : false
+low_memory_mode = false
+
+# Whether or not marking chapters as read with "mark read up to here" counts as reading them on services like anilist
+# values : true, false
+# default : false
+track_reading_when_marking_read = false
+
+# Warn on the first page of a chapter when the chapters between it and the last one read are unread
+# values : true, false
+# default : true
+warn_skipped_chapters = true
+
+# Spin the loading indicators, turning it off keeps the screen still while loading which saves bandwidth on slow ssh sessions
+# values : true, false
+# default : true
+animations = true
             "#;
 
         let mut test_file = Cursor::new(Vec::new());
@@ -255,6 +818,26 @@ amount_pages = 5
 # values : true, false
 # default : false
 track_reading_when_download = false
+
+# Only keep the current page and the ones next to it in memory while reading, recommended for machines with little RAM
+# values : true, false
+# default : false
+low_memory_mode = false
+
+# Whether or not marking chapters as read with "mark read up to here" counts as reading them on services like anilist
+# values : true, false
+# default : false
+track_reading_when_marking_read = false
+
+# Warn on the first page of a chapter when the chapters between it and the last one read are unread
+# values : true, false
+# default : true
+warn_skipped_chapters = true
+
+# Spin the loading indicators, turning it off keeps the screen still while loading which saves bandwidth on slow ssh sessions
+# values : true, false
+# default : true
+animations = true
             "#;
 
         MangaTuiConfig::add_missing_fields(&mut test_file, current_contents.parse::<Table>()?)?;
@@ -63,6 +63,20 @@ pub mod test_utils {
     }
 
     impl MangaTracker for TrackerTest {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        async fn search_manga_entries(
+            &self,
+            _title: manga_tui::SearchTerm,
+        ) -> Result<Vec<crate::backend::tracker::TrackerEntry>, Box<dyn std::error::Error>> {
+            if self.should_fail {
+                return Err(self.error_message.clone().unwrap_or("".to_string()).into());
+            }
+            Ok(vec![])
+        }
+
         async fn search_manga_by_title(
             &self,
             _title: manga_tui::SearchTerm,
@@ -89,5 +103,19 @@ pub mod test_utils {
             }
             Ok(())
         }
+
+        async fn get_reading_progress(&self, _id: &str) -> Result<Option<u32>, Box<dyn Error>> {
+            if self.should_fail {
+                return Err(self.error_message.clone().unwrap_or("".to_string()).into());
+            }
+            Ok(None)
+        }
+
+        async fn update_list_entry(&self, _entry: crate::backend::tracker::UpdateEntryArgs<'_>) -> Result<(), Box<dyn Error>> {
+            if self.should_fail {
+                return Err(self.error_message.clone().unwrap_or("".to_string()).into());
+            }
+            Ok(())
+        }
     }
 }
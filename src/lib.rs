@@ -37,6 +37,39 @@ impl SearchTerm {
     pub fn get(&self) -> &str {
         &self.0
     }
+
+    /// A word starting with `#` filters by that tag, the rest of the words are searched as usual
+    pub fn with_tag_filter(search: &str) -> (Option<Self>, Option<TagName>) {
+        let (tags, words): (Vec<&str>, Vec<&str>) = search.split_whitespace().partition(|word| word.starts_with('#'));
+
+        (Self::trimmed_lowercased(&words.join(" ")), tags.into_iter().find_map(TagName::new))
+    }
+}
+
+/// The name of a tag the user put on a manga, lowercased and without spaces so it can be typed as `#tag` to filter
+/// mangas by it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagName(String);
+
+impl Display for TagName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.get())
+    }
+}
+
+impl TagName {
+    pub fn new(name: &str) -> Option<Self> {
+        let name = name.trim().trim_start_matches('#');
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(Self(name.split_whitespace().collect::<Vec<&str>>().join("-").to_lowercase()))
+    }
+
+    pub fn get(&self) -> &str {
+        &self.0
+    }
 }
 
 /// Remove special characteres that may cause errors when creating directories or files
@@ -48,9 +81,43 @@ fn remove_conflicting_characteres<T: AsRef<Path>>(title: T) -> PathBuf {
 
     let sanitized_title: String = title.chars().map(|c| if invalid_chars.contains(&c) { '_' } else { c }).collect();
 
+    // Only done on Windows, so the files downloaded on other systems keep being found by their name
+    #[cfg(windows)]
+    let sanitized_title = valid_on_windows(&sanitized_title);
+
     sanitized_title.into()
 }
 
+/// Windows does not allow control characters nor files named like devices, and it drops trailing dots and spaces so
+/// `title.` and `title` would end up being the same file
+#[cfg(any(windows, test))]
+fn valid_on_windows(name: &str) -> String {
+    let name: String = name.chars().map(|c| if c.is_control() { '_' } else { c }).collect();
+    let mut name = name.trim_end_matches(['.', ' ']).to_string();
+
+    if is_reserved_on_windows(&name) {
+        let end_of_stem = name.find('.').unwrap_or(name.len());
+        name.insert(end_of_stem, '_');
+    }
+
+    name
+}
+
+/// Windows does not allow files named like devices such as `CON` or `com1.txt`, no matter the extension
+#[cfg(any(windows, test))]
+fn is_reserved_on_windows(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or_default().trim_end().to_uppercase();
+
+    match stem.as_str() {
+        "CON" | "PRN" | "AUX" | "NUL" => true,
+        _ => {
+            (stem.starts_with("COM") || stem.starts_with("LPT"))
+                && stem.len() == 4
+                && stem.chars().last().is_some_and(|c| ('1'..='9').contains(&c))
+        },
+    }
+}
+
 /// This type ensures that a filename will not contain characteres that may throw errors
 /// like ":" or "/"
 #[derive(Debug, Default, PartialEq, Clone)]
@@ -105,6 +172,134 @@ impl<T: Debug> SortedVec<T> {
     }
 }
 
+/// Chapter numbers are reported in many formats like "71.1", "Ch. 5 extra" or "Oneshot", this type parses them into
+/// a number which can be compared, chapters without a number like oneshots are chapter `0`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChapterNumber(f64);
+
+impl ChapterNumber {
+    pub fn parse(raw: &str) -> Self {
+        let raw = raw.trim().replace(',', ".");
+
+        let number: String = raw
+            .chars()
+            .skip_while(|c| !c.is_ascii_digit())
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+
+        Self(number.trim_end_matches('.').parse().unwrap_or_default())
+    }
+
+    pub fn as_f64(self) -> f64 {
+        self.0
+    }
+
+    /// Trackers only accept whole chapters, so "10.5" counts as chapter 10
+    pub fn as_tracker_progress(self) -> u32 {
+        self.0 as u32
+    }
+}
+
+impl From<f64> for ChapterNumber {
+    fn from(value: f64) -> Self {
+        Self(value)
+    }
+}
+
+impl PartialEq for ChapterNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ChapterNumber {}
+
+impl PartialOrd for ChapterNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ChapterNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// A manga pasted in the search bar or passed to `manga-tui open`, either the url of its page, just its id or a
+/// deep link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MangaLink {
+    Mangadex(String),
+    /// The id of the manga and of the chapter to open in the reader
+    MangadexChapter(String, String),
+}
+
+impl MangaLink {
+    /// Accepts urls like `https://mangadex.org/title/<id>/<title>`, the id by itself or the deep links
+    /// `mangadex:<manga-id>[:<chapter-id>]` and `manga-tui://mangadex/<manga-id>[/<chapter-id>]`
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+
+        if let Some(link) = Self::parse_deep_link(input) {
+            return Some(link);
+        }
+
+        if is_uuid(input) {
+            return Some(Self::Mangadex(input.to_lowercase()));
+        }
+
+        let path = input
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_start_matches("www.");
+
+        let mut segments = path.strip_prefix("mangadex.org/")?.split('/');
+
+        match (segments.next(), segments.next()) {
+            (Some("title"), Some(id)) if is_uuid(id) => Some(Self::Mangadex(id.to_lowercase())),
+            _ => None,
+        }
+    }
+
+    fn parse_deep_link(input: &str) -> Option<Self> {
+        let ids: Vec<&str> = match input.strip_prefix("manga-tui://") {
+            Some(uri) => uri.trim_end_matches('/').strip_prefix("mangadex/")?.split('/').collect(),
+            None => input.strip_prefix("mangadex:")?.split(':').collect(),
+        };
+
+        match ids.as_slice() {
+            [manga_id] if is_uuid(manga_id) => Some(Self::Mangadex(manga_id.to_lowercase())),
+            [manga_id, chapter_id] if is_uuid(manga_id) && is_uuid(chapter_id) => {
+                Some(Self::MangadexChapter(manga_id.to_lowercase(), chapter_id.to_lowercase()))
+            },
+            _ => None,
+        }
+    }
+
+    /// The id of the manga
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Mangadex(id) | Self::MangadexChapter(id, _) => id,
+        }
+    }
+
+    pub fn chapter_id(&self) -> Option<&str> {
+        match self {
+            Self::Mangadex(_) => None,
+            Self::MangadexChapter(_, chapter_id) => Some(chapter_id),
+        }
+    }
+}
+
+/// Mangadex ids look like `a1c7c817-4e59-43b7-9365-09675a149a6f`
+fn is_uuid(input: &str) -> bool {
+    let groups: Vec<&str> = input.split('-').collect();
+
+    groups.iter().map(|group| group.len()).eq([8, 4, 4, 4, 12])
+        && groups.iter().all(|group| group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
 pub struct Log;
 
 impl Log {
@@ -121,6 +316,46 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn manga_link_is_parsed_from_mangadex_url_or_id() {
+        let id = "a1c7c817-4e59-43b7-9365-09675a149a6f";
+
+        assert_eq!(Some(MangaLink::Mangadex(id.to_string())), MangaLink::parse(id));
+        assert_eq!(
+            Some(MangaLink::Mangadex(id.to_string())),
+            MangaLink::parse(&format!(" https://mangadex.org/title/{id}/one-piece "))
+        );
+        assert_eq!(Some(MangaLink::Mangadex(id.to_string())), MangaLink::parse(&format!("mangadex.org/title/{id}")));
+
+        assert!(MangaLink::parse("one piece").is_none());
+        assert!(MangaLink::parse(&format!("https://mangadex.org/chapter/{id}")).is_none());
+        assert!(MangaLink::parse(&format!("https://example.com/title/{id}")).is_none());
+        assert!(MangaLink::parse("a1c7c817-4e59-43b7-9365-09675a149a6z").is_none());
+    }
+
+    #[test]
+    fn manga_link_is_parsed_from_deep_link() {
+        let manga_id = "a1c7c817-4e59-43b7-9365-09675a149a6f";
+        let chapter_id = "b2d8c817-4e59-43b7-9365-09675a149a6f";
+
+        assert_eq!(Some(MangaLink::Mangadex(manga_id.to_string())), MangaLink::parse(&format!("mangadex:{manga_id}")));
+        assert_eq!(
+            Some(MangaLink::MangadexChapter(manga_id.to_string(), chapter_id.to_string())),
+            MangaLink::parse(&format!("mangadex:{manga_id}:{chapter_id}"))
+        );
+        assert_eq!(Some(MangaLink::Mangadex(manga_id.to_string())), MangaLink::parse(&format!("manga-tui://mangadex/{manga_id}/")));
+        assert_eq!(
+            Some(MangaLink::MangadexChapter(manga_id.to_string(), chapter_id.to_string())),
+            MangaLink::parse(&format!("manga-tui://mangadex/{manga_id}/{chapter_id}"))
+        );
+
+        assert_eq!(Some(chapter_id), MangaLink::parse(&format!("mangadex:{manga_id}:{chapter_id}")).unwrap().chapter_id());
+
+        assert!(MangaLink::parse(&format!("anilist:{manga_id}")).is_none());
+        assert!(MangaLink::parse(&format!("mangadex:{manga_id}:not-a-chapter")).is_none());
+        assert!(MangaLink::parse(&format!("manga-tui://mangadex/{manga_id}/{chapter_id}/1")).is_none());
+    }
+
     #[test]
     fn none_when_search_term_is_empty() {
         let search_term = "";
@@ -130,6 +365,31 @@ mod test {
         assert!(search.is_none());
     }
 
+    #[test]
+    fn tag_names_are_lowercased_without_spaces() {
+        assert_eq!("to-reread", TagName::new(" #To Reread ").unwrap().get());
+        assert!(TagName::new("#").is_none());
+        assert!(TagName::new("   ").is_none());
+    }
+
+    #[test]
+    fn search_term_can_filter_by_tag() {
+        let (search, tag) = SearchTerm::with_tag_filter("Some #Comfy title");
+
+        assert_eq!("some title", search.unwrap().get());
+        assert_eq!("comfy", tag.unwrap().get());
+
+        let (search, tag) = SearchTerm::with_tag_filter("#comfy");
+
+        assert!(search.is_none());
+        assert_eq!("comfy", tag.unwrap().get());
+
+        let (search, tag) = SearchTerm::with_tag_filter("some title");
+
+        assert_eq!("some title", search.unwrap().get());
+        assert!(tag.is_none());
+    }
+
     #[test]
     fn search_term_is_lowercased() {
         let sample = "Some Example";
@@ -161,6 +421,24 @@ mod test {
         assert_eq!(Path::new("some _ name _ which contains"), file_name.as_path())
     }
 
+    #[test]
+    fn filename_is_valid_on_windows() {
+        assert_eq!("CON_", valid_on_windows("CON"));
+        assert_eq!("com1_.cbz", valid_on_windows("com1.cbz"));
+        assert_eq!("Lpt9_", valid_on_windows("Lpt9"));
+        assert_eq!("CONAN", valid_on_windows("CONAN"));
+        assert_eq!("COM0", valid_on_windows("COM0"));
+        assert_eq!("Is this a title", valid_on_windows("Is this a title..."));
+        assert_eq!("tab_separated", valid_on_windows("tab\tseparated"));
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn filename_keeps_what_only_windows_does_not_allow_on_other_systems() {
+        assert_eq!(Path::new("Is this a title..."), SanitizedFilename::new("Is this a title...").as_path());
+        assert_eq!(Path::new("CON"), SanitizedFilename::new("CON").as_path());
+    }
+
     #[test]
     fn sorted_vec_is_constructed_correctly() {
         let vec: Vec<u32> = [3, 10, 4].to_vec();
@@ -214,4 +492,26 @@ mod test {
 
         assert_eq!(expected, sorted_vec.as_slice())
     }
+
+    #[test]
+    fn chapter_number_is_parsed_from_different_formats() {
+        assert_eq!(71.1, ChapterNumber::parse("71.1").as_f64());
+        assert_eq!(5.0, ChapterNumber::parse("Ch. 5 extra").as_f64());
+        assert_eq!(12.5, ChapterNumber::parse("Chapter 12,5").as_f64());
+        assert_eq!(3.0, ChapterNumber::parse("3.").as_f64());
+        assert_eq!(0.0, ChapterNumber::parse("Oneshot").as_f64());
+        assert_eq!(0.0, ChapterNumber::parse("").as_f64());
+    }
+
+    #[test]
+    fn chapter_numbers_are_compared_by_their_value() {
+        assert_eq!(ChapterNumber::parse("5.50"), ChapterNumber::parse("Ch. 5.5"));
+        assert!(ChapterNumber::parse("10") > ChapterNumber::parse("9.9"));
+
+        let mut numbers = vec![ChapterNumber::parse("10"), ChapterNumber::parse("2"), ChapterNumber::parse("Oneshot")];
+        numbers.sort();
+
+        assert_eq!(vec![ChapterNumber::from(0.0), ChapterNumber::from(2.0), ChapterNumber::from(10.0)], numbers);
+        assert_eq!(10, ChapterNumber::parse("10.9").as_tracker_progress());
+    }
 }
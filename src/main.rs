@@ -7,7 +7,12 @@ use std::time::Duration;
 
 use backend::release_notifier::{ReleaseNotifier, GITHUB_URL};
 use backend::secrets::anilist::AnilistStorage;
+use backend::secrets::mangadex::MangadexStorage;
+use backend::secrets::myanimelist::MyAnimeListStorage;
 use backend::tracker::anilist::{Anilist, BASE_ANILIST_API_URL};
+use backend::tracker::myanimelist::{MyAnimeList, BASE_MYANIMELIST_API_URL};
+use backend::tracker::journal::{JournalFormat, LocalJournal};
+use backend::tracker::{retry_tracker_queue, Tracker};
 use clap::Parser;
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use crossterm::ExecutableCommand;
@@ -15,13 +20,17 @@ use http::StatusCode;
 use log::LevelFilter;
 use logger::{ILogger, Logger};
 
-use self::backend::build_data_dir;
+use self::backend::{build_data_dir, AppDirectories};
 use self::backend::database::Database;
-use self::backend::fetch::{MangadexClient, API_URL_BASE, COVER_IMG_URL_BASE, MANGADEX_CLIENT_INSTANCE};
-use self::backend::migration::migrate_version;
+use self::backend::error_log::{write_to_error_log, ErrorType};
+use self::backend::fetch::{MangadexClient, AT_HOME_REPORT_URL, MANGADEX_CLIENT_INSTANCE};
+use self::backend::migration::migrate_up;
+use self::backend::request_log::RequestLog;
+use self::backend::watch_folder::watch_folder;
+use self::backend::startup_timings::StartupTimings;
 use self::backend::tui::run_app;
 use self::cli::CliArgs;
-use self::config::MangaTuiConfig;
+use self::config::{MangaTuiConfig, TrackerKind};
 
 mod backend;
 mod cli;
@@ -42,14 +51,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let cli_args = CliArgs::parse();
 
+    let manga_to_open = cli_args.manga_to_open();
+
+    let mut timings = StartupTimings::new(cli_args.timings);
+
     cli_args.proccess_args().await?;
 
+    timings.finish_stage("Process cli arguments");
+
     let notifier = ReleaseNotifier::new(GITHUB_URL.parse().unwrap());
 
     if let Err(e) = notifier.check_new_releases(&logger).await {
         logger.error(e);
     }
 
+    timings.finish_stage("Check new releases");
+
     match build_data_dir(&logger) {
         Ok(_) => {},
         Err(e) => {
@@ -65,27 +82,91 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         },
     }
 
-    let anilist_storage = AnilistStorage::new();
-
-    let anilist_client = match anilist_storage.check_credentials_stored() {
-        Ok(Some(credentials)) => {
-            logger.inform("Anilist is setup, tracking reading history");
-            tokio::time::sleep(Duration::from_secs(1)).await;
-            Some(
-                Anilist::new(BASE_ANILIST_API_URL.parse().unwrap())
-                    .with_token(credentials.access_token)
-                    .with_client_id(credentials.client_id),
-            )
-        },
+    timings.finish_stage("Build data directory");
+
+    let mut manga_trackers = vec![];
+    for kind in MangaTuiConfig::get().trackers() {
+        let manga_tracker = match kind {
+            TrackerKind::Anilist => match AnilistStorage::new().check_credentials_stored() {
+                Ok(Some(credentials)) => {
+                    logger.inform("Anilist is setup, tracking reading history");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    Some(Tracker::Anilist(
+                        Anilist::new(BASE_ANILIST_API_URL.parse().unwrap())
+                            .with_token(credentials.access_token)
+                            .with_client_id(credentials.client_id),
+                    ))
+                },
+                Err(e) => {
+                    logger.warn(format!("There is an issue when trying to check for anilist, more details about the error : {e}"));
+                    None
+                },
+                _ => None,
+            },
+            TrackerKind::MyAnimeList => match MyAnimeListStorage::new().check_credentials_stored() {
+                Ok(Some(credentials)) => {
+                    logger.inform("Myanimelist is setup, tracking reading history");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    Some(Tracker::MyAnimeList(
+                        MyAnimeList::new(BASE_MYANIMELIST_API_URL.parse().unwrap())
+                            .with_token(credentials.access_token)
+                            .with_client_id(credentials.client_id),
+                    ))
+                },
+                Err(e) => {
+                    logger.warn(format!(
+                        "There is an issue when trying to check for myanimelist, more details about the error : {e}"
+                    ));
+                    None
+                },
+                _ => None,
+            },
+            kind @ (TrackerKind::JsonJournal | TrackerKind::MarkdownJournal) => {
+                let format = if kind == TrackerKind::JsonJournal { JournalFormat::Json } else { JournalFormat::Markdown };
+                let journal = LocalJournal::new(&AppDirectories::History.get_base_directory(), format);
+                logger.inform(format!("Writing reading history to {}", journal.path().display()));
+                Some(Tracker::Journal(journal))
+            },
+        };
+        manga_trackers.extend(manga_tracker);
+    }
+
+    timings.finish_stage("Check tracker credentials");
+
+    let mangadex_urls = match MangaTuiConfig::get().mangadex_urls() {
+        Ok(urls) => urls,
         Err(e) => {
-            logger.warn(format!("There is an issue when trying to check for anilist, more details about the error : {e}"));
-            None
+            logger.error(format!("The mangadex urls in the config file are not valid, more details : {e}").into());
+            exit(1)
         },
-        _ => None,
     };
 
-    let mangadex_client = MangadexClient::new(API_URL_BASE.parse().unwrap(), COVER_IMG_URL_BASE.parse().unwrap())
-        .with_image_quality(MangaTuiConfig::get().image_quality);
+    let mut mangadex_client = MangadexClient::new(mangadex_urls.api, mangadex_urls.cover)
+        .with_image_quality(MangaTuiConfig::get().image_quality)
+        .with_at_home_report_url(AT_HOME_REPORT_URL.parse().unwrap());
+
+    if let Some(images_url) = mangadex_urls.images {
+        mangadex_client = mangadex_client.with_images_url(images_url);
+    }
+
+    if let Some(request_log) = RequestLog::global() {
+        mangadex_client = mangadex_client.with_request_log(request_log);
+    }
+
+    match MangadexStorage::new().check_credentials_stored() {
+        Ok(Some(account)) => {
+            logger.inform("Mangadex account is setup, syncing chapters read");
+            mangadex_client = mangadex_client.with_account(account);
+        },
+        Err(e) => {
+            logger.warn(format!(
+                "There is an issue when trying to check for the mangadex account, more details about the error : {e}"
+            ));
+        },
+        _ => {},
+    }
+
+    timings.finish_stage("Check mangadex credentials");
 
     logger.inform("Checking mangadex status...");
 
@@ -104,19 +185,56 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         },
     }
 
+    timings.finish_stage("Check mangadex status");
+
     MANGADEX_CLIENT_INSTANCE.set(mangadex_client).unwrap();
 
-    let mut connection = Database::get_connection()?;
+    let connection = Database::get_connection()?;
     let database = Database::new(&connection);
 
-    database.setup()?;
-    migrate_version(&mut connection, &logger)?;
+    database.create_base_tables()?;
+
+    timings.finish_stage("Database setup");
+
+    migrate_up(&connection, &logger)?;
+
+    timings.finish_stage("Database migrations");
+
+    // The user is asked on the home page before these are moved to the trash
+    let history_to_prune = match MangaTuiConfig::get().prune_history_after_months {
+        Some(months) => Some(Database::new(&connection).history_to_prune(months)?).filter(|history| !history.titles.is_empty()),
+        None => None,
+    };
+
+    // If there is a reading session left it means the app was not closed properly last time
+    let session_to_restore = Database::new(&connection).get_reading_session()?;
+
+    let download_to_resume = Database::new(&connection).get_download_all_job()?;
 
     drop(connection);
 
+    if let Some(folder) = MangaTuiConfig::get().watch_folder.clone() {
+        watch_folder(folder);
+    }
+
+    retry_tracker_queue(manga_trackers.clone(), |error| {
+        write_to_error_log(ErrorType::String(&format!("Could not sync reading progress saved while offline: {error}")));
+    });
+
+    timings.report(&logger);
+
     color_eyre::install()?;
     stdout().execute(EnableMouseCapture)?;
-    run_app(ratatui::init(), MangadexClient::global().clone(), anilist_client).await?;
+    run_app(
+        ratatui::init(),
+        MangadexClient::global().clone(),
+        manga_trackers,
+        session_to_restore,
+        download_to_resume,
+        history_to_prune,
+        manga_to_open,
+    )
+    .await?;
     ratatui::restore();
     stdout().execute(DisableMouseCapture)?;
 
@@ -1,6 +1,9 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
 use std::io::Cursor;
 
 use bytes::Bytes;
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
 use image::io::Reader;
 use image::DynamicImage;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
@@ -8,6 +11,7 @@ use ratatui::style::{Color, Style, Stylize};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Paragraph, Widget};
 use ratatui::Frame;
+use throbber_widgets_tui::ThrobberState;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::task::JoinSet;
 use tui_input::Input;
@@ -15,10 +19,19 @@ use tui_input::Input;
 use crate::backend::api_responses::Data;
 use crate::backend::fetch::{ApiClient, MangadexClient};
 use crate::backend::filter::Languages;
-use crate::common::{Artist, Author, Manga};
+use crate::common::{Artist, Author, Manga, RelatedManga};
+use crate::config::MangaTuiConfig;
 use crate::view::widgets::filter_widget::state::{TagListItem, TagListItemState};
 use crate::view::widgets::ImageHandler;
 
+/// Moves a loading indicator to its next frame, unless animations are turned off in the config in which case it stays
+/// still and the screen is not redrawn for it
+pub fn spin_loader(loader: &mut ThrobberState) {
+    if MangaTuiConfig::get().animations {
+        loader.calc_next();
+    }
+}
+
 pub fn set_tags_style(tag: &str) -> Span<'_> {
     match tag.to_lowercase().as_str() {
         "suggestive" => format!("  {tag}  ").black().bg(Color::Yellow),
@@ -78,6 +91,39 @@ pub fn decode_bytes_to_image(data: Bytes) -> Result<DynamicImage, image::ImageEr
     Reader::new(Cursor::new(data)).with_guessed_format()?.decode()
 }
 
+#[derive(Default)]
+struct ColorBucket {
+    pixels: u32,
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+/// The most common vivid color of the image, greys and very dark colors are left out since they don't work as an accent
+pub fn extract_accent_color(image: &DynamicImage) -> Option<Color> {
+    let mut buckets: HashMap<(u8, u8, u8), ColorBucket> = HashMap::new();
+
+    for pixel in image.thumbnail(32, 32).to_rgb8().pixels() {
+        let [r, g, b] = pixel.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+
+        if max < 60 || max - min < 50 {
+            continue;
+        }
+
+        let bucket = buckets.entry((r >> 5, g >> 5, b >> 5)).or_default();
+        bucket.pixels += 1;
+        bucket.red += r as u32;
+        bucket.green += g as u32;
+        bucket.blue += b as u32;
+    }
+
+    buckets.into_iter().max_by_key(|(key, bucket)| (bucket.pixels, *key)).map(|(_, bucket)| {
+        Color::Rgb((bucket.red / bucket.pixels) as u8, (bucket.green / bucket.pixels) as u8, (bucket.blue / bucket.pixels) as u8)
+    })
+}
+
 pub fn from_manga_response(value: Data) -> Manga {
     let id = value.id;
 
@@ -96,6 +142,8 @@ pub fn from_manga_response(value: Data) -> Manga {
         ),
     );
 
+    let alt_titles: Vec<String> = value.attributes.alt_titles.into_iter().flat_map(|titles| titles.into_values()).collect();
+
     let description = match value.attributes.description {
         Some(description) => description.en.unwrap_or("No description".to_string()),
         None => String::from("No description"),
@@ -109,6 +157,16 @@ pub fn from_manga_response(value: Data) -> Manga {
     let mut author = Author::default();
     let mut artist = Artist::default();
 
+    let related: Vec<RelatedManga> = value
+        .relationships
+        .iter()
+        .filter(|rel| rel.type_field == "manga")
+        .map(|rel| RelatedManga {
+            id: rel.id.clone(),
+            relation: rel.related.clone().unwrap_or_default(),
+        })
+        .collect();
+
     for rel in &value.relationships {
         if let Some(attributes) = &rel.attributes {
             match rel.type_field.as_str() {
@@ -147,6 +205,7 @@ pub fn from_manga_response(value: Data) -> Manga {
     Manga {
         id,
         title,
+        alt_titles,
         description,
         content_rating,
         tags,
@@ -157,6 +216,7 @@ pub fn from_manga_response(value: Data) -> Manga {
         publication_demographic,
         available_languages: languages,
         created_at,
+        related,
     }
 }
 
@@ -172,6 +232,43 @@ pub fn display_dates_since_publication(day: i64) -> String {
     }
 }
 
+/// `None` if the format cannot be applied, for example when asking for the hour of a date without time
+fn apply_date_format(formatted: impl Display) -> Option<String> {
+    let mut output = String::new();
+    write!(output, "{formatted}").ok()?;
+    Some(output)
+}
+
+/// `readable_at` is a rfc3339 date, it is shown with the `date_format` of the config or relative to today like "3 days ago"
+pub fn display_publication_date(readable_at: &str, date_format: Option<&str>) -> String {
+    display_publication_date_at(readable_at, date_format, Local::now())
+}
+
+/// The date is shown in the timezone of `now`, which is also where "today" is
+fn display_publication_date_at<Tz: TimeZone>(readable_at: &str, date_format: Option<&str>, now: DateTime<Tz>) -> String
+where
+    Tz::Offset: Display,
+{
+    let date = DateTime::parse_from_rfc3339(readable_at)
+        .unwrap_or_default()
+        .with_timezone(&now.timezone());
+
+    if let Some(formatted) = date_format.and_then(|format| apply_date_format(date.format(format))) {
+        return formatted;
+    }
+
+    display_dates_since_publication((now.date_naive() - date.date_naive()).num_days())
+}
+
+/// `day` is formatted as `YYYY-MM-DD`, which is also how it is shown if there is no `date_format`
+pub fn display_day(day: &str, date_format: Option<&str>) -> String {
+    NaiveDate::parse_from_str(day, "%Y-%m-%d")
+        .ok()
+        .zip(date_format)
+        .and_then(|(date, format)| apply_date_format(date.format(format)))
+        .unwrap_or_else(|| day.to_string())
+}
+
 pub fn centered_rect(r: Rect, percent_x: u16, percent_y: u16) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -208,3 +305,59 @@ pub fn render_search_bar(is_typing: bool, input_help: Line<'_>, input: &Input, f
         false => {},
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::FixedOffset;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_extracts_the_most_common_vivid_color_as_accent() {
+        let mut image = image::RgbImage::from_pixel(10, 10, image::Rgb([30, 30, 30]));
+        for x in 0..6 {
+            for y in 0..10 {
+                image.put_pixel(x, y, image::Rgb([200, 20, 20]));
+            }
+        }
+        image.put_pixel(9, 9, image::Rgb([20, 200, 20]));
+
+        assert_eq!(Some(Color::Rgb(200, 20, 20)), extract_accent_color(&DynamicImage::ImageRgb8(image)));
+
+        let greyscale = image::RgbImage::from_pixel(10, 10, image::Rgb([120, 120, 120]));
+
+        assert_eq!(None, extract_accent_color(&DynamicImage::ImageRgb8(greyscale)));
+    }
+
+    #[test]
+    fn it_displays_dates_with_the_format_set_or_relative_to_today() {
+        let now = FixedOffset::east_opt(0).unwrap().with_ymd_and_hms(2024, 3, 3, 12, 0, 0).unwrap();
+
+        assert_eq!("2 days ago", display_publication_date_at("2024-03-01T12:00:00+00:00", None, now));
+
+        assert_eq!("2024/03/01", display_publication_date_at("2024-03-01T12:00:00+00:00", Some("%Y/%m/%d"), now));
+
+        assert_eq!("2 days ago", display_publication_date_at("2024-03-01T12:00:00+00:00", Some("%Q"), now));
+    }
+
+    #[test]
+    fn publication_dates_are_shown_in_the_timezone_of_the_user() {
+        let now = FixedOffset::east_opt(14 * 3600).unwrap().with_ymd_and_hms(2024, 3, 3, 12, 0, 0).unwrap();
+
+        // already the next day at +14:00
+        assert_eq!("2024/03/02", display_publication_date_at("2024-03-01T12:00:00+00:00", Some("%Y/%m/%d"), now));
+
+        assert_eq!("1 days ago", display_publication_date_at("2024-03-01T12:00:00+00:00", None, now));
+    }
+
+    #[test]
+    fn it_displays_days_with_the_format_set() {
+        assert_eq!("01 Mar 2024", display_day("2024-03-01", Some("%d %b %Y")));
+
+        assert_eq!("2024-03-01", display_day("2024-03-01", None));
+
+        // a day has no hour
+        assert_eq!("2024-03-01", display_day("2024-03-01", Some("%H:%M")));
+    }
+}
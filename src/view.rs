@@ -1,4 +1,5 @@
 pub mod app;
 pub mod pages;
+pub mod shortcuts;
 pub mod tasks;
 pub mod widgets;
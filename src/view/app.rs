@@ -1,25 +1,41 @@
-use ::crossterm::event::KeyCode;
-use crossterm::event::{KeyEvent, KeyModifiers};
+use std::time::Duration;
+
+use crossterm::event::KeyEvent;
+use manga_tui::MangaLink;
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::widgets::{Block, Borders, Tabs, Widget};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Tabs, Widget};
 use ratatui::Frame;
 use ratatui_image::picker::Picker;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
+use self::debug::DebugPage;
 use self::feed::Feed;
 use self::home::Home;
-use self::manga::MangaPage;
+use self::latest_updates::LatestUpdatesPage;
+use self::library::LibraryPage;
+use self::manga::{FetchChapterBookmarked, MangaPage};
 use self::reader::{ChapterToRead, ListOfChapters, MangaReader, SearchChapter, SearchMangaPanel};
 use self::search::{InputMode, SearchPage};
+use self::wrapped::WrappedPage;
 use super::widgets::search::MangaItem;
+use super::shortcuts::GlobalShortcut;
 use super::widgets::Component;
-use crate::backend::fetch::ApiClient;
+use crate::backend::database::{ChapterBookmarked, DownloadAllJob, HistoryToPrune};
+use crate::backend::database_actor::DatabaseActor;
+use crate::backend::error_log::{write_to_error_log, ErrorType};
+use crate::backend::fetch::{ApiClient, MangadexClient, MANGADEX_CLIENT_INSTANCE};
+use crate::backend::filter::MANGADEX_FILTERS_FILE;
+use crate::backend::rate_limit::RateLimit;
+use crate::backend::request_log::RequestLog;
 use crate::backend::tracker::MangaTracker;
 use crate::backend::tui::{Action, Events};
+use crate::backend::AppDirectories;
 use crate::config::MangaTuiConfig;
 use crate::global::INSTRUCTIONS_STYLE;
 use crate::view::pages::*;
+use crate::view::tasks::search::search_manga_by_id;
 
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 pub enum AppState {
@@ -50,8 +66,13 @@ where
     pub search_page: SearchPage<T, S>,
     pub home_page: Home,
     pub feed_page: Feed<T>,
+    pub wrapped_page: WrappedPage,
+    pub library_page: LibraryPage<T>,
+    pub latest_updates_page: LatestUpdatesPage<T>,
+    /// Only available when running with `--debug`
+    pub debug_page: Option<DebugPage>,
     api_client: T,
-    manga_tracker: Option<S>,
+    manga_trackers: Vec<S>,
     // The picker is what decides how big a image needs to be rendered depending on the user's
     // terminal font size and the graphics it supports
     // if the terminal doesn't support any graphics protocol the picker is `None`
@@ -77,14 +98,20 @@ where
 
             self.render_pages(page_area, frame);
         }
+
+        render_rate_limit_countdown(RateLimit::global().remaining(), area, frame.buffer_mut());
     }
 
     fn handle_events(&mut self, events: Events) {
         match events {
             Events::Key(key_event) => self.handle_key_events(key_event),
             Events::GoToMangaPage(manga) => self.go_to_manga_page(manga),
+            Events::ResumeDownloadAll(manga, language) => {
+                self.go_to_manga_page(manga);
+                self.manga_page.as_mut().unwrap().resume_download_all_chapters(language);
+            },
             Events::ReadChapter(chapter_response, manga_to_read) => {
-                self.go_to_read_chapter(chapter_response, manga_to_read, self.manga_tracker.clone())
+                self.go_to_read_chapter(chapter_response, manga_to_read, self.manga_trackers.clone())
             },
             Events::GoSearchPage => {
                 self.go_search_page();
@@ -100,10 +127,26 @@ where
                 self.go_search_page();
                 self.search_page.search_mangas_of_artist(artist);
             },
+            Events::GoSearchMangasTitle(title) => {
+                self.go_search_page();
+                self.search_page.search_mangas_by_title(title);
+            },
+            // Pages other than the current one also need to be updated, the rest load their data when they are opened
+            Events::ChapterStatusChanged(manga_id) => {
+                if let Some(manga_page) = self.manga_page.as_mut() {
+                    manga_page.refresh_chapters_status(&manga_id);
+                }
+                self.home_page.load_continue_reading();
+            },
             Events::GoBackMangaPage => {
                 if self.current_tab == SelectedPage::ReaderTab && self.manga_reader_page.is_some() {
                     self.manga_reader_page.as_mut().unwrap().clean_up();
-                    self.current_tab = SelectedPage::MangaTab;
+                    // A resumed reading session is opened without going through the manga page
+                    if self.manga_page.is_some() {
+                        self.current_tab = SelectedPage::MangaTab;
+                    } else {
+                        self.go_to_home();
+                    }
                 }
             },
             _ => {},
@@ -122,7 +165,7 @@ where
 }
 
 impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S> {
-    pub fn new(api_client: T, manga_tracker: Option<S>, picker: Option<Picker>) -> Self {
+    pub fn new(api_client: T, manga_trackers: Vec<S>, picker: Option<Picker>) -> Self {
         let (global_action_tx, global_action_rx) = unbounded_channel::<Action>();
         let (global_event_tx, global_event_rx) = unbounded_channel::<Events>();
 
@@ -131,26 +174,92 @@ impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S>
         App {
             picker,
             current_tab: SelectedPage::default(),
-            search_page: SearchPage::new(picker, api_client.clone(), manga_tracker.clone())
-                .with_global_sender(global_event_tx.clone()),
+            search_page: SearchPage::new(picker, api_client.clone(), manga_trackers.clone())
+                .with_global_sender(global_event_tx.clone())
+                .with_saved_filters(AppDirectories::History.get_base_directory().join(MANGADEX_FILTERS_FILE)),
             feed_page: Feed::new()
                 .with_global_sender(global_event_tx.clone())
-                .with_api_client(api_client.clone()),
-            home_page: Home::new(picker).with_global_sender(global_event_tx.clone()),
+                .with_api_client(api_client.clone())
+                .with_mangadex_follows(MANGADEX_CLIENT_INSTANCE.get().is_some_and(|client| client.is_logged_in()))
+                .with_reading_goal(MangaTuiConfig::get().reading_goal),
+            home_page: Home::new(picker)
+                .with_global_sender(global_event_tx.clone())
+                .with_custom_lists(MangaTuiConfig::get().home_lists()),
             manga_page: None,
             manga_reader_page: None,
+            wrapped_page: WrappedPage::new(),
+            library_page: LibraryPage::new()
+                .with_global_sender(global_event_tx.clone())
+                .with_api_client(api_client.clone()),
+            latest_updates_page: LatestUpdatesPage::new()
+                .with_global_sender(global_event_tx.clone())
+                .with_api_client(api_client.clone()),
+            debug_page: RequestLog::is_enabled().then(DebugPage::new),
             global_action_tx,
             global_action_rx,
             global_event_tx,
             global_event_rx,
-            manga_tracker,
+            manga_trackers,
             state: AppState::Runnning,
             api_client,
         }
     }
 
+    pub fn with_session_to_restore(mut self, session: Option<ChapterBookmarked>) -> Self {
+        self.home_page = self.home_page.with_session_to_restore(session);
+        self
+    }
+
+    pub fn with_download_to_resume(mut self, job: Option<DownloadAllJob>) -> Self {
+        self.home_page = self.home_page.with_download_to_resume(job);
+        self
+    }
+
+    pub fn with_history_to_prune(mut self, history: Option<HistoryToPrune>) -> Self {
+        self.home_page = self.home_page.with_history_to_prune(history);
+        self
+    }
+
+    /// Go straight to the page of a manga, used when the app is run with `open <url>`. If the link has a chapter it is
+    /// opened in the reader afterwards so that going back leads to the manga page
+    pub fn open_manga_link(&self, link: MangaLink) {
+        let api_client = self.api_client.clone();
+        let tx = self.global_event_tx.clone();
+        // Reading requires image support
+        let chapter_id = link.chapter_id().filter(|_| self.picker.is_some()).map(|id| id.to_string());
+
+        tokio::spawn(async move {
+            let manga = match search_manga_by_id(api_client, link.id()).await {
+                Ok(manga) => manga,
+                Err(e) => {
+                    write_to_error_log(ErrorType::Error(e));
+                    return;
+                },
+            };
+
+            let chapter = chapter_id.map(|id| ChapterBookmarked {
+                id,
+                manga_id: manga.id.clone(),
+                manga_title: manga.title.clone(),
+                ..Default::default()
+            });
+
+            tx.send(Events::GoToMangaPage(MangaItem::new(manga))).ok();
+
+            if let Some(chapter) = chapter {
+                match MangadexClient::global().fetch_chapter_bookmarked(chapter).await {
+                    Ok((chapter, manga)) => {
+                        tx.send(Events::ReadChapter(chapter, manga)).ok();
+                    },
+                    Err(e) => write_to_error_log(ErrorType::Error(e)),
+                }
+            }
+        });
+    }
+
     pub fn render_top_tabs(&self, area: Rect, buf: &mut Buffer) {
-        let mut titles: Vec<&str> = vec!["Home <F1>/<u>", "Search <F2>/<i>", "Feed <F3>/<o>"];
+        let mut titles: Vec<&str> =
+            vec!["Home <F1>/<u>", "Search <F2>/<i>", "Feed <F3>/<o>", "Wrapped <F4>", "Library <F5>", "Latest <F6>"];
 
         let tabs_block = Block::default().borders(Borders::BOTTOM);
 
@@ -158,9 +267,16 @@ impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S>
             SelectedPage::Home => 0,
             SelectedPage::Search => 1,
             SelectedPage::Feed => 2,
+            SelectedPage::Wrapped => 3,
+            SelectedPage::Library => 4,
+            SelectedPage::LatestUpdates => 5,
             SelectedPage::MangaTab => {
                 titles.push(" 📖 Manga page");
-                3
+                6
+            },
+            SelectedPage::Debug => {
+                titles.push("Debug <F12>");
+                6
             },
             _ => 0,
         };
@@ -180,6 +296,14 @@ impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S>
             SelectedPage::MangaTab => self.render_manga_page(area, frame),
             SelectedPage::Home => self.render_home_page(area, frame),
             SelectedPage::Feed => self.render_feed_page(area, frame),
+            SelectedPage::Wrapped => self.wrapped_page.render(area, frame),
+            SelectedPage::Library => self.library_page.render(area, frame),
+            SelectedPage::LatestUpdates => self.latest_updates_page.render(area, frame),
+            SelectedPage::Debug => {
+                if let Some(page) = self.debug_page.as_mut() {
+                    page.render(area, frame);
+                }
+            },
             // Reader tab should be on full screen
             SelectedPage::ReaderTab => {},
         }
@@ -215,35 +339,45 @@ impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S>
 
     fn quit(&mut self) {
         self.auto_bookmark_on_quit();
+        if let Some(reader_page) = self.manga_reader_page.as_ref() {
+            reader_page.clear_reading_session();
+        }
+        DatabaseActor::global().flush(Duration::from_secs(2));
         self.global_action_tx.send(Action::Quit).ok();
     }
 
-    fn handle_key_events(&mut self, key_event: KeyEvent) {
+    fn global_shortcut(&self, key_event: KeyEvent) -> Option<GlobalShortcut> {
         if self.manga_page.as_ref().is_some_and(|page| page.is_downloading_all_chapters()) {
-            return;
+            return None;
         }
 
-        if self.search_page.input_mode != InputMode::Typing && !self.search_page.is_typing_filter() && !self.feed_page.is_typing() {
-            match key_event.code {
-                KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => self.quit(),
-                KeyCode::Char('u') | KeyCode::F(1) => {
-                    if self.current_tab != SelectedPage::ReaderTab {
-                        self.global_event_tx.send(Events::GoToHome).ok();
-                    }
-                },
-                KeyCode::Char('i') | KeyCode::F(2) => {
-                    if self.current_tab != SelectedPage::ReaderTab {
-                        self.global_event_tx.send(Events::GoSearchPage).ok();
-                    }
-                },
-                KeyCode::Char('o') | KeyCode::F(3) => {
-                    if self.current_tab != SelectedPage::ReaderTab {
-                        self.global_event_tx.send(Events::GoFeedPage).ok();
-                    }
-                },
+        let is_typing = self.home_page.is_typing()
+            || self.search_page.input_mode == InputMode::Typing
+            || self.search_page.is_typing_filter()
+            || self.feed_page.is_typing()
+            || self.library_page.is_typing()
+            || self.manga_page.as_ref().is_some_and(|page| page.is_typing());
 
-                _ => {},
-            }
+        GlobalShortcut::from_key(key_event, self.current_tab, is_typing)
+    }
+
+    fn handle_key_events(&mut self, key_event: KeyEvent) {
+        match self.global_shortcut(key_event) {
+            Some(GlobalShortcut::Quit) => self.quit(),
+            Some(GlobalShortcut::GoHome) => {
+                self.global_event_tx.send(Events::GoToHome).ok();
+            },
+            Some(GlobalShortcut::GoSearch) => {
+                self.global_event_tx.send(Events::GoSearchPage).ok();
+            },
+            Some(GlobalShortcut::GoFeed) => {
+                self.global_event_tx.send(Events::GoFeedPage).ok();
+            },
+            Some(GlobalShortcut::GoWrapped) => self.go_wrapped_page(),
+            Some(GlobalShortcut::GoLibrary) => self.go_library_page(),
+            Some(GlobalShortcut::GoLatestUpdates) => self.go_latest_updates_page(),
+            Some(GlobalShortcut::GoDebug) => self.go_debug_page(),
+            None => {},
         }
     }
 
@@ -271,12 +405,12 @@ impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S>
         let manga_page = MangaPage::new(manga.manga, self.picker)
             .with_global_sender(self.global_event_tx.clone())
             .auto_bookmark(config.auto_bookmark)
-            .with_manga_tracker(self.manga_tracker.clone());
+            .with_manga_trackers(self.manga_trackers.clone());
 
         self.manga_page = Some(manga_page);
     }
 
-    fn go_to_read_chapter(&mut self, chapter_to_read: ChapterToRead, manga_to_read: MangaToRead, manga_tracker: Option<S>) {
+    fn go_to_read_chapter(&mut self, chapter_to_read: ChapterToRead, manga_to_read: MangaToRead, manga_trackers: Vec<S>) {
         self.home_page.clean_up();
         self.feed_page.clean_up();
         self.current_tab = SelectedPage::ReaderTab;
@@ -290,7 +424,7 @@ impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S>
         .with_global_sender(self.global_event_tx.clone())
         .with_list_of_chapters(manga_to_read.list)
         .with_manga_title(manga_to_read.title)
-        .with_manga_tracker(manga_tracker);
+        .with_manga_trackers(manga_trackers);
 
         let config = MangaTuiConfig::get();
 
@@ -298,7 +432,19 @@ impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S>
             manga_reader.set_auto_bookmark();
         }
 
+        if config.low_memory_mode {
+            manga_reader.set_low_memory_mode();
+        }
+
+        if config.warn_skipped_chapters {
+            manga_reader.set_warn_skipped_chapters();
+        }
+
+        manga_reader.load_reading_mode();
+
         manga_reader.init_fetching_pages();
+        manga_reader.save_reading_session();
+        manga_reader.check_skipped_chapters();
 
         self.manga_reader_page = Some(manga_reader);
     }
@@ -327,9 +473,57 @@ impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S>
         self.current_tab = SelectedPage::Feed;
     }
 
+    fn go_wrapped_page(&mut self) {
+        if self.manga_page.is_some() {
+            self.manga_page.as_mut().unwrap().clean_up();
+            self.manga_page = None;
+        }
+        self.feed_page.clean_up();
+        self.wrapped_page.load_summary();
+        self.current_tab = SelectedPage::Wrapped;
+    }
+
+    fn go_library_page(&mut self) {
+        if self.manga_page.is_some() {
+            self.manga_page.as_mut().unwrap().clean_up();
+            self.manga_page = None;
+        }
+        self.feed_page.clean_up();
+        self.library_page.clean_up();
+        self.library_page.load();
+        self.current_tab = SelectedPage::Library;
+    }
+
+    fn go_latest_updates_page(&mut self) {
+        if self.manga_page.is_some() {
+            self.manga_page.as_mut().unwrap().clean_up();
+            self.manga_page = None;
+        }
+        self.feed_page.clean_up();
+        self.latest_updates_page.clean_up();
+        self.latest_updates_page.load();
+        self.current_tab = SelectedPage::LatestUpdates;
+    }
+
+    fn go_debug_page(&mut self) {
+        if let Some(debug_page) = self.debug_page.as_mut() {
+            if self.manga_page.is_some() {
+                self.manga_page.as_mut().unwrap().clean_up();
+                self.manga_page = None;
+            }
+            self.feed_page.clean_up();
+            debug_page.refresh();
+            self.current_tab = SelectedPage::Debug;
+        }
+    }
+
     pub async fn listen_to_event(&mut self) {
         if let Some(event) = self.global_event_rx.recv().await {
+            let is_global_shortcut = matches!(event, Events::Key(key_event) if self.global_shortcut(key_event).is_some());
             self.handle_events(event.clone());
+            if is_global_shortcut {
+                return;
+            }
             match self.current_tab {
                 SelectedPage::Search => {
                     self.search_page.handle_events(event);
@@ -346,6 +540,20 @@ impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S>
                 SelectedPage::Feed => {
                     self.feed_page.handle_events(event);
                 },
+                SelectedPage::Wrapped => {
+                    self.wrapped_page.handle_events(event);
+                },
+                SelectedPage::Library => {
+                    self.library_page.handle_events(event);
+                },
+                SelectedPage::LatestUpdates => {
+                    self.latest_updates_page.handle_events(event);
+                },
+                SelectedPage::Debug => {
+                    if let Some(debug_page) = self.debug_page.as_mut() {
+                        debug_page.handle_events(event);
+                    }
+                },
             };
         }
     }
@@ -385,6 +593,28 @@ impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S>
                     self.feed_page.update(feed_event);
                 }
             },
+            SelectedPage::Wrapped => {
+                if let Ok(wrapped_action) = self.wrapped_page.local_action_rx.try_recv() {
+                    self.wrapped_page.update(wrapped_action);
+                }
+            },
+            SelectedPage::Library => {
+                if let Ok(library_action) = self.library_page.local_action_rx.try_recv() {
+                    self.library_page.update(library_action);
+                }
+            },
+            SelectedPage::LatestUpdates => {
+                if let Ok(latest_updates_action) = self.latest_updates_page.local_action_rx.try_recv() {
+                    self.latest_updates_page.update(latest_updates_action);
+                }
+            },
+            SelectedPage::Debug => {
+                if let Some(debug_page) = self.debug_page.as_mut() {
+                    if let Ok(debug_action) = debug_page.local_action_rx.try_recv() {
+                        debug_page.update(debug_action);
+                    }
+                }
+            },
         };
     }
 
@@ -396,9 +626,24 @@ impl<T: ApiClient + SearchChapter + SearchMangaPanel, S: MangaTracker> App<T, S>
     }
 }
 
+/// Shown on the last line of whatever page is open while a request waits to be sent again after being rate limited
+fn render_rate_limit_countdown(remaining: Option<Duration>, area: Rect, buf: &mut Buffer) {
+    let Some(remaining) = remaining else {
+        return;
+    };
+
+    let countdown_area = Rect::new(area.x, area.bottom().saturating_sub(1), area.width, 1.min(area.height));
+
+    Clear.render(countdown_area, buf);
+    Paragraph::new(format!("Mangadex is limiting requests, trying again in {}s", remaining.as_secs() + 1))
+        .style(Style::default().fg(Color::Black).bg(Color::Yellow))
+        .render(countdown_area, buf);
+}
+
 #[cfg(test)]
 mod tests {
 
+    use crossterm::event::KeyCode;
     use pretty_assertions::assert_eq;
 
     use self::reader::{SortedVolumes, Volumes};
@@ -425,9 +670,22 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn goes_to_manga_page_of_manga_link() {
+        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), vec![], None);
+
+        assert_eq!(Some(Events::GoToHome), app.global_event_rx.recv().await);
+
+        app.open_manga_link(MangaLink::Mangadex("manga_id".to_string()));
+
+        let event = app.global_event_rx.recv().await.expect("no event was sent");
+
+        assert!(matches!(event, Events::GoToMangaPage(_)));
+    }
+
     #[test]
     fn goes_to_home_page() {
-        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), None, None);
+        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), vec![], None);
 
         let first_event = app.global_event_rx.blocking_recv().expect("no event was sent");
 
@@ -437,7 +695,7 @@ mod tests {
 
     #[test]
     fn can_go_to_search_page_by_pressing_i() {
-        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), None, None);
+        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), vec![], None);
 
         press_key(&mut app, KeyCode::Char('i'));
 
@@ -448,7 +706,7 @@ mod tests {
 
     #[test]
     fn can_go_to_home_by_pressing_u() {
-        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), None, None);
+        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), vec![], None);
 
         app.go_search_page();
 
@@ -461,7 +719,7 @@ mod tests {
 
     #[test]
     fn can_go_to_feed_by_pressing_o() {
-        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), None, None);
+        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), vec![], None);
 
         press_key(&mut app, KeyCode::Char('o'));
 
@@ -472,7 +730,7 @@ mod tests {
 
     #[test]
     fn doesnt_listen_to_key_events_if_it_is_downloading_all_chapters() {
-        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), None, None).with_manga_page();
+        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), vec![], None).with_manga_page();
 
         app.manga_page.as_mut().unwrap().start_downloading_all_chapters();
 
@@ -487,7 +745,7 @@ mod tests {
 
     #[test]
     fn reader_page_is_initialized_corectly() {
-        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), None, Some(Picker::new((8, 8))));
+        let mut app: App<MockMangadexClient, TrackerTest> = App::new(MockMangadexClient::new(), vec![], Some(Picker::new((8, 8))));
 
         let chapter_to_read = ChapterToRead {
             id: "some_id".to_string(),
@@ -495,6 +753,7 @@ mod tests {
             number: 1.0,
             volume_number: Some("1".to_string()),
             num_page_bookmarked: None,
+            page_to_resume: None,
             language: Languages::default(),
             pages_url: vec!["http://localhost:3000".parse().unwrap()],
         };
@@ -506,7 +765,6 @@ mod tests {
             }]),
         };
 
-        let manga_tracker = TrackerTest::new();
 
         app.go_to_read_chapter(
             chapter_to_read,
@@ -515,7 +773,7 @@ mod tests {
                 manga_id: "some_manga_id".to_string(),
                 list: list_of_chapter.clone(),
             },
-            Some(manga_tracker),
+            vec![TrackerTest::new()],
         );
 
         let reader_page = app.manga_reader_page.unwrap();
@@ -523,6 +781,22 @@ mod tests {
         assert!(reader_page.global_event_tx.is_some());
         assert_eq!(reader_page.list_of_chapters, list_of_chapter);
         assert_eq!(SelectedPage::ReaderTab, app.current_tab);
-        assert!(reader_page.manga_tracker.is_some());
+        assert!(!reader_page.manga_trackers.is_empty());
+    }
+
+    #[test]
+    fn it_shows_a_countdown_while_a_rate_limited_request_waits() {
+        let area = Rect::new(0, 0, 60, 3);
+        let mut buf = Buffer::empty(area);
+
+        render_rate_limit_countdown(None, area, &mut buf);
+
+        assert!(buf.content().iter().all(|cell| cell.symbol() == " "));
+
+        render_rate_limit_countdown(Some(Duration::from_millis(4500)), area, &mut buf);
+
+        let last_line: String = (0..area.width).map(|x| buf[(x, 2)].symbol()).collect();
+
+        assert!(last_line.contains("trying again in 5s"));
     }
 }
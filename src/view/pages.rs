@@ -1,10 +1,14 @@
 use strum::{Display, EnumCount, EnumIter, FromRepr};
 
+pub mod debug;
 pub mod feed;
 pub mod home;
+pub mod latest_updates;
+pub mod library;
 pub mod manga;
 pub mod reader;
 pub mod search;
+pub mod wrapped;
 
 #[derive(Debug, Clone, Copy, Default, FromRepr, Display, EnumIter, EnumCount, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SelectedPage {
@@ -14,4 +18,8 @@ pub enum SelectedPage {
     Home,
     Search,
     Feed,
+    Wrapped,
+    Library,
+    LatestUpdates,
+    Debug,
 }
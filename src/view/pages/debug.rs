@@ -0,0 +1,221 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, StatefulWidget, Widget, Wrap};
+use ratatui::Frame;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::backend::request_log::{RequestLog, RequestRecord};
+use crate::backend::tui::Events;
+use crate::global::{CURRENT_LIST_ITEM_STYLE, ERROR_STYLE, INSTRUCTIONS_STYLE};
+use crate::view::widgets::Component;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DebugPageActions {
+    ScrollUp,
+    ScrollDown,
+}
+
+/// Hidden page only available with `--debug`, it shows the last requests made to the manga provider
+pub struct DebugPage {
+    pub local_action_tx: UnboundedSender<DebugPageActions>,
+    pub local_action_rx: UnboundedReceiver<DebugPageActions>,
+    records: Vec<RequestRecord>,
+    state: ListState,
+}
+
+impl DebugPage {
+    pub fn new() -> Self {
+        let (local_action_tx, local_action_rx) = mpsc::unbounded_channel::<DebugPageActions>();
+        Self {
+            local_action_tx,
+            local_action_rx,
+            records: vec![],
+            state: ListState::default(),
+        }
+    }
+
+    /// Get the latest requests recorded, the selected one is kept if it is still recorded
+    pub fn refresh(&mut self) {
+        if let Some(request_log) = RequestLog::global() {
+            self.load_records(request_log.records());
+        }
+    }
+
+    fn load_records(&mut self, records: Vec<RequestRecord>) {
+        let previously_selected = self.get_selected_record().cloned();
+        self.records = records;
+
+        let index = previously_selected.and_then(|selected| self.records.iter().position(|record| *record == selected));
+
+        match index {
+            Some(index) => self.state.select(Some(index)),
+            None if !self.records.is_empty() => self.state.select(Some(0)),
+            None => self.state.select(None),
+        }
+    }
+
+    fn get_selected_record(&self) -> Option<&RequestRecord> {
+        self.state.selected().and_then(|index| self.records.get(index))
+    }
+
+    fn scroll_down(&mut self) {
+        if self.state.selected().is_some_and(|index| index + 1 < self.records.len()) {
+            self.state.select_next();
+        }
+    }
+
+    fn scroll_up(&mut self) {
+        self.state.select_previous();
+    }
+
+    fn handle_key_events(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.local_action_tx.send(DebugPageActions::ScrollDown).ok();
+            },
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.local_action_tx.send(DebugPageActions::ScrollUp).ok();
+            },
+            _ => {},
+        }
+    }
+
+    fn render_records(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let instructions = Line::from(vec![
+            "Go down ".into(),
+            Span::raw("<j>").style(*INSTRUCTIONS_STYLE),
+            " Go up ".into(),
+            Span::raw("<k>").style(*INSTRUCTIONS_STYLE),
+        ]);
+
+        let items: Vec<ListItem<'_>> = self
+            .records
+            .iter()
+            .map(|record| {
+                let status = match record.status {
+                    Some(status) if status < 400 => Span::raw(status.to_string()).style(Style::default().fg(Color::Green)),
+                    Some(status) => Span::raw(status.to_string()).style(*ERROR_STYLE),
+                    None => Span::raw("failed").style(*ERROR_STYLE),
+                };
+
+                ListItem::new(Line::from(vec![
+                    status,
+                    format!(" {}ms {} {}", record.elapsed.as_millis(), record.method, record.url).into(),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::bordered()
+                    .title(format!("Last {} requests", self.records.len()))
+                    .title_bottom(instructions),
+            )
+            .highlight_style(*CURRENT_LIST_ITEM_STYLE);
+
+        StatefulWidget::render(list, area, frame.buffer_mut(), &mut self.state);
+    }
+
+    fn render_body(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let body = self.get_selected_record().map(|record| record.body_preview.as_str()).unwrap_or_default();
+
+        Paragraph::new(body)
+            .block(Block::bordered().title("Response body"))
+            .wrap(Wrap { trim: false })
+            .render(area, frame.buffer_mut());
+    }
+}
+
+impl Component for DebugPage {
+    type Actions = DebugPageActions;
+
+    fn render(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let [records_area, body_area] = Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(area);
+
+        self.render_records(records_area, frame);
+
+        self.render_body(body_area, frame);
+    }
+
+    fn update(&mut self, action: Self::Actions) {
+        match action {
+            DebugPageActions::ScrollUp => self.scroll_up(),
+            DebugPageActions::ScrollDown => self.scroll_down(),
+        }
+    }
+
+    fn handle_events(&mut self, events: Events) {
+        match events {
+            Events::Key(key_event) => self.handle_key_events(key_event),
+            Events::Tick => self.refresh(),
+            _ => {},
+        }
+    }
+
+    fn clean_up(&mut self) {
+        self.records = vec![];
+        self.state = ListState::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::view::widgets::press_key;
+
+    fn records() -> Vec<RequestRecord> {
+        vec![
+            RequestRecord::new("GET", "http://localhost/3", Some(500), Duration::from_millis(3), "error"),
+            RequestRecord::new("GET", "http://localhost/2", Some(200), Duration::from_millis(2), "{}"),
+            RequestRecord::new("GET", "http://localhost/1", None, Duration::from_millis(1), "timeout"),
+        ]
+    }
+
+    #[test]
+    fn it_selects_the_most_recent_request_when_loading_records() {
+        let mut debug_page = DebugPage::new();
+
+        debug_page.load_records(records());
+
+        assert_eq!("http://localhost/3", debug_page.get_selected_record().unwrap().url);
+    }
+
+    #[test]
+    fn it_keeps_selected_request_when_new_requests_are_recorded() {
+        let mut debug_page = DebugPage::new();
+
+        debug_page.load_records(records());
+
+        press_key(&mut debug_page, KeyCode::Char('j'));
+        let action = debug_page.local_action_rx.blocking_recv().unwrap();
+        debug_page.update(action);
+
+        assert_eq!("http://localhost/2", debug_page.get_selected_record().unwrap().url);
+
+        let mut new_records = records();
+        new_records.insert(0, RequestRecord::new("GET", "http://localhost/4", Some(200), Duration::from_millis(4), ""));
+
+        debug_page.load_records(new_records);
+
+        assert_eq!("http://localhost/2", debug_page.get_selected_record().unwrap().url);
+    }
+
+    #[test]
+    fn it_does_not_scroll_past_the_last_request() {
+        let mut debug_page = DebugPage::new();
+
+        debug_page.load_records(records());
+
+        for _ in 0..5 {
+            debug_page.update(DebugPageActions::ScrollDown);
+        }
+
+        assert_eq!("http://localhost/1", debug_page.get_selected_record().unwrap().url);
+    }
+}
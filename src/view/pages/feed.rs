@@ -1,10 +1,12 @@
+use chrono::Utc;
 use crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
 use manga_tui::SearchTerm;
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Margin, Rect};
 use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span, ToSpan};
-use ratatui::widgets::{Block, Paragraph, StatefulWidget, Tabs, Widget};
+use ratatui::symbols::line::THICK;
+use ratatui::widgets::{Block, LineGauge, Paragraph, StatefulWidget, Tabs, Widget};
 use ratatui::Frame;
 use throbber_widgets_tui::{Throbber, ThrobberState};
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
@@ -13,13 +15,19 @@ use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
 use crate::backend::api_responses::ChapterResponse;
-use crate::backend::database::{get_history, GetHistoryArgs, MangaHistoryResponse, MangaHistoryType, DBCONN};
+use crate::backend::bug_report::report_bug;
+use crate::backend::database::{get_history, Database, GetHistoryArgs, HistoryOrder, MangaHistoryResponse};
+use crate::backend::database_actor::DatabaseActor;
 use crate::backend::error_log::{write_to_error_log, ErrorType};
 use crate::backend::fetch::ApiClient;
 use crate::backend::tui::Events;
+use crate::config::ReadingGoal;
 use crate::global::{ERROR_STYLE, INSTRUCTIONS_STYLE};
-use crate::utils::render_search_bar;
-use crate::view::tasks::feed::{search_latest_chapters, search_manga};
+use crate::utils::{render_search_bar, spin_loader};
+use crate::view::tasks::feed::{
+    check_library_health, search_latest_chapters, search_manga, search_mangadex_follows, LibraryHealth, LIBRARY_HEALTH_CHECK_DELAY,
+};
+use crate::view::widgets::confirmation::{ConfirmationScreen, ConfirmationState};
 use crate::view::widgets::feed::{FeedTabs, HistoryWidget};
 use crate::view::widgets::Component;
 
@@ -42,6 +50,17 @@ pub enum FeedActions {
     PreviousPage,
     SwitchTab,
     GoToMangaPage,
+    CheckLibraryHealth,
+    ToggleSearchAllTabs,
+    ToggleOrder,
+    /// Asks to confirm moving the manga selected to the trash
+    MoveToTrash,
+    ConfirmMoveToTrash,
+    CancelMoveToTrash,
+    /// Restores the manga selected in the trash or undoes the last one moved there
+    RestoreFromTrash,
+    ToggleTrash,
+    ReportBug,
 }
 
 #[derive(Debug, PartialEq)]
@@ -53,6 +72,18 @@ pub enum FeedEvents {
     ErrorSearchingMangaData,
     /// page , (history_data, total_results)
     LoadHistory(Option<MangaHistoryResponse>),
+    LoadDeadMangas(Vec<String>),
+    LibraryHealthChecked(LibraryHealth),
+    /// The manga that was about to be opened no longer exists on mangadex
+    MangaNotFound(String),
+    /// Chapters read in the current period of the reading goal
+    LoadReadingGoalProgress(u32),
+    // The results of the queries sent to the database actor
+    LoadLibraryMangaIds(Vec<String>),
+    /// The dead mangas saved once the library health check finished
+    SaveDeadMangas(Vec<String>),
+    /// Id and title of the manga moved to the trash
+    MovedToTrash(String, String),
 }
 
 pub struct Feed<T: ApiClient> {
@@ -67,9 +98,31 @@ pub struct Feed<T: ApiClient> {
     pub local_event_rx: UnboundedReceiver<FeedEvents>,
     search_bar: Input,
     is_typing: bool,
+    /// Searches the reading history and plan to read list together instead of the tab selected
+    search_all_tabs: bool,
+    /// Shows the mangas moved to the trash instead of the tab selected
+    show_trash: bool,
+    /// `(id, title)` of the last manga moved to the trash so it can be undone
+    manga_trashed: Option<(String, String)>,
+    /// `(id, title)` of the manga the user is asked to confirm moving to the trash
+    manga_to_trash: Option<(String, String)>,
+    trash_confirmation: ConfirmationState,
+    order: HistoryOrder,
     items_per_page: u32,
+    is_checking_library_health: bool,
+    /// How many dead mangas the last library health check found
+    dead_mangas_found: Option<usize>,
+    /// How many mangas the last library health check could not tell if they still exist
+    mangas_not_checked: usize,
+    /// Whether the user is logged in to mangadex to show the tab with the mangas followed
+    show_mangadex_follows: bool,
+    reading_goal: Option<ReadingGoal>,
+    /// Chapters read since the reading goal's period started, `None` until it is counted
+    chapters_read_for_goal: Option<u32>,
     tasks: JoinSet<()>,
     api_client: Option<T>,
+    /// Every query is sent to it so the ui thread never waits for the database
+    database: DatabaseActor,
 }
 
 impl<T: ApiClient> Feed<T> {
@@ -89,13 +142,32 @@ impl<T: ApiClient> Feed<T> {
             tasks: JoinSet::new(),
             search_bar: Input::default(),
             items_per_page: 5,
+            is_checking_library_health: false,
+            dead_mangas_found: None,
+            mangas_not_checked: 0,
+            show_mangadex_follows: false,
             is_typing: false,
+            search_all_tabs: false,
+            show_trash: false,
+            manga_trashed: None,
+            manga_to_trash: None,
+            trash_confirmation: ConfirmationState::default(),
+            order: HistoryOrder::default(),
+            reading_goal: None,
+            chapters_read_for_goal: None,
             api_client: None,
+            database: DatabaseActor::global().clone(),
         }
     }
 
+    #[cfg(test)]
+    fn with_database(mut self, database: DatabaseActor) -> Self {
+        self.database = database;
+        self
+    }
+
     pub fn is_typing(&self) -> bool {
-        self.is_typing
+        self.is_typing || self.manga_to_trash.is_some()
     }
 
     pub fn with_global_sender(mut self, sender: UnboundedSender<Events>) -> Self {
@@ -108,16 +180,39 @@ impl<T: ApiClient> Feed<T> {
         self
     }
 
+    pub fn with_mangadex_follows(mut self, show_mangadex_follows: bool) -> Self {
+        self.show_mangadex_follows = show_mangadex_follows;
+        self
+    }
+
+    pub fn with_reading_goal(mut self, reading_goal: Option<ReadingGoal>) -> Self {
+        self.reading_goal = reading_goal;
+        self
+    }
+
     fn render_history(&mut self, area: Rect, buf: &mut Buffer) {
         if self.state == FeedState::ErrorSearchingHistory {
-            Paragraph::new(
+            Paragraph::new(vec![
                 "Cannot get your reading history due to some issues, please check error logs"
                     .to_span()
-                    .style(*ERROR_STYLE),
-            )
+                    .style(*ERROR_STYLE)
+                    .into(),
+                Line::from(vec!["Report this bug ".into(), Span::raw("<E>").style(*INSTRUCTIONS_STYLE)]),
+            ])
             .render(area, buf);
             return;
         }
+
+        if self.manga_to_trash.is_some() {
+            StatefulWidget::render(
+                ConfirmationScreen::new("This manga will be moved to the trash"),
+                area,
+                buf,
+                &mut self.trash_confirmation,
+            );
+            return;
+        }
+
         match self.history.as_mut() {
             Some(history) => {
                 if self.state == FeedState::HistoryNotFound {
@@ -136,13 +231,40 @@ impl<T: ApiClient> Feed<T> {
         let [tabs_area, search_bar_area] = Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
 
         let selected_tab = match self.tabs {
-            FeedTabs::History => 0,
-            FeedTabs::PlantToRead => 1,
+            _ if self.show_trash => Some(0),
+            _ if self.search_all_tabs => None,
+            FeedTabs::History => Some(0),
+            FeedTabs::PlantToRead => Some(1),
+            FeedTabs::MangadexFollows => Some(2),
         };
 
-        let tabs_instructions = Line::from(vec!["Switch tab: ".into(), Span::raw("<tab>").style(*INSTRUCTIONS_STYLE)]);
+        let mut tabs_titles = vec!["Reading history", "Plan to Read"];
+        if self.show_mangadex_follows {
+            tabs_titles.push("MangaDex follows");
+        }
+        if self.show_trash {
+            tabs_titles = vec!["Trash"];
+        }
 
-        Tabs::new(vec!["Reading history", "Plan to Read"])
+        let tabs_instructions = Line::from(vec![
+            "Switch tab: ".into(),
+            Span::raw("<tab>").style(*INSTRUCTIONS_STYLE),
+            " Check library: ".into(),
+            Span::raw("<c>").style(*INSTRUCTIONS_STYLE),
+            if self.search_all_tabs { " Search this tab: ".into() } else { " Search all tabs: ".into() },
+            Span::raw("<a>").style(*INSTRUCTIONS_STYLE),
+            match self.order {
+                HistoryOrder::LastRead => " Sort by your rating: ".into(),
+                HistoryOrder::PersonalRating => " Sort by last read: ".into(),
+            },
+            Span::raw("<p>").style(*INSTRUCTIONS_STYLE),
+            if self.show_trash { " Close trash: ".into() } else { " Trash: ".into() },
+            Span::raw("<t>").style(*INSTRUCTIONS_STYLE),
+            if self.show_trash { " Restore: ".into() } else { " Move to trash: ".into() },
+            Span::raw(if self.show_trash { "<z>" } else { "<d>" }).style(*INSTRUCTIONS_STYLE),
+        ]);
+
+        Tabs::new(tabs_titles)
             .select(selected_tab)
             .block(Block::bordered().title(tabs_instructions))
             .highlight_style(Style::default().fg(Color::Yellow))
@@ -151,7 +273,13 @@ impl<T: ApiClient> Feed<T> {
         let input_help: Vec<Span<'_>> = if self.is_typing {
             vec!["Press ".into(), Span::raw("<Enter>").style(*INSTRUCTIONS_STYLE), " to search".into()]
         } else {
-            vec!["Press ".into(), Span::raw("<s>").style(*INSTRUCTIONS_STYLE), " to filter mangas".into()]
+            vec![
+                "Press ".into(),
+                Span::raw("<s>").style(*INSTRUCTIONS_STYLE),
+                " to filter mangas, ".into(),
+                Span::raw("#tag").style(*INSTRUCTIONS_STYLE),
+                " filters by tag".into(),
+            ]
         };
 
         render_search_bar(self.is_typing, input_help.into(), &self.search_bar, frame, search_bar_area);
@@ -182,15 +310,70 @@ impl<T: ApiClient> Feed<T> {
                     .style(*ERROR_STYLE),
             )
             .render(area, buf);
+            return;
+        }
+
+        if let Some((_, title)) = self.manga_trashed.as_ref() {
+            Paragraph::new(Line::from(vec![
+                format!("{title} was moved to the trash, undo: ").into(),
+                Span::raw("<z>").style(*INSTRUCTIONS_STYLE),
+            ]))
+            .render(area, buf);
+            return;
+        }
+
+        if self.is_checking_library_health {
+            Paragraph::new("Checking the mangas in your library still exist on mangadex, please wait".to_span()).render(area, buf);
+            return;
+        }
+
+        match (self.dead_mangas_found, self.mangas_not_checked) {
+            (Some(0), 0) => Paragraph::new("All the mangas in your library were found").render(area, buf),
+            (Some(amount), 0) => Paragraph::new(
+                format!("{amount} mangas were not found on mangadex, they may have been removed")
+                    .to_span()
+                    .style(*ERROR_STYLE),
+            )
+            .render(area, buf),
+            (Some(amount), not_checked) => Paragraph::new(
+                format!("{amount} mangas were not found on mangadex and {not_checked} could not be checked, try again later")
+                    .to_span()
+                    .style(*ERROR_STYLE),
+            )
+            .render(area, buf),
+            (None, _) => {},
         }
     }
 
+    fn render_reading_goal(&self, area: Rect, buf: &mut Buffer) {
+        let (Some(goal), Some(chapters_read)) = (self.reading_goal, self.chapters_read_for_goal) else {
+            return;
+        };
+
+        let color = if chapters_read >= goal.chapters { Color::Green } else { Color::Yellow };
+
+        LineGauge::default()
+            .block(
+                Block::bordered().title(format!("Reading goal: {chapters_read}/{} chapters this {}", goal.chapters, goal.period)),
+            )
+            .filled_style(Style::default().fg(color))
+            .line_set(THICK)
+            .ratio(goal.progress(chapters_read))
+            .render(area, buf);
+    }
+
     fn render_top_area(&mut self, area: Rect, frame: &mut Frame) {
         let [tabs_and_search_bar_area, searching_area] = Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
 
         self.render_tabs_and_search_bar(tabs_and_search_bar_area, frame);
 
+        let reading_goal_width = if self.reading_goal.is_some() { 30 } else { 0 };
+        let [searching_area, reading_goal_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Percentage(reading_goal_width)]).areas(searching_area);
+
         self.render_searching_status(searching_area, frame.buffer_mut());
+
+        self.render_reading_goal(reading_goal_area, frame.buffer_mut());
     }
 
     pub fn init_search(&mut self) {
@@ -198,7 +381,17 @@ impl<T: ApiClient> Feed<T> {
     }
 
     fn handle_key_events(&mut self, key_event: KeyEvent) {
-        if self.is_typing && self.state != FeedState::SearchingMangaPage {
+        if self.manga_to_trash.is_some() {
+            match key_event.code {
+                KeyCode::Enter => {
+                    self.local_action_tx.send(FeedActions::ConfirmMoveToTrash).ok();
+                },
+                KeyCode::Esc => {
+                    self.local_action_tx.send(FeedActions::CancelMoveToTrash).ok();
+                },
+                _ => {},
+            }
+        } else if self.is_typing && self.state != FeedState::SearchingMangaPage {
             match key_event.code {
                 KeyCode::Enter => {
                     self.local_event_tx.send(FeedEvents::SearchHistory).ok();
@@ -234,6 +427,29 @@ impl<T: ApiClient> Feed<T> {
                 KeyCode::Char('s') => {
                     self.local_action_tx.send(FeedActions::ToggleSearchBar).ok();
                 },
+                KeyCode::Char('a') => {
+                    self.local_action_tx.send(FeedActions::ToggleSearchAllTabs).ok();
+                },
+                KeyCode::Char('p') => {
+                    self.local_action_tx.send(FeedActions::ToggleOrder).ok();
+                },
+                KeyCode::Char('d') => {
+                    self.local_action_tx.send(FeedActions::MoveToTrash).ok();
+                },
+                KeyCode::Char('z') => {
+                    self.local_action_tx.send(FeedActions::RestoreFromTrash).ok();
+                },
+                KeyCode::Char('t') => {
+                    self.local_action_tx.send(FeedActions::ToggleTrash).ok();
+                },
+                KeyCode::Char('E') if self.state == FeedState::ErrorSearchingHistory => {
+                    self.local_action_tx.send(FeedActions::ReportBug).ok();
+                },
+                KeyCode::Char('c') => {
+                    if !self.is_checking_library_health {
+                        self.local_action_tx.send(FeedActions::CheckLibraryHealth).ok();
+                    }
+                },
                 _ => {},
             }
         }
@@ -241,7 +457,7 @@ impl<T: ApiClient> Feed<T> {
 
     pub fn tick(&mut self) {
         if let Some(loader_state) = self.loading_state.as_mut() {
-            loader_state.calc_next();
+            spin_loader(loader_state);
         }
         if let Ok(local_event) = self.local_event_rx.try_recv() {
             match local_event {
@@ -253,6 +469,13 @@ impl<T: ApiClient> Feed<T> {
                 FeedEvents::LoadRecentChapters(manga_id, maybe_chapters) => {
                     self.load_recent_chapters(manga_id, maybe_chapters);
                 },
+                FeedEvents::LoadDeadMangas(dead_mangas) => self.load_dead_mangas(dead_mangas),
+                FeedEvents::LibraryHealthChecked(health) => self.finish_library_health_check(health),
+                FeedEvents::MangaNotFound(manga_id) => self.flag_manga_not_found(manga_id),
+                FeedEvents::LoadReadingGoalProgress(chapters_read) => self.chapters_read_for_goal = Some(chapters_read),
+                FeedEvents::LoadLibraryMangaIds(manga_ids) => self.start_library_health_check(manga_ids),
+                FeedEvents::SaveDeadMangas(dead_mangas) => self.dead_mangas_saved(dead_mangas),
+                FeedEvents::MovedToTrash(id, title) => self.moved_to_trash(id, title),
             }
         }
     }
@@ -293,33 +516,137 @@ impl<T: ApiClient> Feed<T> {
         };
 
         let items_per_page = self.items_per_page;
+        let order = self.order;
 
-        let history_type: MangaHistoryType = self.tabs.into();
+        let in_trash = self.show_trash;
 
-        self.tasks.spawn(async move {
-            let binding = DBCONN.lock().unwrap();
-            let conn = binding.as_ref().unwrap();
+        let history_type = match self.tabs.history_type() {
+            _ if self.search_all_tabs || in_trash => None,
+            Some(history_type) => Some(history_type),
+            None => {
+                self.tasks.spawn(search_mangadex_follows(page, items_per_page, tx));
+                return;
+            },
+        };
+
+        let (search, tag) = SearchTerm::with_tag_filter(&search_term);
 
-            let maybe_reading_history = get_history(GetHistoryArgs {
+        let reading_goal = self.reading_goal;
+
+        let maybe_reading_history = self.database.run(move |conn| {
+            let history = get_history(GetHistoryArgs {
                 conn,
                 hist_type: history_type,
                 page,
-                search: SearchTerm::trimmed_lowercased(&search_term),
+                search,
+                tag,
                 items_per_page,
-            });
+                order,
+                in_trash,
+            })?;
+
+            let database = Database::new(conn);
+            let dead_mangas = database.get_dead_mangas().unwrap_or_default();
+            let chapters_read = reading_goal.map(|goal| database.chapters_read_since(goal.period_start(Utc::now().date_naive())));
+
+            Ok((history, dead_mangas, chapters_read))
+        });
 
-            match maybe_reading_history {
-                Ok(history) => {
+        self.tasks.spawn(async move {
+            match maybe_reading_history.await {
+                Ok((history, dead_mangas, chapters_read)) => {
                     tx.send(FeedEvents::LoadHistory(Some(history))).ok();
+                    tx.send(FeedEvents::LoadDeadMangas(dead_mangas)).ok();
+
+                    match chapters_read {
+                        Some(Ok(chapters_read)) => {
+                            tx.send(FeedEvents::LoadReadingGoalProgress(chapters_read)).ok();
+                        },
+                        Some(Err(e)) => write_to_error_log(ErrorType::Error(Box::new(e))),
+                        None => {},
+                    }
                 },
                 Err(e) => {
-                    write_to_error_log(ErrorType::Error(Box::new(e)));
+                    write_to_error_log(ErrorType::Error(e));
                     tx.send(FeedEvents::LoadHistory(None)).ok();
                 },
             }
         });
     }
 
+    fn load_dead_mangas(&mut self, dead_mangas: Vec<String>) {
+        if let Some(history) = self.history.as_mut() {
+            history.set_dead_mangas(&dead_mangas);
+        }
+    }
+
+    fn check_library_health(&mut self) {
+        self.database.query(
+            self.local_event_tx.clone(),
+            |conn| Ok(Database::new(conn).get_library_manga_ids()?),
+            FeedEvents::LoadLibraryMangaIds,
+        );
+    }
+
+    fn start_library_health_check(&mut self, manga_ids: Vec<String>) {
+        self.is_checking_library_health = true;
+        self.dead_mangas_found = None;
+        self.manga_trashed = None;
+
+        let api_client = self.api_client.as_ref().cloned().unwrap();
+        let tx = self.local_event_tx.clone();
+
+        // Not spawned in `tasks` since those are aborted when the history is searched again
+        tokio::spawn(check_library_health(api_client, manga_ids, LIBRARY_HEALTH_CHECK_DELAY, tx));
+    }
+
+    /// The mangas which could not be checked keep being flagged as dead if they were before
+    fn finish_library_health_check(&mut self, health: LibraryHealth) {
+        let LibraryHealth {
+            mut dead_mangas,
+            unknown_mangas,
+        } = health;
+
+        self.mangas_not_checked = unknown_mangas.len();
+
+        self.database.query(
+            self.local_event_tx.clone(),
+            move |conn| {
+                let database = Database::new(conn);
+
+                let previously_dead = database.get_dead_mangas()?.into_iter().filter(|id| unknown_mangas.contains(id));
+                dead_mangas.extend(previously_dead);
+
+                database.set_dead_mangas(&dead_mangas)?;
+                Ok(dead_mangas)
+            },
+            FeedEvents::SaveDeadMangas,
+        );
+    }
+
+    fn dead_mangas_saved(&mut self, dead_mangas: Vec<String>) {
+        self.is_checking_library_health = false;
+        self.dead_mangas_found = Some(dead_mangas.len());
+        self.load_dead_mangas(dead_mangas);
+    }
+
+    /// Instead of an error the manga is shown as not found so it can be searched by title
+    fn flag_manga_not_found(&mut self, manga_id: String) {
+        let id = manga_id.clone();
+        self.database.execute(move |conn| Database::new(conn).add_dead_manga(&id));
+
+        self.loading_state = None;
+        self.state = FeedState::DisplayingHistory;
+
+        if let Some(history) = self.history.as_mut() {
+            history
+                .mangas
+                .iter_mut()
+                .filter(|manga| manga.id == manga_id)
+                .for_each(|manga| manga.is_dead = true);
+        }
+    }
+
     fn search_next_page(&mut self) {
         if let Some(history) = self.history.as_mut() {
             if history.can_search_next_page(self.items_per_page as f64) {
@@ -365,15 +692,19 @@ impl<T: ApiClient> Feed<T> {
     }
 
     fn change_tab(&mut self) {
-        match self.tabs {
-            FeedTabs::History => self.tabs = FeedTabs::PlantToRead,
-            FeedTabs::PlantToRead => self.tabs = FeedTabs::History,
-        }
+        self.tabs = self.tabs.cycle(self.show_mangadex_follows);
     }
 
     pub fn go_to_manga_page(&mut self) {
         if let Some(history) = self.history.as_mut() {
             if let Some(currently_selected_manga) = history.get_current_manga_selected() {
+                // Its id no longer exists, so the best that can be done is searching it again by title
+                if currently_selected_manga.is_dead {
+                    let title = currently_selected_manga.title.clone();
+                    self.global_event_tx.as_ref().unwrap().send(Events::GoSearchMangasTitle(title)).ok();
+                    return;
+                }
+
                 self.state = FeedState::SearchingMangaPage;
                 let tx = self.global_event_tx.as_ref().cloned().unwrap();
                 let local_tx = self.local_event_tx.clone();
@@ -396,8 +727,86 @@ impl<T: ApiClient> Feed<T> {
         self.items_per_page = items_per_page;
     }
 
+    fn toggle_search_all_tabs(&mut self) {
+        self.search_all_tabs = !self.search_all_tabs;
+        self.history = None;
+        self.search_history();
+    }
+
+    /// The mangadex follows are not stored so they can't be sorted by rating
+    fn toggle_order(&mut self) {
+        self.order = self.order.toggle();
+        if self.tabs != FeedTabs::MangadexFollows || self.search_all_tabs || self.show_trash {
+            self.history = None;
+            self.search_history();
+        }
+    }
+
+    /// The mangadex follows are not stored so they can't be moved to the trash
+    fn move_to_trash(&mut self) {
+        if self.show_trash || (self.tabs == FeedTabs::MangadexFollows && !self.search_all_tabs) {
+            return;
+        }
+
+        let Some(manga) = self.history.as_ref().and_then(|history| history.get_current_manga_selected()) else {
+            return;
+        };
+
+        self.trash_confirmation = ConfirmationState::new(vec![manga.title.clone()]);
+        self.manga_to_trash = Some((manga.id.clone(), manga.title.clone()));
+    }
+
+    fn confirm_move_to_trash(&mut self) {
+        let Some((id, title)) = self.manga_to_trash.take() else {
+            return;
+        };
+
+        self.database.query(
+            self.local_event_tx.clone(),
+            move |conn| {
+                Database::new(conn).move_to_trash(&id)?;
+                Ok((id, title))
+            },
+            |(id, title)| FeedEvents::MovedToTrash(id, title),
+        );
+    }
+
+    fn moved_to_trash(&mut self, id: String, title: String) {
+        self.manga_trashed = Some((id, title));
+        self.search_history();
+    }
+
+    fn restore_from_trash(&mut self) {
+        let manga_id = if self.show_trash {
+            self.history
+                .as_ref()
+                .and_then(|history| history.get_current_manga_selected())
+                .map(|manga| manga.id.clone())
+        } else {
+            self.manga_trashed.take().map(|(id, _)| id)
+        };
+
+        let Some(manga_id) = manga_id else {
+            return;
+        };
+
+        // the history is searched after the manga is restored since the queries run in the order they are sent
+        self.database.execute(move |conn| Database::new(conn).restore_from_trash(&manga_id));
+        self.search_history();
+    }
+
+    fn toggle_trash(&mut self) {
+        self.show_trash = !self.show_trash;
+        self.manga_trashed = None;
+        self.history = None;
+        self.search_history();
+    }
+
     fn switch_tabs(&mut self) {
-        self.tabs = self.tabs.cycle();
+        self.search_all_tabs = false;
+        self.show_trash = false;
+        self.manga_trashed = None;
+        self.tabs = self.tabs.cycle(self.show_mangadex_follows);
         self.clean_up();
         self.search_history();
     }
@@ -418,6 +827,11 @@ impl<T: ApiClient> Feed<T> {
     fn get_history(&self) -> HistoryWidget {
         self.history.as_ref().cloned().unwrap()
     }
+
+    #[cfg(test)]
+    fn get_history_mut(&mut self) -> &mut HistoryWidget {
+        self.history.as_mut().unwrap()
+    }
 }
 
 impl<T: ApiClient> Component for Feed<T> {
@@ -442,6 +856,15 @@ impl<T: ApiClient> Component for Feed<T> {
             FeedActions::ScrollHistoryUp => self.select_previous_manga(),
             FeedActions::ScrollHistoryDown => self.select_next_manga(),
             FeedActions::SwitchTab => self.switch_tabs(),
+            FeedActions::CheckLibraryHealth => self.check_library_health(),
+            FeedActions::ToggleSearchAllTabs => self.toggle_search_all_tabs(),
+            FeedActions::ToggleOrder => self.toggle_order(),
+            FeedActions::MoveToTrash => self.move_to_trash(),
+            FeedActions::ConfirmMoveToTrash => self.confirm_move_to_trash(),
+            FeedActions::CancelMoveToTrash => self.manga_to_trash = None,
+            FeedActions::RestoreFromTrash => self.restore_from_trash(),
+            FeedActions::ToggleTrash => self.toggle_trash(),
+            FeedActions::ReportBug => report_bug(),
         }
     }
 
@@ -472,14 +895,21 @@ impl<T: ApiClient> Component for Feed<T> {
 #[cfg(test)]
 mod tests {
     use core::panic;
+    use std::time::Duration;
 
+    use httpmock::Method::GET;
+    use httpmock::MockServer;
     use pretty_assertions::{assert_eq, assert_ne};
+    use rusqlite::Connection;
+    use serde_json::json;
 
     use self::mpsc::unbounded_channel;
     use super::*;
     use crate::backend::api_responses::ChapterData;
     use crate::backend::database::MangaHistory;
     use crate::backend::fetch::fake_api_client::MockMangadexClient;
+    use crate::backend::fetch::MangadexClient;
+    use crate::config::GoalPeriod;
     use crate::view::widgets::press_key;
 
     fn history_data() -> MangaHistoryResponse {
@@ -709,6 +1139,28 @@ mod tests {
         assert_eq!(feed_page.tabs, FeedTabs::History);
     }
 
+    #[test]
+    fn mangadex_follows_tab_is_only_available_when_logged_in() {
+        let mut feed_page: Feed<MockMangadexClient> = Feed::new();
+
+        feed_page.change_tab();
+        feed_page.change_tab();
+
+        assert_eq!(feed_page.tabs, FeedTabs::History);
+
+        let mut feed_page: Feed<MockMangadexClient> = Feed::new().with_mangadex_follows(true);
+
+        feed_page.change_tab();
+        feed_page.change_tab();
+
+        assert_eq!(feed_page.tabs, FeedTabs::MangadexFollows);
+        assert!(feed_page.tabs.history_type().is_none());
+
+        feed_page.change_tab();
+
+        assert_eq!(feed_page.tabs, FeedTabs::History);
+    }
+
     #[tokio::test]
     async fn search_history_in_database() {
         let mut feed_page: Feed<MockMangadexClient> = Feed::new();
@@ -909,4 +1361,301 @@ mod tests {
 
         assert_eq!(feed_page.state, FeedState::MangaPageNotFound);
     }
+
+    #[test]
+    fn sends_event_to_check_library_health_on_key_press() {
+        let mut feed_page: Feed<MockMangadexClient> = Feed::new();
+
+        press_key(&mut feed_page, KeyCode::Char('c'));
+
+        let action = feed_page.local_action_rx.blocking_recv().expect("no action was sent");
+
+        assert_eq!(FeedActions::CheckLibraryHealth, action);
+    }
+
+    #[tokio::test]
+    async fn mangas_the_library_health_check_could_not_check_are_not_flagged_as_dead() {
+        let server = MockServer::start_async().await;
+
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path_contains("/manga/removed_manga");
+                then.status(404);
+            })
+            .await;
+
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path_contains("/manga/rate_limited_manga");
+                then.status(429).header("Retry-After", "0");
+            })
+            .await;
+
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path_contains("/manga/existing_manga");
+                then.status(200).json_body(json!({}));
+            })
+            .await;
+
+        let api_client = MangadexClient::new(server.base_url().parse().unwrap(), server.base_url().parse().unwrap());
+        let (tx, mut rx) = unbounded_channel();
+
+        let manga_ids = vec!["removed_manga".to_string(), "rate_limited_manga".to_string(), "existing_manga".to_string()];
+
+        check_library_health(api_client, manga_ids, Duration::ZERO, tx).await;
+
+        let expected = LibraryHealth {
+            dead_mangas: vec!["removed_manga".to_string()],
+            unknown_mangas: vec!["rate_limited_manga".to_string()],
+        };
+
+        assert_eq!(Some(FeedEvents::LibraryHealthChecked(expected)), rx.recv().await);
+    }
+
+    #[tokio::test]
+    async fn mangas_which_could_not_be_checked_stay_dead_once_the_library_health_check_finishes() {
+        let database = DatabaseActor::spawn(|| {
+            let connection = Connection::open_in_memory()?;
+            Database::new(&connection).setup()?;
+            Ok(connection)
+        });
+
+        let mut feed_page: Feed<MockMangadexClient> = Feed::new().with_database(database.clone());
+
+        database
+            .run(|conn| {
+                for id in ["removed_manga", "rate_limited_manga", "existing_manga"] {
+                    conn.execute("INSERT INTO mangas(id, title) VALUES(?1, ?2)", [id, "some_title"])?;
+                }
+                Database::new(conn).set_dead_mangas(&["rate_limited_manga".to_string(), "existing_manga".to_string()])
+            })
+            .await
+            .unwrap();
+
+        feed_page.finish_library_health_check(LibraryHealth {
+            dead_mangas: vec!["removed_manga".to_string()],
+            unknown_mangas: vec!["rate_limited_manga".to_string()],
+        });
+
+        assert!(database.flush(Duration::from_secs(5)));
+        feed_page.tick();
+
+        assert!(!feed_page.is_checking_library_health);
+        assert_eq!(Some(2), feed_page.dead_mangas_found);
+        assert_eq!(1, feed_page.mangas_not_checked);
+
+        let mut dead_mangas = database.run(|conn| Database::new(conn).get_dead_mangas()).await.unwrap();
+        dead_mangas.sort();
+
+        assert_eq!(vec!["rate_limited_manga".to_string(), "removed_manga".to_string()], dead_mangas);
+    }
+
+    #[test]
+    fn it_flags_manga_as_not_found_instead_of_showing_an_error() {
+        let mut feed_page: Feed<MockMangadexClient> = Feed::new();
+
+        let mut history = history_data();
+
+        history.mangas = vec![
+            MangaHistory {
+                id: "removed_manga".to_string(),
+                title: "some title".to_string(),
+                category: None,
+                personal_rating: None,
+            },
+            MangaHistory {
+                id: "another_manga".to_string(),
+                title: "another title".to_string(),
+                category: None,
+                personal_rating: None,
+            },
+        ];
+
+        feed_page.load_history(Some(history));
+        feed_page.state = FeedState::SearchingMangaPage;
+
+        feed_page.flag_manga_not_found("removed_manga".to_string());
+
+        assert_eq!(FeedState::DisplayingHistory, feed_page.state);
+        assert!(feed_page.get_history().mangas[0].is_dead);
+        assert!(!feed_page.get_history().mangas[1].is_dead);
+    }
+
+    #[tokio::test]
+    async fn searches_dead_manga_by_title_instead_of_going_to_manga_page() {
+        let (tx, mut rx) = unbounded_channel::<Events>();
+        let mut feed_page: Feed<MockMangadexClient> = Feed::new().with_global_sender(tx).with_api_client(MockMangadexClient::new());
+
+        let mut history = history_data();
+
+        history.mangas = vec![MangaHistory {
+            id: "dead_manga".to_string(),
+            title: "some title".to_string(),
+            category: None,
+            personal_rating: None,
+        }];
+
+        feed_page.load_history(Some(history));
+
+        feed_page.load_dead_mangas(vec!["dead_manga".to_string()]);
+
+        assert!(feed_page.get_history().mangas[0].is_dead);
+
+        feed_page.get_history_mut().state.select(Some(0));
+
+        feed_page.go_to_manga_page();
+
+        let event_sent = rx.recv().await.expect("no event was sent");
+
+        assert_eq!(Events::GoSearchMangasTitle("some title".to_string()), event_sent);
+    }
+
+    #[tokio::test]
+    async fn it_searches_all_tabs_until_the_tab_is_switched() {
+        let mut feed_page: Feed<MockMangadexClient> = Feed::new();
+
+        press_key(&mut feed_page, KeyCode::Char('a'));
+        let action = feed_page.local_action_rx.recv().await.unwrap();
+
+        assert_eq!(FeedActions::ToggleSearchAllTabs, action);
+
+        feed_page.update(action);
+
+        assert!(feed_page.search_all_tabs);
+        assert_eq!(FeedState::SearchingHistory, feed_page.state);
+
+        feed_page.update(FeedActions::SwitchTab);
+
+        assert!(!feed_page.search_all_tabs);
+        assert_eq!(FeedTabs::PlantToRead, feed_page.tabs);
+    }
+
+    #[tokio::test]
+    async fn it_moves_the_manga_selected_to_the_trash_and_can_undo_it() {
+        let database = DatabaseActor::spawn(|| {
+            let connection = Connection::open_in_memory()?;
+            Database::new(&connection).setup()?;
+            Ok(connection)
+        });
+
+        let mut feed_page: Feed<MockMangadexClient> = Feed::new().with_database(database);
+
+        render_history_and_select(&mut feed_page);
+
+        press_key(&mut feed_page, KeyCode::Char('d'));
+        let action = feed_page.local_action_rx.recv().await.unwrap();
+
+        assert_eq!(FeedActions::MoveToTrash, action);
+
+        feed_page.update(action);
+
+        assert!(feed_page.is_typing(), "the user is asked before moving the manga to the trash");
+        assert!(feed_page.manga_trashed.is_none());
+
+        press_key(&mut feed_page, KeyCode::Esc);
+        let action = feed_page.local_action_rx.recv().await.unwrap();
+
+        assert_eq!(FeedActions::CancelMoveToTrash, action);
+
+        feed_page.update(action);
+
+        assert!(feed_page.manga_to_trash.is_none());
+
+        feed_page.update(FeedActions::MoveToTrash);
+        press_key(&mut feed_page, KeyCode::Enter);
+
+        let action = feed_page.local_action_rx.recv().await.unwrap();
+
+        assert_eq!(FeedActions::ConfirmMoveToTrash, action);
+
+        feed_page.update(action);
+
+        loop {
+            match feed_page.local_event_rx.recv().await {
+                Some(FeedEvents::MovedToTrash(id, title)) => break feed_page.moved_to_trash(id, title),
+                Some(_) => continue,
+                None => panic!("the manga was not moved to the trash"),
+            }
+        }
+
+        assert!(feed_page.manga_to_trash.is_none());
+        assert!(feed_page.manga_trashed.is_some());
+
+        press_key(&mut feed_page, KeyCode::Char('z'));
+        let action = feed_page.local_action_rx.recv().await.unwrap();
+
+        assert_eq!(FeedActions::RestoreFromTrash, action);
+
+        feed_page.update(action);
+
+        assert!(feed_page.manga_trashed.is_none());
+    }
+
+    #[tokio::test]
+    async fn it_shows_the_trash_with_t() {
+        let mut feed_page: Feed<MockMangadexClient> = Feed::new();
+
+        press_key(&mut feed_page, KeyCode::Char('t'));
+        let action = feed_page.local_action_rx.recv().await.unwrap();
+
+        assert_eq!(FeedActions::ToggleTrash, action);
+
+        feed_page.update(action);
+
+        assert!(feed_page.show_trash);
+        assert_eq!(FeedState::SearchingHistory, feed_page.state);
+
+        // Nothing can be moved to the trash while looking at it
+        render_history_and_select(&mut feed_page);
+        feed_page.update(FeedActions::MoveToTrash);
+
+        assert!(feed_page.manga_to_trash.is_none());
+        assert!(feed_page.manga_trashed.is_none());
+
+        feed_page.update(FeedActions::SwitchTab);
+
+        assert!(!feed_page.show_trash);
+    }
+
+    #[tokio::test]
+    async fn it_sorts_the_history_by_personal_rating_with_p() {
+        let mut feed_page: Feed<MockMangadexClient> = Feed::new();
+
+        press_key(&mut feed_page, KeyCode::Char('p'));
+        let action = feed_page.local_action_rx.recv().await.unwrap();
+
+        assert_eq!(FeedActions::ToggleOrder, action);
+
+        feed_page.update(action);
+
+        assert_eq!(HistoryOrder::PersonalRating, feed_page.order);
+        assert_eq!(FeedState::SearchingHistory, feed_page.state);
+
+        feed_page.update(FeedActions::ToggleOrder);
+
+        assert_eq!(HistoryOrder::LastRead, feed_page.order);
+    }
+
+    #[test]
+    fn it_shows_the_progress_of_the_reading_goal() {
+        let mut feed_page: Feed<MockMangadexClient> = Feed::new().with_reading_goal(Some(ReadingGoal {
+            chapters: 20,
+            period: GoalPeriod::Week,
+        }));
+
+        feed_page.local_event_tx.send(FeedEvents::LoadReadingGoalProgress(5)).ok();
+        feed_page.tick();
+
+        assert_eq!(Some(5), feed_page.chapters_read_for_goal);
+
+        let area = Rect::new(0, 0, 50, 3);
+        let mut buf = Buffer::empty(area);
+
+        feed_page.render_reading_goal(area, &mut buf);
+
+        let rendered: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+
+        assert!(rendered.contains("Reading goal: 5/20 chapters this week"));
+    }
 }
@@ -7,9 +7,10 @@ use image::io::Reader;
 use image::DynamicImage;
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Margin, Rect};
-use ratatui::style::Stylize;
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::symbols::line::THICK;
 use ratatui::text::{Line, Span, ToSpan};
-use ratatui::widgets::{Block, List, StatefulWidget, Widget};
+use ratatui::widgets::{Block, Clear, LineGauge, List, Paragraph, StatefulWidget, Widget};
 use ratatui::Frame;
 use ratatui_image::picker::Picker;
 use ratatui_image::protocol::Protocol;
@@ -18,16 +19,27 @@ use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use tokio::task::JoinSet;
 
 use crate::backend::api_responses::SearchMangaResponse;
+use crate::backend::database::{ChapterBookmarked, ContinueReading, Database, DownloadAllJob, HistoryToPrune};
+use crate::backend::database_actor::DatabaseActor;
 use crate::backend::error_log::{write_to_error_log, ErrorType};
-use crate::backend::fetch::{ApiClient, MangadexClient};
+use crate::backend::fetch::{ApiClient, CustomListMangas, MangadexClient};
+use crate::backend::filter::Languages;
 use crate::backend::tui::Events;
 use crate::common::ImageState;
 use crate::global::INSTRUCTIONS_STYLE;
-use crate::utils::search_manga_cover;
+use crate::utils::{centered_rect, decode_bytes_to_image, search_manga_cover};
+use crate::view::app::MangaToRead;
+use crate::view::pages::manga::FetchChapterBookmarked;
+use crate::view::pages::reader::ChapterToRead;
+use crate::view::tasks::search::{not_interested_mangas, search_manga_by_id};
+use crate::view::widgets::confirmation::{ConfirmationScreen, ConfirmationState};
 use crate::view::widgets::home::{CarrouselItem, CarrouselState, PopularMangaCarrousel, RecentlyAddedCarrousel};
 use crate::view::widgets::search::MangaItem;
 use crate::view::widgets::{Component, ImageHandler};
 
+/// Each one is read by pressing its number, so there can't be more than 9
+static CONTINUE_READING_ITEMS: u32 = 5;
+
 #[derive(PartialEq, Eq)]
 pub enum HomeState {
     Unused,
@@ -45,6 +57,13 @@ pub enum HomeEvents {
     LoadRecentlyAddedMangas(Option<SearchMangaResponse>),
     LoadCover(Option<DynamicImage>, String),
     LoadRecentlyAddedMangasCover(Option<DynamicImage>, String),
+    ResumeReadingSession(ChapterToRead, MangaToRead),
+    ResumeDownloadAll(Box<MangaItem>, Languages),
+    SearchCustomLists,
+    /// The index of the list in `custom_lists`
+    LoadCustomList(usize, Option<CustomListMangas>),
+    LoadCustomListCover(usize, Option<DynamicImage>, String),
+    LoadContinueReading(Vec<ContinueReading>),
 }
 
 impl ImageHandler for HomeEvents {
@@ -67,11 +86,32 @@ pub enum HomeActions {
     SelectPreviousRecentlyAddedManga,
     SupportMangadex,
     SupportProject,
+    ResumeReadingSession,
+    ResumeDownloadAll,
+    SelectNextList,
+    /// The index of the manga in `continue_reading`
+    ContinueReading(usize),
+    ScrollDownHistoryToPrune,
+    ScrollUpHistoryToPrune,
+    PruneHistory,
+    SkipPruningHistory,
+}
+
+/// A mangadex list like the staff picks, shown in the same area as the recently added mangas
+struct CustomListRow {
+    id: String,
+    name: String,
+    carrousel: RecentlyAddedCarrousel,
+    image_state: ImageState,
 }
 
 pub struct Home {
     carrousel_popular_mangas: PopularMangaCarrousel,
     carrousel_recently_added: RecentlyAddedCarrousel,
+    custom_list_ids: Vec<String>,
+    custom_lists: Vec<CustomListRow>,
+    /// 0 is the recently added mangas, the rest are the custom lists
+    selected_list: usize,
     state: HomeState,
     pub global_event_tx: Option<UnboundedSender<Events>>,
     pub local_action_tx: UnboundedSender<HomeActions>,
@@ -83,6 +123,14 @@ pub struct Home {
     popular_manga_carrousel_state: ImageState,
     recently_added_manga_state: ImageState,
     picker: Option<Picker>,
+    /// The chapter that was being read when the app was not closed properly
+    session_to_restore: Option<ChapterBookmarked>,
+    /// The manga whose chapters were being downloaded when the app was closed
+    download_to_resume: Option<DownloadAllJob>,
+    /// The mangas read most recently with the chapter they were left at
+    continue_reading: Vec<ContinueReading>,
+    /// Asked on startup when `prune_history_after_months` is set, nothing is moved to the trash until the user confirms
+    history_to_prune: Option<(HistoryToPrune, ConfirmationState)>,
     tasks: JoinSet<()>,
 }
 
@@ -90,14 +138,32 @@ impl Component for Home {
     type Actions = HomeActions;
 
     fn render(&mut self, area: Rect, frame: &mut Frame<'_>) {
-        let layout = Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).margin(1);
+        let continue_reading_height = if self.can_continue_reading() { 4 } else { 0 };
+        let layout =
+            Layout::vertical([Constraint::Length(continue_reading_height), Constraint::Percentage(50), Constraint::Fill(1)])
+                .margin(1);
         let buf = frame.buffer_mut();
 
-        let [carrousel_popular_mangas_area, latest_updates_area] = layout.areas(area);
+        let [continue_reading_area, carrousel_popular_mangas_area, latest_updates_area] = layout.areas(area);
+
+        if self.can_continue_reading() {
+            self.render_continue_reading(continue_reading_area, buf);
+        }
 
         self.render_popular_mangas_carrousel(carrousel_popular_mangas_area, buf);
 
         self.render_recently_added_mangas_area(latest_updates_area, buf);
+
+        if let Some((history, state)) = self.history_to_prune.as_mut() {
+            let popup_area = centered_rect(area, 60, 60);
+            Clear.render(popup_area, buf);
+            StatefulWidget::render(
+                ConfirmationScreen::new(&format!("Not read in {} months, moving these to the trash", history.months)),
+                popup_area,
+                buf,
+                state,
+            );
+        }
     }
 
     fn update(&mut self, action: Self::Actions) {
@@ -107,12 +173,13 @@ impl Component for Home {
             },
             HomeActions::SelectPreviousPopularManga => self.carrousel_popular_mangas.previous_item(),
             HomeActions::GoToPopularMangaPage => self.go_to_manga_page_popular(),
-            HomeActions::SelectNextRecentlyAddedManga => self.carrousel_recently_added.select_next(),
-            HomeActions::SelectPreviousRecentlyAddedManga => self.carrousel_recently_added.select_previous(),
+            HomeActions::SelectNextRecentlyAddedManga => self.selected_carrousel_mut().select_next(),
+            HomeActions::SelectPreviousRecentlyAddedManga => self.selected_carrousel_mut().select_previous(),
+            HomeActions::SelectNextList => self.select_next_list(),
             HomeActions::GoToRecentlyAddedMangaPage => {
-                if let Some(item) = self.carrousel_recently_added.get_current_selected_manga() {
+                if let Some(item) = self.selected_carrousel().get_current_selected_manga() {
                     self.global_event_tx
-                        .as_mut()
+                        .as_ref()
                         .unwrap()
                         .send(Events::GoToMangaPage(MangaItem::new(item.manga.clone())))
                         .ok();
@@ -120,6 +187,21 @@ impl Component for Home {
             },
             HomeActions::SupportProject => self.support_project(),
             HomeActions::SupportMangadex => self.support_mangadex(),
+            HomeActions::ResumeReadingSession => self.resume_reading_session(),
+            HomeActions::ResumeDownloadAll => self.resume_download_all(),
+            HomeActions::ContinueReading(index) => self.continue_reading(index),
+            HomeActions::ScrollDownHistoryToPrune => {
+                if let Some((_, state)) = self.history_to_prune.as_mut() {
+                    state.scroll_down();
+                }
+            },
+            HomeActions::ScrollUpHistoryToPrune => {
+                if let Some((_, state)) = self.history_to_prune.as_mut() {
+                    state.scroll_up();
+                }
+            },
+            HomeActions::PruneHistory => self.prune_history(),
+            HomeActions::SkipPruningHistory => self.history_to_prune = None,
         }
     }
 
@@ -127,6 +209,9 @@ impl Component for Home {
         self.tasks.abort_all();
         self.carrousel_popular_mangas.items = vec![];
         self.carrousel_recently_added.items = vec![];
+        self.custom_lists = vec![];
+        self.continue_reading = vec![];
+        self.selected_list = 0;
         self.support_image = None;
         self.state = HomeState::Unused;
         self.recently_added_manga_state = ImageState::default();
@@ -150,6 +235,9 @@ impl Home {
         Self {
             carrousel_popular_mangas: PopularMangaCarrousel::default(),
             carrousel_recently_added: RecentlyAddedCarrousel::new(picker.is_some()),
+            custom_list_ids: vec![],
+            custom_lists: vec![],
+            selected_list: 0,
             state: HomeState::Unused,
             global_event_tx: None,
             local_event_tx,
@@ -161,6 +249,10 @@ impl Home {
             picker,
             popular_manga_carrousel_state: ImageState::default(),
             recently_added_manga_state: ImageState::default(),
+            session_to_restore: None,
+            download_to_resume: None,
+            history_to_prune: None,
+            continue_reading: vec![],
             tasks: JoinSet::new(),
         }
     }
@@ -170,6 +262,142 @@ impl Home {
         self
     }
 
+    pub fn with_session_to_restore(mut self, session: Option<ChapterBookmarked>) -> Self {
+        self.session_to_restore = session;
+        self
+    }
+
+    pub fn with_download_to_resume(mut self, job: Option<DownloadAllJob>) -> Self {
+        self.download_to_resume = job;
+        self
+    }
+
+    pub fn with_history_to_prune(mut self, history: Option<HistoryToPrune>) -> Self {
+        self.history_to_prune = history.map(|history| {
+            let state = ConfirmationState::new(history.titles.clone());
+            (history, state)
+        });
+        self
+    }
+
+    /// While the user is asked to confirm pruning the history the keys pressed are only used for that
+    pub fn is_typing(&self) -> bool {
+        self.history_to_prune.is_some()
+    }
+
+    fn prune_history(&mut self) {
+        let Some((history, _)) = self.history_to_prune.take() else {
+            return;
+        };
+
+        DatabaseActor::global().execute(move |conn| {
+            Database::new(conn).prune_history(history.months)?;
+            Ok(())
+        });
+    }
+
+    pub fn with_custom_lists(mut self, list_ids: Vec<String>) -> Self {
+        self.custom_list_ids = list_ids;
+        self
+    }
+
+    fn selected_carrousel(&self) -> &RecentlyAddedCarrousel {
+        match self.selected_list.checked_sub(1).and_then(|index| self.custom_lists.get(index)) {
+            Some(list) => &list.carrousel,
+            None => &self.carrousel_recently_added,
+        }
+    }
+
+    fn selected_carrousel_mut(&mut self) -> &mut RecentlyAddedCarrousel {
+        match self.selected_list.checked_sub(1).and_then(|index| self.custom_lists.get_mut(index)) {
+            Some(list) => &mut list.carrousel,
+            None => &mut self.carrousel_recently_added,
+        }
+    }
+
+    fn select_next_list(&mut self) {
+        self.selected_list = (self.selected_list + 1) % (self.custom_lists.len() + 1);
+    }
+
+    /// Reading requires image support, so the session can only be resumed if there is a picker
+    fn can_resume_reading_session(&self) -> bool {
+        self.session_to_restore.is_some() && self.picker.is_some()
+    }
+
+    fn resume_reading_session(&mut self) {
+        if !self.can_resume_reading_session() {
+            return;
+        }
+
+        let session = self.session_to_restore.take().unwrap();
+        let tx = self.local_event_tx.clone();
+
+        self.tasks.spawn(async move {
+            match MangadexClient::global().fetch_chapter_bookmarked(session).await {
+                Ok((chapter, manga)) => {
+                    tx.send(HomeEvents::ResumeReadingSession(chapter, manga)).ok();
+                },
+                Err(e) => write_to_error_log(ErrorType::Error(e)),
+            }
+        });
+    }
+
+    /// Like the reading session, reading requires image support
+    fn can_continue_reading(&self) -> bool {
+        !self.continue_reading.is_empty() && self.picker.is_some()
+    }
+
+    pub fn load_continue_reading(&mut self) {
+        DatabaseActor::global().query(
+            self.local_event_tx.clone(),
+            |conn| Ok(Database::new(conn).get_continue_reading(CONTINUE_READING_ITEMS)?),
+            HomeEvents::LoadContinueReading,
+        );
+    }
+
+    fn continue_reading(&mut self, index: usize) {
+        if !self.can_continue_reading() {
+            return;
+        }
+
+        let Some(item) = self.continue_reading.get(index).cloned() else {
+            return;
+        };
+
+        let tx = self.local_event_tx.clone();
+
+        self.tasks.spawn(async move {
+            let page_to_resume = item.page_to_resume();
+            match MangadexClient::global().fetch_chapter_bookmarked(item.chapter).await {
+                Ok((mut chapter, manga)) => {
+                    if chapter.num_page_bookmarked.is_none() {
+                        chapter.page_to_resume = page_to_resume;
+                    }
+                    tx.send(HomeEvents::ResumeReadingSession(chapter, manga)).ok();
+                },
+                Err(e) => write_to_error_log(ErrorType::Error(e)),
+            }
+        });
+    }
+
+    fn resume_download_all(&mut self) {
+        let Some(job) = self.download_to_resume.take() else {
+            return;
+        };
+
+        let tx = self.local_event_tx.clone();
+        let language = Languages::try_from_iso_code(&job.translated_language).unwrap_or_default();
+
+        self.tasks.spawn(async move {
+            match search_manga_by_id(MangadexClient::global().clone(), &job.manga_id).await {
+                Ok(manga) => {
+                    tx.send(HomeEvents::ResumeDownloadAll(Box::new(MangaItem::new(manga)), language)).ok();
+                },
+                Err(e) => write_to_error_log(ErrorType::Error(e)),
+            }
+        });
+    }
+
     pub fn render_popular_mangas_carrousel(&mut self, area: Rect, buf: &mut Buffer) {
         let inner = area.inner(Margin {
             horizontal: 1,
@@ -218,9 +446,16 @@ impl Home {
     }
 
     pub fn init_search(&mut self) {
+        self.load_continue_reading();
+
         self.local_event_tx.send(HomeEvents::SearchPopularNewMangas).ok();
 
         self.local_event_tx.send(HomeEvents::SearchRecentlyAddedMangas).ok();
+
+        if !self.custom_list_ids.is_empty() {
+            self.local_event_tx.send(HomeEvents::SearchCustomLists).ok();
+        }
+
         if self.picker.is_some() {
             self.local_event_tx.send(HomeEvents::SearchSupportImage).ok();
         }
@@ -264,6 +499,7 @@ impl Home {
     pub fn tick(&mut self) {
         self.carrousel_popular_mangas.tick();
         self.carrousel_recently_added.tick();
+        self.custom_lists.iter_mut().for_each(|list| list.carrousel.tick());
         if let Ok(local_event) = self.local_event_rx.try_recv() {
             match local_event {
                 HomeEvents::SearchPopularMangasCover => self.search_popular_mangas_cover(),
@@ -286,6 +522,20 @@ impl Home {
                 },
                 HomeEvents::SearchSupportImage => self.search_support_image(),
                 HomeEvents::LoadSupportImage(image) => self.load_support_image(image),
+                HomeEvents::ResumeReadingSession(chapter, manga) => {
+                    self.global_event_tx.as_ref().unwrap().send(Events::ReadChapter(chapter, manga)).ok();
+                },
+                HomeEvents::ResumeDownloadAll(manga, language) => {
+                    self.global_event_tx
+                        .as_ref()
+                        .unwrap()
+                        .send(Events::ResumeDownloadAll(*manga, language))
+                        .ok();
+                },
+                HomeEvents::SearchCustomLists => self.search_custom_lists(),
+                HomeEvents::LoadContinueReading(continue_reading) => self.continue_reading = continue_reading,
+                HomeEvents::LoadCustomList(index, maybe_list) => self.load_custom_list(index, maybe_list),
+                HomeEvents::LoadCustomListCover(index, maybe_cover, id) => self.load_custom_list_cover(index, maybe_cover, id),
             }
         }
     }
@@ -303,13 +553,13 @@ impl Home {
     }
 
     fn load_popular_manga_cover(&mut self, maybe_cover: Option<DynamicImage>, id: String) {
-        if let Some(cover) = maybe_cover {
-            if let Some(picker) = self.picker.as_mut() {
-                let fixed_protocol =
-                    picker.new_protocol(cover, self.popular_manga_carrousel_state.get_img_area(), Resize::Fit(None));
-                if let Ok(protocol) = fixed_protocol {
-                    self.popular_manga_carrousel_state.insert_manga(protocol, id);
-                }
+        if let Some(picker) = self.picker.as_mut() {
+            let fixed_protocol = maybe_cover
+                .map(|cover| picker.new_protocol(cover, self.popular_manga_carrousel_state.get_img_area(), Resize::Fit(None)));
+
+            match fixed_protocol {
+                Some(Ok(protocol)) => self.popular_manga_carrousel_state.insert_manga(protocol, id),
+                _ => self.popular_manga_carrousel_state.set_failed(id),
             }
         }
     }
@@ -322,6 +572,7 @@ impl Home {
             match response {
                 Ok(res) => {
                     if let Ok(data) = res.json::<SearchMangaResponse>().await {
+                        let data = data.without_mangas(&not_interested_mangas().await);
                         if data.data.is_empty() {
                             tx.send(HomeEvents::LoadPopularMangas(None)).ok();
                         } else {
@@ -347,17 +598,12 @@ impl Home {
                     let file_name = file_name.clone();
                     self.tasks.spawn(async move {
                         let response = MangadexClient::global().get_cover_for_manga(&manga_id, &file_name).await;
-                        if let Ok(res) = response {
-                            if let Ok(bytes) = res.bytes().await {
-                                let dyn_img = Reader::new(Cursor::new(bytes)).with_guessed_format().unwrap();
+                        let cover = match response {
+                            Ok(res) => res.bytes().await.ok().and_then(|bytes| decode_bytes_to_image(bytes).ok()),
+                            Err(_) => None,
+                        };
 
-                                let maybe_decoded = dyn_img.decode();
-
-                                if let Ok(decoded) = maybe_decoded {
-                                    tx.send(HomeEvents::LoadCover(Some(decoded), manga_id)).ok();
-                                }
-                            }
-                        }
+                        tx.send(HomeEvents::LoadCover(cover, manga_id)).ok();
                     });
                 },
                 None => {
@@ -374,7 +620,8 @@ impl Home {
             let response = MangadexClient::global().get_recently_added().await;
             match response {
                 Ok(mangas) => {
-                    if let Ok(data) = mangas.json().await {
+                    if let Ok(data) = mangas.json::<SearchMangaResponse>().await {
+                        let data = data.without_mangas(&not_interested_mangas().await);
                         tx.send(HomeEvents::LoadRecentlyAddedMangas(Some(data))).ok();
                     }
                 },
@@ -416,13 +663,104 @@ impl Home {
     }
 
     fn load_recently_added_mangas_cover(&mut self, maybe_cover: Option<DynamicImage>, id: String) {
-        if let Some(cover) = maybe_cover {
-            if let Some(picker) = self.picker.as_mut() {
-                let fixed_protocol = picker.new_protocol(cover, self.recently_added_manga_state.get_img_area(), Resize::Fit(None));
+        if let Some(picker) = self.picker.as_mut() {
+            let fixed_protocol = maybe_cover
+                .map(|cover| picker.new_protocol(cover, self.recently_added_manga_state.get_img_area(), Resize::Fit(None)));
 
-                if let Ok(protocol) = fixed_protocol {
-                    self.recently_added_manga_state.insert_manga(protocol, id);
+            match fixed_protocol {
+                Some(Ok(protocol)) => self.recently_added_manga_state.insert_manga(protocol, id),
+                _ => self.recently_added_manga_state.set_failed(id),
+            }
+        }
+    }
+
+    fn search_custom_lists(&mut self) {
+        self.custom_lists = self
+            .custom_list_ids
+            .iter()
+            .map(|id| CustomListRow {
+                id: id.clone(),
+                name: "Mangadex list".to_string(),
+                carrousel: RecentlyAddedCarrousel::new(self.picker.is_some()),
+                image_state: ImageState::default(),
+            })
+            .collect();
+
+        for (index, list) in self.custom_lists.iter().enumerate() {
+            let list_id = list.id.clone();
+            let tx = self.local_event_tx.clone();
+            self.tasks.spawn(async move {
+                match MangadexClient::global().get_custom_list(&list_id).await {
+                    Ok(custom_list) => {
+                        tx.send(HomeEvents::LoadCustomList(index, Some(custom_list))).ok();
+                    },
+                    Err(e) => {
+                        write_to_error_log(ErrorType::Error(e));
+                        tx.send(HomeEvents::LoadCustomList(index, None)).ok();
+                    },
                 }
+            });
+        }
+    }
+
+    fn load_custom_list(&mut self, index: usize, maybe_list: Option<CustomListMangas>) {
+        let can_display_images = self.picker.is_some();
+        let Some(list) = self.custom_lists.get_mut(index) else {
+            return;
+        };
+
+        match maybe_list {
+            Some(custom_list) if !custom_list.mangas.data.is_empty() => {
+                list.name = custom_list.name;
+                list.carrousel = RecentlyAddedCarrousel::from_response(custom_list.mangas, can_display_images);
+                if can_display_images {
+                    self.search_custom_list_covers(index);
+                }
+            },
+            Some(custom_list) => {
+                list.name = custom_list.name;
+                list.carrousel.state = CarrouselState::NotFound;
+            },
+            None => list.carrousel.state = CarrouselState::NotFound,
+        }
+    }
+
+    fn search_custom_list_covers(&mut self, index: usize) {
+        let Some(list) = self.custom_lists.get(index) else {
+            return;
+        };
+
+        for item in list.carrousel.items.iter() {
+            let manga_id = item.manga.id.clone();
+            let tx = self.local_event_tx.clone();
+            match item.manga.img_url.clone() {
+                Some(file_name) => {
+                    self.tasks.spawn(async move {
+                        let response = MangadexClient::global().get_cover_for_manga_lower_quality(&manga_id, &file_name).await;
+                        let cover = match response {
+                            Ok(res) => res.bytes().await.ok().and_then(|bytes| decode_bytes_to_image(bytes).ok()),
+                            Err(_) => None,
+                        };
+
+                        tx.send(HomeEvents::LoadCustomListCover(index, cover, manga_id)).ok();
+                    });
+                },
+                None => {
+                    tx.send(HomeEvents::LoadCustomListCover(index, None, manga_id)).ok();
+                },
+            };
+        }
+    }
+
+    fn load_custom_list_cover(&mut self, index: usize, maybe_cover: Option<DynamicImage>, id: String) {
+        // The lists are rendered in the same area as the recently added mangas
+        let img_area = self.recently_added_manga_state.get_img_area();
+        if let (Some(picker), Some(list)) = (self.picker.as_mut(), self.custom_lists.get_mut(index)) {
+            let fixed_protocol = maybe_cover.map(|cover| picker.new_protocol(cover, img_area, Resize::Fit(None)));
+
+            match fixed_protocol {
+                Some(Ok(protocol)) => list.image_state.insert_manga(protocol, id),
+                _ => list.image_state.set_failed(id),
             }
         }
     }
@@ -446,8 +784,23 @@ impl Home {
             vertical: 1,
         });
 
-        let instructions = Line::from(vec![
-            "Recently added mangas | ".into(),
+        let list_names = std::iter::once("Recently added mangas").chain(self.custom_lists.iter().map(|list| list.name.as_str()));
+
+        let mut instructions: Vec<Span<'_>> = vec![];
+
+        for (index, name) in list_names.enumerate() {
+            if index > 0 {
+                instructions.push(" / ".into());
+            }
+            if index == self.selected_list {
+                instructions.push(name.bold().underlined());
+            } else {
+                instructions.push(name.into());
+            }
+        }
+
+        instructions.extend([
+            " | ".into(),
             "Move right ".into(),
             Span::raw("<l>").style(*INSTRUCTIONS_STYLE),
             " Move left ".into(),
@@ -456,9 +809,58 @@ impl Home {
             Span::raw("<Enter>").style(*INSTRUCTIONS_STYLE),
         ]);
 
-        Block::bordered().title(instructions).render(recently_added_mangas_area, buf);
+        if !self.custom_lists.is_empty() {
+            instructions.extend([" Next list ".into(), Span::raw("<Tab>").style(*INSTRUCTIONS_STYLE)]);
+        }
+
+        Block::bordered().title(Line::from(instructions)).render(recently_added_mangas_area, buf);
 
-        StatefulWidget::render(self.carrousel_recently_added.clone(), inner_area, buf, &mut self.recently_added_manga_state);
+        match self.selected_list.checked_sub(1).and_then(|index| self.custom_lists.get_mut(index)) {
+            Some(list) => StatefulWidget::render(list.carrousel.clone(), inner_area, buf, &mut list.image_state),
+            None => {
+                StatefulWidget::render(self.carrousel_recently_added.clone(), inner_area, buf, &mut self.recently_added_manga_state)
+            },
+        }
+    }
+
+    fn render_continue_reading(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered().title("Continue reading");
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let columns = Layout::horizontal(self.continue_reading.iter().map(|_| Constraint::Fill(1)))
+            .spacing(2)
+            .split(inner_area);
+
+        for (index, (item, column)) in self.continue_reading.iter().zip(columns.iter()).enumerate() {
+            let where_it_was_left = match (item.chapter.number_page_bookmarked, item.progress) {
+                (Some(page), _) => format!("bookmarked at page {}", page + 1),
+                (None, Some(progress)) => progress.to_string(),
+                (None, None) => "not started".to_string(),
+            };
+
+            let [title_area, progress_area] = Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(*column);
+
+            Paragraph::new(Line::from(vec![
+                Span::styled(format!("<{}>", index + 1), *INSTRUCTIONS_STYLE),
+                format!(" {} ({where_it_was_left})", item.chapter.manga_title).into(),
+            ]))
+            .render(title_area, buf);
+
+            match (item.chapters_read_ratio(), item.total_chapters) {
+                (Some(ratio), Some(total_chapters)) => {
+                    LineGauge::default()
+                        .label(format!("{}/{total_chapters}", item.chapters_read))
+                        .filled_style(Style::default().fg(Color::Green))
+                        .line_set(THICK)
+                        .ratio(ratio)
+                        .render(progress_area, buf);
+                },
+                _ => {
+                    Paragraph::new(format!("{} chapters read", item.chapters_read).dark_gray()).render(progress_area, buf);
+                },
+            }
+        }
     }
 
     fn render_app_information(&mut self, area: Rect, buf: &mut Buffer) {
@@ -478,17 +880,49 @@ impl Home {
             },
         }
 
-        Widget::render(
-            List::new([
-                Line::from(vec!["Support mangadex: ".into(), "<m>".to_span().style(*INSTRUCTIONS_STYLE)]),
-                Line::from(vec!["Support this project ".into(), "<g>".to_span().style(*INSTRUCTIONS_STYLE)]),
-            ]),
-            layout[1],
-            buf,
-        )
+        let mut information = vec![
+            Line::from(vec!["Support mangadex: ".into(), "<m>".to_span().style(*INSTRUCTIONS_STYLE)]),
+            Line::from(vec!["Support this project ".into(), "<g>".to_span().style(*INSTRUCTIONS_STYLE)]),
+        ];
+
+        if self.can_resume_reading_session() {
+            let session = self.session_to_restore.as_ref().unwrap();
+            information.push(Line::from(vec![
+                format!("Resume {} at page {}: ", session.manga_title, session.number_page_bookmarked.unwrap_or(0) + 1).into(),
+                "<c>".to_span().style(*INSTRUCTIONS_STYLE),
+            ]));
+        }
+
+        if let Some(job) = self.download_to_resume.as_ref() {
+            information.push(Line::from(vec![
+                format!("Resume downloading {}: ", job.manga_title).into(),
+                "<f>".to_span().style(*INSTRUCTIONS_STYLE),
+            ]));
+        }
+
+        Widget::render(List::new(information), layout[1], buf)
     }
 
     pub fn handle_key_events(&mut self, key_event: KeyEvent) {
+        if self.history_to_prune.is_some() {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.local_action_tx.send(HomeActions::ScrollDownHistoryToPrune).ok();
+                },
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.local_action_tx.send(HomeActions::ScrollUpHistoryToPrune).ok();
+                },
+                KeyCode::Enter => {
+                    self.local_action_tx.send(HomeActions::PruneHistory).ok();
+                },
+                KeyCode::Esc => {
+                    self.local_action_tx.send(HomeActions::SkipPruningHistory).ok();
+                },
+                _ => {},
+            }
+            return;
+        }
+
         match key_event.code {
             KeyCode::Char('w') => {
                 self.local_action_tx.send(HomeActions::SelectNextPopularManga).ok();
@@ -509,12 +943,29 @@ impl Home {
             KeyCode::Enter => {
                 self.local_action_tx.send(HomeActions::GoToRecentlyAddedMangaPage).ok();
             },
+            KeyCode::Tab if !self.custom_lists.is_empty() => {
+                self.local_action_tx.send(HomeActions::SelectNextList).ok();
+            },
             KeyCode::Char('m') => {
                 self.local_action_tx.send(HomeActions::SupportMangadex).ok();
             },
             KeyCode::Char('g') => {
                 self.local_action_tx.send(HomeActions::SupportProject).ok();
             },
+            KeyCode::Char('c') => {
+                if self.can_resume_reading_session() {
+                    self.local_action_tx.send(HomeActions::ResumeReadingSession).ok();
+                }
+            },
+            KeyCode::Char('f') if self.download_to_resume.is_some() => {
+                self.local_action_tx.send(HomeActions::ResumeDownloadAll).ok();
+            },
+            KeyCode::Char(number @ '1'..='9') if self.can_continue_reading() => {
+                let index = number.to_digit(10).unwrap() as usize - 1;
+                if index < self.continue_reading.len() {
+                    self.local_action_tx.send(HomeActions::ContinueReading(index)).ok();
+                }
+            },
             _ => {},
         }
     }
@@ -526,6 +977,7 @@ mod tests {
 
     use super::*;
     use crate::backend::api_responses::Data;
+    use crate::view::widgets::press_key;
 
     #[test]
     fn searches_popular_manga_cover_after_mangas_are_loaded_if_picker_is_some() {
@@ -572,4 +1024,141 @@ mod tests {
 
         assert!(home.local_event_rx.is_empty());
     }
+
+    #[test]
+    fn offers_to_resume_reading_session_only_if_there_is_one_and_picker_is_some() {
+        let mut home = Home::new(Some(Picker::new((8, 8))));
+
+        press_key(&mut home, KeyCode::Char('c'));
+
+        assert!(home.local_action_rx.is_empty());
+
+        let mut home = Home::new(None).with_session_to_restore(Some(ChapterBookmarked::default()));
+
+        press_key(&mut home, KeyCode::Char('c'));
+
+        assert!(home.local_action_rx.is_empty());
+
+        let mut home = Home::new(Some(Picker::new((8, 8)))).with_session_to_restore(Some(ChapterBookmarked::default()));
+
+        press_key(&mut home, KeyCode::Char('c'));
+
+        let action = home.local_action_rx.blocking_recv().expect("no action was sent");
+
+        assert_eq!(action, HomeActions::ResumeReadingSession);
+    }
+
+    #[test]
+    fn offers_to_resume_download_all_only_if_there_is_one() {
+        let mut home = Home::new(None);
+
+        press_key(&mut home, KeyCode::Char('f'));
+
+        assert!(home.local_action_rx.is_empty());
+
+        let mut home = Home::new(None).with_download_to_resume(Some(DownloadAllJob::default()));
+
+        press_key(&mut home, KeyCode::Char('f'));
+
+        let action = home.local_action_rx.blocking_recv().expect("no action was sent");
+
+        assert_eq!(action, HomeActions::ResumeDownloadAll);
+    }
+
+    #[test]
+    fn asks_before_moving_the_stale_history_to_the_trash() {
+        let mut home = Home::new(None).with_history_to_prune(Some(HistoryToPrune {
+            months: 6,
+            titles: vec!["Stale manga".to_string()],
+        }));
+
+        assert!(home.is_typing());
+
+        press_key(&mut home, KeyCode::Char('r'));
+
+        assert!(home.local_action_rx.is_empty());
+
+        press_key(&mut home, KeyCode::Enter);
+
+        assert_eq!(HomeActions::PruneHistory, home.local_action_rx.blocking_recv().expect("no action was sent"));
+
+        press_key(&mut home, KeyCode::Esc);
+
+        let action = home.local_action_rx.blocking_recv().expect("no action was sent");
+
+        assert_eq!(HomeActions::SkipPruningHistory, action);
+
+        home.update(action);
+
+        assert!(!home.is_typing());
+    }
+
+    #[test]
+    fn continues_reading_the_manga_of_the_number_pressed_only_if_picker_is_some() {
+        let continue_reading = vec![ContinueReading::default(), ContinueReading::default()];
+
+        let mut home = Home::new(None);
+        home.continue_reading = continue_reading.clone();
+
+        press_key(&mut home, KeyCode::Char('1'));
+
+        assert!(home.local_action_rx.is_empty());
+
+        let mut home = Home::new(Some(Picker::new((8, 8))));
+        home.continue_reading = continue_reading;
+
+        press_key(&mut home, KeyCode::Char('3'));
+
+        assert!(home.local_action_rx.is_empty());
+
+        press_key(&mut home, KeyCode::Char('2'));
+
+        let action = home.local_action_rx.blocking_recv().expect("no action was sent");
+
+        assert_eq!(action, HomeActions::ContinueReading(1));
+    }
+
+    #[test]
+    fn goes_to_the_manga_page_of_the_manga_selected_in_a_custom_list() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Events>();
+        let mut home = Home::new(None).with_global_sender(tx);
+
+        home.custom_lists = vec![CustomListRow {
+            id: "staff_picks".to_string(),
+            name: "Mangadex list".to_string(),
+            carrousel: RecentlyAddedCarrousel::new(false),
+            image_state: ImageState::default(),
+        }];
+
+        home.load_custom_list(
+            0,
+            Some(CustomListMangas {
+                name: "Staff Picks".to_string(),
+                mangas: SearchMangaResponse {
+                    data: vec![Data {
+                        id: "manga_in_list".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            }),
+        );
+
+        assert_eq!("Staff Picks", home.custom_lists[0].name);
+
+        press_key(&mut home, KeyCode::Tab);
+        press_key(&mut home, KeyCode::Enter);
+
+        while let Ok(action) = home.local_action_rx.try_recv() {
+            home.update(action);
+        }
+
+        assert_eq!(1, home.selected_list);
+
+        let Events::GoToMangaPage(manga) = rx.blocking_recv().expect("no event was sent") else {
+            panic!("expected to go to manga page");
+        };
+
+        assert_eq!("manga_in_list", manga.manga.id);
+    }
 }
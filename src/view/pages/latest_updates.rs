@@ -0,0 +1,606 @@
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, StatefulWidget, Widget};
+use ratatui::Frame;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinSet;
+
+use crate::backend::api_responses::{ChapterData, ChapterResponse};
+use crate::backend::database::{
+    save_plan_to_read, set_chapter_downloaded, ChapterDownloaded, MangaPlanToReadSave, SetChapterDownloaded,
+};
+use crate::backend::database_actor::DatabaseActor;
+use crate::backend::download::DownloadChapter;
+use crate::backend::error_log::{write_to_error_log, ErrorType};
+use crate::backend::fetch::{ApiClient, ITEMS_PER_PAGE_LATEST_UPDATES};
+use crate::backend::filter::Languages;
+use crate::backend::tui::Events;
+use crate::backend::AppDirectories;
+use crate::config::MangaTuiConfig;
+use crate::global::{ERROR_STYLE, INSTRUCTIONS_STYLE};
+use crate::utils::display_publication_date;
+use crate::view::tasks::manga::{chapter_downloaded, describe_chapter_to_download, download_chapter_task};
+use crate::view::tasks::search::search_manga_by_id;
+use crate::view::widgets::search::MangaItem;
+use crate::view::widgets::Component;
+
+/// The next page is requested once the selected chapter is this close to the last one loaded
+const ROWS_LEFT_BEFORE_LOADING: usize = 5;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LatestUpdatesActions {
+    ScrollDown,
+    ScrollUp,
+    GoToMangaPage,
+    AddToPlanToRead,
+    DownloadChapter,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LatestUpdatesEvents {
+    LoadPage(ChapterResponse),
+    ErrorLoadingPage,
+    ErrorOpeningManga,
+    ChapterDownloaded(String, ChapterDownloaded),
+    ErrorDownloading(String),
+    /// The title of the manga added to plan to read
+    AddedToPlanToRead(String),
+    ErrorAddingToPlanToRead,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Status {
+    AddedToPlanToRead(String),
+    Downloading(String),
+    Downloaded(String),
+    Error(&'static str),
+}
+
+/// A chapter published recently along with the manga it belongs to
+#[derive(Debug, Clone, PartialEq)]
+struct LatestUpdate {
+    manga_id: String,
+    manga_title: String,
+    chapter: ChapterData,
+}
+
+impl LatestUpdate {
+    fn new(chapter: ChapterData) -> Option<Self> {
+        let manga = chapter.relationships.iter().find(|rel| rel.type_field == "manga")?;
+        let title = manga
+            .attributes
+            .as_ref()
+            .and_then(|attributes| attributes.title.clone())
+            .unwrap_or_default();
+
+        let manga_title = title
+            .en
+            .or(title.ja_ro)
+            .or(title.ja)
+            .or(title.jp)
+            .or(title.zh)
+            .or(title.ko)
+            .or(title.ko_ro)
+            .unwrap_or_default();
+
+        Some(Self {
+            manga_id: manga.id.clone(),
+            manga_title,
+            chapter,
+        })
+    }
+
+    fn scanlator(&self) -> String {
+        self.chapter
+            .relationships
+            .iter()
+            .find(|rel| rel.type_field == "scanlation_group")
+            .and_then(|rel| rel.attributes.as_ref())
+            .map(|attributes| attributes.name.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// The chapters most recently published on the provider, more chapters are loaded while scrolling down
+pub struct LatestUpdatesPage<T: ApiClient> {
+    pub global_event_tx: Option<UnboundedSender<Events>>,
+    pub local_action_tx: UnboundedSender<LatestUpdatesActions>,
+    pub local_action_rx: UnboundedReceiver<LatestUpdatesActions>,
+    pub local_event_tx: UnboundedSender<LatestUpdatesEvents>,
+    pub local_event_rx: UnboundedReceiver<LatestUpdatesEvents>,
+    updates: Vec<LatestUpdate>,
+    state: ListState,
+    /// The last page that was loaded
+    page: u32,
+    total_results: u32,
+    is_loading: bool,
+    status: Option<Status>,
+    tasks: JoinSet<()>,
+    api_client: Option<T>,
+}
+
+impl<T: ApiClient> LatestUpdatesPage<T> {
+    pub fn new() -> Self {
+        let (local_action_tx, local_action_rx) = mpsc::unbounded_channel::<LatestUpdatesActions>();
+        let (local_event_tx, local_event_rx) = mpsc::unbounded_channel::<LatestUpdatesEvents>();
+        Self {
+            global_event_tx: None,
+            local_action_tx,
+            local_action_rx,
+            local_event_tx,
+            local_event_rx,
+            updates: vec![],
+            state: ListState::default(),
+            page: 0,
+            total_results: 0,
+            is_loading: false,
+            status: None,
+            tasks: JoinSet::new(),
+            api_client: None,
+        }
+    }
+
+    pub fn with_global_sender(mut self, sender: UnboundedSender<Events>) -> Self {
+        self.global_event_tx = Some(sender);
+        self
+    }
+
+    pub fn with_api_client(mut self, api_client: T) -> Self {
+        self.api_client = Some(api_client);
+        self
+    }
+
+    /// Called every time the page is opened so the latest chapters are always shown first
+    pub fn load(&mut self) {
+        self.updates.clear();
+        self.state = ListState::default();
+        self.page = 0;
+        self.total_results = 0;
+        self.load_next_page();
+    }
+
+    fn can_load_next_page(&self) -> bool {
+        !self.is_loading && (self.page == 0 || self.page * ITEMS_PER_PAGE_LATEST_UPDATES < self.total_results)
+    }
+
+    fn load_next_page(&mut self) {
+        let Some(api_client) = self.api_client.clone() else {
+            return;
+        };
+
+        if !self.can_load_next_page() {
+            return;
+        }
+
+        self.is_loading = true;
+        let page = self.page + 1;
+        let tx = self.local_event_tx.clone();
+
+        self.tasks.spawn(async move {
+            let response = api_client.get_latest_updates(page, *Languages::get_preferred_lang()).await;
+            match response {
+                Ok(response) => match response.json::<ChapterResponse>().await {
+                    Ok(chapters) => {
+                        tx.send(LatestUpdatesEvents::LoadPage(chapters)).ok();
+                    },
+                    Err(e) => {
+                        write_to_error_log(ErrorType::Error(Box::new(e)));
+                        tx.send(LatestUpdatesEvents::ErrorLoadingPage).ok();
+                    },
+                },
+                Err(e) => {
+                    write_to_error_log(ErrorType::Error(Box::new(e)));
+                    tx.send(LatestUpdatesEvents::ErrorLoadingPage).ok();
+                },
+            }
+        });
+    }
+
+    fn load_page(&mut self, response: ChapterResponse) {
+        self.is_loading = false;
+        self.page += 1;
+        self.total_results = response.total as u32;
+        self.updates.extend(response.data.into_iter().filter_map(LatestUpdate::new));
+
+        if self.state.selected().is_none() && !self.updates.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    fn current_update(&self) -> Option<&LatestUpdate> {
+        self.state.selected().and_then(|index| self.updates.get(index))
+    }
+
+    fn scroll_down(&mut self) {
+        if self.updates.is_empty() {
+            return;
+        }
+
+        let next = self.state.selected().map_or(0, |index| (index + 1).min(self.updates.len() - 1));
+        self.state.select(Some(next));
+
+        if next + ROWS_LEFT_BEFORE_LOADING >= self.updates.len() {
+            self.load_next_page();
+        }
+    }
+
+    fn scroll_up(&mut self) {
+        self.state.select_previous();
+    }
+
+    fn go_to_manga_page(&mut self) {
+        let Some(update) = self.current_update() else {
+            return;
+        };
+        let (Some(api_client), Some(tx)) = (self.api_client.clone(), self.global_event_tx.clone()) else {
+            return;
+        };
+
+        let manga_id = update.manga_id.clone();
+        let local_tx = self.local_event_tx.clone();
+        self.status = None;
+
+        self.tasks.spawn(async move {
+            match search_manga_by_id(api_client, &manga_id).await {
+                Ok(manga) => {
+                    tx.send(Events::GoToMangaPage(MangaItem::new(manga))).ok();
+                },
+                Err(e) => {
+                    write_to_error_log(ErrorType::Error(e));
+                    local_tx.send(LatestUpdatesEvents::ErrorOpeningManga).ok();
+                },
+            }
+        });
+    }
+
+    fn add_to_plan_to_read(&mut self) {
+        let Some(update) = self.current_update().cloned() else {
+            return;
+        };
+
+        let saved = DatabaseActor::global().run(move |conn| {
+            save_plan_to_read(
+                MangaPlanToReadSave {
+                    id: &update.manga_id,
+                    title: &update.manga_title,
+                    img_url: None,
+                },
+                conn,
+            )?;
+            Ok(update.manga_title)
+        });
+
+        let tx = self.local_event_tx.clone();
+
+        self.tasks.spawn(async move {
+            match saved.await {
+                Ok(manga_title) => {
+                    tx.send(LatestUpdatesEvents::AddedToPlanToRead(manga_title)).ok();
+                },
+                Err(e) => {
+                    write_to_error_log(ErrorType::Error(e));
+                    tx.send(LatestUpdatesEvents::ErrorAddingToPlanToRead).ok();
+                },
+            }
+        });
+    }
+
+    fn download_chapter_selected(&mut self) {
+        let Some(update) = self.current_update().cloned() else {
+            return;
+        };
+        let Some(api_client) = self.api_client.clone() else {
+            return;
+        };
+
+        // There are no pages to download
+        if update.chapter.attributes.external_url.is_some() {
+            self.status = Some(Status::Error("This chapter is hosted by the publisher and cannot be downloaded"));
+            return;
+        }
+
+        let chapter_id = update.chapter.id.clone();
+        let download_chapter = DownloadChapter::new(
+            &chapter_id,
+            &update.manga_id,
+            &update.manga_title,
+            update.chapter.attributes.title.as_deref().unwrap_or_default(),
+            update.chapter.attributes.chapter.as_deref().unwrap_or_default(),
+            &update.scanlator(),
+            &Languages::try_from_iso_code(&update.chapter.attributes.translated_language)
+                .unwrap_or_default()
+                .as_human_readable(),
+        );
+
+        let tx = self.local_event_tx.clone();
+        self.status = Some(Status::Downloading(describe_chapter_to_download(&update.chapter)));
+
+        self.tasks.spawn(async move {
+            let config = MangaTuiConfig::get();
+            // The progress of the download is not shown in this page
+            let (progress_tx, _progress_rx) = mpsc::unbounded_channel();
+
+            let download_result = download_chapter_task(
+                download_chapter,
+                api_client,
+                config.image_quality,
+                AppDirectories::MangaDownloads.get_full_path(),
+                config.download_type,
+                chapter_id.clone(),
+                false,
+                progress_tx,
+            )
+            .await;
+
+            match download_result {
+                Ok(path) => {
+                    tx.send(LatestUpdatesEvents::ChapterDownloaded(chapter_id, chapter_downloaded(path, config.download_type)))
+                        .ok();
+                },
+                Err(e) => {
+                    write_to_error_log(ErrorType::Error(e));
+                    tx.send(LatestUpdatesEvents::ErrorDownloading(chapter_id)).ok();
+                },
+            }
+        });
+    }
+
+    fn save_download_status(&mut self, chapter_id: String, download: ChapterDownloaded) {
+        let Some(update) = self.updates.iter().find(|update| update.chapter.id == chapter_id) else {
+            return;
+        };
+
+        let chapter_title = describe_chapter_to_download(&update.chapter);
+        let update = update.clone();
+
+        DatabaseActor::global().execute(move |conn| {
+            set_chapter_downloaded(
+                SetChapterDownloaded {
+                    id: &chapter_id,
+                    title: update.chapter.attributes.title.as_deref().unwrap_or_default(),
+                    manga_id: &update.manga_id,
+                    manga_title: &update.manga_title,
+                    img_url: None,
+                    download: &download,
+                },
+                conn,
+            )
+        });
+
+        self.status = Some(Status::Downloaded(chapter_title));
+    }
+
+    fn handle_key_events(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.local_action_tx.send(LatestUpdatesActions::ScrollDown).ok();
+            },
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.local_action_tx.send(LatestUpdatesActions::ScrollUp).ok();
+            },
+            KeyCode::Char('r') | KeyCode::Enter => {
+                self.local_action_tx.send(LatestUpdatesActions::GoToMangaPage).ok();
+            },
+            KeyCode::Char('p') => {
+                self.local_action_tx.send(LatestUpdatesActions::AddToPlanToRead).ok();
+            },
+            KeyCode::Char('d') => {
+                self.local_action_tx.send(LatestUpdatesActions::DownloadChapter).ok();
+            },
+            _ => {},
+        }
+    }
+
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => {
+                self.local_action_tx.send(LatestUpdatesActions::ScrollUp).ok();
+            },
+            MouseEventKind::ScrollDown => {
+                self.local_action_tx.send(LatestUpdatesActions::ScrollDown).ok();
+            },
+            _ => {},
+        }
+    }
+
+    fn tick(&mut self) {
+        if let Ok(event) = self.local_event_rx.try_recv() {
+            match event {
+                LatestUpdatesEvents::LoadPage(response) => self.load_page(response),
+                LatestUpdatesEvents::ErrorLoadingPage => {
+                    self.is_loading = false;
+                    self.status = Some(Status::Error("Could not load the latest updates, please try again"));
+                },
+                LatestUpdatesEvents::ErrorOpeningManga => {
+                    self.status = Some(Status::Error("Could not open the manga, please try again"));
+                },
+                LatestUpdatesEvents::ChapterDownloaded(chapter_id, download) => self.save_download_status(chapter_id, download),
+                LatestUpdatesEvents::AddedToPlanToRead(manga_title) => self.status = Some(Status::AddedToPlanToRead(manga_title)),
+                LatestUpdatesEvents::ErrorAddingToPlanToRead => {
+                    self.status = Some(Status::Error("Could not add the manga to plan to read"));
+                },
+                LatestUpdatesEvents::ErrorDownloading(_) => {
+                    self.status = Some(Status::Error("Could not download the chapter, please try again"));
+                },
+            }
+        }
+    }
+
+    fn status_line(&self) -> Option<Span<'static>> {
+        self.status.as_ref().map(|status| match status {
+            Status::AddedToPlanToRead(title) => format!(" | Added {title} to plan to read").into(),
+            Status::Downloading(chapter) => format!(" | Downloading {chapter}").into(),
+            Status::Downloaded(chapter) => format!(" | Downloaded {chapter}").into(),
+            Status::Error(error) => Span::styled(format!(" | {error}"), *ERROR_STYLE),
+        })
+    }
+}
+
+impl<T: ApiClient> Component for LatestUpdatesPage<T> {
+    type Actions = LatestUpdatesActions;
+
+    fn render(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let mut instructions = vec![
+            "Open ".into(),
+            Span::raw("<r>").style(*INSTRUCTIONS_STYLE),
+            " Plan to read ".into(),
+            Span::raw("<p>").style(*INSTRUCTIONS_STYLE),
+            " Download ".into(),
+            Span::raw("<d>").style(*INSTRUCTIONS_STYLE),
+        ];
+
+        instructions.extend(self.status_line());
+
+        let mut block = Block::bordered().title("Latest updates".bold()).title_bottom(Line::from(instructions));
+
+        if self.is_loading {
+            block = block.title_top(Line::from("Loading more chapters...").right_aligned());
+        }
+
+        if self.updates.is_empty() {
+            let message = if self.is_loading { "Loading the latest updates..." } else { "No chapters were found" };
+            Paragraph::new(message).block(block).render(area, frame.buffer_mut());
+            return;
+        }
+
+        let date_format = MangaTuiConfig::get().date_format.as_deref();
+
+        let rows = self.updates.iter().map(|update| {
+            ListItem::new(Line::from(vec![
+                update.manga_title.as_str().bold(),
+                " | ".into(),
+                describe_chapter_to_download(&update.chapter).into(),
+                " | ".into(),
+                display_publication_date(&update.chapter.attributes.readable_at, date_format).into(),
+            ]))
+        });
+
+        let list = List::new(rows).block(block).highlight_style(Style::default().on_blue());
+
+        StatefulWidget::render(list, area, frame.buffer_mut(), &mut self.state);
+    }
+
+    fn update(&mut self, action: Self::Actions) {
+        match action {
+            LatestUpdatesActions::ScrollDown => self.scroll_down(),
+            LatestUpdatesActions::ScrollUp => self.scroll_up(),
+            LatestUpdatesActions::GoToMangaPage => self.go_to_manga_page(),
+            LatestUpdatesActions::AddToPlanToRead => self.add_to_plan_to_read(),
+            LatestUpdatesActions::DownloadChapter => self.download_chapter_selected(),
+        }
+    }
+
+    fn handle_events(&mut self, events: Events) {
+        match events {
+            Events::Key(key_event) => self.handle_key_events(key_event),
+            Events::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
+            Events::Tick => self.tick(),
+            _ => {},
+        }
+    }
+
+    fn clean_up(&mut self) {
+        self.tasks.abort_all();
+        self.is_loading = false;
+        self.status = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::backend::api_responses::{ChapterAttribute, ChapterRelationshipAttribute, Relationship, Title};
+    use crate::backend::fetch::fake_api_client::MockMangadexClient;
+    use crate::view::widgets::press_key;
+
+    fn chapter_of_manga(index: usize) -> ChapterData {
+        ChapterData {
+            id: format!("chapter_{index}"),
+            attributes: ChapterAttribute {
+                chapter: Some(index.to_string()),
+                ..Default::default()
+            },
+            relationships: vec![Relationship {
+                id: format!("manga_{index}"),
+                type_field: "manga".to_string(),
+                attributes: Some(ChapterRelationshipAttribute {
+                    title: Some(Title {
+                        en: Some(format!("title {index}")),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn latest_updates(total: usize) -> ChapterResponse {
+        ChapterResponse {
+            data: (0..10).map(chapter_of_manga).collect(),
+            total: total as i64,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn it_loads_the_next_page_when_scrolling_near_the_end() {
+        let mut page: LatestUpdatesPage<MockMangadexClient> =
+            LatestUpdatesPage::new().with_api_client(MockMangadexClient::new().with_chapter_response(latest_updates(30)));
+
+        page.load();
+
+        let event = page.local_event_rx.recv().await.unwrap();
+        page.local_event_tx.send(event).ok();
+        page.tick();
+
+        assert_eq!(10, page.updates.len());
+        assert_eq!(Some(0), page.state.selected());
+        assert_eq!("manga_0", page.current_update().unwrap().manga_id);
+        assert_eq!("title 0", page.current_update().unwrap().manga_title);
+
+        for _ in 0..4 {
+            press_key(&mut page, KeyCode::Char('j'));
+            let action = page.local_action_rx.recv().await.unwrap();
+            page.update(action);
+        }
+
+        assert!(!page.is_loading);
+
+        press_key(&mut page, KeyCode::Char('j'));
+        let action = page.local_action_rx.recv().await.unwrap();
+        page.update(action);
+
+        assert!(page.is_loading);
+
+        let event = page.local_event_rx.recv().await.unwrap();
+        page.local_event_tx.send(event).ok();
+        page.tick();
+
+        assert!(!page.is_loading);
+        assert_eq!(2, page.page);
+        assert_eq!(20, page.updates.len());
+        assert_eq!(Some(5), page.state.selected());
+    }
+
+    #[tokio::test]
+    async fn it_stops_loading_once_every_chapter_was_loaded() {
+        let mut page: LatestUpdatesPage<MockMangadexClient> =
+            LatestUpdatesPage::new().with_api_client(MockMangadexClient::new().with_chapter_response(latest_updates(10)));
+
+        page.load_page(latest_updates(10));
+
+        assert!(!page.can_load_next_page());
+
+        for _ in 0..20 {
+            page.scroll_down();
+        }
+
+        assert!(!page.is_loading);
+        assert_eq!(Some(9), page.state.selected());
+    }
+}
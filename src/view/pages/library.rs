@@ -0,0 +1,748 @@
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use manga_tui::SearchTerm;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span, ToSpan};
+use ratatui::widgets::{Block, List, ListState, Paragraph, StatefulWidget, Widget};
+use ratatui::Frame;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinSet;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use crate::backend::database::{Collection, Database, MangaHistoryResponse, MangaInsert};
+use crate::backend::database_actor::DatabaseActor;
+use crate::backend::error_log::{write_to_error_log, ErrorType};
+use crate::backend::fetch::ApiClient;
+use crate::backend::tui::Events;
+use crate::global::{ERROR_STYLE, INSTRUCTIONS_STYLE};
+use crate::utils::render_search_bar;
+use crate::view::tasks::search::search_manga_by_id;
+use crate::view::widgets::feed::HistoryWidget;
+use crate::view::widgets::search::MangaItem;
+use crate::view::widgets::Component;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LibraryActions {
+    NextCollection,
+    PreviousCollection,
+    ScrollMangasDown,
+    ScrollMangasUp,
+    NextPage,
+    PreviousPage,
+    ToggleSearchBar,
+    SearchMangas,
+    StartNamingCollection,
+    CancelNamingCollection,
+    CreateCollection,
+    DeleteCollection,
+    RemoveMangaSelected,
+    GoToMangaPage,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LibraryEvents {
+    ErrorOpeningManga,
+    // The results of the queries sent to the database actor
+    LoadCollections(Vec<Collection>),
+    /// The collections once the one with this name was created
+    CollectionCreated(String, Vec<Collection>),
+    LoadMangas(MangaHistoryResponse),
+    /// The mangas of the collection once the one selected was removed from it
+    MangaRemoved(MangaHistoryResponse),
+}
+
+/// What the keys typed are written to
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+enum LibraryInput {
+    #[default]
+    None,
+    Search,
+    CollectionName,
+}
+
+/// The collections the user made, like "Favorites" or "On hold", mangas are added to them from the manga page
+pub struct LibraryPage<T: ApiClient> {
+    pub global_event_tx: Option<UnboundedSender<Events>>,
+    pub local_action_tx: UnboundedSender<LibraryActions>,
+    pub local_action_rx: UnboundedReceiver<LibraryActions>,
+    pub local_event_tx: UnboundedSender<LibraryEvents>,
+    pub local_event_rx: UnboundedReceiver<LibraryEvents>,
+    collections: Vec<Collection>,
+    collections_state: ListState,
+    mangas: Option<HistoryWidget>,
+    search_bar: Input,
+    collection_name: Input,
+    input: LibraryInput,
+    /// The collection is only deleted if `D` is pressed twice in a row
+    is_confirming_delete: bool,
+    error_opening_manga: bool,
+    items_per_page: u32,
+    tasks: JoinSet<()>,
+    api_client: Option<T>,
+    /// Every query is sent to it so the ui thread never waits for the database
+    database: DatabaseActor,
+}
+
+impl<T: ApiClient> LibraryPage<T> {
+    pub fn new() -> Self {
+        let (local_action_tx, local_action_rx) = mpsc::unbounded_channel::<LibraryActions>();
+        let (local_event_tx, local_event_rx) = mpsc::unbounded_channel::<LibraryEvents>();
+        Self {
+            global_event_tx: None,
+            local_action_tx,
+            local_action_rx,
+            local_event_tx,
+            local_event_rx,
+            collections: vec![],
+            collections_state: ListState::default(),
+            mangas: None,
+            search_bar: Input::default(),
+            collection_name: Input::default(),
+            input: LibraryInput::default(),
+            is_confirming_delete: false,
+            error_opening_manga: false,
+            items_per_page: 5,
+            tasks: JoinSet::new(),
+            api_client: None,
+            database: DatabaseActor::global().clone(),
+        }
+    }
+
+    #[cfg(test)]
+    fn with_database(mut self, database: DatabaseActor) -> Self {
+        self.database = database;
+        self
+    }
+
+    pub fn with_global_sender(mut self, sender: UnboundedSender<Events>) -> Self {
+        self.global_event_tx = Some(sender);
+        self
+    }
+
+    pub fn with_api_client(mut self, api_client: T) -> Self {
+        self.api_client = Some(api_client);
+        self
+    }
+
+    pub fn is_typing(&self) -> bool {
+        self.input != LibraryInput::None
+    }
+
+    /// Called every time the page is opened since mangas may have been added to the collections from the manga page
+    pub fn load(&mut self) {
+        self.database.query(
+            self.local_event_tx.clone(),
+            |conn| Ok(Database::new(conn).get_collections()?),
+            LibraryEvents::LoadCollections,
+        );
+    }
+
+    fn current_collection(&self) -> Option<&Collection> {
+        self.collections_state.selected().and_then(|index| self.collections.get(index))
+    }
+
+    fn load_collections(&mut self, collections: Vec<Collection>) {
+        let selected = self.collections_state.selected().unwrap_or_default();
+        self.collections = collections;
+        self.collections_state
+            .select((!self.collections.is_empty()).then(|| selected.min(self.collections.len() - 1)));
+        self.search_mangas(1);
+    }
+
+    fn search_mangas(&mut self, page: u32) {
+        let Some(collection_id) = self.current_collection().map(|collection| collection.id) else {
+            self.mangas = None;
+            return;
+        };
+
+        let (search, tag) = SearchTerm::with_tag_filter(self.search_bar.value());
+        let items_per_page = self.items_per_page;
+
+        self.database.query(
+            self.local_event_tx.clone(),
+            move |conn| Ok(Database::new(conn).get_collection_mangas(collection_id, page, items_per_page, search, tag)?),
+            LibraryEvents::LoadMangas,
+        );
+    }
+
+    fn load_mangas(&mut self, response: MangaHistoryResponse) {
+        let mut mangas = HistoryWidget::from_database_response(response);
+        if !mangas.mangas.is_empty() {
+            mangas.state.select(Some(0));
+        }
+        self.mangas = Some(mangas);
+    }
+
+    fn select_collection(&mut self, next: bool) {
+        if self.collections.is_empty() {
+            return;
+        }
+
+        let last = self.collections.len() - 1;
+        let selected = self.collections_state.selected().unwrap_or_default();
+        let selected = match next {
+            true if selected == last => 0,
+            true => selected + 1,
+            false if selected == 0 => last,
+            false => selected - 1,
+        };
+
+        self.collections_state.select(Some(selected));
+        self.search_bar.reset();
+        self.search_mangas(1);
+    }
+
+    fn search_next_page(&mut self) {
+        if let Some(mangas) = self.mangas.as_ref() {
+            if mangas.can_search_next_page(self.items_per_page as f64) {
+                self.search_mangas(mangas.page + 1);
+            }
+        }
+    }
+
+    fn search_previous_page(&mut self) {
+        if let Some(mangas) = self.mangas.as_ref() {
+            if mangas.can_search_previous_page() {
+                self.search_mangas(mangas.page - 1);
+            }
+        }
+    }
+
+    fn create_collection(&mut self) {
+        let name = self.collection_name.value().trim().to_string();
+        self.collection_name.reset();
+        self.input = LibraryInput::None;
+
+        if name.is_empty() {
+            return;
+        }
+
+        self.database.query(
+            self.local_event_tx.clone(),
+            move |conn| {
+                let database = Database::new(conn);
+                database.create_collection(&name)?;
+                Ok((name, database.get_collections()?))
+            },
+            |(name, collections)| LibraryEvents::CollectionCreated(name, collections),
+        );
+    }
+
+    fn collection_created(&mut self, name: String, collections: Vec<Collection>) {
+        if let Some(index) = collections.iter().position(|collection| collection.name == name) {
+            self.collections_state.select(Some(index));
+        }
+        self.load_collections(collections);
+    }
+
+    fn delete_collection_selected(&mut self) {
+        if !self.is_confirming_delete {
+            self.is_confirming_delete = self.current_collection().is_some();
+            return;
+        }
+
+        self.is_confirming_delete = false;
+
+        let Some(collection_id) = self.current_collection().map(|collection| collection.id) else {
+            return;
+        };
+
+        self.database.query(
+            self.local_event_tx.clone(),
+            move |conn| {
+                let database = Database::new(conn);
+                database.delete_collection(collection_id)?;
+                Ok(database.get_collections()?)
+            },
+            LibraryEvents::LoadCollections,
+        );
+    }
+
+    fn remove_manga_selected(&mut self) {
+        let Some(collection_id) = self.current_collection().map(|collection| collection.id) else {
+            return;
+        };
+        let Some(mangas) = self.mangas.as_ref() else {
+            return;
+        };
+        let Some(manga) = mangas.get_current_manga_selected().cloned() else {
+            return;
+        };
+
+        let page = mangas.page;
+        let items_per_page = self.items_per_page;
+        let search_term = self.search_bar.value().to_string();
+
+        self.database.query(
+            self.local_event_tx.clone(),
+            move |conn| {
+                let database = Database::new(conn);
+
+                // The manga is already in the collection so toggling it removes it
+                database.toggle_manga_in_collection(
+                    collection_id,
+                    MangaInsert {
+                        id: &manga.id,
+                        title: &manga.title,
+                        img_url: None,
+                    },
+                )?;
+
+                let search_mangas = |page| {
+                    let (search, tag) = SearchTerm::with_tag_filter(&search_term);
+                    database.get_collection_mangas(collection_id, page, items_per_page, search, tag)
+                };
+
+                let response = search_mangas(page)?;
+
+                // The page removed its last manga
+                if response.mangas.is_empty() && page > 1 {
+                    return Ok(search_mangas(page - 1)?);
+                }
+
+                Ok(response)
+            },
+            LibraryEvents::MangaRemoved,
+        );
+    }
+
+    fn manga_removed(&mut self, response: MangaHistoryResponse) {
+        if let Some(collection) = self.collections_state.selected().and_then(|index| self.collections.get_mut(index)) {
+            collection.total_mangas = collection.total_mangas.saturating_sub(1);
+        }
+        self.load_mangas(response);
+    }
+
+    fn go_to_manga_page(&mut self) {
+        let Some(manga) = self.mangas.as_ref().and_then(|mangas| mangas.get_current_manga_selected()) else {
+            return;
+        };
+        let (Some(api_client), Some(tx)) = (self.api_client.clone(), self.global_event_tx.clone()) else {
+            return;
+        };
+
+        let manga_id = manga.id.clone();
+        let local_tx = self.local_event_tx.clone();
+        self.error_opening_manga = false;
+
+        self.tasks.spawn(async move {
+            match search_manga_by_id(api_client, &manga_id).await {
+                Ok(manga) => {
+                    tx.send(Events::GoToMangaPage(MangaItem::new(manga))).ok();
+                },
+                Err(e) => {
+                    write_to_error_log(ErrorType::Error(e));
+                    local_tx.send(LibraryEvents::ErrorOpeningManga).ok();
+                },
+            }
+        });
+    }
+
+    fn handle_key_events(&mut self, key_event: KeyEvent) {
+        if key_event.code != KeyCode::Char('D') {
+            self.is_confirming_delete = false;
+        }
+
+        match self.input {
+            LibraryInput::Search => match key_event.code {
+                KeyCode::Enter => {
+                    self.local_action_tx.send(LibraryActions::SearchMangas).ok();
+                },
+                KeyCode::Esc => {
+                    self.local_action_tx.send(LibraryActions::ToggleSearchBar).ok();
+                },
+                _ => {
+                    self.search_bar.handle_event(&crossterm::event::Event::Key(key_event));
+                },
+            },
+            LibraryInput::CollectionName => match key_event.code {
+                KeyCode::Enter => {
+                    self.local_action_tx.send(LibraryActions::CreateCollection).ok();
+                },
+                KeyCode::Esc => {
+                    self.local_action_tx.send(LibraryActions::CancelNamingCollection).ok();
+                },
+                _ => {
+                    self.collection_name.handle_event(&crossterm::event::Event::Key(key_event));
+                },
+            },
+            LibraryInput::None => match key_event.code {
+                KeyCode::Tab => {
+                    self.local_action_tx.send(LibraryActions::NextCollection).ok();
+                },
+                KeyCode::BackTab => {
+                    self.local_action_tx.send(LibraryActions::PreviousCollection).ok();
+                },
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.local_action_tx.send(LibraryActions::ScrollMangasDown).ok();
+                },
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.local_action_tx.send(LibraryActions::ScrollMangasUp).ok();
+                },
+                KeyCode::Char('w') => {
+                    self.local_action_tx.send(LibraryActions::NextPage).ok();
+                },
+                KeyCode::Char('b') => {
+                    self.local_action_tx.send(LibraryActions::PreviousPage).ok();
+                },
+                KeyCode::Char('s') => {
+                    self.local_action_tx.send(LibraryActions::ToggleSearchBar).ok();
+                },
+                KeyCode::Char('n') => {
+                    self.local_action_tx.send(LibraryActions::StartNamingCollection).ok();
+                },
+                KeyCode::Char('D') => {
+                    self.local_action_tx.send(LibraryActions::DeleteCollection).ok();
+                },
+                KeyCode::Char('x') => {
+                    self.local_action_tx.send(LibraryActions::RemoveMangaSelected).ok();
+                },
+                KeyCode::Char('r') | KeyCode::Enter => {
+                    self.local_action_tx.send(LibraryActions::GoToMangaPage).ok();
+                },
+                _ => {},
+            },
+        }
+    }
+
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => {
+                self.local_action_tx.send(LibraryActions::ScrollMangasUp).ok();
+            },
+            MouseEventKind::ScrollDown => {
+                self.local_action_tx.send(LibraryActions::ScrollMangasDown).ok();
+            },
+            _ => {},
+        }
+    }
+
+    fn tick(&mut self) {
+        while let Ok(event) = self.local_event_rx.try_recv() {
+            match event {
+                LibraryEvents::ErrorOpeningManga => self.error_opening_manga = true,
+                LibraryEvents::LoadCollections(collections) => self.load_collections(collections),
+                LibraryEvents::CollectionCreated(name, collections) => self.collection_created(name, collections),
+                LibraryEvents::LoadMangas(response) => self.load_mangas(response),
+                LibraryEvents::MangaRemoved(response) => self.manga_removed(response),
+            }
+        }
+    }
+
+    fn render_collections(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let [list_area, name_area] = Layout::vertical([Constraint::Fill(1), Constraint::Length(3)]).areas(area);
+
+        let instructions = Line::from(vec![
+            "Next ".into(),
+            Span::raw("<tab>").style(*INSTRUCTIONS_STYLE),
+            " New ".into(),
+            Span::raw("<n>").style(*INSTRUCTIONS_STYLE),
+            " Delete ".into(),
+            Span::raw("<D>").style(*INSTRUCTIONS_STYLE),
+        ]);
+
+        let mut block = Block::bordered().title(instructions);
+
+        if self.is_confirming_delete {
+            block = block.title_bottom("Press <D> again to delete this collection".to_span().style(*ERROR_STYLE));
+        }
+
+        if self.collections.is_empty() {
+            Paragraph::new("You have no collections yet, press <n> to create one")
+                .block(block)
+                .render(list_area, frame.buffer_mut());
+        } else {
+            let collections = List::new(
+                self.collections
+                    .iter()
+                    .map(|collection| format!("{} ({})", collection.name, collection.total_mangas)),
+            )
+            .block(block)
+            .highlight_style(Style::default().on_blue());
+
+            StatefulWidget::render(collections, list_area, frame.buffer_mut(), &mut self.collections_state);
+        }
+
+        let is_naming = self.input == LibraryInput::CollectionName;
+        let input_help: Vec<Span<'_>> = if is_naming {
+            vec!["Name of the collection, press ".into(), Span::raw("<Enter>").style(*INSTRUCTIONS_STYLE), " to create it".into()]
+        } else {
+            vec!["New collection ".into(), Span::raw("<n>").style(*INSTRUCTIONS_STYLE)]
+        };
+
+        render_search_bar(is_naming, input_help.into(), &self.collection_name, frame, name_area);
+    }
+
+    fn render_mangas(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let [search_bar_area, mangas_area] = Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+
+        let is_searching = self.input == LibraryInput::Search;
+        let input_help: Vec<Span<'_>> = if is_searching {
+            vec!["Press ".into(), Span::raw("<Enter>").style(*INSTRUCTIONS_STYLE), " to search".into()]
+        } else {
+            vec![
+                "Press ".into(),
+                Span::raw("<s>").style(*INSTRUCTIONS_STYLE),
+                " to filter mangas, ".into(),
+                Span::raw("#tag").style(*INSTRUCTIONS_STYLE),
+                " filters by tag".into(),
+            ]
+        };
+
+        render_search_bar(is_searching, input_help.into(), &self.search_bar, frame, search_bar_area);
+
+        let title = self.current_collection().map(|collection| collection.name.clone()).unwrap_or_default();
+
+        let mut instructions = vec![
+            "Open ".into(),
+            Span::raw("<r>").style(*INSTRUCTIONS_STYLE),
+            " Remove from collection ".into(),
+            Span::raw("<x>").style(*INSTRUCTIONS_STYLE),
+        ];
+
+        if self.error_opening_manga {
+            instructions.push(" | Could not open the manga, please try again".to_span().style(*ERROR_STYLE));
+        }
+
+        let block = Block::bordered().title(title.bold()).title_bottom(Line::from(instructions));
+        let inner = block.inner(mangas_area);
+        block.render(mangas_area, frame.buffer_mut());
+
+        self.render_mangas_list(inner, frame.buffer_mut());
+    }
+
+    fn render_mangas_list(&mut self, area: Rect, buf: &mut Buffer) {
+        match self.mangas.as_mut() {
+            Some(mangas) if !mangas.mangas.is_empty() => {
+                StatefulWidget::render(mangas.clone(), area, buf, &mut mangas.state);
+            },
+            _ if self.collections.is_empty() => {},
+            _ => Paragraph::new("No mangas here, add them from their page with <C>").render(area, buf),
+        }
+    }
+}
+
+impl<T: ApiClient> Component for LibraryPage<T> {
+    type Actions = LibraryActions;
+
+    fn render(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let [collections_area, mangas_area] =
+            Layout::horizontal([Constraint::Percentage(25), Constraint::Percentage(75)]).areas(area);
+
+        self.render_collections(collections_area, frame);
+        self.render_mangas(mangas_area, frame);
+    }
+
+    fn update(&mut self, action: Self::Actions) {
+        match action {
+            LibraryActions::NextCollection => self.select_collection(true),
+            LibraryActions::PreviousCollection => self.select_collection(false),
+            LibraryActions::ScrollMangasDown => {
+                if let Some(mangas) = self.mangas.as_mut() {
+                    mangas.select_next();
+                }
+            },
+            LibraryActions::ScrollMangasUp => {
+                if let Some(mangas) = self.mangas.as_mut() {
+                    mangas.select_previous();
+                }
+            },
+            LibraryActions::NextPage => self.search_next_page(),
+            LibraryActions::PreviousPage => self.search_previous_page(),
+            LibraryActions::ToggleSearchBar => {
+                self.input = if self.input == LibraryInput::Search { LibraryInput::None } else { LibraryInput::Search };
+            },
+            LibraryActions::SearchMangas => {
+                self.input = LibraryInput::None;
+                self.search_mangas(1);
+            },
+            LibraryActions::StartNamingCollection => self.input = LibraryInput::CollectionName,
+            LibraryActions::CancelNamingCollection => {
+                self.collection_name.reset();
+                self.input = LibraryInput::None;
+            },
+            LibraryActions::CreateCollection => self.create_collection(),
+            LibraryActions::DeleteCollection => self.delete_collection_selected(),
+            LibraryActions::RemoveMangaSelected => self.remove_manga_selected(),
+            LibraryActions::GoToMangaPage => self.go_to_manga_page(),
+        }
+    }
+
+    fn handle_events(&mut self, events: Events) {
+        match events {
+            Events::Key(key_event) => self.handle_key_events(key_event),
+            Events::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
+            Events::Tick => self.tick(),
+            _ => {},
+        }
+    }
+
+    fn clean_up(&mut self) {
+        self.tasks.abort_all();
+        self.search_bar.reset();
+        self.collection_name.reset();
+        self.input = LibraryInput::None;
+        self.is_confirming_delete = false;
+        self.error_opening_manga = false;
+        self.mangas = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use manga_tui::TagName;
+    use pretty_assertions::assert_eq;
+    use rusqlite::Connection;
+
+    use super::*;
+    use crate::backend::fetch::fake_api_client::MockMangadexClient;
+    use crate::view::widgets::press_key;
+
+    fn type_text(page: &mut LibraryPage<MockMangadexClient>, text: &str) {
+        for character in text.chars() {
+            press_key(page, KeyCode::Char(character));
+        }
+    }
+
+    fn test_database() -> DatabaseActor {
+        DatabaseActor::spawn(|| {
+            let connection = Connection::open_in_memory()?;
+            Database::new(&connection).setup()?;
+            Ok(connection)
+        })
+    }
+
+    /// Handles the results of the queries sent to the database, including the ones sent while handling them
+    fn wait_for_queries(library: &mut LibraryPage<MockMangadexClient>) {
+        loop {
+            assert!(library.database.flush(Duration::from_secs(5)));
+            if library.local_event_rx.is_empty() {
+                break;
+            }
+            library.tick();
+        }
+    }
+
+    #[tokio::test]
+    async fn it_creates_a_collection_and_browses_its_mangas() {
+        let database = test_database();
+
+        let mut library: LibraryPage<MockMangadexClient> = LibraryPage::new().with_database(database.clone());
+        library.load();
+        wait_for_queries(&mut library);
+
+        press_key(&mut library, KeyCode::Char('n'));
+        let action = library.local_action_rx.recv().await.unwrap();
+        library.update(action);
+
+        assert!(library.is_typing());
+
+        type_text(&mut library, "Favorites");
+        press_key(&mut library, KeyCode::Enter);
+
+        assert_eq!(LibraryActions::CreateCollection, library.local_action_rx.recv().await.unwrap());
+
+        library.create_collection();
+        wait_for_queries(&mut library);
+
+        assert!(!library.is_typing());
+        assert_eq!("Favorites", library.current_collection().unwrap().name);
+
+        let collection_id = library.current_collection().unwrap().id;
+        database
+            .run(move |conn| {
+                let database = Database::new(conn);
+                for index in 0..7 {
+                    let id = format!("manga_{index}");
+                    database.toggle_manga_in_collection(
+                        collection_id,
+                        MangaInsert {
+                            id: &id,
+                            title: &format!("title {index}"),
+                            img_url: None,
+                        },
+                    )?;
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        library.load();
+        wait_for_queries(&mut library);
+
+        assert_eq!(7, library.current_collection().unwrap().total_mangas);
+        assert_eq!(5, library.mangas.as_ref().unwrap().mangas.len());
+
+        library.search_next_page();
+        wait_for_queries(&mut library);
+
+        assert_eq!(2, library.mangas.as_ref().unwrap().page);
+        assert_eq!(2, library.mangas.as_ref().unwrap().mangas.len());
+
+        library.remove_manga_selected();
+        wait_for_queries(&mut library);
+
+        assert_eq!(6, library.current_collection().unwrap().total_mangas);
+        assert_eq!(1, library.mangas.as_ref().unwrap().mangas.len());
+
+        library.search_bar = Input::new("title 3".to_string());
+        library.search_mangas(1);
+        wait_for_queries(&mut library);
+
+        assert_eq!(1, library.mangas.as_ref().unwrap().total_results);
+
+        database
+            .run(|conn| {
+                Database::new(conn).add_manga_tag(
+                    MangaInsert {
+                        id: "manga_2",
+                        title: "title 2",
+                        img_url: None,
+                    },
+                    &TagName::new("comfy").unwrap(),
+                )
+            })
+            .await
+            .unwrap();
+
+        library.search_bar = Input::new("#comfy".to_string());
+        library.search_mangas(1);
+        wait_for_queries(&mut library);
+
+        assert_eq!(1, library.mangas.as_ref().unwrap().total_results);
+        assert_eq!("manga_2", library.mangas.as_ref().unwrap().mangas[0].id);
+    }
+
+    #[tokio::test]
+    async fn it_asks_for_confirmation_before_deleting_a_collection() {
+        let database = test_database();
+        database.run(|conn| Database::new(conn).create_collection("Favorites")).await.unwrap();
+
+        let mut library: LibraryPage<MockMangadexClient> = LibraryPage::new().with_database(database.clone());
+        library.load();
+        wait_for_queries(&mut library);
+
+        library.delete_collection_selected();
+        wait_for_queries(&mut library);
+
+        assert!(library.is_confirming_delete);
+        assert_eq!(1, database.run(|conn| Database::new(conn).get_collections()).await.unwrap().len());
+
+        press_key(&mut library, KeyCode::Char('j'));
+
+        assert!(!library.is_confirming_delete);
+
+        library.delete_collection_selected();
+        library.delete_collection_selected();
+        wait_for_queries(&mut library);
+
+        assert!(database.run(|conn| Database::new(conn).get_collections()).await.unwrap().is_empty());
+        assert!(library.current_collection().is_none());
+        assert!(library.mangas.is_none());
+    }
+}
@@ -1,50 +1,79 @@
 use std::error::Error;
 use std::future::Future;
-use std::io::Cursor;
 
 use crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
-use image::io::Reader;
 use image::DynamicImage;
+use manga_tui::{ChapterNumber, SearchTerm, TagName};
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Style, Stylize};
+use ratatui::style::{Color, Style, Stylize};
 use ratatui::text::{Line, Span, ToSpan};
 use ratatui::widgets::{Block, Clear, List, ListState, Paragraph, StatefulWidget, Widget, Wrap};
 use ratatui::Frame;
 use ratatui_image::picker::Picker;
 use ratatui_image::protocol::Protocol;
 use ratatui_image::{Image, Resize};
-use strum::{Display, EnumIs};
+use rusqlite::Connection;
+use strum::{Display, EnumIs, IntoEnumIterator};
 use throbber_widgets_tui::{Throbber, ThrobberState};
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use tokio::task::JoinSet;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
 
 use super::reader::ChapterToRead;
-use crate::backend::api_responses::{ChapterResponse, MangaStatisticsResponse, Statistics};
+use crate::backend::api_responses::{ChapterData, ChapterResponse, MangaStatisticsResponse, Statistics};
+use crate::backend::bug_report::report_bug;
 use crate::backend::database::{
-    get_chapters_history_status, save_history, set_chapter_downloaded, Bookmark, ChapterBookmarked, ChapterToBookmark,
-    ChapterToSaveHistory, Database, MangaReadingHistorySave, RetrieveBookmark, SetChapterDownloaded, DBCONN,
+    get_chapters_history_status, save_history, set_chapter_downloaded, BlockScanlationGroup, Bookmark, ChapterBookmarked,
+    ChapterDownloaded, ChapterToBookmark, ChapterToSaveHistory, Collection, Database, DownloadAllJob, MangaInsert,
+    MangaReadingHistoryRetrieve, MangaReadingHistorySave, MarkAsRead, MatchedManga, RetrieveBookmark, ScanlationGroup,
+    SetChapterDownloaded,
 };
+use crate::backend::database_actor::DatabaseActor;
 use crate::backend::download::DownloadChapter;
 use crate::backend::error_log::{self, write_to_error_log, ErrorType};
 use crate::backend::fetch::{ApiClient, MangadexClient, ITEMS_PER_PAGE_CHAPTERS};
 use crate::backend::filter::Languages;
-use crate::backend::tracker::{track_manga, MangaTracker};
+use crate::backend::tracker::{
+    get_tracked_progress, track_manga, track_manga_entry, MangaTracker, TrackerEntry, TrackingStatus,
+};
 use crate::backend::tui::Events;
 use crate::backend::AppDirectories;
 use crate::common::{format_error_message_tracking_reading_history, Manga};
 use crate::config::MangaTuiConfig;
 use crate::global::{ERROR_STYLE, INSTRUCTIONS_STYLE};
-use crate::utils::{set_status_style, set_tags_style};
+use crate::utils::{
+    decode_bytes_to_image, extract_accent_color, from_manga_response, set_status_style, set_tags_style, spin_loader,
+};
 use crate::view::app::MangaToRead;
 use crate::view::tasks::manga::{
-    download_all_chapters, download_chapter_task, read_chapter, search_chapters_operation, ChapterArgs, DownloadAllChapters,
+    chapter_downloaded, describe_chapter_to_download, download_all_chapters, download_chapter_task, fetch_chapters_to_download,
+    fetch_chapters_up_to, read_chapter, search_chapters_operation, sync_chapters_read_mangadex, ChapterArgs, DownloadAllChapters,
 };
+use crate::view::widgets::confirmation::{ConfirmationScreen, ConfirmationState};
+use crate::view::widgets::cover::{CoverPlaceholder, CoverViewer, CoverViewerState, PanDirection};
 use crate::view::widgets::manga::{
     ChapterItem, ChaptersListWidget, DownloadAllChaptersState, DownloadAllChaptersWidget, DownloadPhase,
 };
+use crate::view::widgets::search::MangaItem;
 use crate::view::widgets::Component;
 
+/// Some mangas have dozens of related entries like doujinshis, only the first ones are shown
+const MAX_RELATED_MANGAS: usize = 20;
+
+/// The preferred language if the manga is translated to it, otherwise the language the user has read the most chapters in
+/// out of the ones the manga is translated to, `languages_read` goes from the most read to the least
+fn suggest_chapter_language(available_languages: &[Languages], languages_read: &[Languages]) -> Option<Languages> {
+    let preferred = Languages::get_preferred_lang();
+
+    available_languages
+        .iter()
+        .find(|lang| *lang == preferred)
+        .or_else(|| languages_read.iter().find(|lang| available_languages.contains(lang)))
+        .copied()
+}
+
 #[derive(Debug, PartialEq, Eq, Default)]
 pub enum BookmarkPhase {
     SearchingFromApi,
@@ -76,6 +105,8 @@ pub enum MangaPageActions {
     GoToReadBookmarkedChapter,
     DownloadChapter,
     ConfirmDownloadAll,
+    /// Start downloading the chapters listed in the preview
+    DownloadChaptersPreviewed,
     CancelDownloadAll,
     AskDownloadAllChapters,
     AskAbortProcces,
@@ -93,6 +124,46 @@ pub enum MangaPageActions {
     SearchNextChapterPage,
     SearchPreviousChapterPage,
     BookMarkChapterSelected,
+    MarkReadUpToChapterSelected,
+    ToggleChapterSelectedRead,
+    BlockScanlationGroupSelected,
+    BlockScanlationGroupSelectedEverywhere,
+    UnblockScanlationGroups,
+    OpenDownloadLocation,
+    OpenExternalChapter,
+    ToggleRelatedMangasList,
+    ScrollDownRelatedMangas,
+    ScrollUpRelatedMangas,
+    GoToRelatedManga,
+    ToggleTrackerEntryForm,
+    ScrollDownTrackingStatus,
+    ScrollUpTrackingStatus,
+    IncreaseTrackerScore,
+    DecreaseTrackerScore,
+    SaveTrackerEntry,
+    ToggleTrackerBinding,
+    ScrollDownTrackerEntries,
+    ScrollUpTrackerEntries,
+    BindTrackerEntry,
+    ToggleCollectionsPopup,
+    ScrollDownCollections,
+    ScrollUpCollections,
+    ToggleInCollection,
+    ToggleTagsPopup,
+    ToggleTag,
+    ToggleFollowAuthor,
+    ToggleMergeConfirmation,
+    ScrollDownMergeConfirmation,
+    ScrollUpMergeConfirmation,
+    MergeDuplicate,
+    IncreasePersonalRating,
+    DecreasePersonalRating,
+    ReportBug,
+    ToggleCoverViewer,
+    PanCoverUp,
+    PanCoverDown,
+    PanCoverLeft,
+    PanCoverRight,
 }
 
 #[derive(Debug, PartialEq, EnumIs)]
@@ -102,18 +173,32 @@ pub enum MangaPageEvents {
     SearchChapters,
     SearchCover,
     FetchChapterBookmarked(ChapterBookmarked),
-    LoadCover(DynamicImage),
+    LoadCover(Option<DynamicImage>),
+    /// The cover at the highest quality, shown full-screen
+    LoadFullCover(Option<DynamicImage>),
     FethStatistics,
+    SearchRelatedMangas,
+    LoadRelatedMangas(Vec<Manga>),
     CheckChapterStatus,
+    /// Whether each chapter is read or downloaded and its progress, as stored in the database
+    LoadChaptersStatus(Vec<MangaReadingHistoryRetrieve>),
+    FetchTrackerProgress,
+    /// Amount of chapters read according to the tracker
+    LoadTrackerProgress(u32),
+    LoadTrackerEntries(Vec<TrackerEntry>),
     ChapterFinishedDownloading(String),
     DownloadAllChaptersError,
+    /// The chapters that will be downloaded if the user confirms
+    LoadDownloadAllPreview(Vec<ChapterData>),
+    /// Ids of the chapters up to the one selected, the chapter and volume number sent to the trackers
+    MarkChaptersAsRead(Vec<String>, (u32, Option<u32>)),
     /// Percentage, id chapter
     SetDownloadProgress(f64, String),
     StartDownloadProgress(f64),
     SetDownloadAllChaptersProgress,
     FinishedDownloadingAllChapters,
     /// id_chapter, chapter_title
-    SaveChapterDownloadStatus(String, String),
+    SaveChapterDownloadStatus(String, String, ChapterDownloaded),
     /// id_chapter
     DownloadError(String),
     ReadError(String),
@@ -122,6 +207,47 @@ pub enum MangaPageEvents {
     LoadChapters(Option<ChapterResponse>),
     LoadStatistics(Option<MangaStatisticsResponse>),
     TrackingFailed(String),
+    SearchStoredData,
+
+    // The results of the queries sent to the database actor
+    LoadStoredData(Box<StoredMangaData>),
+    NoChapterBookmarked,
+    /// id_chapter
+    ChapterBookmarked(String),
+    /// Ids of the chapters, the chapter and volume number sent to the trackers
+    ChaptersMarkedAsRead(Vec<String>, (u32, Option<u32>)),
+    /// Ids of the chapters read according to the tracker
+    ChaptersMarkedAsReadInTracker(Vec<String>),
+    /// id_chapter, whether it is read now
+    ChapterReadToggled(String, bool),
+    /// Id of the scanlation group
+    ScanlationGroupBlocked(String),
+    /// Ids of the groups which are still blocked
+    LoadBlockedScanlationGroups(Vec<String>),
+    /// Every collection and the ids of the ones the manga is in
+    LoadCollectionsPopup(Vec<Collection>, Vec<i64>),
+    /// Id of the collection, whether the manga is in it now
+    InCollectionToggled(i64, bool),
+    /// Tags of the manga and every tag the user has
+    LoadTagsPopup(Vec<String>, Vec<String>),
+    LoadTags(Vec<String>, Vec<String>),
+    LoadFollowingAuthor(bool),
+    DuplicateMerged,
+    LoadPersonalRating(Option<u8>),
+    TrackerEntryBound,
+    /// Ids of the chapters already downloaded, which are skipped
+    ResumeDownloadAllChapters(Languages, Vec<String>),
+}
+
+/// What the user saved about the manga, loaded from the database once the page is opened
+#[derive(Debug, Default, PartialEq)]
+pub struct StoredMangaData {
+    blocked_scanlation_groups: Vec<String>,
+    personal_rating: Option<u8>,
+    /// From the most read to the least
+    languages_read: Vec<Languages>,
+    is_following_author: bool,
+    duplicate: Option<MatchedManga>,
 }
 
 #[derive(Display, Default, Clone, Copy, Debug, PartialEq, Eq)]
@@ -152,7 +278,11 @@ pub trait FetchChapterBookmarked: Send + Clone + 'static {
 pub struct MangaPage<T: MangaTracker> {
     pub manga: Manga,
     image_state: Option<Box<dyn Protocol>>,
+    /// The manga has no cover or it could not be loaded
+    cover_failed: bool,
     cover_area: Rect,
+    /// Taken from the cover and used for the borders of the page
+    accent_color: Option<Color>,
     global_event_tx: Option<UnboundedSender<Events>>,
     local_action_tx: UnboundedSender<MangaPageActions>,
     pub local_action_rx: UnboundedReceiver<MangaPageActions>,
@@ -169,7 +299,98 @@ pub struct MangaPage<T: MangaTracker> {
     available_languages_state: ListState,
     is_list_languages_open: bool,
     download_all_chapters_state: DownloadAllChaptersState,
-    manga_tracker: Option<T>,
+    /// Fetched when the user asks to download all chapters, they are downloaded once the user confirms
+    chapters_to_download_all: Vec<ChapterData>,
+    /// Progress is pushed to all of them, but entries are bound and progress is read only from the first one
+    manga_trackers: Vec<T>,
+    /// Ids of the groups whose chapters are not requested, be it blocked for this manga or for all of them
+    blocked_scanlation_groups: Vec<String>,
+    /// Sequels, prequels and so on, in the same order as `manga.related`
+    related_mangas: Vec<RelatedMangaItem>,
+    related_mangas_state: ListState,
+    is_list_related_mangas_open: bool,
+    /// Open while the user is choosing the status and score to set on the tracker
+    tracker_entry_form: Option<TrackerEntryForm>,
+    /// Chapters up to this number are marked as read, since they may have been read on another device
+    tracker_progress: Option<u32>,
+    /// Open while the user is choosing which entry of the tracker this manga is bound to
+    tracker_binding: Option<TrackerBindingPopup>,
+    /// Open while the user is adding this manga to the collections of the library or removing it from them
+    collections_popup: Option<CollectionsPopup>,
+    /// Open while the user is typing the tags to add to this manga or remove from it
+    tags_popup: Option<TagsPopup>,
+    /// The 1-10 rating the user gave to this manga
+    personal_rating: Option<u8>,
+    /// New mangas of followed authors are notified by `manga-tui check-chapters`
+    is_following_author: bool,
+    /// Open while the user is looking at the cover full-screen
+    cover_viewer: Option<CoverViewerState>,
+    /// Another manga of the library with the same title, offered to be merged into this one
+    duplicate: Option<MatchedManga>,
+    /// Open while the user is asked to confirm merging the duplicate, since it moves it to the trash
+    merge_confirmation: Option<ConfirmationState>,
+    /// Every query is sent to it so the ui thread never waits for the database
+    database: DatabaseActor,
+}
+
+#[derive(Debug, Default)]
+struct TagsPopup {
+    tags: Vec<String>,
+    /// Every tag the user has, to reuse them instead of making a slightly different one
+    all_tags: Vec<String>,
+    input: Input,
+}
+
+#[derive(Debug, Default)]
+struct CollectionsPopup {
+    collections: Vec<Collection>,
+    /// Ids of the collections the manga is in
+    in_collection: Vec<i64>,
+    state: ListState,
+}
+
+/// The entries of the tracker matching the title of the manga, `None` while they are being searched
+#[derive(Debug, Default)]
+struct TrackerBindingPopup {
+    entries: Option<Vec<TrackerEntry>>,
+    state: ListState,
+}
+
+/// The status and score that will be set on the entry of this manga in the tracker
+#[derive(Debug, Default)]
+struct TrackerEntryForm {
+    status_state: ListState,
+    /// `None` keeps the score the entry already has
+    score: Option<u32>,
+}
+
+impl TrackerEntryForm {
+    fn new() -> Self {
+        Self {
+            status_state: ListState::default().with_selected(Some(0)),
+            score: None,
+        }
+    }
+
+    fn status(&self) -> TrackingStatus {
+        self.status_state
+            .selected()
+            .and_then(|index| TrackingStatus::iter().nth(index))
+            .unwrap_or_default()
+    }
+
+    fn increase_score(&mut self) {
+        self.score = Some(self.score.map_or(1, |score| (score + 1).min(10)));
+    }
+
+    fn decrease_score(&mut self) {
+        self.score = self.score.filter(|score| *score > 1).map(|score| score - 1);
+    }
+}
+
+struct RelatedMangaItem {
+    relation: String,
+    manga: Manga,
 }
 
 struct MangaStatistics {
@@ -191,6 +412,38 @@ struct ChaptersData {
     total_result: u32,
 }
 
+/// Owned `MangaInsert` which can be sent to the database actor
+struct MangaToInsert {
+    id: String,
+    title: String,
+    img_url: Option<String>,
+}
+
+impl MangaToInsert {
+    fn as_insert(&self) -> MangaInsert<'_> {
+        MangaInsert {
+            id: &self.id,
+            title: &self.title,
+            img_url: self.img_url.as_deref(),
+        }
+    }
+}
+
+/// So that the library can be searched by any of the titles the manga is known by, its description or genres, nothing is
+/// saved if it is not in the library
+fn save_search_data(database: &DatabaseActor, manga: &Manga) {
+    let manga_id = manga.id.clone();
+    let alt_titles = manga.alt_titles.clone();
+    let description = manga.description.clone();
+    let genres = manga.tags.clone();
+
+    database.execute(move |conn| {
+        let database = Database::new(conn);
+        database.save_alt_titles(&manga_id, &alt_titles)?;
+        database.save_metadata(&manga_id, &description, &genres)
+    });
+}
+
 impl<T: MangaTracker> MangaPage<T> {
     pub fn new(manga: Manga, picker: Option<Picker>) -> Self {
         let (local_action_tx, local_action_rx) = mpsc::unbounded_channel::<MangaPageActions>();
@@ -199,18 +452,25 @@ impl<T: MangaTracker> MangaPage<T> {
         local_event_tx.send(MangaPageEvents::SearchChapters).ok();
         local_event_tx.send(MangaPageEvents::FethStatistics).ok();
         local_event_tx.send(MangaPageEvents::SearchCover).ok();
+        local_event_tx.send(MangaPageEvents::FetchTrackerProgress).ok();
+
+        if !manga.related.is_empty() {
+            local_event_tx.send(MangaPageEvents::SearchRelatedMangas).ok();
+        }
+
+        local_event_tx.send(MangaPageEvents::SearchStoredData).ok();
 
         let cover_area = Rect::default();
 
-        let chapter_language = manga
-            .available_languages
-            .iter()
-            .find(|lang| *lang == Languages::get_preferred_lang())
-            .cloned();
+        // The languages the user reads the most are known once the stored data is loaded, chapters are searched again if
+        // the language suggested changes
+        let chapter_language = suggest_chapter_language(&manga.available_languages, &[]);
 
         Self {
             manga,
             image_state: None,
+            cover_failed: false,
+            accent_color: None,
             picker,
             global_event_tx: None,
             local_action_tx,
@@ -226,9 +486,25 @@ impl<T: MangaTracker> MangaPage<T> {
             available_languages_state: ListState::default(),
             is_list_languages_open: false,
             download_all_chapters_state: DownloadAllChaptersState::new(local_event_tx),
-            chapter_language: chapter_language.unwrap_or(Languages::default()),
+            chapters_to_download_all: vec![],
+            chapter_language: chapter_language.unwrap_or_default(),
             cover_area,
-            manga_tracker: None,
+            manga_trackers: vec![],
+            blocked_scanlation_groups: vec![],
+            related_mangas: vec![],
+            related_mangas_state: ListState::default(),
+            is_list_related_mangas_open: false,
+            tracker_entry_form: None,
+            tracker_progress: None,
+            tracker_binding: None,
+            collections_popup: None,
+            tags_popup: None,
+            personal_rating: None,
+            is_following_author: false,
+            cover_viewer: None,
+            duplicate: None,
+            merge_confirmation: None,
+            database: DatabaseActor::global().clone(),
         }
     }
 
@@ -242,11 +518,42 @@ impl<T: MangaTracker> MangaPage<T> {
         self
     }
 
-    pub fn with_manga_tracker(mut self, tracker: Option<T>) -> Self {
-        self.manga_tracker = tracker;
+    pub fn with_manga_trackers(mut self, trackers: Vec<T>) -> Self {
+        self.manga_trackers = trackers;
+        self
+    }
+
+    #[cfg(test)]
+    fn with_database(mut self, database: DatabaseActor) -> Self {
+        self.database = database;
         self
     }
 
+    /// Runs `query` in the database actor and sends the event made from its result, which is handled in a later tick.
+    /// Errors are logged and no event is sent
+    fn query_database<R>(
+        &self,
+        query: impl FnOnce(&mut Connection) -> Result<R, Box<dyn Error>> + Send + 'static,
+        into_event: impl FnOnce(R) -> MangaPageEvents + Send + 'static,
+    ) {
+        self.database.query(self.local_event_tx.clone(), query, into_event);
+    }
+
+    fn manga_to_insert(&self) -> MangaToInsert {
+        MangaToInsert {
+            id: self.manga.id.clone(),
+            title: self.manga.title.clone(),
+            img_url: self.manga.img_url.clone(),
+        }
+    }
+
+    fn bordered_block(&self) -> Block<'static> {
+        match self.accent_color {
+            Some(color) => Block::bordered().border_style(Style::new().fg(color)),
+            None => Block::bordered(),
+        }
+    }
+
     fn render_cover(&mut self, area: Rect, buf: &mut Buffer) {
         let [cover_area, more_details_area] =
             Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(area);
@@ -281,7 +588,10 @@ impl<T: MangaTracker> MangaPage<T> {
             },
             None => {
                 self.cover_area = cover_area;
-                Block::bordered().render(area, buf);
+                self.bordered_block().render(area, buf);
+                if self.picker.is_none() || self.cover_failed {
+                    CoverPlaceholder::new(&self.manga.title).render(cover_area, buf);
+                }
             },
         }
     }
@@ -298,19 +608,56 @@ impl<T: MangaTracker> MangaPage<T> {
             None => Span::raw("⭐ follows : "),
         };
 
+        let personal_rating = match self.personal_rating {
+            Some(rating) => Span::raw(format!("| Your rating : {rating}/10 ")).style(Style::default().fg(Color::Yellow)),
+            None => Span::raw("| Your rating : - "),
+        };
+
         let author_and_artist = Span::raw(format!("Author : {} | Artist : {}", self.manga.author.name, self.manga.artist.name));
 
         let go_to_author_artist_instructions = Span::raw("<c>/<v>").style(*INSTRUCTIONS_STYLE);
 
-        Block::bordered()
+        let mut bottom_instructions = vec![
+            statistics,
+            personal_rating,
+            Span::raw("<+><->").style(*INSTRUCTIONS_STYLE),
+            " | ".into(),
+            author_and_artist,
+            " | More about author/artist ".into(),
+            go_to_author_artist_instructions,
+            if self.is_following_author { " | Unfollow author ".into() } else { " | Follow author ".into() },
+            Span::raw("<F>").style(*INSTRUCTIONS_STYLE),
+            " | Collections ".into(),
+            Span::raw("<C>").style(*INSTRUCTIONS_STYLE),
+            " | Tags ".into(),
+            Span::raw("<T>").style(*INSTRUCTIONS_STYLE),
+        ];
+
+        if self.image_state.is_some() {
+            bottom_instructions.push(" | View cover ".into());
+            bottom_instructions.push(Span::raw("<z>").style(*INSTRUCTIONS_STYLE));
+        }
+
+        if !self.related_mangas.is_empty() {
+            bottom_instructions.push(format!(" | {} related ", self.related_mangas.len()).into());
+            bottom_instructions.push(Span::raw("<R>").style(*INSTRUCTIONS_STYLE));
+        }
+
+        if let Some(duplicate) = self.duplicate.as_ref() {
+            bottom_instructions.push(format!(" | Also in your library as \"{}\", merge ", duplicate.title).into());
+            bottom_instructions.push(Span::raw("<M>").style(*INSTRUCTIONS_STYLE));
+        }
+
+        if !self.manga_trackers.is_empty() {
+            bottom_instructions.push(" | Tracker status/score ".into());
+            bottom_instructions.push(Span::raw("<S>").style(*INSTRUCTIONS_STYLE));
+            bottom_instructions.push(" Wrong entry? ".into());
+            bottom_instructions.push(Span::raw("<B>").style(*INSTRUCTIONS_STYLE));
+        }
+
+        self.bordered_block()
             .title_top(self.manga.title.clone())
-            .title_bottom(Line::from(vec![
-                statistics,
-                " ".into(),
-                author_and_artist,
-                " | More about author/artist ".into(),
-                go_to_author_artist_instructions,
-            ]))
+            .title_bottom(Line::from(bottom_instructions))
             .render(manga_information_area, buf);
 
         self.render_details(manga_information_area, frame.buffer_mut());
@@ -332,11 +679,181 @@ impl<T: MangaTracker> MangaPage<T> {
 
         Paragraph::new(Line::from(tags)).wrap(Wrap { trim: true }).render(tags_area, buf);
 
+        if let Some(state) = self.merge_confirmation.as_mut() {
+            StatefulWidget::render(ConfirmationScreen::new("Merging the duplicate will"), description_area, buf, state);
+            return;
+        }
+
+        if self.tracker_binding.is_some() {
+            self.render_tracker_binding(description_area, buf);
+            return;
+        }
+
+        if self.collections_popup.is_some() {
+            self.render_collections_popup(description_area, buf);
+            return;
+        }
+
+        if self.tags_popup.is_some() {
+            self.render_tags_popup(description_area, buf);
+            return;
+        }
+
+        if self.tracker_entry_form.is_some() {
+            self.render_tracker_entry_form(description_area, buf);
+            return;
+        }
+
+        if self.is_list_related_mangas_open {
+            self.render_related_mangas_list(description_area, buf);
+            return;
+        }
+
         Paragraph::new(self.manga.description.clone())
             .wrap(Wrap { trim: true })
             .render(description_area, buf);
     }
 
+    fn render_related_mangas_list(&mut self, area: Rect, buf: &mut Buffer) {
+        let instructions = Line::from(vec![
+            "Close".into(),
+            Span::raw(" <Esc> ").style(*INSTRUCTIONS_STYLE),
+            "Up/Down".into(),
+            Span::raw(" <k><j> ").style(*INSTRUCTIONS_STYLE),
+            "Go to manga ".into(),
+            Span::raw("<Enter>").style(*INSTRUCTIONS_STYLE),
+        ]);
+
+        let related_mangas_list = List::new(
+            self.related_mangas
+                .iter()
+                .map(|related| format!("{} | {}", related.relation.replace('_', " "), related.manga.title)),
+        )
+        .block(Block::bordered().title(instructions))
+        .highlight_style(Style::default().on_blue());
+
+        StatefulWidget::render(related_mangas_list, area, buf, &mut self.related_mangas_state);
+    }
+
+    fn render_tracker_binding(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(popup) = self.tracker_binding.as_mut() else {
+            return;
+        };
+
+        let instructions = Line::from(vec![
+            "Close".into(),
+            Span::raw(" <Esc> ").style(*INSTRUCTIONS_STYLE),
+            "Up/Down".into(),
+            Span::raw(" <k><j> ").style(*INSTRUCTIONS_STYLE),
+            "Bind to this entry ".into(),
+            Span::raw("<Enter>").style(*INSTRUCTIONS_STYLE),
+        ]);
+
+        let block = Block::bordered().title(instructions);
+
+        match popup.entries.as_ref() {
+            None => Paragraph::new("Searching entries in the tracker").block(block).render(area, buf),
+            Some(entries) if entries.is_empty() => {
+                Paragraph::new("No entries found in the tracker").block(block).render(area, buf);
+            },
+            Some(entries) => {
+                let entries = List::new(entries.iter().map(|entry| format!("{} | {}", entry.id, entry.title)))
+                    .block(block)
+                    .highlight_style(Style::default().on_blue());
+
+                StatefulWidget::render(entries, area, buf, &mut popup.state);
+            },
+        }
+    }
+
+    fn render_collections_popup(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(popup) = self.collections_popup.as_mut() else {
+            return;
+        };
+
+        let instructions = Line::from(vec![
+            "Close".into(),
+            Span::raw(" <Esc> ").style(*INSTRUCTIONS_STYLE),
+            "Up/Down".into(),
+            Span::raw(" <k><j> ").style(*INSTRUCTIONS_STYLE),
+            "Add/remove ".into(),
+            Span::raw("<Enter>").style(*INSTRUCTIONS_STYLE),
+        ]);
+
+        let block = Block::bordered().title(instructions);
+
+        if popup.collections.is_empty() {
+            Paragraph::new("You have no collections yet, create one in the library page <F5>")
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        let collections = List::new(popup.collections.iter().map(|collection| {
+            let check = if popup.in_collection.contains(&collection.id) { "[x]" } else { "[ ]" };
+            format!("{check} {} ({})", collection.name, collection.total_mangas)
+        }))
+        .block(block)
+        .highlight_style(Style::default().on_blue());
+
+        StatefulWidget::render(collections, area, buf, &mut popup.state);
+    }
+
+    fn render_tags_popup(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(popup) = self.tags_popup.as_ref() else {
+            return;
+        };
+
+        let instructions = Line::from(vec![
+            "Close".into(),
+            Span::raw(" <Esc> ").style(*INSTRUCTIONS_STYLE),
+            "Add tag or remove it if the manga has it ".into(),
+            Span::raw("<Enter>").style(*INSTRUCTIONS_STYLE),
+        ]);
+
+        let tags = if popup.tags.is_empty() { "No tags yet".to_string() } else { popup.tags.join(", ") };
+
+        let lines = vec![
+            Line::from(vec!["Tags: ".bold(), tags.into()]),
+            Line::from(vec!["> ".yellow(), popup.input.value().into(), "_".yellow()]),
+            Line::default(),
+            Line::from(vec!["Your tags: ".bold(), popup.all_tags.join(", ").into()]),
+        ];
+
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title(instructions))
+            .render(area, buf);
+    }
+
+    fn render_tracker_entry_form(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(form) = self.tracker_entry_form.as_mut() else {
+            return;
+        };
+
+        let instructions = Line::from(vec![
+            "Close".into(),
+            Span::raw(" <Esc> ").style(*INSTRUCTIONS_STYLE),
+            "Status".into(),
+            Span::raw(" <k><j> ").style(*INSTRUCTIONS_STYLE),
+            "Score".into(),
+            Span::raw(" <-><+> ").style(*INSTRUCTIONS_STYLE),
+            "Save ".into(),
+            Span::raw("<Enter>").style(*INSTRUCTIONS_STYLE),
+        ]);
+
+        let score = match form.score {
+            Some(score) => format!("Score : {score}/10"),
+            None => "Score : unchanged".to_string(),
+        };
+
+        let statuses = List::new(TrackingStatus::iter().map(|status| status.to_string()))
+            .block(Block::bordered().title(instructions).title_bottom(score))
+            .highlight_style(Style::default().on_blue());
+
+        StatefulWidget::render(statuses, area, buf, &mut form.status_state);
+    }
+
     fn render_chapters_area(&mut self, area: Rect, buf: &mut Buffer) {
         let layout = Layout::vertical([Constraint::Percentage(10), Constraint::Percentage(90)]).margin(2);
 
@@ -347,6 +864,8 @@ impl<T: MangaTracker> MangaPage<T> {
             return;
         }
 
+        let block = self.bordered_block();
+
         match self.chapters.as_mut() {
             Some(chapters) => {
                 let tota_pages = chapters.total_result as f64 / 16_f64;
@@ -383,25 +902,54 @@ impl<T: MangaTracker> MangaPage<T> {
                     bottom_instructions.push(" Bookmark chapter ".into());
                     bottom_instructions.push("<m>".to_span().style(*INSTRUCTIONS_STYLE));
                 }
+                bottom_instructions.push(" Mark read up to here ".into());
+                bottom_instructions.push("<x>".to_span().style(*INSTRUCTIONS_STYLE));
+                bottom_instructions.push(" Read/unread ".into());
+                bottom_instructions.push("<X>".to_span().style(*INSTRUCTIONS_STYLE));
+                bottom_instructions.push(" Hide group here/everywhere ".into());
+                bottom_instructions.push("<g>/<G>".to_span().style(*INSTRUCTIONS_STYLE));
+                if !self.blocked_scanlation_groups.is_empty() {
+                    bottom_instructions.push(format!(" {} groups hidden, show them ", self.blocked_scanlation_groups.len()).into());
+                    bottom_instructions.push("<U>".to_span().style(*INSTRUCTIONS_STYLE));
+                }
+                if chapters
+                    .state
+                    .selected
+                    .and_then(|index| chapters.widget.chapters.get(index))
+                    .is_some_and(|chapter| chapter.download.is_some())
+                {
+                    bottom_instructions.push(" Open download location ".into());
+                    bottom_instructions.push("<O>".to_span().style(*INSTRUCTIONS_STYLE));
+                }
+                if chapters
+                    .state
+                    .selected
+                    .and_then(|index| chapters.widget.chapters.get(index))
+                    .is_some_and(|chapter| chapter.external_url.is_some())
+                {
+                    bottom_instructions.push(" Published outside mangadex, open in browser ".into());
+                    bottom_instructions.push("<r>".to_span().style(*INSTRUCTIONS_STYLE));
+                }
 
-                Block::bordered()
+                block
                     .title_top(Line::from(chapter_instructions))
                     .title_bottom(Line::from(bottom_instructions))
                     .render(area, buf);
 
-                StatefulWidget::render(chapters.widget.clone(), chapters_area, buf, &mut chapters.state);
+                StatefulWidget::render(&mut chapters.widget, chapters_area, buf, &mut chapters.state);
 
                 self.render_sorting_buttons(sorting_buttons_area, buf);
             },
 
             None => {
-                let title: Span<'_> = if self.state == PageState::ChaptersNotFound {
-                    "Could not get chapters, please try again".to_span().style(*ERROR_STYLE)
+                if self.state == PageState::ChaptersNotFound {
+                    block
+                        .title("Could not get chapters, please try again".to_span().style(*ERROR_STYLE))
+                        .title_bottom(Line::from(vec!["Report this bug ".into(), "<E>".to_span().style(*INSTRUCTIONS_STYLE)]))
+                        .render(area, buf);
                 } else {
-                    "Searching chapters".to_span()
-                };
-
-                Block::bordered().title(title).render(area, buf);
+                    block.title("Searching chapters").render(area, buf);
+                }
             },
         }
     }
@@ -472,7 +1020,124 @@ impl<T: MangaTracker> MangaPage<T> {
     }
 
     fn handle_key_events(&mut self, key_event: KeyEvent) {
-        if self.is_list_languages_open {
+        if self.cover_viewer.is_some() {
+            match key_event.code {
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.local_action_tx.send(MangaPageActions::PanCoverUp).ok();
+                },
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.local_action_tx.send(MangaPageActions::PanCoverDown).ok();
+                },
+                KeyCode::Char('h') | KeyCode::Left => {
+                    self.local_action_tx.send(MangaPageActions::PanCoverLeft).ok();
+                },
+                KeyCode::Char('l') | KeyCode::Right => {
+                    self.local_action_tx.send(MangaPageActions::PanCoverRight).ok();
+                },
+                KeyCode::Char('z') | KeyCode::Esc => {
+                    self.local_action_tx.send(MangaPageActions::ToggleCoverViewer).ok();
+                },
+                _ => {},
+            }
+        } else if self.merge_confirmation.is_some() {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.local_action_tx.send(MangaPageActions::ScrollDownMergeConfirmation).ok();
+                },
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.local_action_tx.send(MangaPageActions::ScrollUpMergeConfirmation).ok();
+                },
+                KeyCode::Enter => {
+                    self.local_action_tx.send(MangaPageActions::MergeDuplicate).ok();
+                },
+                KeyCode::Esc => {
+                    self.local_action_tx.send(MangaPageActions::ToggleMergeConfirmation).ok();
+                },
+                _ => {},
+            }
+        } else if let Some(popup) = self.tags_popup.as_mut() {
+            match key_event.code {
+                KeyCode::Enter => {
+                    self.local_action_tx.send(MangaPageActions::ToggleTag).ok();
+                },
+                KeyCode::Esc => {
+                    self.local_action_tx.send(MangaPageActions::ToggleTagsPopup).ok();
+                },
+                _ => {
+                    popup.input.handle_event(&crossterm::event::Event::Key(key_event));
+                },
+            }
+        } else if self.collections_popup.is_some() {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.local_action_tx.send(MangaPageActions::ScrollDownCollections).ok();
+                },
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.local_action_tx.send(MangaPageActions::ScrollUpCollections).ok();
+                },
+                KeyCode::Enter => {
+                    self.local_action_tx.send(MangaPageActions::ToggleInCollection).ok();
+                },
+                KeyCode::Char('C') | KeyCode::Esc => {
+                    self.local_action_tx.send(MangaPageActions::ToggleCollectionsPopup).ok();
+                },
+                _ => {},
+            }
+        } else if self.tracker_binding.is_some() {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.local_action_tx.send(MangaPageActions::ScrollDownTrackerEntries).ok();
+                },
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.local_action_tx.send(MangaPageActions::ScrollUpTrackerEntries).ok();
+                },
+                KeyCode::Enter => {
+                    self.local_action_tx.send(MangaPageActions::BindTrackerEntry).ok();
+                },
+                KeyCode::Char('B') | KeyCode::Esc => {
+                    self.local_action_tx.send(MangaPageActions::ToggleTrackerBinding).ok();
+                },
+                _ => {},
+            }
+        } else if self.tracker_entry_form.is_some() {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.local_action_tx.send(MangaPageActions::ScrollDownTrackingStatus).ok();
+                },
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.local_action_tx.send(MangaPageActions::ScrollUpTrackingStatus).ok();
+                },
+                KeyCode::Char('+') | KeyCode::Char('=') => {
+                    self.local_action_tx.send(MangaPageActions::IncreaseTrackerScore).ok();
+                },
+                KeyCode::Char('-') => {
+                    self.local_action_tx.send(MangaPageActions::DecreaseTrackerScore).ok();
+                },
+                KeyCode::Enter => {
+                    self.local_action_tx.send(MangaPageActions::SaveTrackerEntry).ok();
+                },
+                KeyCode::Char('S') | KeyCode::Esc => {
+                    self.local_action_tx.send(MangaPageActions::ToggleTrackerEntryForm).ok();
+                },
+                _ => {},
+            }
+        } else if self.is_list_related_mangas_open {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.local_action_tx.send(MangaPageActions::ScrollDownRelatedMangas).ok();
+                },
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.local_action_tx.send(MangaPageActions::ScrollUpRelatedMangas).ok();
+                },
+                KeyCode::Enter => {
+                    self.local_action_tx.send(MangaPageActions::GoToRelatedManga).ok();
+                },
+                KeyCode::Char('R') | KeyCode::Esc => {
+                    self.local_action_tx.send(MangaPageActions::ToggleRelatedMangasList).ok();
+                },
+                _ => {},
+            }
+        } else if self.is_list_languages_open {
             match key_event.code {
                 KeyCode::Char('j') | KeyCode::Down => {
                     self.local_action_tx.send(MangaPageActions::ScrollDownAvailbleLanguages).ok();
@@ -504,10 +1169,18 @@ impl<T: MangaTracker> MangaPage<T> {
                     KeyCode::Enter => {
                         if self.download_all_chapters_state.phase == DownloadPhase::AskAbortProcess {
                             self.local_action_tx.send(MangaPageActions::AbortDownloadAllChapters).ok();
+                        } else if self.download_all_chapters_state.phase == DownloadPhase::Previewing {
+                            self.local_action_tx.send(MangaPageActions::DownloadChaptersPreviewed).ok();
                         } else {
                             self.local_action_tx.send(MangaPageActions::ConfirmDownloadAll).ok();
                         }
                     },
+                    KeyCode::Char('j') | KeyCode::Down if self.download_all_chapters_state.phase == DownloadPhase::Previewing => {
+                        self.download_all_chapters_state.preview.scroll_down();
+                    },
+                    KeyCode::Char('k') | KeyCode::Up if self.download_all_chapters_state.phase == DownloadPhase::Previewing => {
+                        self.download_all_chapters_state.preview.scroll_up();
+                    },
 
                     _ => {},
                 }
@@ -522,6 +1195,11 @@ impl<T: MangaTracker> MangaPage<T> {
                     KeyCode::Char('t') => {
                         self.local_action_tx.send(MangaPageActions::ToggleOrder).ok();
                     },
+                    KeyCode::Char('r') | KeyCode::Enter
+                        if self.get_current_selected_chapter().is_some_and(|chapter| chapter.external_url.is_some()) =>
+                    {
+                        self.local_action_tx.send(MangaPageActions::OpenExternalChapter).ok();
+                    },
                     KeyCode::Char('r') | KeyCode::Enter => {
                         self.local_action_tx.send(MangaPageActions::ReadChapter).ok();
                     },
@@ -554,6 +1232,57 @@ impl<T: MangaTracker> MangaPage<T> {
                     KeyCode::Tab => {
                         self.local_action_tx.send(MangaPageActions::GoToReadBookmarkedChapter).ok();
                     },
+                    KeyCode::Char('x') => {
+                        self.local_action_tx.send(MangaPageActions::MarkReadUpToChapterSelected).ok();
+                    },
+                    KeyCode::Char('X') => {
+                        self.local_action_tx.send(MangaPageActions::ToggleChapterSelectedRead).ok();
+                    },
+                    KeyCode::Char('g') => {
+                        self.local_action_tx.send(MangaPageActions::BlockScanlationGroupSelected).ok();
+                    },
+                    KeyCode::Char('G') => {
+                        self.local_action_tx.send(MangaPageActions::BlockScanlationGroupSelectedEverywhere).ok();
+                    },
+                    KeyCode::Char('U') => {
+                        self.local_action_tx.send(MangaPageActions::UnblockScanlationGroups).ok();
+                    },
+                    KeyCode::Char('O') => {
+                        self.local_action_tx.send(MangaPageActions::OpenDownloadLocation).ok();
+                    },
+                    KeyCode::Char('R') if !self.related_mangas.is_empty() => {
+                        self.local_action_tx.send(MangaPageActions::ToggleRelatedMangasList).ok();
+                    },
+                    KeyCode::Char('S') if !self.manga_trackers.is_empty() => {
+                        self.local_action_tx.send(MangaPageActions::ToggleTrackerEntryForm).ok();
+                    },
+                    KeyCode::Char('B') if !self.manga_trackers.is_empty() => {
+                        self.local_action_tx.send(MangaPageActions::ToggleTrackerBinding).ok();
+                    },
+                    KeyCode::Char('C') => {
+                        self.local_action_tx.send(MangaPageActions::ToggleCollectionsPopup).ok();
+                    },
+                    KeyCode::Char('T') => {
+                        self.local_action_tx.send(MangaPageActions::ToggleTagsPopup).ok();
+                    },
+                    KeyCode::Char('F') => {
+                        self.local_action_tx.send(MangaPageActions::ToggleFollowAuthor).ok();
+                    },
+                    KeyCode::Char('M') if self.duplicate.is_some() => {
+                        self.local_action_tx.send(MangaPageActions::ToggleMergeConfirmation).ok();
+                    },
+                    KeyCode::Char('+') | KeyCode::Char('=') => {
+                        self.local_action_tx.send(MangaPageActions::IncreasePersonalRating).ok();
+                    },
+                    KeyCode::Char('-') => {
+                        self.local_action_tx.send(MangaPageActions::DecreasePersonalRating).ok();
+                    },
+                    KeyCode::Char('z') if self.image_state.is_some() => {
+                        self.local_action_tx.send(MangaPageActions::ToggleCoverViewer).ok();
+                    },
+                    KeyCode::Char('E') if self.state == PageState::ChaptersNotFound => {
+                        self.local_action_tx.send(MangaPageActions::ReportBug).ok();
+                    },
 
                     _ => {},
                 }
@@ -594,44 +1323,403 @@ impl<T: MangaTracker> MangaPage<T> {
         self.is_list_languages_open = !self.is_list_languages_open;
     }
 
-    fn get_current_selected_chapter_mut(&mut self) -> Option<&mut ChapterItem> {
-        match self.chapters.as_mut() {
-            Some(chapters_data) => match chapters_data.state.selected {
-                Some(selected_chapter_index) => return chapters_data.widget.chapters.get_mut(selected_chapter_index),
-                None => None,
-            },
-            None => None,
-        }
+    fn toggle_tracker_entry_form(&mut self) {
+        self.tracker_entry_form = match self.tracker_entry_form {
+            Some(_) => None,
+            None => Some(TrackerEntryForm::new()),
+        };
     }
 
-    fn get_current_selected_chapter(&self) -> Option<&ChapterItem> {
-        match self.chapters.as_ref() {
-            Some(chapters_data) => match chapters_data.state.selected {
-                Some(selected_chapter_index) => return chapters_data.widget.chapters.get(selected_chapter_index),
-                None => None,
+    fn save_tracker_entry(&mut self) {
+        let Some(form) = self.tracker_entry_form.take() else {
+            return;
+        };
+
+        let tx = self.local_event_tx.clone();
+        track_manga_entry(
+            self.manga_trackers.clone(),
+            self.manga.id.clone(),
+            self.manga.title.clone(),
+            form.status(),
+            form.score,
+            move |error| {
+                tx.send(MangaPageEvents::TrackingFailed(error)).ok();
             },
-            None => None,
-        }
+        );
     }
 
-    fn read_chapter(&mut self) {
-        if self.picker.is_none() {
+    fn toggle_tracker_binding(&mut self) {
+        if self.tracker_binding.take().is_some() {
             return;
         }
-        self.state = PageState::SearchingChapterData;
-        match self.get_current_selected_chapter_mut() {
-            Some(chapter_selected) => {
-                chapter_selected.set_normal_state();
-
-                let id_chapter = chapter_selected.id.clone();
-                let chapter_title = chapter_selected.title.clone();
-                let number: f64 = chapter_selected.chapter_number.parse().unwrap_or_default();
-                let volume_number = chapter_selected.volume_number.clone();
-                let language = self.get_current_selected_language();
-                let manga_id = self.manga.id.clone();
-                let title = self.manga.title.clone();
-                let img_url = self.manga.img_url.clone();
-                let local_tx = self.local_event_tx.clone();
+
+        let Some(tracker) = self.manga_trackers.first().cloned() else {
+            return;
+        };
+        let Some(title) = SearchTerm::trimmed(&self.manga.title) else {
+            return;
+        };
+
+        self.tracker_binding = Some(TrackerBindingPopup::default());
+
+        let tx = self.local_event_tx.clone();
+        self.tasks.spawn(async move {
+            let entries = match tracker.search_manga_entries(title).await {
+                Ok(entries) => entries,
+                Err(e) => {
+                    tx.send(MangaPageEvents::TrackingFailed(e.to_string())).ok();
+                    vec![]
+                },
+            };
+            tx.send(MangaPageEvents::LoadTrackerEntries(entries)).ok();
+        });
+    }
+
+    fn load_tracker_entries(&mut self, entries: Vec<TrackerEntry>) {
+        if let Some(popup) = self.tracker_binding.as_mut() {
+            if !entries.is_empty() {
+                popup.state.select_first();
+            }
+            popup.entries = Some(entries);
+        }
+    }
+
+    /// From now on the tracker is updated using the entry chosen instead of searching the manga by its title
+    fn bind_tracker_entry_selected(&mut self) {
+        let Some(tracker) = self.manga_trackers.first() else {
+            return;
+        };
+
+        let Some(entry) = self.tracker_binding.as_ref().and_then(|popup| {
+            popup
+                .state
+                .selected()
+                .and_then(|index| popup.entries.as_ref().and_then(|entries| entries.get(index)))
+        }) else {
+            return;
+        };
+
+        let manga_id = self.manga.id.clone();
+        let tracker_name = tracker.name();
+        let entry_id = entry.id.clone();
+
+        self.query_database(
+            move |conn| Ok(Database::new(conn).bind_tracker_entry(&manga_id, tracker_name, &entry_id)?),
+            |()| MangaPageEvents::TrackerEntryBound,
+        );
+    }
+
+    fn tracker_entry_bound(&mut self) {
+        self.tracker_binding = None;
+        self.local_event_tx.send(MangaPageEvents::FetchTrackerProgress).ok();
+    }
+
+    fn toggle_collections_popup(&mut self) {
+        if self.collections_popup.take().is_some() {
+            return;
+        }
+
+        let manga_id = self.manga.id.clone();
+
+        self.query_database(
+            move |conn| {
+                let database = Database::new(conn);
+                Ok((database.get_collections()?, database.get_manga_collections(&manga_id)?))
+            },
+            |(collections, in_collection)| MangaPageEvents::LoadCollectionsPopup(collections, in_collection),
+        );
+    }
+
+    fn load_collections_popup(&mut self, collections: Vec<Collection>, in_collection: Vec<i64>) {
+        let mut state = ListState::default();
+        if !collections.is_empty() {
+            state.select_first();
+        }
+        self.collections_popup = Some(CollectionsPopup {
+            collections,
+            in_collection,
+            state,
+        });
+    }
+
+    fn toggle_in_collection_selected(&mut self) {
+        let Some(popup) = self.collections_popup.as_ref() else {
+            return;
+        };
+        let Some(collection_id) = popup
+            .state
+            .selected()
+            .and_then(|index| popup.collections.get(index))
+            .map(|collection| collection.id)
+        else {
+            return;
+        };
+
+        let manga = self.manga_to_insert();
+
+        self.query_database(
+            move |conn| Ok(Database::new(conn).toggle_manga_in_collection(collection_id, manga.as_insert())?),
+            move |is_in_collection| MangaPageEvents::InCollectionToggled(collection_id, is_in_collection),
+        );
+    }
+
+    fn in_collection_toggled(&mut self, collection_id: i64, is_in_collection: bool) {
+        let Some(popup) = self.collections_popup.as_mut() else {
+            return;
+        };
+        let Some(collection) = popup.collections.iter_mut().find(|collection| collection.id == collection_id) else {
+            return;
+        };
+
+        if is_in_collection {
+            collection.total_mangas += 1;
+            popup.in_collection.push(collection_id);
+        } else {
+            collection.total_mangas = collection.total_mangas.saturating_sub(1);
+            popup.in_collection.retain(|id| *id != collection_id);
+        }
+    }
+
+    fn toggle_tags_popup(&mut self) {
+        if self.tags_popup.take().is_some() {
+            return;
+        }
+
+        let manga_id = self.manga.id.clone();
+
+        self.query_database(
+            move |conn| {
+                let database = Database::new(conn);
+                Ok((database.get_manga_tags(&manga_id)?, database.get_tags()?))
+            },
+            |(tags, all_tags)| MangaPageEvents::LoadTagsPopup(tags, all_tags),
+        );
+    }
+
+    fn load_tags_popup(&mut self, tags: Vec<String>, all_tags: Vec<String>) {
+        self.tags_popup = Some(TagsPopup {
+            tags,
+            all_tags,
+            input: Input::default(),
+        });
+    }
+
+    /// Adds the tag typed to the manga, or removes it if the manga already has it
+    fn toggle_tag(&mut self) {
+        let Some(popup) = self.tags_popup.as_mut() else {
+            return;
+        };
+        let Some(tag) = TagName::new(popup.input.value()) else {
+            return;
+        };
+
+        popup.input.reset();
+
+        let has_tag = popup.tags.iter().any(|name| name == tag.get());
+        let manga = self.manga_to_insert();
+
+        self.query_database(
+            move |conn| {
+                let database = Database::new(conn);
+                if has_tag {
+                    database.remove_manga_tag(&manga.id, &tag)?;
+                } else {
+                    database.add_manga_tag(manga.as_insert(), &tag)?;
+                }
+                Ok((database.get_manga_tags(&manga.id)?, database.get_tags()?))
+            },
+            |(tags, all_tags)| MangaPageEvents::LoadTags(tags, all_tags),
+        );
+    }
+
+    fn load_tags(&mut self, tags: Vec<String>, all_tags: Vec<String>) {
+        if let Some(popup) = self.tags_popup.as_mut() {
+            popup.tags = tags;
+            popup.all_tags = all_tags;
+        }
+    }
+
+    fn toggle_follow_author(&mut self) {
+        let author = self.manga.author.clone();
+        if author.id.is_empty() {
+            return;
+        }
+
+        self.query_database(
+            move |conn| Ok(Database::new(conn).toggle_followed_author(&author.id, &author.name)?),
+            MangaPageEvents::LoadFollowingAuthor,
+        );
+    }
+
+    /// Lists what merging does before doing it, the duplicate ends up in the trash
+    fn toggle_merge_confirmation(&mut self) {
+        if self.merge_confirmation.take().is_some() {
+            return;
+        }
+
+        let Some(duplicate) = self.duplicate.as_ref() else {
+            return;
+        };
+
+        self.merge_confirmation = Some(ConfirmationState::new(vec![
+            format!("Move \"{}\" to the trash", duplicate.title),
+            format!("Move its chapters read, lists, tags, collections and rating to \"{}\"", self.manga.title),
+        ]));
+    }
+
+    fn merge_duplicate(&mut self) {
+        if self.merge_confirmation.take().is_none() {
+            return;
+        }
+
+        let Some(duplicate_id) = self.duplicate.as_ref().map(|duplicate| duplicate.id.clone()) else {
+            return;
+        };
+
+        let manga = self.manga_to_insert();
+
+        self.query_database(
+            move |conn| Ok(Database::new(conn).merge_duplicate(&duplicate_id, manga.as_insert())?),
+            |()| MangaPageEvents::DuplicateMerged,
+        );
+    }
+
+    /// The chapters read of the duplicate are now this manga's, so their status is checked again
+    fn duplicate_merged(&mut self) {
+        self.duplicate = None;
+        self.local_event_tx.send(MangaPageEvents::CheckChapterStatus).ok();
+    }
+
+    /// Goes from 1 to 10, lowering it below 1 removes the rating
+    fn change_personal_rating(&mut self, increase: bool) {
+        let rating = match (self.personal_rating, increase) {
+            (None, true) => Some(1),
+            (None, false) => None,
+            (Some(rating), true) => Some(rating.saturating_add(1).min(10)),
+            (Some(rating), false) => rating.checked_sub(1).filter(|rating| *rating > 0),
+        };
+
+        let manga = self.manga_to_insert();
+
+        self.query_database(
+            move |conn| Ok(Database::new(conn).set_personal_rating(manga.as_insert(), rating)?),
+            move |()| MangaPageEvents::LoadPersonalRating(rating),
+        );
+    }
+
+    fn toggle_related_mangas_list(&mut self) {
+        self.is_list_related_mangas_open = !self.is_list_related_mangas_open;
+        if self.related_mangas_state.selected().is_none() {
+            self.related_mangas_state.select_first();
+        }
+    }
+
+    fn go_to_related_manga(&mut self) {
+        let Some(related) = self.related_mangas_state.selected().and_then(|index| self.related_mangas.get(index)) else {
+            return;
+        };
+
+        self.global_event_tx
+            .as_ref()
+            .unwrap()
+            .send(Events::GoToMangaPage(MangaItem::new(related.manga.clone())))
+            .ok();
+    }
+
+    fn search_related_mangas(&mut self) {
+        let tx = self.local_event_tx.clone();
+        let related_ids: Vec<String> = self
+            .manga
+            .related
+            .iter()
+            .take(MAX_RELATED_MANGAS)
+            .map(|related| related.id.clone())
+            .collect();
+
+        self.tasks.spawn(async move {
+            let ids: Vec<&str> = related_ids.iter().map(String::as_str).collect();
+
+            match MangadexClient::global().get_mangas_by_ids(&ids).await {
+                Ok(response) => {
+                    let mangas = response.data.into_iter().map(from_manga_response).collect();
+                    tx.send(MangaPageEvents::LoadRelatedMangas(mangas)).ok();
+                },
+                Err(e) => write_to_error_log(ErrorType::Error(e)),
+            }
+        });
+    }
+
+    /// Mangadex does not return the mangas in the order they were requested, so they are sorted as in `manga.related`
+    fn load_related_mangas(&mut self, mut mangas: Vec<Manga>) {
+        self.related_mangas = self
+            .manga
+            .related
+            .iter()
+            .filter_map(|related| {
+                let index = mangas.iter().position(|manga| manga.id == related.id)?;
+                Some(RelatedMangaItem {
+                    relation: related.relation.clone(),
+                    manga: mangas.swap_remove(index),
+                })
+            })
+            .collect();
+    }
+
+    fn get_current_selected_chapter_mut(&mut self) -> Option<&mut ChapterItem> {
+        match self.chapters.as_mut() {
+            Some(chapters_data) => match chapters_data.state.selected {
+                Some(selected_chapter_index) => return chapters_data.widget.chapters.get_mut(selected_chapter_index),
+                None => None,
+            },
+            None => None,
+        }
+    }
+
+    /// Show the file or directory of the selected chapter in the system file manager
+    fn open_download_location(&self) {
+        if let Some(download) = self.get_current_selected_chapter().and_then(|chapter| chapter.download.as_ref()) {
+            if let Err(e) = open::that(download.location()) {
+                write_to_error_log(ErrorType::Error(Box::new(e)));
+            }
+        }
+    }
+
+    fn open_external_chapter(&self) {
+        if let Some(url) = self.get_current_selected_chapter().and_then(|chapter| chapter.external_url.as_ref()) {
+            if let Err(e) = open::that(url) {
+                write_to_error_log(ErrorType::Error(Box::new(e)));
+            }
+        }
+    }
+
+    fn get_current_selected_chapter(&self) -> Option<&ChapterItem> {
+        match self.chapters.as_ref() {
+            Some(chapters_data) => match chapters_data.state.selected {
+                Some(selected_chapter_index) => return chapters_data.widget.chapters.get(selected_chapter_index),
+                None => None,
+            },
+            None => None,
+        }
+    }
+
+    fn read_chapter(&mut self) {
+        if self.picker.is_none() {
+            return;
+        }
+        self.state = PageState::SearchingChapterData;
+        match self.get_current_selected_chapter_mut() {
+            Some(chapter_selected) => {
+                chapter_selected.set_normal_state();
+
+                let id_chapter = chapter_selected.id.clone();
+                let chapter_title = chapter_selected.title.clone();
+                let number = ChapterNumber::parse(&chapter_selected.chapter_number).as_f64();
+                let volume_number = chapter_selected.volume_number.clone();
+                let language = self.get_current_selected_language();
+                let manga_id = self.manga.id.clone();
+                let title = self.manga.title.clone();
+                let img_url = self.manga.img_url.clone();
+                let local_tx = self.local_event_tx.clone();
 
                 let chapter_to_read: ChapterArgs = ChapterArgs {
                     id_chapter,
@@ -645,7 +1733,7 @@ impl<T: MangaTracker> MangaPage<T> {
                 };
 
                 tokio::spawn(async move {
-                    let search_chapter_response = read_chapter(&chapter_to_read).await;
+                    let search_chapter_response = read_chapter(MangadexClient::global(), &chapter_to_read).await;
 
                     match search_chapter_response {
                         Ok((chapter, manga_to_read)) => {
@@ -702,7 +1790,10 @@ impl<T: MangaTracker> MangaPage<T> {
 
         let page = if let Some(chapters) = self.chapters.as_ref() { chapters.page } else { 1 };
 
-        self.tasks.spawn(search_chapters_operation(manga_id, page, language, chapter_order, tx));
+        let excluded_groups = self.blocked_scanlation_groups.clone();
+
+        self.tasks
+            .spawn(search_chapters_operation(manga_id, page, language, chapter_order, excluded_groups, tx));
     }
 
     fn fetch_statistics(&mut self) {
@@ -725,25 +1816,63 @@ impl<T: MangaTracker> MangaPage<T> {
         });
     }
 
+    fn search_stored_data(&mut self) {
+        save_search_data(&self.database, &self.manga);
+
+        let manga_id = self.manga.id.clone();
+        let author_id = self.manga.author.id.clone();
+        let titles = [std::slice::from_ref(&self.manga.title), self.manga.alt_titles.as_slice()].concat();
+
+        // Nothing stored is not an error, so the chapters are always searched with what could be loaded
+        self.query_database(
+            move |conn| {
+                let database = Database::new(conn);
+                let languages_read = database.get_chapters_read_by_language().unwrap_or_default();
+
+                Ok(StoredMangaData {
+                    blocked_scanlation_groups: database.get_blocked_groups(&manga_id).unwrap_or_default(),
+                    personal_rating: database.get_personal_rating(&manga_id).unwrap_or_default(),
+                    languages_read: languages_read.iter().filter_map(|(code, _)| Languages::try_from_iso_code(code)).collect(),
+                    is_following_author: database.is_following_author(&author_id).unwrap_or_default(),
+                    duplicate: database.find_duplicate(&manga_id, &titles).unwrap_or_default(),
+                })
+            },
+            |data| MangaPageEvents::LoadStoredData(Box::new(data)),
+        );
+    }
+
+    fn load_stored_data(&mut self, data: StoredMangaData) {
+        let chapter_language = suggest_chapter_language(&self.manga.available_languages, &data.languages_read).unwrap_or_default();
+        let search_again = chapter_language != self.chapter_language || !data.blocked_scanlation_groups.is_empty();
+
+        self.chapter_language = chapter_language;
+        self.blocked_scanlation_groups = data.blocked_scanlation_groups;
+        self.personal_rating = data.personal_rating;
+        self.is_following_author = data.is_following_author;
+        self.duplicate = data.duplicate;
+
+        if search_again {
+            self.local_event_tx.send(MangaPageEvents::SearchChapters).ok();
+        }
+    }
+
     fn check_chapters_read(&mut self) {
-        let binding = DBCONN.lock().unwrap();
-        let conn = binding.as_ref().unwrap();
-        let history = get_chapters_history_status(&self.manga.id, conn);
-        match history {
-            Ok(his) => {
-                if let Some(chapters) = self.chapters.as_mut() {
-                    for chapter in chapters.widget.chapters.iter_mut() {
-                        let chapter_found = his.iter().find(|chap| chap.id == chapter.id);
-                        if let Some(chapt) = chapter_found {
-                            chapter.is_read = chapt.is_read;
-                            chapter.is_downloaded = chapt.is_downloaded
-                        }
-                    }
+        let manga_id = self.manga.id.clone();
+
+        self.query_database(move |conn| Ok(get_chapters_history_status(&manga_id, conn)?), MangaPageEvents::LoadChaptersStatus);
+    }
+
+    fn load_chapters_status(&mut self, history: Vec<MangaReadingHistoryRetrieve>) {
+        if let Some(chapters) = self.chapters.as_mut() {
+            for chapter in chapters.widget.chapters.iter_mut() {
+                let chapter_found = history.iter().find(|chap| chap.id == chapter.id);
+                if let Some(chapt) = chapter_found {
+                    chapter.is_read = chapt.is_read;
+                    chapter.is_downloaded = chapt.is_downloaded;
+                    chapter.download = chapt.download.clone();
+                    chapter.progress = chapt.progress;
                 }
-            },
-            Err(e) => {
-                write_to_error_log(error_log::ErrorType::Error(Box::new(e)));
-            },
+            }
         }
     }
 
@@ -753,60 +1882,350 @@ impl<T: MangaTracker> MangaPage<T> {
         }
     }
 
-    fn bookmark_current_chapter_selected(&mut self, database: &mut dyn Bookmark) {
-        self.clear_chapters_as_bookmarked();
-        let manga_id = self.manga.id.clone();
-        let manga_title = self.manga.title.clone();
-        let cover_img_url = self.manga.img_url.clone();
+    fn bookmark_current_chapter_selected(&mut self) {
+        let Some(chapter_selected) = self.get_current_selected_chapter() else {
+            return;
+        };
+
+        let chapter_id = chapter_selected.id.clone();
+        let chapter_title = chapter_selected.title.clone();
+        let manga = self.manga_to_insert();
         let chapter_language = self.get_current_selected_language();
-        if let Some(chapter_selected) = self.get_current_selected_chapter_mut() {
-            let chapter_to_bookmark: ChapterToBookmark = ChapterToBookmark {
-                chapter_id: &chapter_selected.id,
-                manga_id: &manga_id,
-                chapter_title: &chapter_selected.title,
-                manga_title: &manga_title,
-                manga_cover_url: cover_img_url.as_deref(),
-                translated_language: chapter_language,
-                page_number: None,
-            };
 
-            match database.bookmark(chapter_to_bookmark) {
-                Ok(()) => chapter_selected.is_bookmarked = true,
-                Err(e) => write_to_error_log(ErrorType::Error(e)),
-            }
+        self.query_database(
+            move |conn| {
+                let mut database = Database::new(conn);
+                database.bookmark(ChapterToBookmark {
+                    chapter_id: &chapter_id,
+                    manga_id: &manga.id,
+                    chapter_title: &chapter_title,
+                    manga_title: &manga.title,
+                    manga_cover_url: manga.img_url.as_deref(),
+                    translated_language: chapter_language,
+                    page_number: None,
+                })?;
+                Ok(chapter_id)
+            },
+            MangaPageEvents::ChapterBookmarked,
+        );
+    }
+
+    /// Only one chapter is bookmarked at a time
+    fn set_chapter_bookmarked(&mut self, chapter_id: String) {
+        self.clear_chapters_as_bookmarked();
+        if let Some(chapter) = self
+            .chapters
+            .as_mut()
+            .and_then(|chapters| chapters.widget.chapters.iter_mut().find(|chapter| chapter.id == chapter_id))
+        {
+            chapter.is_bookmarked = true;
         }
     }
 
-    fn get_chapter_bookmarked_from_db(&mut self, datatabase: impl RetrieveBookmark) {
-        match datatabase.get_bookmarked(&self.manga.id) {
-            Ok(maybe_chapter) => match maybe_chapter {
-                Some(chapter) => {
-                    self.local_event_tx.send(MangaPageEvents::FetchChapterBookmarked(chapter)).ok();
-                },
-                None => {
-                    self.bookmark_state.phase = BookmarkPhase::NotFoundDatabase;
-                },
-            },
-            Err(e) => write_to_error_log(ErrorType::Error(e)),
+    /// Marking as read goes by chapter number through the whole list of chapters, so chapters in pages not loaded yet
+    /// are also marked
+    fn fetch_chapters_up_to_selected(&mut self) {
+        let Some(chapter) = self.get_current_selected_chapter() else {
+            return;
         };
-    }
 
-    fn fetch_chapter_bookmarked(&mut self, bookmarked_chapter: ChapterBookmarked, api_client: impl FetchChapterBookmarked) {
-        let sender = self.local_event_tx.clone();
-        self.bookmark_state.phase = BookmarkPhase::SearchingFromApi;
+        let chapter_number = ChapterNumber::parse(&chapter.chapter_number);
+        let tracker_progress =
+            (chapter_number.as_tracker_progress(), chapter.volume_number.as_ref().and_then(|volume| volume.parse().ok()));
+        let manga_id = self.manga.id.clone();
+        let language = self.get_current_selected_language();
+        let tx = self.local_event_tx.clone();
 
         self.tasks.spawn(async move {
-            let response = api_client.fetch_chapter_bookmarked(bookmarked_chapter).await;
-
-            match response {
-                Ok(response) => {
-                    sender.send(MangaPageEvents::ReadChapterBookmarked(response.0, response.1)).ok();
+            match fetch_chapters_up_to(MangadexClient::global(), &manga_id, language, chapter_number).await {
+                Ok(chapter_ids) => {
+                    tx.send(MangaPageEvents::MarkChaptersAsRead(chapter_ids, tracker_progress)).ok();
                 },
-                Err(e) => {
-                    #[cfg(not(test))]
-                    {
-                        write_to_error_log(ErrorType::Error(e));
-                    }
+                Err(e) => write_to_error_log(ErrorType::Error(e)),
+            }
+        });
+    }
+
+    /// Chapters which are not loaded are saved without a title
+    fn chapters_to_save_history(&self, chapter_ids: &[String]) -> Vec<(String, String)> {
+        let loaded_chapters = self
+            .chapters
+            .as_ref()
+            .map(|chapters| chapters.widget.chapters.as_slice())
+            .unwrap_or_default();
+
+        chapter_ids
+            .iter()
+            .map(|id| {
+                let title = loaded_chapters
+                    .iter()
+                    .find(|chapter| chapter.id == *id)
+                    .map(|chapter| chapter.title.clone())
+                    .unwrap_or_default();
+                (id.clone(), title)
+            })
+            .collect()
+    }
+
+    /// `tracker_progress` is the chapter and volume number of the furthest chapter, which is sent to the trackers
+    fn mark_chapters_as_read(&mut self, chapter_ids: Vec<String>, tracker_progress: (u32, Option<u32>)) {
+        if chapter_ids.is_empty() {
+            return;
+        }
+
+        let translated_language = self.get_current_selected_language().as_iso_code();
+        let chapters = self.chapters_to_save_history(&chapter_ids);
+        let manga = self.manga_to_insert();
+
+        self.query_database(
+            move |conn| {
+                let mut database = Database::new(conn);
+                let chapters_to_mark: Vec<ChapterToSaveHistory<'_>> = chapters
+                    .iter()
+                    .map(|(id, title)| ChapterToSaveHistory {
+                        id,
+                        title,
+                        translated_language,
+                    })
+                    .collect();
+
+                database.mark_as_read(manga.as_insert(), &chapters_to_mark)
+            },
+            move |()| MangaPageEvents::ChaptersMarkedAsRead(chapter_ids, tracker_progress),
+        );
+    }
+
+    fn chapters_marked_as_read(&mut self, chapter_ids: Vec<String>, tracker_progress: (u32, Option<u32>)) {
+        self.set_chapters_as_read(&chapter_ids);
+        sync_chapters_read_mangadex(self.manga.id.clone(), chapter_ids);
+
+        if MangaTuiConfig::get().track_reading_when_marking_read {
+            let (chapter_number, volume_number) = tracker_progress;
+            self.track_manga(self.manga_trackers.clone(), self.manga.title.clone(), chapter_number, volume_number);
+        }
+    }
+
+    fn set_chapters_as_read(&mut self, chapter_ids: &[String]) {
+        if let Some(chapters_data) = self.chapters.as_mut() {
+            chapters_data
+                .widget
+                .chapters
+                .iter_mut()
+                .filter(|chapter| chapter_ids.contains(&chapter.id))
+                .for_each(|chapter| chapter.is_read = true);
+        }
+    }
+
+    /// Used to undo reading a chapter by accident
+    fn toggle_chapter_selected_read(&mut self) {
+        let Some(chapter) = self.get_current_selected_chapter() else {
+            return;
+        };
+
+        let is_read = chapter.is_read;
+        let chapter_id = chapter.id.clone();
+        let chapter_title = chapter.title.clone();
+        let translated_language = chapter.translated_language.as_iso_code();
+        let manga = self.manga_to_insert();
+
+        self.query_database(
+            move |conn| {
+                let mut database = Database::new(conn);
+                if is_read {
+                    database.mark_as_unread(&chapter_id)?;
+                } else {
+                    database.mark_as_read(
+                        manga.as_insert(),
+                        &[ChapterToSaveHistory {
+                            id: &chapter_id,
+                            title: &chapter_title,
+                            translated_language,
+                        }],
+                    )?;
+                }
+                Ok(chapter_id)
+            },
+            move |chapter_id| MangaPageEvents::ChapterReadToggled(chapter_id, !is_read),
+        );
+    }
+
+    fn chapter_read_toggled(&mut self, chapter_id: String, is_read: bool) {
+        let Some(chapter) = self
+            .chapters
+            .as_mut()
+            .and_then(|chapters| chapters.widget.chapters.iter_mut().find(|chapter| chapter.id == chapter_id))
+        else {
+            return;
+        };
+
+        chapter.is_read = is_read;
+
+        if is_read {
+            sync_chapters_read_mangadex(self.manga.id.clone(), vec![chapter_id]);
+        }
+    }
+
+    fn fetch_tracker_progress(&mut self) {
+        let Some(tracker) = self.manga_trackers.first().cloned() else {
+            return;
+        };
+        let Some(title) = SearchTerm::trimmed(&self.manga.title) else {
+            return;
+        };
+
+        let manga_id = self.manga.id.clone();
+        let tx = self.local_event_tx.clone();
+        self.tasks.spawn(async move {
+            match get_tracked_progress(&manga_id, title, tracker).await {
+                Ok(Some(progress)) => {
+                    tx.send(MangaPageEvents::LoadTrackerProgress(progress)).ok();
+                },
+                Ok(None) => {},
+                Err(e) => {
+                    tx.send(MangaPageEvents::TrackingFailed(e.to_string())).ok();
+                },
+            }
+        });
+    }
+
+    /// Only the chapters of the page being displayed can be marked, so this runs again every time chapters are loaded
+    fn mark_chapters_read_in_tracker(&mut self) {
+        let Some(progress) = self.tracker_progress else {
+            return;
+        };
+
+        let Some(chapters_data) = self.chapters.as_ref() else {
+            return;
+        };
+
+        let chapters_to_mark: Vec<(String, String, &'static str)> = chapters_data
+            .widget
+            .chapters
+            .iter()
+            .filter(|chapter| {
+                !chapter.is_read && chapter.chapter_number.parse::<f64>().is_ok_and(|number| number <= progress as f64)
+            })
+            .map(|chapter| (chapter.id.clone(), chapter.title.clone(), chapter.translated_language.as_iso_code()))
+            .collect();
+
+        if chapters_to_mark.is_empty() {
+            return;
+        }
+
+        let manga = self.manga_to_insert();
+
+        self.query_database(
+            move |conn| {
+                let mut database = Database::new(conn);
+                let chapters: Vec<ChapterToSaveHistory<'_>> = chapters_to_mark
+                    .iter()
+                    .map(|(id, title, translated_language)| ChapterToSaveHistory {
+                        id,
+                        title,
+                        translated_language,
+                    })
+                    .collect();
+
+                database.mark_as_read(manga.as_insert(), &chapters)?;
+
+                Ok(chapters_to_mark.into_iter().map(|(id, _, _)| id).collect())
+            },
+            MangaPageEvents::ChaptersMarkedAsReadInTracker,
+        );
+    }
+
+    fn load_tracker_progress(&mut self, progress: u32) {
+        self.tracker_progress = Some(progress);
+        self.mark_chapters_read_in_tracker();
+    }
+
+    /// Hide the chapters of the group which translated the selected chapter, only in this manga unless `everywhere`
+    fn block_scanlation_group_selected(&mut self, everywhere: bool) {
+        let Some(chapter) = self.get_current_selected_chapter() else {
+            return;
+        };
+
+        let Some(group_id) = chapter.scanlator_id.clone() else {
+            return;
+        };
+
+        let group_name = chapter.scanlator.clone();
+        let manga_id = if everywhere { None } else { Some(self.manga.id.clone()) };
+
+        self.query_database(
+            move |conn| {
+                let mut database = Database::new(conn);
+                let group = ScanlationGroup {
+                    id: &group_id,
+                    name: &group_name,
+                };
+                database.block_group(group, manga_id.as_deref())?;
+                Ok(group_id)
+            },
+            MangaPageEvents::ScanlationGroupBlocked,
+        );
+    }
+
+    fn scanlation_group_blocked(&mut self, group_id: String) {
+        if !self.blocked_scanlation_groups.contains(&group_id) {
+            self.blocked_scanlation_groups.push(group_id);
+        }
+        self.local_event_tx.send(MangaPageEvents::SearchChapters).ok();
+    }
+
+    /// The groups hidden only in this manga are shown first, the ones hidden everywhere once there are none left here
+    fn unblock_scanlation_groups(&mut self) {
+        if self.blocked_scanlation_groups.is_empty() {
+            return;
+        }
+
+        let manga_id = self.manga.id.clone();
+
+        self.query_database(
+            move |conn| {
+                let mut database = Database::new(conn);
+                if database.unblock_groups(Some(&manga_id))? == 0 {
+                    database.unblock_groups(None)?;
+                }
+                database.get_blocked_groups(&manga_id)
+            },
+            MangaPageEvents::LoadBlockedScanlationGroups,
+        );
+    }
+
+    fn load_blocked_scanlation_groups(&mut self, blocked_groups: Vec<String>) {
+        self.blocked_scanlation_groups = blocked_groups;
+        self.local_event_tx.send(MangaPageEvents::SearchChapters).ok();
+    }
+
+    fn get_chapter_bookmarked_from_db(&mut self) {
+        let manga_id = self.manga.id.clone();
+
+        self.query_database(
+            move |conn| Database::new(conn).get_bookmarked(&manga_id),
+            |chapter| match chapter {
+                Some(chapter) => MangaPageEvents::FetchChapterBookmarked(chapter),
+                None => MangaPageEvents::NoChapterBookmarked,
+            },
+        );
+    }
+
+    fn fetch_chapter_bookmarked(&mut self, bookmarked_chapter: ChapterBookmarked, api_client: impl FetchChapterBookmarked) {
+        let sender = self.local_event_tx.clone();
+        self.bookmark_state.phase = BookmarkPhase::SearchingFromApi;
+
+        self.tasks.spawn(async move {
+            let response = api_client.fetch_chapter_bookmarked(bookmarked_chapter).await;
+
+            match response {
+                Ok(response) => {
+                    sender.send(MangaPageEvents::ReadChapterBookmarked(response.0, response.1)).ok();
+                },
+                Err(e) => {
+                    #[cfg(not(test))]
+                    {
+                        write_to_error_log(ErrorType::Error(e));
+                    }
                     sender.send(MangaPageEvents::FetchBookmarkFailed).ok();
                 },
             }
@@ -816,12 +2235,13 @@ impl<T: MangaTracker> MangaPage<T> {
     fn download_chapter_selected(&mut self) {
         let manga_id = self.manga.id.clone();
         let manga_title = self.manga.title.clone();
-        let tracker = self.manga_tracker.clone();
+        let tracker = self.manga_trackers.clone();
         let tx = self.local_event_tx.clone();
 
         self.state = PageState::DownloadingChapters;
         if let Some(chapter) = self.get_current_selected_chapter_mut() {
-            if chapter.download_loading_state.is_some() {
+            // There are no pages to download
+            if chapter.download_loading_state.is_some() || chapter.external_url.is_some() {
                 return;
             }
             chapter.set_normal_state();
@@ -858,17 +2278,16 @@ impl<T: MangaTracker> MangaPage<T> {
                 .await;
 
                 match download_result {
-                    Ok(_) => {
+                    Ok(path) => {
                         if config.track_reading_when_download {
                             // clone chapter title so that it can be used inside `track_manga` error
                             // closure
                             let chapter_title_error = chapter_title.clone();
                             track_manga(
                                 tracker,
+                                manga_id.clone(),
                                 manga_title.clone(),
-                                // This conversion is needed so that we take into account chapters
-                                // like 1.2, 10.1 etc
-                                number.parse::<f64>().unwrap_or(0.0) as u32,
+                                ChapterNumber::parse(&number).as_tracker_progress(),
                                 volume_number.and_then(|vol| vol.parse().ok()),
                                 move |error| {
                                     write_to_error_log(
@@ -882,8 +2301,12 @@ impl<T: MangaTracker> MangaPage<T> {
                                 },
                             );
                         }
-                        tx.send(MangaPageEvents::SaveChapterDownloadStatus(chapter_id.clone(), chapter_title))
-                            .ok();
+                        tx.send(MangaPageEvents::SaveChapterDownloadStatus(
+                            chapter_id.clone(),
+                            chapter_title,
+                            chapter_downloaded(path, config.download_type),
+                        ))
+                        .ok();
                         tx.send(MangaPageEvents::ChapterFinishedDownloading(chapter_id)).ok();
                     },
                     Err(e) => {
@@ -904,24 +2327,31 @@ impl<T: MangaTracker> MangaPage<T> {
         }
     }
 
-    fn save_download_status(&mut self, id_chapter: String, title: String) {
-        let binding = DBCONN.lock().unwrap();
-        let conn = binding.as_ref().unwrap();
-
-        let save_download_operation = set_chapter_downloaded(
-            SetChapterDownloaded {
-                id: &id_chapter,
-                title: &title,
-                manga_id: &self.manga.id,
-                manga_title: &self.manga.title,
-                img_url: self.manga.img_url.as_deref(),
-            },
-            conn,
-        );
+    /// Queued before the chapter status is checked again so the download is already saved by then
+    fn save_download_status(&mut self, id_chapter: String, title: String, download: ChapterDownloaded) {
+        let manga_id = self.manga.id.clone();
+        let manga_title = self.manga.title.clone();
+        let img_url = self.manga.img_url.clone();
+
+        let save_download_operation = self.database.run(move |conn| {
+            set_chapter_downloaded(
+                SetChapterDownloaded {
+                    id: &id_chapter,
+                    title: &title,
+                    manga_id: &manga_id,
+                    manga_title: &manga_title,
+                    img_url: img_url.as_deref(),
+                    download: &download,
+                },
+                conn,
+            )
+        });
 
-        if let Err(e) = save_download_operation {
-            write_to_error_log(error_log::ErrorType::Error(Box::new(e)));
-        }
+        self.tasks.spawn(async move {
+            if let Err(e) = save_download_operation.await {
+                write_to_error_log(error_log::ErrorType::Error(e));
+            }
+        });
     }
 
     fn go_mangas_author(&mut self) {
@@ -977,6 +2407,11 @@ impl<T: MangaTracker> MangaPage<T> {
 
                 let page = if let Some(previous) = self.chapters.as_ref() { previous.page } else { 1 };
 
+                let manga_id = self.manga.id.clone();
+                let total_chapters = response.total as u32;
+                self.database
+                    .execute(move |conn| Database::new(conn).save_total_chapters(&manga_id, total_chapters));
+
                 self.chapters = Some(ChaptersData {
                     state: list_state,
                     widget: chapter_widget,
@@ -1001,12 +2436,74 @@ impl<T: MangaTracker> MangaPage<T> {
         self.download_all_chapters_state.ask_for_confirmation();
     }
 
+    /// Lists the chapters that would be downloaded, nothing is downloaded until the user confirms again
     fn confirm_download_all_chapters(&mut self) {
         self.download_all_chapters_state.fetch_chapters_data();
         let manga_id = self.manga.id.clone();
-        let manga_title = self.manga.title.clone();
         let lang = self.get_current_selected_language();
         let tx = self.local_event_tx.clone();
+
+        self.tasks.spawn(async move {
+            #[cfg(not(test))]
+            let api_client = MangadexClient::global().clone();
+
+            #[cfg(test)]
+            let api_client = crate::backend::fetch::fake_api_client::MockMangadexClient::new();
+
+            match fetch_chapters_to_download(&api_client, &manga_id, lang, &[]).await {
+                Ok(chapters) => {
+                    tx.send(MangaPageEvents::LoadDownloadAllPreview(chapters)).ok();
+                },
+                Err(e) => {
+                    tx.send(MangaPageEvents::DownloadAllChaptersError).ok();
+                    write_to_error_log(ErrorType::Error(e));
+                },
+            }
+        });
+    }
+
+    fn load_download_all_preview(&mut self, chapters: Vec<ChapterData>) {
+        self.download_all_chapters_state
+            .show_preview(chapters.iter().map(describe_chapter_to_download).collect());
+        self.chapters_to_download_all = chapters;
+    }
+
+    fn download_chapters_previewed(&mut self) {
+        if self.download_all_chapters_state.phase != DownloadPhase::Previewing {
+            return;
+        }
+
+        let chapters = std::mem::take(&mut self.chapters_to_download_all);
+        let lang = self.get_current_selected_language();
+        self.download_all_chapters(lang, Some(chapters), vec![]);
+    }
+
+    /// Continue a download of all chapters which did not finish the last time the app was used, the chapters which are
+    /// already downloaded are skipped
+    pub fn resume_download_all_chapters(&mut self, language: Languages) {
+        let manga_id = self.manga.id.clone();
+
+        self.query_database(
+            move |conn| Ok(Database::new(conn).get_downloaded_chapter_ids(&manga_id)?),
+            move |chapters_to_skip| MangaPageEvents::ResumeDownloadAllChapters(language, chapters_to_skip),
+        );
+    }
+
+    /// Downloads `chapters`, if they are not known yet they are fetched and the ones in `chapters_to_skip` are left
+    /// out
+    fn download_all_chapters(&mut self, lang: Languages, chapters: Option<Vec<ChapterData>>, chapters_to_skip: Vec<String>) {
+        self.download_all_chapters_state.fetch_chapters_data();
+        let manga_id = self.manga.id.clone();
+        let manga_title = self.manga.title.clone();
+        let tx = self.local_event_tx.clone();
+
+        let job = DownloadAllJob {
+            manga_id: manga_id.clone(),
+            manga_title: manga_title.clone(),
+            translated_language: lang.as_iso_code().to_string(),
+        };
+        self.database.execute(move |conn| Database::new(conn).save_download_all_job(&job));
+
         self.tasks.spawn(async move {
             #[cfg(not(test))]
             let api_client = MangadexClient::global().clone();
@@ -1016,15 +2513,31 @@ impl<T: MangaTracker> MangaPage<T> {
 
             let config = MangaTuiConfig::get();
 
-            let download_all_chapters_process = download_all_chapters(api_client, DownloadAllChapters {
-                sender: tx.clone(),
-                manga_id,
-                manga_title,
-                image_quality: config.image_quality,
-                directory_to_download: AppDirectories::MangaDownloads.get_full_path(),
-                file_format: config.download_type,
-                language: lang,
-            })
+            let chapters = match chapters {
+                Some(chapters) => chapters,
+                None => match fetch_chapters_to_download(&api_client, &manga_id, lang, &chapters_to_skip).await {
+                    Ok(chapters) => chapters,
+                    Err(e) => {
+                        tx.send(MangaPageEvents::DownloadAllChaptersError).ok();
+                        write_to_error_log(ErrorType::Error(e));
+                        return;
+                    },
+                },
+            };
+
+            let download_all_chapters_process = download_all_chapters(
+                api_client,
+                DownloadAllChapters {
+                    sender: tx.clone(),
+                    manga_id,
+                    manga_title,
+                    image_quality: config.image_quality,
+                    directory_to_download: AppDirectories::MangaDownloads.get_full_path(),
+                    file_format: config.download_type,
+                    language: lang,
+                },
+                chapters,
+            )
             .await;
 
             if let Err(e) = download_all_chapters_process {
@@ -1038,6 +2551,7 @@ impl<T: MangaTracker> MangaPage<T> {
         if !self.download_all_chapters_state.is_downloading() {
             self.state = PageState::DisplayingChapters;
             self.download_all_chapters_state.cancel();
+            self.chapters_to_download_all.clear();
         }
     }
 
@@ -1048,11 +2562,36 @@ impl<T: MangaTracker> MangaPage<T> {
             .set_download_location(AppDirectories::MangaDownloads.get_full_path().join(&self.manga.title));
     }
 
+    /// Check again which chapters are read or downloaded if the manga with `manga_id` is the one being displayed
+    pub fn refresh_chapters_status(&mut self, manga_id: &str) {
+        if self.manga.id == manga_id {
+            self.local_event_tx.send(MangaPageEvents::CheckChapterStatus).ok();
+        }
+    }
+
+    /// Whether a popup, list or input is open and takes the keys pressed, so they should not be used as global shortcuts
+    pub fn is_typing(&self) -> bool {
+        self.cover_viewer.is_some()
+            || self.tags_popup.is_some()
+            || self.collections_popup.is_some()
+            || self.merge_confirmation.is_some()
+            || self.tracker_binding.is_some()
+            || self.tracker_entry_form.is_some()
+            || self.is_list_related_mangas_open
+            || self.is_list_languages_open
+            || self.download_process_started()
+    }
+
     pub fn is_downloading_all_chapters(&self) -> bool {
         self.download_all_chapters_state.is_downloading()
     }
 
+    fn clear_download_all_job(&self) {
+        self.database.execute(|conn| Database::new(conn).clear_download_all_job());
+    }
+
     fn finish_download_all_chapters(&mut self) {
+        self.clear_download_all_job();
         self.download_all_chapters_state.reset();
         self.state = PageState::DisplayingChapters;
         self.local_event_tx.send(MangaPageEvents::CheckChapterStatus).ok();
@@ -1063,6 +2602,7 @@ impl<T: MangaTracker> MangaPage<T> {
     }
 
     fn abort_download_all_chapters(&mut self) {
+        self.clear_download_all_job();
         self.download_all_chapters_state.abort_proccess();
         self.tasks.abort_all();
         self.local_event_tx.send(MangaPageEvents::CheckChapterStatus).ok();
@@ -1078,23 +2618,71 @@ impl<T: MangaTracker> MangaPage<T> {
         }
         let tx = self.local_event_tx.clone();
         let manga_id = self.manga.id.clone();
-        let file_name = self.manga.img_url.as_ref().cloned().unwrap_or_default();
+        let Some(file_name) = self.manga.img_url.clone() else {
+            self.cover_failed = true;
+            return;
+        };
         self.tasks.spawn(async move {
             let cover_image_response = MangadexClient::global().get_cover_for_manga_lower_quality(&manga_id, &file_name).await;
 
-            if let Ok(response) = cover_image_response {
-                if let Ok(bytes) = response.bytes().await {
-                    let img = Reader::new(Cursor::new(bytes)).with_guessed_format().unwrap().decode().unwrap();
-                    tx.send(MangaPageEvents::LoadCover(img)).ok();
-                }
-            }
+            let cover = match cover_image_response {
+                Ok(response) => response.bytes().await.ok().and_then(|bytes| decode_bytes_to_image(bytes).ok()),
+                Err(_) => None,
+            };
+
+            tx.send(MangaPageEvents::LoadCover(cover)).ok();
+        });
+    }
+
+    fn load_cover(&mut self, maybe_cover: Option<DynamicImage>) {
+        let Some(picker) = self.picker.as_mut() else {
+            return;
+        };
+
+        self.accent_color = maybe_cover.as_ref().and_then(extract_accent_color);
+
+        match maybe_cover.map(|cover| picker.new_protocol(cover, self.cover_area, Resize::Fit(None))) {
+            Some(Ok(protocol)) => self.image_state = Some(protocol),
+            _ => self.cover_failed = true,
+        }
+    }
+
+    fn toggle_cover_viewer(&mut self) {
+        if self.cover_viewer.take().is_some() {
+            return;
+        }
+
+        let Some(file_name) = self.manga.img_url.clone() else {
+            return;
+        };
+
+        self.cover_viewer = Some(CoverViewerState::default());
+
+        let tx = self.local_event_tx.clone();
+        let manga_id = self.manga.id.clone();
+        self.tasks.spawn(async move {
+            let cover_image_response = MangadexClient::global().get_cover_for_manga(&manga_id, &file_name).await;
+
+            let cover = match cover_image_response {
+                Ok(response) => response.bytes().await.ok().and_then(|bytes| decode_bytes_to_image(bytes).ok()),
+                Err(_) => None,
+            };
+
+            tx.send(MangaPageEvents::LoadFullCover(cover)).ok();
         });
     }
 
-    fn load_cover(&mut self, img: DynamicImage) {
-        let fixed_protocol = self.picker.as_mut().unwrap().new_protocol(img, self.cover_area, Resize::Fit(None));
-        if let Ok(protocol) = fixed_protocol {
-            self.image_state = Some(protocol);
+    /// If the cover could not be fetched the viewer is closed since there is nothing to show
+    fn load_full_cover(&mut self, maybe_cover: Option<DynamicImage>) {
+        match (self.cover_viewer.as_mut(), maybe_cover) {
+            (Some(viewer), Some(cover)) => viewer.set_cover(cover),
+            _ => self.cover_viewer = None,
+        }
+    }
+
+    fn pan_cover(&mut self, direction: PanDirection) {
+        if let Some(viewer) = self.cover_viewer.as_mut() {
+            viewer.pan(direction);
         }
     }
 
@@ -1129,36 +2717,43 @@ impl<T: MangaTracker> MangaPage<T> {
     fn read_chapter_bookmarked(&mut self, chapter: ChapterToRead, manga_to_read: MangaToRead) {
         self.bookmark_state.phase = BookmarkPhase::default();
 
-        let connection = Database::get_connection();
-        let language = self.get_current_selected_language();
+        let translated_language = self.get_current_selected_language().as_iso_code();
+        let manga = self.manga_to_insert();
+        let chapter_id = chapter.id.clone();
+        let chapter_title = chapter.title.clone();
 
-        if let Ok(conn) = connection {
-            save_history(
+        // Queued before anything the reader saves, so the history is there by then
+        self.database.execute(move |conn| {
+            let saved = save_history(
                 MangaReadingHistorySave {
-                    id: &self.manga.id,
-                    title: &self.manga.title,
-                    img_url: self.manga.img_url.as_deref(),
+                    id: &manga.id,
+                    title: &manga.title,
+                    img_url: manga.img_url.as_deref(),
                     chapter: ChapterToSaveHistory {
-                        id: &chapter.id,
-                        title: &chapter.title,
-                        translated_language: language.as_iso_code(),
+                        id: &chapter_id,
+                        title: &chapter_title,
+                        translated_language,
                     },
                 },
-                &conn,
-            )
-            .expect("error saving reading history");
+                conn,
+            );
 
-            self.global_event_tx
-                .as_ref()
-                .unwrap()
-                .send(Events::ReadChapter(chapter, manga_to_read))
-                .ok();
-        }
+            if let Err(e) = saved {
+                write_to_error_log(ErrorType::Error(Box::new(e)));
+            }
+            Ok(())
+        });
+
+        self.global_event_tx
+            .as_ref()
+            .unwrap()
+            .send(Events::ReadChapter(chapter, manga_to_read))
+            .ok();
     }
 
-    fn track_manga(&self, tracker: Option<T>, manga_title: String, chapter_number: u32, volume_number: Option<u32>) {
+    fn track_manga(&self, trackers: Vec<T>, manga_title: String, chapter_number: u32, volume_number: Option<u32>) {
         let tx = self.local_event_tx.clone();
-        track_manga(tracker, manga_title, chapter_number, volume_number, move |error| {
+        track_manga(trackers, self.manga.id.clone(), manga_title, chapter_number, volume_number, move |error| {
             tx.send(MangaPageEvents::TrackingFailed(error)).ok();
         });
     }
@@ -1171,7 +2766,7 @@ impl<T: MangaTracker> MangaPage<T> {
         if self.download_process_started() {
             self.download_all_chapters_state.tick();
         } else if self.bookmark_state.phase == BookmarkPhase::SearchingFromApi {
-            self.bookmark_state.loader.calc_next();
+            spin_loader(&mut self.bookmark_state.loader);
         }
 
         while let Ok(background_event) = self.local_event_rx.try_recv() {
@@ -1184,10 +2779,17 @@ impl<T: MangaTracker> MangaPage<T> {
 
                     self.fetch_chapter_bookmarked(chapter_bookmarked, api_client);
                 },
-                MangaPageEvents::LoadCover(img) => self.load_cover(img),
+                MangaPageEvents::LoadCover(maybe_cover) => self.load_cover(maybe_cover),
+                MangaPageEvents::LoadFullCover(maybe_cover) => self.load_full_cover(maybe_cover),
                 MangaPageEvents::SearchCover => self.search_cover(),
+                MangaPageEvents::SearchRelatedMangas => self.search_related_mangas(),
+                MangaPageEvents::LoadRelatedMangas(mangas) => self.load_related_mangas(mangas),
                 MangaPageEvents::FinishedDownloadingAllChapters => self.finish_download_all_chapters(),
                 MangaPageEvents::DownloadAllChaptersError => self.set_download_all_chapters_error(),
+                MangaPageEvents::LoadDownloadAllPreview(chapters) => self.load_download_all_preview(chapters),
+                MangaPageEvents::MarkChaptersAsRead(chapter_ids, tracker_progress) => {
+                    self.mark_chapters_as_read(chapter_ids, tracker_progress)
+                },
                 MangaPageEvents::StartDownloadProgress(total_chapters) => self.start_download_all_chapters(total_chapters),
                 MangaPageEvents::SetDownloadAllChaptersProgress => self.set_manga_download_progress(),
                 MangaPageEvents::ReadError(chapter_id) => {
@@ -1197,14 +2799,21 @@ impl<T: MangaTracker> MangaPage<T> {
                 MangaPageEvents::SetDownloadProgress(progress, id_chapter) => {
                     self.set_download_progress_for_chapter(progress, id_chapter)
                 },
-                MangaPageEvents::SaveChapterDownloadStatus(id_chapter, title) => self.save_download_status(id_chapter, title),
+                MangaPageEvents::SaveChapterDownloadStatus(id_chapter, title, download) => {
+                    self.save_download_status(id_chapter, title, download)
+                },
                 MangaPageEvents::ChapterFinishedDownloading(id_chapter) => self.set_chapter_finished_downloading(id_chapter),
                 MangaPageEvents::FethStatistics => self.fetch_statistics(),
                 MangaPageEvents::SearchChapters => self.search_chapters(),
                 MangaPageEvents::LoadChapters(response) => self.load_chapters(response),
+                MangaPageEvents::LoadChaptersStatus(history) => self.load_chapters_status(history),
                 MangaPageEvents::CheckChapterStatus => {
                     self.check_chapters_read();
+                    self.mark_chapters_read_in_tracker();
                 },
+                MangaPageEvents::FetchTrackerProgress => self.fetch_tracker_progress(),
+                MangaPageEvents::LoadTrackerProgress(progress) => self.load_tracker_progress(progress),
+                MangaPageEvents::LoadTrackerEntries(entries) => self.load_tracker_entries(entries),
                 MangaPageEvents::LoadStatistics(maybe_statistics) => {
                     if let Some(response) = maybe_statistics {
                         let statistics: &Statistics = &response.statistics[&self.manga.id];
@@ -1217,7 +2826,12 @@ impl<T: MangaTracker> MangaPage<T> {
                 MangaPageEvents::ReadSuccesful(chapter_to_read, manga_to_read) => {
                     self.state = PageState::DisplayingChapters;
                     let volume = chapter_to_read.clone().volume_number.and_then(|vol| vol.parse::<u32>().ok());
-                    self.track_manga(self.manga_tracker.clone(), self.manga.title.clone(), chapter_to_read.number as u32, volume);
+                    self.track_manga(
+                        self.manga_trackers.clone(),
+                        self.manga.title.clone(),
+                        ChapterNumber::from(chapter_to_read.number).as_tracker_progress(),
+                        volume,
+                    );
 
                     self.local_event_tx.send(MangaPageEvents::CheckChapterStatus).ok();
 
@@ -1227,6 +2841,32 @@ impl<T: MangaTracker> MangaPage<T> {
                         .send(Events::ReadChapter(chapter_to_read, manga_to_read))
                         .ok();
                 },
+                MangaPageEvents::SearchStoredData => self.search_stored_data(),
+                MangaPageEvents::LoadStoredData(data) => self.load_stored_data(*data),
+                MangaPageEvents::NoChapterBookmarked => self.bookmark_state.phase = BookmarkPhase::NotFoundDatabase,
+                MangaPageEvents::ChapterBookmarked(chapter_id) => self.set_chapter_bookmarked(chapter_id),
+                MangaPageEvents::ChaptersMarkedAsRead(chapter_ids, tracker_progress) => {
+                    self.chapters_marked_as_read(chapter_ids, tracker_progress)
+                },
+                MangaPageEvents::ChaptersMarkedAsReadInTracker(chapter_ids) => self.set_chapters_as_read(&chapter_ids),
+                MangaPageEvents::ChapterReadToggled(chapter_id, is_read) => self.chapter_read_toggled(chapter_id, is_read),
+                MangaPageEvents::ScanlationGroupBlocked(group_id) => self.scanlation_group_blocked(group_id),
+                MangaPageEvents::LoadBlockedScanlationGroups(blocked_groups) => self.load_blocked_scanlation_groups(blocked_groups),
+                MangaPageEvents::LoadCollectionsPopup(collections, in_collection) => {
+                    self.load_collections_popup(collections, in_collection)
+                },
+                MangaPageEvents::InCollectionToggled(collection_id, is_in_collection) => {
+                    self.in_collection_toggled(collection_id, is_in_collection)
+                },
+                MangaPageEvents::LoadTagsPopup(tags, all_tags) => self.load_tags_popup(tags, all_tags),
+                MangaPageEvents::LoadTags(tags, all_tags) => self.load_tags(tags, all_tags),
+                MangaPageEvents::LoadFollowingAuthor(is_following) => self.is_following_author = is_following,
+                MangaPageEvents::DuplicateMerged => self.duplicate_merged(),
+                MangaPageEvents::LoadPersonalRating(rating) => self.personal_rating = rating,
+                MangaPageEvents::TrackerEntryBound => self.tracker_entry_bound(),
+                MangaPageEvents::ResumeDownloadAllChapters(language, chapters_to_skip) => {
+                    self.download_all_chapters(language, None, chapters_to_skip)
+                },
             }
         }
     }
@@ -1255,6 +2895,11 @@ impl<T: MangaTracker> Component for MangaPage<T> {
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(15), Constraint::Percentage(85)]);
 
+        if let (Some(viewer), Some(picker)) = (self.cover_viewer.as_mut(), self.picker.as_mut()) {
+            CoverViewer::new(&self.manga.title, picker).render(area, frame.buffer_mut(), viewer);
+            return;
+        }
+
         let [cover_area, information_area] = layout.areas(area);
 
         self.render_cover(cover_area, frame.buffer_mut());
@@ -1263,34 +2908,104 @@ impl<T: MangaTracker> Component for MangaPage<T> {
 
     fn update(&mut self, action: Self::Actions) {
         match action {
-            MangaPageActions::GoToReadBookmarkedChapter => {
-                let connection = Database::get_connection();
-                if let Ok(conn) = connection {
-                    let database = Database::new(&conn);
-
-                    self.get_chapter_bookmarked_from_db(database);
+            MangaPageActions::GoToReadBookmarkedChapter => self.get_chapter_bookmarked_from_db(),
+            MangaPageActions::BookMarkChapterSelected => self.bookmark_current_chapter_selected(),
+            MangaPageActions::MarkReadUpToChapterSelected => self.fetch_chapters_up_to_selected(),
+            MangaPageActions::ToggleChapterSelectedRead => self.toggle_chapter_selected_read(),
+            MangaPageActions::BlockScanlationGroupSelected | MangaPageActions::BlockScanlationGroupSelectedEverywhere => {
+                let everywhere = action == MangaPageActions::BlockScanlationGroupSelectedEverywhere;
+
+                self.block_scanlation_group_selected(everywhere);
+            },
+            MangaPageActions::UnblockScanlationGroups => self.unblock_scanlation_groups(),
+            MangaPageActions::OpenDownloadLocation => self.open_download_location(),
+            MangaPageActions::OpenExternalChapter => self.open_external_chapter(),
+            MangaPageActions::ToggleTrackerEntryForm => self.toggle_tracker_entry_form(),
+            MangaPageActions::ScrollDownTrackingStatus => {
+                if let Some(form) = self.tracker_entry_form.as_mut() {
+                    form.status_state.select_next();
                 }
             },
-            MangaPageActions::BookMarkChapterSelected => {
-                let connection = Database::get_connection();
-
-                if let Ok(conn) = connection {
-                    let mut database = Database::new(&conn);
-
-                    self.bookmark_current_chapter_selected(&mut database);
+            MangaPageActions::ScrollUpTrackingStatus => {
+                if let Some(form) = self.tracker_entry_form.as_mut() {
+                    form.status_state.select_previous();
+                }
+            },
+            MangaPageActions::IncreaseTrackerScore => {
+                if let Some(form) = self.tracker_entry_form.as_mut() {
+                    form.increase_score();
+                }
+            },
+            MangaPageActions::DecreaseTrackerScore => {
+                if let Some(form) = self.tracker_entry_form.as_mut() {
+                    form.decrease_score();
+                }
+            },
+            MangaPageActions::SaveTrackerEntry => self.save_tracker_entry(),
+            MangaPageActions::ToggleTrackerBinding => self.toggle_tracker_binding(),
+            MangaPageActions::ScrollDownTrackerEntries => {
+                if let Some(popup) = self.tracker_binding.as_mut() {
+                    popup.state.select_next();
+                }
+            },
+            MangaPageActions::ScrollUpTrackerEntries => {
+                if let Some(popup) = self.tracker_binding.as_mut() {
+                    popup.state.select_previous();
+                }
+            },
+            MangaPageActions::ToggleCollectionsPopup => self.toggle_collections_popup(),
+            MangaPageActions::ScrollDownCollections => {
+                if let Some(popup) = self.collections_popup.as_mut() {
+                    popup.state.select_next();
+                }
+            },
+            MangaPageActions::ScrollUpCollections => {
+                if let Some(popup) = self.collections_popup.as_mut() {
+                    popup.state.select_previous();
+                }
+            },
+            MangaPageActions::ToggleInCollection => self.toggle_in_collection_selected(),
+            MangaPageActions::ToggleTagsPopup => self.toggle_tags_popup(),
+            MangaPageActions::ToggleTag => self.toggle_tag(),
+            MangaPageActions::ReportBug => report_bug(),
+            MangaPageActions::ToggleCoverViewer => self.toggle_cover_viewer(),
+            MangaPageActions::PanCoverUp => self.pan_cover(PanDirection::Up),
+            MangaPageActions::PanCoverDown => self.pan_cover(PanDirection::Down),
+            MangaPageActions::PanCoverLeft => self.pan_cover(PanDirection::Left),
+            MangaPageActions::PanCoverRight => self.pan_cover(PanDirection::Right),
+            MangaPageActions::ToggleFollowAuthor => self.toggle_follow_author(),
+            MangaPageActions::ToggleMergeConfirmation => self.toggle_merge_confirmation(),
+            MangaPageActions::ScrollDownMergeConfirmation => {
+                if let Some(state) = self.merge_confirmation.as_mut() {
+                    state.scroll_down();
+                }
+            },
+            MangaPageActions::ScrollUpMergeConfirmation => {
+                if let Some(state) = self.merge_confirmation.as_mut() {
+                    state.scroll_up();
                 }
             },
+            MangaPageActions::MergeDuplicate => self.merge_duplicate(),
+            MangaPageActions::IncreasePersonalRating | MangaPageActions::DecreasePersonalRating => {
+                self.change_personal_rating(action == MangaPageActions::IncreasePersonalRating)
+            },
+            MangaPageActions::BindTrackerEntry => self.bind_tracker_entry_selected(),
             MangaPageActions::AbortDownloadAllChapters => self.abort_download_all_chapters(),
             MangaPageActions::AskAbortProcces => self.ask_abort_download_chapters(),
             MangaPageActions::SearchByLanguage => self.search_by_language(),
             MangaPageActions::CancelDownloadAll => self.cancel_download_all_chapters(),
             MangaPageActions::AskDownloadAllChapters => self.ask_download_all_chapters(),
             MangaPageActions::ConfirmDownloadAll => self.confirm_download_all_chapters(),
+            MangaPageActions::DownloadChaptersPreviewed => self.download_chapters_previewed(),
             MangaPageActions::SearchPreviousChapterPage => self.search_previous_chapters(),
             MangaPageActions::SearchNextChapterPage => self.search_next_chapters(),
             MangaPageActions::ScrollDownAvailbleLanguages => self.scroll_language_down(),
             MangaPageActions::ScrollUpAvailbleLanguages => self.scroll_language_up(),
             MangaPageActions::ToggleAvailableLanguagesList => self.toggle_available_languages_list(),
+            MangaPageActions::ToggleRelatedMangasList => self.toggle_related_mangas_list(),
+            MangaPageActions::ScrollDownRelatedMangas => self.related_mangas_state.select_next(),
+            MangaPageActions::ScrollUpRelatedMangas => self.related_mangas_state.select_previous(),
+            MangaPageActions::GoToRelatedManga => self.go_to_related_manga(),
             MangaPageActions::GoMangasArtist => self.go_mangas_artist(),
             MangaPageActions::GoMangasAuthor => self.go_mangas_author(),
             MangaPageActions::ScrollChapterUp => self.scroll_chapter_up(),
@@ -1320,6 +3035,8 @@ impl<T: MangaTracker> Component for MangaPage<T> {
 
     fn clean_up(&mut self) {
         self.abort_tasks();
+        // Saved again in case the manga was added to the library while the page was open
+        save_search_data(&self.database, &self.manga);
         self.manga.tags = vec![];
         self.manga.description = String::new();
     }
@@ -1335,9 +3052,10 @@ mod test {
 
     use self::mpsc::unbounded_channel;
     use super::*;
-    use crate::backend::api_responses::ChapterData;
-    use crate::backend::database::ChapterBookmarked;
+    use crate::backend::api_responses::ChapterAttribute;
+    use crate::backend::database::{save_plan_to_read, ChapterBookmarked, MangaPlanToReadSave};
     use crate::backend::tracker::MangaTracker;
+    use crate::common::{Author, RelatedManga};
     use crate::global::test_utils::TrackerTest;
     use crate::view::widgets::press_key;
 
@@ -1353,7 +3071,7 @@ mod test {
         let area = Rect::new(0, 0, 50, 50);
         let mut buf = Buffer::empty(area);
         let chapters = manga_page.chapters.as_mut().unwrap();
-        StatefulWidget::render(chapters.widget.clone(), area, &mut buf, &mut chapters.state);
+        StatefulWidget::render(&mut chapters.widget, area, &mut buf, &mut chapters.state);
     }
 
     fn render_available_languages_list<T: MangaTracker>(manga_page: &mut MangaPage<T>) {
@@ -1480,6 +3198,12 @@ mod test {
         let action = manga_page.local_action_rx.recv().await.unwrap();
 
         assert_eq!(MangaPageActions::GoMangasArtist, action);
+
+        // open the location of a downloaded chapter
+        press_key(&mut manga_page, KeyCode::Char('O'));
+        let action = manga_page.local_action_rx.recv().await.unwrap();
+
+        assert_eq!(MangaPageActions::OpenDownloadLocation, action);
     }
 
     #[tokio::test]
@@ -1510,6 +3234,75 @@ mod test {
         assert_eq!(MangaPageActions::ConfirmDownloadAll, action);
     }
 
+    #[test]
+    fn it_suggests_the_language_read_the_most_if_the_preferred_one_is_not_available() {
+        let preferred = *Languages::get_preferred_lang();
+
+        assert_eq!(Some(preferred), suggest_chapter_language(&[Languages::Spanish, preferred], &[Languages::Spanish]));
+        assert_eq!(
+            Some(Languages::Spanish),
+            suggest_chapter_language(
+                &[Languages::French, Languages::Spanish],
+                &[Languages::Italian, Languages::Spanish, Languages::French]
+            )
+        );
+        assert_eq!(None, suggest_chapter_language(&[Languages::French], &[Languages::Spanish]));
+    }
+
+    #[tokio::test]
+    async fn it_loads_what_the_user_saved_about_the_manga_and_searches_chapters_again() {
+        let database = test_database();
+        query_test_database(&database, |database| {
+            database.block_group(
+                ScanlationGroup {
+                    id: "group_id",
+                    name: "Some group",
+                },
+                Some("some_manga"),
+            )?;
+            Ok(database.set_personal_rating(
+                MangaInsert {
+                    id: "some_manga",
+                    title: "Some manga",
+                    img_url: None,
+                },
+                Some(7),
+            )?)
+        })
+        .await;
+
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(
+            Manga {
+                id: "some_manga".to_string(),
+                title: "Some manga".to_string(),
+                ..Default::default()
+            },
+            None,
+        )
+        .with_database(database);
+
+        let mut events = vec![];
+        while let Ok(event) = manga_page.local_event_rx.try_recv() {
+            events.push(event);
+        }
+
+        assert!(events.contains(&MangaPageEvents::SearchStoredData));
+
+        manga_page.search_stored_data();
+
+        let MangaPageEvents::LoadStoredData(data) = query_result(&mut manga_page) else {
+            panic!("the stored data was not loaded");
+        };
+
+        assert_eq!(vec!["group_id".to_string()], data.blocked_scanlation_groups);
+        assert_eq!(Some(7), data.personal_rating);
+
+        manga_page.load_stored_data(*data);
+
+        assert_eq!(Some(7), manga_page.personal_rating);
+        assert_eq!(MangaPageEvents::SearchChapters, manga_page.local_event_rx.try_recv().unwrap());
+    }
+
     async fn manga_page_initialized_correctly<T: MangaTracker>(manga_page: &mut MangaPage<T>) {
         assert_eq!(manga_page.chapter_language, Languages::default());
 
@@ -1637,58 +3430,86 @@ mod test {
     }
 
     #[test]
-    fn doesnt_search_manga_cover_if_picker_is_none() {
-        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None);
+    fn it_takes_the_accent_color_from_the_cover() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), Some(Picker::new((8, 8))));
 
-        manga_page.search_cover();
-    }
+        let cover = image::RgbImage::from_pixel(10, 10, image::Rgb([20, 20, 200]));
 
-    #[derive(Default, Clone)]
-    struct ChapterTest {
-        id: String,
-        was_bookmarked: bool,
+        manga_page.load_cover(Some(DynamicImage::ImageRgb8(cover)));
+
+        assert_eq!(Some(Color::Rgb(20, 20, 200)), manga_page.accent_color);
     }
 
-    #[derive(Default, Clone)]
-    struct TestDatabase {
-        should_fail: bool,
-        chapter: ChapterTest,
-        chapter_bookmarked: Option<ChapterBookmarked>,
+    #[tokio::test]
+    async fn it_shows_the_cover_full_screen_once_it_is_loaded() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), Some(Picker::new((8, 8))));
+
+        press_key(&mut manga_page, KeyCode::Char('z'));
+
+        assert!(manga_page.local_action_rx.try_recv().is_err());
+
+        manga_page.load_cover(Some(DynamicImage::new_rgb8(10, 10)));
+
+        press_key(&mut manga_page, KeyCode::Char('z'));
+
+        assert_eq!(Ok(MangaPageActions::ToggleCoverViewer), manga_page.local_action_rx.try_recv());
+
+        manga_page.cover_viewer = Some(CoverViewerState::default());
+        manga_page.load_full_cover(Some(DynamicImage::new_rgb8(1000, 1500)));
+
+        press_key(&mut manga_page, KeyCode::Char('j'));
+
+        assert_eq!(Ok(MangaPageActions::PanCoverDown), manga_page.local_action_rx.try_recv());
+
+        press_key(&mut manga_page, KeyCode::Esc);
+
+        let action = manga_page.local_action_rx.recv().await.unwrap();
+        manga_page.update(action);
+
+        assert!(manga_page.cover_viewer.is_none());
+
+        manga_page.cover_viewer = Some(CoverViewerState::default());
+        manga_page.load_full_cover(None);
+
+        assert!(manga_page.cover_viewer.is_none());
     }
 
-    impl TestDatabase {
-        fn new() -> Self {
-            Self {
-                should_fail: false,
-                chapter: ChapterTest::default(),
-                chapter_bookmarked: None,
-            }
-        }
+    #[test]
+    fn doesnt_search_manga_cover_if_picker_is_none() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None);
 
-        fn with_bookmarked_chapter(chapter: ChapterBookmarked) -> Self {
-            Self {
-                should_fail: false,
-                chapter: ChapterTest::default(),
-                chapter_bookmarked: Some(chapter),
-            }
-        }
+        manga_page.search_cover();
+    }
 
-        fn was_bookmarked(&self) -> bool {
-            self.chapter.was_bookmarked
-        }
+    /// Each test has its own database with every table
+    fn test_database() -> DatabaseActor {
+        DatabaseActor::spawn(|| {
+            let connection = Connection::open_in_memory()?;
+            Database::new(&connection).setup()?;
+            Ok(connection)
+        })
     }
 
-    impl Bookmark for TestDatabase {
-        fn bookmark(&mut self, _chapter_to_bookmark: ChapterToBookmark) -> Result<(), Box<dyn std::error::Error>> {
-            self.chapter.was_bookmarked = true;
-            Ok(())
-        }
+    /// Waits for the queries sent to the database and returns the event made from the result of the first one
+    fn query_result<T: MangaTracker>(manga_page: &mut MangaPage<T>) -> MangaPageEvents {
+        assert!(manga_page.database.flush(Duration::from_secs(5)));
+        manga_page.local_event_rx.try_recv().expect("the query did not send an event")
     }
 
-    impl RetrieveBookmark for TestDatabase {
-        fn get_bookmarked(&self, _manga_id: &str) -> Result<Option<ChapterBookmarked>, Box<dyn std::error::Error>> {
-            Ok(self.chapter_bookmarked.clone())
-        }
+    fn no_query_result<T: MangaTracker>(manga_page: &mut MangaPage<T>) -> bool {
+        assert!(manga_page.database.flush(Duration::from_secs(5)));
+        manga_page.local_event_rx.try_recv().is_err()
+    }
+
+    async fn query_test_database<R: Send + 'static>(
+        database: &DatabaseActor,
+        query: impl FnOnce(&mut Database<'_>) -> Result<R, Box<dyn Error>> + Send + 'static,
+    ) -> R {
+        database
+            .run(move |conn| Ok(query(&mut Database::new(conn)).map_err(|e| e.to_string())))
+            .await
+            .unwrap()
+            .unwrap()
     }
 
     #[tokio::test]
@@ -1704,6 +3525,31 @@ mod test {
         assert_eq!(MangaPageActions::BookMarkChapterSelected, result)
     }
 
+    #[tokio::test]
+    async fn it_opens_chapters_published_outside_mangadex_instead_of_reading_them() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None);
+
+        let mut external_chapter = ChapterData::default();
+        external_chapter.attributes.external_url = Some("https://mangaplus.shueisha.co.jp/viewer/1".to_string());
+
+        manga_page.state = PageState::DisplayingChapters;
+        manga_page.load_chapters(Some(ChapterResponse {
+            data: vec![external_chapter, ChapterData::default()],
+            total: 2,
+            ..Default::default()
+        }));
+        render_chapters(&mut manga_page);
+
+        press_key(&mut manga_page, KeyCode::Char('r'));
+
+        assert_eq!(MangaPageActions::OpenExternalChapter, manga_page.local_action_rx.recv().await.unwrap());
+
+        manga_page.update(MangaPageActions::ScrollChapterDown);
+        press_key(&mut manga_page, KeyCode::Char('r'));
+
+        assert_eq!(MangaPageActions::ReadChapter, manga_page.local_action_rx.recv().await.unwrap());
+    }
+
     #[tokio::test]
     async fn it_does_not_send_event_bookmark_chapter_selected_if_auto_bookmark_is_true() {
         let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).auto_bookmark(true);
@@ -1712,9 +3558,11 @@ mod test {
         assert!(manga_page.local_action_rx.is_empty());
     }
 
-    #[test]
-    fn it_bookmarks_currently_selected_chapter() {
-        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None);
+    #[tokio::test]
+    async fn it_bookmarks_currently_selected_chapter() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).with_database(test_database());
+
+        flush_events(&mut manga_page);
 
         let chapter_to_bookmark: ChapterItem = ChapterItem {
             id: "id_chapter_bookmarked".to_string(),
@@ -1733,9 +3581,11 @@ mod test {
             ..Default::default()
         });
 
-        let mut test_database = TestDatabase::new();
+        manga_page.bookmark_current_chapter_selected();
 
-        manga_page.bookmark_current_chapter_selected(&mut test_database);
+        assert_eq!(MangaPageEvents::ChapterBookmarked("id_chapter_bookmarked".to_string()), query_result(&mut manga_page));
+
+        manga_page.set_chapter_bookmarked("id_chapter_bookmarked".to_string());
 
         let bookmarked_chapter = manga_page
             .chapters
@@ -1748,6 +3598,13 @@ mod test {
             .unwrap();
 
         assert!(bookmarked_chapter.is_bookmarked);
+        assert_eq!(
+            "id_chapter_bookmarked",
+            query_test_database(&manga_page.database, |database| database.get_bookmarked(""))
+                .await
+                .map(|chapter| chapter.id)
+                .unwrap_or_default()
+        );
     }
 
     #[test]
@@ -1762,10 +3619,12 @@ mod test {
             widget: ChaptersListWidget {
                 chapters: vec![
                     ChapterItem {
+                        id: "first".to_string(),
                         is_bookmarked: true,
                         ..Default::default()
                     },
                     ChapterItem {
+                        id: "second".to_string(),
                         is_bookmarked: true,
                         ..Default::default()
                     },
@@ -1775,9 +3634,7 @@ mod test {
             ..Default::default()
         });
 
-        let mut test_database = TestDatabase::new();
-
-        manga_page.bookmark_current_chapter_selected(&mut test_database);
+        manga_page.set_chapter_bookmarked("first".to_string());
 
         let chapters = manga_page.get_chapter_data();
 
@@ -1785,165 +3642,452 @@ mod test {
         assert!(!chapters.widget.chapters[1].is_bookmarked);
     }
 
-    // clear all the events from initialization
-    fn flush_events<T: MangaTracker>(manga_page: &mut MangaPage<T>) {
-        while manga_page.local_event_rx.try_recv().is_ok() {}
-    }
+    #[test]
+    fn it_marks_as_read_the_chapters_up_to_the_one_selected() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).with_database(test_database());
 
-    #[tokio::test]
-    async fn it_sends_event_to_fetch_chapter_bookmarked_if_there_is_any() {
-        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None);
+        press_key(&mut manga_page, KeyCode::Char('x'));
 
-        flush_events(&mut manga_page);
+        assert_eq!(MangaPageActions::MarkReadUpToChapterSelected, manga_page.local_action_rx.blocking_recv().unwrap());
 
-        let expected = ChapterBookmarked {
-            id: "chapter_bookmarked".to_string(),
+        let chapter = |id: &str| ChapterItem {
+            id: id.to_string(),
             ..Default::default()
         };
 
-        let test_database = TestDatabase::with_bookmarked_chapter(expected.clone());
+        manga_page.chapters = Some(ChaptersData {
+            widget: ChaptersListWidget {
+                chapters: vec![chapter("third"), chapter("second"), chapter("first")],
+            },
+            ..Default::default()
+        });
+
+        flush_events(&mut manga_page);
 
-        let expected = MangaPageEvents::FetchChapterBookmarked(expected);
+        // the chapters up to the one selected are searched in the whole list, which has chapters not loaded
+        let chapter_ids = vec!["second".to_string(), "first".to_string(), "not_loaded".to_string()];
 
-        manga_page.get_chapter_bookmarked_from_db(test_database);
+        manga_page.mark_chapters_as_read(chapter_ids.clone(), (2, None));
 
-        let result = timeout(Duration::from_millis(250), manga_page.local_event_rx.recv())
-            .await
-            .unwrap()
-            .unwrap();
+        assert_eq!(MangaPageEvents::ChaptersMarkedAsRead(chapter_ids.clone(), (2, None)), query_result(&mut manga_page));
 
-        assert_eq!(expected, result);
+        manga_page.set_chapters_as_read(&chapter_ids);
+
+        let chapters = manga_page.get_chapter_data();
+
+        assert!(!chapters.widget.chapters[0].is_read);
+        assert!(chapters.widget.chapters[1].is_read);
+        assert!(chapters.widget.chapters[2].is_read);
     }
 
     #[test]
-    fn it_is_set_as_bookmark_not_found_when_no_chapter_is_bookmarked() {
-        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None);
+    fn it_does_not_mark_chapters_as_read_if_saving_them_failed() {
+        // Without tables every query fails
+        let mut manga_page: MangaPage<TrackerTest> =
+            MangaPage::new(Manga::default(), None).with_database(DatabaseActor::spawn(Connection::open_in_memory));
 
         flush_events(&mut manga_page);
 
-        let test_database = TestDatabase::new();
-
-        manga_page.get_chapter_bookmarked_from_db(test_database);
+        manga_page.chapters = Some(ChaptersData {
+            widget: ChaptersListWidget {
+                chapters: vec![
+                    ChapterItem {
+                        id: "first".to_string(),
+                        ..Default::default()
+                    },
+                    ChapterItem::default(),
+                ],
+            },
+            ..Default::default()
+        });
 
-        assert_eq!(manga_page.bookmark_state.phase, BookmarkPhase::NotFoundDatabase);
-    }
+        manga_page.mark_chapters_as_read(vec!["first".to_string()], (1, None));
 
-    #[derive(Clone)]
-    struct TestApiClient {
-        should_fail: bool,
-        response: (ChapterToRead, MangaToRead),
-    }
+        assert!(no_query_result(&mut manga_page));
 
-    impl TestApiClient {
-        pub fn with_response(response: (ChapterToRead, MangaToRead)) -> Self {
-            Self {
-                should_fail: false,
-                response,
-            }
-        }
+        let chapters = manga_page.get_chapter_data();
 
-        pub fn with_failing_response() -> Self {
-            Self {
-                should_fail: true,
-                response: (Default::default(), Default::default()),
-            }
-        }
+        assert!(chapters.widget.chapters.iter().all(|chapter| !chapter.is_read));
     }
 
-    impl FetchChapterBookmarked for TestApiClient {
-        async fn fetch_chapter_bookmarked(
-            &self,
-            _chapter: ChapterBookmarked,
-        ) -> Result<(ChapterToRead, MangaToRead), Box<dyn Error>> {
-            if self.should_fail {
-                return Err("should fail".into());
-            }
-            Ok(self.response.clone())
-        }
-    }
+    #[test]
+    fn it_toggles_the_selected_chapter_between_read_and_unread() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).with_database(test_database());
 
-    #[tokio::test]
-    async fn it_send_event_to_read_bookmark_chapter_by_pressing_tab() {
-        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None);
-        manga_page.handle_events(Events::Key(KeyCode::Tab.into()));
+        press_key(&mut manga_page, KeyCode::Char('X'));
 
-        let expected = MangaPageActions::GoToReadBookmarkedChapter;
+        assert_eq!(MangaPageActions::ToggleChapterSelectedRead, manga_page.local_action_rx.blocking_recv().unwrap());
 
-        let result = timeout(Duration::from_millis(250), manga_page.local_action_rx.recv())
-            .await
-            .unwrap()
-            .unwrap();
+        flush_events(&mut manga_page);
 
-        assert_eq!(expected, result)
-    }
+        let mut list_state = tui_widget_list::ListState::default();
 
-    #[tokio::test]
-    async fn it_sends_event_to_go_reader_page_from_bookmarked_chapter() {
-        let (tx, _) = unbounded_channel();
-        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).with_global_sender(tx);
+        list_state.select(Some(0));
 
-        flush_events(&mut manga_page);
-        let chapter_bookmarked: ChapterBookmarked = ChapterBookmarked {
-            id: "bookmarked".to_string(),
+        manga_page.chapters = Some(ChaptersData {
+            widget: ChaptersListWidget {
+                chapters: vec![
+                    ChapterItem {
+                        id: "selected".to_string(),
+                        is_read: true,
+                        ..Default::default()
+                    },
+                    ChapterItem::default(),
+                ],
+            },
+            state: list_state,
             ..Default::default()
-        };
+        });
 
-        let response = (
-            ChapterToRead {
-                id: "bookmarked".to_string(),
-                ..Default::default()
-            },
-            MangaToRead::default(),
-        );
+        manga_page.toggle_chapter_selected_read();
 
-        let expected = MangaPageEvents::ReadChapterBookmarked(response.0.clone(), response.1.clone());
+        assert_eq!(MangaPageEvents::ChapterReadToggled("selected".to_string(), false), query_result(&mut manga_page));
 
-        let api_client = TestApiClient::with_response(response);
+        manga_page.chapter_read_toggled("selected".to_string(), false);
 
-        manga_page.fetch_chapter_bookmarked(chapter_bookmarked, api_client);
+        assert!(!manga_page.get_chapter_data().widget.chapters[0].is_read);
 
-        let result = timeout(Duration::from_millis(250), manga_page.local_event_rx.recv())
-            .await
-            .unwrap()
-            .unwrap();
+        manga_page.toggle_chapter_selected_read();
 
-        assert_eq!(manga_page.bookmark_state.phase, BookmarkPhase::SearchingFromApi);
-        assert_eq!(expected, result)
-    }
+        assert_eq!(MangaPageEvents::ChapterReadToggled("selected".to_string(), true), query_result(&mut manga_page));
 
-    #[tokio::test]
-    async fn it_sends_event_chapter_bookmarked_failed_to_fetch() {
-        let (tx, _) = unbounded_channel();
-        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).with_global_sender(tx);
+        manga_page.chapter_read_toggled("selected".to_string(), true);
 
-        flush_events(&mut manga_page);
+        assert!(manga_page.get_chapter_data().widget.chapters[0].is_read);
+        assert!(!manga_page.get_chapter_data().widget.chapters[1].is_read);
 
-        let api_client = TestApiClient::with_failing_response();
+        manga_page.database = DatabaseActor::spawn(Connection::open_in_memory);
+        manga_page.toggle_chapter_selected_read();
 
-        manga_page.fetch_chapter_bookmarked(ChapterBookmarked::default(), api_client);
+        assert!(no_query_result(&mut manga_page));
+    }
 
-        let expected = MangaPageEvents::FetchBookmarkFailed;
+    // clear all the events from initialization
+    fn flush_events<T: MangaTracker>(manga_page: &mut MangaPage<T>) {
+        while manga_page.local_event_rx.try_recv().is_ok() {}
+    }
 
-        let result = timeout(Duration::from_millis(250), manga_page.local_event_rx.recv())
-            .await
-            .unwrap()
-            .unwrap();
+    #[test]
+    fn it_hides_chapters_of_scanlation_group_selected_and_shows_them_again() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).with_database(test_database());
 
-        assert_eq!(expected, result);
-    }
+        press_key(&mut manga_page, KeyCode::Char('g'));
 
-    #[tokio::test]
-    async fn if_manga_tracking_fails_it_sends_event_to_write_error_to_error_log_file() -> Result<(), Box<dyn Error>> {
-        let expected_error_message = "some_error_message";
-        let failing_tracker = TrackerTest::failing_with_error_message(&expected_error_message);
+        assert_eq!(MangaPageActions::BlockScanlationGroupSelected, manga_page.local_action_rx.blocking_recv().unwrap());
+
+        flush_events(&mut manga_page);
+
+        let mut list_state = tui_widget_list::ListState::default();
+
+        list_state.select(Some(0));
+
+        manga_page.chapters = Some(ChaptersData {
+            widget: ChaptersListWidget {
+                chapters: vec![ChapterItem::default().with_scanlator_id(Some("group_id".to_string()))],
+            },
+            state: list_state,
+            ..Default::default()
+        });
+
+        manga_page.block_scanlation_group_selected(false);
+
+        assert_eq!(MangaPageEvents::ScanlationGroupBlocked("group_id".to_string()), query_result(&mut manga_page));
+
+        manga_page.scanlation_group_blocked("group_id".to_string());
+        manga_page.block_scanlation_group_selected(true);
+
+        assert!(manga_page.local_event_rx.try_recv().unwrap().is_search_chapters());
+        assert_eq!(MangaPageEvents::ScanlationGroupBlocked("group_id".to_string()), query_result(&mut manga_page));
+
+        manga_page.scanlation_group_blocked("group_id".to_string());
+
+        assert_eq!(vec!["group_id".to_string()], manga_page.blocked_scanlation_groups);
+        assert!(manga_page.local_event_rx.try_recv().unwrap().is_search_chapters());
+
+        // The group blocked only in this manga is unblocked first
+        manga_page.unblock_scanlation_groups();
+
+        assert_eq!(MangaPageEvents::LoadBlockedScanlationGroups(vec!["group_id".to_string()]), query_result(&mut manga_page));
+
+        manga_page.unblock_scanlation_groups();
+
+        assert_eq!(MangaPageEvents::LoadBlockedScanlationGroups(vec![]), query_result(&mut manga_page));
+
+        manga_page.load_blocked_scanlation_groups(vec![]);
+
+        assert!(manga_page.blocked_scanlation_groups.is_empty());
+        assert!(manga_page.local_event_rx.try_recv().unwrap().is_search_chapters());
+    }
+
+    #[test]
+    fn it_does_not_hide_scanlation_group_if_saving_it_failed() {
+        let mut manga_page: MangaPage<TrackerTest> =
+            MangaPage::new(Manga::default(), None).with_database(DatabaseActor::spawn(Connection::open_in_memory));
+
+        flush_events(&mut manga_page);
+
+        let mut list_state = tui_widget_list::ListState::default();
+
+        list_state.select(Some(0));
+
+        manga_page.chapters = Some(ChaptersData {
+            widget: ChaptersListWidget {
+                chapters: vec![ChapterItem::default().with_scanlator_id(Some("group_id".to_string()))],
+            },
+            state: list_state,
+            ..Default::default()
+        });
+
+        manga_page.block_scanlation_group_selected(false);
+
+        assert!(no_query_result(&mut manga_page));
+        assert!(manga_page.blocked_scanlation_groups.is_empty());
+    }
+
+    #[test]
+    fn it_checks_chapters_status_again_only_if_it_is_the_same_manga() {
+        let manga = Manga {
+            id: "manga_displayed".to_string(),
+            ..Default::default()
+        };
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(manga, None);
+
+        flush_events(&mut manga_page);
+
+        manga_page.refresh_chapters_status("another_manga");
+
+        assert!(manga_page.local_event_rx.is_empty());
+
+        manga_page.refresh_chapters_status("manga_displayed");
+
+        assert_eq!(MangaPageEvents::CheckChapterStatus, manga_page.local_event_rx.try_recv().unwrap());
+    }
+
+    #[tokio::test]
+    async fn it_sends_event_to_fetch_chapter_bookmarked_if_there_is_any() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).with_database(test_database());
+
+        flush_events(&mut manga_page);
+
+        query_test_database(&manga_page.database, |database| {
+            database.bookmark(ChapterToBookmark {
+                chapter_id: "chapter_bookmarked",
+                manga_id: "",
+                chapter_title: "",
+                manga_title: "",
+                manga_cover_url: None,
+                translated_language: Languages::default(),
+                page_number: None,
+            })
+        })
+        .await;
+
+        manga_page.get_chapter_bookmarked_from_db();
+
+        let MangaPageEvents::FetchChapterBookmarked(chapter) = query_result(&mut manga_page) else {
+            panic!("the chapter bookmarked was not found");
+        };
+
+        assert_eq!("chapter_bookmarked", chapter.id);
+    }
+
+    #[test]
+    fn it_is_set_as_bookmark_not_found_when_no_chapter_is_bookmarked() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).with_database(test_database());
+
+        flush_events(&mut manga_page);
+
+        manga_page.get_chapter_bookmarked_from_db();
+
+        let event = query_result(&mut manga_page);
+
+        assert_eq!(MangaPageEvents::NoChapterBookmarked, event);
+
+        manga_page.local_event_tx.send(event).ok();
+        manga_page.tick();
+
+        assert_eq!(manga_page.bookmark_state.phase, BookmarkPhase::NotFoundDatabase);
+    }
+
+    #[derive(Clone)]
+    struct TestApiClient {
+        should_fail: bool,
+        response: (ChapterToRead, MangaToRead),
+    }
+
+    impl TestApiClient {
+        pub fn with_response(response: (ChapterToRead, MangaToRead)) -> Self {
+            Self {
+                should_fail: false,
+                response,
+            }
+        }
+
+        pub fn with_failing_response() -> Self {
+            Self {
+                should_fail: true,
+                response: (Default::default(), Default::default()),
+            }
+        }
+    }
+
+    impl FetchChapterBookmarked for TestApiClient {
+        async fn fetch_chapter_bookmarked(
+            &self,
+            _chapter: ChapterBookmarked,
+        ) -> Result<(ChapterToRead, MangaToRead), Box<dyn Error>> {
+            if self.should_fail {
+                return Err("should fail".into());
+            }
+            Ok(self.response.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn it_send_event_to_read_bookmark_chapter_by_pressing_tab() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None);
+        manga_page.handle_events(Events::Key(KeyCode::Tab.into()));
+
+        let expected = MangaPageActions::GoToReadBookmarkedChapter;
+
+        let result = timeout(Duration::from_millis(250), manga_page.local_action_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn it_shows_related_mangas_in_order_and_goes_to_the_one_selected() {
+        let (tx, mut rx) = unbounded_channel::<Events>();
+        let manga = Manga {
+            related: vec![
+                RelatedManga {
+                    id: "prequel".to_string(),
+                    relation: "prequel".to_string(),
+                },
+                RelatedManga {
+                    id: "sequel".to_string(),
+                    relation: "sequel".to_string(),
+                },
+            ],
+            ..Default::default()
+        };
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(manga, None).with_global_sender(tx);
+
+        let mut events = vec![];
+        while let Ok(event) = manga_page.local_event_rx.try_recv() {
+            events.push(event);
+        }
+
+        assert!(events.contains(&MangaPageEvents::SearchRelatedMangas));
+
+        let sequel = Manga {
+            id: "sequel".to_string(),
+            title: "Sequel".to_string(),
+            ..Default::default()
+        };
+        let prequel = Manga {
+            id: "prequel".to_string(),
+            title: "Prequel".to_string(),
+            ..Default::default()
+        };
+
+        manga_page.load_related_mangas(vec![sequel.clone(), prequel]);
+
+        let titles: Vec<&str> = manga_page.related_mangas.iter().map(|related| related.manga.title.as_str()).collect();
+
+        assert_eq!(vec!["Prequel", "Sequel"], titles);
+
+        press_key(&mut manga_page, KeyCode::Char('R'));
+
+        assert_eq!(MangaPageActions::ToggleRelatedMangasList, manga_page.local_action_rx.blocking_recv().unwrap());
+
+        manga_page.toggle_related_mangas_list();
+
+        press_key(&mut manga_page, KeyCode::Char('j'));
+
+        assert_eq!(MangaPageActions::ScrollDownRelatedMangas, manga_page.local_action_rx.blocking_recv().unwrap());
+
+        manga_page.related_mangas_state.select_next();
+
+        press_key(&mut manga_page, KeyCode::Enter);
+
+        assert_eq!(MangaPageActions::GoToRelatedManga, manga_page.local_action_rx.blocking_recv().unwrap());
+
+        manga_page.go_to_related_manga();
+
+        assert_eq!(Events::GoToMangaPage(MangaItem::new(sequel)), rx.blocking_recv().expect("no event was sent"));
+    }
+
+    #[tokio::test]
+    async fn it_sends_event_to_go_reader_page_from_bookmarked_chapter() {
+        let (tx, _) = unbounded_channel();
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).with_global_sender(tx);
+
+        flush_events(&mut manga_page);
+        let chapter_bookmarked: ChapterBookmarked = ChapterBookmarked {
+            id: "bookmarked".to_string(),
+            ..Default::default()
+        };
+
+        let response = (
+            ChapterToRead {
+                id: "bookmarked".to_string(),
+                ..Default::default()
+            },
+            MangaToRead::default(),
+        );
+
+        let expected = MangaPageEvents::ReadChapterBookmarked(response.0.clone(), response.1.clone());
+
+        let api_client = TestApiClient::with_response(response);
+
+        manga_page.fetch_chapter_bookmarked(chapter_bookmarked, api_client);
+
+        let result = timeout(Duration::from_millis(250), manga_page.local_event_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(manga_page.bookmark_state.phase, BookmarkPhase::SearchingFromApi);
+        assert_eq!(expected, result)
+    }
+
+    #[tokio::test]
+    async fn it_sends_event_chapter_bookmarked_failed_to_fetch() {
+        let (tx, _) = unbounded_channel();
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).with_global_sender(tx);
+
+        flush_events(&mut manga_page);
+
+        let api_client = TestApiClient::with_failing_response();
+
+        manga_page.fetch_chapter_bookmarked(ChapterBookmarked::default(), api_client);
+
+        let expected = MangaPageEvents::FetchBookmarkFailed;
+
+        let result = timeout(Duration::from_millis(250), manga_page.local_event_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[tokio::test]
+    async fn if_manga_tracking_fails_it_sends_event_to_write_error_to_error_log_file() -> Result<(), Box<dyn Error>> {
+        let expected_error_message = "some_error_message";
+        let failing_tracker = TrackerTest::failing_with_error_message(&expected_error_message);
 
         let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), Some(Picker::new((1, 2))));
 
         flush_events(&mut manga_page);
 
-        manga_page.track_manga(Some(failing_tracker), "manga-test".to_string(), 1, Some(3));
+        manga_page.track_manga(vec![failing_tracker], "manga-test".to_string(), 1, Some(3));
 
-        let expected = MangaPageEvents::TrackingFailed(expected_error_message.to_string());
+        let expected = MangaPageEvents::TrackingFailed(format!("test: {expected_error_message}"));
 
         let result = timeout(Duration::from_millis(500), manga_page.local_event_rx.recv()).await?.unwrap();
 
@@ -1951,4 +4095,530 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn it_keeps_updating_the_other_trackers_when_one_fails() -> Result<(), Box<dyn Error>> {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), Some(Picker::new((1, 2))));
+
+        flush_events(&mut manga_page);
+
+        manga_page.track_manga(
+            vec![
+                TrackerTest::failing_with_error_message("first"),
+                TrackerTest::new(),
+                TrackerTest::failing_with_error_message("third"),
+            ],
+            "manga-test".to_string(),
+            1,
+            None,
+        );
+
+        let first = timeout(Duration::from_millis(500), manga_page.local_event_rx.recv()).await?.unwrap();
+        let third = timeout(Duration::from_millis(500), manga_page.local_event_rx.recv()).await?.unwrap();
+
+        assert_eq!(MangaPageEvents::TrackingFailed("test: first".to_string()), first);
+        assert_eq!(MangaPageEvents::TrackingFailed("test: third".to_string()), third);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_sets_the_status_and_score_of_the_tracked_entry() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None);
+
+        press_key(&mut manga_page, KeyCode::Char('S'));
+
+        assert!(manga_page.local_action_rx.try_recv().is_err());
+
+        let mut manga_page: MangaPage<TrackerTest> =
+            MangaPage::new(Manga::default(), None).with_manga_trackers(vec![TrackerTest::new()]);
+
+        for (key, expected) in [
+            (KeyCode::Char('S'), MangaPageActions::ToggleTrackerEntryForm),
+            (KeyCode::Char('j'), MangaPageActions::ScrollDownTrackingStatus),
+            (KeyCode::Char('+'), MangaPageActions::IncreaseTrackerScore),
+            (KeyCode::Char('+'), MangaPageActions::IncreaseTrackerScore),
+            (KeyCode::Char('-'), MangaPageActions::DecreaseTrackerScore),
+        ] {
+            press_key(&mut manga_page, key);
+            let action = manga_page.local_action_rx.recv().await.unwrap();
+            assert_eq!(expected, action);
+            manga_page.update(action);
+        }
+
+        let form = manga_page.tracker_entry_form.as_ref().expect("the form should be open");
+
+        assert_eq!(TrackingStatus::Completed, form.status());
+        assert_eq!(Some(1), form.score);
+
+        press_key(&mut manga_page, KeyCode::Enter);
+        let action = manga_page.local_action_rx.recv().await.unwrap();
+
+        assert_eq!(MangaPageActions::SaveTrackerEntry, action);
+
+        manga_page.update(action);
+
+        assert!(manga_page.tracker_entry_form.is_none());
+    }
+
+    #[test]
+    fn it_marks_chapters_as_read_up_to_the_progress_in_the_tracker() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None).with_database(test_database());
+
+        let chapters = ["3", "2", "1"]
+            .into_iter()
+            .map(|number| {
+                let mut chapter = ChapterData {
+                    id: format!("chapter_{number}"),
+                    ..Default::default()
+                };
+                chapter.attributes.chapter = Some(number.to_string());
+                chapter
+            })
+            .collect();
+
+        manga_page.load_chapters(Some(ChapterResponse {
+            data: chapters,
+            total: 3,
+            ..Default::default()
+        }));
+
+        flush_events(&mut manga_page);
+
+        manga_page.tracker_progress = Some(2);
+        manga_page.mark_chapters_read_in_tracker();
+
+        let chapters_read = vec!["chapter_2".to_string(), "chapter_1".to_string()];
+
+        assert_eq!(MangaPageEvents::ChaptersMarkedAsReadInTracker(chapters_read.clone()), query_result(&mut manga_page));
+
+        manga_page.set_chapters_as_read(&chapters_read);
+
+        let is_read: Vec<bool> = manga_page
+            .get_chapter_data()
+            .widget
+            .chapters
+            .iter()
+            .map(|chapter| chapter.is_read)
+            .collect();
+
+        assert_eq!(vec![false, true, true], is_read);
+    }
+
+    #[tokio::test]
+    async fn it_binds_the_manga_to_the_tracker_entry_chosen() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(
+            Manga {
+                id: "some_manga".to_string(),
+                title: "some title".to_string(),
+                ..Default::default()
+            },
+            None,
+        )
+        .with_manga_trackers(vec![TrackerTest::new()])
+        .with_database(test_database());
+
+        flush_events(&mut manga_page);
+
+        press_key(&mut manga_page, KeyCode::Char('B'));
+        let action = manga_page.local_action_rx.recv().await.unwrap();
+
+        assert_eq!(MangaPageActions::ToggleTrackerBinding, action);
+
+        manga_page.update(action);
+        manga_page.load_tracker_entries(vec![
+            TrackerEntry {
+                id: "1".to_string(),
+                title: "some title".to_string(),
+            },
+            TrackerEntry {
+                id: "2".to_string(),
+                title: "some title: side story".to_string(),
+            },
+        ]);
+
+        press_key(&mut manga_page, KeyCode::Char('j'));
+        let action = manga_page.local_action_rx.recv().await.unwrap();
+        manga_page.update(action);
+
+        press_key(&mut manga_page, KeyCode::Enter);
+
+        assert_eq!(MangaPageActions::BindTrackerEntry, manga_page.local_action_rx.recv().await.unwrap());
+
+        manga_page.bind_tracker_entry_selected();
+
+        assert_eq!(MangaPageEvents::TrackerEntryBound, query_result(&mut manga_page));
+
+        manga_page.tracker_entry_bound();
+
+        assert!(manga_page.tracker_binding.is_none());
+        assert_eq!(MangaPageEvents::FetchTrackerProgress, manga_page.local_event_rx.try_recv().unwrap());
+        assert_eq!(
+            Some("2".to_string()),
+            query_test_database(&manga_page.database, |database| Ok(database.get_tracker_entry("some_manga", "test")?)).await
+        );
+    }
+
+    #[tokio::test]
+    async fn it_adds_the_manga_to_the_collection_selected() {
+        let database = test_database();
+        query_test_database(&database, |database| {
+            database.create_collection("Favorites")?;
+            Ok(database.create_collection("On hold")?)
+        })
+        .await;
+
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(
+            Manga {
+                id: "some_manga".to_string(),
+                title: "some title".to_string(),
+                ..Default::default()
+            },
+            None,
+        )
+        .with_database(database);
+
+        flush_events(&mut manga_page);
+
+        press_key(&mut manga_page, KeyCode::Char('C'));
+
+        assert_eq!(MangaPageActions::ToggleCollectionsPopup, manga_page.local_action_rx.recv().await.unwrap());
+
+        manga_page.toggle_collections_popup();
+
+        let MangaPageEvents::LoadCollectionsPopup(collections, in_collection) = query_result(&mut manga_page) else {
+            panic!("the collections were not loaded");
+        };
+
+        manga_page.load_collections_popup(collections, in_collection);
+
+        press_key(&mut manga_page, KeyCode::Char('j'));
+        let action = manga_page.local_action_rx.recv().await.unwrap();
+        manga_page.update(action);
+
+        press_key(&mut manga_page, KeyCode::Enter);
+
+        assert_eq!(MangaPageActions::ToggleInCollection, manga_page.local_action_rx.recv().await.unwrap());
+
+        manga_page.toggle_in_collection_selected();
+
+        let on_hold = query_test_database(&manga_page.database, |database| Ok(database.get_collections()?[1].clone())).await;
+
+        assert_eq!(1, on_hold.total_mangas);
+        assert_eq!(MangaPageEvents::InCollectionToggled(on_hold.id, true), query_result(&mut manga_page));
+
+        manga_page.in_collection_toggled(on_hold.id, true);
+
+        assert_eq!(vec![on_hold.id], manga_page.collections_popup.as_ref().unwrap().in_collection);
+        assert_eq!(1, manga_page.collections_popup.as_ref().unwrap().collections[1].total_mangas);
+
+        manga_page.toggle_in_collection_selected();
+
+        assert_eq!(MangaPageEvents::InCollectionToggled(on_hold.id, false), query_result(&mut manga_page));
+
+        manga_page.in_collection_toggled(on_hold.id, false);
+
+        assert!(
+            query_test_database(&manga_page.database, |database| Ok(database.get_manga_collections("some_manga")?))
+                .await
+                .is_empty()
+        );
+        assert!(manga_page.collections_popup.as_ref().unwrap().in_collection.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_adds_and_removes_the_tags_typed() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(
+            Manga {
+                id: "some_manga".to_string(),
+                title: "some title".to_string(),
+                ..Default::default()
+            },
+            None,
+        )
+        .with_database(test_database());
+
+        flush_events(&mut manga_page);
+
+        press_key(&mut manga_page, KeyCode::Char('T'));
+
+        assert_eq!(MangaPageActions::ToggleTagsPopup, manga_page.local_action_rx.recv().await.unwrap());
+
+        manga_page.toggle_tags_popup();
+
+        assert_eq!(MangaPageEvents::LoadTagsPopup(vec![], vec![]), query_result(&mut manga_page));
+
+        manga_page.load_tags_popup(vec![], vec![]);
+
+        assert!(manga_page.is_typing());
+
+        for key in "Comfy".chars() {
+            press_key(&mut manga_page, KeyCode::Char(key));
+        }
+        press_key(&mut manga_page, KeyCode::Enter);
+
+        assert_eq!(MangaPageActions::ToggleTag, manga_page.local_action_rx.recv().await.unwrap());
+
+        manga_page.toggle_tag();
+
+        let comfy = vec!["comfy".to_string()];
+
+        assert!(manga_page.tags_popup.as_ref().unwrap().input.value().is_empty());
+        assert_eq!(MangaPageEvents::LoadTags(comfy.clone(), comfy.clone()), query_result(&mut manga_page));
+
+        manga_page.load_tags(comfy.clone(), comfy.clone());
+
+        assert_eq!(comfy, manga_page.tags_popup.as_ref().unwrap().tags);
+
+        manga_page.tags_popup.as_mut().unwrap().input = Input::new("comfy".to_string());
+        manga_page.toggle_tag();
+
+        let MangaPageEvents::LoadTags(tags, _) = query_result(&mut manga_page) else {
+            panic!("the tags were not loaded");
+        };
+        manga_page.load_tags(tags, comfy);
+
+        assert!(manga_page.tags_popup.as_ref().unwrap().tags.is_empty());
+        assert!(
+            query_test_database(&manga_page.database, |database| Ok(database.get_manga_tags("some_manga")?))
+                .await
+                .is_empty()
+        );
+
+        press_key(&mut manga_page, KeyCode::Esc);
+
+        assert_eq!(MangaPageActions::ToggleTagsPopup, manga_page.local_action_rx.recv().await.unwrap());
+    }
+    #[tokio::test]
+    async fn it_lists_the_chapters_before_downloading_all_of_them() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None);
+
+        manga_page.ask_download_all_chapters();
+        manga_page.confirm_download_all_chapters();
+
+        assert_eq!(DownloadPhase::FetchingChaptersData, manga_page.download_all_chapters_state.phase);
+
+        let chapters: Vec<ChapterData> = (1..=3)
+            .map(|number| ChapterData {
+                id: number.to_string(),
+                attributes: ChapterAttribute {
+                    chapter: Some(number.to_string()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .collect();
+
+        manga_page.load_download_all_preview(chapters);
+
+        assert_eq!(DownloadPhase::Previewing, manga_page.download_all_chapters_state.phase);
+        assert_eq!(vec!["Ch. 1", "Ch. 2", "Ch. 3"], manga_page.download_all_chapters_state.preview.items);
+        assert!(!manga_page.is_downloading_all_chapters());
+
+        press_key(&mut manga_page, KeyCode::Char('j'));
+
+        assert_eq!(Some(1), manga_page.download_all_chapters_state.preview.list_state.selected());
+
+        press_key(&mut manga_page, KeyCode::Enter);
+
+        assert_eq!(MangaPageActions::DownloadChaptersPreviewed, manga_page.local_action_rx.recv().await.unwrap());
+
+        press_key(&mut manga_page, KeyCode::Esc);
+
+        let action = manga_page.local_action_rx.recv().await.unwrap();
+        assert_eq!(MangaPageActions::CancelDownloadAll, action);
+        manga_page.update(action);
+
+        assert!(!manga_page.download_process_started());
+        assert!(manga_page.chapters_to_download_all.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_follows_and_unfollows_the_author() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(
+            Manga {
+                id: "some_manga".to_string(),
+                author: Author {
+                    id: "some_author".to_string(),
+                    name: "Some author".to_string(),
+                },
+                ..Default::default()
+            },
+            None,
+        )
+        .with_database(test_database());
+
+        flush_events(&mut manga_page);
+
+        press_key(&mut manga_page, KeyCode::Char('F'));
+
+        assert_eq!(MangaPageActions::ToggleFollowAuthor, manga_page.local_action_rx.recv().await.unwrap());
+
+        manga_page.toggle_follow_author();
+
+        assert_eq!(MangaPageEvents::LoadFollowingAuthor(true), query_result(&mut manga_page));
+        assert!(query_test_database(&manga_page.database, |database| Ok(database.is_following_author("some_author")?)).await);
+
+        manga_page.toggle_follow_author();
+
+        assert_eq!(MangaPageEvents::LoadFollowingAuthor(false), query_result(&mut manga_page));
+        assert!(!query_test_database(&manga_page.database, |database| Ok(database.is_following_author("some_author")?)).await);
+    }
+
+    #[tokio::test]
+    async fn it_merges_the_duplicate_found_in_the_library() {
+        let database = test_database();
+        let duplicate = database
+            .run(|conn| {
+                save_plan_to_read(
+                    MangaPlanToReadSave {
+                        id: "duplicate",
+                        title: "Some manga",
+                        img_url: None,
+                    },
+                    conn,
+                )?;
+                Database::new(conn).find_duplicate("some_manga", &["Some Manga".to_string()])
+            })
+            .await
+            .unwrap();
+
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(
+            Manga {
+                id: "some_manga".to_string(),
+                title: "Some Manga".to_string(),
+                ..Default::default()
+            },
+            None,
+        )
+        .with_database(database);
+
+        flush_events(&mut manga_page);
+
+        press_key(&mut manga_page, KeyCode::Char('M'));
+
+        assert!(manga_page.local_action_rx.try_recv().is_err());
+
+        manga_page.duplicate = duplicate;
+
+        press_key(&mut manga_page, KeyCode::Char('M'));
+
+        assert_eq!(MangaPageActions::ToggleMergeConfirmation, manga_page.local_action_rx.recv().await.unwrap());
+
+        manga_page.merge_duplicate();
+
+        assert!(no_query_result(&mut manga_page), "nothing is merged before the user confirms it");
+
+        manga_page.toggle_merge_confirmation();
+
+        assert_eq!(2, manga_page.merge_confirmation.as_ref().unwrap().items.len());
+        assert!(manga_page.is_typing());
+
+        press_key(&mut manga_page, KeyCode::Esc);
+
+        assert_eq!(MangaPageActions::ToggleMergeConfirmation, manga_page.local_action_rx.recv().await.unwrap());
+
+        manga_page.toggle_merge_confirmation();
+
+        assert!(manga_page.merge_confirmation.is_none());
+
+        manga_page.toggle_merge_confirmation();
+
+        press_key(&mut manga_page, KeyCode::Enter);
+
+        assert_eq!(MangaPageActions::MergeDuplicate, manga_page.local_action_rx.recv().await.unwrap());
+
+        manga_page.merge_duplicate();
+
+        assert!(manga_page.merge_confirmation.is_none());
+
+        assert_eq!(MangaPageEvents::DuplicateMerged, query_result(&mut manga_page));
+
+        manga_page.duplicate_merged();
+
+        assert!(manga_page.duplicate.is_none());
+        assert_eq!(MangaPageEvents::CheckChapterStatus, manga_page.local_event_rx.try_recv().unwrap());
+        assert!(
+            query_test_database(&manga_page.database, |database| {
+                Ok(database.find_duplicate("some_manga", &["Some Manga".to_string()])?)
+            })
+            .await
+            .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rates_the_manga_from_1_to_10() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(
+            Manga {
+                id: "some_manga".to_string(),
+                ..Default::default()
+            },
+            None,
+        )
+        .with_database(test_database());
+
+        flush_events(&mut manga_page);
+
+        press_key(&mut manga_page, KeyCode::Char('+'));
+
+        assert_eq!(MangaPageActions::IncreasePersonalRating, manga_page.local_action_rx.recv().await.unwrap());
+
+        manga_page.change_personal_rating(true);
+
+        assert_eq!(MangaPageEvents::LoadPersonalRating(Some(1)), query_result(&mut manga_page));
+        assert_eq!(
+            Some(1),
+            query_test_database(&manga_page.database, |database| Ok(database.get_personal_rating("some_manga")?)).await
+        );
+
+        manga_page.personal_rating = Some(10);
+        manga_page.change_personal_rating(true);
+
+        assert_eq!(MangaPageEvents::LoadPersonalRating(Some(10)), query_result(&mut manga_page));
+
+        manga_page.personal_rating = Some(1);
+        manga_page.change_personal_rating(false);
+
+        assert_eq!(MangaPageEvents::LoadPersonalRating(None), query_result(&mut manga_page));
+        assert_eq!(
+            None,
+            query_test_database(&manga_page.database, |database| Ok(database.get_personal_rating("some_manga")?)).await
+        );
+    }
+
+    #[tokio::test]
+    async fn global_shortcuts_are_not_used_while_a_popup_or_list_is_open() {
+        let mut manga_page: MangaPage<TrackerTest> = MangaPage::new(Manga::default(), None);
+
+        assert!(!manga_page.is_typing());
+
+        manga_page.tracker_binding = Some(TrackerBindingPopup::default());
+
+        assert!(manga_page.is_typing());
+
+        manga_page.tracker_binding = None;
+        manga_page.collections_popup = Some(CollectionsPopup::default());
+
+        assert!(manga_page.is_typing());
+
+        manga_page.collections_popup = None;
+        manga_page.tracker_entry_form = Some(TrackerEntryForm::new());
+
+        assert!(manga_page.is_typing());
+
+        manga_page.tracker_entry_form = None;
+        manga_page.is_list_related_mangas_open = true;
+
+        assert!(manga_page.is_typing());
+
+        manga_page.is_list_related_mangas_open = false;
+        manga_page.is_list_languages_open = true;
+
+        assert!(manga_page.is_typing());
+
+        manga_page.is_list_languages_open = false;
+
+        assert!(!manga_page.is_typing());
+    }
 }
@@ -2,16 +2,19 @@ use std::cmp::Ordering;
 use std::error::Error;
 use std::fmt::Display;
 use std::future::Future;
+use std::time::{Duration, Instant};
 
 use crossterm::event::{KeyCode, KeyEvent};
-use image::DynamicImage;
-use manga_tui::SortedVec;
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, Rgba, RgbaImage};
+use manga_tui::{ChapterNumber, SortedVec};
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::style::Stylize;
 use ratatui::text::{Line, ToSpan};
 use ratatui::widgets::{Block, List, Paragraph, StatefulWidget, Widget, Wrap};
 use ratatui::Frame;
-use ratatui_image::picker::Picker;
+use ratatui_image::picker::{Picker, ProtocolType};
 use ratatui_image::protocol::StatefulProtocol;
 use ratatui_image::{Resize, StatefulImage};
 use reqwest::Url;
@@ -22,8 +25,10 @@ use tokio::task::JoinSet;
 
 use crate::backend::api_responses::AggregateChapterResponse;
 use crate::backend::database::{
-    save_history, Bookmark, ChapterToBookmark, ChapterToSaveHistory, Database, MangaReadingHistorySave,
+    get_chapters_history_status, save_history, save_plan_to_read, Bookmark, ChapterBookmarked, ChapterProgress, ChapterToBookmark,
+    ChapterToSaveHistory, Database, MangaHistoryType, MangaPlanToReadSave, MangaReadingHistorySave, ReadingMode,
 };
+use crate::backend::database_actor::DatabaseActor;
 use crate::backend::error_log::{write_to_error_log, ErrorType};
 use crate::backend::filter::Languages;
 use crate::backend::tracker::{track_manga, MangaTracker};
@@ -31,6 +36,8 @@ use crate::backend::tui::Events;
 use crate::common::format_error_message_tracking_reading_history;
 use crate::config::MangaTuiConfig;
 use crate::global::{ERROR_STYLE, INSTRUCTIONS_STYLE};
+use crate::utils::spin_loader;
+use crate::view::tasks::manga::sync_chapters_read_mangadex;
 use crate::view::tasks::reader::get_manga_panel;
 use crate::view::widgets::reader::{PageItemState, PagesItem, PagesList, PagesListState};
 use crate::view::widgets::Component;
@@ -49,6 +56,12 @@ pub struct MangaPanel {
     pub dimensions: (u32, u32),
 }
 
+/// Narrower than this two pages side by side are too small to read, so double page mode shows one page at a time
+const MIN_SPREAD_WIDTH: u16 = 120;
+
+/// Staying longer on a page most likely means the reader was left open, so the rest is not counted as reading time
+const MAX_TIME_ON_A_PAGE: Duration = Duration::from_secs(5 * 60);
+
 #[derive(Debug, PartialEq, Clone, Default)]
 pub enum PageSize {
     #[default]
@@ -56,6 +69,20 @@ pub enum PageSize {
     Wide,
 }
 
+impl PageSize {
+    fn of(width: u32, height: u32) -> Self {
+        if width > height && width > 300 { Self::Wide } else { Self::Normal }
+    }
+
+    /// The list of pages, the page itself and the instructions
+    fn layout(&self) -> [Constraint; 3] {
+        match self {
+            Self::Normal => [Constraint::Percentage(30), Constraint::Percentage(40), Constraint::Percentage(30)],
+            Self::Wide => [Constraint::Percentage(20), Constraint::Percentage(60), Constraint::Percentage(20)],
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum MangaReaderActions {
     BookMarkCurrentChapter,
@@ -64,12 +91,16 @@ pub enum MangaReaderActions {
     NextPage,
     PreviousPage,
     ReloadPage,
+    SwitchReadingMode(ReadingMode),
+    ShiftSpreads,
+    AddToLibrary(MangaHistoryType),
     ExitReaderPage,
 }
 
 #[derive(Debug, PartialEq, Eq, Default)]
 pub enum State {
     ManualBookmark,
+    AddedToLibrary(MangaHistoryType),
     ErrorSearchingChapter,
     DisplayingChapterNotFound,
     SearchingChapter,
@@ -94,21 +125,148 @@ pub enum MangaReaderEvents {
     FetchPages,
     LoadPage(PageData),
     FailedPage(usize),
+    PageEncoded(EncodedPage),
     ErrorTrackingReadingProgress(String),
+    CheckSkippedChapters,
+    /// Ids of the chapters of this manga marked as read in the database
+    LoadChaptersRead(Vec<String>),
+    // The results of the queries sent to the database actor
+    LoadReadingMode(ReadingMode),
+    ReadingHistorySaved(String),
+    ChapterBookmarked(usize),
+    AddedToLibrary(MangaHistoryType),
+}
+
+/// A page resized and encoded in the background for the area it is shown in
+pub struct EncodedPage {
+    pub index: usize,
+    pub area: Rect,
+    pub protocol: Box<dyn StatefulProtocol>,
+}
+
+impl std::fmt::Debug for EncodedPage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncodedPage")
+            .field("index", &self.index)
+            .field("area", &self.area)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for EncodedPage {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.area == other.area
+    }
 }
 
 pub struct Page {
     pub image_state: Option<Box<dyn StatefulProtocol>>,
+    /// Only kept in webtoon mode, where parts of the page are stitched with the pages next to it
+    pub image: Option<DynamicImage>,
     pub dimensions: Option<(u32, u32)>,
+    /// The area the page is being encoded for in the background
+    pub encoding_area: Option<Rect>,
 }
 
 impl Page {
     pub fn new() -> Self {
         Self {
             image_state: None,
+            image: None,
             dimensions: None,
+            encoding_area: None,
         }
     }
+
+    pub fn is_loaded(&self) -> bool {
+        self.image_state.is_some() || self.image.is_some()
+    }
+}
+
+/// The pages shown side by side with the page `index` in double page mode. Pages are paired from the first one or, once
+/// `shifted`, from the second one so the first page is shown alone
+fn spread_of(index: usize, shifted: bool, total_pages: usize) -> (usize, Option<usize>) {
+    let shift = usize::from(shifted);
+
+    if index < shift {
+        return (index, None);
+    }
+
+    let first = index - (index - shift) % 2;
+
+    (first, Some(first + 1).filter(|second| *second < total_pages))
+}
+
+/// The pages stitched for the part of the chapter shown in webtoon mode, they are stitched again when the strip is
+/// scrolled or resized
+struct WebtoonStrip {
+    area: Rect,
+    index: usize,
+    offset: f64,
+    protocol: Box<dyn StatefulProtocol>,
+}
+
+/// The part of a page shown in the webtoon strip, in pixels of the page scaled to the width of the strip
+#[derive(Debug, PartialEq, Eq)]
+struct StripSlice {
+    index: usize,
+    top: u32,
+    height: u32,
+}
+
+/// The slices of the pages filling a strip `height` pixels tall, starting at page `start` scrolled past by `offset`, the
+/// strip stops at the first page not loaded yet
+fn webtoon_slices(scaled_heights: &[Option<u32>], start: usize, offset: f64, height: u32) -> Vec<StripSlice> {
+    let mut slices = vec![];
+    let mut remaining = height;
+
+    for (index, page_height) in scaled_heights.iter().enumerate().skip(start) {
+        let Some(page_height) = *page_height else {
+            break;
+        };
+
+        let top = if index == start { ((offset * f64::from(page_height)) as u32).min(page_height) } else { 0 };
+        let shown = (page_height - top).min(remaining);
+
+        if shown > 0 {
+            slices.push(StripSlice {
+                index,
+                top,
+                height: shown,
+            });
+        }
+
+        remaining -= shown;
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    slices
+}
+
+/// The page at the top of the webtoon strip and how much of it is scrolled past after scrolling `pixels`, pages not
+/// loaded yet are taken as tall as the strip. The strip stops once the end of the last page is at its bottom
+fn scroll_position(scaled_heights: &[Option<u32>], index: usize, offset: f64, pixels: f64, strip_height: u32) -> (usize, f64) {
+    let height_of = |index: usize| f64::from(scaled_heights.get(index).copied().flatten().unwrap_or(strip_height).max(1));
+
+    let mut index = index;
+    let mut position = offset * height_of(index) + pixels;
+
+    while position < 0.0 && index > 0 {
+        index -= 1;
+        position += height_of(index);
+    }
+
+    while position >= height_of(index) && index + 1 < scaled_heights.len() {
+        position -= height_of(index);
+        index += 1;
+    }
+
+    let max_position =
+        if index + 1 >= scaled_heights.len() { (height_of(index) - f64::from(strip_height)).max(0.0) } else { height_of(index) };
+
+    (index, position.clamp(0.0, max_position) / height_of(index))
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -119,6 +277,8 @@ pub struct ChapterToRead {
     /// This is string because it could also be "none" for chapters with no volume associated
     pub volume_number: Option<String>,
     pub num_page_bookmarked: Option<u32>,
+    /// The last page reached the previous time the chapter was read, unlike the bookmark it is not highlighted
+    pub page_to_resume: Option<u32>,
     pub language: Languages,
     pub pages_url: Vec<Url>,
 }
@@ -155,6 +315,7 @@ impl Default for ChapterToRead {
             pages_url: vec![],
             language: Languages::default(),
             num_page_bookmarked: None,
+            page_to_resume: None,
         }
     }
 }
@@ -202,14 +363,13 @@ impl SortedVolumes {
 
 impl SortedChapters {
     pub fn new(chapters: Vec<Chapter>) -> Self {
-        Self(SortedVec::sorted_by(chapters, |a, b| {
-            a.number.parse::<f64>().unwrap_or(0.0).total_cmp(&b.number.parse().unwrap_or(0.0))
-        }))
+        Self(SortedVec::sorted_by(chapters, |a, b| ChapterNumber::parse(&a.number).cmp(&ChapterNumber::parse(&b.number))))
     }
 
     pub fn search_next_chapter(&self, current: &str) -> Option<Chapter> {
+        let current = ChapterNumber::parse(current);
         let chapters = self.as_slice();
-        let position = chapters.iter().position(|chap| chap.number == current);
+        let position = chapters.iter().position(|chap| ChapterNumber::parse(&chap.number) == current);
 
         match position {
             Some(index) => chapters.get(index + 1).cloned(),
@@ -295,7 +455,29 @@ impl ListOfChapters {
             .as_slice()
             .last()
             .cloned()
-            .filter(|chapter| chapter.number != chapter_number.to_string())
+            .filter(|chapter| ChapterNumber::parse(&chapter.number) != ChapterNumber::from(chapter_number))
+    }
+
+    /// Chapters before `chapter_number` which are not read since the last one that was, if no chapter before it was
+    /// read nothing was skipped since the manga may have been started from there on purpose
+    pub fn count_skipped_chapters(&self, chapter_number: f64, chapters_read: &[String]) -> usize {
+        let current = ChapterNumber::from(chapter_number);
+
+        let mut previous_chapters: Vec<&Chapter> = self
+            .volumes
+            .as_slice()
+            .iter()
+            .flat_map(|volume| volume.chapters.as_slice())
+            .filter(|chapter| ChapterNumber::parse(&chapter.number) < current)
+            .collect();
+
+        previous_chapters.sort_by_key(|chapter| ChapterNumber::parse(&chapter.number));
+        previous_chapters.dedup_by_key(|chapter| ChapterNumber::parse(&chapter.number));
+
+        match previous_chapters.iter().rposition(|chapter| chapters_read.contains(&chapter.id)) {
+            Some(last_read) => previous_chapters.len() - last_read - 1,
+            None => 0,
+        }
     }
 
     pub fn get_previous_chapter(&self, volume: Option<&str>, chapter_number: f64) -> Option<Chapter> {
@@ -305,14 +487,16 @@ impl ListOfChapters {
 
         let chapters = volumes.chapters.as_slice();
 
-        let current_index = chapters.iter().position(|chap| chap.number == chapter_number.to_string());
+        let current = ChapterNumber::from(chapter_number);
+
+        let current_index = chapters.iter().position(|chap| ChapterNumber::parse(&chap.number) == current);
 
         match current_index {
             Some(index) => {
                 let previous_chapter = chapters
                     .get(index.saturating_sub(1))
                     .cloned()
-                    .filter(|chap| chap.number != chapter_number.to_string());
+                    .filter(|chap| ChapterNumber::parse(&chap.number) != current);
 
                 previous_chapter.or_else(|| self.get_previous_chapter_in_previous_volume(volume_number, chapter_number))
             },
@@ -333,14 +517,35 @@ where
     pages: Vec<Page>,
     pages_list: PagesList,
     current_page_size: PageSize,
+    /// Where the reader was rendered last, the pages encoded in the background are encoded for the size they will have
+    /// in it
+    reader_area: Rect,
+    /// The terminal was resized so the pages have to be encoded again for the new area
+    needs_relayout: bool,
+    /// Time spent reading the current chapter which is not saved yet
+    reading_time: Duration,
+    last_page_turn: Instant,
     page_list_state: PagesListState,
+    reading_mode: ReadingMode,
+    /// How much of the page at the top of the webtoon strip is scrolled past, from 0 to 1
+    webtoon_offset: f64,
+    webtoon_strip: Option<WebtoonStrip>,
+    /// In double page mode the pages are paired from the second one, so the first one is shown alone
+    spread_shifted: bool,
     state: State,
     image_tasks: JoinSet<()>,
     picker: Picker,
     search_next_chapter_loader: ThrobberState,
     api_client: T,
-    pub manga_tracker: Option<S>,
+    pub manga_trackers: Vec<S>,
     pub auto_bookmark: bool,
+    /// Only keep the current page and the ones next to it decoded
+    pub low_memory_mode: bool,
+    pub warn_skipped_chapters: bool,
+    /// Unread chapters between the last one read and the current one, shown on its first page
+    skipped_chapters: usize,
+    /// Every query is sent to it so the ui thread never waits for the database
+    database: DatabaseActor,
     pub global_event_tx: Option<UnboundedSender<Events>>,
     pub local_action_tx: UnboundedSender<MangaReaderActions>,
     pub local_action_rx: UnboundedReceiver<MangaReaderActions>,
@@ -348,6 +553,62 @@ where
     pub local_event_rx: UnboundedReceiver<MangaReaderEvents>,
 }
 
+/// The manga and the chapter being read, owned so they can be sent to the database actor
+#[derive(Debug, Clone)]
+struct ChapterToSave {
+    manga_id: String,
+    manga_title: String,
+    chapter_id: String,
+    chapter_title: String,
+    translated_language: Languages,
+}
+
+impl ChapterToSave {
+    fn save_reading_history(&self, connection: &mut Connection) -> rusqlite::Result<String> {
+        save_history(
+            MangaReadingHistorySave {
+                id: &self.manga_id,
+                title: &self.manga_title,
+                img_url: None,
+                chapter: ChapterToSaveHistory {
+                    id: &self.chapter_id,
+                    title: &self.chapter_title,
+                    translated_language: "en",
+                },
+            },
+            connection,
+        )?;
+
+        Ok(self.chapter_id.clone())
+    }
+
+    fn save_to_library(&self, history_type: MangaHistoryType, connection: &mut Connection) -> rusqlite::Result<()> {
+        match history_type {
+            MangaHistoryType::ReadingHistory => self.save_reading_history(connection).map(|_| ()),
+            MangaHistoryType::PlanToRead => save_plan_to_read(
+                MangaPlanToReadSave {
+                    id: &self.manga_id,
+                    title: &self.manga_title,
+                    img_url: None,
+                },
+                connection,
+            ),
+        }
+    }
+
+    fn bookmark(&self, page_number: Option<u32>, database: &mut dyn Bookmark) -> Result<(), Box<dyn Error>> {
+        database.bookmark(ChapterToBookmark {
+            chapter_id: &self.chapter_id,
+            manga_id: &self.manga_id,
+            chapter_title: &self.chapter_title,
+            manga_title: &self.manga_title,
+            manga_cover_url: None,
+            translated_language: self.translated_language,
+            page_number,
+        })
+    }
+}
+
 impl<T, S> Component for MangaReader<T, S>
 where
     T: SearchChapter + SearchMangaPanel,
@@ -358,39 +619,47 @@ where
     fn render(&mut self, area: Rect, frame: &mut Frame<'_>) {
         let buf = frame.buffer_mut();
 
-        let layout = match self.current_page_size {
-            PageSize::Normal => [Constraint::Percentage(30), Constraint::Percentage(40), Constraint::Percentage(30)],
-            PageSize::Wide => [Constraint::Percentage(20), Constraint::Percentage(60), Constraint::Percentage(20)],
-        };
-
-        let [left, center, right] = Layout::horizontal(layout).areas(area);
+        // a tmux pane can change size without the terminal being resized
+        if self.needs_relayout || (!self.reader_area.is_empty() && self.reader_area != area) {
+            self.relayout(area);
+        }
 
-        Block::bordered().render(left, buf);
+        self.reader_area = area;
 
         let index = self.current_page_index();
-        let show_reload = if let Some(page) = self.pages.get_mut(index).filter(|page| page.image_state.is_some()) {
-            let image = StatefulImage::new(None).resize(Resize::Fit(None));
-            StatefulWidget::render(image, center, buf, page.image_state.as_mut().unwrap());
-            let (width, height) = page.dimensions.unwrap();
+
+        if self.reading_mode == ReadingMode::Webtoon {
+            let [left, center, right] = Layout::horizontal(PageSize::Normal.layout()).areas(area);
+
+            Block::bordered().render(left, buf);
+            let show_reload = self.render_webtoon(center, buf);
+
+            self.render_page_list(left, buf);
+            self.render_right_panel(buf, right, show_reload);
+            return;
+        }
+
+        if let Some((first, second)) = self.current_spread() {
+            let [left, _, right] = Layout::horizontal(PageSize::Wide.layout()).areas(area);
+
+            Block::bordered().render(left, buf);
+            let first_failed = self.render_page(first, self.page_area(first), buf);
+            let second_failed = self.render_page(second, self.page_area(second), buf);
+
+            self.render_page_list(left, buf);
+            self.render_right_panel(buf, right, first_failed || second_failed);
+            return;
+        }
+
+        if let Some((width, height)) = self.pages.get(index).and_then(|page| page.dimensions) {
             self.resize_based_on_image_size(width, height);
+        }
 
-            false
-        } else {
-            let show_failed = self
-                .pages_list
-                .pages
-                .get(index)
-                .map(|page| page.state == PageItemState::FailedLoad)
-                .unwrap_or(false);
-
-            if show_failed {
-                Block::bordered().title("Failed to load page").render(center, buf);
-            } else {
-                Block::bordered().title("Loading page").render(center, buf);
-            }
+        let [left, center, right] = Layout::horizontal(self.current_page_size.layout()).areas(area);
 
-            show_failed
-        };
+        Block::bordered().render(left, buf);
+
+        let show_reload = self.render_page(index, center, buf);
 
         self.render_page_list(left, buf);
         self.render_right_panel(buf, right, show_reload);
@@ -402,9 +671,20 @@ where
             MangaReaderActions::BookMarkCurrentChapter => self.bookmark_current_chapter(),
             MangaReaderActions::SearchPreviousChapter => self.initiate_search_previous_chapter(),
             MangaReaderActions::SearchNextChapter => self.initiate_search_next_chapter(),
-            MangaReaderActions::NextPage => self.next_page(),
-            MangaReaderActions::PreviousPage => self.previous_page(),
+            MangaReaderActions::NextPage => match self.reading_mode {
+                ReadingMode::Paged => self.next_page(),
+                ReadingMode::Webtoon => self.scroll_webtoon(true),
+                ReadingMode::DoublePage => self.next_spread(),
+            },
+            MangaReaderActions::PreviousPage => match self.reading_mode {
+                ReadingMode::Paged => self.previous_page(),
+                ReadingMode::Webtoon => self.scroll_webtoon(false),
+                ReadingMode::DoublePage => self.previous_spread(),
+            },
             MangaReaderActions::ReloadPage => self.reload_page(),
+            MangaReaderActions::SwitchReadingMode(mode) => self.switch_reading_mode(mode),
+            MangaReaderActions::ShiftSpreads => self.shift_spreads(),
+            MangaReaderActions::AddToLibrary(history_type) => self.add_to_library(history_type),
         }
     }
 
@@ -420,6 +700,7 @@ where
                 },
                 _ => {},
             },
+            Events::Resize(..) => self.needs_relayout = true,
             Events::Tick => self.tick(),
             _ => {},
         }
@@ -430,6 +711,8 @@ where
         self.pages = vec![];
         self.pages_list.pages = vec![];
         self.page_list_state = PagesListState::default();
+        self.webtoon_offset = 0.0;
+        self.webtoon_strip = None;
     }
 }
 
@@ -445,27 +728,67 @@ where
 
         let num_page_bookmarked = chapter.num_page_bookmarked;
 
+        let mut page_list_state = PagesListState::new(num_page_bookmarked.map(|num| num as usize));
+        page_list_state.list_state.select(chapter.page_to_resume.map(|num| num as usize));
+
         Self {
             global_event_tx: None,
             auto_bookmark: false,
+            low_memory_mode: false,
+            warn_skipped_chapters: false,
+            skipped_chapters: 0,
             current_chapter: chapter,
             manga_title: String::default(),
             pages: vec![],
             manga_id,
             list_of_chapters: ListOfChapters::default(),
-            page_list_state: PagesListState::new(num_page_bookmarked.map(|num| num as usize)),
+            page_list_state,
+            reading_mode: ReadingMode::default(),
+            webtoon_offset: 0.0,
+            webtoon_strip: None,
+            spread_shifted: false,
             image_tasks: set,
             local_action_tx,
             local_action_rx,
             local_event_tx,
             local_event_rx,
             state: State::default(),
-            manga_tracker: None,
+            manga_trackers: vec![],
             current_page_size: PageSize::default(),
+            reader_area: Rect::default(),
+            needs_relayout: false,
+            reading_time: Duration::ZERO,
+            last_page_turn: Instant::now(),
             pages_list: PagesList::default(),
             search_next_chapter_loader: ThrobberState::default(),
             picker,
             api_client,
+            database: DatabaseActor::global().clone(),
+        }
+    }
+
+    #[cfg(test)]
+    fn with_database(mut self, database: DatabaseActor) -> Self {
+        self.database = database;
+        self
+    }
+
+    /// Runs `query` in the database actor and sends the event made from its result, which is handled in a later tick
+    fn query_database<R>(
+        &self,
+        query: impl FnOnce(&mut Connection) -> Result<R, Box<dyn Error>> + Send + 'static,
+        into_event: impl FnOnce(R) -> MangaReaderEvents + Send + 'static,
+    ) {
+        self.database.query(self.local_event_tx.clone(), query, into_event);
+    }
+
+    fn chapter_to_save(&self) -> ChapterToSave {
+        ChapterToSave {
+            manga_id: self.manga_id.clone(),
+            manga_title: self.manga_title.clone(),
+            chapter_id: self.current_chapter.id.clone(),
+            chapter_title: self.current_chapter.title.clone(),
+            translated_language: self.current_chapter.language,
         }
     }
 
@@ -478,6 +801,24 @@ where
         self.auto_bookmark = true;
     }
 
+    pub fn set_low_memory_mode(&mut self) {
+        self.low_memory_mode = true;
+    }
+
+    pub fn set_warn_skipped_chapters(&mut self) {
+        self.warn_skipped_chapters = true;
+    }
+
+    pub fn set_webtoon_mode(&mut self) {
+        self.reading_mode = ReadingMode::Webtoon;
+    }
+
+    /// The reading mode chosen for this manga the last time it was read
+    pub fn load_reading_mode(&mut self) {
+        let manga_id = self.manga_id.clone();
+        self.query_database(move |conn| Ok(Database::new(conn).get_reading_mode(&manga_id)?), MangaReaderEvents::LoadReadingMode);
+    }
+
     pub fn with_list_of_chapters(mut self, list: ListOfChapters) -> Self {
         self.list_of_chapters = list;
         self
@@ -488,23 +829,284 @@ where
         self
     }
 
-    pub fn with_manga_tracker(mut self, manga_tracker: Option<S>) -> Self {
-        self.manga_tracker = manga_tracker;
+    pub fn with_manga_trackers(mut self, manga_trackers: Vec<S>) -> Self {
+        self.manga_trackers = manga_trackers;
         self
     }
 
     fn next_page(&mut self) {
+        self.count_time_on_page();
         self.page_list_state.list_state.next();
-        self.fetch_pages();
+        self.page_turned();
     }
 
     fn previous_page(&mut self) {
+        self.count_time_on_page();
         self.page_list_state.list_state.previous();
+        self.page_turned();
+    }
+
+    fn go_to_page(&mut self, index: usize) {
+        self.count_time_on_page();
+        self.page_list_state.list_state.select(Some(index));
+        self.page_turned();
+    }
+
+    fn page_turned(&mut self) {
+        self.drop_pages_out_of_range();
         self.fetch_pages();
+        self.save_reading_session();
+        self.save_chapter_progress();
+    }
+
+    fn switch_reading_mode(&mut self, mode: ReadingMode) {
+        self.set_reading_mode(self.reading_mode.switch_to(mode));
+
+        let manga_id = self.manga_id.clone();
+        let reading_mode = self.reading_mode;
+        self.database
+            .execute(move |conn| Database::new(conn).set_reading_mode(&manga_id, reading_mode));
+    }
+
+    fn set_reading_mode(&mut self, mode: ReadingMode) {
+        let previous_mode = self.reading_mode;
+
+        self.reading_mode = mode;
+        self.webtoon_offset = 0.0;
+        self.webtoon_strip = None;
+        self.spread_shifted = false;
+
+        // pages are kept decoded differently in webtoon mode, so the ones already loaded are fetched again
+        if (previous_mode == ReadingMode::Webtoon) == (self.reading_mode == ReadingMode::Webtoon) {
+            self.needs_relayout = true;
+            return;
+        }
+
+        for (page, item) in self.pages.iter_mut().zip(self.pages_list.pages.iter_mut()) {
+            *page = Page::new();
+            if item.state != PageItemState::Loading {
+                item.state = PageItemState::Waiting;
+            }
+        }
+
+        self.fetch_pages();
+    }
+
+    fn shows_spreads(&self) -> bool {
+        self.reading_mode == ReadingMode::DoublePage && self.reader_area.width >= MIN_SPREAD_WIDTH
+    }
+
+    /// The two pages shown side by side, `None` if only one page is shown
+    fn current_spread(&self) -> Option<(usize, usize)> {
+        if !self.shows_spreads() {
+            return None;
+        }
+
+        let (first, second) = spread_of(self.current_page_index(), self.spread_shifted, self.pages.len());
+
+        second.map(|second| (first, second))
+    }
+
+    fn pages_shown(&self) -> Vec<usize> {
+        match self.current_spread() {
+            Some((first, second)) => vec![first, second],
+            None => vec![self.current_page_index()],
+        }
+    }
+
+    /// Where the page is shown, pages are encoded for the size of this area
+    fn page_area(&self, index: usize) -> Rect {
+        if self.shows_spreads() {
+            if let (first, Some(_)) = spread_of(index, self.spread_shifted, self.pages.len()) {
+                let [_, center, _] = Layout::horizontal(PageSize::Wide.layout()).areas(self.reader_area);
+                let [left, right] = Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(center);
+
+                // manga is read from right to left, so the first page of the spread goes on the right
+                return if index == first { right } else { left };
+            }
+        }
+
+        let page_size = self
+            .pages
+            .get(index)
+            .and_then(|page| page.dimensions)
+            .map_or(PageSize::Normal, |(width, height)| PageSize::of(width, height));
+
+        let [_, area, _] = Layout::horizontal(page_size.layout()).areas(self.reader_area);
+        area
+    }
+
+    /// Both pages of the spread are turned, if only one page is shown it's turned like in page by page mode
+    fn next_spread(&mut self) {
+        if !self.shows_spreads() {
+            self.next_page();
+        } else {
+            let last_shown = self.current_spread().map_or(self.current_page_index(), |(_, second)| second);
+
+            if last_shown + 1 < self.pages.len() {
+                self.go_to_page(last_shown + 1);
+            }
+        }
+    }
+
+    fn previous_spread(&mut self) {
+        if !self.shows_spreads() {
+            self.previous_page();
+        } else {
+            let first_shown = self.current_spread().map_or(self.current_page_index(), |(first, _)| first);
+
+            if let Some(previous) = first_shown.checked_sub(1) {
+                self.go_to_page(spread_of(previous, self.spread_shifted, self.pages.len()).0);
+            }
+        }
+    }
+
+    /// Spreads don't line up when a page like the cover is meant to be shown alone, so they can be paired from the
+    /// second page instead
+    fn shift_spreads(&mut self) {
+        self.spread_shifted = !self.spread_shifted;
+        self.needs_relayout = true;
+    }
+
+    fn webtoon_area(&self) -> Rect {
+        let [_, center, _] = Layout::horizontal(PageSize::Normal.layout()).areas(self.reader_area);
+        center
+    }
+
+    /// The size in pixels of `area`
+    fn webtoon_size(&self, area: Rect) -> (u32, u32) {
+        let (font_width, font_height) = self.picker.font_size;
+        (u32::from(area.width) * u32::from(font_width), u32::from(area.height) * u32::from(font_height))
+    }
+
+    /// The height of each page once scaled to the width of the strip, `None` if it's not loaded
+    fn scaled_heights(&self, width: u32) -> Vec<Option<u32>> {
+        self.pages
+            .iter()
+            .map(|page| {
+                page.dimensions.filter(|_| page.image.is_some()).map(|(page_width, page_height)| {
+                    (u64::from(page_height) * u64::from(width) / u64::from(page_width.max(1))) as u32
+                })
+            })
+            .collect()
+    }
+
+    /// Scrolls a quarter of the strip, the page at its top is the current page
+    fn scroll_webtoon(&mut self, down: bool) {
+        let (width, height) = self.webtoon_size(self.webtoon_area());
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let step = f64::from(height) / 4.0;
+        let (index, offset) = scroll_position(
+            &self.scaled_heights(width),
+            self.current_page_index(),
+            self.webtoon_offset,
+            if down { step } else { -step },
+            height,
+        );
+
+        self.webtoon_offset = offset;
+
+        if index != self.current_page_index() {
+            self.go_to_page(index);
+        }
+    }
+
+    /// Only the slices of the pages shown are resized, resizing whole pages every time the strip moves is too slow
+    fn stitch_webtoon_strip(&self, width: u32, height: u32) -> DynamicImage {
+        let mut strip = RgbaImage::from_pixel(width, height, Rgba([0, 0, 0, 255]));
+        let mut y = 0;
+
+        for slice in webtoon_slices(&self.scaled_heights(width), self.current_page_index(), self.webtoon_offset, height) {
+            let Some(image) = self.pages.get(slice.index).and_then(|page| page.image.as_ref()) else {
+                break;
+            };
+
+            let scale = f64::from(image.width()) / f64::from(width);
+            let top = ((f64::from(slice.top) * scale) as u32).min(image.height().saturating_sub(1));
+            let slice_height = ((f64::from(slice.height) * scale).ceil() as u32).clamp(1, image.height() - top);
+
+            let part = image
+                .crop_imm(0, top, image.width(), slice_height)
+                .resize_exact(width, slice.height, FilterType::Triangle);
+
+            imageops::overlay(&mut strip, &part.to_rgba8(), 0, i64::from(y));
+            y += slice.height;
+        }
+
+        DynamicImage::ImageRgba8(strip)
+    }
+
+    /// Returns whether the current page failed to load and can be reloaded
+    fn render_webtoon(&mut self, area: Rect, buf: &mut Buffer) -> bool {
+        let index = self.current_page_index();
+        let (width, height) = self.webtoon_size(area);
+
+        let is_loaded = self.pages.get(index).is_some_and(|page| page.image.is_some());
+        if !is_loaded || width == 0 || height == 0 {
+            return self.render_page_placeholder(index, area, buf);
+        }
+
+        let is_outdated = !matches!(
+            &self.webtoon_strip,
+            Some(strip) if strip.area == area && strip.index == index && strip.offset == self.webtoon_offset
+        );
+
+        if is_outdated {
+            let strip = self.stitch_webtoon_strip(width, height);
+            self.webtoon_strip = Some(WebtoonStrip {
+                area,
+                index,
+                offset: self.webtoon_offset,
+                protocol: self.picker.new_resize_protocol(strip),
+            });
+        }
+
+        if let Some(strip) = self.webtoon_strip.as_mut() {
+            StatefulWidget::render(StatefulImage::new(None).resize(Resize::Fit(None)), area, buf, &mut strip.protocol);
+        }
+
+        false
+    }
+
+    /// Returns whether the page failed to load and can be reloaded
+    fn render_page(&mut self, index: usize, area: Rect, buf: &mut Buffer) -> bool {
+        let is_encoded = !self.encodes_in_background() || self.encode_page_in_background(index);
+
+        if let Some(page) = self.pages.get_mut(index).filter(|page| page.image_state.is_some() && is_encoded) {
+            let image = StatefulImage::new(None).resize(Resize::Fit(None));
+            StatefulWidget::render(image, area, buf, page.image_state.as_mut().unwrap());
+
+            false
+        } else {
+            self.render_page_placeholder(index, area, buf)
+        }
+    }
+
+    /// Returns whether the page failed to load and can be reloaded
+    fn render_page_placeholder(&self, index: usize, area: Rect, buf: &mut Buffer) -> bool {
+        let show_failed = self
+            .pages_list
+            .pages
+            .get(index)
+            .map(|page| page.state == PageItemState::FailedLoad)
+            .unwrap_or(false);
+
+        if show_failed {
+            Block::bordered().title("Failed to load page").render(area, buf);
+        } else {
+            Block::bordered().title("Loading page").render(area, buf);
+        }
+
+        show_failed
     }
 
     fn reload_page(&mut self) {
-        self.fetch_page(self.current_page_index());
+        for index in self.pages_shown() {
+            self.fetch_page(index);
+        }
     }
 
     fn render_page_list(&mut self, area: Rect, buf: &mut Buffer) {
@@ -512,15 +1114,28 @@ where
             horizontal: 1,
             vertical: 1,
         });
-        StatefulWidget::render(self.pages_list.clone(), inner_area, buf, &mut self.page_list_state);
+        StatefulWidget::render(&mut self.pages_list, inner_area, buf, &mut self.page_list_state);
     }
 
     fn load_page(&mut self, data: PageData) {
+        // the reader moved on while this page was being fetched
+        if self.low_memory_mode && !self.is_page_in_range(data.index) {
+            if let Some(page_item) = self.pages_list.pages.get_mut(data.index) {
+                page_item.state = PageItemState::Waiting;
+            }
+            return;
+        }
+
         match self.pages.get_mut(data.index) {
             Some(page) => {
-                let protocol = self.picker.new_resize_protocol(data.panel.image_decoded);
-                page.image_state = Some(protocol);
+                match self.reading_mode {
+                    ReadingMode::Paged | ReadingMode::DoublePage => {
+                        page.image_state = Some(self.picker.new_resize_protocol(data.panel.image_decoded))
+                    },
+                    ReadingMode::Webtoon => page.image = Some(data.panel.image_decoded),
+                }
                 page.dimensions = Some(data.panel.dimensions);
+                self.webtoon_strip = None;
             },
             None => {
                 // Todo! indicate that the page couldnot be loaded
@@ -532,25 +1147,153 @@ where
                 // Todo! indicate with an x that some page didnt load
             },
         }
+
+        if self.encodes_in_background() {
+            self.encode_page_in_background(data.index);
+        }
     }
 
-    fn resize_based_on_image_size(&mut self, width: u32, height: u32) {
-        if width > height && width > 300 {
-            self.current_page_size = PageSize::Wide;
-        } else {
-            self.current_page_size = PageSize::Normal;
+    /// Encoding sixel is slow enough to notice when flipping pages, so with it pages are encoded in the background as
+    /// soon as they load. Low memory mode skips this since a copy of the page is encoded. There is no reader without
+    /// image support, so halfblocks is never used here
+    fn encodes_in_background(&self) -> bool {
+        !self.low_memory_mode && self.picker.protocol_type == ProtocolType::Sixel
+    }
+
+    /// Returns whether the page is already encoded for the area it is shown in and can be rendered
+    fn encode_page_in_background(&mut self, index: usize) -> bool {
+        if self.reader_area.is_empty() {
+            return false;
         }
+
+        let area = self.page_area(index);
+
+        let Some(page) = self.pages.get_mut(index) else {
+            return false;
+        };
+
+        let Some(protocol) = page.image_state.as_mut() else {
+            return false;
+        };
+
+        let Some(rect) = protocol.needs_resize(&Resize::Fit(None), area) else {
+            return true;
+        };
+
+        if page.encoding_area == Some(area) {
+            return false;
+        }
+
+        page.encoding_area = Some(area);
+
+        let mut protocol = protocol.clone();
+        let tx = self.local_event_tx.clone();
+
+        self.image_tasks.spawn(async move {
+            let encoded = tokio::task::spawn_blocking(move || {
+                protocol.resize_encode(&Resize::Fit(None), None, rect);
+                protocol
+            })
+            .await;
+
+            if let Ok(protocol) = encoded {
+                tx.send(MangaReaderEvents::PageEncoded(EncodedPage {
+                    index,
+                    area,
+                    protocol,
+                }))
+                .ok();
+            }
+        });
+
+        false
+    }
+
+    /// The page is only updated if it was not dropped or encoded for another area in the meantime
+    fn load_encoded_page(&mut self, encoded: EncodedPage) {
+        if let Some(page) = self.pages.get_mut(encoded.index).filter(|page| page.encoding_area == Some(encoded.area)) {
+            page.image_state = Some(encoded.protocol);
+            page.encoding_area = None;
+        }
+    }
+
+    /// The current page is encoded right away for the new area so it is not shown clipped until the next page turn, the
+    /// pages next to it are encoded again in the background and whatever was being encoded for the old area is dropped
+    fn relayout(&mut self, area: Rect) {
+        self.needs_relayout = false;
+        self.reader_area = area;
+
+        for page in self.pages.iter_mut() {
+            page.encoding_area = None;
+        }
+
+        let pages_shown = self.pages_shown();
+
+        for &index in &pages_shown {
+            let page_area = self.page_area(index);
+
+            if let Some(protocol) = self.pages.get_mut(index).and_then(|page| page.image_state.as_mut()) {
+                if let Some(rect) = protocol.needs_resize(&Resize::Fit(None), page_area) {
+                    protocol.resize_encode(&Resize::Fit(None), None, rect);
+                }
+            }
+        }
+
+        if self.encodes_in_background() {
+            let (first, last) = (pages_shown[0], pages_shown[pages_shown.len() - 1]);
+
+            self.encode_page_in_background(last + 1);
+            if let Some(previous) = first.checked_sub(1) {
+                self.encode_page_in_background(previous);
+            }
+        }
+    }
+
+    fn resize_based_on_image_size(&mut self, width: u32, height: u32) {
+        self.current_page_size = PageSize::of(width, height);
     }
 
     fn load_chapter(&mut self, chapter: ChapterToRead) {
+        self.save_reading_time();
         self.clean_up();
 
+        self.page_list_state.list_state.select(chapter.page_to_resume.map(|num| num as usize));
         self.current_chapter = chapter;
         self.state = State::SearchingPages;
 
         self.init_fetching_pages();
         self.init_save_reading_history();
-        self.track_manga_reading_history(self.manga_tracker.clone());
+        self.save_reading_session();
+        self.track_manga_reading_history(self.manga_trackers.clone());
+        self.check_skipped_chapters();
+    }
+
+    pub fn check_skipped_chapters(&mut self) {
+        self.skipped_chapters = 0;
+
+        if self.warn_skipped_chapters {
+            self.local_event_tx.send(MangaReaderEvents::CheckSkippedChapters).ok();
+        }
+    }
+
+    fn search_chapters_read(&mut self) {
+        let manga_id = self.manga_id.clone();
+        let tx = self.local_event_tx.clone();
+        let history = self.database.run(move |conn| get_chapters_history_status(&manga_id, conn));
+
+        self.image_tasks.spawn(async move {
+            match history.await {
+                Ok(history) => {
+                    let chapters_read = history.into_iter().filter(|chapter| chapter.is_read).map(|chapter| chapter.id).collect();
+                    tx.send(MangaReaderEvents::LoadChaptersRead(chapters_read)).ok();
+                },
+                Err(e) => write_to_error_log(ErrorType::Error(e)),
+            }
+        });
+    }
+
+    fn load_chapters_read(&mut self, chapters_read: Vec<String>) {
+        self.skipped_chapters = self.list_of_chapters.count_skipped_chapters(self.current_chapter.number, &chapters_read);
     }
 
     fn init_save_reading_history(&self) {
@@ -570,8 +1313,37 @@ where
         }
     }
 
+    /// How many pages before and after the current one are fetched
+    fn pages_around_current(&self) -> usize {
+        if self.low_memory_mode { 1 } else { MangaTuiConfig::get().amount_pages as usize }
+    }
+
+    fn is_page_in_range(&self, index: usize) -> bool {
+        index.abs_diff(self.current_page_index()) <= self.pages_around_current()
+    }
+
+    /// In low memory mode the decoded pages far from the current one are dropped, they are fetched again if the
+    /// user goes back to them
+    fn drop_pages_out_of_range(&mut self) {
+        if !self.low_memory_mode {
+            return;
+        }
+
+        let pages_out_of_range: Vec<usize> = (0..self.pages.len()).filter(|index| !self.is_page_in_range(*index)).collect();
+
+        for index in pages_out_of_range {
+            if let Some(page) = self.pages.get_mut(index).filter(|page| page.is_loaded()) {
+                *page = Page::new();
+
+                if let Some(page_item) = self.pages_list.pages.get_mut(index) {
+                    page_item.state = PageItemState::Waiting;
+                }
+            }
+        }
+    }
+
     fn get_pages_to_fetch(&self) -> Vec<usize> {
-        let pages = MangaTuiConfig::get().amount_pages as usize;
+        let pages = self.pages_around_current();
 
         if self.pages.len() == 1 {
             return vec![0];
@@ -586,10 +1358,7 @@ where
             self.pages[start_index..=end_index]
                 .iter()
                 .enumerate()
-                .filter_map(|(base_index, page)| match page.image_state {
-                    Some(_) => None,
-                    None => Some(base_index + start_index),
-                })
+                .filter_map(|(base_index, page)| (!page.is_loaded()).then_some(base_index + start_index))
                 .collect()
         } else {
             vec![]
@@ -629,46 +1398,95 @@ where
         self.state = State::ErrorSearchingChapter;
     }
 
-    fn set_current_chapter_bookmarked(&mut self, num_page: Option<u32>, database: &mut dyn Bookmark) {
-        let chapter_to_bookmark = ChapterToBookmark {
-            chapter_id: &self.current_chapter.id,
-            manga_id: &self.manga_id,
-            chapter_title: &self.current_chapter.title,
-            manga_title: &self.manga_title,
-            manga_cover_url: None,
-            translated_language: self.current_chapter.language,
-            page_number: num_page,
-        };
+    fn set_current_chapter_bookmarked(&mut self, page_index: usize) {
+        self.state = State::ManualBookmark;
+        self.pages_list.highlight_page_as_bookmarked(page_index);
+        self.page_list_state.set_page_bookmarked(page_index);
+    }
 
-        match database.bookmark(chapter_to_bookmark) {
-            Ok(()) => {
-                let page_index = num_page.unwrap_or(0) as usize;
-                self.state = State::ManualBookmark;
-                self.pages_list.highlight_page_as_bookmarked(page_index);
-                self.page_list_state.set_page_bookmarked(page_index);
-            },
-            Err(e) => {
-                write_to_error_log(ErrorType::String(format!("Could not mark chapter as bookmarked: more details : {e}").as_str()))
+    pub fn bookmark_current_chapter(&mut self) {
+        let chapter = self.chapter_to_save();
+        let num_page = self.page_list_state.list_state.selected.map(|index| index as u32);
+
+        self.query_database(
+            move |conn| {
+                chapter
+                    .bookmark(num_page, &mut Database::new(conn))
+                    .map_err(|e| format!("Could not mark chapter as bookmarked: more details : {e}").into())
             },
+            move |()| MangaReaderEvents::ChapterBookmarked(num_page.unwrap_or(0) as usize),
+        );
+    }
+
+    /// Store which chapter and page is being read so it can be resumed if the app crashes
+    pub fn save_reading_session(&self) {
+        let session = ChapterBookmarked {
+            id: self.current_chapter.id.clone(),
+            translated_language: Some(self.current_chapter.language.as_iso_code().to_string()),
+            number_page_bookmarked: self.page_list_state.list_state.selected.map(|index| index as u32),
+            manga_title: self.manga_title.clone(),
+            manga_id: self.manga_id.clone(),
+        };
+
+        self.database.execute(move |conn| Database::new(conn).save_reading_session(&session));
+    }
+
+    fn chapter_progress(&self) -> Option<ChapterProgress> {
+        let total_pages = self.current_chapter.pages_url.len() as u32;
+
+        self.page_list_state
+            .list_state
+            .selected
+            .filter(|_| total_pages > 0)
+            .map(|index| ChapterProgress {
+                last_page: index as u32,
+                total_pages,
+            })
+    }
+
+    /// Store the page reached in the current chapter so the chapter list can show it and reading can be resumed there
+    pub fn save_chapter_progress(&self) {
+        if let Some(progress) = self.chapter_progress() {
+            let chapter_id = self.current_chapter.id.clone();
+            self.database
+                .execute(move |conn| Database::new(conn).save_chapter_progress(&chapter_id, progress));
         }
     }
 
-    pub fn bookmark_current_chapter(&mut self) {
-        let connection = Database::get_connection();
-        if let Ok(conn) = connection {
-            let mut database = Database::new(&conn);
-            self.set_current_chapter_bookmarked(self.page_list_state.list_state.selected.map(|index| index as u32), &mut database);
+    fn count_time_on_page(&mut self) {
+        self.reading_time += self.last_page_turn.elapsed().min(MAX_TIME_ON_A_PAGE);
+        self.last_page_turn = Instant::now();
+    }
+
+    /// Adds the time spent on the current chapter to the one saved for it
+    pub fn save_reading_time(&mut self) {
+        self.count_time_on_page();
+
+        let seconds = std::mem::take(&mut self.reading_time).as_secs();
+        if seconds == 0 {
+            return;
         }
+
+        let chapter_id = self.current_chapter.id.clone();
+        self.database
+            .execute(move |conn| Database::new(conn).add_reading_time(&chapter_id, seconds));
+    }
+
+    /// Leaving the reader normally means there is nothing to resume on the next launch, it goes through the same
+    /// queue as `save_reading_session` so a session saved just before is not written back after being cleared
+    pub fn clear_reading_session(&self) {
+        self.database.execute(|conn| Database::new(conn).clear_reading_session());
     }
 
-    fn track_manga_reading_history(&self, manga_tracker: Option<S>) {
+    fn track_manga_reading_history(&self, manga_trackers: Vec<S>) {
         let chapter_to_track = self.current_chapter.clone();
         let tx = self.local_event_tx.clone();
 
         track_manga(
-            manga_tracker,
+            manga_trackers,
+            self.manga_id.clone(),
             self.manga_title.clone(),
-            chapter_to_track.number as u32,
+            ChapterNumber::from(chapter_to_track.number).as_tracker_progress(),
             chapter_to_track.volume_number.clone().unwrap_or("0".to_string()).parse().ok(),
             move |error| {
                 tx.send(MangaReaderEvents::ErrorTrackingReadingProgress(error)).ok();
@@ -677,17 +1495,23 @@ where
     }
 
     pub fn exit(&mut self) {
+        self.save_reading_time();
         if self.auto_bookmark {
             self.bookmark_current_chapter()
         }
+        self.clear_reading_session();
         self.global_event_tx.as_ref().unwrap().send(Events::GoBackMangaPage).ok();
     }
 
     fn render_right_panel(&mut self, buf: &mut Buffer, area: Rect, show_reload: bool) {
-        let [instructions_area, information_era, status_area] =
-            Layout::vertical([Constraint::Percentage(20), Constraint::Percentage(20), Constraint::Percentage(20)])
-                .margin(2)
-                .areas(area);
+        let [instructions_area, information_era, status_area, skipped_chapters_area] = Layout::vertical([
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+        ])
+        .margin(2)
+        .areas(area);
 
         let mut instructions = vec![
             Line::from(vec!["Go back: ".into(), "<Backspace>".to_span().style(*INSTRUCTIONS_STYLE)]),
@@ -703,6 +1527,24 @@ where
             instructions.push(Line::from(vec!["Bookmark: ".into(), "<m>".to_span().style(*INSTRUCTIONS_STYLE)]));
         }
 
+        instructions.push(Line::from(vec![
+            "Webtoon: ".into(),
+            "<v>".to_span().style(*INSTRUCTIONS_STYLE),
+            " Two pages: ".into(),
+            "<d>".to_span().style(*INSTRUCTIONS_STYLE),
+        ]));
+
+        if self.reading_mode == ReadingMode::DoublePage {
+            instructions.push(Line::from(vec!["Shift pages: ".into(), "<o>".to_span().style(*INSTRUCTIONS_STYLE)]));
+        }
+
+        instructions.push(Line::from(vec![
+            "Reading: ".into(),
+            "<a>".to_span().style(*INSTRUCTIONS_STYLE),
+            " Plan to read: ".into(),
+            "<p>".to_span().style(*INSTRUCTIONS_STYLE),
+        ]));
+
         Widget::render(List::new(instructions).block(Block::bordered()), instructions_area, buf);
 
         let current_chapter_title = format!(
@@ -740,24 +1582,36 @@ where
                     .wrap(Wrap { trim: true })
                     .render(status_area, buf)
             },
+            State::AddedToLibrary(history_type) => {
+                let message = match history_type {
+                    MangaHistoryType::ReadingHistory => "Added to reading history",
+                    MangaHistoryType::PlanToRead => "Added to plan to read",
+                };
+
+                Paragraph::new(message.to_span().style(*INSTRUCTIONS_STYLE))
+                    .wrap(Wrap { trim: true })
+                    .render(status_area, buf)
+            },
             _ => {},
         };
+
+        if self.skipped_chapters > 0 && self.current_page_index() == 0 {
+            let plural = if self.skipped_chapters == 1 { "" } else { "s" };
+            Paragraph::new(format!("You skipped {} chapter{plural} since the last one you read", self.skipped_chapters).yellow())
+                .wrap(Wrap { trim: true })
+                .render(skipped_chapters_area, buf);
+        }
     }
 
     fn tick(&mut self) {
         self.pages_list.on_tick();
         if self.state == State::SearchingChapter {
-            self.search_next_chapter_loader.calc_next();
+            spin_loader(&mut self.search_next_chapter_loader);
         }
 
         while let Ok(background_event) = self.local_event_rx.try_recv() {
             match background_event {
-                MangaReaderEvents::SaveReadingToDatabase => {
-                    let connection = Database::get_connection();
-                    if let Ok(mut conn) = connection {
-                        self.save_reading_history(&mut conn).ok();
-                    }
-                },
+                MangaReaderEvents::SaveReadingToDatabase => self.save_reading_history(),
                 MangaReaderEvents::SearchPreviousChapter(id_chapter) => self.search_chapter(id_chapter),
                 MangaReaderEvents::ErrorSearchingChapter => self.set_error_searching_chapter(),
                 MangaReaderEvents::ChapterNotFound => self.set_chapter_not_found(),
@@ -766,11 +1620,25 @@ where
                 MangaReaderEvents::FetchPages => self.fetch_pages(),
                 MangaReaderEvents::LoadPage(maybe_data) => self.load_page(maybe_data),
                 MangaReaderEvents::FailedPage(index) => self.failed_page(index),
+                MangaReaderEvents::PageEncoded(encoded) => self.load_encoded_page(encoded),
                 MangaReaderEvents::ErrorTrackingReadingProgress(error_message) => self.log_manga_tracking_error(error_message),
+                MangaReaderEvents::CheckSkippedChapters => self.search_chapters_read(),
+                MangaReaderEvents::LoadChaptersRead(chapters_read) => self.load_chapters_read(chapters_read),
+                MangaReaderEvents::LoadReadingMode(mode) => self.set_reading_mode(mode),
+                MangaReaderEvents::ReadingHistorySaved(chapter_id) => self.reading_history_saved(chapter_id),
+                MangaReaderEvents::ChapterBookmarked(page_index) => self.set_current_chapter_bookmarked(page_index),
+                MangaReaderEvents::AddedToLibrary(history_type) => self.added_to_library(history_type),
             }
         }
     }
 
+    /// Let other pages like the manga page know this chapter is now read
+    fn notify_chapter_status_changed(&self) {
+        if let Some(tx) = self.global_event_tx.as_ref() {
+            tx.send(Events::ChapterStatusChanged(self.manga_id.clone())).ok();
+        }
+    }
+
     fn log_manga_tracking_error(&self, error_message: String) {
         write_to_error_log(
             format_error_message_tracking_reading_history(self.current_chapter.clone(), self.manga_title.clone(), error_message)
@@ -795,6 +1663,29 @@ where
             KeyCode::Char('r') => {
                 self.local_action_tx.send(MangaReaderActions::ReloadPage).ok();
             },
+            KeyCode::Char('v') => {
+                self.local_action_tx
+                    .send(MangaReaderActions::SwitchReadingMode(ReadingMode::Webtoon))
+                    .ok();
+            },
+            KeyCode::Char('d') => {
+                self.local_action_tx
+                    .send(MangaReaderActions::SwitchReadingMode(ReadingMode::DoublePage))
+                    .ok();
+            },
+            KeyCode::Char('o') if self.reading_mode == ReadingMode::DoublePage => {
+                self.local_action_tx.send(MangaReaderActions::ShiftSpreads).ok();
+            },
+            KeyCode::Char('a') => {
+                self.local_action_tx
+                    .send(MangaReaderActions::AddToLibrary(MangaHistoryType::ReadingHistory))
+                    .ok();
+            },
+            KeyCode::Char('p') => {
+                self.local_action_tx
+                    .send(MangaReaderActions::AddToLibrary(MangaHistoryType::PlanToRead))
+                    .ok();
+            },
             KeyCode::Char('m') => {
                 if !self.auto_bookmark {
                     self.local_action_tx.send(MangaReaderActions::BookMarkCurrentChapter).ok();
@@ -870,22 +1761,29 @@ where
         });
     }
 
-    fn save_reading_history(&self, connection: &mut Connection) -> rusqlite::Result<String> {
-        save_history(
-            MangaReadingHistorySave {
-                id: &self.manga_id,
-                title: &self.manga_title,
-                img_url: None,
-                chapter: ChapterToSaveHistory {
-                    id: &self.current_chapter.id,
-                    title: &self.current_chapter.title,
-                    translated_language: "en",
-                },
-            },
-            connection,
-        )?;
+    /// Chapters opened without going through the manga page, like the ones of the latest updates, can be added to the
+    /// library from the reader
+    fn add_to_library(&mut self, history_type: MangaHistoryType) {
+        let chapter = self.chapter_to_save();
+        self.query_database(
+            move |conn| Ok(chapter.save_to_library(history_type, conn)?),
+            move |()| MangaReaderEvents::AddedToLibrary(history_type),
+        );
+    }
+
+    fn added_to_library(&mut self, history_type: MangaHistoryType) {
+        self.notify_chapter_status_changed();
+        self.state = State::AddedToLibrary(history_type);
+    }
+
+    fn save_reading_history(&self) {
+        let chapter = self.chapter_to_save();
+        self.query_database(move |conn| Ok(chapter.save_reading_history(conn)?), MangaReaderEvents::ReadingHistorySaved);
+    }
 
-        Ok(self.current_chapter.id.clone())
+    fn reading_history_saved(&self, chapter_id: String) {
+        self.notify_chapter_status_changed();
+        sync_chapters_read_mangadex(self.manga_id.clone(), vec![chapter_id]);
     }
 }
 
@@ -894,6 +1792,8 @@ mod test {
     use std::time::Duration;
 
     use pretty_assertions::assert_eq;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
     use tokio::time::timeout;
 
     use self::mpsc::unbounded_channel;
@@ -971,6 +1871,7 @@ mod test {
                 pages_url: url_imgs,
                 language: Languages::default(),
                 num_page_bookmarked: None,
+                page_to_resume: None,
                 volume_number: Some("2".to_string()),
             },
             "some_manga_id".to_string(),
@@ -1201,6 +2102,79 @@ mod test {
         assert!(from_first_chapter.is_none());
     }
 
+    #[test]
+    fn it_counts_the_chapters_skipped_since_the_last_one_read() {
+        let chapter = |id: &str, number: &str, volume: &str| Chapter {
+            id: id.to_string(),
+            number: number.to_string(),
+            volume: volume.to_string(),
+        };
+
+        let list = ListOfChapters {
+            volumes: SortedVolumes::new(vec![
+                Volumes {
+                    volume: "1".to_string(),
+                    chapters: SortedChapters::new(vec![chapter("1", "1", "1"), chapter("2", "2", "1"), chapter("3", "3", "1")]),
+                },
+                Volumes {
+                    volume: "none".to_string(),
+                    chapters: SortedChapters::new(vec![
+                        chapter("4", "4", "none"),
+                        chapter("5", "5", "none"),
+                        chapter("6", "6", "none"),
+                    ]),
+                },
+            ]),
+        };
+
+        let chapters_read = vec!["1".to_string(), "2".to_string()];
+
+        assert_eq!(3, list.count_skipped_chapters(6.0, &chapters_read));
+        assert_eq!(0, list.count_skipped_chapters(3.0, &chapters_read));
+        assert_eq!(0, list.count_skipped_chapters(6.0, &[]));
+        assert_eq!(0, list.count_skipped_chapters(2.0, &chapters_read));
+    }
+
+    #[test]
+    fn it_warns_about_the_chapters_skipped_on_the_first_page() {
+        let mut manga_reader: MangaReader<TestApiClient, TrackerTest> = initialize_reader_page(TestApiClient::new());
+
+        manga_reader.current_chapter.number = 3.0;
+        manga_reader.current_chapter.volume_number = None;
+        manga_reader.list_of_chapters = ListOfChapters {
+            volumes: SortedVolumes::new(vec![Volumes {
+                volume: "none".to_string(),
+                chapters: SortedChapters::new(
+                    ["1", "2", "3"]
+                        .map(|number| Chapter {
+                            id: number.to_string(),
+                            number: number.to_string(),
+                            volume: "none".to_string(),
+                        })
+                        .to_vec(),
+                ),
+            }]),
+        };
+
+        manga_reader
+            .local_event_tx
+            .send(MangaReaderEvents::LoadChaptersRead(vec!["1".to_string()]))
+            .ok();
+        manga_reader.tick();
+
+        let mut terminal = Terminal::new(TestBackend::new(200, 40)).unwrap();
+        let rendered = |terminal: &mut Terminal<TestBackend>, manga_reader: &mut MangaReader<TestApiClient, TrackerTest>| {
+            terminal.draw(|frame| manga_reader.render(frame.area(), frame)).unwrap();
+            terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect::<String>()
+        };
+
+        assert!(rendered(&mut terminal, &mut manga_reader).contains("You skipped 1 chapter"));
+
+        manga_reader.page_list_state.list_state.select(Some(1));
+
+        assert!(!rendered(&mut terminal, &mut manga_reader).contains("You skipped"));
+    }
+
     #[test]
     fn list_of_chapters_searches_previous_which_is_in_previos_volume() {
         let mut list_of_volumes: Vec<Volumes> = vec![];
@@ -1325,6 +2299,342 @@ mod test {
         assert_eq!(PageItemState::Loading, manga_reader.pages_list.pages[0].state);
     }
 
+    #[tokio::test]
+    async fn low_memory_mode_only_keeps_pages_next_to_the_current_one() {
+        let chapter: ChapterToRead = ChapterToRead {
+            pages_url: vec!["http://localhost".parse().unwrap(); 5],
+            ..Default::default()
+        };
+
+        let mut manga_reader: MangaReader<TestApiClient, TrackerTest> =
+            MangaReader::new(chapter, "some_id".to_string(), Picker::new((8, 8)), TestApiClient::new());
+
+        manga_reader.set_low_memory_mode();
+        manga_reader.init_fetching_pages();
+
+        assert_eq!(vec![0, 1], manga_reader.get_pages_to_fetch());
+
+        let page_data = |index: usize| PageData {
+            panel: MangaPanel {
+                image_decoded: DynamicImage::new_rgb8(1, 1),
+                dimensions: (1, 1),
+            },
+            index,
+        };
+
+        manga_reader.load_page(page_data(0));
+        manga_reader.load_page(page_data(1));
+
+        // page 4 is too far from the current page
+        manga_reader.load_page(page_data(4));
+
+        assert!(manga_reader.pages[4].image_state.is_none());
+        assert_eq!(PageItemState::Waiting, manga_reader.pages_list.pages[4].state);
+
+        manga_reader.page_list_state.list_state.select(Some(1));
+        manga_reader.load_page(page_data(2));
+
+        manga_reader.page_list_state.list_state.select(Some(2));
+        manga_reader.drop_pages_out_of_range();
+
+        assert!(manga_reader.pages[0].image_state.is_none());
+        assert_eq!(PageItemState::Waiting, manga_reader.pages_list.pages[0].state);
+        assert!(manga_reader.pages[1].image_state.is_some());
+        assert!(manga_reader.pages[2].image_state.is_some());
+        assert_eq!(vec![3], manga_reader.get_pages_to_fetch());
+    }
+
+    #[test]
+    fn time_left_on_a_page_is_only_counted_up_to_a_limit() {
+        let mut manga_reader: MangaReader<TestApiClient, TrackerTest> =
+            MangaReader::new(ChapterToRead::default(), "some_id".to_string(), Picker::new((8, 8)), TestApiClient::new());
+
+        manga_reader.last_page_turn = Instant::now() - Duration::from_secs(10 * 60);
+        manga_reader.next_page();
+
+        assert_eq!(MAX_TIME_ON_A_PAGE.as_secs(), manga_reader.reading_time.as_secs());
+
+        manga_reader.last_page_turn = Instant::now() - Duration::from_secs(30);
+        manga_reader.previous_page();
+
+        assert_eq!(MAX_TIME_ON_A_PAGE.as_secs() + 30, manga_reader.reading_time.as_secs());
+
+        manga_reader.save_reading_time();
+
+        assert_eq!(Duration::ZERO, manga_reader.reading_time);
+    }
+
+    #[tokio::test]
+    async fn pages_are_encoded_in_the_background_once_they_load() {
+        let chapter: ChapterToRead = ChapterToRead {
+            pages_url: vec!["http://localhost".parse().unwrap(); 2],
+            ..Default::default()
+        };
+
+        let mut picker = Picker::new((8, 8));
+        picker.protocol_type = ProtocolType::Sixel;
+
+        let mut manga_reader: MangaReader<TestApiClient, TrackerTest> =
+            MangaReader::new(chapter, "some_id".to_string(), picker, TestApiClient::new());
+
+        manga_reader.init_fetching_pages();
+        manga_reader.local_event_rx.recv().await.expect("pages were not fetched");
+        manga_reader.reader_area = Rect::new(0, 0, 80, 40);
+
+        manga_reader.load_page(PageData {
+            panel: MangaPanel {
+                image_decoded: DynamicImage::new_rgb8(40, 60),
+                dimensions: (40, 60),
+            },
+            index: 1,
+        });
+
+        assert!(manga_reader.pages[1].encoding_area.is_some());
+        assert!(!manga_reader.encode_page_in_background(1));
+
+        let MangaReaderEvents::PageEncoded(encoded) = manga_reader.local_event_rx.recv().await.expect("page was not encoded")
+        else {
+            panic!("expected the page to be encoded");
+        };
+
+        assert_eq!(1, encoded.index);
+
+        manga_reader.load_encoded_page(encoded);
+
+        assert!(manga_reader.pages[1].encoding_area.is_none());
+        assert!(manga_reader.encode_page_in_background(1));
+    }
+
+    #[tokio::test]
+    async fn the_current_page_is_encoded_again_when_the_terminal_is_resized() {
+        let chapter: ChapterToRead = ChapterToRead {
+            pages_url: vec!["http://localhost".parse().unwrap(); 2],
+            ..Default::default()
+        };
+
+        let mut picker = Picker::new((8, 8));
+        picker.protocol_type = ProtocolType::Sixel;
+
+        let mut manga_reader: MangaReader<TestApiClient, TrackerTest> =
+            MangaReader::new(chapter, "some_id".to_string(), picker, TestApiClient::new());
+
+        manga_reader.init_fetching_pages();
+        manga_reader.local_event_rx.recv().await.expect("pages were not fetched");
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+        terminal.draw(|frame| manga_reader.render(frame.area(), frame)).unwrap();
+
+        for index in 0..2 {
+            manga_reader.load_page(PageData {
+                panel: MangaPanel {
+                    image_decoded: DynamicImage::new_rgb8(40, 60),
+                    dimensions: (40, 60),
+                },
+                index,
+            });
+        }
+
+        // the page being encoded for the old size is dropped once the terminal is resized
+        assert!(manga_reader.pages[1].encoding_area.is_some());
+
+        manga_reader.handle_events(Events::Resize(120, 50));
+
+        assert!(manga_reader.needs_relayout);
+
+        terminal.backend_mut().resize(120, 50);
+        terminal.draw(|frame| manga_reader.render(frame.area(), frame)).unwrap();
+
+        let new_area = Rect::new(0, 0, 120, 50);
+        let [_, page_area, _] = Layout::horizontal(PageSize::of(40, 60).layout()).areas(new_area);
+
+        assert!(!manga_reader.needs_relayout);
+        assert_eq!(new_area, manga_reader.reader_area);
+        assert!(
+            manga_reader.pages[0]
+                .image_state
+                .as_mut()
+                .is_some_and(|protocol| protocol.needs_resize(&Resize::Fit(None), page_area).is_none())
+        );
+        assert_eq!(Some(page_area), manga_reader.pages[1].encoding_area);
+    }
+
+    #[test]
+    fn the_webtoon_strip_is_filled_with_the_pages_after_the_one_at_its_top() {
+        let scaled_heights = [Some(1000), Some(500), None, Some(800)];
+
+        assert_eq!(
+            vec![
+                StripSlice {
+                    index: 0,
+                    top: 800,
+                    height: 200,
+                },
+                StripSlice {
+                    index: 1,
+                    top: 0,
+                    height: 200,
+                },
+            ],
+            webtoon_slices(&scaled_heights, 0, 0.8, 400)
+        );
+
+        // the strip stops at the page which is not loaded yet
+        assert_eq!(
+            vec![
+                StripSlice {
+                    index: 0,
+                    top: 500,
+                    height: 500,
+                },
+                StripSlice {
+                    index: 1,
+                    top: 0,
+                    height: 500,
+                },
+            ],
+            webtoon_slices(&scaled_heights, 0, 0.5, 1200)
+        );
+
+        assert!(webtoon_slices(&scaled_heights, 2, 0.0, 400).is_empty());
+    }
+
+    #[test]
+    fn scrolling_the_webtoon_strip_moves_on_to_the_next_and_previous_pages() {
+        let scaled_heights = [Some(1000), Some(500), None];
+
+        assert_eq!((0, 0.25), scroll_position(&scaled_heights, 0, 0.0, 250.0, 400));
+        assert_eq!((1, 0.1), scroll_position(&scaled_heights, 0, 0.25, 800.0, 400));
+        assert_eq!((0, 0.95), scroll_position(&scaled_heights, 1, 0.1, -100.0, 400));
+        assert_eq!((0, 0.0), scroll_position(&scaled_heights, 0, 0.0, -100.0, 400));
+
+        // the page not loaded yet is as tall as the strip, which is already showing all of it
+        assert_eq!((2, 0.0), scroll_position(&scaled_heights, 1, 0.9, 250.0, 400));
+    }
+
+    #[tokio::test]
+    async fn webtoon_mode_scrolls_through_the_pages_and_is_toggled_with_v() {
+        let mut manga_reader: MangaReader<TestApiClient, TrackerTest> = initialize_reader_page(TestApiClient::new());
+
+        manga_reader.set_webtoon_mode();
+        manga_reader.init_fetching_pages();
+        manga_reader.reader_area = Rect::new(0, 0, 100, 40);
+
+        for index in 0..2 {
+            manga_reader.load_page(PageData {
+                panel: MangaPanel {
+                    image_decoded: DynamicImage::new_rgba8(100, 400),
+                    dimensions: (100, 400),
+                },
+                index,
+            });
+        }
+
+        assert!(manga_reader.pages.iter().all(|page| page.image.is_some() && page.image_state.is_none()));
+
+        // each page is 1280 pixels tall once scaled to the strip and each scroll moves 190 pixels
+        for _ in 0..7 {
+            manga_reader.update(MangaReaderActions::NextPage);
+        }
+
+        assert_eq!(1, manga_reader.current_page_index());
+        assert_eq!(50.0 / 1280.0, manga_reader.webtoon_offset);
+
+        let area = Rect::new(0, 0, 100, 40);
+        let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+        terminal.draw(|frame| manga_reader.render(area, frame)).unwrap();
+
+        assert!(manga_reader.webtoon_strip.is_some());
+
+        press_key(&mut manga_reader, KeyCode::Char('v'));
+        let action = manga_reader.local_action_rx.recv().await.expect("no action was sent");
+        assert_eq!(MangaReaderActions::SwitchReadingMode(ReadingMode::Webtoon), action);
+        manga_reader.update(action);
+
+        assert_eq!(ReadingMode::Paged, manga_reader.reading_mode);
+        assert_eq!(0.0, manga_reader.webtoon_offset);
+        assert!(manga_reader.pages.iter().all(|page| !page.is_loaded()));
+    }
+
+    #[test]
+    fn the_reading_mode_saved_for_the_manga_is_loaded_from_the_database() {
+        let database = DatabaseActor::spawn(|| {
+            let connection = Connection::open_in_memory()?;
+            Database::new(&connection).setup()?;
+            Ok(connection)
+        });
+
+        let mut manga_reader: MangaReader<TestApiClient, TrackerTest> =
+            MangaReader::new(ChapterToRead::default(), "some_id".to_string(), Picker::new((8, 8)), TestApiClient::new())
+                .with_database(database.clone());
+
+        manga_reader.switch_reading_mode(ReadingMode::DoublePage);
+        manga_reader.reading_mode = ReadingMode::Paged;
+
+        manga_reader.load_reading_mode();
+
+        assert!(database.flush(Duration::from_secs(5)));
+        manga_reader.tick();
+
+        assert_eq!(ReadingMode::DoublePage, manga_reader.reading_mode);
+    }
+
+    #[test]
+    fn pages_are_paired_into_spreads() {
+        assert_eq!((0, Some(1)), spread_of(1, false, 5));
+        assert_eq!((2, Some(3)), spread_of(2, false, 5));
+        assert_eq!((4, None), spread_of(4, false, 5));
+
+        assert_eq!((0, None), spread_of(0, true, 5));
+        assert_eq!((1, Some(2)), spread_of(2, true, 5));
+        assert_eq!((3, Some(4)), spread_of(3, true, 5));
+    }
+
+    #[tokio::test]
+    async fn double_page_mode_shows_two_pages_from_right_to_left_when_the_terminal_is_wide_enough() {
+        let chapter_to_read: ChapterToRead = ChapterToRead {
+            pages_url: vec!["http://localhost".parse().unwrap(); 5],
+            ..Default::default()
+        };
+
+        let mut manga_reader: MangaReader<TestApiClient, TrackerTest> =
+            MangaReader::new(chapter_to_read, "".to_string(), Picker::new((8, 8)), TestApiClient::new());
+
+        manga_reader.init_fetching_pages();
+
+        press_key(&mut manga_reader, KeyCode::Char('d'));
+        let action = manga_reader.local_action_rx.recv().await.expect("no action was sent");
+        assert_eq!(MangaReaderActions::SwitchReadingMode(ReadingMode::DoublePage), action);
+        manga_reader.update(action);
+
+        manga_reader.reader_area = Rect::new(0, 0, 150, 40);
+
+        assert_eq!(Some((0, 1)), manga_reader.current_spread());
+        assert!(manga_reader.page_area(0).x > manga_reader.page_area(1).x);
+
+        manga_reader.update(MangaReaderActions::NextPage);
+
+        assert_eq!(Some((2, 3)), manga_reader.current_spread());
+
+        manga_reader.update(MangaReaderActions::NextPage);
+
+        assert_eq!(4, manga_reader.current_page_index());
+        assert_eq!(None, manga_reader.current_spread());
+
+        press_key(&mut manga_reader, KeyCode::Char('o'));
+        let action = manga_reader.local_action_rx.recv().await.expect("no action was sent");
+        manga_reader.update(action);
+
+        assert_eq!(Some((3, 4)), manga_reader.current_spread());
+
+        manga_reader.update(MangaReaderActions::PreviousPage);
+
+        assert_eq!(Some((1, 2)), manga_reader.current_spread());
+
+        manga_reader.reader_area = Rect::new(0, 0, 100, 40);
+
+        assert_eq!(None, manga_reader.current_spread());
+    }
+
     #[test]
     fn it_increases_page_size_based_on_manga_panel_dimesions() {
         let mut manga_reader: MangaReader<TestApiClient, TrackerTest> =
@@ -1488,6 +2798,7 @@ mod test {
             language: Languages::default(),
             volume_number: Some("1".to_string()),
             num_page_bookmarked: None,
+            page_to_resume: None,
             pages_url: vec!["http://localhost".parse().unwrap()],
         };
 
@@ -1531,6 +2842,7 @@ mod test {
             language: Languages::default(),
             number: 1.0,
             num_page_bookmarked: None,
+            page_to_resume: None,
             volume_number: Some("1".to_string()),
             pages_url: vec![],
         };
@@ -1623,7 +2935,7 @@ mod test {
         let manga_reader: MangaReader<TestApiClient, TrackerTest> =
             MangaReader::new(chapter, "".to_string(), Picker::new((8, 8)), TestApiClient::new());
 
-        let id_chapter_saved = manga_reader.save_reading_history(&mut conn)?;
+        let id_chapter_saved = manga_reader.chapter_to_save().save_reading_history(&mut conn)?;
 
         let has_been_saved: bool =
             conn.query_row("SELECT is_read FROM chapters WHERE id = ?1", [id_chapter_saved], |row| row.get(0))?;
@@ -1633,6 +2945,34 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn the_manga_is_added_to_plan_to_read_from_the_reader() -> Result<(), Box<dyn Error>> {
+        let mut conn = Connection::open_in_memory()?;
+        Database::new(&conn).setup()?;
+
+        let mut manga_reader: MangaReader<TestApiClient, TrackerTest> = MangaReader::new(
+            ChapterToRead::default(),
+            "manga_from_latest_updates".to_string(),
+            Picker::new((8, 8)),
+            TestApiClient::new(),
+        )
+        .with_manga_title("some_title".to_string());
+
+        press_key(&mut manga_reader, KeyCode::Char('p'));
+        let action = manga_reader.local_action_rx.recv().await.expect("no action was sent");
+        assert_eq!(MangaReaderActions::AddToLibrary(MangaHistoryType::PlanToRead), action);
+
+        press_key(&mut manga_reader, KeyCode::Char('a'));
+        let action = manga_reader.local_action_rx.recv().await.expect("no action was sent");
+        assert_eq!(MangaReaderActions::AddToLibrary(MangaHistoryType::ReadingHistory), action);
+
+        manga_reader.chapter_to_save().save_to_library(MangaHistoryType::PlanToRead, &mut conn)?;
+
+        assert!(Database::new(&conn).manga_exists("manga_from_latest_updates")?);
+
+        Ok(())
+    }
+
     #[test]
     fn it_loads_chapter_on_event() {
         let chapter_to_load = ChapterToRead {
@@ -1705,9 +3045,13 @@ mod test {
 
         let mut database = TestDatabase::new();
 
-        manga_reader.set_current_chapter_bookmarked(Some(2), &mut database);
+        manga_reader.chapter_to_save().bookmark(Some(2), &mut database).unwrap();
 
         assert!(database.was_bookmarked());
+
+        manga_reader.local_event_tx.send(MangaReaderEvents::ChapterBookmarked(2)).ok();
+        manga_reader.tick();
+
         assert_eq!(Some(2), manga_reader.page_list_state.page_bookmarked);
         assert_eq!(State::ManualBookmark, manga_reader.state)
     }
@@ -1727,11 +3071,42 @@ mod test {
         let area = Rect::new(0, 0, 10, 10);
         let mut buf = Buffer::empty(area);
 
-        StatefulWidget::render(manga_reader.pages_list.clone(), area, &mut buf, &mut manga_reader.page_list_state);
+        StatefulWidget::render(&mut manga_reader.pages_list, area, &mut buf, &mut manga_reader.page_list_state);
 
         assert_eq!(1, manga_reader.page_list_state.list_state.selected.expect("should not be none"));
     }
 
+    #[test]
+    fn it_resumes_the_chapter_at_the_last_page_reached() {
+        let chapter_to_read: ChapterToRead = ChapterToRead {
+            page_to_resume: Some(1),
+            pages_url: vec!["http://localhost".parse().unwrap(); 3],
+            ..Default::default()
+        };
+
+        let mut manga_reader: MangaReader<TestApiClient, TrackerTest> =
+            MangaReader::new(chapter_to_read, "".to_string(), Picker::new((8, 8)), TestApiClient::new());
+
+        assert_eq!(Some(1), manga_reader.page_list_state.list_state.selected);
+        assert_eq!(None, manga_reader.page_list_state.page_bookmarked);
+
+        manga_reader.pages_list = PagesList::new(vec![PagesItem::new(0), PagesItem::new(1), PagesItem::new(2)]);
+
+        let area = Rect::new(0, 0, 20, 20);
+        let mut buf = Buffer::empty(area);
+
+        manga_reader.render_page_list(area, &mut buf);
+        manga_reader.next_page();
+
+        assert_eq!(
+            Some(ChapterProgress {
+                last_page: 2,
+                total_pages: 3
+            }),
+            manga_reader.chapter_progress()
+        );
+    }
+
     #[tokio::test]
     async fn it_does_not_send_event_to_bookmark_chapter_on_m_key_press_if_autobookmarking_is_true() {
         let mut manga_reader: MangaReader<TestApiClient, TrackerTest> =
@@ -1778,9 +3153,9 @@ mod test {
 
         let tracker = TrackerTest::failing_with_error_message(expected_error_message);
 
-        manga_reader.track_manga_reading_history(Some(tracker));
+        manga_reader.track_manga_reading_history(vec![tracker]);
 
-        let expected = MangaReaderEvents::ErrorTrackingReadingProgress(expected_error_message.to_string());
+        let expected = MangaReaderEvents::ErrorTrackingReadingProgress(format!("test: {expected_error_message}"));
 
         let result = timeout(Duration::from_millis(500), manga_reader.local_event_rx.recv())
             .await?
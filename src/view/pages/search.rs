@@ -1,13 +1,14 @@
+use std::path::PathBuf;
 use std::thread::sleep;
 use std::time::Duration;
 
 use crossterm::event::{self, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use image::DynamicImage;
-use manga_tui::SearchTerm;
+use manga_tui::{MangaLink, SearchTerm};
 use ratatui::layout::{Constraint, Direction, Layout, Margin, Rect};
 use ratatui::style::{Color, Style, Stylize};
 use ratatui::text::{Line, Span, ToSpan};
-use ratatui::widgets::{Block, Paragraph, StatefulWidget, StatefulWidgetRef, Widget, Wrap};
+use ratatui::widgets::{Block, Paragraph, StatefulWidget, Tabs, Widget, Wrap};
 use ratatui::Frame;
 use ratatui_image::picker::Picker;
 use ratatui_image::Resize;
@@ -19,19 +20,22 @@ use tui_input::Input;
 use tui_widget_list::ListState;
 
 use crate::backend::api_responses::SearchMangaResponse;
-use crate::backend::database::{save_plan_to_read, MangaPlanToReadSave, DBCONN};
+use crate::backend::bug_report::report_bug;
+use crate::backend::database::{save_plan_to_read, Database, MangaPlanToReadSave};
+use crate::backend::database_actor::DatabaseActor;
 use crate::backend::error_log::{write_to_error_log, ErrorType};
 #[cfg(test)]
 use crate::backend::fetch::fake_api_client::MockMangadexClient;
 use crate::backend::fetch::ApiClient;
 #[cfg(not(test))]
 use crate::backend::fetch::MangadexClient;
+use crate::backend::provider_error::ProviderErrorKind;
 use crate::backend::tracker::{track_manga_plan_to_read, MangaTracker};
 use crate::backend::tui::Events;
 use crate::common::{Artist, Author, ImageState};
 use crate::global::{ERROR_STYLE, INSTRUCTIONS_STYLE};
-use crate::utils::render_search_bar;
-use crate::view::tasks::search::{search_manga_covers, search_mangas_operation};
+use crate::utils::{render_search_bar, spin_loader};
+use crate::view::tasks::search::{search_manga_by_id, search_manga_covers, search_mangas_operation};
 use crate::view::widgets::filter_widget::state::FilterState;
 use crate::view::widgets::filter_widget::FilterWidget;
 use crate::view::widgets::search::*;
@@ -54,7 +58,11 @@ pub enum SearchPageEvents {
     /// Indicate to search manga covers, if the terminal supports it
     SearchCovers,
     LoadCover(Option<DynamicImage>, String),
-    LoadMangasFound(Option<SearchMangaResponse>),
+    LoadMangasFound(Result<SearchMangaResponse, ProviderErrorKind>),
+    /// The titles of the mangas saved as plan to read
+    AddedToPlanToRead(Vec<String>),
+    MarkedNotInterested(MangaItem),
+    NotInterestedUndone(MangaItem),
 }
 
 /// These are actions that the user actively via key events or mouse events
@@ -70,6 +78,13 @@ pub enum SearchPageActions {
     PreviousPage,
     GoToMangaPage,
     PlanToRead,
+    ToggleMark,
+    NotInterested,
+    UndoNotInterested,
+    NextStatusTab,
+    /// Searches again after the search failed
+    Retry,
+    ReportBug,
 }
 
 #[derive(Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -94,15 +109,21 @@ where
     pub input_mode: InputMode,
     search_bar: Input,
     state: PageState,
+    /// Why the last search failed, shown while the state is `ErrorOcurred`
+    search_error: Option<ProviderErrorKind>,
     loader_state: ThrobberState,
     mangas_found_list: MangasFoundList,
     filter_state: FilterState,
     manga_added_to_plan_to_read: Option<String>,
+    /// The last manga marked as not interested, kept so it can be brought back
+    manga_not_interested: Option<MangaItem>,
     picker: Option<Picker>,
     manga_cover_state: ImageState,
     tasks: JoinSet<()>,
     api_client: T,
-    manga_tracker: Option<S>,
+    manga_trackers: Vec<S>,
+    /// Every query is sent to it so the ui thread never waits for the database
+    database: DatabaseActor,
 }
 
 /// This contains the data the application gets when doing a search
@@ -110,6 +131,9 @@ where
 struct MangasFoundList {
     widget: ListMangasFoundWidget,
     state: tui_widget_list::ListState,
+    /// All the mangas of the current page, `widget` only has the ones matching `status_tab`
+    all_mangas: Vec<MangaItem>,
+    status_tab: StatusTabs,
     total_result: u32,
     page: u32,
 }
@@ -140,8 +164,9 @@ where
             SearchPageActions::StopTyping => self.input_mode = InputMode::Idle,
             SearchPageActions::Search => {
                 self.mangas_found_list.page = 1;
-                self.search_mangas();
+                self.search_or_open_link();
             },
+            SearchPageActions::Retry => self.search_or_open_link(),
             SearchPageActions::ScrollUp => self.scroll_up(),
             SearchPageActions::ScrollDown => self.scroll_down(),
             SearchPageActions::NextPage => self.search_next_page(),
@@ -153,6 +178,11 @@ where
                 }
             },
             SearchPageActions::PlanToRead => self.plan_to_read(),
+            SearchPageActions::ToggleMark => self.toggle_mark(),
+            SearchPageActions::NotInterested => self.set_not_interested(),
+            SearchPageActions::UndoNotInterested => self.undo_not_interested(),
+            SearchPageActions::NextStatusTab => self.next_status_tab(),
+            SearchPageActions::ReportBug => report_bug(),
         }
     }
 
@@ -175,12 +205,15 @@ where
         self.abort_tasks();
         self.manga_cover_state = ImageState::default();
         self.state = PageState::default();
+        self.search_error = None;
         self.manga_added_to_plan_to_read = None;
+        self.manga_not_interested = None;
         self.input_mode = InputMode::Idle;
         self.mangas_found_list.state = ListState::default();
         if !self.mangas_found_list.widget.mangas.is_empty() {
             self.mangas_found_list.widget.mangas = vec![];
         }
+        self.mangas_found_list.all_mangas = vec![];
     }
 }
 
@@ -189,7 +222,7 @@ where
     T: ApiClient,
     S: MangaTracker,
 {
-    pub fn new(picker: Option<Picker>, api_client: T, manga_tracker: Option<S>) -> Self {
+    pub fn new(picker: Option<Picker>, api_client: T, manga_trackers: Vec<S>) -> Self {
         let (action_tx, action_rx) = mpsc::unbounded_channel::<SearchPageActions>();
         let (local_event_tx, local_event) = mpsc::unbounded_channel::<SearchPageEvents>();
 
@@ -202,23 +235,37 @@ where
             input_mode: InputMode::default(),
             search_bar: Input::default(),
             state: PageState::default(),
+            search_error: None,
             mangas_found_list: MangasFoundList::default(),
             tasks: JoinSet::new(),
             filter_state: FilterState::new(),
             loader_state: ThrobberState::default(),
             manga_added_to_plan_to_read: None,
+            manga_not_interested: None,
             picker,
             manga_cover_state: ImageState::default(),
             api_client,
-            manga_tracker,
+            manga_trackers,
+            database: DatabaseActor::global().clone(),
         }
     }
 
+    #[cfg(test)]
+    fn with_database(mut self, database: DatabaseActor) -> Self {
+        self.database = database;
+        self
+    }
+
     pub fn with_global_sender(mut self, sender: UnboundedSender<Events>) -> Self {
         self.global_event_tx = Some(sender);
         self
     }
 
+    pub fn with_saved_filters(mut self, path: PathBuf) -> Self {
+        self.filter_state.load_saved_filters(path);
+        self
+    }
+
     fn render_input_area(&self, area: Rect, frame: &mut Frame<'_>) {
         let [input_area, information_area] = Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
 
@@ -241,16 +288,24 @@ where
 
         render_search_bar(self.input_mode == InputMode::Typing, input_help, &self.search_bar, frame, input_area);
 
-        if let Some(name) = self.manga_added_to_plan_to_read.as_ref() {
-            Paragraph::new(format!("Added: {} to plan to read 📖", name).to_span().underlined())
-                .wrap(Wrap { trim: true })
-                .render(
-                    information_area.inner(Margin {
-                        horizontal: 1,
-                        vertical: 1,
-                    }),
-                    frame.buffer_mut(),
-                );
+        let information = match (self.manga_not_interested.as_ref(), self.manga_added_to_plan_to_read.as_ref()) {
+            (Some(item), _) => Some(Line::from(vec![
+                Span::raw(format!("{} won't be shown again ", item.manga.title)).underlined(),
+                "Undo ".into(),
+                "<z>".to_span().style(*INSTRUCTIONS_STYLE),
+            ])),
+            (None, Some(name)) => Some(Line::from(Span::raw(format!("Added: {} to plan to read 📖", name)).underlined())),
+            (None, None) => None,
+        };
+
+        if let Some(information) = information {
+            Paragraph::new(information).wrap(Wrap { trim: true }).render(
+                information_area.inner(Margin {
+                    horizontal: 1,
+                    vertical: 1,
+                }),
+                frame.buffer_mut(),
+            );
         }
     }
 
@@ -276,9 +331,21 @@ where
                 Block::bordered().title("No mangas were found").render(area, buf);
             },
             PageState::ErrorOcurred => {
-                Block::bordered()
-                    .title("An error ocurred when searching mangas, please try again".to_span().style(*ERROR_STYLE))
-                    .render(area, buf);
+                let [banner_area, _] = Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+
+                let error = match self.search_error {
+                    Some(kind) => format!("Could not search mangas, {kind}"),
+                    None => "An error ocurred when searching mangas".to_string(),
+                };
+
+                Paragraph::new(Line::from(vec![
+                    "Retry ".into(),
+                    Span::raw("<r>").style(*INSTRUCTIONS_STYLE),
+                    " Report this bug ".into(),
+                    Span::raw("<E>").style(*INSTRUCTIONS_STYLE),
+                ]))
+                .block(Block::bordered().title(error.to_span().style(*ERROR_STYLE)).border_style(*ERROR_STYLE))
+                .render(banner_area, buf);
             },
             PageState::DisplayingMangasFound => {
                 let total_pages = self.mangas_found_list.total_result as f64 / 10_f64;
@@ -290,8 +357,14 @@ where
                     Span::raw("<k>").style(*INSTRUCTIONS_STYLE),
                     " Plan to read ".into(),
                     Span::raw("<p>").style(*INSTRUCTIONS_STYLE),
+                    " Mark ".into(),
+                    Span::raw("<Space>").style(*INSTRUCTIONS_STYLE),
+                    " Not interested ".into(),
+                    Span::raw("<x>").style(*INSTRUCTIONS_STYLE),
                     " Read ".into(),
                     Span::raw("<r>").style(*INSTRUCTIONS_STYLE),
+                    " Status ".into(),
+                    Span::raw("<tab>").style(*INSTRUCTIONS_STYLE),
                 ]);
 
                 let pagination_instructions = Line::from(vec![
@@ -313,14 +386,24 @@ where
                     .title_bottom(pagination_instructions)
                     .render(manga_list_area, buf);
 
-                let inner_list_area = manga_list_area.inner(Margin {
-                    horizontal: 1,
-                    vertical: 1,
-                });
+                let [status_tabs_area, inner_list_area] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(manga_list_area.inner(Margin {
+                        horizontal: 1,
+                        vertical: 1,
+                    }));
+
+                Tabs::new(StatusTabs::TITLES)
+                    .select(self.mangas_found_list.status_tab.index())
+                    .highlight_style(Style::default().fg(Color::Yellow))
+                    .render(status_tabs_area, buf);
+
+                if self.mangas_found_list.widget.mangas.is_empty() {
+                    Paragraph::new("No mangas with this status in this page").render(inner_list_area, buf);
+                }
 
                 if !self.filter_state.is_open {
-                    StatefulWidgetRef::render_ref(
-                        &self.mangas_found_list.widget,
+                    StatefulWidget::render(
+                        &mut self.mangas_found_list.widget,
                         inner_list_area,
                         buf,
                         &mut self.mangas_found_list.state,
@@ -396,36 +479,160 @@ where
         None
     }
 
+    fn toggle_mark(&mut self) {
+        let Some(item) = self.get_current_manga_selected_mut() else {
+            return;
+        };
+
+        item.is_marked = !item.is_marked;
+        let (id, is_marked) = (item.manga.id.clone(), item.is_marked);
+
+        if let Some(manga) = self.mangas_found_list.all_mangas.iter_mut().find(|manga| manga.manga.id == id) {
+            manga.is_marked = is_marked;
+        }
+    }
+
+    /// The mangas marked in this page of results are added, if none is marked the one selected is added
     fn plan_to_read(&mut self) {
-        if let Some(item) = self.get_current_manga_selected() {
+        let marked: Vec<MangaItem> = self.mangas_found_list.all_mangas.iter().filter(|item| item.is_marked).cloned().collect();
+
+        let mangas = if marked.is_empty() { self.get_current_manga_selected().cloned().into_iter().collect() } else { marked };
+
+        for item in &mangas {
             let manga_selected = item.clone();
-            track_manga_plan_to_read(self.manga_tracker.clone(), manga_selected.manga.title.clone(), move |error| {
-                write_to_error_log(
-                    format!(
-                        "Could not add manga {} as plan to read, more details about the error : \n {}",
-                        manga_selected.manga.title.clone(),
-                        error
-                    )
-                    .into(),
-                );
-            });
-            let binding = DBCONN.lock().unwrap();
-            let conn = binding.as_ref().unwrap();
-            let plan_to_read_operation = save_plan_to_read(
-                MangaPlanToReadSave {
-                    id: &item.manga.id,
-                    title: &item.manga.title,
-                    img_url: item.manga.img_url.as_deref(),
+            track_manga_plan_to_read(
+                self.manga_trackers.clone(),
+                manga_selected.manga.id.clone(),
+                manga_selected.manga.title.clone(),
+                move |error| {
+                    write_to_error_log(
+                        format!(
+                            "Could not add manga {} as plan to read, more details about the error : \n {}",
+                            manga_selected.manga.title.clone(),
+                            error
+                        )
+                        .into(),
+                    );
                 },
-                conn,
             );
+        }
 
-            match plan_to_read_operation {
-                Ok(()) => {
-                    self.manga_added_to_plan_to_read = Some(item.manga.title.clone());
-                },
-                Err(e) => write_to_error_log(ErrorType::Error(Box::new(e))),
-            }
+        self.database.query(
+            self.local_event_tx.clone(),
+            move |conn| {
+                let mut added = vec![];
+
+                for item in mangas {
+                    let plan_to_read_operation = save_plan_to_read(
+                        MangaPlanToReadSave {
+                            id: &item.manga.id,
+                            title: &item.manga.title,
+                            img_url: item.manga.img_url.as_deref(),
+                        },
+                        conn,
+                    );
+
+                    match plan_to_read_operation {
+                        Ok(()) => added.push(item.manga.title),
+                        Err(e) => write_to_error_log(ErrorType::Error(Box::new(e))),
+                    }
+                }
+
+                Ok(added)
+            },
+            SearchPageEvents::AddedToPlanToRead,
+        );
+
+        for item in self
+            .mangas_found_list
+            .all_mangas
+            .iter_mut()
+            .chain(self.mangas_found_list.widget.mangas.iter_mut())
+        {
+            item.is_marked = false;
+        }
+    }
+
+    fn added_to_plan_to_read(&mut self, added: Vec<String>) {
+        self.manga_added_to_plan_to_read = match added.as_slice() {
+            [] => return,
+            [title] => Some(title.clone()),
+            titles => Some(format!("{} mangas", titles.len())),
+        };
+        self.manga_not_interested = None;
+    }
+
+    /// The manga is hidden from this and the next searches
+    fn set_not_interested(&mut self) {
+        let Some(item) = self.get_current_manga_selected().cloned() else {
+            return;
+        };
+
+        self.database.query(
+            self.local_event_tx.clone(),
+            move |conn| {
+                Database::new(conn).set_not_interested(&item.manga.id, &item.manga.title)?;
+                Ok(item)
+            },
+            SearchPageEvents::MarkedNotInterested,
+        );
+    }
+
+    fn hide_manga_not_interested(&mut self, item: MangaItem) {
+        let selected = self.mangas_found_list.state.selected;
+
+        self.mangas_found_list.all_mangas.retain(|manga| manga.manga.id != item.manga.id);
+        self.filter_mangas_by_status();
+
+        let total = self.mangas_found_list.widget.mangas.len();
+        if let Some(index) = selected.filter(|_| total > 0) {
+            self.mangas_found_list.state.select(Some(index.min(total - 1)));
+        }
+
+        self.manga_added_to_plan_to_read = None;
+        self.manga_not_interested = Some(item);
+    }
+
+    fn undo_not_interested(&mut self) {
+        let Some(item) = self.manga_not_interested.take() else {
+            return;
+        };
+
+        self.database.query(
+            self.local_event_tx.clone(),
+            move |conn| {
+                Database::new(conn).remove_not_interested(&item.manga.id)?;
+                Ok(item)
+            },
+            SearchPageEvents::NotInterestedUndone,
+        );
+    }
+
+    fn show_manga_not_interested(&mut self, item: MangaItem) {
+        self.mangas_found_list.all_mangas.push(item);
+        self.filter_mangas_by_status();
+    }
+
+    fn next_status_tab(&mut self) {
+        self.mangas_found_list.status_tab = self.mangas_found_list.status_tab.cycle();
+        self.filter_mangas_by_status();
+    }
+
+    /// Mangadex's search already supports filtering by status, this only narrows down the page which was loaded
+    fn filter_mangas_by_status(&mut self) {
+        let status_tab = self.mangas_found_list.status_tab;
+
+        self.mangas_found_list.widget.mangas = self
+            .mangas_found_list
+            .all_mangas
+            .iter()
+            .filter(|item| status_tab.matches(&item.manga.status))
+            .cloned()
+            .collect();
+
+        self.mangas_found_list.state = ListState::default();
+        if !self.mangas_found_list.widget.mangas.is_empty() {
+            self.mangas_found_list.state.select(Some(0));
         }
     }
 
@@ -452,15 +659,33 @@ where
                 KeyCode::Char('p') => {
                     self.local_action_tx.send(SearchPageActions::PlanToRead).ok();
                 },
+                KeyCode::Char(' ') => {
+                    self.local_action_tx.send(SearchPageActions::ToggleMark).ok();
+                },
+                KeyCode::Char('x') => {
+                    self.local_action_tx.send(SearchPageActions::NotInterested).ok();
+                },
+                KeyCode::Char('z') if self.manga_not_interested.is_some() => {
+                    self.local_action_tx.send(SearchPageActions::UndoNotInterested).ok();
+                },
                 KeyCode::Char('b') => {
                     self.local_action_tx.send(SearchPageActions::PreviousPage).ok();
                 },
                 KeyCode::Char('f') => {
                     self.local_action_tx.send(SearchPageActions::ToggleFilters).ok();
                 },
+                KeyCode::Char('r') if self.state == PageState::ErrorOcurred => {
+                    self.local_action_tx.send(SearchPageActions::Retry).ok();
+                },
                 KeyCode::Char('r') | KeyCode::Enter => {
                     self.local_action_tx.send(SearchPageActions::GoToMangaPage).ok();
                 },
+                KeyCode::Tab => {
+                    self.local_action_tx.send(SearchPageActions::NextStatusTab).ok();
+                },
+                KeyCode::Char('E') if self.state == PageState::ErrorOcurred => {
+                    self.local_action_tx.send(SearchPageActions::ReportBug).ok();
+                },
 
                 _ => {},
             },
@@ -497,7 +722,7 @@ where
         }
     }
 
-    pub fn is_typing_filter(&mut self) -> bool {
+    pub fn is_typing_filter(&self) -> bool {
         self.filter_state.is_typing
     }
 
@@ -520,6 +745,45 @@ where
         self.tasks.spawn(search_mangas_operation(api_client, manga_to_search, page, filters, tx));
     }
 
+    /// A mangadex url or id was typed, so instead of searching go straight to the manga page
+    fn open_manga_link(&mut self, link: MangaLink) {
+        self.clean_up();
+
+        self.state = PageState::SearchingMangas;
+
+        let tx = self.local_event_tx.clone();
+        let global_tx = self.global_event_tx.clone();
+
+        #[cfg(not(test))]
+        let api_client = MangadexClient::global().clone();
+
+        #[cfg(test)]
+        let api_client = MockMangadexClient::new();
+
+        self.tasks.spawn(async move {
+            match search_manga_by_id(api_client, link.id()).await {
+                Ok(manga) => {
+                    if let Some(global_tx) = global_tx {
+                        global_tx.send(Events::GoToMangaPage(MangaItem::new(manga))).ok();
+                    }
+                },
+                Err(e) => {
+                    let kind = ProviderErrorKind::classify(e.as_ref());
+                    write_to_error_log(ErrorType::Error(e));
+                    tx.send(SearchPageEvents::LoadMangasFound(Err(kind))).ok();
+                },
+            }
+        });
+    }
+
+    /// A mangadex url or id is opened directly instead of being searched
+    fn search_or_open_link(&mut self) {
+        match MangaLink::parse(self.search_bar.value()) {
+            Some(link) => self.open_manga_link(link),
+            None => self.search_mangas(),
+        }
+    }
+
     fn search_next_page(&mut self) {
         if self.state == PageState::DisplayingMangasFound
             && self.state != PageState::SearchingMangas
@@ -547,6 +811,12 @@ where
         self.search_mangas();
     }
 
+    pub fn search_mangas_by_title(&mut self, title: String) {
+        self.search_bar = Input::new(title);
+        self.mangas_found_list.page = 1;
+        self.search_mangas();
+    }
+
     pub fn search_mangas_of_artist(&mut self, artist: Artist) {
         self.filter_state.set_artist(artist);
         self.search_bar.reset();
@@ -554,22 +824,23 @@ where
         self.search_mangas();
     }
 
-    fn load_mangas_found(&mut self, response: Option<SearchMangaResponse>) {
+    fn load_mangas_found(&mut self, response: Result<SearchMangaResponse, ProviderErrorKind>) {
         match response {
-            Some(response) => {
+            Ok(response) => {
                 if response.data.is_empty() {
                     self.state = PageState::NotFound;
                     self.mangas_found_list.total_result = 0;
                     return;
                 }
-                self.mangas_found_list.widget = ListMangasFoundWidget::from_response(response.data);
+                self.mangas_found_list.all_mangas = ListMangasFoundWidget::from_response(response.data).mangas;
                 self.mangas_found_list.total_result = response.total;
-                self.mangas_found_list.state.select(Some(0));
+                self.filter_mangas_by_status();
                 self.state = PageState::DisplayingMangasFound;
                 self.init_search_manga_covers();
             },
-            None => {
+            Err(kind) => {
                 self.state = PageState::ErrorOcurred;
+                self.search_error = Some(kind);
                 self.mangas_found_list.total_result = 0;
             },
         }
@@ -582,7 +853,7 @@ where
     }
 
     fn search_covers(&mut self) {
-        for item in self.mangas_found_list.widget.mangas.iter() {
+        for item in self.mangas_found_list.all_mangas.iter() {
             let manga_id = item.manga.id.clone();
             let tx = self.local_event_tx.clone();
 
@@ -604,17 +875,18 @@ where
     }
 
     fn load_cover(&mut self, maybe_cover: Option<DynamicImage>, manga_id: String) {
-        if let Some(cover) = maybe_cover {
-            if let Some(picker) = self.picker.as_mut() {
-                if let Ok(protocol) = picker.new_protocol(cover, self.manga_cover_state.get_img_area(), Resize::Fit(None)) {
-                    self.manga_cover_state.insert_manga(protocol, manga_id);
-                }
-            }
+        let Some(picker) = self.picker.as_mut() else {
+            return;
+        };
+
+        match maybe_cover.map(|cover| picker.new_protocol(cover, self.manga_cover_state.get_img_area(), Resize::Fit(None))) {
+            Some(Ok(protocol)) => self.manga_cover_state.insert_manga(protocol, manga_id),
+            _ => self.manga_cover_state.set_failed(manga_id),
         }
     }
 
     pub fn tick(&mut self) {
-        self.loader_state.calc_next();
+        spin_loader(&mut self.loader_state);
         if let Ok(event) = self.local_event_rx.try_recv() {
             match event {
                 SearchPageEvents::LoadMangasFound(response) => self.load_mangas_found(response),
@@ -626,6 +898,9 @@ where
                     }
                 },
                 SearchPageEvents::LoadCover(maybe_image, manga_id) => self.load_cover(maybe_image, manga_id),
+                SearchPageEvents::AddedToPlanToRead(added) => self.added_to_plan_to_read(added),
+                SearchPageEvents::MarkedNotInterested(item) => self.hide_manga_not_interested(item),
+                SearchPageEvents::NotInterestedUndone(item) => self.show_manga_not_interested(item),
             }
         }
     }
@@ -633,17 +908,43 @@ where
 
 #[cfg(test)]
 mod test {
+    use ratatui::backend::TestBackend;
     use ratatui::buffer::Buffer;
+    use ratatui::Terminal;
+    use rusqlite::Connection;
 
     use super::*;
-    use crate::backend::api_responses::{Data, MangaSearchAttributes, MangaSearchRelationship};
+    use crate::backend::api_responses::{Attributes, Data, MangaSearchAttributes, MangaSearchRelationship};
     use crate::global::test_utils::TrackerTest;
     use crate::view::widgets::press_key;
 
+    fn test_database() -> DatabaseActor {
+        DatabaseActor::spawn(|| {
+            let connection = Connection::open_in_memory()?;
+            Database::new(&connection).setup()?;
+            Ok(connection)
+        })
+    }
+
+    /// Handles the results of the queries sent to the database
+    fn wait_for_queries(search_page: &mut SearchPage<MockMangadexClient, TrackerTest>) {
+        assert!(search_page.database.flush(Duration::from_secs(5)));
+        while !search_page.local_event_rx.is_empty() {
+            search_page.tick();
+        }
+    }
+
+    fn query_test_database<R: Send + 'static>(
+        database: &DatabaseActor,
+        query: impl FnOnce(&mut Connection) -> rusqlite::Result<R> + Send + 'static,
+    ) -> R {
+        tokio::runtime::Runtime::new().unwrap().block_on(database.run(query)).unwrap()
+    }
+
     #[tokio::test]
     async fn search_page_events() {
         let mut search_page: SearchPage<MockMangadexClient, TrackerTest> =
-            SearchPage::new(Some(Picker::new((8, 9))), MockMangadexClient::new(), None);
+            SearchPage::new(Some(Picker::new((8, 9))), MockMangadexClient::new(), vec![]);
 
         let mock_search_result = SearchMangaResponse {
             data: vec![
@@ -680,7 +981,7 @@ mod test {
         // assuming a search was made
         search_page
             .local_event_tx
-            .send(SearchPageEvents::LoadMangasFound(Some(mock_search_result)))
+            .send(SearchPageEvents::LoadMangasFound(Ok(mock_search_result)))
             .ok();
 
         search_page.tick();
@@ -700,9 +1001,69 @@ mod test {
         assert!(search_page.manga_cover_state.get_image_state("manga_id_2").is_some())
     }
 
+    #[tokio::test]
+    async fn goes_to_manga_page_if_a_mangadex_url_is_searched() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Events>();
+        let mut search_page: SearchPage<MockMangadexClient, TrackerTest> =
+            SearchPage::new(None, MockMangadexClient::new(), vec![]).with_global_sender(tx);
+
+        search_page.search_bar = Input::new("https://mangadex.org/title/a1c7c817-4e59-43b7-9365-09675a149a6f/one-piece".into());
+
+        search_page.update(SearchPageActions::Search);
+
+        let event = tokio::time::timeout(Duration::from_millis(500), rx.recv())
+            .await
+            .expect("no event was sent")
+            .expect("no event was sent");
+
+        assert!(matches!(event, Events::GoToMangaPage(_)));
+    }
+
+    #[tokio::test]
+    async fn it_offers_to_report_a_bug_only_if_an_error_ocurred() {
+        let mut search_page: SearchPage<MockMangadexClient, TrackerTest> = SearchPage::new(None, MockMangadexClient::new(), vec![]);
+
+        press_key(&mut search_page, KeyCode::Char('E'));
+
+        assert!(search_page.local_action_rx.try_recv().is_err());
+
+        search_page.state = PageState::ErrorOcurred;
+
+        press_key(&mut search_page, KeyCode::Char('E'));
+
+        assert_eq!(Ok(SearchPageActions::ReportBug), search_page.local_action_rx.try_recv());
+    }
+
+    #[tokio::test]
+    async fn it_shows_why_the_search_failed_and_retries_with_r() {
+        let mut search_page: SearchPage<MockMangadexClient, TrackerTest> = SearchPage::new(None, MockMangadexClient::new(), vec![]);
+
+        search_page.load_mangas_found(Err(ProviderErrorKind::RateLimited));
+
+        assert_eq!(PageState::ErrorOcurred, search_page.state);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 10)).unwrap();
+
+        terminal.draw(|frame| search_page.render_manga_found_area(frame.area(), frame)).unwrap();
+
+        let rendered: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+
+        assert!(rendered.contains("Could not search mangas, too many requests were made, wait a moment"));
+
+        press_key(&mut search_page, KeyCode::Char('r'));
+
+        let action = search_page.local_action_rx.try_recv().expect("no action was sent");
+
+        assert_eq!(SearchPageActions::Retry, action);
+
+        search_page.update(action);
+
+        assert_eq!(PageState::SearchingMangas, search_page.state);
+    }
+
     #[tokio::test]
     async fn search_page_key_events() {
-        let mut search_page: SearchPage<MockMangadexClient, TrackerTest> = SearchPage::new(None, MockMangadexClient::new(), None);
+        let mut search_page: SearchPage<MockMangadexClient, TrackerTest> = SearchPage::new(None, MockMangadexClient::new(), vec![]);
 
         assert!(search_page.state == PageState::Normal);
         assert!(!search_page.filter_state.is_open);
@@ -741,12 +1102,7 @@ mod test {
         let mut buf = Buffer::empty(area);
 
         // Render the list of mangas found
-        StatefulWidgetRef::render_ref(
-            &search_page.mangas_found_list.widget,
-            area,
-            &mut buf,
-            &mut search_page.mangas_found_list.state,
-        );
+        StatefulWidget::render(&mut search_page.mangas_found_list.widget, area, &mut buf, &mut search_page.mangas_found_list.state);
 
         // scroll down the list
         press_key(&mut search_page, KeyCode::Char('j'));
@@ -817,12 +1173,184 @@ mod test {
         }
     }
 
+    #[test]
+    fn status_tabs_filter_the_mangas_found() {
+        let mut search_page: SearchPage<MockMangadexClient, TrackerTest> = SearchPage::new(None, MockMangadexClient::new(), vec![]);
+
+        let manga_with_status = |id: &str, status: &str| Data {
+            id: id.to_string(),
+            attributes: Attributes {
+                status: status.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        search_page.load_mangas_found(Ok(SearchMangaResponse {
+            data: vec![
+                manga_with_status("ongoing_manga", "ongoing"),
+                manga_with_status("completed_manga", "completed"),
+                manga_with_status("hiatus_manga", "hiatus"),
+            ],
+            total: 3,
+            ..Default::default()
+        }));
+
+        assert_eq!(3, search_page.mangas_found_list.widget.mangas.len());
+
+        press_key(&mut search_page, KeyCode::Tab);
+        let action = search_page.local_action_rx.blocking_recv().expect("no action was sent");
+        assert_eq!(SearchPageActions::NextStatusTab, action);
+        search_page.update(action);
+
+        assert_eq!(StatusTabs::Ongoing, search_page.mangas_found_list.status_tab);
+        assert_eq!("ongoing_manga", search_page.get_current_manga_selected().unwrap().manga.id);
+        assert_eq!(1, search_page.mangas_found_list.widget.mangas.len());
+
+        search_page.update(SearchPageActions::NextStatusTab);
+        search_page.update(SearchPageActions::NextStatusTab);
+
+        assert_eq!(StatusTabs::Hiatus, search_page.mangas_found_list.status_tab);
+        assert_eq!("hiatus_manga", search_page.get_current_manga_selected().unwrap().manga.id);
+
+        // no mangas match the tab, so nothing is selected
+        search_page.mangas_found_list.all_mangas.retain(|item| item.manga.status != "hiatus");
+        search_page.filter_mangas_by_status();
+
+        assert!(search_page.get_current_manga_selected().is_none());
+
+        search_page.update(SearchPageActions::NextStatusTab);
+
+        assert_eq!(StatusTabs::All, search_page.mangas_found_list.status_tab);
+        assert_eq!(2, search_page.mangas_found_list.widget.mangas.len());
+    }
+
+    #[test]
+    fn mangas_marked_as_not_interested_are_hidden_until_undone() {
+        let database = test_database();
+        let mut search_page: SearchPage<MockMangadexClient, TrackerTest> =
+            SearchPage::new(None, MockMangadexClient::new(), vec![]).with_database(database.clone());
+
+        let not_interested_id = uuid::Uuid::new_v4().to_string();
+
+        search_page.load_mangas_found(Ok(SearchMangaResponse {
+            data: vec![
+                Data {
+                    id: not_interested_id.clone(),
+                    ..Default::default()
+                },
+                Data {
+                    id: "other_manga".to_string(),
+                    ..Default::default()
+                },
+            ],
+            total: 2,
+            ..Default::default()
+        }));
+
+        press_key(&mut search_page, KeyCode::Char('x'));
+        let action = search_page.local_action_rx.blocking_recv().expect("no action was sent");
+        assert_eq!(SearchPageActions::NotInterested, action);
+
+        search_page.set_not_interested();
+        wait_for_queries(&mut search_page);
+
+        let is_not_interested = |id: &str| {
+            let id = id.to_string();
+            query_test_database(&database, move |conn| Database::new(conn).get_not_interested_mangas()).contains(&id)
+        };
+
+        assert!(is_not_interested(&not_interested_id));
+        assert_eq!("other_manga", search_page.get_current_manga_selected().unwrap().manga.id);
+        assert_eq!(1, search_page.mangas_found_list.widget.mangas.len());
+
+        press_key(&mut search_page, KeyCode::Char('z'));
+        let action = search_page.local_action_rx.blocking_recv().expect("no action was sent");
+        assert_eq!(SearchPageActions::UndoNotInterested, action);
+
+        search_page.undo_not_interested();
+        wait_for_queries(&mut search_page);
+
+        assert!(!is_not_interested(&not_interested_id));
+        assert_eq!(2, search_page.mangas_found_list.widget.mangas.len());
+        assert!(search_page.manga_not_interested.is_none());
+    }
+
+    #[test]
+    fn marked_mangas_are_added_to_plan_to_read_all_at_once() {
+        let database = test_database();
+        let mut search_page: SearchPage<MockMangadexClient, TrackerTest> =
+            SearchPage::new(None, MockMangadexClient::new(), vec![]).with_database(database.clone());
+
+        let ids: Vec<String> = (0..3).map(|_| uuid::Uuid::new_v4().to_string()).collect();
+
+        search_page.load_mangas_found(Ok(SearchMangaResponse {
+            data: ids
+                .iter()
+                .map(|id| Data {
+                    id: id.clone(),
+                    ..Default::default()
+                })
+                .collect(),
+            total: 3,
+            ..Default::default()
+        }));
+
+        press_key(&mut search_page, KeyCode::Char(' '));
+        let action = search_page.local_action_rx.blocking_recv().expect("no action was sent");
+        assert_eq!(SearchPageActions::ToggleMark, action);
+        search_page.update(action);
+
+        search_page.mangas_found_list.state.select(Some(2));
+        search_page.toggle_mark();
+
+        let marked: Vec<&str> = search_page
+            .mangas_found_list
+            .all_mangas
+            .iter()
+            .filter(|item| item.is_marked)
+            .map(|item| item.manga.id.as_str())
+            .collect();
+
+        assert_eq!(vec![ids[0].as_str(), ids[2].as_str()], marked);
+
+        search_page.plan_to_read();
+
+        assert!(search_page.mangas_found_list.all_mangas.iter().all(|item| !item.is_marked));
+
+        wait_for_queries(&mut search_page);
+
+        let saved: Vec<bool> = query_test_database(&database, move |conn| {
+            ids.iter()
+                .map(|id| conn.query_row("SELECT COUNT(*) FROM mangas WHERE id = ?1", [id], |row| row.get(0)))
+                .collect()
+        });
+
+        assert_eq!(vec![true, false, true], saved);
+        assert_eq!(Some("2 mangas".to_string()), search_page.manga_added_to_plan_to_read);
+    }
+
+    #[test]
+    fn manga_cover_is_set_as_failed_if_it_could_not_be_loaded() {
+        let mut search_page: SearchPage<MockMangadexClient, TrackerTest> =
+            SearchPage::new(Some(Picker::new((8, 9))), MockMangadexClient::new(), vec![]);
+
+        search_page
+            .local_event_tx
+            .send(SearchPageEvents::LoadCover(None, "manga_id".to_string()))
+            .ok();
+        search_page.tick();
+
+        assert!(search_page.manga_cover_state.has_failed("manga_id"));
+        assert!(search_page.manga_cover_state.get_image_state("manga_id").is_none());
+    }
+
     #[test]
     fn search_manga_cover_if_picker_is_some_after_mangas_were_found() {
         let mut search_page: SearchPage<MockMangadexClient, TrackerTest> =
-            SearchPage::new(Some(Picker::new((8, 9))), MockMangadexClient::new(), None);
+            SearchPage::new(Some(Picker::new((8, 9))), MockMangadexClient::new(), vec![]);
 
-        search_page.load_mangas_found(Some(SearchMangaResponse {
+        search_page.load_mangas_found(Ok(SearchMangaResponse {
             data: vec![Data::default()],
             ..Default::default()
         }));
@@ -834,9 +1362,9 @@ mod test {
 
     #[test]
     fn doesnt_search_cover_if_picker_is_none_after_mangas_were_found() {
-        let mut search_page: SearchPage<MockMangadexClient, TrackerTest> = SearchPage::new(None, MockMangadexClient::new(), None);
+        let mut search_page: SearchPage<MockMangadexClient, TrackerTest> = SearchPage::new(None, MockMangadexClient::new(), vec![]);
 
-        search_page.load_mangas_found(Some(SearchMangaResponse {
+        search_page.load_mangas_found(Ok(SearchMangaResponse {
             data: vec![Data::default()],
             ..Default::default()
         }));
@@ -0,0 +1,304 @@
+use chrono::{Datelike, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Paragraph, Widget};
+use ratatui::Frame;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::backend::database::{Database, ReadingSummary};
+use crate::backend::database_actor::DatabaseActor;
+use crate::backend::error_log::{write_to_error_log, ErrorType};
+use crate::backend::filter::Languages;
+use crate::backend::reading_report::export_report;
+use crate::backend::tui::Events;
+use crate::backend::AppDirectories;
+use crate::config::MangaTuiConfig;
+use crate::global::INSTRUCTIONS_STYLE;
+use crate::utils::display_day;
+use crate::view::widgets::Component;
+
+/// The rest do not fit in the panel
+const MAX_LANGUAGES_SHOWN: usize = 4;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum WrappedPageActions {
+    Reload,
+    ExportReport,
+    /// Also saves the heatmap as a png
+    ExportReportWithImage,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum WrappedPageEvents {
+    LoadSummary(ReadingSummary),
+}
+
+/// A summary of what was read and downloaded, built from the database every time the page is opened
+pub struct WrappedPage {
+    pub local_action_tx: UnboundedSender<WrappedPageActions>,
+    pub local_action_rx: UnboundedReceiver<WrappedPageActions>,
+    pub local_event_tx: UnboundedSender<WrappedPageEvents>,
+    pub local_event_rx: UnboundedReceiver<WrappedPageEvents>,
+    summary: Option<ReadingSummary>,
+    /// Where the report was exported to or why it failed
+    export_message: Option<String>,
+}
+
+impl WrappedPage {
+    pub fn new() -> Self {
+        let (local_action_tx, local_action_rx) = mpsc::unbounded_channel::<WrappedPageActions>();
+        let (local_event_tx, local_event_rx) = mpsc::unbounded_channel::<WrappedPageEvents>();
+        Self {
+            local_action_tx,
+            local_action_rx,
+            local_event_tx,
+            local_event_rx,
+            summary: None,
+            export_message: None,
+        }
+    }
+
+    pub fn load_summary(&mut self) {
+        DatabaseActor::global().query(
+            self.local_event_tx.clone(),
+            |conn| Ok(Database::new(conn).get_reading_summary(Utc::now().year())?),
+            WrappedPageEvents::LoadSummary,
+        );
+    }
+
+    fn tick(&mut self) {
+        if let Ok(WrappedPageEvents::LoadSummary(summary)) = self.local_event_rx.try_recv() {
+            self.summary = Some(summary);
+        }
+    }
+
+    /// The report is saved in the directory where manga-tui keeps its data
+    fn export_report(&mut self, with_image: bool) {
+        let Some(summary) = self.summary.as_ref() else {
+            return;
+        };
+
+        match export_report(summary, AppDirectories::get_app_directory(), with_image) {
+            Ok(path) => self.export_message = Some(format!("Report saved to {}", path.display())),
+            Err(e) => {
+                write_to_error_log(ErrorType::Error(e));
+                self.export_message = Some("Could not export the report".to_string());
+            },
+        }
+    }
+
+    fn handle_key_events(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('r') => {
+                self.local_action_tx.send(WrappedPageActions::Reload).ok();
+            },
+            KeyCode::Char('e') => {
+                self.local_action_tx.send(WrappedPageActions::ExportReport).ok();
+            },
+            KeyCode::Char('E') => {
+                self.local_action_tx.send(WrappedPageActions::ExportReportWithImage).ok();
+            },
+            _ => {},
+        }
+    }
+
+    fn render_panel(title: &str, lines: Vec<Line<'_>>, area: Rect, frame: &mut Frame<'_>) {
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::bordered().title(title.bold()))
+            .render(area, frame.buffer_mut());
+    }
+
+    fn languages_read(summary: &ReadingSummary) -> Vec<Line<'static>> {
+        if summary.chapters_read_by_language.is_empty() {
+            return vec![Line::from("Nothing read yet")];
+        }
+
+        summary
+            .chapters_read_by_language
+            .iter()
+            .take(MAX_LANGUAGES_SHOWN)
+            .map(|(code, chapters_read)| {
+                let language = Languages::try_from_iso_code(code).map_or(code.clone(), |language| language.as_human_readable());
+                Line::from(vec![language.bold().yellow(), format!(" {chapters_read} chapters").into()])
+            })
+            .collect()
+    }
+
+    fn render_most_read(summary: &ReadingSummary, area: Rect, frame: &mut Frame<'_>) {
+        let block = Block::bordered().title("Most read series".bold());
+
+        if summary.most_read.is_empty() {
+            Paragraph::new("Nothing read yet").block(block).render(area, frame.buffer_mut());
+            return;
+        }
+
+        let bars: Vec<Bar<'_>> = summary
+            .most_read
+            .iter()
+            .map(|(title, chapters_read)| {
+                Bar::default()
+                    .value(*chapters_read)
+                    .label(Line::from(title.as_str()))
+                    .text_value(format!("{chapters_read} chapters"))
+            })
+            .collect();
+
+        BarChart::default()
+            .block(block)
+            .direction(Direction::Horizontal)
+            .bar_width(1)
+            .bar_gap(1)
+            .bar_style(Style::new().cyan())
+            .data(BarGroup::default().bars(&bars))
+            .render(area, frame.buffer_mut());
+    }
+}
+
+pub fn format_reading_time(seconds: u64) -> String {
+    let minutes = seconds / 60;
+    if minutes >= 60 { format!("{}h {:02}m", minutes / 60, minutes % 60) } else { format!("{minutes}m") }
+}
+
+pub fn format_size(bytes: u64) -> String {
+    let megabytes = bytes as f64 / (1024.0 * 1024.0);
+    if megabytes >= 1024.0 { format!("{:.2} GB", megabytes / 1024.0) } else { format!("{megabytes:.2} MB") }
+}
+
+impl Component for WrappedPage {
+    type Actions = WrappedPageActions;
+
+    fn render(&mut self, area: Rect, frame: &mut Frame<'_>) {
+        let Some(summary) = self.summary.as_ref() else {
+            Block::bordered()
+                .title("Could not get your reading summary")
+                .render(area, frame.buffer_mut());
+            return;
+        };
+
+        let instructions = Line::from(vec![
+            "Reload ".into(),
+            Span::raw("<r>").style(*INSTRUCTIONS_STYLE),
+            " Export as markdown/with heatmap image ".into(),
+            Span::raw("<e>/<E>").style(*INSTRUCTIONS_STYLE),
+        ]);
+
+        let mut block = Block::bordered()
+            .title(format!("Your {} wrapped", summary.year).bold())
+            .title_bottom(instructions);
+
+        if let Some(message) = self.export_message.as_deref() {
+            block = block.title_bottom(Line::from(message).right_aligned());
+        }
+        let inner = block.inner(area);
+        block.render(area, frame.buffer_mut());
+
+        let [top_area, bottom_area] = Layout::vertical([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(inner);
+        let [read_area, languages_area, downloads_area, busiest_day_area] =
+            Layout::horizontal([Constraint::Fill(1); 4]).areas(top_area);
+
+        Self::render_panel(
+            "Read",
+            vec![
+                Line::from(summary.chapters_read.to_string().bold().yellow()),
+                Line::from("chapters"),
+                Line::from(summary.mangas_read.to_string().bold().yellow()),
+                Line::from("series"),
+                Line::from(format_reading_time(summary.seconds_read).bold().yellow()),
+                Line::from("in the reader"),
+            ],
+            read_area,
+            frame,
+        );
+
+        Self::render_panel("Read in", Self::languages_read(summary), languages_area, frame);
+
+        Self::render_panel(
+            &format!("Downloaded in {}", summary.year),
+            vec![
+                Line::from(summary.chapters_downloaded.to_string().bold().yellow()),
+                Line::from("chapters"),
+                Line::from(format_size(summary.bytes_downloaded).bold().yellow()),
+                Line::from("on disk"),
+            ],
+            downloads_area,
+            frame,
+        );
+
+        let busiest_day = match summary.busiest_download_day.as_ref() {
+            Some((day, total)) => {
+                let day = display_day(day, MangaTuiConfig::get().date_format.as_deref());
+                vec![Line::from(day.bold().yellow()), Line::from(format!("{total} chapters downloaded"))]
+            },
+            None => vec![Line::from("No downloads yet")],
+        };
+
+        Self::render_panel("Busiest download day", busiest_day, busiest_day_area, frame);
+
+        Self::render_most_read(summary, bottom_area, frame);
+    }
+
+    fn update(&mut self, action: Self::Actions) {
+        match action {
+            WrappedPageActions::Reload => self.load_summary(),
+            WrappedPageActions::ExportReport => self.export_report(false),
+            WrappedPageActions::ExportReportWithImage => self.export_report(true),
+        }
+    }
+
+    fn handle_events(&mut self, events: Events) {
+        match events {
+            Events::Key(key_event) => self.handle_key_events(key_event),
+            Events::Tick => self.tick(),
+            _ => {},
+        }
+    }
+
+    fn clean_up(&mut self) {
+        self.summary = None;
+        self.export_message = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::view::widgets::press_key;
+
+    #[test]
+    fn it_formats_the_size_downloaded() {
+        assert_eq!("0.50 MB", format_size(512 * 1024));
+        assert_eq!("1.50 GB", format_size(3 * 512 * 1024 * 1024));
+    }
+
+    #[test]
+    fn it_formats_the_time_spent_reading() {
+        assert_eq!("0m", format_reading_time(59));
+        assert_eq!("45m", format_reading_time(45 * 60));
+        assert_eq!("2h 05m", format_reading_time(2 * 3600 + 5 * 60 + 30));
+    }
+
+    #[test]
+    fn it_reloads_the_summary_with_r() {
+        let mut wrapped_page = WrappedPage::new();
+
+        press_key(&mut wrapped_page, KeyCode::Char('r'));
+
+        assert_eq!(WrappedPageActions::Reload, wrapped_page.local_action_rx.blocking_recv().unwrap());
+    }
+
+    #[test]
+    fn it_exports_the_report_with_or_without_the_heatmap_image() {
+        let mut wrapped_page = WrappedPage::new();
+
+        press_key(&mut wrapped_page, KeyCode::Char('e'));
+        press_key(&mut wrapped_page, KeyCode::Char('E'));
+
+        assert_eq!(WrappedPageActions::ExportReport, wrapped_page.local_action_rx.blocking_recv().unwrap());
+        assert_eq!(WrappedPageActions::ExportReportWithImage, wrapped_page.local_action_rx.blocking_recv().unwrap());
+    }
+}
@@ -0,0 +1,73 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::view::pages::SelectedPage;
+
+/// Shortcuts that do the same on every page, when one of them is pressed the key is not passed down to the current page
+/// so pages can't use these keys for something else
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalShortcut {
+    Quit,
+    GoHome,
+    GoSearch,
+    GoFeed,
+    GoWrapped,
+    GoLibrary,
+    GoLatestUpdates,
+    GoDebug,
+}
+
+impl GlobalShortcut {
+    /// Returns `None` if the key should be handled by the current page, while typing every key goes to the page
+    pub fn from_key(key_event: KeyEvent, current_page: SelectedPage, is_typing: bool) -> Option<Self> {
+        if is_typing {
+            return None;
+        }
+
+        if key_event.code == KeyCode::Char('c') && key_event.modifiers == KeyModifiers::CONTROL {
+            return Some(Self::Quit);
+        }
+
+        // The reader can only be left by going back to the manga page
+        if current_page == SelectedPage::ReaderTab {
+            return None;
+        }
+
+        match key_event.code {
+            KeyCode::Char('u') | KeyCode::F(1) => Some(Self::GoHome),
+            KeyCode::Char('i') | KeyCode::F(2) => Some(Self::GoSearch),
+            KeyCode::Char('o') | KeyCode::F(3) => Some(Self::GoFeed),
+            KeyCode::F(4) => Some(Self::GoWrapped),
+            KeyCode::F(5) => Some(Self::GoLibrary),
+            KeyCode::F(6) => Some(Self::GoLatestUpdates),
+            KeyCode::F(12) => Some(Self::GoDebug),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_maps_keys_to_the_same_shortcut_on_every_page() {
+        for page in [SelectedPage::Home, SelectedPage::MangaTab, SelectedPage::Search, SelectedPage::Feed] {
+            assert_eq!(Some(GlobalShortcut::GoHome), GlobalShortcut::from_key(KeyCode::Char('u').into(), page, false));
+            assert_eq!(Some(GlobalShortcut::GoFeed), GlobalShortcut::from_key(KeyCode::F(3).into(), page, false));
+            assert_eq!(None, GlobalShortcut::from_key(KeyCode::Char('j').into(), page, false));
+        }
+    }
+
+    #[test]
+    fn it_only_allows_quitting_while_reading_and_nothing_while_typing() {
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+
+        assert_eq!(Some(GlobalShortcut::Quit), GlobalShortcut::from_key(ctrl_c, SelectedPage::ReaderTab, false));
+        assert_eq!(None, GlobalShortcut::from_key(KeyCode::Char('u').into(), SelectedPage::ReaderTab, false));
+
+        assert_eq!(None, GlobalShortcut::from_key(ctrl_c, SelectedPage::Search, true));
+        assert_eq!(None, GlobalShortcut::from_key(KeyCode::Char('i').into(), SelectedPage::Search, true));
+    }
+}
@@ -1,8 +1,13 @@
+use std::time::Duration;
+
+use reqwest::StatusCode;
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::backend::api_responses::feed::OneMangaResponse;
 use crate::backend::error_log::{write_to_error_log, ErrorType};
-use crate::backend::fetch::ApiClient;
+use crate::backend::database::{MangaHistory, MangaHistoryResponse};
+use crate::backend::fetch::{ApiClient, MangadexClient};
+use crate::backend::rate_limit::{retry_after, MAX_RETRY_AFTER};
 use crate::backend::tui::Events;
 use crate::utils::from_manga_response;
 use crate::view::pages::feed::FeedEvents;
@@ -17,11 +22,19 @@ pub async fn search_manga<T: ApiClient>(
     let response = api_client.get_one_manga(&manga_id).await;
 
     match response {
-        Ok(res) => {
-            if let Ok(manga) = res.json::<OneMangaResponse>().await {
+        // Mangadex answers with not found for mangas that were removed or are no longer available due to licensing
+        Ok(res) if res.status() == StatusCode::NOT_FOUND => {
+            feed_page_sender.send(FeedEvents::MangaNotFound(manga_id)).ok();
+        },
+        Ok(res) => match res.json::<OneMangaResponse>().await {
+            Ok(manga) => {
                 let manga_found = from_manga_response(manga.data);
                 sender.send(Events::GoToMangaPage(MangaItem::new(manga_found))).ok();
-            }
+            },
+            Err(e) => {
+                write_to_error_log(ErrorType::Error(Box::new(e)));
+                feed_page_sender.send(FeedEvents::ErrorSearchingMangaData).ok();
+            },
         },
         Err(e) => {
             write_to_error_log(ErrorType::Error(Box::new(e)));
@@ -44,3 +57,82 @@ pub async fn search_latest_chapters<T: ApiClient>(api_client: T, manga_id: Strin
         },
     }
 }
+
+/// The follows need the account to be logged in, which is not part of `ApiClient`
+pub async fn search_mangadex_follows(page: u32, items_per_page: u32, sender: UnboundedSender<FeedEvents>) {
+    match MangadexClient::global().get_followed_mangas(page, items_per_page).await {
+        Ok(response) => {
+            let mangas = response
+                .data
+                .into_iter()
+                .map(|data| {
+                    let manga = from_manga_response(data);
+                    MangaHistory {
+                        id: manga.id,
+                        title: manga.title,
+                        category: None,
+                        personal_rating: None,
+                    }
+                })
+                .collect();
+
+            sender
+                .send(FeedEvents::LoadHistory(Some(MangaHistoryResponse {
+                    mangas,
+                    page,
+                    total_items: response.total,
+                })))
+                .ok();
+        },
+        Err(e) => {
+            write_to_error_log(ErrorType::Error(e));
+            sender.send(FeedEvents::LoadHistory(None)).ok();
+        },
+    }
+}
+
+/// Time waited between the requests of the library health check, so checking a big library does not hit the rate
+/// limit of mangadex
+pub static LIBRARY_HEALTH_CHECK_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LibraryHealth {
+    /// Mangadex responded with `404 Not Found`
+    pub dead_mangas: Vec<String>,
+    /// Could not tell if they still exist because the request failed or was rate limited
+    pub unknown_mangas: Vec<String>,
+}
+
+/// Request every manga of the library one at a time and collect the ones mangadex responds with `404 Not Found`, if
+/// mangadex keeps rate limiting the requests the time waited between them is doubled
+pub async fn check_library_health<T: ApiClient>(
+    api_client: T,
+    manga_ids: Vec<String>,
+    delay: Duration,
+    sender: UnboundedSender<FeedEvents>,
+) {
+    let mut health = LibraryHealth::default();
+    let mut delay = delay;
+
+    for (index, manga_id) in manga_ids.into_iter().enumerate() {
+        if index > 0 {
+            tokio::time::sleep(delay).await;
+        }
+
+        match api_client.get_one_manga(&manga_id).await {
+            Ok(res) if res.status() == StatusCode::NOT_FOUND => health.dead_mangas.push(manga_id),
+            Ok(res) if res.status() == StatusCode::TOO_MANY_REQUESTS => {
+                delay = (delay * 2).max(retry_after(res.headers())).min(MAX_RETRY_AFTER);
+                health.unknown_mangas.push(manga_id);
+            },
+            Ok(res) if !res.status().is_success() => health.unknown_mangas.push(manga_id),
+            Ok(_) => {},
+            Err(e) => {
+                write_to_error_log(ErrorType::Error(Box::new(e)));
+                health.unknown_mangas.push(manga_id);
+            },
+        }
+    }
+
+    sender.send(FeedEvents::LibraryHealthChecked(health)).ok();
+}
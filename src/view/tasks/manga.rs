@@ -3,20 +3,20 @@ use std::path::{Path, PathBuf};
 use std::thread::sleep;
 use std::time::{Duration, Instant};
 
+use manga_tui::ChapterNumber;
 use reqwest::Url;
 use tokio::sync::mpsc::UnboundedSender;
 
-use crate::backend::api_responses::{AggregateChapterResponse, ChapterPagesResponse, ChapterResponse};
-use crate::backend::database::{save_history, ChapterToSaveHistory, Database, MangaReadingHistorySave};
-use crate::backend::download::DownloadChapter;
+use crate::backend::api_responses::{AggregateChapterResponse, ChapterData, ChapterPagesResponse, ChapterResponse};
+use crate::backend::database::{save_history, ChapterDownloaded, ChapterToSaveHistory, Database, MangaReadingHistorySave};
+use crate::backend::database_actor::DatabaseActor;
+use crate::backend::download::{download_size, finish_download, partial_download_path, DownloadChapter};
 use crate::backend::error_log::{write_to_error_log, ErrorType};
 #[cfg(test)]
 use crate::backend::fetch::fake_api_client::MockMangadexClient;
-use crate::backend::fetch::ApiClient;
-#[cfg(not(test))]
-use crate::backend::fetch::MangadexClient;
+use crate::backend::fetch::{ApiClient, MangadexClient};
 use crate::backend::filter::Languages;
-use crate::config::{DownloadType, ImageQuality, MangaTuiConfig};
+use crate::config::{DownloadType, ImageQuality, MangaTuiConfig, MangadexUrls};
 use crate::view::app::MangaToRead;
 use crate::view::pages::manga::{ChapterOrder, MangaPageEvents};
 use crate::view::pages::reader::{ChapterToRead, ListOfChapters};
@@ -26,6 +26,7 @@ pub async fn search_chapters_operation(
     page: u32,
     language: Languages,
     chapter_order: ChapterOrder,
+    excluded_groups: Vec<String>,
     tx: UnboundedSender<MangaPageEvents>,
 ) {
     #[cfg(test)]
@@ -34,7 +35,9 @@ pub async fn search_chapters_operation(
     #[cfg(not(test))]
     let api_client = MangadexClient::global();
 
-    let response = api_client.get_manga_chapters(&manga_id, page, language, chapter_order).await;
+    let response = api_client
+        .get_manga_chapters(&manga_id, page, language, chapter_order, &excluded_groups)
+        .await;
 
     match response {
         Ok(chapters_response) => {
@@ -94,14 +97,12 @@ async fn download_chapter_raw_images(
             .parse()
             .unwrap_or("http://localhost".parse().unwrap());
 
-        if let Ok(response) = api_client.get_chapter_page(endpoint).await {
-            if let Ok(bytes) = response.bytes().await {
-                data.chapter_to_download.create_image_file(
-                    &bytes,
-                    &chapter_directory,
-                    format!("{}.{}", index + 1, extension).into(),
-                )?;
-            }
+        if let Ok(bytes) = api_client.get_chapter_page(endpoint).await {
+            data.chapter_to_download.create_image_file(
+                &bytes,
+                &partial_download_path(&chapter_directory),
+                format!("{}.{}", index + 1, extension).into(),
+            )?;
         }
         if data.should_report_progress {
             data.sender_report_download_progress
@@ -110,6 +111,8 @@ async fn download_chapter_raw_images(
         }
     }
 
+    finish_download(&chapter_directory)?;
+
     Ok(chapter_directory)
 }
 
@@ -128,11 +131,9 @@ async fn download_chapter_cbz(
             .parse()
             .unwrap_or("http://localhost".parse().unwrap());
 
-        if let Ok(response) = api_client.get_chapter_page(endpoint).await {
-            if let Ok(bytes) = response.bytes().await {
-                let file_name = format!("{}.{}", index + 1, extension);
-                data.chapter_to_download.insert_into_cbz(&mut zip_writer, &file_name, &bytes);
-            }
+        if let Ok(bytes) = api_client.get_chapter_page(endpoint).await {
+            let file_name = format!("{}.{}", index + 1, extension);
+            data.chapter_to_download.insert_into_cbz(&mut zip_writer, &file_name, &bytes);
         }
 
         if data.should_report_progress {
@@ -142,7 +143,9 @@ async fn download_chapter_cbz(
         }
     }
 
-    zip_writer.finish()?;
+    zip_writer.finish()?.sync_all()?;
+
+    finish_download(&cbz_path)?;
 
     Ok(cbz_path)
 }
@@ -162,12 +165,10 @@ async fn download_chapter_epub(
             .parse()
             .unwrap_or("http://localhost".parse().unwrap());
 
-        if let Ok(response) = api_client.get_chapter_page(endpoint).await {
-            if let Ok(bytes) = response.bytes().await {
-                let file_name = format!("{}.{}", index + 1, extension);
-                data.chapter_to_download
-                    .insert_into_epub(&mut epub_builder, &file_name, extension, index, &bytes);
-            }
+        if let Ok(bytes) = api_client.get_chapter_page(endpoint).await {
+            let file_name = format!("{}.{}", index + 1, extension);
+            data.chapter_to_download
+                .insert_into_epub(&mut epub_builder, &file_name, extension, index, &bytes);
         }
 
         if data.should_report_progress {
@@ -178,6 +179,10 @@ async fn download_chapter_epub(
     }
 
     epub_builder.generate(&mut epub_file)?;
+    epub_file.sync_all()?;
+    drop(epub_file);
+
+    finish_download(&epub_path)?;
 
     Ok(epub_path)
 }
@@ -195,7 +200,14 @@ pub async fn download_chapter_task(
 ) -> Result<PathBuf, Box<dyn Error>> {
     let manga_base_directory = chapter_to_download.make_base_manga_directory(&directory_to_download)?;
 
-    let pages_response: ChapterPagesResponse = api_client.get_chapter_pages(&chapter_id).await?.json().await?;
+    let mut pages_response: ChapterPagesResponse = api_client.get_chapter_pages(&chapter_id).await?.json().await?;
+    if let Ok(MangadexUrls {
+        images: Some(images_url),
+        ..
+    }) = MangaTuiConfig::get().mangadex_urls()
+    {
+        pages_response.set_base_url(&images_url);
+    }
 
     let image_endpoint = pages_response.get_image_url_endpoint(image_quality);
 
@@ -252,6 +264,20 @@ pub async fn download_chapter_task(
     Ok(file_created)
 }
 
+/// What is saved in the database once a chapter finished downloading
+pub fn chapter_downloaded(path: PathBuf, format: DownloadType) -> ChapterDownloaded {
+    let bytes = download_size(&path).unwrap_or_else(|e| {
+        write_to_error_log(ErrorType::Error(Box::new(e)));
+        0
+    });
+
+    ChapterDownloaded {
+        path,
+        format,
+        bytes,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DownloadAllChapters {
     pub sender: UnboundedSender<MangaPageEvents>,
@@ -263,17 +289,48 @@ pub struct DownloadAllChapters {
     pub language: Languages,
 }
 
+/// The chapters that downloading all chapters would download, `chapters_to_skip` are the ids of the chapters which
+/// were already downloaded, used when resuming a download
+pub async fn fetch_chapters_to_download(
+    api_client: &impl ApiClient,
+    manga_id: &str,
+    language: Languages,
+    chapters_to_skip: &[String],
+) -> Result<Vec<ChapterData>, Box<dyn Error>> {
+    let all_chapters_response: ChapterResponse = api_client.get_all_chapters_for_manga(manga_id, language).await?.json().await?;
+
+    Ok(all_chapters_response
+        .data
+        .into_iter()
+        .filter(|chapter| !chapters_to_skip.contains(&chapter.id))
+        // Chapters hosted by the publisher have no pages to download
+        .filter(|chapter| chapter.attributes.external_url.is_none())
+        .collect())
+}
+
+/// How a chapter is listed before downloading all of them, so the user can check what will be downloaded
+pub fn describe_chapter_to_download(chapter: &ChapterData) -> String {
+    let number = chapter.attributes.chapter.as_deref().unwrap_or("?");
+    let scanlator = chapter
+        .relationships
+        .iter()
+        .find(|rel| rel.type_field == "scanlation_group")
+        .and_then(|rel| rel.attributes.as_ref())
+        .map(|attributes| format!(" | {}", attributes.name))
+        .unwrap_or_default();
+
+    match chapter.attributes.title.as_deref().filter(|title| !title.is_empty()) {
+        Some(title) => format!("Ch. {number} {title}{scanlator}"),
+        None => format!("Ch. {number}{scanlator}"),
+    }
+}
+
 pub async fn download_all_chapters(
     api_client: impl ApiClient + 'static,
     download_data: DownloadAllChapters,
+    chapters: Vec<ChapterData>,
 ) -> Result<(), Box<dyn Error>> {
-    let all_chapters_response: ChapterResponse = api_client
-        .get_all_chapters_for_manga(&download_data.manga_id, download_data.language)
-        .await?
-        .json()
-        .await?;
-
-    let total_chapters = all_chapters_response.data.len();
+    let total_chapters = chapters.len();
 
     download_data
         .sender
@@ -290,7 +347,7 @@ pub async fn download_all_chapters(
         8
     };
 
-    for chapter in all_chapters_response.data {
+    for chapter in chapters {
         let scanlator = chapter
             .relationships
             .iter()
@@ -328,16 +385,21 @@ pub async fn download_all_chapters(
             )
             .await;
 
-            if let Err(e) = download_proccess {
-                write_to_error_log(ErrorType::Error(e));
-            }
-
             download_data.sender.send(MangaPageEvents::SetDownloadAllChaptersProgress).ok();
 
-            download_data
-                .sender
-                .send(MangaPageEvents::SaveChapterDownloadStatus(chapter.id, chapter_title))
-                .ok();
+            match download_proccess {
+                Ok(path) => {
+                    download_data
+                        .sender
+                        .send(MangaPageEvents::SaveChapterDownloadStatus(
+                            chapter.id,
+                            chapter_title,
+                            chapter_downloaded(path, download_data.file_format),
+                        ))
+                        .ok();
+                },
+                Err(e) => write_to_error_log(ErrorType::Error(e)),
+            }
         });
 
         let time_since = start_fetch_time.elapsed();
@@ -347,6 +409,30 @@ pub async fn download_all_chapters(
     Ok(())
 }
 
+/// The ids of every chapter numbered up to `chapter_number` in the whole list of chapters, not only the page loaded,
+/// including the ones uploaded by other scanlation groups
+pub async fn fetch_chapters_up_to(
+    api_client: &MangadexClient,
+    manga_id: &str,
+    language: Languages,
+    chapter_number: ChapterNumber,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let aggregate_res: AggregateChapterResponse = api_client.search_chapters_aggregate(manga_id, language).await?.json().await?;
+
+    Ok(chapters_up_to(aggregate_res, chapter_number))
+}
+
+fn chapters_up_to(aggregate_res: AggregateChapterResponse, chapter_number: ChapterNumber) -> Vec<String> {
+    aggregate_res
+        .volumes
+        .into_values()
+        .flat_map(|volume| volume.chapters.into_values())
+        .filter(|chapter| ChapterNumber::parse(&chapter.chapter) <= chapter_number)
+        .flat_map(|chapter| std::iter::once(chapter.id).chain(chapter.others))
+        .collect()
+}
+
+#[derive(Debug, Clone)]
 pub struct ChapterArgs {
     pub id_chapter: String,
     pub manga_id: String,
@@ -360,34 +446,41 @@ pub struct ChapterArgs {
 
 /// These function looks very similar  to the implementation `impl FetchChapterBookmarked for MangadexClient` but where it is called
 /// provides with data that reduce one api call
-pub async fn read_chapter(chapter: &ChapterArgs) -> Result<(ChapterToRead, MangaToRead), Box<dyn std::error::Error>> {
-    use crate::backend::fetch::MangadexClient;
+pub async fn read_chapter(
+    api_client: &MangadexClient,
+    chapter: &ChapterArgs,
+) -> Result<(ChapterToRead, MangaToRead), Box<dyn std::error::Error>> {
+    let pages_url = api_client.get_chapter_pages_url(&chapter.id_chapter).await?;
 
-    let chapter_response: ChapterPagesResponse =
-        MangadexClient::global().get_chapter_pages(&chapter.id_chapter).await?.json().await?;
-
-    let aggregate_res: AggregateChapterResponse = MangadexClient::global()
+    let aggregate_res: AggregateChapterResponse = api_client
         .search_chapters_aggregate(&chapter.manga_id, chapter.language)
         .await?
         .json()
         .await?;
 
-    let connection = Database::get_connection()?;
-    save_history(
-        MangaReadingHistorySave {
-            id: &chapter.manga_id,
-            title: &chapter.title,
-            img_url: chapter.img_url.as_deref(),
-            chapter: ChapterToSaveHistory {
-                id: &chapter.id_chapter,
-                title: &chapter.chapter_title,
-                translated_language: chapter.language.as_iso_code(),
-            },
-        },
-        &connection,
-    )?;
+    let chapter_to_save = chapter.clone();
+    let page_to_resume = DatabaseActor::global()
+        .run(move |conn| {
+            let chapter = chapter_to_save;
+            save_history(
+                MangaReadingHistorySave {
+                    id: &chapter.manga_id,
+                    title: &chapter.title,
+                    img_url: chapter.img_url.as_deref(),
+                    chapter: ChapterToSaveHistory {
+                        id: &chapter.id_chapter,
+                        title: &chapter.chapter_title,
+                        translated_language: chapter.language.as_iso_code(),
+                    },
+                },
+                conn,
+            )?;
 
-    let config = MangaTuiConfig::get();
+            Database::new(conn).get_chapter_progress(&chapter.id_chapter)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .and_then(|progress| progress.page_to_resume());
 
     let chapter_to_read: ChapterToRead = ChapterToRead {
         id: chapter.id_chapter.clone(),
@@ -396,7 +489,8 @@ pub async fn read_chapter(chapter: &ChapterArgs) -> Result<(ChapterToRead, Manga
         volume_number: chapter.volume_number.clone(),
         language: chapter.language,
         num_page_bookmarked: None,
-        pages_url: chapter_response.get_files_based_on_quality_as_url(config.image_quality),
+        page_to_resume,
+        pages_url,
     };
 
     let manga_to_read: MangaToRead = MangaToRead {
@@ -408,6 +502,21 @@ pub async fn read_chapter(chapter: &ChapterArgs) -> Result<(ChapterToRead, Manga
     Ok((chapter_to_read, manga_to_read))
 }
 
+/// Mark the chapters as read on the mangadex account too, does nothing if the user is not logged in
+pub fn sync_chapters_read_mangadex(manga_id: String, chapter_ids: Vec<String>) {
+    use crate::backend::fetch::MANGADEX_CLIENT_INSTANCE;
+
+    let Some(mangadex_client) = MANGADEX_CLIENT_INSTANCE.get().filter(|client| client.is_logged_in()) else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        if let Err(e) = mangadex_client.mark_chapters_as_read(&manga_id, &chapter_ids).await {
+            write_to_error_log(ErrorType::Error(e));
+        }
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -422,7 +531,7 @@ mod tests {
     use uuid::Uuid;
 
     use super::*;
-    use crate::backend::api_responses::{ChapterAttribute, ChapterData};
+    use crate::backend::api_responses::{ChapterAttribute, ChapterData, ChapterRelationshipAttribute, Chapters, Relationship, Volumes};
     use crate::backend::fetch::fake_api_client::MockMangadexClient;
 
     async fn validate_progress_sent(
@@ -449,7 +558,7 @@ mod tests {
             let event = rx.recv().await.expect("no event was sent");
             match event {
                 MangaPageEvents::SetDownloadAllChaptersProgress => {},
-                MangaPageEvents::SaveChapterDownloadStatus(_, _) => {},
+                MangaPageEvents::SaveChapterDownloadStatus(..) => {},
                 _ => panic!("wrong event was sent"),
             }
         }
@@ -591,15 +700,21 @@ mod tests {
         let file_format = DownloadType::Cbz;
         let image_quality = ImageQuality::Low;
 
-        download_all_chapters(api_client, DownloadAllChapters {
-            sender,
-            manga_id,
-            manga_title,
-            image_quality,
-            directory_to_download: directory_to_download.clone(),
-            file_format,
-            language,
-        })
+        let chapters = fetch_chapters_to_download(&api_client, &manga_id, language, &[]).await?;
+
+        download_all_chapters(
+            api_client,
+            DownloadAllChapters {
+                sender,
+                manga_id,
+                manga_title,
+                image_quality,
+                directory_to_download: directory_to_download.clone(),
+                file_format,
+                language,
+            },
+            chapters,
+        )
         .await?;
 
         let expected_event = rx.recv().await.expect("no event was sent");
@@ -610,4 +725,84 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn only_chapters_that_can_be_downloaded_are_listed_before_downloading_all() -> Result<(), Box<dyn std::error::Error>> {
+        let chapter = |id: &str, external_url: Option<&str>| ChapterData {
+            id: id.to_string(),
+            attributes: ChapterAttribute {
+                chapter: Some(id.to_string()),
+                external_url: external_url.map(String::from),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let api_client = MockMangadexClient::new().with_chapter_response(ChapterResponse {
+            data: vec![chapter("1", None), chapter("2", None), chapter("3", Some("https://publisher.com")), chapter("4", None)],
+            ..Default::default()
+        });
+
+        let chapters = fetch_chapters_to_download(&api_client, "manga_id", Languages::default(), &["2".to_string()]).await?;
+
+        assert_eq!(vec!["1", "4"], chapters.iter().map(|chapter| chapter.id.as_str()).collect::<Vec<_>>());
+
+        Ok(())
+    }
+
+    #[test]
+    fn chapters_to_download_are_described_by_number_title_and_scanlator() {
+        let mut chapter = ChapterData {
+            attributes: ChapterAttribute {
+                chapter: Some("12".to_string()),
+                title: Some("The end".to_string()),
+                ..Default::default()
+            },
+            relationships: vec![Relationship {
+                type_field: "scanlation_group".to_string(),
+                attributes: Some(ChapterRelationshipAttribute {
+                    name: "some group".to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!("Ch. 12 The end | some group", describe_chapter_to_download(&chapter));
+
+        chapter.attributes.title = None;
+        chapter.relationships.clear();
+
+        assert_eq!("Ch. 12", describe_chapter_to_download(&chapter));
+    }
+
+    #[test]
+    fn chapters_up_to_a_number_are_taken_from_every_volume_and_scanlation_group() {
+        let chapter = |number: &str, id: &str, others: &[&str]| Chapters {
+            chapter: number.to_string(),
+            id: id.to_string(),
+            others: others.iter().map(|other| other.to_string()).collect(),
+            ..Default::default()
+        };
+
+        let volume = |chapters: Vec<Chapters>| Volumes {
+            chapters: chapters.into_iter().map(|chapter| (chapter.chapter.clone(), chapter)).collect(),
+            ..Default::default()
+        };
+
+        let aggregate_res = AggregateChapterResponse {
+            volumes: [
+                ("1".to_string(), volume(vec![chapter("1", "first", &["first_other_group"]), chapter("2", "second", &[])])),
+                ("2".to_string(), volume(vec![chapter("2.5", "extra", &[]), chapter("3", "third", &[])])),
+            ]
+            .into(),
+            ..Default::default()
+        };
+
+        let mut chapter_ids = chapters_up_to(aggregate_res, ChapterNumber::parse("2.5"));
+        chapter_ids.sort();
+
+        assert_eq!(vec!["extra", "first", "first_other_group", "second"], chapter_ids);
+    }
 }
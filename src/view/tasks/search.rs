@@ -1,12 +1,29 @@
+use std::error::Error;
+
 use manga_tui::SearchTerm;
+use reqwest::Response;
 use tokio::sync::mpsc::UnboundedSender;
 
+use crate::backend::api_responses::feed::OneMangaResponse;
+use crate::backend::api_responses::SearchMangaResponse;
+use crate::backend::database::Database;
+use crate::backend::database_actor::DatabaseActor;
 use crate::backend::error_log::{write_to_error_log, ErrorType};
 use crate::backend::fetch::ApiClient;
 use crate::backend::filter::Filters;
-use crate::utils::decode_bytes_to_image;
+use crate::backend::provider_error::ProviderErrorKind;
+use crate::common::Manga;
+use crate::utils::{decode_bytes_to_image, from_manga_response};
 use crate::view::pages::search::SearchPageEvents;
 
+/// The mangas marked as "not interested", if they can't be retrieved nothing is left out
+pub async fn not_interested_mangas() -> Vec<String> {
+    DatabaseActor::global()
+        .run(|conn| Database::new(conn).get_not_interested_mangas())
+        .await
+        .unwrap_or_default()
+}
+
 /// This function searchs for mangas and send a `SearchPageEvents::LoadMangasFound` event
 pub async fn search_mangas_operation(
     api_client: impl ApiClient,
@@ -15,16 +32,25 @@ pub async fn search_mangas_operation(
     filters: Filters,
     tx: UnboundedSender<SearchPageEvents>,
 ) {
-    let search_response = api_client.search_mangas(search_by_manga_title, page, filters).await;
-    match search_response {
-        Ok(mangas_found) => {
-            if let Ok(data) = mangas_found.json().await {
-                tx.send(SearchPageEvents::LoadMangasFound(Some(data))).ok();
-            }
+    let search_response = api_client
+        .search_mangas(search_by_manga_title, page, filters)
+        .await
+        .and_then(Response::error_for_status);
+
+    let mangas_found = match search_response {
+        Ok(response) => response.json::<SearchMangaResponse>().await,
+        Err(e) => Err(e),
+    };
+
+    match mangas_found {
+        Ok(data) => {
+            tx.send(SearchPageEvents::LoadMangasFound(Ok(data.without_mangas(&not_interested_mangas().await))))
+                .ok();
         },
         Err(e) => {
+            let kind = ProviderErrorKind::classify(&e);
             write_to_error_log(ErrorType::Error(Box::new(e)));
-            tx.send(SearchPageEvents::LoadMangasFound(None)).ok();
+            tx.send(SearchPageEvents::LoadMangasFound(Err(kind))).ok();
         },
     }
 }
@@ -36,12 +62,26 @@ pub async fn search_manga_covers(
     tx: UnboundedSender<SearchPageEvents>,
 ) {
     let search_cover_response = api_client.get_cover_for_manga_lower_quality(&manga_id, &file_name).await;
-    if let Ok(response) = search_cover_response {
-        if let Ok(bytes) = response.bytes().await {
-            let decoding_operation = decode_bytes_to_image(bytes);
-            tx.send(SearchPageEvents::LoadCover(decoding_operation.ok(), manga_id)).ok();
-        }
+
+    let cover = match search_cover_response {
+        Ok(response) => response.bytes().await.ok().and_then(|bytes| decode_bytes_to_image(bytes).ok()),
+        Err(_) => None,
+    };
+
+    tx.send(SearchPageEvents::LoadCover(cover, manga_id)).ok();
+}
+
+/// Used to open a manga by its url or id without searching it first
+pub async fn search_manga_by_id(api_client: impl ApiClient, manga_id: &str) -> Result<Manga, Box<dyn Error>> {
+    let response = api_client.get_one_manga(manga_id).await?;
+
+    if !response.status().is_success() {
+        return Err(format!("could not find the manga with id {manga_id}, status: {}", response.status()).into());
     }
+
+    let manga: OneMangaResponse = response.json().await?;
+
+    Ok(from_manga_response(manga.data))
 }
 
 #[cfg(test)]
@@ -63,7 +103,18 @@ mod test {
 
         let event = rx.recv().await.expect("LoadMangasFound event not sent");
 
-        assert_eq!(SearchPageEvents::LoadMangasFound(Some(expected)), event);
+        assert_eq!(SearchPageEvents::LoadMangasFound(Ok(expected)), event);
+    }
+
+    #[tokio::test]
+    async fn search_manga_by_id_fails_if_manga_could_not_be_found() {
+        let manga = search_manga_by_id(MockMangadexClient::new(), "manga_id").await;
+
+        assert!(manga.is_ok());
+
+        let manga = search_manga_by_id(MockMangadexClient::new().with_returning_errors(), "manga_id").await;
+
+        assert!(manga.is_err());
     }
 
     #[tokio::test]
@@ -1,11 +1,18 @@
+use std::rc::Rc;
+
 #[cfg(test)]
 use crossterm::event::KeyCode;
 use image::DynamicImage;
+use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
+use ratatui::widgets::StatefulWidget;
 use ratatui::Frame;
+use tui_widget_list::{ListBuilder, ListState, ListView, PreRender, PreRenderContext};
 
 use crate::backend::tui::Events;
 
+pub mod confirmation;
+pub mod cover;
 pub mod feed;
 pub mod filter_widget;
 pub mod home;
@@ -34,6 +41,35 @@ pub trait ImageHandler: Send + 'static {
     fn not_found(id: String) -> Self;
 }
 
+/// Only the items visible in `area` are built, the rest are not cloned on every frame which keeps long lists like
+/// the chapters of a long series or the pages of a chapter smooth
+pub fn render_virtual_list<T>(items: &mut Vec<T>, area: Rect, buf: &mut Buffer, state: &mut ListState)
+where
+    T: PreRender + Clone + 'static,
+{
+    let shared_items = Rc::new(std::mem::take(items));
+    let item_count = shared_items.len();
+
+    let visible_items = Rc::clone(&shared_items);
+    let builder = ListBuilder::new(move |context| {
+        let mut item = visible_items[context.index].clone();
+
+        let main_axis_size = item.pre_render(&PreRenderContext {
+            is_selected: context.is_selected,
+            cross_axis_size: context.cross_axis_size,
+            scroll_axis: context.scroll_axis,
+            index: context.index,
+        });
+
+        (item, main_axis_size)
+    });
+
+    StatefulWidget::render(ListView::new(builder, item_count), area, buf, state);
+
+    // The builder is dropped after rendering so the items are given back without cloning them
+    *items = Rc::try_unwrap(shared_items).unwrap_or_else(|shared_items| shared_items.as_ref().clone());
+}
+
 #[cfg(test)]
 // Use in testing
 pub fn press_key<T>(page: &mut dyn Component<Actions = T>, key: KeyCode) {
@@ -0,0 +1,103 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListState, Paragraph, StatefulWidget, Widget};
+
+use crate::global::INSTRUCTIONS_STYLE;
+
+/// Everything a bulk operation is about to change, it is listed before running it so the user knows exactly what will
+/// happen
+#[derive(Debug, Default, Clone)]
+pub struct ConfirmationState {
+    pub items: Vec<String>,
+    pub list_state: ListState,
+}
+
+impl ConfirmationState {
+    pub fn new(items: Vec<String>) -> Self {
+        let mut list_state = ListState::default();
+        if !items.is_empty() {
+            list_state.select_first();
+        }
+
+        Self { items, list_state }
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.list_state.select_next();
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.list_state.select_previous();
+    }
+}
+
+/// Asks the user to confirm a bulk operation, used before anything is changed
+pub struct ConfirmationScreen<'a> {
+    /// What the operation will do, for example "These chapters will be downloaded"
+    description: &'a str,
+}
+
+impl<'a> ConfirmationScreen<'a> {
+    pub fn new(description: &'a str) -> Self {
+        Self { description }
+    }
+}
+
+impl StatefulWidget for ConfirmationScreen<'_> {
+    type State = ConfirmationState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let instructions = Line::from(vec![
+            "Proceed".into(),
+            Span::raw(" <Enter> ").style(*INSTRUCTIONS_STYLE),
+            "Cancel".into(),
+            Span::raw(" <Esc> ").style(*INSTRUCTIONS_STYLE),
+            "Up/Down".into(),
+            Span::raw(" <k><j> ").style(*INSTRUCTIONS_STYLE),
+        ]);
+
+        let block = Block::bordered()
+            .title(format!("{} ({})", self.description, state.items.len()).bold())
+            .title_bottom(instructions);
+
+        if state.items.is_empty() {
+            Paragraph::new("Nothing would be changed").block(block).render(area, buf);
+            return;
+        }
+
+        let items = List::new(state.items.iter().map(String::as_str))
+            .block(block)
+            .highlight_style(Style::default().on_blue());
+
+        StatefulWidget::render(items, area, buf, &mut state.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_lists_everything_that_would_be_changed() {
+        let mut state = ConfirmationState::new(vec!["Ch. 1".to_string(), "Ch. 2".to_string()]);
+        let area = Rect::new(0, 0, 60, 6);
+        let mut buf = Buffer::empty(area);
+
+        ConfirmationScreen::new("These chapters will be downloaded").render(area, &mut buf, &mut state);
+
+        let rendered: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+
+        assert!(rendered.contains("These chapters will be downloaded (2)"));
+        assert!(rendered.contains("Ch. 1"));
+        assert!(rendered.contains("Ch. 2"));
+        assert_eq!(Some(0), state.list_state.selected());
+
+        state.scroll_down();
+
+        assert_eq!(Some(1), state.list_state.selected());
+    }
+}
@@ -0,0 +1,218 @@
+use image::DynamicImage;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, StatefulWidget, Widget};
+use ratatui_image::picker::Picker;
+use ratatui_image::protocol::Protocol;
+use ratatui_image::{Image, Resize};
+
+use crate::global::INSTRUCTIONS_STYLE;
+
+const PLACEHOLDER_COLORS: [Color; 6] = [Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan];
+
+/// Rendered instead of the cover when the manga has none or it could not be loaded
+pub struct CoverPlaceholder<'a> {
+    title: &'a str,
+}
+
+impl<'a> CoverPlaceholder<'a> {
+    pub fn new(title: &'a str) -> Self {
+        Self { title }
+    }
+
+    /// The first letter of the first two words of the title
+    pub fn initials(&self) -> String {
+        self.title
+            .split_whitespace()
+            .filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+            .take(2)
+            .flat_map(char::to_uppercase)
+            .collect()
+    }
+
+    /// The same manga always gets the same color
+    pub fn color(&self) -> Color {
+        let sum: usize = self.title.bytes().map(usize::from).sum();
+        PLACEHOLDER_COLORS[sum % PLACEHOLDER_COLORS.len()]
+    }
+}
+
+impl Widget for CoverPlaceholder<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Block::bordered().style(Style::default().bg(self.color())).render(area, buf);
+
+        let [initials_area] = Layout::vertical([Constraint::Length(1)]).flex(Flex::Center).areas(area);
+
+        Paragraph::new(self.initials().bold().black()).centered().render(initials_area, buf);
+    }
+}
+
+/// Each time the user pans the cover moves this percentage of what is visible
+const PAN_STEP_PERCENTAGE: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// The cover shown full-screen, if it is bigger than the terminal only a part of it is visible and the user can pan around
+#[derive(Default)]
+pub struct CoverViewerState {
+    /// `None` while the cover is being fetched
+    cover: Option<DynamicImage>,
+    /// Top left corner of the visible part of the cover, in pixels
+    offset: (u32, u32),
+    /// Size in pixels of the visible part of the cover, known once it is rendered
+    viewport: (u32, u32),
+    protocol: Option<Box<dyn Protocol>>,
+    /// The protocol is made again only if the area or the offset changed since it was made
+    rendered_with: Option<(Rect, (u32, u32))>,
+}
+
+impl CoverViewerState {
+    pub fn set_cover(&mut self, cover: DynamicImage) {
+        *self = Self {
+            cover: Some(cover),
+            ..Default::default()
+        };
+    }
+
+    pub fn offset(&self) -> (u32, u32) {
+        self.offset
+    }
+
+    fn max_offset(&self) -> (u32, u32) {
+        self.cover
+            .as_ref()
+            .map_or((0, 0), |cover| (cover.width().saturating_sub(self.viewport.0), cover.height().saturating_sub(self.viewport.1)))
+    }
+
+    pub fn pan(&mut self, direction: PanDirection) {
+        let step_x = (self.viewport.0 * PAN_STEP_PERCENTAGE / 100).max(1);
+        let step_y = (self.viewport.1 * PAN_STEP_PERCENTAGE / 100).max(1);
+        let (max_x, max_y) = self.max_offset();
+
+        let (x, y) = self.offset;
+        self.offset = match direction {
+            PanDirection::Up => (x, y.saturating_sub(step_y)),
+            PanDirection::Down => (x, (y + step_y).min(max_y)),
+            PanDirection::Left => (x.saturating_sub(step_x), y),
+            PanDirection::Right => ((x + step_x).min(max_x), y),
+        };
+    }
+
+    fn update_protocol(&mut self, area: Rect, picker: &mut Picker) {
+        let Some(cover) = self.cover.as_ref() else {
+            return;
+        };
+
+        let (font_width, font_height) = picker.font_size;
+        self.viewport = (
+            (u32::from(area.width) * u32::from(font_width)).min(cover.width()),
+            (u32::from(area.height) * u32::from(font_height)).min(cover.height()),
+        );
+
+        // the terminal may have been resized so the offset could now leave empty space
+        let (max_x, max_y) = self.max_offset();
+        self.offset = (self.offset.0.min(max_x), self.offset.1.min(max_y));
+
+        if self.rendered_with == Some((area, self.offset)) {
+            return;
+        }
+
+        let visible_part = cover.crop_imm(self.offset.0, self.offset.1, self.viewport.0, self.viewport.1);
+
+        self.protocol = picker.new_protocol(visible_part, area, Resize::Fit(None)).ok();
+        self.rendered_with = Some((area, self.offset));
+    }
+}
+
+pub struct CoverViewer<'a> {
+    title: &'a str,
+    picker: &'a mut Picker,
+}
+
+impl<'a> CoverViewer<'a> {
+    pub fn new(title: &'a str, picker: &'a mut Picker) -> Self {
+        Self { title, picker }
+    }
+}
+
+impl StatefulWidget for CoverViewer<'_> {
+    type State = CoverViewerState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let instructions = Line::from(vec![
+            "Pan".into(),
+            Span::raw(" <h><j><k><l> ").style(*INSTRUCTIONS_STYLE),
+            "Close".into(),
+            Span::raw(" <Esc> ").style(*INSTRUCTIONS_STYLE),
+        ]);
+
+        let block = Block::bordered().title(self.title.bold()).title_bottom(instructions);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if state.cover.is_none() {
+            Paragraph::new("Loading cover...").render(inner, buf);
+            return;
+        }
+
+        state.update_protocol(inner, self.picker);
+
+        if let Some(protocol) = state.protocol.as_ref() {
+            Widget::render(Image::new(protocol.as_ref()), inner, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn placeholder_shows_initials_of_title() {
+        assert_eq!("OP", CoverPlaceholder::new("one piece").initials());
+        assert_eq!("BT", CoverPlaceholder::new("¡Black Torch! volume 1").initials());
+        assert_eq!("", CoverPlaceholder::new("").initials());
+
+        let placeholder = CoverPlaceholder::new("Some manga");
+        assert_eq!(placeholder.color(), CoverPlaceholder::new("Some manga").color());
+    }
+
+    #[test]
+    fn the_cover_can_be_panned_without_going_past_its_edges() {
+        let mut state = CoverViewerState::default();
+        state.set_cover(DynamicImage::new_rgb8(100, 100));
+
+        let mut picker = Picker::new((1, 1));
+        let area = Rect::new(0, 0, 22, 12);
+        let mut buf = Buffer::empty(area);
+
+        CoverViewer::new("some manga", &mut picker).render(area, &mut buf, &mut state);
+
+        state.pan(PanDirection::Right);
+        state.pan(PanDirection::Down);
+
+        assert_eq!((2, 1), state.offset());
+
+        for _ in 0..100 {
+            state.pan(PanDirection::Right);
+            state.pan(PanDirection::Down);
+        }
+
+        assert_eq!((80, 90), state.offset());
+
+        state.pan(PanDirection::Left);
+        state.pan(PanDirection::Up);
+
+        assert_eq!((78, 89), state.offset());
+    }
+}
@@ -1,27 +1,40 @@
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Margin, Rect};
 use ratatui::style::{Color, Style, Stylize};
-use ratatui::text::Line;
+use ratatui::text::{Line, Span, Text, ToSpan};
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, StatefulWidget, Widget, Wrap};
 use tui_widget_list::PreRender;
 
 use crate::backend::api_responses::{ChapterData, ChapterResponse};
-use crate::backend::database::MangaHistoryResponse;
+use crate::backend::database::{MangaHistoryResponse, MangaHistoryType};
 use crate::backend::filter::Languages;
-use crate::global::CURRENT_LIST_ITEM_STYLE;
-use crate::utils::display_dates_since_publication;
+use crate::global::{CURRENT_LIST_ITEM_STYLE, ERROR_STYLE, INSTRUCTIONS_STYLE};
+use crate::config::MangaTuiConfig;
+use crate::utils::display_publication_date;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum FeedTabs {
     History,
     PlantToRead,
+    /// Only available when logged in to mangadex
+    MangadexFollows,
 }
 
 impl FeedTabs {
-    pub fn cycle(self) -> Self {
+    pub fn cycle(self, include_mangadex_follows: bool) -> Self {
         match self {
             Self::History => Self::PlantToRead,
-            Self::PlantToRead => Self::History,
+            Self::PlantToRead if include_mangadex_follows => Self::MangadexFollows,
+            Self::PlantToRead | Self::MangadexFollows => Self::History,
+        }
+    }
+
+    /// The follows are not stored in the database, they are requested to mangadex
+    pub fn history_type(self) -> Option<MangaHistoryType> {
+        match self {
+            Self::History => Some(MangaHistoryType::ReadingHistory),
+            Self::PlantToRead => Some(MangaHistoryType::PlanToRead),
+            Self::MangadexFollows => None,
         }
     }
 }
@@ -58,18 +71,16 @@ pub struct MangasRead {
     pub title: String,
     pub style: Style,
     pub recent_chapters: Vec<RecentChapters>,
+    /// The manga could not be found on mangadex the last time the library was checked
+    pub is_dead: bool,
+    /// Shown when the reading history and plan to read list are searched together
+    pub category: Option<MangaHistoryType>,
+    pub personal_rating: Option<u8>,
 }
 
 impl From<ChapterData> for RecentChapters {
     fn from(value: ChapterData) -> Self {
         let id = value.id;
-        let today = chrono::offset::Local::now().date_naive();
-        let parse_date = chrono::DateTime::parse_from_rfc3339(&value.attributes.readable_at).unwrap_or_default();
-
-        let difference = today - parse_date.date_naive();
-
-        let num_days = difference.num_days();
-
         let translated_language =
             Languages::try_from_iso_code(&value.attributes.translated_language).unwrap_or(*Languages::get_preferred_lang());
 
@@ -77,7 +88,7 @@ impl From<ChapterData> for RecentChapters {
             id,
             title: value.attributes.title.unwrap_or("No title ".to_string()),
             number: value.attributes.chapter.unwrap_or_default(),
-            readeable_at: display_dates_since_publication(num_days),
+            readeable_at: display_publication_date(&value.attributes.readable_at, MangaTuiConfig::get().date_format.as_deref()),
             translated_language,
         }
     }
@@ -92,9 +103,27 @@ impl Widget for MangasRead {
 
         let [title_area, recent_chapters_area] = layout.margin(1).areas(area);
 
-        Block::bordered().border_style(self.style).render(area, buf);
+        let block = match self.category {
+            Some(MangaHistoryType::ReadingHistory) => Block::bordered().title("Reading history"),
+            Some(MangaHistoryType::PlanToRead) => Block::bordered().title("Plan to Read"),
+            None => Block::bordered(),
+        };
+
+        block.border_style(self.style).render(area, buf);
 
-        Paragraph::new(self.title)
+        let mut title = Text::from(self.title);
+
+        if let Some(rating) = self.personal_rating {
+            title.push_line(Span::styled(format!("Your rating: {rating}/10"), Style::default().fg(Color::Yellow)));
+        }
+
+        if self.is_dead {
+            title.push_line("");
+            title.push_line("Not found on mangadex".to_span().style(*ERROR_STYLE));
+            title.push_line(Line::from(vec!["Search by title ".into(), "<r>".to_span().style(*INSTRUCTIONS_STYLE)]));
+        }
+
+        Paragraph::new(title)
             .block(Block::default().borders(Borders::RIGHT))
             .wrap(Wrap { trim: true })
             .render(title_area, buf);
@@ -161,6 +190,12 @@ impl HistoryWidget {
         }
     }
 
+    pub fn set_dead_mangas(&mut self, dead_manga_ids: &[String]) {
+        for manga in self.mangas.iter_mut() {
+            manga.is_dead = dead_manga_ids.contains(&manga.id);
+        }
+    }
+
     pub fn can_search_next_page(&self, total_items: f64) -> bool {
         self.page as f64 != (self.total_results as f64 / total_items).ceil() && !self.mangas.is_empty()
     }
@@ -181,6 +216,9 @@ impl HistoryWidget {
                     title: history.title.clone(),
                     recent_chapters: vec![],
                     style: Style::default(),
+                    is_dead: false,
+                    category: history.category,
+                    personal_rating: history.personal_rating,
                 })
                 .collect(),
             state: tui_widget_list::ListState::default(),
@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::path::PathBuf;
 
 use crossterm::event::{KeyCode, KeyEvent};
 use manga_tui::SearchTerm;
@@ -10,9 +11,11 @@ use tui_input::Input;
 
 use crate::backend::api_responses::authors::AuthorsResponse;
 use crate::backend::api_responses::tags::TagsResponse;
+use crate::backend::error_log::{write_to_error_log, ErrorType};
 use crate::backend::fetch::{ApiClient, MangadexClient};
 use crate::backend::filter::{
-    Artist, Author, ContentRating, Filters, Languages, MagazineDemographic, PublicationStatus, SortBy, TagData,
+    Artist, Author, ContentRating, Filters, Languages, MagazineDemographic, PublicationStatus, SavedFilters, SortBy,
+    TagData,
 };
 use crate::backend::tui::Events;
 
@@ -441,6 +444,10 @@ pub struct FilterState {
     pub artist_state: FilterListDynamic<ArtistState>,
     pub lang_state: FilterList<LanguageState>,
     pub is_typing: bool,
+    /// Where the filters are saved when the widget is closed
+    saved_filters_path: Option<PathBuf>,
+    /// The tags can only be restored once they are fetched
+    tags_to_restore: Option<SavedFilters>,
     tx: UnboundedSender<FilterEvents>,
     rx: UnboundedReceiver<FilterEvents>,
 }
@@ -462,11 +469,103 @@ impl FilterState {
             artist_state: FilterListDynamic::<ArtistState>::default(),
             lang_state: FilterList::<LanguageState>::default(),
             is_typing: false,
+            saved_filters_path: None,
+            tags_to_restore: None,
             tx,
             rx,
         }
     }
 
+    /// Restores the filters saved on `path`, they will be saved there again every time the widget is closed
+    pub fn load_saved_filters(&mut self, path: PathBuf) {
+        if let Some(saved) = SavedFilters::load(&path) {
+            self.restore(saved);
+        }
+        self.saved_filters_path = Some(path);
+    }
+
+    pub fn to_saved_filters(&self) -> SavedFilters {
+        let selected_names = |items: &[FilterListItem]| -> Vec<String> {
+            items.iter().filter(|item| item.is_selected).map(|item| item.name.clone()).collect()
+        };
+
+        let tags_with_state = |state: TagListItemState| -> Vec<String> {
+            self.tags_state
+                .tags
+                .iter()
+                .flatten()
+                .filter(|tag| tag.state == state)
+                .map(|tag| tag.id.clone())
+                .collect()
+        };
+
+        SavedFilters {
+            content_rating: selected_names(&self.content_rating.items),
+            publication_status: selected_names(&self.publication_status.items),
+            sort_by: self
+                .sort_by_state
+                .items
+                .iter()
+                .find(|item| item.is_selected)
+                .map(|item| item.name.clone()),
+            magazine_demographic: selected_names(&self.magazine_demographic.items),
+            languages: selected_names(&self.lang_state.items),
+            included_tags: tags_with_state(TagListItemState::Included),
+            excluded_tags: tags_with_state(TagListItemState::Excluded),
+        }
+    }
+
+    fn restore(&mut self, saved: SavedFilters) {
+        let select = |items: &mut [FilterListItem], names: &[String]| {
+            for item in items.iter_mut() {
+                item.is_selected = names.contains(&item.name);
+            }
+        };
+
+        select(&mut self.content_rating.items, &saved.content_rating);
+        select(&mut self.publication_status.items, &saved.publication_status);
+        select(&mut self.magazine_demographic.items, &saved.magazine_demographic);
+        select(&mut self.lang_state.items, &saved.languages);
+        if let Some(sort_by) = saved.sort_by.as_ref() {
+            select(&mut self.sort_by_state.items, std::slice::from_ref(sort_by));
+        }
+
+        self.set_content_rating();
+        self.set_publication_status();
+        self.set_magazine_demographic();
+        self.set_languages();
+        self.set_sort_by();
+
+        if self.tags_state.tags.is_some() {
+            self.restore_tags(&saved);
+        } else {
+            self.tags_to_restore = Some(saved);
+        }
+    }
+
+    fn restore_tags(&mut self, saved: &SavedFilters) {
+        if let Some(tags) = self.tags_state.tags.as_mut() {
+            for tag in tags.iter_mut() {
+                tag.state = if saved.included_tags.contains(&tag.id) {
+                    TagListItemState::Included
+                } else if saved.excluded_tags.contains(&tag.id) {
+                    TagListItemState::Excluded
+                } else {
+                    TagListItemState::NotSelected
+                };
+            }
+        }
+        self.set_tags();
+    }
+
+    fn save_filters(&self) {
+        if let Some(path) = self.saved_filters_path.as_ref() {
+            if let Err(e) = self.to_saved_filters().save(path) {
+                write_to_error_log(ErrorType::Error(e));
+            }
+        }
+    }
+
     pub fn reset(&mut self) {
         if self.tags_state.tags.is_some() {
             self.tags_state
@@ -486,10 +585,15 @@ impl FilterState {
         self.lang_state = FilterList::<LanguageState>::default();
         self.author_state = FilterListDynamic::<AuthorState>::default();
         self.artist_state = FilterListDynamic::<ArtistState>::default();
+        self.tags_to_restore = None;
+        self.save_filters();
     }
 
     pub fn toggle(&mut self) {
         self.is_open = !self.is_open;
+        if !self.is_open {
+            self.save_filters();
+        }
     }
 
     fn tick(&mut self) {
@@ -506,11 +610,8 @@ impl FilterState {
     fn search_tags(&mut self) {
         let tx = self.tx.clone();
         tokio::spawn(async move {
-            let response = MangadexClient::global().get_tags().await;
-            if let Ok(res) = response {
-                if let Ok(tags) = res.json().await {
-                    tx.send(FilterEvents::LoadTags(tags)).ok();
-                }
+            if let Ok(tags) = MangadexClient::global().get_cached_filters().await {
+                tx.send(FilterEvents::LoadTags(tags)).ok();
             }
         });
     }
@@ -761,6 +862,10 @@ impl FilterState {
             .collect();
 
         self.tags_state.tags = Some(tags);
+
+        if let Some(saved) = self.tags_to_restore.take() {
+            self.restore_tags(&saved);
+        }
     }
 
     fn set_tags(&mut self) {
@@ -1120,6 +1225,70 @@ mod test {
         assert!(!filter_state.is_open);
     }
 
+    fn tags_response() -> TagsResponse {
+        TagsResponse {
+            data: vec![
+                TagsData {
+                    id: "action".to_string(),
+                    ..Default::default()
+                },
+                TagsData {
+                    id: "romance".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn filters_are_saved_when_closed_and_restored_on_next_launch() {
+        let directory = PathBuf::from("./test_results/saved-filters");
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("filters.json");
+        std::fs::remove_file(&path).ok();
+
+        let mut filter_state = FilterState::new();
+        filter_state.load_saved_filters(path.clone());
+        filter_state.set_tags_from_response(tags_response());
+        filter_state.is_open = true;
+
+        // Go to publication status
+        next_tab(&mut filter_state);
+        next_tab(&mut filter_state);
+        next_tab(&mut filter_state);
+        scroll_down(&mut filter_state);
+        press_s(&mut filter_state);
+
+        // Go to tags and exclude the first one
+        next_tab(&mut filter_state);
+        scroll_down(&mut filter_state);
+        filter_state.handle_events(Events::Key(KeyCode::Char('d').into()));
+
+        close_filter(&mut filter_state);
+
+        let saved = filter_state.to_saved_filters();
+
+        assert_eq!(vec!["action".to_string()], saved.excluded_tags);
+        assert_eq!(1, saved.publication_status.len());
+
+        let mut next_launch = FilterState::new();
+        next_launch.load_saved_filters(path.clone());
+
+        assert_eq!(1, next_launch.publication_status.num_filters_active());
+        assert_eq!(1, next_launch.filters.publication_status.len());
+
+        // the tags are fetched after the filters are loaded
+        next_launch.set_tags_from_response(tags_response());
+
+        assert_eq!(saved, next_launch.to_saved_filters());
+        assert!(!next_launch.filters.tags.is_empty());
+
+        next_launch.handle_events(Events::Key(KeyCode::Char('r').into()));
+
+        assert_eq!(Some(FilterState::new().to_saved_filters()), SavedFilters::load(&path));
+    }
+
     #[tokio::test]
     async fn search_authors_sends_expected_event() {
         let (tx, mut rx) = unbounded_channel::<FilterEvents>();
@@ -8,7 +8,8 @@ use throbber_widgets_tui::{Throbber, ThrobberState};
 
 use crate::backend::api_responses::{Data, SearchMangaResponse};
 use crate::common::{ImageState, Manga};
-use crate::utils::{from_manga_response, set_status_style, set_tags_style};
+use crate::utils::{from_manga_response, set_status_style, set_tags_style, spin_loader};
+use crate::view::widgets::cover::CoverPlaceholder;
 
 #[derive(Clone, Default, PartialEq, Eq)]
 pub enum CarrouselState {
@@ -33,6 +34,11 @@ impl CarrouselItem {
     }
 
     fn render_cover(&mut self, area: Rect, buf: &mut Buffer, state: &mut ImageState) {
+        if state.has_failed(&self.manga.id) {
+            CoverPlaceholder::new(&self.manga.title).render(area, buf);
+            return;
+        }
+
         match state.get_image_state(&self.manga.id) {
             Some(image_state) => {
                 let cover = Image::new(image_state.as_ref());
@@ -78,7 +84,7 @@ impl CarrouselItem {
     }
 
     pub fn tick(&mut self) {
-        self.loader_state.calc_next();
+        spin_loader(&mut self.loader_state);
     }
 }
 
@@ -227,7 +233,7 @@ impl StatefulWidget for RecentlyAddedCarrousel {
                 }
             },
             CarrouselState::Searching => {
-                Block::bordered().title("Searching recent mangas").render(area, buf);
+                Block::bordered().title("Searching mangas").render(area, buf);
                 if self.can_display_images {
                     let margin = layout[0].inner(Margin {
                         horizontal: 1,
@@ -239,7 +245,7 @@ impl StatefulWidget for RecentlyAddedCarrousel {
                 }
             },
             CarrouselState::NotFound => {
-                Block::bordered().title("Could not get recent mangas").render(area, buf);
+                Block::bordered().title("Could not get mangas").render(area, buf);
             },
         }
     }
@@ -11,10 +11,14 @@ use tokio::sync::mpsc::UnboundedSender;
 use tui_widget_list::PreRender;
 
 use crate::backend::api_responses::ChapterResponse;
+use crate::backend::database::{ChapterDownloaded, ChapterProgress};
 use crate::backend::filter::Languages;
 use crate::global::{CURRENT_LIST_ITEM_STYLE, ERROR_STYLE, INSTRUCTIONS_STYLE};
-use crate::utils::display_dates_since_publication;
+use crate::config::MangaTuiConfig;
+use crate::utils::{display_publication_date, spin_loader};
 use crate::view::pages::manga::MangaPageEvents;
+use crate::view::widgets::confirmation::{ConfirmationScreen, ConfirmationState};
+use crate::view::widgets::render_virtual_list;
 
 #[derive(Clone, Debug, Default)]
 pub enum ChapterItemState {
@@ -32,11 +36,18 @@ pub struct ChapterItem {
     pub title: String,
     pub readable_at: String,
     pub scanlator: String,
+    pub scanlator_id: Option<String>,
     pub chapter_number: String,
     pub volume_number: Option<String>,
     pub is_read: bool,
     pub is_downloaded: bool,
     pub is_bookmarked: bool,
+    /// Where and how the chapter was downloaded
+    pub download: Option<ChapterDownloaded>,
+    /// Pages reached the last time it was read
+    pub progress: Option<ChapterProgress>,
+    /// Chapters published on the site of the publisher have no pages on mangadex, only this link
+    pub external_url: Option<String>,
     pub state: ChapterItemState,
     pub download_loading_state: Option<f64>,
     pub translated_language: Languages,
@@ -60,7 +71,13 @@ impl Widget for ChapterItem {
 
         let is_read_icon = if self.is_read { "👀" } else { " " };
 
-        let is_downloaded_icon = if self.is_downloaded { "📥" } else { " " };
+        let is_downloaded_icon = if self.is_downloaded {
+            "📥"
+        } else if self.external_url.is_some() {
+            "🔗"
+        } else {
+            " "
+        };
 
         Line::from(is_read_icon).style(self.style).render(is_read_area, buf);
         Line::from(is_downloaded_icon).style(self.style).render(is_downloaded_area, buf);
@@ -92,7 +109,17 @@ impl Widget for ChapterItem {
                         .wrap(Wrap { trim: true })
                         .render(scanlator_area, buf);
 
-                    Paragraph::new(self.readable_at)
+                    let readable_at = match self.download.as_ref() {
+                        Some(download) => format!("{} | {}", download.format.to_string().to_lowercase(), self.readable_at),
+                        None => self.readable_at,
+                    };
+
+                    let readable_at = match self.progress.filter(|progress| !progress.is_finished()) {
+                        Some(progress) => format!("{progress} | {readable_at}"),
+                        None => readable_at,
+                    };
+
+                    Paragraph::new(readable_at)
                         .style(self.style)
                         .wrap(Wrap { trim: true })
                         .render(readable_at_area, buf);
@@ -155,11 +182,15 @@ impl ChapterItem {
             title,
             readable_at,
             scanlator,
+            scanlator_id: None,
             chapter_number,
             volume_number,
             is_read: false,
             is_downloaded: false,
             is_bookmarked: false,
+            download: None,
+            progress: None,
+            external_url: None,
             download_loading_state: None,
             translated_language,
             style: Style::default(),
@@ -167,6 +198,16 @@ impl ChapterItem {
         }
     }
 
+    pub fn with_scanlator_id(mut self, scanlator_id: Option<String>) -> Self {
+        self.scanlator_id = scanlator_id;
+        self
+    }
+
+    pub fn with_external_url(mut self, external_url: Option<String>) -> Self {
+        self.external_url = external_url;
+        self
+    }
+
     pub fn set_download_error(&mut self) {
         self.download_loading_state = None;
         self.state = ChapterItemState::DownloadError;
@@ -190,7 +231,7 @@ impl ChaptersListWidget {
     pub fn from_response(response: &ChapterResponse) -> Self {
         let mut chapters: Vec<ChapterItem> = vec![];
 
-        let today = chrono::offset::Local::now().date_naive();
+        let date_format = MangaTuiConfig::get().date_format.as_deref();
         for chapter in response.data.iter() {
             let id = chapter.id.clone();
             let title = chapter.attributes.title.clone().unwrap_or("No title".to_string());
@@ -200,39 +241,37 @@ impl ChaptersListWidget {
             let translated_language: Languages =
                 Languages::try_from_iso_code(&chapter.attributes.translated_language).unwrap_or(*Languages::get_preferred_lang());
 
-            let parse_date = chrono::DateTime::parse_from_rfc3339(&chapter.attributes.readable_at).unwrap_or_default();
-
-            let difference = today - parse_date.date_naive();
+            let scanlation_group = chapter.relationships.iter().find(|rel| rel.type_field == "scanlation_group");
 
-            let scanlator = chapter
-                .relationships
-                .iter()
-                .find(|rel| rel.type_field == "scanlation_group")
-                .map(|rel| rel.attributes.as_ref().unwrap().name.to_string());
+            let scanlator = scanlation_group.map(|rel| rel.attributes.as_ref().unwrap().name.to_string());
+            let scanlator_id = scanlation_group.map(|rel| rel.id.clone());
 
             let volume = chapter.attributes.volume.clone();
 
-            chapters.push(ChapterItem::new(
-                id,
-                title,
-                chapter_number,
-                volume,
-                display_dates_since_publication(difference.num_days()),
-                scanlator.unwrap_or_default(),
-                translated_language,
-            ))
+            chapters.push(
+                ChapterItem::new(
+                    id,
+                    title,
+                    chapter_number,
+                    volume,
+                    display_publication_date(&chapter.attributes.readable_at, date_format),
+                    scanlator.unwrap_or_default(),
+                    translated_language,
+                )
+                .with_scanlator_id(scanlator_id)
+                .with_external_url(chapter.attributes.external_url.clone()),
+            )
         }
 
         Self { chapters }
     }
 }
 
-impl StatefulWidget for ChaptersListWidget {
+impl StatefulWidget for &mut ChaptersListWidget {
     type State = tui_widget_list::ListState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let chapters_list = tui_widget_list::List::new(self.chapters);
-        StatefulWidget::render(chapters_list, area, buf, state);
+        render_virtual_list(&mut self.chapters, area, buf, state);
     }
 }
 
@@ -242,6 +281,8 @@ pub enum DownloadPhase {
     ProccessNotStarted,
     Asking,
     FetchingChaptersData,
+    /// The chapters that will be downloaded are listed and the user has to confirm again
+    Previewing,
     DownloadingChapters,
     AskAbortProcess,
     ErrorChaptersData,
@@ -254,6 +295,7 @@ pub struct DownloadAllChaptersState {
     pub loader_state: ThrobberState,
     pub download_progress: f64,
     pub download_location: PathBuf,
+    pub preview: ConfirmationState,
     pub tx: UnboundedSender<MangaPageEvents>,
 }
 
@@ -265,6 +307,7 @@ impl DownloadAllChaptersState {
             loader_state: ThrobberState::default(),
             download_progress: 0.0,
             download_location: PathBuf::default(),
+            preview: ConfirmationState::default(),
             tx,
         }
     }
@@ -298,6 +341,14 @@ impl DownloadAllChaptersState {
         }
     }
 
+    /// `chapters` are the chapters that will be downloaded
+    pub fn show_preview(&mut self, chapters: Vec<String>) {
+        if !self.is_downloading() {
+            self.phase = DownloadPhase::Previewing;
+            self.preview = ConfirmationState::new(chapters);
+        }
+    }
+
     pub fn start_download(&mut self) {
         if !self.is_downloading() {
             self.phase = DownloadPhase::DownloadingChapters;
@@ -309,6 +360,7 @@ impl DownloadAllChaptersState {
     pub fn cancel(&mut self) {
         if !self.is_downloading() {
             self.phase = DownloadPhase::ProccessNotStarted;
+            self.preview = ConfirmationState::default();
         }
     }
 
@@ -355,7 +407,7 @@ impl DownloadAllChaptersState {
     }
 
     pub fn tick(&mut self) {
-        self.loader_state.calc_next();
+        spin_loader(&mut self.loader_state);
     }
 }
 
@@ -427,13 +479,26 @@ impl<'a> StatefulWidget for DownloadAllChaptersWidget<'a> {
             },
             DownloadPhase::FetchingChaptersData => {
                 let loader = Throbber::default()
-                    .label("fetching manga data after this each chapter will begin to be downloaded")
+                    .label("fetching the chapters, they will be listed before downloading them")
                     .style(Style::default().fg(Color::Yellow))
                     .throbber_set(throbber_widgets_tui::BRAILLE_SIX)
                     .use_type(throbber_widgets_tui::WhichUse::Spin);
 
                 StatefulWidget::render(loader, download_information_area, buf, &mut state.loader_state);
             },
+            DownloadPhase::Previewing => {
+                let preview_area = area.inner(Margin {
+                    horizontal: 1,
+                    vertical: 1,
+                });
+
+                StatefulWidget::render(
+                    ConfirmationScreen::new("These chapters will be downloaded"),
+                    preview_area,
+                    buf,
+                    &mut state.preview,
+                );
+            },
             DownloadPhase::ErrorChaptersData => {
                 "Could not get chapters data, press <Enter> to try again"
                     .to_span()
@@ -533,4 +598,35 @@ mod test {
 
         assert_eq!(MangaPageEvents::FinishedDownloadingAllChapters, download_finished);
     }
+
+    #[test]
+    fn chapters_list_keeps_its_chapters_after_rendering_the_visible_ones() {
+        let chapters: Vec<ChapterItem> = (0..2000)
+            .map(|number| ChapterItem {
+                id: number.to_string(),
+                chapter_number: number.to_string(),
+                ..Default::default()
+            })
+            .collect();
+
+        let mut widget = ChaptersListWidget {
+            chapters: chapters.clone(),
+        };
+        let mut state = tui_widget_list::ListState::default();
+        state.select(Some(1500));
+
+        let area = Rect::new(0, 0, 100, 10);
+        let mut buf = Buffer::empty(area);
+
+        StatefulWidget::render(&mut widget, area, &mut buf, &mut state);
+
+        assert_eq!(chapters.len(), widget.chapters.len());
+        assert_eq!(chapters.last().map(|chapter| &chapter.id), widget.chapters.last().map(|chapter| &chapter.id));
+        assert_eq!(Some(1500), state.selected);
+
+        let rendered: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+
+        assert!(rendered.contains("Ch. 1500"));
+        assert!(!rendered.contains("Ch. 1 |"));
+    }
 }
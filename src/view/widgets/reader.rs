@@ -7,6 +7,8 @@ use throbber_widgets_tui::{Throbber, ThrobberState};
 use tui_widget_list::PreRender;
 
 use crate::global::CURRENT_LIST_ITEM_STYLE;
+use crate::utils::spin_loader;
+use crate::view::widgets::render_virtual_list;
 
 pub static STYLE_PAGE_BOOKMARKED: Lazy<Style> = Lazy::new(|| Style::new().on_green().black());
 
@@ -88,7 +90,7 @@ impl PagesItem {
 
     pub fn on_tick(&mut self) {
         if self.state == PageItemState::Loading {
-            self.loading_state.calc_next();
+            spin_loader(&mut self.loading_state);
         }
     }
 }
@@ -140,10 +142,10 @@ impl PagesListState {
     }
 }
 
-impl StatefulWidget for PagesList {
+impl StatefulWidget for &mut PagesList {
     type State = PagesListState;
 
-    fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         if let Some(page) = state.page_bookmarked {
             if state.list_state.selected.is_none() {
                 state.list_state.select(Some(page));
@@ -151,9 +153,7 @@ impl StatefulWidget for PagesList {
             self.highlight_page_as_bookmarked(page);
         }
 
-        let items = tui_widget_list::List::new(self.pages);
-
-        StatefulWidget::render(items, area, buf, &mut state.list_state)
+        render_virtual_list(&mut self.pages, area, buf, &mut state.list_state);
     }
 }
 
@@ -2,7 +2,7 @@ use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Margin, Rect};
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Paragraph, StatefulWidget, StatefulWidgetRef, Widget, Wrap};
+use ratatui::widgets::{Block, Paragraph, StatefulWidget, Widget, Wrap};
 use ratatui_image::Image;
 use throbber_widgets_tui::{Throbber, ThrobberState};
 use tui_widget_list::PreRender;
@@ -11,6 +11,8 @@ use crate::backend::api_responses::Data;
 use crate::common::{ImageState, Manga};
 use crate::global::CURRENT_LIST_ITEM_STYLE;
 use crate::utils::{from_manga_response, set_status_style, set_tags_style};
+use crate::view::widgets::cover::CoverPlaceholder;
+use crate::view::widgets::render_virtual_list;
 
 pub struct MangaPreview<'a> {
     id: &'a str,
@@ -55,6 +57,11 @@ impl<'a> MangaPreview<'a> {
         if self.can_display_images {
             self.render_details(details_area, buf);
 
+            if state.has_failed(self.id) {
+                CoverPlaceholder::new(self.title).render(cover_area, buf);
+                return;
+            }
+
             match state.get_image_state(self.id) {
                 Some(image_state) => {
                     let cover = Image::new(image_state.as_ref());
@@ -133,6 +140,8 @@ impl<'a> StatefulWidget for MangaPreview<'a> {
 pub struct MangaItem {
     pub manga: Manga,
     pub style: Style,
+    /// Marked mangas are added to plan to read all at once
+    pub is_marked: bool,
 }
 
 impl Widget for MangaItem {
@@ -140,10 +149,9 @@ impl Widget for MangaItem {
     where
         Self: Sized,
     {
-        Paragraph::new(self.manga.title)
-            .wrap(Wrap { trim: true })
-            .style(self.style)
-            .render(area, buf);
+        let title = if self.is_marked { format!("✔ {}", self.manga.title) } else { self.manga.title };
+
+        Paragraph::new(title).wrap(Wrap { trim: true }).style(self.style).render(area, buf);
     }
 }
 
@@ -169,6 +177,48 @@ impl MangaItem {
         Self {
             manga,
             style: Style::default(),
+            is_marked: false,
+        }
+    }
+}
+
+/// Narrows down the mangas found by their publication status, only the current page of results is filtered
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum StatusTabs {
+    #[default]
+    All,
+    Ongoing,
+    Completed,
+    Hiatus,
+}
+
+impl StatusTabs {
+    pub const TITLES: [&'static str; 4] = ["All", "Ongoing", "Completed", "Hiatus"];
+
+    pub fn cycle(self) -> Self {
+        match self {
+            Self::All => Self::Ongoing,
+            Self::Ongoing => Self::Completed,
+            Self::Completed => Self::Hiatus,
+            Self::Hiatus => Self::All,
+        }
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            Self::All => 0,
+            Self::Ongoing => 1,
+            Self::Completed => 2,
+            Self::Hiatus => 3,
+        }
+    }
+
+    pub fn matches(self, status: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Ongoing => status.eq_ignore_ascii_case("ongoing"),
+            Self::Completed => status.eq_ignore_ascii_case("completed"),
+            Self::Hiatus => status.eq_ignore_ascii_case("hiatus"),
         }
     }
 }
@@ -190,11 +240,10 @@ impl ListMangasFoundWidget {
     }
 }
 
-impl StatefulWidgetRef for ListMangasFoundWidget {
+impl StatefulWidget for &mut ListMangasFoundWidget {
     type State = tui_widget_list::ListState;
 
-    fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let list = tui_widget_list::List::new(self.mangas.clone());
-        StatefulWidget::render(list, area, buf, state);
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        render_virtual_list(&mut self.mangas, area, buf, state);
     }
 }